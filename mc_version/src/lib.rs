@@ -1,6 +1,7 @@
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
-use std::sync::{Arc, RwLock};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MinecraftVersion {
@@ -15,6 +16,16 @@ impl MinecraftVersion {
             minor
         }
     }
+
+    /// Returns true if this version is the same as or newer than `other`
+    pub fn is_at_least(&self, other: &MinecraftVersion) -> bool {
+        self >= other
+    }
+
+    /// Returns true if this version is strictly older than `other`
+    pub fn is_before(&self, other: &MinecraftVersion) -> bool {
+        self < other
+    }
 }
 
 impl Display for MinecraftVersion {
@@ -58,6 +69,116 @@ impl FromStr for MinecraftVersion {
     }
 }
 
+/// A weekly snapshot identifier such as `23w45a`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SnapshotVersion {
+    year: u8,
+    week: u8,
+    revision: char,
+}
+
+impl SnapshotVersion {
+    pub fn new(year: u8, week: u8, revision: char) -> Self {
+        Self {
+            year,
+            week,
+            revision,
+        }
+    }
+}
+
+impl Display for SnapshotVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}w{:02}{}", self.year, self.week, self.revision)
+    }
+}
+
+impl FromStr for SnapshotVersion {
+    type Err = VersionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, rest) = s.split_once('w').ok_or_else(|| VersionParseError::InvalidFormat(s.to_string()))?;
+
+        let mut rest_chars = rest.chars();
+        let revision = rest_chars.next_back().ok_or_else(|| VersionParseError::InvalidFormat(s.to_string()))?;
+        let week = rest_chars.as_str();
+
+        if !revision.is_ascii_lowercase() {
+            return Err(VersionParseError::InvalidFormat(s.to_string()));
+        }
+
+        let year = year.parse::<u8>().map_err(|_| VersionParseError::NotNumeric(s.to_string()))?;
+        let week = week.parse::<u8>().map_err(|_| VersionParseError::NotNumeric(s.to_string()))?;
+
+        Ok(SnapshotVersion::new(year, week, revision))
+    }
+}
+
+/// An inclusive range of Minecraft versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VersionRange {
+    start: MinecraftVersion,
+    end: MinecraftVersion,
+}
+
+impl VersionRange {
+    /// Creates a range spanning both versions, regardless of the order they are given in
+    pub fn new(start: MinecraftVersion, end: MinecraftVersion) -> Self {
+        if start <= end {
+            Self { start, end }
+        } else {
+            Self { start: end, end: start }
+        }
+    }
+
+    pub fn single(version: MinecraftVersion) -> Self {
+        Self::new(version, version)
+    }
+
+    pub fn start(&self) -> MinecraftVersion {
+        self.start
+    }
+
+    pub fn end(&self) -> MinecraftVersion {
+        self.end
+    }
+
+    pub fn contains(&self, version: &MinecraftVersion) -> bool {
+        version.is_at_least(&self.start) && !self.end.is_before(version)
+    }
+
+    /// Returns the range of versions contained in both ranges, or `None` if they do not overlap
+    pub fn intersect(&self, other: &VersionRange) -> Option<VersionRange> {
+        let start = self.start.max(other.start);
+        let end = self.end.min(other.end);
+
+        if start <= end {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over the given versions which fall within this range.
+    /// This crate has no knowledge of which versions were actually released, so the set of known versions
+    /// must be supplied by the caller (e.g. from the generated version map).
+    pub fn iter<'a>(&'a self, versions: impl IntoIterator<Item = &'a MinecraftVersion> + 'a) -> impl Iterator<Item = MinecraftVersion> + 'a {
+        versions.into_iter()
+            .filter(|version| self.contains(version))
+            .copied()
+    }
+}
+
+impl Display for VersionRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum VersionParseError {
     #[error("Invalid Minecraft version format: {0}")]
@@ -71,14 +192,25 @@ pub enum VersionParseError {
 #[derive(Debug, Clone)]
 pub struct PackFormat {
     format_id: u8,
-    versions: Arc<RwLock<Vec<MinecraftVersion>>>
+    /// Set once at construction and never changed, so reading them never needs a lock and clones are cheap
+    versions: Arc<[MinecraftVersion]>,
+    snapshots: Arc<[SnapshotVersion]>,
 }
 
 impl PackFormat {
-    pub fn new(format_id: u8, versions: Vec<MinecraftVersion>) -> Self {
+    pub fn new(format_id: u8, versions: impl Into<Arc<[MinecraftVersion]>>) -> Self {
+        Self::with_snapshots(format_id, versions, [])
+    }
+
+    pub fn with_snapshots(
+        format_id: u8,
+        versions: impl Into<Arc<[MinecraftVersion]>>,
+        snapshots: impl Into<Arc<[SnapshotVersion]>>,
+    ) -> Self {
         Self {
             format_id,
-            versions: Arc::new(RwLock::new(versions))
+            versions: versions.into(),
+            snapshots: snapshots.into(),
         }
     }
     
@@ -86,19 +218,367 @@ impl PackFormat {
         self.format_id
     }
     
-    pub fn get_versions(&self) -> Arc<RwLock<Vec<MinecraftVersion>>> {
-        self.versions.clone()
+    pub fn get_versions(&self) -> &[MinecraftVersion] {
+        &self.versions
+    }
+
+    /// Snapshots in which this format was used before (or without) a full release
+    pub fn get_snapshots(&self) -> &[SnapshotVersion] {
+        &self.snapshots
+    }
+
+    pub fn contains_version(&self, version: &MinecraftVersion) -> bool {
+        self.versions.contains(version)
+    }
+
+    /// Versions which use both this format and the other, e.g. to find what a data and resource pack have in common
+    pub fn common_versions<'a>(&'a self, other: &'a PackFormat) -> impl Iterator<Item = MinecraftVersion> + 'a {
+        self.versions.iter()
+            .filter(|version| other.contains_version(version))
+            .copied()
+    }
+
+    /// The oldest version using this format, or `None` if the format has no versions
+    pub fn min_version(&self) -> Option<MinecraftVersion> {
+        self.versions.iter().min().copied()
+    }
+
+    /// The newest version using this format, or `None` if the format has no versions
+    pub fn max_version(&self) -> Option<MinecraftVersion> {
+        self.versions.iter().max().copied()
     }
 }
 
+/// Formats are equal when their ids are, ignoring the versions and snapshots listed for them
 impl PartialEq<Self> for PackFormat {
     fn eq(&self, other: &Self) -> bool {
         self.format_id == other.format_id
     }
 }
 
+impl Eq for PackFormat {}
+
+/// Hashes only the id, to agree with equality
+impl Hash for PackFormat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.format_id.hash(state);
+    }
+}
+
 impl PartialOrd for PackFormat {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.format_id.cmp(&other.format_id))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod minecraft_version {
+        use super::*;
+
+        #[test]
+        fn test_is_at_least() {
+            let version = MinecraftVersion::new(20, 4);
+
+            assert!(version.is_at_least(&MinecraftVersion::new(20, 4)));
+            assert!(version.is_at_least(&MinecraftVersion::new(20, 1)));
+            assert!(version.is_at_least(&MinecraftVersion::new(19, 4)));
+            assert!(!version.is_at_least(&MinecraftVersion::new(20, 5)));
+            assert!(!version.is_at_least(&MinecraftVersion::new(21, 0)));
+        }
+
+        #[test]
+        fn test_is_before() {
+            let version = MinecraftVersion::new(20, 4);
+
+            assert!(version.is_before(&MinecraftVersion::new(20, 5)));
+            assert!(version.is_before(&MinecraftVersion::new(21, 0)));
+            assert!(!version.is_before(&MinecraftVersion::new(20, 4)));
+            assert!(!version.is_before(&MinecraftVersion::new(19, 4)));
+        }
+    }
+
+    mod snapshot_version {
+        use super::*;
+
+        #[test]
+        fn test_snapshot_display() {
+            assert_eq!(SnapshotVersion::new(23, 45, 'a').to_string(), "23w45a");
+            assert_eq!(SnapshotVersion::new(24, 3, 'b').to_string(), "24w03b");
+        }
+
+        #[test]
+        fn test_snapshot_from_str() {
+            assert_eq!(SnapshotVersion::from_str("23w45a").unwrap(), SnapshotVersion::new(23, 45, 'a'));
+            assert_eq!(SnapshotVersion::from_str("24w03b").unwrap(), SnapshotVersion::new(24, 3, 'b'));
+
+            assert!(matches!(SnapshotVersion::from_str("1.20.4"), Err(VersionParseError::InvalidFormat(_))));
+            assert!(matches!(SnapshotVersion::from_str("23w45"), Err(VersionParseError::InvalidFormat(_))));
+            assert!(matches!(SnapshotVersion::from_str("xxw45a"), Err(VersionParseError::NotNumeric(_))));
+        }
+
+        #[test]
+        fn test_snapshot_ordering() {
+            assert!(SnapshotVersion::new(23, 45, 'a') < SnapshotVersion::new(23, 45, 'b'));
+            assert!(SnapshotVersion::new(23, 45, 'b') < SnapshotVersion::new(23, 46, 'a'));
+            assert!(SnapshotVersion::new(23, 51, 'a') < SnapshotVersion::new(24, 3, 'a'));
+        }
+    }
+
+    mod version_range {
+        use super::*;
+
+        fn range(start: (u8, u8), end: (u8, u8)) -> VersionRange {
+            VersionRange::new(MinecraftVersion::new(start.0, start.1), MinecraftVersion::new(end.0, end.1))
+        }
+
+        #[test]
+        fn test_new_orders_bounds() {
+            // Given a range constructed with its bounds reversed
+            let range = range((20, 4), (20, 1));
+
+            // Then the bounds should be swapped into order
+            assert_eq!(range.start(), MinecraftVersion::new(20, 1));
+            assert_eq!(range.end(), MinecraftVersion::new(20, 4));
+        }
+
+        #[test]
+        fn test_contains() {
+            // Given a range spanning a major version boundary
+            let range = range((20, 5), (21, 1));
+
+            // Then the bounds should be inclusive
+            assert!(range.contains(&MinecraftVersion::new(20, 5)));
+            assert!(range.contains(&MinecraftVersion::new(20, 6)));
+            assert!(range.contains(&MinecraftVersion::new(21, 0)));
+            assert!(range.contains(&MinecraftVersion::new(21, 1)));
+
+            assert!(!range.contains(&MinecraftVersion::new(20, 4)));
+            assert!(!range.contains(&MinecraftVersion::new(21, 2)));
+        }
+
+        #[test]
+        fn test_intersect_overlapping() {
+            // Given two overlapping ranges
+            let first = range((20, 0), (20, 4));
+            let second = range((20, 2), (20, 6));
+
+            // When I intersect them
+            let intersection = first.intersect(&second);
+
+            // Then I should get the overlapping portion, regardless of order
+            assert_eq!(intersection, Some(range((20, 2), (20, 4))));
+            assert_eq!(second.intersect(&first), intersection);
+        }
+
+        #[test]
+        fn test_intersect_nested() {
+            // Given a range fully contained within another
+            let outer = range((19, 0), (21, 0));
+            let inner = range((20, 2), (20, 4));
+
+            // Then the intersection should be the inner range
+            assert_eq!(outer.intersect(&inner), Some(inner));
+        }
+
+        #[test]
+        fn test_intersect_touching() {
+            // Given two ranges sharing a single endpoint
+            let first = range((20, 0), (20, 2));
+            let second = range((20, 2), (20, 4));
+
+            // Then the intersection should be that single version
+            assert_eq!(first.intersect(&second), Some(VersionRange::single(MinecraftVersion::new(20, 2))));
+        }
+
+        #[test]
+        fn test_intersect_adjacent() {
+            // Given two adjacent ranges which do not share any version
+            let first = range((20, 0), (20, 2));
+            let second = range((20, 3), (20, 4));
+
+            // Then there should be no intersection
+            assert_eq!(first.intersect(&second), None);
+        }
+
+        #[test]
+        fn test_intersect_disjoint() {
+            // Given two ranges far apart
+            let first = range((18, 0), (18, 2));
+            let second = range((21, 0), (21, 4));
+
+            // Then there should be no intersection
+            assert_eq!(first.intersect(&second), None);
+            assert_eq!(second.intersect(&first), None);
+        }
+
+        #[test]
+        fn test_iter() {
+            // Given a range and a list of known versions
+            let range = range((20, 2), (21, 0));
+            let known_versions = vec![
+                MinecraftVersion::new(20, 1),
+                MinecraftVersion::new(20, 2),
+                MinecraftVersion::new(20, 4),
+                MinecraftVersion::new(21, 0),
+                MinecraftVersion::new(21, 1),
+            ];
+
+            // When I iterate over the range
+            let contained: Vec<_> = range.iter(&known_versions).collect();
+
+            // Then only the known versions within the range should be returned
+            assert_eq!(contained, vec![
+                MinecraftVersion::new(20, 2),
+                MinecraftVersion::new(20, 4),
+                MinecraftVersion::new(21, 0),
+            ]);
+        }
+
+        #[test]
+        fn test_display() {
+            assert_eq!(range((20, 2), (20, 4)).to_string(), "1.20.2-1.20.4");
+            assert_eq!(VersionRange::single(MinecraftVersion::new(20, 2)).to_string(), "1.20.2");
+        }
+    }
+
+    mod pack_format {
+        use std::collections::HashSet;
+        use super::*;
+
+        fn test_format() -> PackFormat {
+            PackFormat::new(15, vec![
+                MinecraftVersion::new(20, 0),
+                MinecraftVersion::new(20, 1),
+                MinecraftVersion::new(20, 2),
+            ])
+        }
+
+        #[test]
+        fn test_contains_version() {
+            // Given a format with several versions
+            let format = test_format();
+
+            // Then it should contain only those versions
+            assert!(format.contains_version(&MinecraftVersion::new(20, 0)));
+            assert!(format.contains_version(&MinecraftVersion::new(20, 1)));
+            assert!(format.contains_version(&MinecraftVersion::new(20, 2)));
+
+            assert!(!format.contains_version(&MinecraftVersion::new(19, 4)));
+            assert!(!format.contains_version(&MinecraftVersion::new(20, 3)));
+        }
+
+        #[test]
+        fn test_min_max_version() {
+            // Given a format with several versions
+            let format = test_format();
+
+            // Then the min and max should be the oldest and newest versions
+            assert_eq!(format.min_version(), Some(MinecraftVersion::new(20, 0)));
+            assert_eq!(format.max_version(), Some(MinecraftVersion::new(20, 2)));
+        }
+
+        #[test]
+        fn test_min_max_version_unordered() {
+            // Given a format whose versions are not declared in order
+            let format = PackFormat::new(15, vec![
+                MinecraftVersion::new(20, 1),
+                MinecraftVersion::new(20, 2),
+                MinecraftVersion::new(20, 0),
+            ]);
+
+            // Then the min and max should still be correct
+            assert_eq!(format.min_version(), Some(MinecraftVersion::new(20, 0)));
+            assert_eq!(format.max_version(), Some(MinecraftVersion::new(20, 2)));
+        }
+
+        #[test]
+        fn test_get_versions() {
+            // Given formats built from a vec and from an array
+            let from_vec = test_format();
+            let from_array = PackFormat::with_snapshots(
+                15,
+                [MinecraftVersion::new(20, 0), MinecraftVersion::new(20, 1), MinecraftVersion::new(20, 2)],
+                [SnapshotVersion::new(23, 12, 'a')],
+            );
+
+            // Then both should return the versions they were built with, in order
+            let expected = [MinecraftVersion::new(20, 0), MinecraftVersion::new(20, 1), MinecraftVersion::new(20, 2)];
+            assert_eq!(from_vec.get_versions(), expected);
+            assert_eq!(from_array.get_versions(), expected);
+            assert_eq!(from_array.get_snapshots(), [SnapshotVersion::new(23, 12, 'a')]);
+
+            // And clones should share the same versions rather than copying them
+            let clone = from_array.clone();
+            assert!(std::ptr::eq(clone.get_versions(), from_array.get_versions()));
+        }
+
+        #[test]
+        fn test_common_versions() {
+            // Given two formats which share some versions
+            let format = test_format();
+            let other = PackFormat::new(16, vec![
+                MinecraftVersion::new(20, 1),
+                MinecraftVersion::new(20, 2),
+                MinecraftVersion::new(20, 3),
+            ]);
+
+            // When I intersect them
+            let common: Vec<_> = format.common_versions(&other).collect();
+
+            // Then only the shared versions should be returned
+            assert_eq!(common, vec![MinecraftVersion::new(20, 1), MinecraftVersion::new(20, 2)]);
+            assert_eq!(format.common_versions(&PackFormat::new(17, vec![])).next(), None);
+        }
+
+        #[test]
+        fn test_common_versions_concurrently() {
+            // Given formats shared between threads
+            let format = Arc::new(test_format());
+            let other = Arc::new(test_format());
+
+            // When several threads intersect them while also borrowing the versions
+            let held = format.get_versions();
+            let results: Vec<_> = std::thread::scope(|scope| {
+                (0..4)
+                    .map(|_| scope.spawn(|| format.common_versions(&other).count()))
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap())
+                    .collect()
+            });
+
+            // Then every thread should get the same versions while the others still hold a borrow
+            assert_eq!(results, vec![3; 4]);
+            assert_eq!(held.len(), 3);
+        }
+
+        #[test]
+        fn test_hash_set_dedups_by_id() {
+            // Given formats with the same id but different versions, and one with another id
+            let format = test_format();
+            let same_id = PackFormat::new(15, [MinecraftVersion::new(20, 0)]);
+            let other_id = PackFormat::new(18, [MinecraftVersion::new(20, 2)]);
+
+            // When I collect them into a set
+            let formats: HashSet<_> = [format.clone(), same_id, other_id].into_iter().collect();
+
+            // Then formats with the same id should only be kept once
+            assert_eq!(formats.len(), 2);
+            assert!(formats.contains(&format));
+            assert!(formats.contains(&PackFormat::new(18, [])));
+        }
+
+        #[test]
+        fn test_min_max_version_empty() {
+            // Given a format with no versions
+            let format = PackFormat::new(15, vec![]);
+
+            // Then there should be no min or max
+            assert_eq!(format.min_version(), None);
+            assert_eq!(format.max_version(), None);
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use quote::{format_ident, quote};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use proc_macro2::TokenStream;
 use syn::parse::{Parse, ParseStream};
 use syn::Token;
@@ -15,14 +15,22 @@ use syn::Token;
 ///         (9, 1.18.2)
 ///     ],
 ///     resource = [
-///         (8, 1.18..1.18.2)
+///         (8, 1.18..1.18.2),
+///         (18, [23w31a, 23w32a..23w35a])
 ///     ]
 /// ];
+///
+/// Snapshots (e.g. `23w45a`) may be used anywhere a release version can, and generate
+/// statics named like `S23W45A`. Snapshot ranges must stay within a single year and revision letter.
 #[proc_macro]
 pub fn define_versions(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = syn::parse(input).unwrap();
-    let parsed_input = parse_input(ast).unwrap();
-    generate_output(parsed_input).into()
+
+    // Surface parse errors as compile errors so they point at the offending tokens
+    match parse_input(ast) {
+        Ok(parsed_input) => generate_output(parsed_input).into(),
+        Err(error) => error.to_compile_error().into(),
+    }
 }
 
 fn generate_output(input: FormatList) -> TokenStream {
@@ -36,6 +44,10 @@ fn generate_output(input: FormatList) -> TokenStream {
     let version_statics = generate_version_statics(&all_versions);
     output.extend(version_statics);
 
+    // Generate snapshot static declarations
+    let snapshot_statics = generate_snapshot_statics(&input.mc_snapshots);
+    output.extend(snapshot_statics);
+
     // Generate data format static declarations
     let data_statics = generate_data_format_statics(&input.data_formats);
     output.extend(data_statics);
@@ -52,6 +64,10 @@ fn generate_output(input: FormatList) -> TokenStream {
     let version_map = generate_version_map(&all_versions);
     output.extend(version_map);
 
+    // Generate map for snapshots
+    let snapshot_map = generate_snapshot_map(&input.mc_snapshots);
+    output.extend(snapshot_map);
+
     output
 }
 
@@ -92,9 +108,53 @@ fn generate_format_maps(data_formats: &[PackFormat], resource_formats: &[PackFor
     };
     output.extend(resource_map_tokens);
 
+    // Generate reverse lookups from versions to every format they belong to
+    output.extend(generate_reverse_format_map(
+        format_ident!("VERSION_TO_DATA_FORMATS"),
+        "D",
+        data_formats
+    ));
+
+    output.extend(generate_reverse_format_map(
+        format_ident!("VERSION_TO_RESOURCE_FORMATS"),
+        "R",
+        resource_formats
+    ));
+
     output
 }
 
+fn generate_reverse_format_map(map_ident: syn::Ident, format_prefix: &str, formats: &[PackFormat]) -> TokenStream {
+    // BTreeMap keeps the generated entries in version order, and formats in declaration order
+    let mut version_formats: BTreeMap<SemanticVersion, Vec<syn::Ident>> = BTreeMap::new();
+
+    for format in formats {
+        let format_ident = format_ident!("{}{}", format_prefix, format.format_id);
+
+        for version in &format.versions {
+            let entry = version_formats.entry(*version).or_default();
+
+            if !entry.contains(&format_ident) {
+                entry.push(format_ident.clone());
+            }
+        }
+    }
+
+    let entries = version_formats.iter().map(|(version, format_idents)| {
+        let version_ident = version.ident();
+        quote! { *#version_ident, vec![#(&*#format_idents),*] }
+    });
+
+    quote! {
+        pub static #map_ident: ::once_cell::sync::Lazy<::dashmap::DashMap<::mc_version::MinecraftVersion, Vec<&'static ::mc_version::PackFormat>>> = 
+            ::once_cell::sync::Lazy::new(|| {
+                let map = ::dashmap::DashMap::new();
+                #(map.insert(#entries);)*
+                map
+            });
+    }
+}
+
 fn generate_version_map(versions: &[SemanticVersion]) -> TokenStream {
     let version_entries = versions.iter().map(|v| {
         let version_str = if v.patch == 0 {
@@ -122,6 +182,64 @@ fn generate_version_map(versions: &[SemanticVersion]) -> TokenStream {
     }
 }
 
+fn generate_snapshot_map(snapshots: &[SnapshotVersion]) -> TokenStream {
+    let snapshot_entries = snapshots.iter().map(|snapshot| {
+        let snapshot_str = snapshot.to_string();
+        let ident = snapshot.ident();
+
+        quote! { #snapshot_str.to_string(), &*#ident }
+    });
+
+    quote! {
+        pub static SNAPSHOT_MAP: ::once_cell::sync::Lazy<::dashmap::DashMap<String, &'static ::mc_version::SnapshotVersion>> = 
+            ::once_cell::sync::Lazy::new(|| {
+                let map = ::dashmap::DashMap::new();
+                #(map.insert(#snapshot_entries);)*
+                map
+            });
+    }
+}
+
+// Generate static declarations for SnapshotVersion values
+fn generate_snapshot_statics(snapshots: &[SnapshotVersion]) -> TokenStream {
+    let mut output = TokenStream::new();
+
+    for snapshot in snapshots {
+        // Create a name like S23W45A
+        let ident = snapshot.ident();
+
+        let year = snapshot.year;
+        let week = snapshot.week;
+        let revision = snapshot.revision;
+
+        let tokens = quote::quote! {
+            pub static #ident: ::once_cell::sync::Lazy<::mc_version::SnapshotVersion> = ::once_cell::sync::Lazy::new(|| 
+                ::mc_version::SnapshotVersion::new(#year, #week, #revision)
+            );
+        };
+
+        output.extend(tokens);
+    }
+
+    output
+}
+
+// Generates the constructor for a pack format, only including snapshots if the format has any
+fn generate_pack_format_constructor(format: &PackFormat, version_refs: &[TokenStream]) -> TokenStream {
+    let format_id = format.format_id;
+
+    if format.snapshots.is_empty() {
+        quote! { ::mc_version::PackFormat::new(#format_id, [#(#version_refs),*]) }
+    }
+    else {
+        let snapshot_refs = format.snapshots.iter().map(|snapshot| {
+            let snapshot_ident = snapshot.ident();
+            quote! { *#snapshot_ident }
+        });
+
+        quote! { ::mc_version::PackFormat::with_snapshots(#format_id, [#(#version_refs),*], [#(#snapshot_refs),*]) }
+    }
+}
 
 // Generate static declarations for MinecraftVersion values
 fn generate_version_statics(versions: &[SemanticVersion]) -> TokenStream {
@@ -172,11 +290,11 @@ fn generate_data_format_statics(formats: &[PackFormat]) -> TokenStream {
             quote::quote! { *#version_ident }
         }).collect::<Vec<_>>();
 
-        let format_id = format.format_id;
+        let constructor = generate_pack_format_constructor(format, &version_refs);
         
         let tokens = quote::quote! {
             pub static #ident: ::once_cell::sync::Lazy<::mc_version::PackFormat> = ::once_cell::sync::Lazy::new(|| 
-                ::mc_version::PackFormat::new(#format_id, vec![#(#version_refs),*])
+                #constructor
             );
         };
 
@@ -206,12 +324,12 @@ fn generate_resource_format_statics(formats: &[PackFormat]) -> TokenStream {
             quote::quote! { *#version_ident }
         }).collect::<Vec<_>>();
 
-        let format_id = format.format_id;
+        let constructor = generate_pack_format_constructor(format, &version_refs);
 
         // Create the static declaration using once_cell::Lazy
         let tokens = quote::quote! {
             pub static #ident: ::once_cell::sync::Lazy<::mc_version::PackFormat> = ::once_cell::sync::Lazy::new(|| 
-                ::mc_version::PackFormat::new(#format_id, vec![#(#version_refs),*])
+                #constructor
             );
         };
 
@@ -232,6 +350,17 @@ struct SemanticVersion {
     patch: u8,
 }
 
+impl SemanticVersion {
+    /// The name of the generated static for this version, like V1_18 or V1_18_2
+    fn ident(&self) -> syn::Ident {
+        if self.patch == 0 {
+            format_ident!("V{}_{}", self.major, self.minor)
+        } else {
+            format_ident!("V{}_{}_{}", self.major, self.minor, self.patch)
+        }
+    }
+}
+
 impl Parse for SemanticVersion {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let first_token: proc_macro2::TokenTree = input.parse()?;
@@ -283,14 +412,73 @@ impl Parse for SemanticVersion {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct SnapshotVersion {
+    year: u8,
+    week: u8,
+    revision: char,
+}
+
+impl SnapshotVersion {
+    fn ident(&self) -> syn::Ident {
+        format_ident!("S{}W{:02}{}", self.year, self.week, self.revision.to_ascii_uppercase())
+    }
+}
+
+impl std::fmt::Display for SnapshotVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}w{:02}{}", self.year, self.week, self.revision)
+    }
+}
+
+impl Parse for SnapshotVersion {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Snapshots such as 23w45a are tokenized as an integer literal with a suffix of w45a
+        let literal: syn::LitInt = input.parse()?;
+        let malformed = || syn::Error::new(literal.span(), "Malformed snapshot version");
+
+        let week_and_revision = literal.suffix().strip_prefix('w').ok_or_else(malformed)?;
+
+        let mut chars = week_and_revision.chars();
+        let revision = chars.next_back().ok_or_else(malformed)?;
+        if !revision.is_ascii_lowercase() {
+            return Err(malformed());
+        }
+
+        let week = chars.as_str().parse::<u8>().map_err(|_| malformed())?;
+        let year = literal.base10_parse::<u8>()?;
+
+        Ok(SnapshotVersion {
+            year,
+            week,
+            revision,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum VersionSetElement {
     Single(SemanticVersion),
     Range(SemanticVersion, SemanticVersion),
+    Snapshot(SnapshotVersion),
+    SnapshotRange(SnapshotVersion, SnapshotVersion),
 }
 
 impl Parse for VersionSetElement {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.fork().parse::<SnapshotVersion>().is_ok() {
+            let start: SnapshotVersion = input.parse()?;
+
+            return if input.peek(Token![..]) {
+                input.parse::<Token![..]>()?;
+                let end: SnapshotVersion = input.parse()?;
+                Ok(Self::SnapshotRange(start, end))
+            }
+            else {
+                Ok(Self::Snapshot(start))
+            }
+        }
+
         let start: SemanticVersion = input.parse()?;
 
         if input.peek(Token![..]) {
@@ -328,6 +516,33 @@ impl VersionSetElement {
                 Ok(versions)
             }
             Self::Single(version) => Ok(vec![*version]),
+            Self::Snapshot(_) | Self::SnapshotRange(_, _) => Ok(Vec::new()),
+        }
+    }
+
+    fn expand_snapshots(&self) -> Result<Vec<SnapshotVersion>, VersionExpandError> {
+        match self {
+            Self::SnapshotRange(start, end) => {
+                if end <= start {
+                    return Err(VersionExpandError("End snapshot must be greater than start snapshot".to_string()));
+                }
+
+                if start.year != end.year || start.revision != end.revision {
+                    return Err(VersionExpandError("Start and end snapshots must have the same year and revision".to_string()));
+                }
+
+                let versions = (start.week..=end.week)
+                    .map(|week| SnapshotVersion {
+                        year: start.year,
+                        week,
+                        revision: start.revision,
+                    })
+                    .collect();
+
+                Ok(versions)
+            }
+            Self::Snapshot(snapshot) => Ok(vec![*snapshot]),
+            Self::Single(_) | Self::Range(_, _) => Ok(Vec::new()),
         }
     }
 }
@@ -376,12 +591,28 @@ impl VersionSet {
         
         Ok(elements)
     }
+
+    fn expand_snapshots(&self) -> Result<Vec<SnapshotVersion>, VersionExpandError> {
+        let elements = match self {
+            Self::Element(element) => element.expand_snapshots()?,
+            Self::List(elements) => {
+                let mut expanded_elements = Vec::new();
+                for element in elements {
+                    expanded_elements.extend(element.expand_snapshots()?);
+                }
+                expanded_elements
+            }
+        };
+
+        Ok(elements)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct PackFormat {
     format_id: u8,
     versions: Vec<SemanticVersion>,
+    snapshots: Vec<SnapshotVersion>,
 }
 
 impl Parse for PackFormat {
@@ -393,10 +624,12 @@ impl Parse for PackFormat {
             content.parse::<Token![,]>()?;
             let versions: VersionSet = content.parse()?;
             
+            let snapshots = versions.expand_snapshots().map_err(|e| syn::Error::new(proc_macro2::Span::call_site(), e.to_string()))?;
             let versions = versions.expand().map_err(|e| syn::Error::new(proc_macro2::Span::call_site(), e.to_string()))?;
             Ok(PackFormat {
                 format_id: format_id.base10_parse()?,
                 versions,
+                snapshots,
             })
         }
         else {
@@ -410,6 +643,7 @@ struct FormatList {
     data_formats: Vec<PackFormat>,
     resource_packs: Vec<PackFormat>,
     mc_versions: Vec<SemanticVersion>,
+    mc_snapshots: Vec<SnapshotVersion>,
 }
 
 impl Parse for FormatList {
@@ -432,8 +666,12 @@ impl Parse for FormatList {
         syn::bracketed!(content in input);
 
         // Parse comma-separated pack formats for data
+        let mut data_format_ids = HashSet::new();
         while !content.is_empty() {
-            data_formats.push(content.parse::<PackFormat>()?);
+            let span = content.span();
+            let format = content.parse::<PackFormat>()?;
+            validate_unique_format_id(&mut data_format_ids, &format, span, "data")?;
+            data_formats.push(format);
 
             if !content.is_empty() {
                 content.parse::<Token![,]>()?;
@@ -459,8 +697,12 @@ impl Parse for FormatList {
         syn::bracketed!(content in input);
 
         // Parse comma-separated pack formats for resource
+        let mut resource_format_ids = HashSet::new();
         while !content.is_empty() {
-            resource_packs.push(content.parse::<PackFormat>()?);
+            let span = content.span();
+            let format = content.parse::<PackFormat>()?;
+            validate_unique_format_id(&mut resource_format_ids, &format, span, "resource")?;
+            resource_packs.push(format);
 
             if !content.is_empty() {
                 content.parse::<Token![,]>()?;
@@ -482,14 +724,36 @@ impl Parse for FormatList {
         let mut mc_versions: Vec<SemanticVersion> = mc_versions.into_iter().collect();
         mc_versions.sort();
 
+        let mut mc_snapshots: Vec<SnapshotVersion> = data_formats.iter()
+            .chain(resource_packs.iter())
+            .flat_map(|pack| pack.snapshots.iter().copied())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        mc_snapshots.sort();
+
         Ok(Self {
             data_formats,
             resource_packs,
             mc_versions,
+            mc_snapshots,
         })
     }
 }
 
+/// Errors at the duplicate's position if a format id has already been declared within the same section,
+/// since it would otherwise generate conflicting statics
+fn validate_unique_format_id(seen_ids: &mut HashSet<u8>, format: &PackFormat, span: proc_macro2::Span, section: &str) -> syn::Result<()> {
+    if !seen_ids.insert(format.format_id) {
+        return Err(syn::Error::new(
+            span,
+            format!("Duplicate {} format id {}", section, format.format_id)
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -629,6 +893,110 @@ mod test {
             assert!(result.is_err());
         }
 
+        //------ Snapshot Tests ------//
+
+        #[test]
+        fn test_snapshot_version_parsing() {
+            // Given a valid snapshot version
+            let input = quote!(23w45a);
+            // When I parse it
+            let version = syn::parse2::<SnapshotVersion>(input).unwrap();
+            // It should parse correctly
+            assert_eq!(version, SnapshotVersion { year: 23, week: 45, revision: 'a' })
+        }
+
+        #[test]
+        fn test_snapshot_version_parsing_leading_zero_week() {
+            // Given a snapshot version with a single digit week
+            let input = quote!(24w03b);
+            // When I parse it
+            let version = syn::parse2::<SnapshotVersion>(input).unwrap();
+            // It should parse correctly
+            assert_eq!(version, SnapshotVersion { year: 24, week: 3, revision: 'b' })
+        }
+
+        #[test]
+        fn test_snapshot_version_parsing_invalid() {
+            // Given malformed snapshot versions
+            for input in [quote!(23w45), quote!(23x45a), quote!(23wa), quote!(1.18)] {
+                // When I parse them
+                let result = syn::parse2::<SnapshotVersion>(input);
+                // It should return an error
+                assert!(result.is_err());
+            }
+        }
+
+        #[test]
+        fn test_version_set_element_parsing_snapshot() {
+            // Given a valid snapshot version
+            let input = quote!(23w45a);
+            // When I parse it
+            let version = syn::parse2::<VersionSetElement>(input).unwrap();
+            // It should parse correctly
+            assert_eq!(version, VersionSetElement::Snapshot(SnapshotVersion { year: 23, week: 45, revision: 'a' }))
+        }
+
+        #[test]
+        fn test_version_set_element_parsing_snapshot_range() {
+            // Given a valid snapshot range
+            let input = quote!(23w32a..23w35a);
+            // When I parse it
+            let version = syn::parse2::<VersionSetElement>(input).unwrap();
+            // It should parse correctly
+            let expected = VersionSetElement::SnapshotRange(
+                SnapshotVersion { year: 23, week: 32, revision: 'a' },
+                SnapshotVersion { year: 23, week: 35, revision: 'a' }
+            );
+            assert_eq!(version, expected)
+        }
+
+        #[test]
+        fn test_version_set_element_parsing_mixed_range() {
+            // Given a range from a snapshot to a release
+            let input = quote!(23w32a..1.20.2);
+            // When I parse it
+            let result = syn::parse2::<VersionSetElement>(input);
+            // It should return an error
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn test_version_set_element_expand_snapshot_range() {
+            // Given a valid snapshot range
+            let range = VersionSetElement::SnapshotRange(
+                SnapshotVersion { year: 23, week: 32, revision: 'a' },
+                SnapshotVersion { year: 23, week: 34, revision: 'a' }
+            );
+            // When I expand it
+            let snapshots = range.expand_snapshots().unwrap();
+            let versions = range.expand().unwrap();
+            // It should expand into snapshots only
+            let expected = vec![
+                SnapshotVersion { year: 23, week: 32, revision: 'a' },
+                SnapshotVersion { year: 23, week: 33, revision: 'a' },
+                SnapshotVersion { year: 23, week: 34, revision: 'a' },
+            ];
+            assert_eq!(snapshots, expected);
+            assert!(versions.is_empty());
+        }
+
+        #[test]
+        fn test_version_set_element_expand_snapshot_range_invalid() {
+            // Given snapshot ranges spanning years, spanning revisions, or out of order
+            let ranges = [
+                VersionSetElement::SnapshotRange(SnapshotVersion { year: 23, week: 51, revision: 'a' }, SnapshotVersion { year: 24, week: 3, revision: 'a' }),
+                VersionSetElement::SnapshotRange(SnapshotVersion { year: 23, week: 32, revision: 'a' }, SnapshotVersion { year: 23, week: 34, revision: 'b' }),
+                VersionSetElement::SnapshotRange(SnapshotVersion { year: 23, week: 34, revision: 'a' }, SnapshotVersion { year: 23, week: 32, revision: 'a' }),
+            ];
+
+            for range in ranges {
+                // When I expand them
+                let result = range.expand_snapshots();
+                // It should return an error
+                assert!(result.is_err());
+            }
+        }
+
         //------ Version Set Tests ------//
 
         #[test]
@@ -747,6 +1115,7 @@ mod test {
             let expected = PackFormat {
                 format_id: 8,
                 versions: vec![SemanticVersion { major: 1, minor: 18, patch: 0 }],
+                snapshots: vec![],
             };
             assert_eq!(format, expected);
         }
@@ -765,6 +1134,7 @@ mod test {
                     SemanticVersion { major: 1, minor: 18, patch: 1 },
                     SemanticVersion { major: 1, minor: 18, patch: 2 },
                 ],
+                snapshots: vec![],
             };
             assert_eq!(format, expected);
         }
@@ -783,10 +1153,30 @@ mod test {
                     SemanticVersion { major: 1, minor: 18, patch: 1 },
                     SemanticVersion { major: 1, minor: 18, patch: 2 },
                 ],
+                snapshots: vec![],
             };
             assert_eq!(format, expected);
         }
         
+        #[test]
+        fn test_pack_format_parsing_with_snapshots() {
+            // Given a pack format list mixing snapshots and releases
+            let input = quote!((18, [23w31a, 23w32a..23w33a, 1.20.2]));
+            // When I parse it
+            let format = syn::parse2::<PackFormat>(input).unwrap();
+            // It should split releases and snapshots
+            let expected = PackFormat {
+                format_id: 18,
+                versions: vec![SemanticVersion { major: 1, minor: 20, patch: 2 }],
+                snapshots: vec![
+                    SnapshotVersion { year: 23, week: 31, revision: 'a' },
+                    SnapshotVersion { year: 23, week: 32, revision: 'a' },
+                    SnapshotVersion { year: 23, week: 33, revision: 'a' },
+                ],
+            };
+            assert_eq!(format, expected);
+        }
+
         #[test]
         fn test_pack_format_parsing_non_numeric_format_id() {
             // Given a non-numeric pack format id
@@ -854,12 +1244,14 @@ mod test {
                             SemanticVersion { major: 1, minor: 18, patch: 0 },
                             SemanticVersion { major: 1, minor: 18, patch: 1 },
                         ],
+                        snapshots: vec![],
                     },
                     PackFormat {
                         format_id: 9,
                         versions: vec![
                             SemanticVersion { major: 1, minor: 18, patch: 2 },
                         ],
+                        snapshots: vec![],
                     }
                 ],
                 resource_packs: vec![
@@ -870,6 +1262,7 @@ mod test {
                             SemanticVersion { major: 1, minor: 18, patch: 1 },
                             SemanticVersion { major: 1, minor: 18, patch: 2 },
                         ],
+                        snapshots: vec![],
                     },
                 ],
                 mc_versions: vec![
@@ -877,10 +1270,77 @@ mod test {
                     SemanticVersion { major: 1, minor: 18, patch: 1 },
                     SemanticVersion { major: 1, minor: 18, patch: 2 },
                 ],
+                mc_snapshots: vec![],
             };
             assert_eq!(format_list, expected);
         }
         
+        #[test]
+        fn test_format_list_parsing_snapshots() {
+            // Given a format list with snapshots shared between data and resource formats
+            let input = quote!{
+                data = [
+                    (18, [23w32a, 1.20.2])
+                ],
+                resource = [
+                    (17, 23w31a..23w32a)
+                ]
+            };
+
+            //When I parse it
+            let format_list = syn::parse2::<FormatList>(input).unwrap();
+
+            // It should collect each snapshot once, in order
+            assert_eq!(format_list.mc_snapshots, vec![
+                SnapshotVersion { year: 23, week: 31, revision: 'a' },
+                SnapshotVersion { year: 23, week: 32, revision: 'a' },
+            ]);
+            assert_eq!(format_list.mc_versions, vec![SemanticVersion { major: 1, minor: 20, patch: 2 }]);
+        }
+
+        #[test]
+        fn test_format_list_parsing_duplicate_data_format() {
+            // Given a format list declaring the same data format twice
+            let input = quote!{
+                data = [
+                    (8, [1.18, 1.18.1]),
+                    (8, 1.18.2)
+                ],
+                resource = [
+                    (8, 1.18..1.18.2)
+                ]
+            };
+
+            //When I parse it
+            let result = syn::parse2::<FormatList>(input);
+
+            // It should return an error identifying the duplicate
+            let error = result.unwrap_err();
+            assert_eq!(error.to_string(), "Duplicate data format id 8");
+        }
+
+        #[test]
+        fn test_format_list_parsing_duplicate_resource_format() {
+            // Given a format list declaring the same resource format twice
+            let input = quote!{
+                data = [
+                    (8, [1.18, 1.18.1]),
+                    (9, 1.18.2)
+                ],
+                resource = [
+                    (8, 1.18..1.18.1),
+                    (8, 1.18.2)
+                ]
+            };
+
+            //When I parse it
+            let result = syn::parse2::<FormatList>(input);
+
+            // It should return an error identifying the duplicate
+            let error = result.unwrap_err();
+            assert_eq!(error.to_string(), "Duplicate resource format id 8");
+        }
+
         #[test]
         fn test_format_list_parsing_missing_comma() {
             // Given a format list missing the comma
@@ -971,6 +1431,61 @@ mod test {
 
     mod generate_output {
         use super::*;
+
+        #[test]
+        fn test_generate_reverse_format_maps() {
+            // Given an intermediate where a version is shared between data formats
+            let input = quote!(
+                data = [
+                    (8, [1.18, 1.18.1]),
+                    (9, [1.18.1, 1.18.2])
+                ],
+                resource = [
+                    (8, 1.18..1.18.2)
+                ]
+            );
+            let intermediate = syn::parse2::<FormatList>(input).unwrap();
+
+            // When I generate the format maps
+            let output = generate_format_maps(&intermediate.data_formats, &intermediate.resource_packs).to_string();
+
+            // Then the reverse maps should map each version to every format containing it
+            assert!(output.contains("pub static VERSION_TO_DATA_FORMATS"));
+            assert!(output.contains("pub static VERSION_TO_RESOURCE_FORMATS"));
+
+            assert!(output.contains(&quote!(map.insert(*V1_18, vec![&*D8]);).to_string()));
+            assert!(output.contains(&quote!(map.insert(*V1_18_1, vec![&*D8, &*D9]);).to_string()));
+            assert!(output.contains(&quote!(map.insert(*V1_18_2, vec![&*D9]);).to_string()));
+
+            assert!(output.contains(&quote!(map.insert(*V1_18, vec![&*R8]);).to_string()));
+            assert!(output.contains(&quote!(map.insert(*V1_18_1, vec![&*R8]);).to_string()));
+            assert!(output.contains(&quote!(map.insert(*V1_18_2, vec![&*R8]);).to_string()));
+        }
+
+        #[test]
+        fn test_generate_output_snapshots() {
+            // Given an intermediate containing a snapshot
+            let input = quote!(
+                data = [
+                    (18, [23w32a, 1.20.2])
+                ],
+                resource = [
+                    (18, 1.20.2)
+                ]
+            );
+            let intermediate = syn::parse2::<FormatList>(input).unwrap();
+
+            // When I generate the output
+            let output = generate_output(intermediate).to_string();
+
+            // It should generate a snapshot static, a map entry, and a format referencing it
+            assert!(output.contains("pub static S23W32A"));
+            assert!(output.contains("SnapshotVersion :: new (23u8 , 32u8 , 'a')"));
+            assert!(output.contains("SNAPSHOT_MAP"));
+            assert!(output.contains("\"23w32a\""));
+            assert!(output.contains("PackFormat :: with_snapshots (18u8 , [* V1_20_2] , [* S23W32A])"));
+            assert!(output.contains("PackFormat :: new (18u8 , [* V1_20_2])"));
+        }
         
         #[test]
         fn test_generate_output() {
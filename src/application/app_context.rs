@@ -1,4 +1,5 @@
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::sync::Arc;
 use getset::Getters;
 use tokio::sync::RwLock;
@@ -7,7 +8,7 @@ use crate::repositories::{adapter_repo, project_repo};
 use crate::services::filesystem_service::{DefaultFilesystemProvider, FilesystemProvider};
 use crate::services::project_service::{self, ProjectService, ProjectServiceProvider};
 use crate::services::zip_service;
-use crate::services::translation_service::{TranslationProvider, TranslationService};
+use crate::services::translation_service::{self, TranslationProvider, TranslationService};
 use crate::services::undo_service::{UndoProvider, UndoService};
 
 pub struct Uninitialized;
@@ -37,7 +38,15 @@ impl Default for AppContextBuilder<Finalized> {
         
         let undo_service = UndoService::new();
         
-        let translation_service = TranslationService::try_with_default_language(filesystem_service.clone()).expect("Failed to initialize translation service");
+        let mut translation_service = TranslationService::try_with_default_language(filesystem_service.clone()).expect("Failed to initialize translation service");
+
+        if let Some(external_language_path) = std::env::var_os(translation_service::EXTERNAL_LANGUAGE_PATH_VAR) {
+            let external_language_path = PathBuf::from(external_language_path);
+
+            if let Err(error) = translation_service.set_external_language_path(Some(external_language_path.clone())) {
+                tracing::error!("Failed to load languages from {} - {}", external_language_path.display(), error);
+            }
+        }
         
         let self_ = AppContextBuilder::new()
             .with_filesystem(filesystem_service.clone())
@@ -152,6 +161,14 @@ pub struct AppContext {
 #[derive(Clone)]
 pub struct FilesystemServiceContext(Arc<RwLock<dyn FilesystemProvider + Send + Sync>>);
 
+impl Deref for FilesystemServiceContext {
+    type Target = Arc<RwLock<dyn FilesystemProvider + Send + Sync>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 #[derive(Clone)]
 pub struct ProjectServiceContext(Arc<RwLock<dyn ProjectServiceProvider + Send + Sync>>);
 
@@ -167,6 +184,14 @@ impl ProjectServiceContext {
     }
 }
 
+impl Deref for ProjectServiceContext {
+    type Target = Arc<RwLock<dyn ProjectServiceProvider + Send + Sync>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 #[derive(Clone)]
 pub struct UndoServiceContext(Arc<RwLock<dyn UndoProvider + Send + Sync>>);
 
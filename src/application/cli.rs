@@ -0,0 +1,280 @@
+//! Runs project operations from the command line without starting the GUI, for scripting and CI.
+//!
+//! ```text
+//! gaea-mc import <pack.zip> [project dir]
+//! gaea-mc export <project dir> <out.zip> [--json pretty|compact] [--sort-keys]
+//! gaea-mc validate <pack.zip> [--format human|json]
+//! gaea-mc describe <project dir> data|resource <description>
+//! ```
+
+use std::path::{Path, PathBuf};
+use crate::data::diagnostic::{self, Diagnostic, DiagnosticSeverity, OutputFormat};
+use crate::data::domain::pack_info::PackDescription;
+use crate::data::domain::project::PackHalf;
+use crate::data::serialization::json::{JsonLayout, JsonStyle, KeyOrder};
+use crate::services::lint_service::LintService;
+use crate::services::project_service::{ProjectServiceError, ProjectServiceProvider, ProjectZipData, ZipPath};
+
+pub const IMPORT_COMMAND: &str = "import";
+pub const EXPORT_COMMAND: &str = "export";
+pub const VALIDATE_COMMAND: &str = "validate";
+pub const DESCRIBE_COMMAND: &str = "describe";
+
+const FORMAT_FLAG: &str = "--format";
+const JSON_FLAG: &str = "--json";
+const SORT_KEYS_FLAG: &str = "--sort-keys";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CliCommand {
+    /// Imports a zipped pack and saves it as a project
+    Import {
+        zip: PathBuf,
+        project_dir: PathBuf,
+    },
+    /// Opens a project's folder, such as one saved by `import`, and exports it as a zip.
+    /// Its JSON is written as it was unless a layout or key order is asked for
+    Export {
+        project: PathBuf,
+        out: PathBuf,
+        json_style: JsonStyle,
+    },
+    /// Imports a zipped pack and reports anything it had to work around, anything the linter finds,
+    /// and anything its pack format doesn't support. Fails if any of it is an error
+    Validate {
+        zip: PathBuf,
+        format: OutputFormat,
+    },
+    /// Sets the description in one of a saved project's `pack.mcmeta` files. Combined projects keep the other pack's as it was
+    Describe {
+        project: PathBuf,
+        pack: PackHalf,
+        description: String,
+    },
+}
+
+impl CliCommand {
+    /// Parses the arguments after the executable's name, or `None` if they don't start with a command,
+    /// in which case the GUI should start instead
+    pub fn parse(args: &[String]) -> Option<Result<Self, CliError>> {
+        let (command, args) = args.split_first()?;
+
+        let command = match command.as_str() {
+            IMPORT_COMMAND => match args {
+                [zip] => Ok(CliCommand::Import { project_dir: zip_stem(Path::new(zip)), zip: zip.into() }),
+                [zip, project_dir] => Ok(CliCommand::Import { zip: zip.into(), project_dir: project_dir.into() }),
+                _ => Err(CliError::Usage(format!("{} <pack.zip> [project dir]", IMPORT_COMMAND))),
+            },
+            EXPORT_COMMAND => {
+                let usage = || CliError::Usage(format!("{} <project dir> <out.zip> [{} pretty|compact] [{}]", EXPORT_COMMAND, JSON_FLAG, SORT_KEYS_FLAG));
+                match args {
+                    [project, out, flags @ ..] => json_style(flags)
+                        .map(|json_style| CliCommand::Export { project: project.into(), out: out.into(), json_style })
+                        .ok_or_else(usage),
+                    _ => Err(usage()),
+                }
+            }
+            VALIDATE_COMMAND => {
+                let usage = || CliError::Usage(format!("{} <pack.zip> [{} human|json]", VALIDATE_COMMAND, FORMAT_FLAG));
+                match args {
+                    [zip] => Ok(CliCommand::Validate { zip: zip.into(), format: OutputFormat::Human }),
+                    [zip, flag, format] if flag == FORMAT_FLAG => match format.as_str() {
+                        "human" => Ok(CliCommand::Validate { zip: zip.into(), format: OutputFormat::Human }),
+                        "json" => Ok(CliCommand::Validate { zip: zip.into(), format: OutputFormat::Json }),
+                        _ => Err(usage()),
+                    },
+                    _ => Err(usage()),
+                }
+            }
+            DESCRIBE_COMMAND => {
+                let usage = || CliError::Usage(format!("{} <project dir> data|resource <description>", DESCRIBE_COMMAND));
+                match args {
+                    [project, pack, description] => pack_half(pack)
+                        .map(|pack| CliCommand::Describe { project: project.into(), pack, description: description.clone() })
+                        .ok_or_else(usage),
+                    _ => Err(usage()),
+                }
+            }
+            _ => return None,
+        };
+
+        Some(command)
+    }
+}
+
+fn pack_half(pack: &str) -> Option<PackHalf> {
+    match pack {
+        "data" => Some(PackHalf::Data),
+        "resource" => Some(PackHalf::Resource),
+        _ => None,
+    }
+}
+
+/// Reads the export's JSON flags, or `None` if any of them isn't one. Without any, files keep their own formatting
+fn json_style(flags: &[String]) -> Option<JsonStyle> {
+    let mut layout = None;
+    let mut key_order = None;
+
+    let mut flags = flags.iter();
+    while let Some(flag) = flags.next() {
+        match flag.as_str() {
+            JSON_FLAG => layout = Some(match flags.next()?.as_str() {
+                "pretty" => JsonLayout::Pretty,
+                "compact" => JsonLayout::Compact,
+                _ => return None,
+            }),
+            SORT_KEYS_FLAG => key_order = Some(KeyOrder::Alphabetical),
+            _ => return None,
+        }
+    }
+
+    if layout.is_none() && key_order.is_none() {
+        return Some(JsonStyle::Preserve);
+    }
+    Some(JsonStyle::Reformat { layout: layout.unwrap_or_default(), key_order: key_order.unwrap_or_default() })
+}
+
+/// What a command prints, and whether it succeeded. Validation prints its findings either way,
+/// so that tools reading the output don't have to look in two places
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CliOutput {
+    pub text: String,
+    pub success: bool,
+}
+
+impl From<String> for CliOutput {
+    fn from(text: String) -> Self {
+        Self { text, success: true }
+    }
+}
+
+/// Runs a command, returning what should be printed for the user
+pub async fn run(command: CliCommand, project_service: &(dyn ProjectServiceProvider + Send + Sync)) -> Result<CliOutput, CliError> {
+    match command {
+        CliCommand::Import { zip, project_dir } => {
+            let (project_id, report) = project_service.import_zip(ZipPath::Single(zip.clone())).await?;
+            let saved_to = project_service.save_project_as(project_id, &project_dir, false).await?;
+
+            let mut output = format!("Imported {} to {}", zip.display(), saved_to.display());
+            if !report.is_empty() {
+                output.push_str(&format!("\nWorked around: {}", report));
+            }
+            Ok(output.into())
+        }
+        CliCommand::Export { project, out, json_style } => {
+            // Exporting never changes the project, so it's opened read-only
+            let project_id = project_service.open_project_read_only(&project).await?;
+            let warnings = project_service.export_zip(ProjectZipData {
+                project_id,
+                path: ZipPath::Single(out.clone()),
+                strict_compatibility: false,
+                json_style,
+            }, false).await?;
+
+            let mut output = format!("Exported {} to {}", project.display(), out.display());
+            for warning in warnings {
+                output.push_str(&format!("\nWarning: {}", warning));
+            }
+            Ok(output.into())
+        }
+        CliCommand::Validate { zip, format } => {
+            let (project_id, report) = project_service.import_zip(ZipPath::Single(zip.clone())).await?;
+            let plan = project_service.plan_export(ProjectZipData {
+                project_id,
+                path: ZipPath::Single(zip.clone()),
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
+            }).await?;
+
+            let lint_service = LintService::new();
+            let lints = project_service.lint_project(project_id, &lint_service).await?;
+            tracing::debug!("Lint parse cache: {:?}", lint_service.cache_stats());
+
+            let diagnostics: Vec<Diagnostic> = report.diagnostics().into_iter()
+                .chain(lints.into_iter().map(Diagnostic::from))
+                .chain(plan.warnings.into_iter().map(Diagnostic::from))
+                .collect();
+            let success = diagnostics.iter().all(|diagnostic| diagnostic.severity < DiagnosticSeverity::Error);
+
+            let text = match format {
+                OutputFormat::Human if diagnostics.is_empty() => format!("{} is valid", zip.display()),
+                _ => diagnostic::render(&diagnostics, format),
+            };
+            Ok(CliOutput { text, success })
+        }
+        CliCommand::Describe { project, pack, description } => {
+            let project_id = project_service.open_project(&project).await?;
+            project_service.set_description(project_id, pack, PackDescription::new(description)).await?;
+            project_service.save_project(project_id).await?;
+
+            Ok(format!("Set the description of {}", project.display()).into())
+        }
+    }
+}
+
+/// Imports are saved beside the zip by default, in a folder named after it
+fn zip_stem(zip: &Path) -> PathBuf {
+    zip.with_extension("")
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error("Usage: gaea-mc {0}")]
+    Usage(String),
+    #[error(transparent)]
+    Service(#[from] ProjectServiceError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[rstest]
+    #[case::import(&["import", "packs/my_pack.zip"], CliCommand::Import { zip: "packs/my_pack.zip".into(), project_dir: "packs/my_pack".into() })]
+    #[case::import_to(&["import", "my_pack.zip", "projects/my_pack"], CliCommand::Import { zip: "my_pack.zip".into(), project_dir: "projects/my_pack".into() })]
+    #[case::export(&["export", "my_pack", "out.zip"], CliCommand::Export { project: "my_pack".into(), out: "out.zip".into(), json_style: JsonStyle::Preserve })]
+    #[case::export_compact(&["export", "my_pack", "out.zip", "--json", "compact"], CliCommand::Export {
+        project: "my_pack".into(),
+        out: "out.zip".into(),
+        json_style: JsonStyle::Reformat { layout: JsonLayout::Compact, key_order: KeyOrder::Vanilla },
+    })]
+    #[case::export_sorted(&["export", "my_pack", "out.zip", "--sort-keys"], CliCommand::Export {
+        project: "my_pack".into(),
+        out: "out.zip".into(),
+        json_style: JsonStyle::Reformat { layout: JsonLayout::Pretty, key_order: KeyOrder::Alphabetical },
+    })]
+    #[case::validate(&["validate", "my_pack.zip"], CliCommand::Validate { zip: "my_pack.zip".into(), format: OutputFormat::Human })]
+    #[case::validate_json(&["validate", "my_pack.zip", "--format", "json"], CliCommand::Validate { zip: "my_pack.zip".into(), format: OutputFormat::Json })]
+    #[case::describe(&["describe", "my_pack", "resource", "Gem textures"], CliCommand::Describe {
+        project: "my_pack".into(),
+        pack: PackHalf::Resource,
+        description: "Gem textures".to_string(),
+    })]
+    fn test_parse(#[case] arguments: &[&str], #[case] expected: CliCommand) {
+        // Given arguments naming a command
+        // When I parse them
+        let command = CliCommand::parse(&args(arguments)).unwrap().unwrap();
+
+        // Then the command should be recognized
+        assert_eq!(command, expected);
+    }
+
+    #[test]
+    fn test_parse_not_a_command() {
+        // Given no arguments, or ones the GUI handles
+        // When I parse them
+        // Then the GUI should start
+        assert!(CliCommand::parse(&args(&[])).is_none());
+        assert!(CliCommand::parse(&args(&["--export-translations"])).is_none());
+
+        // Unless a command is missing its arguments
+        assert!(matches!(CliCommand::parse(&args(&["export", "my_pack"])), Some(Err(CliError::Usage(_)))));
+        assert!(matches!(CliCommand::parse(&args(&["export", "my_pack", "out.zip", "--json", "tabs"])), Some(Err(CliError::Usage(_)))));
+        assert!(matches!(CliCommand::parse(&args(&["validate", "my_pack.zip", "--format", "xml"])), Some(Err(CliError::Usage(_)))));
+        assert!(matches!(CliCommand::parse(&args(&["describe", "my_pack", "both", "Gems"])), Some(Err(CliError::Usage(_)))));
+    }
+}
@@ -0,0 +1,118 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Platforms with a known command for opening the file manager
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Platform {
+    Windows,
+    MacOs,
+    /// Linux and the BSDs, through the desktop's default file manager
+    Unix,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Platform::Windows
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else {
+            Platform::Unix
+        }
+    }
+}
+
+/// Opens the OS file manager at a project or file, such as to find a pack on disk.
+/// Files are selected in their directory where the file manager supports it
+pub fn reveal(path: &Path) -> Result<(), RevealError> {
+    let metadata = std::fs::metadata(path).map_err(|_| RevealError::Missing(path.to_path_buf()))?;
+
+    reveal_command(Platform::current(), path, metadata.is_dir())
+        .spawn()
+        .map_err(RevealError::Spawn)?;
+
+    Ok(())
+}
+
+/// The command [`reveal`] runs, without running it. `xdg-open` can't select a file, so a file's directory is opened instead
+pub fn reveal_command(platform: Platform, path: &Path, is_dir: bool) -> Command {
+    match (platform, is_dir) {
+        (Platform::Windows, false) => {
+            // Explorer only takes the path as part of the same argument
+            let mut select = OsString::from("/select,");
+            select.push(path);
+
+            let mut command = Command::new("explorer");
+            command.arg(select);
+            command
+        }
+        (Platform::Windows, true) => {
+            let mut command = Command::new("explorer");
+            command.arg(path);
+            command
+        }
+        (Platform::MacOs, false) => {
+            let mut command = Command::new("open");
+            command.arg("-R").arg(path);
+            command
+        }
+        (Platform::MacOs, true) => {
+            let mut command = Command::new("open");
+            command.arg(path);
+            command
+        }
+        (Platform::Unix, is_dir) => {
+            let directory = if is_dir { path } else { path.parent().unwrap_or(path) };
+
+            let mut command = Command::new("xdg-open");
+            command.arg(directory);
+            command
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RevealError {
+    #[error("{0:?} does not exist!")]
+    Missing(PathBuf),
+    #[error("Couldn't open the file manager! {0}")]
+    Spawn(std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case::windows_file(Platform::Windows, false, "explorer", &["/select,packs/gems/pack.mcmeta"])]
+    #[case::windows_directory(Platform::Windows, true, "explorer", &["packs/gems/pack.mcmeta"])]
+    #[case::mac_file(Platform::MacOs, false, "open", &["-R", "packs/gems/pack.mcmeta"])]
+    #[case::mac_directory(Platform::MacOs, true, "open", &["packs/gems/pack.mcmeta"])]
+    #[case::unix_file(Platform::Unix, false, "xdg-open", &["packs/gems"])]
+    #[case::unix_directory(Platform::Unix, true, "xdg-open", &["packs/gems/pack.mcmeta"])]
+    fn test_reveal_command(#[case] platform: Platform, #[case] is_dir: bool, #[case] program: &str, #[case] args: &[&str]) {
+        // Given a path on some platform
+        let path = Path::new("packs/gems/pack.mcmeta");
+
+        // When I build the command revealing it
+        let command = reveal_command(platform, path, is_dir);
+
+        // Then it should run that platform's file manager at the path
+        assert_eq!(command.get_program(), program);
+        assert_eq!(command.get_args().collect::<Vec<_>>(), args);
+    }
+
+    #[test]
+    fn test_reveal_missing_path() {
+        // Given a path with nothing there
+        let path = Path::new("packs/does_not_exist/pack.mcmeta");
+
+        // When I try to reveal it
+        let result = reveal(path);
+
+        // Then it should be refused without running anything
+        assert!(matches!(result, Err(RevealError::Missing(missing)) if missing == path));
+    }
+}
@@ -0,0 +1,116 @@
+use iced::{Element, Length};
+use iced::widget::{button, horizontal_space, row, text};
+use crate::application::app_context::TranslationServiceContext;
+use crate::application::gui::window;
+use crate::data::domain::project::ProjectID;
+use crate::services::translation_service::TranslationKey;
+
+/// Something with unsaved work which the user has asked to close
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CloseTarget {
+    Window(iced::window::Id),
+    Project(ProjectID),
+    /// An editor tab, by its index in the tab bar
+    Tab(usize),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CloseChoice {
+    Save,
+    Discard,
+    Cancel,
+}
+
+/// What the window should do once the user has answered the prompt
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CloseAction {
+    /// Save the unsaved work, and only close once that has succeeded
+    SaveThenClose(CloseTarget),
+    /// Close, throwing the unsaved work away
+    Close(CloseTarget),
+    KeepOpen,
+}
+
+pub fn resolve(target: CloseTarget, choice: CloseChoice) -> CloseAction {
+    match choice {
+        CloseChoice::Save => CloseAction::SaveThenClose(target),
+        CloseChoice::Discard => CloseAction::Close(target),
+        CloseChoice::Cancel => CloseAction::KeepOpen,
+    }
+}
+
+pub fn view(translations: &ClosePromptTranslations) -> Element<window::Message> {
+    row![
+        text(translations.unsaved_changes.as_str()),
+        horizontal_space(),
+        button(text(translations.save.as_str())).on_press(window::Message::CloseChoiceMade(CloseChoice::Save)),
+        button(text(translations.discard.as_str())).style(button::danger).on_press(window::Message::CloseChoiceMade(CloseChoice::Discard)),
+        button(text(translations.cancel.as_str())).style(button::secondary).on_press(window::Message::CloseChoiceMade(CloseChoice::Cancel)),
+    ]
+        .spacing(5)
+        .width(Length::Fill)
+        .into()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
+pub enum ClosePromptTranslationKeys {
+    #[translation(en_us = "You have unsaved changes. Save them before closing?")]
+    UnsavedChanges,
+    #[translation(en_us = "Save")]
+    Save,
+    #[translation(en_us = "Discard")]
+    Discard,
+    #[translation(en_us = "Cancel")]
+    Cancel,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClosePromptTranslations {
+    pub unsaved_changes: String,
+    pub save: String,
+    pub discard: String,
+    pub cancel: String,
+}
+
+impl ClosePromptTranslations {
+    pub(crate) async fn translate(translation_service: TranslationServiceContext) -> Self {
+        let translation_service = translation_service.read().await;
+
+        Self {
+            unsaved_changes: translation_service.translate(&ClosePromptTranslationKeys::UnsavedChanges),
+            save: translation_service.translate(&ClosePromptTranslationKeys::Save),
+            discard: translation_service.translate(&ClosePromptTranslationKeys::Discard),
+            cancel: translation_service.translate(&ClosePromptTranslationKeys::Cancel),
+        }
+    }
+}
+
+impl Default for ClosePromptTranslations {
+    /// Falls back to the English copy until the translations have loaded
+    fn default() -> Self {
+        Self {
+            unsaved_changes: ClosePromptTranslationKeys::UnsavedChanges.english_text().to_string(),
+            save: ClosePromptTranslationKeys::Save.english_text().to_string(),
+            discard: ClosePromptTranslationKeys::Discard.english_text().to_string(),
+            cancel: ClosePromptTranslationKeys::Cancel.english_text().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::domain::project::Project;
+
+    #[test]
+    fn test_resolve_choice() {
+        // Given a project waiting to be closed
+        let target = CloseTarget::Project(Project::generate_test_id());
+
+        // When the user answers the prompt
+        // Then saving should close only after the save, discarding should close straight away, and cancelling should do nothing
+        assert_eq!(resolve(target, CloseChoice::Save), CloseAction::SaveThenClose(target));
+        assert_eq!(resolve(target, CloseChoice::Discard), CloseAction::Close(target));
+        assert_eq!(resolve(target, CloseChoice::Cancel), CloseAction::KeepOpen);
+    }
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use iced::{Element, Length};
+use iced::widget::{button, horizontal_space, row, scrollable, text, Column};
+use crate::application::app_context::{ProjectServiceContext, TranslationServiceContext};
+use crate::application::gui::window;
+use crate::data::domain::project::{CasingFix, ExtensionFix, ProjectID};
+use crate::services::lint_service::{LintDiagnostic, LintRule, LintService, LintSeverity};
+use crate::services::translation_service::TranslationKey;
+
+/// Tall enough for a handful of problems, so the editor stays usable while the panel is open
+const DIAGNOSTICS_HEIGHT: f32 = 160.0;
+
+/// Problems the lint rules found in the shown project, with a fix next to those which can be fixed automatically
+#[derive(Debug, Clone)]
+pub struct DiagnosticsPanel {
+    project_id: ProjectID,
+    /// `None` until the project has been linted
+    diagnostics: Option<Vec<LintDiagnostic>>,
+}
+
+impl DiagnosticsPanel {
+    pub fn new(project_id: ProjectID) -> Self {
+        Self { project_id, diagnostics: None }
+    }
+
+    pub fn project_id(&self) -> ProjectID {
+        self.project_id
+    }
+
+    pub fn set_diagnostics(&mut self, diagnostics: Vec<LintDiagnostic>) {
+        self.diagnostics = Some(diagnostics);
+    }
+
+    pub fn view<'a>(&'a self, translations: &'a DiagnosticsTranslations) -> Element<'a, window::Message> {
+        let header = row![
+            text(translations.title.as_str()),
+            horizontal_space(),
+            button(text(translations.lint.as_str()))
+                .style(button::secondary)
+                .on_press(window::Message::LintProject(self.project_id)),
+            button(text("x")).style(button::text).on_press(window::Message::ToggleDiagnostics),
+        ]
+            .spacing(5);
+
+        let diagnostics: Element<window::Message> = match &self.diagnostics {
+            None => text(translations.linting.as_str()).into(),
+            Some(diagnostics) if diagnostics.is_empty() => text(translations.no_problems.as_str()).into(),
+            Some(diagnostics) => {
+                let rows = diagnostics.iter()
+                    .map(|diagnostic| self.diagnostic_row(diagnostic, translations));
+
+                scrollable(Column::with_children(rows).spacing(2).width(Length::Fill))
+                    .height(Length::Fixed(DIAGNOSTICS_HEIGHT))
+                    .into()
+            }
+        };
+
+        Column::new()
+            .push(header)
+            .push(diagnostics)
+            .spacing(5)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn diagnostic_row<'a>(&self, diagnostic: &'a LintDiagnostic, translations: &'a DiagnosticsTranslations) -> Element<'a, window::Message> {
+        let location = match diagnostic.line {
+            Some(line) => format!("{}:{}", diagnostic.path.display(), line),
+            None => diagnostic.path.display().to_string(),
+        };
+        let style = match diagnostic.severity {
+            LintSeverity::Error => text::danger,
+            LintSeverity::Warning => text::primary,
+            LintSeverity::Info => text::default,
+        };
+
+        row![text(format!("{} - {}", location, diagnostic.message)).style(style)]
+            .push_maybe(fix(self.project_id, diagnostic, translations).map(|(fix, label)| button(text(label))
+                .style(button::secondary)
+                .on_press(fix)))
+            .spacing(5)
+            .into()
+    }
+}
+
+/// What fixes a problem automatically, if anything does, along with the label of the button which does it
+fn fix<'a>(project_id: ProjectID, diagnostic: &LintDiagnostic, translations: &'a DiagnosticsTranslations) -> Option<(window::Message, &'a str)> {
+    match diagnostic.rule {
+        LintRule::WrongExtension => Some((window::Message::FixExtensions(project_id), translations.fix_extensions.as_str())),
+        // Lowercasing only helps locations which are invalid because of their casing
+        LintRule::InvalidResourceLocation if diagnostic.path.to_string_lossy().chars().any(char::is_uppercase) => {
+            Some((window::Message::FixCasing(project_id), translations.fix_casing.as_str()))
+        }
+        _ => None,
+    }
+}
+
+pub async fn lint(
+    project_service: ProjectServiceContext,
+    lint_service: Arc<LintService>,
+    project_id: ProjectID,
+) -> Result<Vec<LintDiagnostic>, String> {
+    project_service.read().await
+        .lint_project(project_id, &*lint_service).await
+        .map_err(|error| error.to_string())
+}
+
+/// Renames every file whose extension doesn't match its kind, then saves the project so the renames reach the disk
+pub async fn fix_extensions(project_service: ProjectServiceContext, project_id: ProjectID) -> Result<Vec<ExtensionFix>, String> {
+    let project_service = project_service.read().await;
+
+    let fixes = project_service.fix_resource_extensions(project_id).await.map_err(|error| error.to_string())?;
+    if !fixes.is_empty() {
+        project_service.save_project(project_id).await.map_err(|error| error.to_string())?;
+    }
+
+    Ok(fixes)
+}
+
+/// Lowercases every file path with uppercase letters in its resource location, rewriting references to them,
+/// then saves the project so the moves reach the disk
+pub async fn fix_casing(project_service: ProjectServiceContext, project_id: ProjectID) -> Result<Vec<CasingFix>, String> {
+    let project_service = project_service.read().await;
+
+    let fixes = project_service.fix_resource_casing(project_id).await.map_err(|error| error.to_string())?;
+    if !fixes.is_empty() {
+        project_service.save_project(project_id).await.map_err(|error| error.to_string())?;
+    }
+
+    Ok(fixes)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
+pub enum DiagnosticsTranslationKeys {
+    #[translation(en_us = "Problems")]
+    Title,
+    #[translation(en_us = "Check again")]
+    Lint,
+    #[translation(en_us = "Checking the project...")]
+    Linting,
+    #[translation(en_us = "No problems found")]
+    NoProblems,
+    #[translation(en_us = "Fix extensions")]
+    FixExtensions,
+    #[translation(en_us = "Files renamed")]
+    ExtensionsFixed,
+    #[translation(en_us = "Fix casing")]
+    FixCasing,
+    #[translation(en_us = "Files lowercased")]
+    CasingFixed,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticsTranslations {
+    pub title: String,
+    pub lint: String,
+    pub linting: String,
+    pub no_problems: String,
+    pub fix_extensions: String,
+    pub extensions_fixed: String,
+    pub fix_casing: String,
+    pub casing_fixed: String,
+}
+
+impl DiagnosticsTranslations {
+    pub(crate) async fn translate(translation_service: TranslationServiceContext) -> Self {
+        let translation_service = translation_service.read().await;
+
+        Self {
+            title: translation_service.translate(&DiagnosticsTranslationKeys::Title),
+            lint: translation_service.translate(&DiagnosticsTranslationKeys::Lint),
+            linting: translation_service.translate(&DiagnosticsTranslationKeys::Linting),
+            no_problems: translation_service.translate(&DiagnosticsTranslationKeys::NoProblems),
+            fix_extensions: translation_service.translate(&DiagnosticsTranslationKeys::FixExtensions),
+            extensions_fixed: translation_service.translate(&DiagnosticsTranslationKeys::ExtensionsFixed),
+            fix_casing: translation_service.translate(&DiagnosticsTranslationKeys::FixCasing),
+            casing_fixed: translation_service.translate(&DiagnosticsTranslationKeys::CasingFixed),
+        }
+    }
+}
+
+impl Default for DiagnosticsTranslations {
+    /// Falls back to the English copy until the translations have loaded
+    fn default() -> Self {
+        Self {
+            title: DiagnosticsTranslationKeys::Title.english_text().to_string(),
+            lint: DiagnosticsTranslationKeys::Lint.english_text().to_string(),
+            linting: DiagnosticsTranslationKeys::Linting.english_text().to_string(),
+            no_problems: DiagnosticsTranslationKeys::NoProblems.english_text().to_string(),
+            fix_extensions: DiagnosticsTranslationKeys::FixExtensions.english_text().to_string(),
+            extensions_fixed: DiagnosticsTranslationKeys::ExtensionsFixed.english_text().to_string(),
+            fix_casing: DiagnosticsTranslationKeys::FixCasing.english_text().to_string(),
+            casing_fixed: DiagnosticsTranslationKeys::CasingFixed.english_text().to_string(),
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use iced::{Element, Length};
+use iced::widget::{button, horizontal_space, row, scrollable, text, Column};
+use tracing::Level;
+use crate::application::app_context::TranslationServiceContext;
+use crate::application::gui::window;
+use crate::application::logging::LogEvent;
+use crate::services::metrics_service::{Operation, OperationStats};
+use crate::services::translation_service::TranslationKey;
+
+/// Tall enough for a handful of events, so the editor stays usable while the log is open
+const EVENT_LOG_HEIGHT: f32 = 160.0;
+
+pub fn view<'a>(
+    events: &'a [LogEvent],
+    timings: &[(Operation, OperationStats)],
+    translations: &'a EventLogTranslations,
+) -> Element<'a, window::Message> {
+    let header = row![
+        text(translations.title.as_str()),
+        horizontal_space(),
+        button(text(translations.copy.as_str()))
+            .style(button::secondary)
+            .on_press_maybe((!events.is_empty()).then_some(window::Message::CopyEventLog)),
+        button(text("x")).style(button::text).on_press(window::Message::ToggleEventLog),
+    ]
+        .spacing(5);
+
+    let events: Element<window::Message> = if events.is_empty() {
+        text(translations.empty.as_str()).into()
+    } else {
+        let lines = events.iter()
+            .map(|event| text(event.to_string()).style(match event.level {
+                Level::ERROR => text::danger,
+                Level::WARN => text::primary,
+                _ => text::default,
+            }).into());
+
+        scrollable(Column::with_children(lines).spacing(2).width(Length::Fill))
+            .anchor_bottom()
+            .height(Length::Fixed(EVENT_LOG_HEIGHT))
+            .into()
+    };
+
+    Column::new()
+        .push(header)
+        .push_maybe(timings_text(timings, translations).map(|timings| text(timings).style(text::secondary)))
+        .push(events)
+        .spacing(5)
+        .width(Length::Fill)
+        .into()
+}
+
+/// How long each operation last took, such as `Import: 1.8s`, or `None` if nothing has been timed yet
+pub fn timings_text(timings: &[(Operation, OperationStats)], translations: &EventLogTranslations) -> Option<String> {
+    let timings: Vec<_> = timings.iter()
+        .map(|(operation, stats)| format!("{}: {:.1?}", translations.operation(*operation), stats.last))
+        .collect();
+
+    (!timings.is_empty()).then(|| timings.join(" · "))
+}
+
+/// The events as plain text, one per line, for copying into a bug report
+pub fn copy_text(events: &[LogEvent]) -> String {
+    events.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
+pub enum EventLogTranslationKeys {
+    #[translation(en_us = "Event log")]
+    Title,
+    #[translation(en_us = "Copy")]
+    Copy,
+    #[translation(en_us = "Nothing has been logged yet")]
+    Empty,
+    #[translation(en_us = "Import")]
+    Import,
+    #[translation(en_us = "Export")]
+    Export,
+    #[translation(en_us = "Save")]
+    Save,
+    #[translation(en_us = "Indexing")]
+    Index,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventLogTranslations {
+    pub title: String,
+    pub copy: String,
+    pub empty: String,
+    pub import: String,
+    pub export: String,
+    pub save: String,
+    pub index: String,
+}
+
+impl EventLogTranslations {
+    pub fn operation(&self, operation: Operation) -> &str {
+        match operation {
+            Operation::Import => &self.import,
+            Operation::Export => &self.export,
+            Operation::Save => &self.save,
+            Operation::Index => &self.index,
+        }
+    }
+
+    pub(crate) async fn translate(translation_service: TranslationServiceContext) -> Self {
+        let translation_service = translation_service.read().await;
+
+        Self {
+            title: translation_service.translate(&EventLogTranslationKeys::Title),
+            copy: translation_service.translate(&EventLogTranslationKeys::Copy),
+            empty: translation_service.translate(&EventLogTranslationKeys::Empty),
+            import: translation_service.translate(&EventLogTranslationKeys::Import),
+            export: translation_service.translate(&EventLogTranslationKeys::Export),
+            save: translation_service.translate(&EventLogTranslationKeys::Save),
+            index: translation_service.translate(&EventLogTranslationKeys::Index),
+        }
+    }
+}
+
+impl Default for EventLogTranslations {
+    /// Falls back to the English copy until the translations have loaded
+    fn default() -> Self {
+        Self {
+            title: EventLogTranslationKeys::Title.english_text().to_string(),
+            copy: EventLogTranslationKeys::Copy.english_text().to_string(),
+            empty: EventLogTranslationKeys::Empty.english_text().to_string(),
+            import: EventLogTranslationKeys::Import.english_text().to_string(),
+            export: EventLogTranslationKeys::Export.english_text().to_string(),
+            save: EventLogTranslationKeys::Save.english_text().to_string(),
+            index: EventLogTranslationKeys::Index.english_text().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+
+    #[test]
+    fn test_timings_text() {
+        // Given timings for two operations, and translations for their names
+        let stats = |millis| OperationStats { count: 1, last: Duration::from_millis(millis), total: Duration::from_millis(millis) };
+        let timings = [(Operation::Import, stats(1800)), (Operation::Save, stats(200))];
+        let translations = EventLogTranslations { import: "Importer".to_string(), ..EventLogTranslations::default() };
+
+        // When I describe them
+        let text = timings_text(&timings, &translations);
+
+        // Then each should be named in the current language
+        assert_eq!(text.as_deref(), Some("Importer: 1.8s · Save: 200.0ms"));
+        assert_eq!(timings_text(&[], &translations), None);
+    }
+}
@@ -0,0 +1,849 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use iced::{Element, Length, Task};
+use iced::widget::{button, checkbox, mouse_area, row, text, text_input, Column, Scrollable};
+use crate::application::app_context::AppContext;
+use crate::application::gui::{text_editor, window};
+use crate::data::domain::pack_path::PackRelativePath;
+use crate::data::serialization::project::PACK_INFO_FILE;
+use crate::services::filesystem_service::{FilesystemProvider, FilesystemProviderError};
+
+const INDENT_WIDTH: f32 = 12.0;
+
+/// The only directories an overlay can hold, so the only files which can be copied into one
+const OVERLAY_CATEGORIES: &[&str] = &["data", "assets"];
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    RootSelected(PathBuf),
+    RootLoaded(Result<PackTree, FileTreeError>),
+    /// Empties the tree once its project is closed
+    RootCleared,
+
+    /// Opens a file in the editor, or expands or collapses a directory
+    NodeClicked(PathBuf),
+
+    RenameStarted(PathBuf),
+    RenameInputChanged(String),
+    /// Whether references to a renamed resource are rewritten to its new location
+    UpdateReferencesToggled(bool),
+    RenameSubmitted,
+    RenameCancelled,
+    Renamed(Result<RenamedFile, FileTreeError>),
+
+    /// Copies a base pack file into an overlay, so that it can be edited for that overlay's versions
+    CopyToOverlay { path: PathBuf, overlay: String },
+    CopiedToOverlay(Result<PathBuf, FileTreeError>),
+
+    /// Set while the open project is read-only, which hides renaming and copying
+    ReadOnlyChanged(bool),
+    /// Narrows the tree to a directory and everything in it, e.g. from the editor's breadcrumbs. `None` shows the whole tree again
+    FilterSelected(Option<PathBuf>),
+}
+
+impl From<Message> for window::Message {
+    fn from(value: Message) -> Self {
+        window::Message::FileTree(value)
+    }
+}
+
+pub struct FileTree {
+    app_context: Arc<AppContext>,
+
+    root: Option<FileNode>,
+    /// Overlays listed in the pack's `pack.mcmeta`, including any which have no files yet
+    overlays: Vec<String>,
+    /// Directories showing their contents. Everything else is collapsed, apart from the root
+    expanded: HashSet<PathBuf>,
+    renaming: Option<RenameState>,
+    /// Kept between renames, so it only has to be turned off once
+    update_references: bool,
+    error: Option<FileTreeError>,
+    read_only: bool,
+    filter: Option<PathBuf>,
+}
+
+impl FileTree {
+    pub fn new(app_context: Arc<AppContext>) -> Self {
+        Self {
+            app_context,
+
+            root: None,
+            overlays: Vec::new(),
+            expanded: HashSet::new(),
+            renaming: None,
+            update_references: true,
+            error: None,
+            read_only: false,
+            filter: None,
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<window::Message> {
+        match message {
+            Message::RootSelected(path) => {
+                self.expanded.clear();
+                self.load_root(path)
+            }
+            Message::RootCleared => {
+                self.root = None;
+                self.overlays.clear();
+                self.expanded.clear();
+                self.renaming = None;
+                self.error = None;
+                self.filter = None;
+                Task::none()
+            }
+            Message::NodeClicked(path) => {
+                let is_directory = self.root.as_ref()
+                    .and_then(|root| root.find(&path))
+                    .is_some_and(|node| matches!(node.kind, FileNodeKind::Directory { .. }));
+
+                if !is_directory {
+                    return Task::done(text_editor::Message::OpenPath(path).into());
+                }
+
+                if !self.expanded.remove(&path) {
+                    self.expanded.insert(path);
+                }
+                Task::none()
+            }
+            Message::RootLoaded(result) => {
+                match result {
+                    Ok(PackTree { root, overlays }) => {
+                        self.root = Some(root);
+                        self.overlays = overlays;
+                        self.error = None;
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+
+                Task::none()
+            }
+            Message::RenameStarted(_) | Message::CopyToOverlay { .. } if self.read_only => Task::none(),
+            Message::RenameStarted(path) => {
+                let new_name = path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                self.renaming = Some(RenameState { path, new_name });
+                Task::none()
+            }
+            Message::RenameInputChanged(new_name) => {
+                if let Some(renaming) = &mut self.renaming {
+                    renaming.new_name = new_name;
+                }
+
+                Task::none()
+            }
+            Message::UpdateReferencesToggled(update_references) => {
+                self.update_references = update_references;
+                Task::none()
+            }
+            Message::RenameSubmitted => {
+                let Some(RenameState { path, new_name }) = self.renaming.take() else {
+                    return Task::none();
+                };
+
+                let filesystem = self.app_context.filesystem_service_context().clone();
+
+                Task::perform(
+                    async move {
+                        let filesystem = filesystem.read().await;
+                        rename_file(&*filesystem, &path, &new_name).await
+                    },
+                    |result| Message::Renamed(result).into()
+                )
+            }
+            Message::ReadOnlyChanged(read_only) => {
+                self.read_only = read_only;
+                self.renaming = None;
+                Task::none()
+            }
+            Message::RenameCancelled => {
+                self.renaming = None;
+                Task::none()
+            }
+            Message::FilterSelected(filter) => {
+                self.filter = filter;
+                Task::none()
+            }
+            Message::Renamed(result) => {
+                match result {
+                    Ok(renamed) => {
+                        self.error = None;
+
+                        let reload = match &self.root {
+                            Some(root) => self.load_root(root.path.clone()),
+                            None => Task::none(),
+                        };
+
+                        Task::batch([
+                            reload,
+                            Task::done(window::Message::FileRenamed(renamed, self.update_references)),
+                        ])
+                    }
+                    Err(error) => {
+                        self.error = Some(error);
+                        Task::none()
+                    }
+                }
+            }
+            Message::CopyToOverlay { path, overlay } => {
+                let Some(root) = self.root.as_ref().map(|root| root.path.clone()) else {
+                    return Task::none();
+                };
+
+                let filesystem = self.app_context.filesystem_service_context().clone();
+
+                Task::perform(
+                    async move {
+                        let filesystem = filesystem.read().await;
+                        copy_to_overlay(&*filesystem, &root, &path, &overlay).await
+                    },
+                    |result| Message::CopiedToOverlay(result).into()
+                )
+            }
+            Message::CopiedToOverlay(result) => {
+                match result {
+                    Ok(path) => {
+                        self.error = None;
+
+                        let reload = match &self.root {
+                            Some(root) => self.load_root(root.path.clone()),
+                            None => Task::none(),
+                        };
+
+                        Task::batch([
+                            reload,
+                            Task::done(text_editor::Message::OpenPath(path).into()),
+                        ])
+                    }
+                    Err(error) => {
+                        self.error = Some(error);
+                        Task::none()
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<window::Message> {
+        let mut column = Column::new().spacing(2);
+
+        if let Some(error) = &self.error {
+            column = column.push(text(error.to_string()));
+        }
+
+        if let Some(filter) = &self.filter {
+            column = column.push(row![
+                text(filter.display().to_string()).style(text::secondary).width(Length::Fill),
+                button(text("x")).style(button::text).on_press(Message::FilterSelected(None).into()),
+            ]);
+        }
+
+        match &self.root {
+            Some(root) => {
+                for (depth, node) in root.iter_filtered(self.filter.as_deref()) {
+                    if self.is_visible(root, node) {
+                        column = column.push(self.view_node(node, depth));
+                    }
+                }
+            }
+            None => {
+                column = column.push(text("No project open"));
+            }
+        }
+
+        Scrollable::new(column)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn view_node<'a>(&'a self, node: &'a FileNode, depth: usize) -> Element<'a, window::Message> {
+        let indent = iced::widget::horizontal_space().width(INDENT_WIDTH * depth as f32);
+
+        match &self.renaming {
+            Some(renaming) if renaming.path == node.path => {
+                row![
+                    indent,
+                    text_input("", &renaming.new_name)
+                        .on_input(|input| Message::RenameInputChanged(input).into())
+                        .on_submit(Message::RenameSubmitted.into()),
+                    checkbox("Update references", self.update_references)
+                        .on_toggle(|update_references| Message::UpdateReferencesToggled(update_references).into()),
+                    button(text("x")).on_press(Message::RenameCancelled.into()),
+                ]
+                    .spacing(2)
+                    .into()
+            }
+            _ => {
+                let label = match &node.kind {
+                    FileNodeKind::Directory { .. } if self.expanded.contains(&node.path) => format!("▾ {}", node.name()),
+                    FileNodeKind::Directory { .. } => format!("▸ {}", node.name()),
+                    FileNodeKind::File => node.name(),
+                };
+                let node_button = button(text(label))
+                    .style(button::text)
+                    .width(Length::Fill)
+                    .on_press(Message::NodeClicked(node.path.clone()).into());
+
+                // Renaming is a right click, so that a left click can open the file
+                let node_button: Element<_> = match self.read_only {
+                    true => node_button.into(),
+                    false => mouse_area(node_button).on_right_press(Message::RenameStarted(node.path.clone()).into()).into(),
+                };
+
+                let mut node_row = row![indent, node_button].spacing(2);
+
+                match (node.origin(), &node.kind) {
+                    // Badge the top of each overlay, everything inside is from the same overlay
+                    (NodeOrigin::Overlay(overlay), FileNodeKind::Directory { .. }) if depth == 1 => {
+                        node_row = node_row.push(text(format!("[{}]", overlay)).style(text::secondary));
+                    }
+                    (NodeOrigin::Base, FileNodeKind::File) if !self.read_only && self.can_copy_to_overlay(&node.path) => {
+                        for overlay in &self.overlays {
+                            node_row = node_row.push(button(text(format!("+{}", overlay)))
+                                .style(button::text)
+                                .on_press(Message::CopyToOverlay { path: node.path.clone(), overlay: overlay.to_string() }.into()));
+                        }
+                    }
+                    _ => {}
+                }
+
+                node_row.into()
+            }
+        }
+    }
+
+    /// Whether a file belongs in an overlay. Files at the pack root, like `pack.mcmeta` or `pack.png`, only apply to the whole pack
+    fn can_copy_to_overlay(&self, path: &Path) -> bool {
+        self.root.as_ref().is_some_and(|root| is_overlay_content(&root.path, path))
+    }
+
+    /// Whether every directory between the root and the node is expanded. The directories leading to the filter
+    /// count as expanded, so that filtering always shows what it narrowed the tree to
+    fn is_visible(&self, root: &FileNode, node: &FileNode) -> bool {
+        node.path.ancestors()
+            .skip(1)
+            .take_while(|ancestor| *ancestor != root.path)
+            .all(|ancestor| self.expanded.contains(ancestor) || self.filter.as_deref().is_some_and(|filter| filter.starts_with(ancestor)))
+    }
+
+    fn load_root(&self, path: PathBuf) -> Task<window::Message> {
+        let filesystem = self.app_context.filesystem_service_context().clone();
+
+        Task::perform(
+            async move {
+                let filesystem = filesystem.read().await;
+                build_pack_tree(&*filesystem, path).await
+            },
+            |result| Message::RootLoaded(result).into()
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RenameState {
+    path: PathBuf,
+    new_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamedFile {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileNode {
+    path: PathBuf,
+    kind: FileNodeKind,
+    origin: NodeOrigin,
+}
+
+/// Whether a node belongs to the base pack, or to one of the overlay directories listed in its `pack.mcmeta`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeOrigin {
+    Base,
+    Overlay(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileNodeKind {
+    File,
+    Directory { children: Vec<FileNode> },
+}
+
+impl FileNode {
+    pub fn name(&self) -> String {
+        self.path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    pub fn origin(&self) -> &NodeOrigin {
+        &self.origin
+    }
+
+    fn set_origin(&mut self, origin: NodeOrigin) {
+        if let FileNodeKind::Directory { children } = &mut self.kind {
+            for child in children {
+                child.set_origin(origin.clone());
+            }
+        }
+
+        self.origin = origin;
+    }
+
+    /// The node at the path, if it's this node or one of its descendants
+    pub fn find(&self, path: &Path) -> Option<&FileNode> {
+        if self.path == path {
+            return Some(self);
+        }
+
+        match &self.kind {
+            FileNodeKind::Directory { children } => children.iter()
+                .filter(|child| path.starts_with(&child.path))
+                .find_map(|child| child.find(path)),
+            FileNodeKind::File => None,
+        }
+    }
+
+    /// Depth-first traversal of this node and all of its descendants, in display order
+    pub fn iter_with_depth(&self) -> Vec<(usize, &FileNode)> {
+        let mut nodes = vec![(0, self)];
+
+        if let FileNodeKind::Directory { children } = &self.kind {
+            for child in children {
+                nodes.extend(child.iter_with_depth().into_iter().map(|(depth, node)| (depth + 1, node)));
+            }
+        }
+
+        nodes
+    }
+
+    /// Like [`FileNode::iter_with_depth`], keeping only the nodes at or below `filter` and the directories leading to it
+    pub fn iter_filtered(&self, filter: Option<&Path>) -> Vec<(usize, &FileNode)> {
+        let Some(filter) = filter else {
+            return self.iter_with_depth();
+        };
+
+        self.iter_with_depth().into_iter()
+            .filter(|(_, node)| node.path.starts_with(filter) || filter.starts_with(&node.path))
+            .collect()
+    }
+}
+
+/// A pack's file tree, and the overlays its `pack.mcmeta` lists
+#[derive(Debug, Clone)]
+pub struct PackTree {
+    root: FileNode,
+    overlays: Vec<String>,
+}
+
+/// Builds the tree for a pack, marking the overlay directories listed in its `pack.mcmeta`
+/// and listing them after the base pack's own files
+async fn build_pack_tree(filesystem: &(dyn FilesystemProvider + Send + Sync), root: PathBuf) -> Result<PackTree, FileTreeError> {
+    let overlays = overlay_directories(filesystem, &root).await;
+    let mut tree = build_node(filesystem, root.clone()).await?;
+
+    if let FileNodeKind::Directory { children } = &mut tree.kind {
+        for child in children.iter_mut() {
+            if let Some(overlay) = overlays.iter().find(|overlay| child.path == root.join(overlay)) {
+                child.set_origin(NodeOrigin::Overlay(overlay.clone()));
+            }
+        }
+
+        children.sort_by_key(|child| child.origin != NodeOrigin::Base);
+    }
+
+    Ok(PackTree { root: tree, overlays })
+}
+
+/// Whether a file is inside one of the directories an overlay can hold
+fn is_overlay_content(root: &Path, path: &Path) -> bool {
+    PackRelativePath::from_os_path(root, path)
+        .is_ok_and(|relative_path| relative_path.segments().count() > 1
+            && relative_path.segments().next().is_some_and(|category| OVERLAY_CATEGORIES.contains(&category)))
+}
+
+/// Directories of the overlays listed in a pack's `pack.mcmeta`.
+/// A missing or unreadable `pack.mcmeta` just means there are no overlays to show
+async fn overlay_directories(filesystem: &(dyn FilesystemProvider + Send + Sync), root: &Path) -> Vec<String> {
+    let Ok(contents) = filesystem.read_file(&root.join(PACK_INFO_FILE)).await else {
+        return Vec::new();
+    };
+    let Ok(pack_info) = serde_json::from_slice::<serde_json::Value>(&contents) else {
+        return Vec::new();
+    };
+
+    pack_info.pointer("/overlays/entries")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("directory")?.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Copies a base pack file to the same place inside an overlay, returning the copy's path.
+/// Only files in the directories an overlay can hold are copied
+pub(crate) async fn copy_to_overlay(
+    filesystem: &(dyn FilesystemProvider + Send + Sync),
+    root: &Path,
+    path: &Path,
+    overlay: &str,
+) -> Result<PathBuf, FileTreeError> {
+    if !is_overlay_content(root, path) {
+        return Err(FileTreeError::NotOverlayContent(path.to_path_buf()));
+    }
+
+    let relative_path = PackRelativePath::from_os_path(root, path).map_err(|_| FileTreeError::OutsideRoot(path.to_path_buf()))?;
+    let destination = relative_path.to_os_path(&root.join(overlay));
+
+    if filesystem.file_exists(&destination).await? {
+        return Err(FileTreeError::NameCollision(destination));
+    }
+
+    if let Some(parent) = destination.parent() {
+        filesystem.create_directory_recursive(parent).await?;
+    }
+    filesystem.copy_file(path, &destination).await?;
+
+    Ok(destination)
+}
+
+/// Builds the tree below `path`, listing directories before files and sorting each alphabetically.
+/// The tree is put together from the deepest entries up rather than by recursing, so its depth is only limited by
+/// [`FilesystemProvider::list_directory_recursive`]
+async fn build_node(filesystem: &(dyn FilesystemProvider + Send + Sync), path: PathBuf) -> Result<FileNode, FileTreeError> {
+    if !filesystem.is_directory(&path).await? {
+        return Ok(FileNode { path, kind: FileNodeKind::File, origin: NodeOrigin::Base });
+    }
+
+    // Entries are listed breadth first, so going backwards finishes every directory's children before the directory
+    let mut children_of: HashMap<PathBuf, Vec<FileNode>> = HashMap::new();
+    for entry in filesystem.list_directory_recursive(&path).await?.into_iter().rev() {
+        let kind = match filesystem.is_directory(&entry).await? {
+            true => FileNodeKind::Directory { children: sorted(children_of.remove(&entry).unwrap_or_default()) },
+            false => FileNodeKind::File,
+        };
+        let parent = entry.parent().map(Path::to_path_buf).unwrap_or_default();
+        children_of.entry(parent).or_default().push(FileNode { path: entry, kind, origin: NodeOrigin::Base });
+    }
+
+    let children = sorted(children_of.remove(&path).unwrap_or_default());
+    Ok(FileNode { path, kind: FileNodeKind::Directory { children }, origin: NodeOrigin::Base })
+}
+
+/// Sorts sibling nodes with directories before files, each by name
+fn sorted(mut nodes: Vec<FileNode>) -> Vec<FileNode> {
+    nodes.sort_by(|a, b| {
+        let a_is_file = matches!(a.kind, FileNodeKind::File);
+        let b_is_file = matches!(b.kind, FileNodeKind::File);
+        a_is_file.cmp(&b_is_file).then_with(|| a.path.cmp(&b.path))
+    });
+    nodes
+}
+
+/// Renames a file in place, refusing names which would move it to another directory or replace an existing file
+pub(crate) async fn rename_file(filesystem: &(dyn FilesystemProvider + Send + Sync), path: &Path, new_name: &str) -> Result<RenamedFile, FileTreeError> {
+    let new_name = new_name.trim();
+
+    if new_name.is_empty() || new_name == "." || new_name == ".." || new_name.contains(['/', '\\']) {
+        return Err(FileTreeError::InvalidName(new_name.to_string()));
+    }
+
+    let new_path = path.with_file_name(new_name);
+
+    if new_path == path {
+        return Ok(RenamedFile { from: path.to_path_buf(), to: new_path });
+    }
+
+    if filesystem.file_exists(&new_path).await? {
+        return Err(FileTreeError::NameCollision(new_path));
+    }
+
+    filesystem.move_file(path, &new_path).await?;
+
+    Ok(RenamedFile { from: path.to_path_buf(), to: new_path })
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum FileTreeError {
+    #[error("A file already exists at {0:?}!")]
+    NameCollision(PathBuf),
+    #[error("Invalid file name: {0:?}!")]
+    InvalidName(String),
+    #[error("{0:?} is not inside the open pack!")]
+    OutsideRoot(PathBuf),
+    #[error("{0:?} applies to the whole pack, so it can't be placed in an overlay!")]
+    NotOverlayContent(PathBuf),
+    #[error(transparent)]
+    Filesystem(Arc<FilesystemProviderError>),
+}
+
+impl From<FilesystemProviderError> for FileTreeError {
+    fn from(value: FilesystemProviderError) -> Self {
+        FileTreeError::Filesystem(Arc::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use crate::application::gui::text_editor::{self, TextEditor};
+    use crate::application::gui::text_editor::highlighter;
+    use crate::services::filesystem_service::{FilesystemService, MAX_DIRECTORY_DEPTH};
+
+    mod rename {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_rename_open_file() {
+            // Given a file on disk which is open in the editor
+            let temp_dir = tempdir().unwrap();
+            let path = temp_dir.path().join("old.mcfunction");
+            std::fs::write(&path, "say hi").unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            let (mut editor, _) = TextEditor::with_task(highlighter::Theme::SolarizedDark);
+            let _ = editor.update(text_editor::Message::FileOpened(Ok((path.clone(), Arc::new("say hi".to_string())))));
+
+            // When I rename it
+            let renamed = rename_file(&filesystem, &path, "new.mcfunction").await.unwrap();
+            editor.rename_open_file(&renamed.from, &renamed.to);
+
+            // Then it should be moved on disk
+            let new_path = temp_dir.path().join("new.mcfunction");
+            assert_eq!(renamed.to, new_path);
+            assert!(!path.exists());
+            assert_eq!(std::fs::read_to_string(&new_path).unwrap(), "say hi");
+
+            // And the editor should track the new path
+            assert_eq!(editor.file(), Some(new_path.as_path()));
+        }
+
+        #[tokio::test]
+        async fn test_rename_collision() {
+            // Given two files in the same directory
+            let temp_dir = tempdir().unwrap();
+            let path = temp_dir.path().join("first.json");
+            let other_path = temp_dir.path().join("second.json");
+            std::fs::write(&path, "first").unwrap();
+            std::fs::write(&other_path, "second").unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            // When I rename one to the name of the other
+            let result = rename_file(&filesystem, &path, "second.json").await;
+
+            // Then it should be refused, leaving both files untouched
+            assert!(matches!(result, Err(FileTreeError::NameCollision(collision)) if collision == other_path));
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "first");
+            assert_eq!(std::fs::read_to_string(&other_path).unwrap(), "second");
+        }
+
+        #[tokio::test]
+        async fn test_rename_invalid_name() {
+            // Given a file on disk
+            let temp_dir = tempdir().unwrap();
+            let path = temp_dir.path().join("file.json");
+            std::fs::write(&path, "").unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            for new_name in ["", "..", "../escape.json", "nested/file.json"] {
+                // When I rename it to a name which isn't a plain file name
+                let result = rename_file(&filesystem, &path, new_name).await;
+
+                // Then it should be refused
+                assert!(matches!(result, Err(FileTreeError::InvalidName(_))));
+                assert!(path.exists());
+            }
+        }
+    }
+
+    mod build {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_build_tree_order() {
+            // Given a directory containing files and a subdirectory
+            let temp_dir = tempdir().unwrap();
+            std::fs::write(temp_dir.path().join("b.json"), "").unwrap();
+            std::fs::write(temp_dir.path().join("a.json"), "").unwrap();
+            std::fs::create_dir(temp_dir.path().join("data")).unwrap();
+            std::fs::write(temp_dir.path().join("data").join("c.json"), "").unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            // When I build the tree
+            let root = build_node(&filesystem, temp_dir.path().to_path_buf()).await.unwrap();
+
+            // Then directories should be listed before files, each sorted by name
+            let names: Vec<_> = root.iter_with_depth().into_iter()
+                .skip(1)
+                .map(|(depth, node)| (depth, node.name()))
+                .collect();
+
+            assert_eq!(names, vec![
+                (1, "data".to_string()),
+                (2, "c.json".to_string()),
+                (1, "a.json".to_string()),
+                (1, "b.json".to_string()),
+            ]);
+        }
+
+        #[tokio::test]
+        async fn test_build_tree_too_deep() {
+            // Given directories nested deeper than the tree will show
+            let temp_dir = tempdir().unwrap();
+            let nested: PathBuf = (0..=MAX_DIRECTORY_DEPTH + 1).map(|_| "d").collect();
+            std::fs::create_dir_all(temp_dir.path().join(nested)).unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            // When I build the tree
+            let result = build_node(&filesystem, temp_dir.path().to_path_buf()).await;
+
+            // Then it should be refused instead of building without limit
+            assert!(matches!(result, Err(FileTreeError::Filesystem(error)) if matches!(*error, FilesystemProviderError::TooDeep(_))));
+        }
+
+        #[tokio::test]
+        async fn test_build_tree_with_overlay() {
+            // Given a pack with one overlay
+            let temp_dir = tempdir().unwrap();
+            std::fs::write(temp_dir.path().join("pack.mcmeta"), r#"{
+                "pack": {"pack_format": 71, "description": "Test Pack"},
+                "overlays": {"entries": [{"formats": [71, 80], "directory": "modern"}]}
+            }"#).unwrap();
+            std::fs::create_dir_all(temp_dir.path().join("data/test/function")).unwrap();
+            std::fs::write(temp_dir.path().join("data/test/function/load.mcfunction"), "").unwrap();
+            std::fs::create_dir_all(temp_dir.path().join("modern/data/test/function")).unwrap();
+            std::fs::write(temp_dir.path().join("modern/data/test/function/load.mcfunction"), "").unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            // When I build the tree
+            let PackTree { root, overlays } = build_pack_tree(&filesystem, temp_dir.path().to_path_buf()).await.unwrap();
+
+            // Then the base pack should come first, and everything in the overlay should be marked as from it
+            let overlay = NodeOrigin::Overlay("modern".to_string());
+            let origins: Vec<_> = root.iter_with_depth().into_iter()
+                .skip(1)
+                .map(|(depth, node)| (depth, node.name(), node.origin().clone()))
+                .collect();
+
+            assert_eq!(origins, vec![
+                (1, "data".to_string(), NodeOrigin::Base),
+                (2, "test".to_string(), NodeOrigin::Base),
+                (3, "function".to_string(), NodeOrigin::Base),
+                (4, "load.mcfunction".to_string(), NodeOrigin::Base),
+                (1, "pack.mcmeta".to_string(), NodeOrigin::Base),
+                (1, "modern".to_string(), overlay.clone()),
+                (2, "data".to_string(), overlay.clone()),
+                (3, "test".to_string(), overlay.clone()),
+                (4, "function".to_string(), overlay.clone()),
+                (5, "load.mcfunction".to_string(), overlay.clone()),
+            ]);
+            assert_eq!(overlays, vec!["modern".to_string()]);
+        }
+
+        #[tokio::test]
+        async fn test_build_tree_with_empty_overlay() {
+            // Given a pack listing an overlay which has no files yet
+            let temp_dir = tempdir().unwrap();
+            std::fs::write(temp_dir.path().join("pack.mcmeta"), r#"{
+                "pack": {"pack_format": 71, "description": "Test Pack"},
+                "overlays": {"entries": [{"formats": [71, 80], "directory": "modern"}]}
+            }"#).unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            // When I build the tree
+            let tree = build_pack_tree(&filesystem, temp_dir.path().to_path_buf()).await.unwrap();
+
+            // Then the overlay should still be offered, so that files can be copied into it
+            assert_eq!(tree.overlays, vec!["modern".to_string()]);
+        }
+
+        #[tokio::test]
+        async fn test_filter_tree() {
+            // Given a pack with files in two namespaces
+            let temp_dir = tempdir().unwrap();
+            std::fs::create_dir_all(temp_dir.path().join("data/first/recipe")).unwrap();
+            std::fs::create_dir_all(temp_dir.path().join("data/second/recipe")).unwrap();
+            std::fs::write(temp_dir.path().join("data/first/recipe/gem.json"), "").unwrap();
+            std::fs::write(temp_dir.path().join("data/second/recipe/gem.json"), "").unwrap();
+            std::fs::write(temp_dir.path().join("pack.mcmeta"), "").unwrap();
+
+            let filesystem = FilesystemService::new();
+            let root = build_node(&filesystem, temp_dir.path().to_path_buf()).await.unwrap();
+
+            // When I filter it to one namespace
+            let filter = temp_dir.path().join("data/first");
+            let names: Vec<_> = root.iter_filtered(Some(&filter)).into_iter()
+                .skip(1)
+                .map(|(depth, node)| (depth, node.name()))
+                .collect();
+
+            // Then only that namespace and the directories leading to it should be left
+            assert_eq!(names, vec![
+                (1, "data".to_string()),
+                (2, "first".to_string()),
+                (3, "recipe".to_string()),
+                (4, "gem.json".to_string()),
+            ]);
+        }
+
+        #[tokio::test]
+        async fn test_copy_to_overlay() {
+            // Given a base pack file
+            let temp_dir = tempdir().unwrap();
+            let path = temp_dir.path().join("data/test/function/load.mcfunction");
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "say base").unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            // When I copy it into an overlay which doesn't have it yet
+            let copy = copy_to_overlay(&filesystem, temp_dir.path(), &path, "modern").await.unwrap();
+
+            // Then it should be placed at the same path inside the overlay
+            assert_eq!(copy, temp_dir.path().join("modern/data/test/function/load.mcfunction"));
+            assert_eq!(std::fs::read_to_string(&copy).unwrap(), "say base");
+
+            // And copying it again should be refused rather than overwrite the overlay's version
+            let result = copy_to_overlay(&filesystem, temp_dir.path(), &path, "modern").await;
+            assert!(matches!(result, Err(FileTreeError::NameCollision(_))));
+        }
+
+        #[rstest::rstest]
+        #[case::pack_info("pack.mcmeta")]
+        #[case::pack_icon("pack.png")]
+        #[case::category_itself("data")]
+        #[tokio::test]
+        async fn test_copy_root_file_to_overlay(#[case] file: &str) {
+            // Given a file at the root of the pack
+            let temp_dir = tempdir().unwrap();
+            let path = temp_dir.path().join(file);
+            std::fs::write(&path, "").unwrap();
+
+            let filesystem = FilesystemService::new();
+
+            // When I copy it into an overlay
+            let result = copy_to_overlay(&filesystem, temp_dir.path(), &path, "modern").await;
+
+            // Then it should be refused, since it applies to the whole pack
+            assert!(matches!(result, Err(FileTreeError::NotOverlayContent(_))));
+            assert!(!temp_dir.path().join("modern").exists());
+        }
+    }
+}
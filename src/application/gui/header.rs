@@ -32,7 +32,13 @@ impl Header {
             translations: HeaderTranslations::default(),
         };
         
-        (self_, Task::perform(Self::translate(app_context.clone()), |translation| Message::TranslationsUpdated(translation).into()))
+        let translations = self_.refresh_translations();
+        (self_, translations)
+    }
+
+    /// Translates the header's text again, e.g. after the language changes
+    pub fn refresh_translations(&self) -> Task<window::Message> {
+        Task::perform(Self::translate(self.app_context.clone()), |translation| Message::TranslationsUpdated(translation).into())
     }
 
     pub fn update(&mut self, message: Message) -> Task<window::Message> {
@@ -65,13 +71,23 @@ impl Header {
     async fn translate(app_context: Arc<AppContext>) -> HeaderTranslations {
         let file_menu_translations = {
             let title = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::Title);
+            let new = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::New);
+            let open = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::Open);
+            let open_read_only = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::OpenReadOnly);
+            let close = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::Close);
             let import = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::Import);
             let export = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::Export);
+            let reveal = app_context.translation_service_context().read().await.translate(&FileMenuTranslationKeys::Reveal);
             
             FileMenuTranslations {
                 title,
+                new,
+                open,
+                open_read_only,
+                close,
                 import,
                 export,
+                reveal,
             }
         };
         
@@ -84,8 +100,13 @@ impl Header {
         let file_translations = &self.translations.file_menu;
         
         Menu::new(menu_items!(
+            (Button::new(file_translations.new.as_str()).width(Length::Fill).style(iced::widget::button::secondary).on_press(window::Message::OpenNewProject))
+            (Button::new(file_translations.open.as_str()).width(Length::Fill).style(iced::widget::button::secondary).on_press(window::Message::OpenProject { read_only: false }))
+            (Button::new(file_translations.open_read_only.as_str()).width(Length::Fill).style(iced::widget::button::secondary).on_press(window::Message::OpenProject { read_only: true }))
+            (Button::new(file_translations.close.as_str()).width(Length::Fill).style(iced::widget::button::secondary).on_press(window::Message::CloseShownProject))
             (Button::new(file_translations.import.as_str()).width(Length::Fill).style(iced::widget::button::secondary))
             (Button::new(file_translations.export.as_str()).width(Length::Fill).style(iced::widget::button::secondary))
+            (Button::new(file_translations.reveal.as_str()).width(Length::Fill).style(iced::widget::button::secondary).on_press(window::Message::RevealInFileManager))
         ))
         .max_width(MENU_WIDTH)
         .offset(MENU_OFFSET)
@@ -97,10 +118,20 @@ impl Header {
 pub enum FileMenuTranslationKeys {
     #[translation(en_us = "File")]
     Title,
+    #[translation(en_us = "New Project")]
+    New,
+    #[translation(en_us = "Open Project")]
+    Open,
+    #[translation(en_us = "Open Project Read-Only")]
+    OpenReadOnly,
+    #[translation(en_us = "Close Project")]
+    Close,
     #[translation(en_us = "Import Project")]
     Import,
     #[translation(en_us = "Export Project")]
     Export,
+    #[translation(en_us = "Reveal in File Manager")]
+    Reveal,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -111,6 +142,11 @@ pub struct HeaderTranslations {
 #[derive(Debug, Clone, Default)]
 pub struct FileMenuTranslations {
     pub title: String,
+    pub new: String,
+    pub open: String,
+    pub open_read_only: String,
+    pub close: String,
     pub import: String,
     pub export: String,
+    pub reveal: String,
 }
\ No newline at end of file
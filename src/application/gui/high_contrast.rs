@@ -0,0 +1,152 @@
+use std::str::FromStr;
+use iced::theme::Palette;
+use syntect::highlighting;
+
+/// Name of the high-contrast highlighting theme, as registered in the highlighter's theme set
+pub const THEME_KEY: &str = "Gaea High Contrast";
+
+/// Minimum contrast ratio required by WCAG 2.x level AAA for normal text
+pub const MIN_CONTRAST_RATIO: f64 = 7.0;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PaletteColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl PaletteColor {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    pub fn to_iced(self) -> iced::Color {
+        iced::Color::from_rgb8(self.r, self.g, self.b)
+    }
+
+    pub fn to_syntect(self) -> highlighting::Color {
+        highlighting::Color { r: self.r, g: self.g, b: self.b, a: 0xFF }
+    }
+
+    /// Relative luminance as defined by WCAG 2.x
+    pub fn relative_luminance(self) -> f64 {
+        fn linearize(channel: u8) -> f64 {
+            let channel = channel as f64 / 255.0;
+
+            if channel <= 0.03928 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        0.2126 * linearize(self.r) + 0.7152 * linearize(self.g) + 0.0722 * linearize(self.b)
+    }
+
+    /// Contrast ratio between two colors as defined by WCAG 2.x, from 1:1 to 21:1
+    pub fn contrast_ratio(self, other: PaletteColor) -> f64 {
+        let (lighter, darker) = {
+            let a = self.relative_luminance();
+            let b = other.relative_luminance();
+            (a.max(b), a.min(b))
+        };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
+}
+
+pub const BACKGROUND: PaletteColor = PaletteColor::new(0x00, 0x00, 0x00);
+pub const FOREGROUND: PaletteColor = PaletteColor::new(0xFF, 0xFF, 0xFF);
+pub const ACCENT: PaletteColor = PaletteColor::new(0xFF, 0xD7, 0x00);
+pub const SUCCESS: PaletteColor = PaletteColor::new(0x7C, 0xFC, 0x00);
+pub const DANGER: PaletteColor = PaletteColor::new(0xFF, 0x70, 0x70);
+
+/// Token colors, paired with the scope selectors they apply to
+pub const TOKEN_COLORS: &[(&str, PaletteColor)] = &[
+    ("comment", PaletteColor::new(0xC0, 0xC0, 0xC0)),
+    ("keyword, storage", ACCENT),
+    ("string", SUCCESS),
+    ("constant", PaletteColor::new(0x00, 0xFF, 0xFF)),
+    ("entity.name, support.function", PaletteColor::new(0xFF, 0x80, 0xFF)),
+    ("variable", PaletteColor::new(0x87, 0xCE, 0xFA)),
+    ("invalid", DANGER),
+];
+
+/// Application palette used alongside the high-contrast highlighting theme
+pub fn iced_theme() -> iced::Theme {
+    iced::Theme::custom(THEME_KEY.to_string(), Palette {
+        background: BACKGROUND.to_iced(),
+        text: FOREGROUND.to_iced(),
+        primary: ACCENT.to_iced(),
+        success: SUCCESS.to_iced(),
+        danger: DANGER.to_iced(),
+    })
+}
+
+pub fn highlighting_theme() -> highlighting::Theme {
+    let scopes = TOKEN_COLORS.iter()
+        .map(|(scope, color)| highlighting::ThemeItem {
+            scope: highlighting::ScopeSelectors::from_str(scope).expect("Invalid scope selector in high contrast theme"),
+            style: highlighting::StyleModifier {
+                foreground: Some(color.to_syntect()),
+                background: None,
+                font_style: None,
+            },
+        })
+        .collect();
+
+    highlighting::Theme {
+        name: Some(THEME_KEY.to_string()),
+        author: None,
+        settings: highlighting::ThemeSettings {
+            foreground: Some(FOREGROUND.to_syntect()),
+            background: Some(BACKGROUND.to_syntect()),
+            caret: Some(ACCENT.to_syntect()),
+            ..Default::default()
+        },
+        scopes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio_bounds() {
+        // Given black and white
+        // When I compute their contrast ratio
+        // Then it should be the maximum of 21:1, regardless of order
+        assert!((BACKGROUND.contrast_ratio(FOREGROUND) - 21.0).abs() < 0.01);
+        assert!((FOREGROUND.contrast_ratio(BACKGROUND) - 21.0).abs() < 0.01);
+
+        // And a color against itself should be 1:1
+        assert!((ACCENT.contrast_ratio(ACCENT) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_highlighting_theme_uses_palette() {
+        // When I build the highlighting theme
+        let theme = highlighting_theme();
+
+        // Then every token color should be mapped to a scope
+        assert_eq!(theme.scopes.len(), TOKEN_COLORS.len());
+        assert_eq!(theme.settings.background, Some(BACKGROUND.to_syntect()));
+    }
+
+    #[test]
+    fn test_palette_meets_wcag_contrast() {
+        // Given the high contrast palette
+        let colors = TOKEN_COLORS.iter()
+            .map(|(scope, color)| (*scope, *color))
+            .chain([("foreground", FOREGROUND), ("accent", ACCENT), ("success", SUCCESS), ("danger", DANGER)]);
+
+        for (name, color) in colors {
+            // When I compute the contrast against the background
+            let contrast_ratio = color.contrast_ratio(BACKGROUND);
+
+            // Then it should meet WCAG AAA
+            assert!(contrast_ratio >= MIN_CONTRAST_RATIO, "Contrast for {} is only {:.2}:1", name, contrast_ratio);
+        }
+    }
+}
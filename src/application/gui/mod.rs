@@ -1,5 +1,31 @@
 pub(crate) mod window;
 mod text_editor;
+mod file_tree;
 mod header;
+mod high_contrast;
+mod notifications;
 mod action_bar;
-mod widgets;
\ No newline at end of file
+mod close_prompt;
+mod new_project;
+mod event_log;
+mod diagnostics;
+mod pack_icon;
+mod tabs;
+mod widgets;
+
+/// English defaults for every translation key used by the GUI
+#[cfg(feature = "translation-export")]
+pub(crate) fn english_defaults() -> Vec<(&'static str, &'static str)> {
+    use crate::services::translation_service::english_defaults;
+
+    [
+        english_defaults::<header::FileMenuTranslationKeys>(),
+        english_defaults::<text_editor::EditorTranslationKeys>(),
+        english_defaults::<notifications::NotificationTranslationKeys>(),
+        english_defaults::<close_prompt::ClosePromptTranslationKeys>(),
+        english_defaults::<event_log::EventLogTranslationKeys>(),
+        english_defaults::<new_project::NewProjectTranslationKeys>(),
+        english_defaults::<diagnostics::DiagnosticsTranslationKeys>(),
+        english_defaults::<pack_icon::PackIconTranslationKeys>(),
+    ].concat()
+}
\ No newline at end of file
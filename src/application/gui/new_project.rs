@@ -0,0 +1,541 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
+use iced::{Center, Element, Length};
+use iced::widget::{button, horizontal_space, pick_list, row, text, text_input, Column};
+use crate::application::app_context::{ProjectServiceContext, TranslationServiceContext};
+use crate::application::gui::window;
+use crate::data::domain::install_context::InstallContext;
+use crate::data::domain::pack_info::PackDescription;
+use crate::data::domain::project::{ProjectID, ProjectSettings, ProjectType};
+use crate::data::domain::project_template::{ProjectTemplate, PROJECT_TEMPLATES};
+use crate::data::domain::versions::{self, SelectableVersion};
+use crate::repositories::project_repo::{ProjectCreationError, ProjectRepoError};
+use crate::services::dialog_service::DialogProvider;
+use crate::services::project_service::{ProjectServiceError, ProjectServiceProvider};
+use crate::services::translation_service::TranslationKey;
+
+/// Wide enough for the longest label, so the fields line up
+const LABEL_WIDTH: f32 = 200.0;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ProjectKind {
+    #[default]
+    DataPack,
+    ResourcePack,
+    Combined,
+}
+
+impl ProjectKind {
+    pub const ALL: [ProjectKind; 3] = [ProjectKind::DataPack, ProjectKind::ResourcePack, ProjectKind::Combined];
+
+    fn project_type(self) -> ProjectType {
+        match self {
+            ProjectKind::DataPack => ProjectType::DataPack,
+            ProjectKind::ResourcePack => ProjectType::ResourcePack,
+            ProjectKind::Combined => ProjectType::Combined,
+        }
+    }
+}
+
+impl From<ProjectType> for ProjectKind {
+    fn from(project_type: ProjectType) -> Self {
+        match project_type {
+            ProjectType::DataPack => ProjectKind::DataPack,
+            ProjectType::ResourcePack => ProjectKind::ResourcePack,
+            ProjectType::Combined => ProjectKind::Combined,
+        }
+    }
+}
+
+impl Display for ProjectKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectKind::DataPack => write!(f, "Data pack"),
+            ProjectKind::ResourcePack => write!(f, "Resource pack"),
+            ProjectKind::Combined => write!(f, "Data and resource pack"),
+        }
+    }
+}
+
+/// Files to start a project with, shown by the template's name
+#[derive(Debug, Clone, Copy, Default)]
+pub enum TemplateChoice {
+    #[default]
+    Empty,
+    Template(&'static ProjectTemplate),
+}
+
+impl TemplateChoice {
+    /// The choices with files for a type of project
+    fn for_kind(kind: ProjectKind) -> Vec<TemplateChoice> {
+        std::iter::once(TemplateChoice::Empty)
+            .chain(PROJECT_TEMPLATES.iter()
+                .filter(|template| template.fits(kind.project_type()))
+                .map(TemplateChoice::Template))
+            .collect()
+    }
+
+    fn fits(self, kind: ProjectKind) -> bool {
+        match self {
+            TemplateChoice::Empty => true,
+            TemplateChoice::Template(template) => template.fits(kind.project_type()),
+        }
+    }
+}
+
+impl PartialEq for TemplateChoice {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TemplateChoice::Empty, TemplateChoice::Empty) => true,
+            (TemplateChoice::Template(template), TemplateChoice::Template(other)) => template.id() == other.id(),
+            _ => false,
+        }
+    }
+}
+
+impl Display for TemplateChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateChoice::Empty => write!(f, "None"),
+            TemplateChoice::Template(template) => write!(f, "{}", template.name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    NameChanged(String),
+    KindSelected(ProjectKind),
+    TemplateSelected(TemplateChoice),
+    VersionSelected(SelectableVersion),
+    DescriptionChanged(String),
+    /// Only used by combined projects, whose resource pack has its own description
+    ResourceDescriptionChanged(String),
+    PathChanged(String),
+}
+
+/// Fields of the dialog for creating a project, turned into [`ProjectSettings`] once they are submitted
+#[derive(Debug, Clone)]
+pub struct NewProjectDialog {
+    name: String,
+    kind: ProjectKind,
+    /// Always one with files for the kind of project
+    template: TemplateChoice,
+    version: SelectableVersion,
+    description: String,
+    resource_description: String,
+    /// Where the project will be saved. Left empty, one is chosen when the project is first saved
+    path: String,
+    /// Why the project couldn't be created, shown until the fields are changed
+    error: Option<String>,
+}
+
+impl Default for NewProjectDialog {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            kind: ProjectKind::default(),
+            template: TemplateChoice::default(),
+            version: versions::selectable_versions()[0],
+            description: String::new(),
+            resource_description: String::new(),
+            path: String::new(),
+            error: None,
+        }
+    }
+}
+
+impl NewProjectDialog {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::NameChanged(name) => self.name = name,
+            Message::KindSelected(kind) => self.set_kind(kind),
+            Message::TemplateSelected(template) => self.template = template,
+            Message::VersionSelected(version) => self.version = version,
+            Message::DescriptionChanged(description) => self.description = description,
+            Message::ResourceDescriptionChanged(description) => self.resource_description = description,
+            Message::PathChanged(path) => self.path = path,
+        }
+
+        self.error = None;
+    }
+
+    /// Picks the type of project the game loads from where the path is, e.g. a data pack inside a world's `datapacks` folder.
+    /// Ignored if the path has changed since it was checked
+    pub fn install_context_detected(&mut self, path: &str, context: &InstallContext) {
+        if self.path == path {
+            self.set_kind(context.project_type().into());
+        }
+    }
+
+    /// Changes the kind of project, dropping the template if it has no files for the new kind
+    fn set_kind(&mut self, kind: ProjectKind) {
+        self.kind = kind;
+
+        if !self.template.fits(kind) {
+            self.template = TemplateChoice::Empty;
+        }
+    }
+
+    /// The template to start the project with, if one was picked
+    pub fn template_id(&self) -> Option<&'static str> {
+        match self.template {
+            TemplateChoice::Empty => None,
+            TemplateChoice::Template(template) => Some(template.id().as_str()),
+        }
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Checks the fields and builds the settings to create the project with
+    pub fn settings(&self) -> Result<ProjectSettings, NewProjectError> {
+        let name = self.name.trim().to_string();
+
+        if name.is_empty() {
+            return Err(NewProjectError::EmptyName);
+        }
+
+        // The name becomes the project's folder and zip names, so it has to be usable as a file name
+        if !sanitize_filename::is_sanitized(&name) {
+            return Err(NewProjectError::InvalidName(name));
+        }
+
+        let path = match self.path.trim() {
+            "" => None,
+            path => {
+                let path = PathBuf::from(path);
+
+                if !path.is_absolute() {
+                    return Err(NewProjectError::RelativePath(path));
+                }
+
+                Some(path)
+            }
+        };
+
+        let description = PackDescription::String(self.description.clone());
+        let project_version = self.version.project_version();
+
+        Ok(match self.kind {
+            ProjectKind::DataPack => ProjectSettings::DataPack { name, description, path, project_version },
+            ProjectKind::ResourcePack => ProjectSettings::ResourcePack { name, description, path, project_version },
+            ProjectKind::Combined => ProjectSettings::Combined {
+                name,
+                data_description: description,
+                resource_description: PackDescription::String(self.resource_description.clone()),
+                path,
+                project_version,
+            },
+        })
+    }
+
+    pub fn view<'a>(&'a self, translations: &'a NewProjectTranslations) -> Element<'a, window::Message> {
+        let description_label = match self.kind {
+            ProjectKind::Combined => &translations.data_description,
+            _ => &translations.description,
+        };
+
+        let kind = pick_list(ProjectKind::ALL, Some(self.kind), |kind| window::Message::NewProject(Message::KindSelected(kind)));
+        let template = pick_list(TemplateChoice::for_kind(self.kind), Some(self.template), |template| window::Message::NewProject(Message::TemplateSelected(template)));
+        let version = pick_list(versions::selectable_versions(), Some(self.version), |version| window::Message::NewProject(Message::VersionSelected(version)));
+
+        Column::new()
+            .push(text(translations.title.as_str()).size(18))
+            .push(field(&translations.name, text_input("", &self.name)
+                .on_input(|name| window::Message::NewProject(Message::NameChanged(name)))))
+            .push(field(&translations.project_type, kind))
+            .push(field(&translations.template, template))
+            .push(field(&translations.version, version))
+            .push(field(description_label, text_input("", &self.description)
+                .on_input(|description| window::Message::NewProject(Message::DescriptionChanged(description)))))
+            .push_maybe((self.kind == ProjectKind::Combined).then(|| field(&translations.resource_description, text_input("", &self.resource_description)
+                .on_input(|description| window::Message::NewProject(Message::ResourceDescriptionChanged(description))))))
+            .push(field(&translations.path, text_input("", &self.path)
+                .on_input(|path| window::Message::NewProject(Message::PathChanged(path)))))
+            .push_maybe(self.error.as_deref().map(|error| text(error.to_string()).style(text::danger)))
+            .push(row![
+                horizontal_space(),
+                button(text(translations.create.as_str())).on_press(window::Message::CreateProject),
+                button(text(translations.cancel.as_str())).style(button::secondary).on_press(window::Message::CancelNewProject),
+            ].spacing(5))
+            .spacing(5)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+/// A labelled row of the dialog
+fn field<'a>(label: &'a str, input: impl Into<Element<'a, window::Message>>) -> Element<'a, window::Message> {
+    row![text(label).width(Length::Fixed(LABEL_WIDTH)), input.into()]
+        .spacing(10)
+        .align_y(Center)
+        .into()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum NewProjectError {
+    #[error("The project needs a name")]
+    EmptyName,
+    #[error("{0} can't be used as a file name")]
+    InvalidName(String),
+    #[error("{} isn't a full path", .0.display())]
+    RelativePath(PathBuf),
+}
+
+/// Creates the project, starting it with the template's files if one is given, and asking before replacing anything
+/// already at its path. Declining keeps the error, so the dialog stays open
+pub async fn create_project(
+    project_service: ProjectServiceContext,
+    dialogs: Arc<dyn DialogProvider>,
+    settings: ProjectSettings,
+    template_id: Option<&'static str>,
+    translations: NewProjectTranslations,
+) -> Result<ProjectID, String> {
+    let project_service = project_service.read().await;
+
+    let result = match create(&*project_service, settings.clone(), template_id, false).await {
+        Err(error @ ProjectServiceError::RepoError(ProjectRepoError::Create(ProjectCreationError::FileAlreadyExists))) => {
+            if !dialogs.confirm(&translations.replace_title, &translations.replace_existing).await {
+                return Err(error.to_string());
+            }
+
+            create(&*project_service, settings, template_id, true).await
+        }
+        result => result,
+    };
+
+    result.map_err(|error| error.to_string())
+}
+
+async fn create(
+    project_service: &(dyn ProjectServiceProvider + Send + Sync),
+    settings: ProjectSettings,
+    template_id: Option<&str>,
+    overwrite_existing: bool,
+) -> Result<ProjectID, ProjectServiceError> {
+    match template_id {
+        Some(template_id) => project_service.create_from_template(template_id, settings, overwrite_existing).await,
+        None => project_service.create_project(settings, overwrite_existing).await,
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
+pub enum NewProjectTranslationKeys {
+    #[translation(en_us = "New project")]
+    Title,
+    #[translation(en_us = "Name")]
+    Name,
+    #[translation(en_us = "Type")]
+    ProjectType,
+    #[translation(en_us = "Template")]
+    Template,
+    #[translation(en_us = "Minecraft version")]
+    Version,
+    #[translation(en_us = "Description")]
+    Description,
+    #[translation(en_us = "Data pack description")]
+    DataDescription,
+    #[translation(en_us = "Resource pack description")]
+    ResourceDescription,
+    #[translation(en_us = "Folder (optional)")]
+    Path,
+    #[translation(en_us = "Create")]
+    Create,
+    #[translation(en_us = "Cancel")]
+    Cancel,
+    #[translation(en_us = "Project created")]
+    Created,
+    #[translation(en_us = "Replace project?")]
+    ReplaceTitle,
+    #[translation(en_us = "Something is already saved at this folder. Replace it with the new project?")]
+    ReplaceExisting,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewProjectTranslations {
+    pub title: String,
+    pub name: String,
+    pub project_type: String,
+    pub template: String,
+    pub version: String,
+    pub description: String,
+    pub data_description: String,
+    pub resource_description: String,
+    pub path: String,
+    pub create: String,
+    pub cancel: String,
+    pub created: String,
+    pub replace_title: String,
+    pub replace_existing: String,
+}
+
+impl NewProjectTranslations {
+    pub(crate) async fn translate(translation_service: TranslationServiceContext) -> Self {
+        let translation_service = translation_service.read().await;
+
+        Self {
+            title: translation_service.translate(&NewProjectTranslationKeys::Title),
+            name: translation_service.translate(&NewProjectTranslationKeys::Name),
+            project_type: translation_service.translate(&NewProjectTranslationKeys::ProjectType),
+            template: translation_service.translate(&NewProjectTranslationKeys::Template),
+            version: translation_service.translate(&NewProjectTranslationKeys::Version),
+            description: translation_service.translate(&NewProjectTranslationKeys::Description),
+            data_description: translation_service.translate(&NewProjectTranslationKeys::DataDescription),
+            resource_description: translation_service.translate(&NewProjectTranslationKeys::ResourceDescription),
+            path: translation_service.translate(&NewProjectTranslationKeys::Path),
+            create: translation_service.translate(&NewProjectTranslationKeys::Create),
+            cancel: translation_service.translate(&NewProjectTranslationKeys::Cancel),
+            created: translation_service.translate(&NewProjectTranslationKeys::Created),
+            replace_title: translation_service.translate(&NewProjectTranslationKeys::ReplaceTitle),
+            replace_existing: translation_service.translate(&NewProjectTranslationKeys::ReplaceExisting),
+        }
+    }
+}
+
+impl Default for NewProjectTranslations {
+    /// Falls back to the English copy until the translations have loaded
+    fn default() -> Self {
+        Self {
+            title: NewProjectTranslationKeys::Title.english_text().to_string(),
+            name: NewProjectTranslationKeys::Name.english_text().to_string(),
+            project_type: NewProjectTranslationKeys::ProjectType.english_text().to_string(),
+            template: NewProjectTranslationKeys::Template.english_text().to_string(),
+            version: NewProjectTranslationKeys::Version.english_text().to_string(),
+            description: NewProjectTranslationKeys::Description.english_text().to_string(),
+            data_description: NewProjectTranslationKeys::DataDescription.english_text().to_string(),
+            resource_description: NewProjectTranslationKeys::ResourceDescription.english_text().to_string(),
+            path: NewProjectTranslationKeys::Path.english_text().to_string(),
+            create: NewProjectTranslationKeys::Create.english_text().to_string(),
+            cancel: NewProjectTranslationKeys::Cancel.english_text().to_string(),
+            created: NewProjectTranslationKeys::Created.english_text().to_string(),
+            replace_title: NewProjectTranslationKeys::ReplaceTitle.english_text().to_string(),
+            replace_existing: NewProjectTranslationKeys::ReplaceExisting.english_text().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+    use crate::data::domain::project::ProjectVersion;
+
+    fn absolute_path() -> PathBuf {
+        std::env::temp_dir().join("packs")
+    }
+
+    fn filled_dialog(kind: ProjectKind) -> NewProjectDialog {
+        let mut dialog = NewProjectDialog::default();
+
+        for message in [
+            Message::NameChanged(" My Pack ".to_string()),
+            Message::KindSelected(kind),
+            Message::DescriptionChanged("Adds gems".to_string()),
+            Message::ResourceDescriptionChanged("Gem textures".to_string()),
+            Message::PathChanged(absolute_path().display().to_string()),
+        ] {
+            dialog.update(message);
+        }
+
+        dialog
+    }
+
+    #[rstest]
+    #[case::data_pack(ProjectKind::DataPack, ProjectSettings::DataPack {
+        name: "My Pack".to_string(),
+        description: PackDescription::String("Adds gems".to_string()),
+        path: Some(absolute_path()),
+        project_version: ProjectVersion { version: versions::latest() },
+    })]
+    #[case::resource_pack(ProjectKind::ResourcePack, ProjectSettings::ResourcePack {
+        name: "My Pack".to_string(),
+        description: PackDescription::String("Adds gems".to_string()),
+        path: Some(absolute_path()),
+        project_version: ProjectVersion { version: versions::latest() },
+    })]
+    #[case::combined(ProjectKind::Combined, ProjectSettings::Combined {
+        name: "My Pack".to_string(),
+        data_description: PackDescription::String("Adds gems".to_string()),
+        resource_description: PackDescription::String("Gem textures".to_string()),
+        path: Some(absolute_path()),
+        project_version: ProjectVersion { version: versions::latest() },
+    })]
+    fn test_settings_from_fields(#[case] kind: ProjectKind, #[case] expected: ProjectSettings) {
+        // Given a dialog filled in for a type of project
+        let dialog = filled_dialog(kind);
+
+        // When I build its settings
+        let settings = dialog.settings();
+
+        // Then they should be for that type, with the name trimmed and the newest version picked by default
+        assert_eq!(settings, Ok(expected));
+    }
+
+    #[test]
+    fn test_template_follows_kind() {
+        // Given a data pack dialog starting from the data pack template
+        let mut dialog = filled_dialog(ProjectKind::DataPack);
+        let template = TemplateChoice::for_kind(ProjectKind::DataPack)[1];
+        dialog.update(Message::TemplateSelected(template));
+        assert_eq!(dialog.template_id(), Some("data_pack"));
+
+        // When it becomes a combined project, which has a data pack
+        dialog.update(Message::KindSelected(ProjectKind::Combined));
+
+        // Then the template should be kept
+        assert_eq!(dialog.template_id(), Some("data_pack"));
+
+        // When it becomes a resource pack
+        dialog.update(Message::KindSelected(ProjectKind::ResourcePack));
+
+        // Then the template should be dropped, as it has no files for one
+        assert_eq!(dialog.template_id(), None);
+    }
+
+    #[test]
+    fn test_install_context_suggests_kind() {
+        // Given a dialog whose path is inside a resource packs folder
+        let path = absolute_path().join("resourcepacks").join("My Pack").display().to_string();
+        let mut dialog = filled_dialog(ProjectKind::DataPack);
+        dialog.update(Message::PathChanged(path.clone()));
+
+        // When the installation is detected for an older path
+        let context = InstallContext::ResourcePacks { minecraft_dir: absolute_path() };
+        dialog.install_context_detected("/somewhere/else", &context);
+
+        // Then the kind should be left alone
+        assert!(matches!(dialog.settings(), Ok(ProjectSettings::DataPack { .. })));
+
+        // When it is detected for the current path
+        dialog.install_context_detected(&path, &context);
+
+        // Then it should become a resource pack
+        assert!(matches!(dialog.settings(), Ok(ProjectSettings::ResourcePack { .. })));
+    }
+
+    #[test]
+    fn test_invalid_fields_are_refused() {
+        // Given dialogs with a missing name, a name which can't be a file name, and a relative path
+        let mut empty_name = filled_dialog(ProjectKind::DataPack);
+        empty_name.update(Message::NameChanged("  ".to_string()));
+
+        let mut invalid_name = filled_dialog(ProjectKind::DataPack);
+        invalid_name.update(Message::NameChanged("My/Pack".to_string()));
+
+        let mut relative_path = filled_dialog(ProjectKind::DataPack);
+        relative_path.update(Message::PathChanged("packs/mine".to_string()));
+
+        // When I build their settings
+        // Then each should be refused, saying why
+        assert_eq!(empty_name.settings(), Err(NewProjectError::EmptyName));
+        assert_eq!(invalid_name.settings(), Err(NewProjectError::InvalidName("My/Pack".to_string())));
+        assert_eq!(relative_path.settings(), Err(NewProjectError::RelativePath(PathBuf::from("packs/mine"))));
+
+        // And leaving the path empty should be fine, as one can be chosen when the project is first saved
+        relative_path.update(Message::PathChanged(String::new()));
+        assert!(matches!(relative_path.settings(), Ok(ProjectSettings::DataPack { path: None, .. })));
+    }
+}
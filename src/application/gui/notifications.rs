@@ -0,0 +1,258 @@
+use std::time::{Duration, Instant};
+use iced::{Element, Length};
+use iced::widget::{button, horizontal_space, row, text, Column};
+use crate::application::app_context::TranslationServiceContext;
+use crate::application::gui::window;
+use crate::services::translation_service::TranslationKey;
+
+/// How long a notification stays on screen unless it is closed sooner
+pub const NOTIFICATION_LIFETIME: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NotificationLevel {
+    Info,
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub message: String,
+}
+
+impl Notification {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self { level: NotificationLevel::Info, message: message.into() }
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self { level: NotificationLevel::Success, message: message.into() }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { level: NotificationLevel::Error, message: message.into() }
+    }
+}
+
+pub type NotificationID = u64;
+
+#[derive(Debug, Clone)]
+struct QueuedNotification {
+    id: NotificationID,
+    notification: Notification,
+    expires_at: Instant,
+}
+
+/// Notifications currently on screen, oldest first.
+/// Times are passed in rather than read from the clock so that expiry can be driven by a subscription
+#[derive(Debug)]
+pub struct NotificationQueue {
+    lifetime: Duration,
+    next_id: NotificationID,
+    notifications: Vec<QueuedNotification>,
+}
+
+impl NotificationQueue {
+    pub fn new(lifetime: Duration) -> Self {
+        Self {
+            lifetime,
+            next_id: 0,
+            notifications: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, notification: Notification, now: Instant) -> NotificationID {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.notifications.push(QueuedNotification {
+            id,
+            notification,
+            expires_at: now + self.lifetime,
+        });
+
+        id
+    }
+
+    /// Closes a notification early, returning whether it was still shown
+    pub fn dismiss(&mut self, id: NotificationID) -> bool {
+        let count = self.notifications.len();
+        self.notifications.retain(|queued| queued.id != id);
+        self.notifications.len() != count
+    }
+
+    /// Removes every notification which has been shown for its full lifetime
+    pub fn expire(&mut self, now: Instant) {
+        self.notifications.retain(|queued| queued.expires_at > now);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notifications.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (NotificationID, &Notification)> {
+        self.notifications.iter().map(|queued| (queued.id, &queued.notification))
+    }
+
+    pub fn view(&self) -> Element<window::Message> {
+        let mut column = Column::new().spacing(2);
+
+        for (id, notification) in self.iter() {
+            let message = text(notification.message.as_str()).style(match notification.level {
+                NotificationLevel::Info => text::default,
+                NotificationLevel::Success => text::success,
+                NotificationLevel::Error => text::danger,
+            });
+
+            column = column.push(row![
+                message,
+                horizontal_space(),
+                button(text("x")).style(button::text).on_press(window::Message::DismissNotification(id)),
+            ]);
+        }
+
+        column.width(Length::Fill).into()
+    }
+}
+
+impl Default for NotificationQueue {
+    fn default() -> Self {
+        Self::new(NOTIFICATION_LIFETIME)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
+pub enum NotificationTranslationKeys {
+    #[translation(en_us = "Project imported")]
+    ProjectImported,
+    #[translation(en_us = "Project imported, but check these entries")]
+    ImportedWithWarnings,
+    #[translation(en_us = "Import failed")]
+    ImportFailed,
+    #[translation(en_us = "Unsupported file")]
+    UnsupportedFile,
+    #[translation(en_us = "Indexing")]
+    Indexing,
+    #[translation(en_us = "Extracting")]
+    Extracting,
+    #[translation(en_us = "Parsing")]
+    Parsing,
+    #[translation(en_us = "Save the file to reveal it")]
+    NothingToReveal,
+    #[translation(en_us = "Choose a project folder")]
+    PickProject,
+    #[translation(en_us = "Could not open project")]
+    OpenFailed,
+    #[translation(en_us = "References updated, files changed")]
+    ReferencesUpdated,
+}
+
+#[derive(Debug, Clone)]
+pub struct NotificationTranslations {
+    pub project_imported: String,
+    pub imported_with_warnings: String,
+    pub import_failed: String,
+    pub unsupported_file: String,
+    pub indexing: String,
+    pub extracting: String,
+    pub parsing: String,
+    pub nothing_to_reveal: String,
+    pub pick_project: String,
+    pub open_failed: String,
+    pub references_updated: String,
+}
+
+impl NotificationTranslations {
+    pub(crate) async fn translate(translation_service: TranslationServiceContext) -> Self {
+        let translation_service = translation_service.read().await;
+
+        Self {
+            project_imported: translation_service.translate(&NotificationTranslationKeys::ProjectImported),
+            imported_with_warnings: translation_service.translate(&NotificationTranslationKeys::ImportedWithWarnings),
+            import_failed: translation_service.translate(&NotificationTranslationKeys::ImportFailed),
+            unsupported_file: translation_service.translate(&NotificationTranslationKeys::UnsupportedFile),
+            indexing: translation_service.translate(&NotificationTranslationKeys::Indexing),
+            extracting: translation_service.translate(&NotificationTranslationKeys::Extracting),
+            parsing: translation_service.translate(&NotificationTranslationKeys::Parsing),
+            nothing_to_reveal: translation_service.translate(&NotificationTranslationKeys::NothingToReveal),
+            pick_project: translation_service.translate(&NotificationTranslationKeys::PickProject),
+            open_failed: translation_service.translate(&NotificationTranslationKeys::OpenFailed),
+            references_updated: translation_service.translate(&NotificationTranslationKeys::ReferencesUpdated),
+        }
+    }
+}
+
+impl Default for NotificationTranslations {
+    /// Falls back to the English copy until the translations have loaded
+    fn default() -> Self {
+        Self {
+            project_imported: NotificationTranslationKeys::ProjectImported.english_text().to_string(),
+            imported_with_warnings: NotificationTranslationKeys::ImportedWithWarnings.english_text().to_string(),
+            import_failed: NotificationTranslationKeys::ImportFailed.english_text().to_string(),
+            unsupported_file: NotificationTranslationKeys::UnsupportedFile.english_text().to_string(),
+            indexing: NotificationTranslationKeys::Indexing.english_text().to_string(),
+            extracting: NotificationTranslationKeys::Extracting.english_text().to_string(),
+            parsing: NotificationTranslationKeys::Parsing.english_text().to_string(),
+            nothing_to_reveal: NotificationTranslationKeys::NothingToReveal.english_text().to_string(),
+            pick_project: NotificationTranslationKeys::PickProject.english_text().to_string(),
+            open_failed: NotificationTranslationKeys::OpenFailed.english_text().to_string(),
+            references_updated: NotificationTranslationKeys::ReferencesUpdated.english_text().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enqueue() {
+        // Given an empty queue
+        let mut queue = NotificationQueue::default();
+        let now = Instant::now();
+
+        // When I push two notifications
+        let first = queue.push(Notification::success("Saved"), now);
+        let second = queue.push(Notification::error("Failed"), now);
+
+        // Then both should be shown in order, with distinct ids
+        assert_ne!(first, second);
+        assert_eq!(queue.iter().collect::<Vec<_>>(), vec![
+            (first, &Notification::success("Saved")),
+            (second, &Notification::error("Failed")),
+        ]);
+    }
+
+    #[test]
+    fn test_expire() {
+        // Given notifications pushed at different times
+        let mut queue = NotificationQueue::new(Duration::from_secs(5));
+        let start = Instant::now();
+        queue.push(Notification::info("Old"), start);
+        let newer = queue.push(Notification::info("New"), start + Duration::from_secs(3));
+
+        // When the first has been shown for its whole lifetime
+        queue.expire(start + Duration::from_secs(5));
+
+        // Then only it should be removed
+        assert_eq!(queue.iter().map(|(id, _)| id).collect::<Vec<_>>(), vec![newer]);
+
+        // And once the second expires too, the queue should be empty
+        queue.expire(start + Duration::from_secs(8));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_dismiss() {
+        // Given a queue with a notification
+        let mut queue = NotificationQueue::default();
+        let id = queue.push(Notification::error("Failed"), Instant::now());
+
+        // When I dismiss it
+        // Then it should be removed, and dismissing it again should do nothing
+        assert!(queue.dismiss(id));
+        assert!(queue.is_empty());
+        assert!(!queue.dismiss(id));
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use iced::{Element, Length};
+use iced::widget::{button, text, Column};
+use crate::application::app_context::{ProjectServiceContext, TranslationServiceContext};
+use crate::application::gui::window;
+use crate::data::domain::pack_icon::PackIcon;
+use crate::data::domain::project::ProjectID;
+use crate::services::dialog_service::DialogProvider;
+use crate::services::translation_service::TranslationKey;
+
+/// The icon of the project shown in the preview pane. The image itself isn't drawn, as the window has no image
+/// support, so its size is shown instead, with anything about it the game would handle badly
+#[derive(Debug, Clone)]
+pub struct PackIconPanel {
+    /// `None` while previewing a zip which is still being imported, so there's no project to replace the icon of yet
+    project_id: Option<ProjectID>,
+    icon: Option<PackIcon>,
+}
+
+impl PackIconPanel {
+    pub fn new(project_id: ProjectID, icon: Option<PackIcon>) -> Self {
+        Self { project_id: Some(project_id), icon }
+    }
+
+    pub fn preview(icon: Option<PackIcon>) -> Self {
+        Self { project_id: None, icon }
+    }
+
+    pub fn project_id(&self) -> Option<ProjectID> {
+        self.project_id
+    }
+
+    pub fn set_icon(&mut self, icon: PackIcon) {
+        self.icon = Some(icon);
+    }
+
+    pub fn view<'a>(&'a self, translations: &'a PackIconTranslations) -> Element<'a, window::Message> {
+        let details: Vec<Element<window::Message>> = match &self.icon {
+            None => vec![text(translations.no_icon.as_str()).into()],
+            Some(icon) => icon.dimensions()
+                .map(|(width, height)| text(format!("{}x{}", width, height)).into())
+                .into_iter()
+                .chain(icon.warnings().into_iter().map(|warning| text(warning.to_string()).style(text::danger).into()))
+                .collect(),
+        };
+
+        Column::new()
+            .push(text(translations.title.as_str()))
+            .extend(details)
+            .push(button(text(translations.replace.as_str())).on_press_maybe(self.project_id.map(window::Message::ReplacePackIcon)))
+            .spacing(5)
+            .padding(10)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+/// Asks for an image and makes it the project's icon, giving `None` if the dialog was closed without choosing one
+pub async fn replace_icon(
+    dialogs: Arc<dyn DialogProvider>,
+    project_service: ProjectServiceContext,
+    project_id: ProjectID,
+    title: String,
+) -> Result<Option<PackIcon>, String> {
+    let Some(path) = dialogs.pick_file(&title).await else {
+        return Ok(None);
+    };
+
+    let data = tokio::fs::read(&path).await.map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    // Anything the service accepts is a PNG, so the copy kept for the panel doesn't need checking again
+    project_service.read().await
+        .set_pack_icon(project_id, data.clone()).await
+        .map_err(|error| error.to_string())?;
+
+    Ok(Some(PackIcon::unchecked(data)))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
+pub enum PackIconTranslationKeys {
+    #[translation(en_us = "Pack icon")]
+    Title,
+    #[translation(en_us = "Replace icon")]
+    Replace,
+    #[translation(en_us = "This pack has no icon")]
+    NoIcon,
+    #[translation(en_us = "Choose a PNG image")]
+    PickImage,
+    #[translation(en_us = "Pack icon replaced")]
+    Replaced,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackIconTranslations {
+    pub title: String,
+    pub replace: String,
+    pub no_icon: String,
+    pub pick_image: String,
+    pub replaced: String,
+}
+
+impl PackIconTranslations {
+    pub(crate) async fn translate(translation_service: TranslationServiceContext) -> Self {
+        let translation_service = translation_service.read().await;
+
+        Self {
+            title: translation_service.translate(&PackIconTranslationKeys::Title),
+            replace: translation_service.translate(&PackIconTranslationKeys::Replace),
+            no_icon: translation_service.translate(&PackIconTranslationKeys::NoIcon),
+            pick_image: translation_service.translate(&PackIconTranslationKeys::PickImage),
+            replaced: translation_service.translate(&PackIconTranslationKeys::Replaced),
+        }
+    }
+}
+
+impl Default for PackIconTranslations {
+    /// Falls back to the English copy until the translations have loaded
+    fn default() -> Self {
+        Self {
+            title: PackIconTranslationKeys::Title.english_text().to_string(),
+            replace: PackIconTranslationKeys::Replace.english_text().to_string(),
+            no_icon: PackIconTranslationKeys::NoIcon.english_text().to_string(),
+            pick_image: PackIconTranslationKeys::PickImage.english_text().to_string(),
+            replaced: PackIconTranslationKeys::Replaced.english_text().to_string(),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+/// Anything which can be open in a tab, and may need saving before it is closed
+pub trait TabContent {
+    fn is_dirty(&self) -> bool;
+}
+
+/// What came of asking to close a tab
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CloseTabOutcome {
+    /// The tab was closed, and the given tab is now active, if any are left
+    Closed { activated: Option<usize> },
+    /// The tab has unsaved work, so the user has to be asked first. Nothing has changed yet
+    NeedsConfirmation,
+    /// There is no tab at that index, which can happen when a close arrives after the tab has already gone
+    NotFound,
+}
+
+/// The open documents of the editor, of which at most one is shown at a time
+#[derive(Debug)]
+pub struct Tabs<T> {
+    tabs: Vec<T>,
+    active: Option<usize>,
+}
+
+impl<T> Default for Tabs<T> {
+    fn default() -> Self {
+        Self {
+            tabs: Vec::new(),
+            active: None,
+        }
+    }
+}
+
+impl<T: TabContent> Tabs<T> {
+    /// Opens a tab after the others and makes it active, returning its index
+    pub fn push(&mut self, tab: T) -> usize {
+        self.tabs.push(tab);
+        let index = self.tabs.len() - 1;
+        self.active = Some(index);
+        index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn active_index(&self) -> Option<usize> {
+        self.active
+    }
+
+    pub fn active(&self) -> Option<&T> {
+        self.active.and_then(|index| self.tabs.get(index))
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut T> {
+        self.active.and_then(|index| self.tabs.get_mut(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.tabs.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.tabs.iter_mut()
+    }
+
+    /// Shows the tab at `index`, returning `false` if there isn't one
+    pub fn activate(&mut self, index: usize) -> bool {
+        let exists = index < self.tabs.len();
+        if exists {
+            self.active = Some(index);
+        }
+        exists
+    }
+
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.tabs.iter().position(predicate)
+    }
+
+    pub fn any_dirty(&self) -> bool {
+        self.tabs.iter().any(TabContent::is_dirty)
+    }
+
+    /// Closes a tab straight away if it has nothing to save, otherwise leaves it open for the caller to confirm
+    pub fn request_close(&mut self, index: usize) -> CloseTabOutcome {
+        match self.tabs.get(index) {
+            None => CloseTabOutcome::NotFound,
+            Some(tab) if tab.is_dirty() => CloseTabOutcome::NeedsConfirmation,
+            Some(_) => {
+                self.remove(index);
+                CloseTabOutcome::Closed { activated: self.active }
+            }
+        }
+    }
+
+    /// Closes a tab whatever its state. Closing the active tab activates the one that took its place,
+    /// or the one before it if it was the last, so the user stays in the same part of the tab bar
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+
+        let tab = self.tabs.remove(index);
+
+        self.active = match self.active {
+            _ if self.tabs.is_empty() => None,
+            Some(active) if active > index => Some(active - 1),
+            Some(active) if active == index => Some(index.min(self.tabs.len() - 1)),
+            active => active,
+        };
+
+        Some(tab)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Document {
+        name: &'static str,
+        dirty: bool,
+    }
+
+    impl TabContent for Document {
+        fn is_dirty(&self) -> bool {
+            self.dirty
+        }
+    }
+
+    fn tabs(documents: &[(&'static str, bool)]) -> Tabs<Document> {
+        let mut tabs = Tabs::default();
+        for (name, dirty) in documents {
+            tabs.push(Document { name, dirty: *dirty });
+        }
+        tabs
+    }
+
+    fn names(tabs: &Tabs<Document>) -> Vec<&'static str> {
+        tabs.iter().map(|document| document.name).collect()
+    }
+
+    #[test]
+    fn test_close_clean_tab() {
+        // Given three saved tabs, with the middle one active
+        let mut tabs = tabs(&[("load.mcfunction", false), ("tick.mcfunction", false), ("pack.mcmeta", false)]);
+        tabs.activate(1);
+
+        // When I close the active tab
+        let outcome = tabs.request_close(1);
+
+        // Then it should be gone, and the tab that took its place should be active
+        assert_eq!(outcome, CloseTabOutcome::Closed { activated: Some(1) });
+        assert_eq!(names(&tabs), vec!["load.mcfunction", "pack.mcmeta"]);
+        assert_eq!(tabs.active().unwrap().name, "pack.mcmeta");
+
+        // And closing the last tab in the bar should activate the one before it
+        assert_eq!(tabs.request_close(1), CloseTabOutcome::Closed { activated: Some(0) });
+        assert_eq!(tabs.active().unwrap().name, "load.mcfunction");
+    }
+
+    #[test]
+    fn test_close_inactive_tab() {
+        // Given three saved tabs, with the last one active
+        let mut tabs = tabs(&[("load.mcfunction", false), ("tick.mcfunction", false), ("pack.mcmeta", false)]);
+
+        // When I close a tab before it
+        tabs.request_close(0);
+
+        // Then the same tab should stay active
+        assert_eq!(tabs.active().unwrap().name, "pack.mcmeta");
+        assert_eq!(tabs.active_index(), Some(1));
+    }
+
+    #[test]
+    fn test_close_dirty_tab() {
+        // Given a tab with unsaved changes
+        let mut tabs = tabs(&[("load.mcfunction", false), ("tick.mcfunction", true)]);
+
+        // When I ask to close it
+        let outcome = tabs.request_close(1);
+
+        // Then I should be asked first, with the tab left open
+        assert_eq!(outcome, CloseTabOutcome::NeedsConfirmation);
+        assert_eq!(tabs.iter().count(), 2);
+        assert!(tabs.any_dirty());
+
+        // And once I have confirmed, it should close
+        assert_eq!(tabs.remove(1).unwrap().name, "tick.mcfunction");
+        assert!(!tabs.any_dirty());
+    }
+
+    #[test]
+    fn test_close_last_tab() {
+        // Given a single saved tab
+        let mut tabs = tabs(&[("load.mcfunction", false)]);
+
+        // When I close it
+        let outcome = tabs.request_close(0);
+
+        // Then there should be no tabs and nothing active
+        assert_eq!(outcome, CloseTabOutcome::Closed { activated: None });
+        assert!(tabs.is_empty());
+        assert!(tabs.active().is_none());
+
+        // And closing again should be ignored
+        assert_eq!(tabs.request_close(0), CloseTabOutcome::NotFound);
+    }
+}
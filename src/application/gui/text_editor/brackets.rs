@@ -0,0 +1,263 @@
+const JSON_PAIRS: &[(char, char)] = &[('{', '}'), ('[', ']'), ('"', '"')];
+/// SNBT inside mcfunction also allows single quoted strings, and commands use parentheses in some arguments
+const SNBT_PAIRS: &[(char, char)] = &[('{', '}'), ('[', ']'), ('(', ')'), ('"', '"'), ('\'', '\'')];
+
+/// What typing a character should do, given the text around the cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoClose {
+    /// Insert the character as normal
+    Insert,
+    /// Insert the character followed by its closing pair, leaving the cursor between them
+    InsertPair(char, char),
+    /// Move past the auto-inserted closing character at the cursor instead of inserting another
+    SkipOver,
+}
+
+fn pairs_for_extension(extension: &str) -> &'static [(char, char)] {
+    match extension {
+        "json" | "mcmeta" => JSON_PAIRS,
+        _ => SNBT_PAIRS,
+    }
+}
+
+/// Returns the quote character of the string the cursor is in, if any
+fn open_quote(before_cursor: &str, pairs: &[(char, char)]) -> Option<char> {
+    let mut open_quote = None;
+    let mut escaped = false;
+
+    for c in before_cursor.chars() {
+        match open_quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(quote) if c == quote => open_quote = None,
+            None if pairs.iter().any(|(open, close)| open == close && *open == c) => open_quote = Some(c),
+            _ => {}
+        }
+    }
+
+    open_quote
+}
+
+/// Decides how to handle a typed character, auto-closing brackets and quotes and typing over closing characters.
+/// Only closing characters which were auto-inserted are typed over, as told by `next_auto_closed`,
+/// so that ones the user typed themselves are never skipped
+pub fn auto_close(typed: char, before_cursor: &str, after_cursor: &str, extension: &str, next_auto_closed: bool) -> AutoClose {
+    let pairs = pairs_for_extension(extension);
+    let next_char = after_cursor.chars().next();
+    let open_quote = open_quote(before_cursor, pairs);
+
+    let is_closing = pairs.iter().any(|(_, close)| *close == typed);
+    let is_quote = pairs.iter().any(|(open, close)| open == close && *open == typed);
+
+    // Typing a closing character over the same auto-inserted character moves past it, unless it would close a string early
+    if is_closing && next_auto_closed && next_char == Some(typed) && (open_quote.is_none() || open_quote == Some(typed)) {
+        return AutoClose::SkipOver;
+    }
+
+    // Brackets inside strings are plain text
+    if open_quote.is_some() {
+        return AutoClose::Insert;
+    }
+
+    let Some((open, close)) = pairs.iter().find(|(open, _)| *open == typed).copied() else {
+        return AutoClose::Insert;
+    };
+
+    // Only quote words which haven't been typed yet, so that e.g. `don't` doesn't insert a pair
+    if is_quote && before_cursor.chars().last().is_some_and(|c| c.is_alphanumeric()) {
+        return AutoClose::Insert;
+    }
+
+    // Don't auto-close directly before a word, where the user is likely wrapping existing text
+    if next_char.is_some_and(|c| c.is_alphanumeric()) {
+        return AutoClose::Insert;
+    }
+
+    AutoClose::InsertPair(open, close)
+}
+
+/// Finds the bracket at or just before the cursor and its matching pair, as `(line, byte column)` positions.
+/// Brackets inside strings are ignored. The text is only read up to the cursor, and past it only as far as the match
+pub fn find_matching_bracket(lines: &[&str], line: usize, column: usize) -> Option<[(usize, usize); 2]> {
+    const BRACKETS: &[(char, char)] = &[('{', '}'), ('[', ']'), ('(', ')')];
+
+    let mut brackets = bracket_positions(lines).peekable();
+
+    let mut before_cursor = Vec::new();
+    while let Some(bracket) = brackets.next_if(|(position, _)| *position < (line, column)) {
+        before_cursor.push(bracket);
+    }
+
+    let (position, bracket) = match brackets.next_if(|(position, _)| *position == (line, column)) {
+        Some(at_cursor) => at_cursor,
+        None => before_cursor.pop().filter(|(position, _)| column > 0 && *position == (line, column - 1))?,
+    };
+
+    let (open, close, forward) = BRACKETS.iter()
+        .find_map(|(open, close)| {
+            if bracket == *open {
+                Some((*open, *close, true))
+            } else if bracket == *close {
+                Some((*open, *close, false))
+            } else {
+                None
+            }
+        })?;
+
+    let candidates: Box<dyn Iterator<Item = ((usize, usize), char)> + '_> = if forward {
+        Box::new(brackets)
+    } else {
+        Box::new(before_cursor.into_iter().rev())
+    };
+
+    let mut depth = 0;
+    for (candidate_position, candidate) in candidates {
+        let (same, other) = if forward { (open, close) } else { (close, open) };
+
+        if candidate == same {
+            depth += 1;
+        } else if candidate == other {
+            if depth == 0 {
+                return Some([position, candidate_position]);
+            }
+            depth -= 1;
+        }
+    }
+
+    None
+}
+
+/// Brackets outside of strings, in document order, read from the text as they're needed
+fn bracket_positions<'a>(lines: &'a [&'a str]) -> impl Iterator<Item = ((usize, usize), char)> + 'a {
+    let mut open_quote = None;
+    let mut escaped = false;
+
+    lines.iter()
+        .enumerate()
+        .flat_map(|(line_index, line)| line.char_indices().map(move |(column, c)| ((line_index, column), c)))
+        .filter_map(move |(position, c)| {
+            match open_quote {
+                Some(_) if escaped => escaped = false,
+                Some(_) if c == '\\' => escaped = true,
+                Some(quote) if c == quote => open_quote = None,
+                Some(_) => {}
+                None if c == '"' || c == '\'' => open_quote = Some(c),
+                None if matches!(c, '{' | '}' | '[' | ']' | '(' | ')') => return Some((position, c)),
+                None => {}
+            }
+
+            None
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod auto_close {
+        use rstest::rstest;
+        use super::*;
+
+        #[rstest]
+        #[case::open_brace('{', "", "", AutoClose::InsertPair('{', '}'))]
+        #[case::nested_brace('{', "{", "}", AutoClose::InsertPair('{', '}'))]
+        #[case::nested_bracket('[', "{\"values\": ", "}", AutoClose::InsertPair('[', ']'))]
+        #[case::over_type_brace('}', "{", "}", AutoClose::SkipOver)]
+        #[case::over_type_nested('}', "{{", "}}", AutoClose::SkipOver)]
+        #[case::closing_without_pair('}', "{", "", AutoClose::Insert)]
+        #[case::quote('"', "{", "}", AutoClose::InsertPair('"', '"'))]
+        #[case::over_type_quote('"', "{\"key", "\"}", AutoClose::SkipOver)]
+        #[case::brace_in_string('{', "\"text ", "\"", AutoClose::Insert)]
+        #[case::escaped_quote_in_string('{', "\"a \\\" ", "\"", AutoClose::Insert)]
+        #[case::before_word('{', "", "key", AutoClose::Insert)]
+        #[case::json_no_single_quotes('\'', "", "", AutoClose::Insert)]
+        #[case::json_no_parentheses('(', "", "", AutoClose::Insert)]
+        fn test_json_auto_close(#[case] typed: char, #[case] before_cursor: &str, #[case] after_cursor: &str, #[case] expected: AutoClose) {
+            // Given the text around the cursor in a JSON file
+            // When I type a character
+            let result = auto_close(typed, before_cursor, after_cursor, "json", true);
+
+            // Then it should be handled correctly
+            assert_eq!(result, expected);
+        }
+
+        #[rstest]
+        #[case::snbt_compound('{', "data merge entity @s ", "", AutoClose::InsertPair('{', '}'))]
+        #[case::snbt_single_quote('\'', "give @s stone{display:{Name:", "}}", AutoClose::InsertPair('\'', '\''))]
+        #[case::apostrophe_in_word('\'', "say don", "", AutoClose::Insert)]
+        #[case::double_quote_in_single_quoted('"', "{Name:'", "'}", AutoClose::Insert)]
+        #[case::over_type_single_quote('\'', "{Name:'text", "'}", AutoClose::SkipOver)]
+        fn test_snbt_auto_close(#[case] typed: char, #[case] before_cursor: &str, #[case] after_cursor: &str, #[case] expected: AutoClose) {
+            // Given the text around the cursor in an mcfunction file
+            // When I type a character
+            let result = auto_close(typed, before_cursor, after_cursor, "mcfunction", true);
+
+            // Then it should be handled correctly
+            assert_eq!(result, expected);
+        }
+
+        #[rstest]
+        #[case::brace('}', "{", "}")]
+        #[case::quote('"', "{\"key", "\"}")]
+        fn test_typed_closing_not_skipped(#[case] typed: char, #[case] before_cursor: &str, #[case] after_cursor: &str) {
+            // Given a closing character the user typed themselves after the cursor
+            // When I type the same character
+            let result = auto_close(typed, before_cursor, after_cursor, "json", false);
+
+            // Then it should be inserted rather than typed over
+            assert_eq!(result, AutoClose::Insert);
+        }
+    }
+
+    mod matching {
+        use super::*;
+
+        #[test]
+        fn test_match_forward_across_lines() {
+            // Given nested brackets over several lines
+            let lines = ["{", "  \"a\": [1, 2],", "  \"b\": {}", "}"];
+
+            // When the cursor is at the outer opening brace
+            let result = find_matching_bracket(&lines, 0, 0);
+
+            // Then it should match the outer closing brace
+            assert_eq!(result, Some([(0, 0), (3, 0)]));
+        }
+
+        #[test]
+        fn test_match_backward_after_cursor() {
+            // Given a closing bracket just before the cursor
+            let lines = ["[1, [2]] "];
+
+            // When the cursor is just after it
+            let result = find_matching_bracket(&lines, 0, 8);
+
+            // Then it should match its opening bracket
+            assert_eq!(result, Some([(0, 7), (0, 0)]));
+        }
+
+        #[test]
+        fn test_match_ignores_brackets_in_strings() {
+            // Given a string containing a bracket
+            let lines = ["{\"a\": \"}\"}"];
+
+            // When the cursor is at the opening brace
+            let result = find_matching_bracket(&lines, 0, 0);
+
+            // Then the bracket in the string should be skipped
+            assert_eq!(result, Some([(0, 0), (0, 9)]));
+        }
+
+        #[test]
+        fn test_no_match() {
+            // Given an unclosed bracket
+            let lines = ["{\"a\": ["];
+
+            // When the cursor is at it
+            let result = find_matching_bracket(&lines, 0, 6);
+
+            // Then there should be no match
+            assert_eq!(result, None);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Top-level directories of a pack, which namespaces sit inside
+const PACK_CATEGORIES: &[&str] = &["data", "assets"];
+
+/// One segment of the open file's resource location, e.g. `minecraft`, `recipe` or `stick`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    pub label: String,
+    /// The directory this segment stands for, or the file itself for the last one, for narrowing the file tree to it
+    pub path: PathBuf,
+}
+
+/// Splits a pack file's path into its namespace, resource kind and path, e.g. `data/minecraft/recipe/stick.json`
+/// gives `minecraft › recipe › stick`. The pack root is taken to be the last `data` or `assets` directory with
+/// a namespace, kind and file below it, so files outside of a namespace have no breadcrumbs
+pub fn breadcrumbs(file: &Path) -> Vec<Breadcrumb> {
+    let components: Vec<_> = file.components().collect();
+
+    let Some(category_index) = (0..components.len())
+        .rev()
+        .filter(|index| components.len() - index > 3)
+        .find(|index| matches!(components[*index], Component::Normal(name) if name.to_str().is_some_and(|name| PACK_CATEGORIES.contains(&name))))
+    else {
+        return Vec::new();
+    };
+
+    let mut path: PathBuf = components[..=category_index].iter().collect();
+    let last_index = components.len() - 1;
+
+    components.iter()
+        .enumerate()
+        .skip(category_index + 1)
+        .map(|(index, component)| {
+            path.push(component);
+
+            let label = if index == last_index {
+                path.file_stem().unwrap_or_default()
+            } else {
+                component.as_os_str()
+            };
+
+            Breadcrumb { label: label.to_string_lossy().into_owned(), path: path.clone() }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    fn labels(file: &str) -> Vec<String> {
+        breadcrumbs(Path::new(file)).into_iter().map(|breadcrumb| breadcrumb.label).collect()
+    }
+
+    #[rstest]
+    #[case::data_pack("packs/gems/data/minecraft/recipe/stick.json", &["minecraft", "recipe", "stick"])]
+    #[case::nested_function("packs/gems/data/example/function/util/setup.mcfunction", &["example", "function", "util", "setup"])]
+    #[case::tag("data/example/tags/item/gems.json", &["example", "tags", "item", "gems"])]
+    #[case::resource_pack("packs/gems/assets/minecraft/textures/item/gem.png", &["minecraft", "textures", "item", "gem"])]
+    #[case::overlay("packs/gems/v2/data/example/recipe/gem.json", &["example", "recipe", "gem"])]
+    #[case::function_folder_named_data("data/example/function/data/load.mcfunction", &["example", "function", "data", "load"])]
+    #[case::pack_info("packs/gems/pack.mcmeta", &[])]
+    #[case::outside_namespace("packs/gems/data/readme.txt", &[])]
+    fn test_breadcrumb_labels(#[case] file: &str, #[case] expected: &[&str]) {
+        // Given a file in a pack
+        // When I split it into breadcrumbs
+        // Then there should be one for each part of its resource location
+        assert_eq!(labels(file), expected);
+    }
+
+    #[test]
+    fn test_breadcrumb_paths() {
+        // Given a file in a resource pack
+        let file = Path::new("/packs/gems/assets/minecraft/models/item/gem.json");
+
+        // When I split it into breadcrumbs
+        let paths: Vec<_> = breadcrumbs(file).into_iter().map(|breadcrumb| breadcrumb.path).collect();
+
+        // Then each should point at its directory, with the last pointing at the file
+        assert_eq!(paths, vec![
+            PathBuf::from("/packs/gems/assets/minecraft"),
+            PathBuf::from("/packs/gems/assets/minecraft/models"),
+            PathBuf::from("/packs/gems/assets/minecraft/models/item"),
+            PathBuf::from("/packs/gems/assets/minecraft/models/item/gem.json"),
+        ]);
+    }
+}
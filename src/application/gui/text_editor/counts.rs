@@ -0,0 +1,37 @@
+/// How long some text is, e.g. a pack's readme or credits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextCounts {
+    pub words: usize,
+    pub lines: usize,
+    pub characters: usize,
+}
+
+impl TextCounts {
+    /// Words are runs of anything but whitespace. Lines are counted as the editor numbers them, so empty text is one line
+    /// and a trailing line break starts another. Characters include line breaks
+    pub fn count(text: &str) -> Self {
+        Self {
+            words: text.split_whitespace().count(),
+            lines: text.split('\n').count(),
+            characters: text.chars().count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case::empty("", TextCounts { words: 0, lines: 1, characters: 0 })]
+    #[case::single_line("Made by someone", TextCounts { words: 3, lines: 1, characters: 15 })]
+    #[case::multi_line("# Gems\n\nAdds  gems.\n", TextCounts { words: 4, lines: 4, characters: 20 })]
+    #[case::unicode("Für Spieler ✨", TextCounts { words: 3, lines: 1, characters: 13 })]
+    fn test_count(#[case] text: &str, #[case] expected: TextCounts) {
+        // Given some text
+        // When I count it
+        // Then every word, line and character should be counted
+        assert_eq!(TextCounts::count(text), expected);
+    }
+}
@@ -0,0 +1,304 @@
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use crate::data::domain::project::VANILLA_NAMESPACE;
+use crate::data::domain::project_index::ProjectIndex;
+use crate::data::domain::resource::resource::ResourceLocation;
+use crate::data::serialization::project::PACK_INFO_FILE;
+use crate::services::filesystem_service::{FilesystemProvider, FilesystemProviderError};
+
+/// Directories searched for a reference when there's nothing around it saying what kind of resource it is,
+/// paired with the extension of the files inside
+const DATA_KINDS: &[(&str, &str)] = &[
+    ("function", "mcfunction"),
+    ("functions", "mcfunction"),
+    ("advancement", "json"),
+    ("advancements", "json"),
+    ("loot_table", "json"),
+    ("loot_tables", "json"),
+    ("predicate", "json"),
+    ("predicates", "json"),
+    ("recipe", "json"),
+    ("recipes", "json"),
+    ("item_modifier", "json"),
+    ("item_modifiers", "json"),
+];
+
+const DATA_TAG_KINDS: &[&str] = &[
+    "tags/function", "tags/functions",
+    "tags/block", "tags/blocks",
+    "tags/item", "tags/items",
+    "tags/entity_type", "tags/entity_types",
+    "tags/fluid", "tags/fluids",
+];
+
+const ASSET_KINDS: &[(&str, &str)] = &[
+    ("items", "json"),
+    ("models", "json"),
+    ("blockstates", "json"),
+    ("textures", "png"),
+];
+
+/// A resource location in the editor's buffer
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Reference {
+    pub location: ResourceLocation,
+    pub is_tag: bool,
+    /// Whether the reference is known to be a function, e.g. after a `function` command or in a `"function"` field
+    pub is_function: bool,
+}
+
+/// Finds the resource location at a byte column in a line, if there is one.
+/// Outside of function calls, only namespaced locations count, so that ordinary words aren't mistaken for references
+pub fn reference_at(line: &str, column: usize) -> Option<Reference> {
+    let is_reference_char = |c: char| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.' | '/' | ':' | '#');
+
+    let column = column.min(line.len());
+    let start = line[..column].rfind(|c: char| !is_reference_char(c)).map_or(0, |index| index + 1);
+    let end = line[column..].find(|c: char| !is_reference_char(c)).map_or(line.len(), |index| column + index);
+
+    let token = &line[start..end];
+    let (is_tag, token) = match token.strip_prefix('#') {
+        Some(token) => (true, token),
+        None => (false, token),
+    };
+
+    let before = line[..start].trim_end();
+    let is_function = before.ends_with("function")
+        || before.trim_end_matches('"').trim_end().trim_end_matches(':').trim_end().ends_with("\"function\"");
+
+    if token.is_empty() || (!token.contains(':') && !is_function) {
+        return None;
+    }
+
+    let location = ResourceLocation::from_str(token).ok()?;
+
+    Some(Reference { location, is_tag, is_function })
+}
+
+/// Roots of the packs a file could belong to, deepest first.
+/// A pack root is a directory containing `data/<namespace>/...` or `assets/<namespace>/...`
+pub fn pack_roots(file: &Path) -> Vec<PathBuf> {
+    let components: Vec<_> = file.components().collect();
+
+    (0..components.len())
+        .rev()
+        .filter(|index| {
+            let is_pack_directory = matches!(components[*index], Component::Normal(name) if name == "data" || name == "assets");
+            // The namespace and resource kind directories, then the file itself
+            is_pack_directory && components.len() - index > 3
+        })
+        .map(|index| components[..index].iter().collect())
+        .collect()
+}
+
+/// Resource kinds a reference could be, in the same order as [`candidate_paths`]
+fn candidate_kinds(reference: &Reference) -> Vec<&'static str> {
+    match (reference.is_tag, reference.is_function) {
+        (true, true) => vec!["tags/function", "tags/functions"],
+        (true, false) => DATA_TAG_KINDS.to_vec(),
+        (false, true) => vec!["function", "functions"],
+        (false, false) => DATA_KINDS.iter().chain(ASSET_KINDS).map(|(kind, _)| *kind).collect(),
+    }
+}
+
+/// Looks a reference up in the project's index, giving the file defining it relative to the pack root.
+/// Function calls come from the call graph, everything else from the indexed resources
+pub fn indexed_definition<'a>(index: &'a ProjectIndex, reference: &Reference) -> Option<&'a PathBuf> {
+    let called_function = (reference.is_function && !reference.is_tag)
+        .then(|| index.call_graph().definition(&reference.location))
+        .flatten();
+
+    called_function.or_else(|| candidate_kinds(reference).into_iter()
+        .find_map(|kind| index.definitions(kind, &reference.location).first()))
+}
+
+/// Where the definition of a reference could be, relative to the pack root, in order of preference
+pub fn candidate_paths(reference: &Reference) -> Vec<PathBuf> {
+    let namespace = reference.location.to_string();
+    let (namespace, value) = namespace.split_once(':').expect("Resource locations always have a namespace");
+
+    let data_path = |kind: &str, extension: &str| PathBuf::from("data").join(namespace).join(kind).join(format!("{}.{}", value, extension));
+    let asset_path = |kind: &str, extension: &str| PathBuf::from("assets").join(namespace).join(kind).join(format!("{}.{}", value, extension));
+
+    match (reference.is_tag, reference.is_function) {
+        (true, true) => vec![data_path("tags/function", "json"), data_path("tags/functions", "json")],
+        (true, false) => DATA_TAG_KINDS.iter().map(|kind| data_path(kind, "json")).collect(),
+        (false, true) => vec![data_path("function", "mcfunction"), data_path("functions", "mcfunction")],
+        (false, false) => DATA_KINDS.iter()
+            .map(|(kind, extension)| data_path(kind, extension))
+            .chain(ASSET_KINDS.iter().map(|(kind, extension)| asset_path(kind, extension)))
+            .collect(),
+    }
+}
+
+/// Resolves the reference under the cursor in `file` to the file which defines it.
+/// The shown project's root and index are checked first when the file is in it. Until the index is built,
+/// or for files it doesn't know about yet, the pack is searched on disk instead
+pub async fn find_definition(
+    filesystem: &(dyn FilesystemProvider + Send + Sync),
+    index: Option<(&Path, &ProjectIndex)>,
+    file: &Path,
+    line: &str,
+    column: usize,
+) -> Result<PathBuf, DefinitionError> {
+    let reference = reference_at(line, column).ok_or(DefinitionError::NoReference)?;
+
+    let indexed = index
+        .filter(|(root, _)| file.starts_with(root))
+        .and_then(|(root, index)| Some(root.join(indexed_definition(index, &reference)?)));
+    if let Some(path) = indexed {
+        // The index can be behind the disk, e.g. after a file is deleted, so it's only trusted if the file is still there
+        if filesystem.file_exists(&path).await? {
+            return Ok(path);
+        }
+    }
+
+    let mut pack_roots = pack_roots(file);
+    if pack_roots.is_empty() {
+        return Err(DefinitionError::NotInPack);
+    }
+
+    // Prefer the root which is actually a pack, falling back to the deepest match
+    for (index, root) in pack_roots.iter().enumerate() {
+        if filesystem.file_exists(&root.join(PACK_INFO_FILE)).await? {
+            pack_roots.swap(0, index);
+            break;
+        }
+    }
+    let pack_root = &pack_roots[0];
+
+    for candidate in candidate_paths(&reference) {
+        let path = pack_root.join(candidate);
+
+        if filesystem.file_exists(&path).await? {
+            return Ok(path);
+        }
+    }
+
+    if reference.location.to_string().starts_with(&format!("{}:", VANILLA_NAMESPACE)) {
+        Err(DefinitionError::Vanilla(reference.location))
+    } else {
+        Err(DefinitionError::Unresolved(reference.location))
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DefinitionError {
+    #[error("No resource location under the cursor")]
+    NoReference,
+    #[error("This file isn't inside a pack")]
+    NotInPack,
+    #[error("{0} is defined by the game, not this pack")]
+    Vanilla(ResourceLocation),
+    #[error("Could not find {0} in this pack")]
+    Unresolved(ResourceLocation),
+    #[error(transparent)]
+    Filesystem(Arc<FilesystemProviderError>),
+}
+
+impl From<FilesystemProviderError> for DefinitionError {
+    fn from(value: FilesystemProviderError) -> Self {
+        DefinitionError::Filesystem(Arc::new(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+    use crate::services::filesystem_service::FileWriteOptions;
+    use crate::data::domain::project::Project;
+    use crate::services::in_memory_filesystem::InMemoryFilesystem;
+
+    async fn filesystem_with_files(files: &[&str]) -> InMemoryFilesystem {
+        let filesystem = InMemoryFilesystem::new();
+
+        for file in files {
+            let path = Path::new(file);
+            filesystem.create_directory_recursive(path.parent().unwrap()).await.unwrap();
+            filesystem.write_file(path, b"", FileWriteOptions::CreateNew).await.unwrap();
+        }
+
+        filesystem
+    }
+
+    #[rstest]
+    #[case::function_call("function example:util/setup", 12, Some(("example:util/setup", false, true)))]
+    #[case::execute_run("execute as @a run function example:tick", 30, Some(("example:tick", false, true)))]
+    #[case::function_tag("function #example:load", 15, Some(("example:load", true, true)))]
+    #[case::json_field(r#"{"function": "example:reward"}"#, 16, Some(("example:reward", false, true)))]
+    #[case::json_value(r#"{"id": "example:gem"}"#, 12, Some(("example:gem", false, false)))]
+    #[case::plain_word("say hello", 6, None)]
+    fn test_reference_at(#[case] line: &str, #[case] column: usize, #[case] expected: Option<(&str, bool, bool)>) {
+        // Given a line with the cursor at a column
+        // When I look for a reference there
+        let reference = reference_at(line, column);
+
+        // Then it should be found with the right context
+        let expected = expected.map(|(location, is_tag, is_function)| Reference {
+            location: ResourceLocation::from_str(location).unwrap(),
+            is_tag,
+            is_function,
+        });
+        assert_eq!(reference, expected);
+    }
+
+    #[tokio::test]
+    async fn test_find_function_definition() {
+        // Given a pack with two functions
+        let filesystem = filesystem_with_files(&[
+            "/packs/example/pack.mcmeta",
+            "/packs/example/data/example/function/load.mcfunction",
+            "/packs/example/data/example/function/util/setup.mcfunction",
+        ]).await;
+
+        // When I go to the definition of a call in one of them
+        let file = Path::new("/packs/example/data/example/function/load.mcfunction");
+        let result = find_definition(&filesystem, None, file, "function example:util/setup", 14).await;
+
+        // Then it should resolve to the called function's file
+        assert_eq!(result.unwrap(), PathBuf::from("/packs/example/data/example/function/util/setup.mcfunction"));
+    }
+
+    #[tokio::test]
+    async fn test_find_definition_from_index() {
+        // Given a pack whose project has been indexed, with a tag at its root the disk search would miss
+        let filesystem = filesystem_with_files(&[
+            "/packs/example/pack.mcmeta",
+            "/packs/example/data/example/function/load.mcfunction",
+            "/packs/example/data/example/tags/item/gems.json",
+        ]).await;
+        let project = Project::test_with_files(&[
+            ("data/example/function/load.mcfunction", "function example:missing"),
+            ("data/example/tags/item/gems.json", r#"{"values": []}"#),
+        ]);
+        let index = ProjectIndex::build(&project, |_| {});
+        let root = Path::new("/packs/example");
+
+        // When I go to the definition of a tag from the pack's own metadata
+        let file = Path::new("/packs/example/pack.mcmeta");
+        let result = find_definition(&filesystem, Some((root, &index)), file, r##"{"tag": "#example:gems"}"##, 12).await;
+
+        // Then it should be found through the index
+        assert_eq!(result.unwrap(), PathBuf::from("/packs/example/data/example/tags/item/gems.json"));
+    }
+
+    #[tokio::test]
+    async fn test_find_definition_unresolved() {
+        // Given a pack with one function
+        let filesystem = filesystem_with_files(&[
+            "/packs/example/pack.mcmeta",
+            "/packs/example/data/example/function/load.mcfunction",
+        ]).await;
+        let file = Path::new("/packs/example/data/example/function/load.mcfunction");
+
+        // When I go to the definition of a missing function, and of a vanilla one
+        let missing = find_definition(&filesystem, None, file, "function example:missing", 14).await;
+        let vanilla = find_definition(&filesystem, None, file, "function minecraft:tick", 14).await;
+
+        // Then each should say why it couldn't be opened
+        assert!(matches!(missing, Err(DefinitionError::Unresolved(_))));
+        assert!(matches!(vanilla, Err(DefinitionError::Vanilla(_))));
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt::{Display, Formatter};
+
+const BOM: char = '\u{feff}';
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    /// UTF-8 starting with a byte order mark, which some tools write and the game tolerates
+    Utf8Bom,
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encoding::Utf8 => write!(f, "UTF-8"),
+            Encoding::Utf8Bom => write!(f, "UTF-8 BOM"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+impl Display for LineEnding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineEnding::Lf => write!(f, "LF"),
+            LineEnding::CrLf => write!(f, "CRLF"),
+        }
+    }
+}
+
+/// How the open file is written to disk. The editor itself always works with a BOM-less, LF-only copy of the text,
+/// so the format is detected when a file is opened and applied again when it's saved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileFormat {
+    pub encoding: Encoding,
+    pub line_ending: LineEnding,
+}
+
+impl FileFormat {
+    /// Every combination, for picking the format from the status bar
+    pub const ALL: [FileFormat; 4] = [
+        FileFormat { encoding: Encoding::Utf8, line_ending: LineEnding::Lf },
+        FileFormat { encoding: Encoding::Utf8, line_ending: LineEnding::CrLf },
+        FileFormat { encoding: Encoding::Utf8Bom, line_ending: LineEnding::Lf },
+        FileFormat { encoding: Encoding::Utf8Bom, line_ending: LineEnding::CrLf },
+    ];
+
+    /// Detects the format of a file's contents. Line endings follow the first line break, and files without any are taken as LF
+    pub fn detect(contents: &str) -> Self {
+        let encoding = if contents.starts_with(BOM) { Encoding::Utf8Bom } else { Encoding::Utf8 };
+
+        let line_ending = match contents.find('\n') {
+            Some(index) if contents[..index].ends_with('\r') => LineEnding::CrLf,
+            _ => LineEnding::Lf,
+        };
+
+        Self { encoding, line_ending }
+    }
+
+    /// The text as the editor works with it, without a BOM and with LF line endings
+    pub fn normalize(contents: &str) -> String {
+        contents.strip_prefix(BOM).unwrap_or(contents).replace("\r\n", "\n")
+    }
+
+    /// The editor's text as it should be written in this format
+    pub fn apply(&self, text: &str) -> String {
+        let mut contents = String::with_capacity(text.len() + BOM.len_utf8());
+
+        if self.encoding == Encoding::Utf8Bom {
+            contents.push(BOM);
+        }
+
+        match self.line_ending {
+            LineEnding::Lf => contents.push_str(text),
+            LineEnding::CrLf => contents.push_str(&text.replace('\n', self.line_ending.as_str())),
+        }
+
+        contents
+    }
+}
+
+/// Formats as shown in the status bar, e.g. `UTF-8 · LF`
+impl Display for FileFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} · {}", self.encoding, self.line_ending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_text() {
+        // Given each combination of encoding and line ending
+        // When I format them for the status bar
+        let status: Vec<String> = FileFormat::ALL.iter().map(ToString::to_string).collect();
+
+        // Then each should show both halves
+        assert_eq!(status, vec!["UTF-8 · LF", "UTF-8 · CRLF", "UTF-8 BOM · LF", "UTF-8 BOM · CRLF"]);
+    }
+
+    #[test]
+    fn test_detect_and_round_trip() {
+        // Given files written in each format
+        for (contents, expected) in [
+            ("say hi\nsay bye\n", FileFormat::ALL[0]),
+            ("say hi\r\nsay bye\r\n", FileFormat::ALL[1]),
+            ("\u{feff}say hi\nsay bye", FileFormat::ALL[2]),
+            ("\u{feff}say hi\r\nsay bye", FileFormat::ALL[3]),
+            ("say hi", FileFormat::default()),
+        ] {
+            // When I detect the format and normalize the text
+            let format = FileFormat::detect(contents);
+            let normalized = FileFormat::normalize(contents);
+
+            // Then the format should be found, and applying it again should give back the original file
+            assert_eq!(format, expected, "Wrong format for {:?}", contents);
+            assert!(!normalized.contains(['\r', BOM]));
+            assert_eq!(format.apply(&normalized), contents);
+        }
+    }
+}
@@ -17,10 +17,15 @@ use iced::advanced::text::highlighter::Format;
 use mc_version::MinecraftVersion;
 use once_cell::sync::Lazy;
 use syntect::{highlighting, parsing};
+use crate::application::gui::high_contrast;
 use crate::data::domain::versions;
 
 static THEMES: Lazy<highlighting::ThemeSet> =
-    Lazy::new(highlighting::ThemeSet::load_defaults);
+    Lazy::new(|| {
+        let mut themes = highlighting::ThemeSet::load_defaults();
+        themes.themes.insert(high_contrast::THEME_KEY.to_string(), high_contrast::highlighting_theme());
+        themes
+    });
 
 const LINES_PER_SNAPSHOT: usize = 50;
 
@@ -124,10 +129,13 @@ fn get_syntax_set_for_version(version: MinecraftVersion) -> &'static parsing::Sy
 #[derive(Debug)]
 pub(crate) struct MinecraftHighlighter {
     version: MinecraftVersion,
+    theme: Theme,
+    token: String,
     syntax: &'static parsing::SyntaxReference,
     highlighter: highlighting::Highlighter<'static>,
     caches: Vec<(parsing::ParseState, parsing::ScopeStack)>,
     current_line: usize,
+    matching_brackets: Vec<(usize, usize)>,
 }
 
 impl MinecraftHighlighter {
@@ -167,16 +175,39 @@ impl iced::advanced::text::highlighter::Highlighter for MinecraftHighlighter {
         
         MinecraftHighlighter {
             version,
+            theme: settings.theme,
+            token: settings.token.clone(),
             syntax,
             highlighter,
             caches: vec![(parser, stack)],
             current_line: 0,
+            matching_brackets: settings.matching_brackets.clone(),
         }
     }
     
     fn update(&mut self, new_settings: &Self::Settings) {
         let version = new_settings.version.clone();
 
+        // Only the matching brackets moved, so only the lines from the first one they touch need highlighting again
+        if version == self.version && new_settings.theme == self.theme && new_settings.token == self.token {
+            let first_changed_line = self.matching_brackets.iter()
+                .chain(new_settings.matching_brackets.iter())
+                .map(|(line, _)| *line)
+                .min();
+
+            self.matching_brackets = new_settings.matching_brackets.clone();
+
+            if let Some(line) = first_changed_line.filter(|line| *line < self.current_line) {
+                self.change_line(line);
+            }
+
+            return;
+        }
+
+        self.version = version;
+        self.theme = new_settings.theme;
+        self.token = new_settings.token.clone();
+
         let syntax_set = get_syntax_set_for_version(version);
         self.syntax = syntax_set.find_syntax_by_token(&new_settings.token).expect(format!("Failed to find syntax for token {}", new_settings.token).as_str());
         
@@ -184,6 +215,8 @@ impl iced::advanced::text::highlighter::Highlighter for MinecraftHighlighter {
             &THEMES.themes[new_settings.theme.key()],
         );
         
+        self.matching_brackets = new_settings.matching_brackets.clone();
+        
         // Restart the highlighter
         self.change_line(0);
     }
@@ -218,6 +251,7 @@ impl iced::advanced::text::highlighter::Highlighter for MinecraftHighlighter {
             self.caches.push((parser.clone(), stack.clone()));
         }
         
+        let line_index = self.current_line;
         self.current_line += 1;
         
         let (parser, stack) =
@@ -227,6 +261,11 @@ impl iced::advanced::text::highlighter::Highlighter for MinecraftHighlighter {
         
         let highlighter = &self.highlighter;
         
+        let bracket_columns: Vec<usize> = self.matching_brackets.iter()
+            .filter(|(line, _)| *line == line_index)
+            .map(|(_, column)| *column)
+            .collect();
+        
         Box::new(
             ScopeRangeIterator {
                 ops,
@@ -247,7 +286,8 @@ impl iced::advanced::text::highlighter::Highlighter for MinecraftHighlighter {
                             ),
                         ))
                     }
-                }),
+                })
+                .flat_map(move |(range, highlight)| split_brackets(range, highlight, &bracket_columns)),
         )
     }
     
@@ -256,17 +296,48 @@ impl iced::advanced::text::highlighter::Highlighter for MinecraftHighlighter {
     }
 }
 
+/// Splits out any matched brackets within `range` so they can be emphasized
+fn split_brackets(range: Range<usize>, highlight: Highlight, bracket_columns: &[usize]) -> Vec<(Range<usize>, Highlight)> {
+    let mut ranges = Vec::new();
+    let mut start = range.start;
+    
+    for column in bracket_columns.iter().copied().filter(|column| range.contains(column)) {
+        if start < column {
+            ranges.push((start..column, Highlight(highlight.0)));
+        }
+        
+        ranges.push((column..column + 1, highlight.emphasized()));
+        start = column + 1;
+    }
+    
+    if start < range.end {
+        ranges.push((start..range.end, highlight));
+    }
+    
+    ranges
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Settings {
     pub version: MinecraftVersion,
     pub theme: Theme,
     pub token: String,
+    /// Positions of the bracket at the cursor and its match, as `(line, byte column)`
+    pub matching_brackets: Vec<(usize, usize)>,
 }
 
 #[derive(Debug)]
 pub struct Highlight(highlighting::StyleModifier);
 
 impl Highlight {
+    /// Returns this [`Highlight`] in bold, used to mark matching brackets
+    fn emphasized(&self) -> Self {
+        Highlight(highlighting::StyleModifier {
+            font_style: Some(highlighting::FontStyle::BOLD),
+            ..self.0
+        })
+    }
+    
     /// Returns the color of this ['Highlight']
     /// If `None`, the original text color should be unchanged.
     pub fn color(&self) -> Option<Color> {
@@ -325,6 +396,7 @@ pub enum Theme {
     Base16Ocean,
     Base16Eighties,
     InspiredGitHub,
+    HighContrast,
 }
 
 impl Theme {
@@ -335,6 +407,7 @@ impl Theme {
         Self::Base16Ocean,
         Self::Base16Eighties,
         Self::InspiredGitHub,
+        Self::HighContrast,
     ];
     
     /// Returns `true` if the [`Theme`] is dark, and false otherwise.
@@ -343,7 +416,8 @@ impl Theme {
             Self::SolarizedDark
             | Self::Base16Mocha
             | Self::Base16Ocean
-            | Self::Base16Eighties => true,
+            | Self::Base16Eighties
+            | Self::HighContrast => true,
             Self::InspiredGitHub => false,
         }
     }
@@ -355,6 +429,7 @@ impl Theme {
             Theme::Base16Ocean => "base16-ocean.dark",
             Theme::Base16Eighties => "base16-eighties.dark",
             Theme::InspiredGitHub => "InspiredGitHub",
+            Theme::HighContrast => high_contrast::THEME_KEY,
         }
     }
 }
@@ -367,6 +442,7 @@ impl std::fmt::Display for Theme {
             Theme::Base16Ocean => write!(f, "Ocean"),
             Theme::Base16Eighties => write!(f, "Eighties"),
             Theme::InspiredGitHub => write!(f, "Inspired GitHub"),
+            Theme::HighContrast => write!(f, "High Contrast"),
         }
     }
 }
@@ -0,0 +1,245 @@
+use std::fmt::{Display, Formatter};
+use iced::widget::text_editor::Binding;
+
+/// How indentation is inserted, configurable per file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndentSettings {
+    pub tab_width: usize,
+    /// Whether Tab inserts spaces instead of a tab character
+    pub soft_tabs: bool,
+}
+
+impl Default for IndentSettings {
+    fn default() -> Self {
+        Self {
+            tab_width: 4,
+            soft_tabs: true,
+        }
+    }
+}
+
+impl IndentSettings {
+    /// The settings offered in the editor's status bar
+    pub const PRESETS: &'static [IndentSettings] = &[
+        IndentSettings { tab_width: 2, soft_tabs: true },
+        IndentSettings { tab_width: 4, soft_tabs: true },
+        IndentSettings { tab_width: 4, soft_tabs: false },
+    ];
+
+    /// Defaults following each file type's usual conventions
+    pub fn for_extension(extension: &str) -> Self {
+        match extension {
+            "json" | "mcmeta" => Self { tab_width: 2, soft_tabs: true },
+            _ => Self::default(),
+        }
+    }
+
+    /// The whitespace inserted for a single level of indentation
+    pub fn indent_unit(&self) -> String {
+        if self.soft_tabs {
+            " ".repeat(self.tab_width)
+        } else {
+            "\t".to_string()
+        }
+    }
+
+    /// Editor binding for the Tab key, inserting one level of indentation
+    pub fn tab_binding<Message>(&self) -> Binding<Message> {
+        Binding::Sequence(self.indent_unit().chars().map(Binding::Insert).collect())
+    }
+
+    /// Editor binding for the Enter key, breaking the line and inserting `indent` on the new line
+    pub fn newline_binding<Message>(indent: &str) -> Binding<Message> {
+        Binding::Sequence(
+            std::iter::once(Binding::Enter)
+                .chain(indent.chars().map(Binding::Insert))
+                .collect()
+        )
+    }
+
+    /// Removes one level of indentation from the end of `indent`
+    fn dedent(&self, indent: &str) -> String {
+        if let Some(dedented) = indent.strip_suffix(self.indent_unit().as_str()) {
+            return dedented.to_string();
+        }
+
+        if let Some(dedented) = indent.strip_suffix('\t') {
+            return dedented.to_string();
+        }
+
+        let trailing_spaces = indent.len() - indent.trim_end_matches(' ').len();
+        indent[..indent.len() - trailing_spaces.min(self.tab_width)].to_string()
+    }
+}
+
+/// Indent settings labelled for the status bar, such as `Spaces: 2` or `Tabs`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndentChoice {
+    pub settings: IndentSettings,
+    label: String,
+}
+
+impl IndentChoice {
+    pub fn new(settings: IndentSettings, spaces: &str, tabs: &str) -> Self {
+        let label = if settings.soft_tabs {
+            format!("{}: {}", spaces, settings.tab_width)
+        } else {
+            tabs.to_string()
+        };
+
+        Self { settings, label }
+    }
+}
+
+impl Display for IndentChoice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// Computes the indentation for a new line when breaking a line at the cursor.
+/// The current line's leading whitespace is carried over, and for JSON the indent increases after an opening
+/// bracket and decreases when the cursor is before a closing bracket
+pub fn newline_indent(before_cursor: &str, after_cursor: &str, extension: &str, settings: &IndentSettings) -> String {
+    let mut indent: String = before_cursor.chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+
+    if !matches!(extension, "json" | "mcmeta") {
+        return indent;
+    }
+
+    if before_cursor.trim_end().ends_with(['{', '[']) {
+        indent.push_str(&settings.indent_unit());
+    }
+
+    if after_cursor.trim_start().starts_with(['}', ']']) {
+        indent = settings.dedent(&indent);
+    }
+
+    indent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inserted_text(binding: Binding<()>) -> String {
+        match binding {
+            Binding::Sequence(bindings) => bindings.into_iter()
+                .map(|binding| match binding {
+                    Binding::Insert(c) => c,
+                    _ => panic!("Tab should only insert characters"),
+                })
+                .collect(),
+            _ => panic!("Tab should produce a sequence of bindings"),
+        }
+    }
+
+    #[test]
+    fn test_soft_tabs_insert_spaces() {
+        // Given soft tabs with a width of 4
+        let settings = IndentSettings { tab_width: 4, soft_tabs: true };
+
+        // When I press Tab
+        let inserted = inserted_text(settings.tab_binding());
+
+        // Then 4 spaces should be inserted
+        assert_eq!(inserted, "    ");
+    }
+
+    #[test]
+    fn test_soft_tabs_configured_width() {
+        // Given soft tabs with a width of 2
+        let settings = IndentSettings { tab_width: 2, soft_tabs: true };
+
+        // When I press Tab
+        let inserted = inserted_text(settings.tab_binding());
+
+        // Then 2 spaces should be inserted
+        assert_eq!(inserted, "  ");
+    }
+
+    #[test]
+    fn test_hard_tabs_insert_tab() {
+        // Given hard tabs
+        let settings = IndentSettings { tab_width: 4, soft_tabs: false };
+
+        // When I press Tab
+        let inserted = inserted_text(settings.tab_binding());
+
+        // Then a single tab character should be inserted
+        assert_eq!(inserted, "\t");
+    }
+
+    mod newline {
+        use rstest::rstest;
+        use super::*;
+
+        const SETTINGS: IndentSettings = IndentSettings { tab_width: 2, soft_tabs: true };
+
+        #[rstest]
+        #[case::plain_line("  \"key\": 1,", "", "  ")]
+        #[case::unindented_line("\"key\": 1,", "", "")]
+        #[case::after_opening_brace("  \"key\": {", "", "    ")]
+        #[case::after_opening_bracket("  \"values\": [", "", "    ")]
+        #[case::trailing_whitespace_after_brace("{  ", "", "  ")]
+        #[case::before_closing_brace("    \"key\": 1", "}", "  ")]
+        #[case::between_braces("  {", "}", "  ")]
+        #[case::before_closing_bracket_at_root("", "]", "")]
+        fn test_json_newline_indent(#[case] before_cursor: &str, #[case] after_cursor: &str, #[case] expected: &str) {
+            // Given a JSON line split at the cursor
+            // When I compute the indent for a new line
+            let indent = newline_indent(before_cursor, after_cursor, "json", &SETTINGS);
+
+            // Then it should follow the surrounding brackets
+            assert_eq!(indent, expected);
+        }
+
+        #[test]
+        fn test_mcfunction_newline_keeps_indent() {
+            // Given an indented mcfunction line which ends in a brace
+            let before_cursor = "\tdata merge entity @s {";
+
+            // When I compute the indent for a new line
+            let indent = newline_indent(before_cursor, "", "mcfunction", &SETTINGS);
+
+            // Then only the existing whitespace should be carried over
+            assert_eq!(indent, "\t");
+        }
+
+        #[test]
+        fn test_dedent_hard_tabs() {
+            // Given hard tabs
+            let settings = IndentSettings { tab_width: 4, soft_tabs: false };
+
+            // When I break a line before a closing brace
+            let indent = newline_indent("\t\t\"key\": 1", "}", "json", &settings);
+
+            // Then one tab should be removed
+            assert_eq!(indent, "\t");
+        }
+    }
+
+    #[test]
+    fn test_extension_defaults() {
+        // Given JSON and mcfunction files
+        // When I get their defaults
+        // Then they should follow each format's conventions
+        assert_eq!(IndentSettings::for_extension("json"), IndentSettings { tab_width: 2, soft_tabs: true });
+        assert_eq!(IndentSettings::for_extension("mcfunction"), IndentSettings { tab_width: 4, soft_tabs: true });
+    }
+
+    #[test]
+    fn test_indent_choice_labels() {
+        // Given each preset
+        // When I label them
+        let labels: Vec<_> = IndentSettings::PRESETS.iter()
+            .map(|settings| IndentChoice::new(*settings, "Spaces", "Tabs").to_string())
+            .collect();
+
+        // Then soft tabs should show their width, and hard tabs shouldn't
+        assert_eq!(labels, vec!["Spaces: 2", "Spaces: 4", "Tabs"]);
+    }
+
+}
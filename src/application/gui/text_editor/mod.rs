@@ -1,19 +1,48 @@
 // Copyright 2019 Héctor Ramón, Iced contributors
 // This code is licensed under MIT license (see third-party-licenses/LICENSE-MIT or https://opensource.org/licenses/MIT)
 
+use std::collections::HashMap;
 use std::ffi;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use iced::{Center, Element, Fill, keyboard, Task, widget, Font};
-use iced::widget::{Column, horizontal_space, row, Row, text, text_editor, toggler};
+use iced::widget::{button, Column, horizontal_space, pick_list, row, Row, text, text_editor, toggler};
 
+use crate::application::app_context::TranslationServiceContext;
 use crate::application::gui::widgets::icons::{action, Icon, NEW_ICON, OPEN_ICON, SAVE_ICON};
+use crate::application::gui::file_tree;
+use crate::application::gui::notifications::Notification;
+use crate::application::gui::tabs::TabContent;
 use crate::application::gui::window;
+use crate::data::domain::completion::{self, CompletionCandidate, CompletionSource, ResourceCompletion};
+use crate::data::domain::project_index::ProjectIndex;
+use crate::data::domain::vanilla_registry::VANILLA_REGISTRY;
 use crate::data::domain::versions;
+use crate::data::serialization::project::PACK_INFO_FILE;
+use crate::data::validation::{self, ValidationError, ValidationIssue};
+use crate::application::gui::text_editor::counts::TextCounts;
+use crate::application::gui::text_editor::definition::DefinitionError;
+use crate::application::gui::text_editor::file_format::FileFormat;
+use crate::application::gui::text_editor::indent::{IndentChoice, IndentSettings};
+use crate::application::gui::text_editor::save_check::SaveValidation;
+use crate::services::dialog_service::{DefaultDialogProvider, DialogProvider};
+use crate::services::filesystem_service::FilesystemService;
+use crate::services::translation_service::TranslationKey;
 
+pub mod brackets;
+pub mod breadcrumbs;
+pub mod counts;
+pub mod definition;
+pub mod file_format;
 pub mod highlighter;
+pub mod indent;
+pub mod save_check;
+
+/// How many completion suggestions are offered at once
+const MAX_COMPLETIONS: usize = 8;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -21,31 +50,100 @@ pub enum Message {
     WordWrapToggled(bool),
     NewFile,
     OpenFile,
+    OpenPath(PathBuf),
     FileOpened(Result<(PathBuf, Arc<String>), Error>),
     SaveFile,
+    /// The text to save, with whatever checking it found. Unchecked saves arrive with no problems
+    SaveChecked(String, Vec<ValidationError>),
     FileSaved(Result<PathBuf, Error>),
     ThemeChanged(highlighter::Theme),
+    /// Boxed, as the translations are much larger than any other message
+    TranslationsUpdated(Box<EditorTranslations>),
+    /// Overrides the indent settings for files with the given extension
+    IndentSettingsChanged(String, IndentSettings),
+    /// Opens the file defining the resource location under the cursor
+    GoToDefinition,
+    DefinitionFound(Result<PathBuf, DefinitionError>),
+    /// The shown project's root and index once it has been built, or `None` when there isn't one
+    ProjectIndexChanged(Option<(PathBuf, Arc<ProjectIndex>)>),
+    /// Set while the open project is read-only, which stops edits and saves
+    ReadOnlyChanged(bool),
+    /// Whether files are checked before saving, and whether errors stop the save
+    SaveValidationChanged(SaveValidation),
+    /// Changes the encoding and line endings the open file is saved with
+    FileFormatSelected(FileFormat),
+    /// Shows how many words, lines and characters are in the selection, or the whole file if nothing is selected
+    CountText,
+    /// Suggests resource locations for the one being typed at the cursor
+    RequestCompletion,
+    /// Replaces the resource location being typed with a suggestion
+    CompletionChosen(CompletionCandidate),
+    /// Inserts an opening character and its closing pair, leaving the cursor between them
+    AutoClosePair(char, char),
+    /// Moves past an auto-inserted closing character instead of typing another
+    SkipOverClosing,
 }
 
 pub struct TextEditor {
     theme: highlighter::Theme,
     file: Option<PathBuf>,
     content: text_editor::Content,
+    file_format: FileFormat,
     word_wrap: bool,
     is_loading: bool,
     is_dirty: bool,
+    /// Hash of the content as it was last opened or saved, in its file format, so reverting edits clears `is_dirty`
+    saved_content_hash: u64,
+    /// Hash of the content sent to be saved, applied once the save completes
+    pending_save_hash: Option<u64>,
+    translations: EditorTranslations,
+    indent_overrides: HashMap<String, IndentSettings>,
+    /// Explains why the last action couldn't be completed, e.g. an unresolved go-to-definition
+    status_message: Option<String>,
+    read_only: bool,
+    save_validation: SaveValidation,
+    /// Root and index of the shown project, which go-to-definition looks references up in before searching the disk
+    project_index: Option<(PathBuf, Arc<ProjectIndex>)>,
+    /// The resource location being typed and the suggestions for it, until the next edit or cursor move
+    completions: Option<(String, Vec<CompletionCandidate>)>,
+    /// Asks which file to open, and where to save new files
+    dialogs: Arc<dyn DialogProvider>,
+    /// Positions of the bracket at the cursor and its match, as `(line, byte column)`
+    matching_brackets: Vec<(usize, usize)>,
+    /// Positions of closing characters inserted by auto-closing, which typing the same character moves past
+    auto_closed: Vec<(usize, usize)>,
 }
 
 impl<'a> TextEditor {
+    pub(crate) fn new(theme: highlighter::Theme, translations: EditorTranslations) -> Self {
+        Self {
+            theme,
+            file: None,
+            content: text_editor::Content::new(),
+            file_format: FileFormat::default(),
+            word_wrap: true,
+            is_loading: false,
+            is_dirty: false,
+            saved_content_hash: content_hash(""),
+            pending_save_hash: None,
+            translations,
+            indent_overrides: HashMap::new(),
+            status_message: None,
+            read_only: false,
+            save_validation: SaveValidation::default(),
+            project_index: None,
+            completions: None,
+            dialogs: Arc::new(DefaultDialogProvider::new()),
+            matching_brackets: Vec::new(),
+            auto_closed: Vec::new(),
+        }
+    }
+    
     pub(crate) fn with_task(theme: highlighter::Theme) -> (Self, Task<window::Message>) {
         (
             Self {
-                theme,
-                file: None,
-                content: text_editor::Content::new(),
-                word_wrap: true,
                 is_loading: true,
-                is_dirty: false,
+                ..Self::new(theme, EditorTranslations::default())
             },
             Task::batch([
                 Task::perform(
@@ -63,9 +161,8 @@ impl<'a> TextEditor {
     pub(crate) fn update(&mut self, message: Message) -> Task<window::Message> {
         match message {
             Message::ActionPerformed(action) => {
-                self.is_dirty = self.is_dirty || action.is_edit();
-                
-                self.content.perform(action);
+                self.completions = None;
+                self.perform([action]);
                 
                 Task::none()
             }
@@ -77,7 +174,10 @@ impl<'a> TextEditor {
             Message::NewFile => {
                 if !self.is_loading {
                     self.file = None;
-                    self.content = text_editor::Content::new();
+                    self.set_content(text_editor::Content::new());
+                    self.file_format = FileFormat::default();
+                    self.saved_content_hash = content_hash("");
+                    self.is_dirty = false;
                 }
                 
                 Task::none()
@@ -88,27 +188,49 @@ impl<'a> TextEditor {
                 } else {
                     self.is_loading = true;
                     
-                    Task::perform(open_file(), |result| Message::FileOpened(result).into())
+                    Task::perform(open_file(self.dialogs.clone()), |result| Message::FileOpened(result).into())
+                }
+            }
+            Message::OpenPath(path) => {
+                if self.is_loading {
+                    Task::none()
+                } else {
+                    self.is_loading = true;
+                    
+                    Task::perform(load_file(path), |result| Message::FileOpened(result).into())
                 }
             }
             Message::FileOpened(result) => {
                 self.is_loading = false;
                 self.is_dirty = false;
                 
-                if let Ok((path, contents)) = result {
-                    self.file = Some(path);
-                    self.content = text_editor::Content::with_text(&contents);
+                match result {
+                    Ok((path, contents)) => {
+                        self.status_message = None;
+                        self.file = Some(path);
+                        self.file_format = FileFormat::detect(&contents);
+                        self.set_content(text_editor::Content::with_text(&FileFormat::normalize(&contents)));
+                        self.saved_content_hash = self.formatted_content_hash();
+                        self.status_message = self.file_problems();
+                    }
+                    Err(error) => {
+                        self.status_message = error.message(&self.translations);
+                        
+                        if let Some(message) = self.status_message.clone() {
+                            return Task::done(window::Message::Notify(Notification::error(message)));
+                        }
+                    }
                 }
                 
                 Task::none()
             }
             Message::SaveFile => {
-                if self.is_loading {
+                if self.is_loading || self.read_only {
                     Task::none()
                 } else {
                     self.is_loading = true;
                     
-                    let mut text = self.content.text();
+                    let text = self.file_format.apply(&self.content.text());
                     /*
                     if let Some(ending) = self.content.line_ending() {
                         if !text.ends_with(ending.as_str()) {
@@ -116,43 +238,477 @@ impl<'a> TextEditor {
                         }
                     }
                     */
+                    if self.save_validation == SaveValidation::Off {
+                        return self.update(Message::SaveChecked(text, Vec::new()));
+                    }
+                    
+                    let path = self.file.clone();
                     Task::perform(
-                        save_file(self.file.clone(), text),
-                        |result| Message::FileSaved(result).into(),
+                        async move {
+                            let problems = save_check::check(path, text.clone()).await;
+                            (text, problems)
+                        },
+                        |(text, problems)| Message::SaveChecked(text, problems).into(),
                     )
                 }
             }
+            Message::SaveChecked(text, problems) => {
+                if !self.save_validation.allows(&problems) {
+                    return self.update(Message::FileSaved(Err(Error::Invalid(problems))));
+                }
+                
+                self.pending_save_hash = Some(content_hash(&text));
+                self.status_message = self.file_problems();
+                
+                let save = Task::perform(
+                    save_file(self.dialogs.clone(), self.file.clone(), text),
+                    |result| Message::FileSaved(result).into(),
+                );
+                
+                if problems.is_empty() {
+                    save
+                } else {
+                    let problems: Vec<_> = problems.iter().map(ToString::to_string).collect();
+                    let message = format!("{}: {}", self.translations.saved_with_problems, problems.join("; "));
+                    
+                    Task::batch([save, Task::done(window::Message::Notify(Notification::info(message)))])
+                }
+            }
             Message::FileSaved(result) => {
                 self.is_loading = false;
                 
-                if let Ok(path) = result {
-                    self.file = Some(path);
-                    self.is_dirty = false;
-                }
+                let pending_save_hash = self.pending_save_hash.take();
                 
+                match result {
+                    Ok(path) => {
+                        let message = format!("{}: {}", self.translations.file_saved, path.display());
+                        self.file = Some(path);
+                        
+                        if let Some(saved_content_hash) = pending_save_hash {
+                            self.saved_content_hash = saved_content_hash;
+                        }
+                        
+                        // Edits made while the save was in flight still count as unsaved
+                        self.update_dirty();
+                        
+                        Task::done(window::Message::Notify(Notification::success(message)))
+                    }
+                    Err(error) => {
+                        self.status_message = error.message(&self.translations);
+                        
+                        match self.status_message.clone() {
+                            Some(message) => Task::done(window::Message::Notify(Notification::error(message))),
+                            None => Task::none(),
+                        }
+                    }
+                }
+            }
+            Message::ThemeChanged(theme) => {
+                self.theme = theme;
+
+                Task::none()
+            }
+            Message::TranslationsUpdated(translations) => {
+                self.translations = *translations;
+
+                Task::none()
+            }
+            Message::IndentSettingsChanged(extension, settings) => {
+                self.indent_overrides.insert(extension, settings);
+
+                Task::none()
+            }
+            Message::GoToDefinition => {
+                let Some(file) = self.file.clone() else {
+                    self.status_message = Some(DefinitionError::NotInPack.to_string());
+                    return Task::none();
+                };
+
+                let (line, column) = self.content.cursor_position();
+                let line = self.content.line(line).map(|line| line.to_string()).unwrap_or_default();
+                let project_index = self.project_index.clone();
+
+                Task::perform(
+                    async move {
+                        let filesystem = FilesystemService::new();
+                        let index = project_index.as_ref().map(|(root, index)| (root.as_path(), &**index));
+                        definition::find_definition(&filesystem, index, &file, &line, column).await
+                    },
+                    |result| Message::DefinitionFound(result).into(),
+                )
+            }
+            Message::ReadOnlyChanged(read_only) => {
+                self.read_only = read_only;
+
+                Task::none()
+            }
+            Message::ProjectIndexChanged(project_index) => {
+                self.project_index = project_index;
+
+                Task::none()
+            }
+            Message::SaveValidationChanged(save_validation) => {
+                self.save_validation = save_validation;
+
+                Task::none()
+            }
+            Message::FileFormatSelected(file_format) => {
+                if !self.read_only {
+                    self.file_format = file_format;
+                    self.update_dirty();
+                }
+
+                Task::none()
+            }
+            Message::CountText => {
+                let (counts, is_selection) = self.text_counts();
+
+                let mut message = format!(
+                    "{}: {} · {}: {} · {}: {}",
+                    self.translations.words, counts.words,
+                    self.translations.lines, counts.lines,
+                    self.translations.characters, counts.characters,
+                );
+                if is_selection {
+                    message = format!("{}: {}", self.translations.selection, message);
+                }
+
+                Task::done(window::Message::Notify(Notification::info(message)))
+            }
+            Message::RequestCompletion => {
+                if self.read_only {
+                    return Task::none();
+                }
+
+                let (before_cursor, _) = self.cursor_context();
+                let Some((prefix, context)) = completion::context_at(&before_cursor, self.file.as_deref()) else {
+                    self.status_message = Some(self.translations.no_completions.clone());
+                    return Task::none();
+                };
+
+                let index = self.project_index.as_ref().map(|(_, index)| &**index);
+                let mut candidates = ResourceCompletion::new(&VANILLA_REGISTRY, index).complete(&prefix, &context);
+                candidates.truncate(MAX_COMPLETIONS);
+
+                match candidates.len() {
+                    0 => self.status_message = Some(self.translations.no_completions.clone()),
+                    // Nothing to choose between, so it goes straight in
+                    1 => {
+                        let candidate = candidates.remove(0);
+                        self.completions = Some((prefix, Vec::new()));
+                        return self.update(Message::CompletionChosen(candidate));
+                    }
+                    _ => self.completions = Some((prefix, candidates)),
+                }
+
+                Task::none()
+            }
+            Message::CompletionChosen(candidate) => {
+                let Some((prefix, _)) = self.completions.take() else {
+                    return Task::none();
+                };
+
+                let backspaces = prefix.chars().map(|_| text_editor::Action::Edit(text_editor::Edit::Backspace));
+                let paste = text_editor::Action::Edit(text_editor::Edit::Paste(Arc::new(candidate.text())));
+                self.perform(backspaces.chain([paste]));
+
                 Task::none()
             }
-            Message::ThemeChanged(_) => todo!(),
+            Message::AutoClosePair(open, close) => {
+                self.completions = None;
+                if self.read_only {
+                    return Task::none();
+                }
+
+                self.perform([
+                    text_editor::Action::Edit(text_editor::Edit::Insert(open)),
+                    text_editor::Action::Edit(text_editor::Edit::Insert(close)),
+                    text_editor::Action::Move(text_editor::Motion::Left),
+                ]);
+                self.auto_closed.push(self.content.cursor_position());
+
+                Task::none()
+            }
+            Message::SkipOverClosing => {
+                self.completions = None;
+
+                let cursor = self.content.cursor_position();
+                self.auto_closed.retain(|position| *position != cursor);
+                self.perform([text_editor::Action::Move(text_editor::Motion::Right)]);
+
+                Task::none()
+            }
+            Message::DefinitionFound(result) => {
+                match result {
+                    // Goes through the window, which opens the definition in its own tab
+                    Ok(path) => Task::done(Message::OpenPath(path).into()),
+                    Err(error) => {
+                        self.status_message = Some(error.to_string());
+                        Task::none()
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) async fn translate(translation_service: TranslationServiceContext) -> EditorTranslations {
+        let translation_service = translation_service.read().await;
+
+        EditorTranslations {
+            new_file: translation_service.translate(&EditorTranslationKeys::NewFile),
+            open_file: translation_service.translate(&EditorTranslationKeys::OpenFile),
+            save_file: translation_service.translate(&EditorTranslationKeys::SaveFile),
+            word_wrap: translation_service.translate(&EditorTranslationKeys::WordWrap),
+            untitled_file: translation_service.translate(&EditorTranslationKeys::UntitledFile),
+            file_not_found: translation_service.translate(&EditorTranslationKeys::FileNotFound),
+            permission_denied: translation_service.translate(&EditorTranslationKeys::PermissionDenied),
+            open_failed: translation_service.translate(&EditorTranslationKeys::OpenFailed),
+            save_failed: translation_service.translate(&EditorTranslationKeys::SaveFailed),
+            file_saved: translation_service.translate(&EditorTranslationKeys::FileSaved),
+            no_open_files: translation_service.translate(&EditorTranslationKeys::NoOpenFiles),
+            count_text: translation_service.translate(&EditorTranslationKeys::CountText),
+            words: translation_service.translate(&EditorTranslationKeys::Words),
+            lines: translation_service.translate(&EditorTranslationKeys::Lines),
+            characters: translation_service.translate(&EditorTranslationKeys::Characters),
+            selection: translation_service.translate(&EditorTranslationKeys::Selection),
+            saved_with_problems: translation_service.translate(&EditorTranslationKeys::SavedWithProblems),
+            save_blocked: translation_service.translate(&EditorTranslationKeys::SaveBlocked),
+            no_completions: translation_service.translate(&EditorTranslationKeys::NoCompletions),
+            spaces: translation_service.translate(&EditorTranslationKeys::Spaces),
+            tabs: translation_service.translate(&EditorTranslationKeys::Tabs),
+        }
+    }
+    
+    /// Counts the selected text if there is any, or else the whole file, along with whether it was the selection
+    fn text_counts(&self) -> (TextCounts, bool) {
+        match self.content.selection() {
+            Some(selection) => (TextCounts::count(&selection), true),
+            None => {
+                // The editor's text ends every line with a line break, including the last
+                let text = self.content.text();
+                (TextCounts::count(text.strip_suffix('\n').unwrap_or(&text)), false)
+            }
+        }
+    }
+    
+    /// Performs editor actions, ignoring edits while read-only, and keeps everything tracked about the cursor up to date
+    fn perform(&mut self, actions: impl IntoIterator<Item = text_editor::Action>) {
+        let mut edited = false;
+
+        for action in actions {
+            let is_edit = action.is_edit();
+            if is_edit && self.read_only {
+                continue;
+            }
+
+            self.track_auto_closed(&action);
+            self.content.perform(action);
+            edited |= is_edit;
+        }
+
+        // Auto-inserted closing characters are only typed over while the cursor stays on their line
+        let (line, _) = self.content.cursor_position();
+        self.auto_closed.retain(|(closed_line, _)| *closed_line == line);
+
+        if edited {
+            self.update_dirty();
+        }
+        self.update_matching_brackets();
+    }
+
+    /// Moves the auto-inserted closing characters along with a typed character, or forgets them after any other edit
+    fn track_auto_closed(&mut self, action: &text_editor::Action) {
+        match action {
+            text_editor::Action::Edit(text_editor::Edit::Insert(c)) if self.content.selection().is_none() => {
+                let (line, column) = self.content.cursor_position();
+
+                for (closed_line, closed_column) in &mut self.auto_closed {
+                    if *closed_line == line && *closed_column >= column {
+                        *closed_column += c.len_utf8();
+                    }
+                }
+            }
+            text_editor::Action::Edit(_) => self.auto_closed.clear(),
+            _ => {}
+        }
+    }
+
+    /// Replaces the whole text, forgetting anything tracked about the old one
+    fn set_content(&mut self, content: text_editor::Content) {
+        self.content = content;
+        self.auto_closed.clear();
+        self.update_matching_brackets();
+    }
+
+    fn update_dirty(&mut self) {
+        self.is_dirty = self.formatted_content_hash() != self.saved_content_hash;
+    }
+    
+    /// Hash of the content as it would be saved, so changing only the file format also counts as unsaved
+    fn formatted_content_hash(&self) -> u64 {
+        content_hash(&self.file_format.apply(&self.content.text()))
+    }
+    
+    /// Describes what is wrong with the open file if it is a `pack.mcmeta`, or with the text components in any other JSON file.
+    /// Only errors are listed when there are any, since those stop the game loading the pack
+    fn file_problems(&self) -> Option<String> {
+        let is_pack_info = self.file.as_deref()
+            .and_then(Path::file_name)
+            .is_some_and(|name| name == PACK_INFO_FILE);
+        
+        let problems = if is_pack_info {
+            validation::pack_info::validate_str(&self.content.text())
+        } else if self.file.is_some() && self.extension() == "json" {
+            // Only the text components are checked here, not whether the file is well formed JSON
+            validation::text_component::validate_str(&self.content.text()).into_iter()
+                .filter(|problem| !matches!(problem.issue, ValidationIssue::InvalidJson(_)))
+                .collect()
+        } else {
+            return None;
+        };
+        
+        let has_errors = validation::has_errors(&problems);
+        
+        let shown: Vec<_> = problems.iter()
+            .filter(|problem| problem.is_error() || !has_errors)
+            .map(ToString::to_string)
+            .collect();
+        
+        (!shown.is_empty()).then(|| shown.join("; "))
+    }
+    
+    fn extension(&self) -> &str {
+        self.file
+            .as_deref()
+            .and_then(Path::extension)
+            .and_then(ffi::OsStr::to_str)
+            .unwrap_or("json")
+    }
+    
+    /// Indent settings for the open file, falling back to the defaults for its extension
+    pub(crate) fn indent_settings(&self) -> IndentSettings {
+        let extension = self.extension();
+        
+        self.indent_overrides.get(extension)
+            .copied()
+            .unwrap_or_else(|| IndentSettings::for_extension(extension))
+    }
+    
+    /// Changes the indentation of every open file with the same extension as this one
+    fn indent_picker(&self) -> Element<window::Message> {
+        let extension = self.extension().to_string();
+        let current = self.indent_settings();
+        let choices: Vec<_> = IndentSettings::PRESETS.iter()
+            .map(|settings| IndentChoice::new(*settings, &self.translations.spaces, &self.translations.tabs))
+            .collect();
+        let selected = choices.iter().find(|choice| choice.settings == current).cloned();
+        
+        pick_list(choices, selected, move |choice| Message::IndentSettingsChanged(extension.clone(), choice.settings).into())
+            .text_size(14)
+            .into()
+    }
+    
+    /// The cursor's line, split at the cursor
+    fn cursor_context(&self) -> (String, String) {
+        let (line, column) = self.content.cursor_position();
+        
+        let Some(line) = self.content.line(line) else {
+            return (String::new(), String::new());
+        };
+        
+        let column = (0..=column.min(line.len()))
+            .rev()
+            .find(|index| line.is_char_boundary(*index))
+            .unwrap_or(0);
+        let (before_cursor, after_cursor) = line.split_at(column);
+        
+        (before_cursor.to_string(), after_cursor.to_string())
+    }
+    
+    /// The suggestions for the resource location being typed, if there is a choice
+    fn completion_bar(&self) -> Option<Element<'_, window::Message>> {
+        let (_, candidates) = self.completions.as_ref().filter(|(_, candidates)| !candidates.is_empty())?;
+
+        let bar = candidates.iter().fold(Row::new(), |bar, candidate| {
+            bar.push(button(text(candidate.text()))
+                .style(match candidate.source {
+                    CompletionSource::Project => button::primary,
+                    CompletionSource::Vanilla => button::secondary,
+                })
+                .on_press(Message::CompletionChosen(candidate.clone()).into()))
+        });
+
+        Some(bar.spacing(5).into())
+    }
+    
+    /// Finds the bracket at the cursor and its match, if any, once the cursor has moved or the text changed
+    fn update_matching_brackets(&mut self) {
+        let (line, column) = self.content.cursor_position();
+        let text = self.content.text();
+        let lines: Vec<&str> = text.split('\n').collect();
+        
+        self.matching_brackets = brackets::find_matching_bracket(&lines, line, column)
+            .map(Vec::from)
+            .unwrap_or_default();
+    }
+    
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.is_dirty
+    }
+    
+    /// Whether a file is being opened or saved
+    pub(crate) fn is_loading(&self) -> bool {
+        self.is_loading
+    }
+    
+    pub(crate) fn with_save_validation(self, save_validation: SaveValidation) -> Self {
+        Self { save_validation, ..self }
+    }
+    
+    pub(crate) fn with_read_only(self, read_only: bool) -> Self {
+        Self { read_only, ..self }
+    }
+    
+    pub(crate) fn with_project_index(self, project_index: Option<(PathBuf, Arc<ProjectIndex>)>) -> Self {
+        Self { project_index, ..self }
+    }
+    
+    pub(crate) fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+    
+    /// The open file's name, for its tab
+    pub(crate) fn title(&self) -> String {
+        self.file.as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.translations.untitled_file.clone())
+    }
+
+    /// Keeps the tracked path in sync when the open file is renamed elsewhere, e.g. from the file tree
+    pub(crate) fn rename_open_file(&mut self, from: &Path, to: &Path) {
+        if self.file.as_deref() == Some(from) {
+            self.file = Some(to.to_path_buf());
         }
     }
     
     pub(crate) fn view(&self) -> Element<window::Message> {
         // Row macro didn't like external function calls
         let controls = Row::new()
-            .push(action(Icon::new(NEW_ICON), "New file", Some(Message::NewFile.into())))
+            .push(action(Icon::new(NEW_ICON), &self.translations.new_file, Some(Message::NewFile.into())))
             .push(action(
                 Icon::new(OPEN_ICON),
-                "Open file",
+                &self.translations.open_file,
                 (!self.is_loading).then_some(Message::OpenFile.into())
             ))
             .push(action(
                 Icon::new(SAVE_ICON),
-                "Save file",
-                self.is_dirty.then_some(Message::SaveFile.into())
+                &self.translations.save_file,
+                (self.is_dirty && !self.read_only).then_some(Message::SaveFile.into())
             ))
             .push(horizontal_space())
             .push(toggler(self.word_wrap)
-                .label("Word Wrap")
+                .label(self.translations.word_wrap.as_str())
                 .on_toggle(|toggled| Message::WordWrapToggled(toggled).into())
                 .text_size(14)
             )
@@ -170,16 +726,45 @@ impl<'a> TextEditor {
                     path
                 }
             } else {
-                String::from("New file")
+                self.translations.untitled_file.clone()
             }),
             horizontal_space(),
+            text(self.status_message.as_deref().unwrap_or_default()),
+            button(text(self.translations.count_text.as_str()))
+                .style(button::text)
+                .padding(0)
+                .on_press(Message::CountText.into()),
+            self.indent_picker(),
+            pick_list(FileFormat::ALL, Some(self.file_format), |file_format| Message::FileFormatSelected(file_format).into())
+                .text_size(14),
             text({
                 let (line, column) = self.content.cursor_position();
 
                 format!("{}:{}", line + 1, column + 1)
             })
         ]
-            .spacing(10);
+            .spacing(10)
+            .align_y(Center);
+        
+        let breadcrumbs = self.file.as_deref().map(breadcrumbs::breadcrumbs).unwrap_or_default();
+        let breadcrumb_bar = breadcrumbs.into_iter()
+            .enumerate()
+            .fold(Row::new(), |bar, (index, breadcrumb)| {
+                bar.push_maybe((index > 0).then(|| text("›")))
+                    .push(button(text(breadcrumb.label))
+                        .style(button::text)
+                        .padding(0)
+                        .on_press(file_tree::Message::FilterSelected(Some(breadcrumb.path)).into()))
+            })
+            .spacing(5)
+            .align_y(Center);
+        
+        let indent_settings = self.indent_settings();
+        let extension = self.extension().to_owned();
+        let (before_cursor, after_cursor) = self.cursor_context();
+        let newline_indent = indent::newline_indent(&before_cursor, &after_cursor, &extension, &indent_settings);
+        let key_extension = extension.clone();
+        let next_auto_closed = self.auto_closed.contains(&self.content.cursor_position());
         
         let text_editor = text_editor(&self.content)
             .height(Fill)
@@ -189,8 +774,26 @@ impl<'a> TextEditor {
             } else {
                 text::Wrapping::None
             })
-            .key_binding(|key_press| {
+            .key_binding(move |key_press| {
                 match key_press.key.as_ref() {
+                    keyboard::Key::Named(keyboard::key::Named::Tab)
+                    if key_press.status == text_editor::Status::Focused && !key_press.modifiers.shift() =>
+                        {
+                            Some(indent_settings.tab_binding())
+                        }
+                    keyboard::Key::Named(keyboard::key::Named::Enter)
+                    if key_press.status == text_editor::Status::Focused =>
+                        {
+                            Some(IndentSettings::newline_binding(&newline_indent))
+                        }
+                    keyboard::Key::Named(keyboard::key::Named::F12) => {
+                        Some(text_editor::Binding::Custom(Message::GoToDefinition.into()))
+                    }
+                    keyboard::Key::Named(keyboard::key::Named::Space)
+                    if key_press.modifiers.command() =>
+                        {
+                            Some(text_editor::Binding::Custom(Message::RequestCompletion.into()))
+                        }
                     keyboard::Key::Character("s")
                     if key_press.modifiers.command() =>
                         {
@@ -198,28 +801,40 @@ impl<'a> TextEditor {
                                 Message::SaveFile.into(),
                             ))
                         }
-                    _ => text_editor::Binding::from_key_press(key_press),
+                    _ => {
+                        let typed = key_press.text.as_ref()
+                            .and_then(|text| text.chars().find(|c| !c.is_control()))
+                            .filter(|_| key_press.status == text_editor::Status::Focused && !key_press.modifiers.command());
+                        
+                        let auto_close = typed.map(|typed| brackets::auto_close(typed, &before_cursor, &after_cursor, &key_extension, next_auto_closed));
+                        
+                        match auto_close {
+                            Some(brackets::AutoClose::InsertPair(open, close)) => {
+                                Some(text_editor::Binding::Custom(Message::AutoClosePair(open, close).into()))
+                            }
+                            Some(brackets::AutoClose::SkipOver) => {
+                                Some(text_editor::Binding::Custom(Message::SkipOverClosing.into()))
+                            }
+                            Some(brackets::AutoClose::Insert) | None => text_editor::Binding::from_key_press(key_press),
+                        }
+                    }
                 }
             })
             .font(Font::MONOSPACE);
         
-        let extension = self.file
-            .as_deref()
-            .and_then(Path::extension)
-            .and_then(ffi::OsStr::to_str)
-            .unwrap_or("json")
-            .to_owned();
-        
         Column::new()
                 .push(controls)
+                .push(breadcrumb_bar)
                 .push(text_editor.highlight_with::<highlighter::MinecraftHighlighter>(
                 highlighter::Settings {
                         version: versions::latest(),
                         theme: self.theme,
-                        token: extension
+                        token: extension,
+                        matching_brackets: self.matching_brackets.clone(),
                     },
                     |highlight, _theme| highlight.to_format()
                 ))
+                .push_maybe(self.completion_bar())
                 .push(status)
                 .spacing(10)
                 .padding(10)
@@ -227,16 +842,176 @@ impl<'a> TextEditor {
     }
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
+pub enum EditorTranslationKeys {
+    #[translation(en_us = "New file")]
+    NewFile,
+    #[translation(en_us = "Open file")]
+    OpenFile,
+    #[translation(en_us = "Save file")]
+    SaveFile,
+    #[translation(en_us = "Word Wrap")]
+    WordWrap,
+    #[translation(en_us = "New file")]
+    UntitledFile,
+    #[translation(en_us = "File not found")]
+    FileNotFound,
+    #[translation(en_us = "Permission denied")]
+    PermissionDenied,
+    #[translation(en_us = "Could not open file")]
+    OpenFailed,
+    #[translation(en_us = "Could not save file")]
+    SaveFailed,
+    #[translation(en_us = "File saved")]
+    FileSaved,
+    #[translation(en_us = "No files are open")]
+    NoOpenFiles,
+    #[translation(en_us = "Count words")]
+    CountText,
+    #[translation(en_us = "Words")]
+    Words,
+    #[translation(en_us = "Lines")]
+    Lines,
+    #[translation(en_us = "Characters")]
+    Characters,
+    #[translation(en_us = "Selection")]
+    Selection,
+    #[translation(en_us = "Saving despite problems")]
+    SavedWithProblems,
+    #[translation(en_us = "Not saved, as the file has errors")]
+    SaveBlocked,
+    #[translation(en_us = "Nothing to complete here")]
+    NoCompletions,
+    #[translation(en_us = "Spaces")]
+    Spaces,
+    #[translation(en_us = "Tabs")]
+    Tabs,
+}
+
 #[derive(Debug, Clone)]
+pub struct EditorTranslations {
+    pub new_file: String,
+    pub open_file: String,
+    pub save_file: String,
+    pub word_wrap: String,
+    pub untitled_file: String,
+    pub file_not_found: String,
+    pub permission_denied: String,
+    pub open_failed: String,
+    pub save_failed: String,
+    pub file_saved: String,
+    pub no_open_files: String,
+    pub count_text: String,
+    pub words: String,
+    pub lines: String,
+    pub characters: String,
+    pub selection: String,
+    pub saved_with_problems: String,
+    pub save_blocked: String,
+    pub no_completions: String,
+    pub spaces: String,
+    pub tabs: String,
+}
+
+impl Default for EditorTranslations {
+    /// Falls back to the English copy until the translations have loaded
+    fn default() -> Self {
+        Self {
+            new_file: EditorTranslationKeys::NewFile.english_text().to_string(),
+            open_file: EditorTranslationKeys::OpenFile.english_text().to_string(),
+            save_file: EditorTranslationKeys::SaveFile.english_text().to_string(),
+            word_wrap: EditorTranslationKeys::WordWrap.english_text().to_string(),
+            untitled_file: EditorTranslationKeys::UntitledFile.english_text().to_string(),
+            file_not_found: EditorTranslationKeys::FileNotFound.english_text().to_string(),
+            permission_denied: EditorTranslationKeys::PermissionDenied.english_text().to_string(),
+            open_failed: EditorTranslationKeys::OpenFailed.english_text().to_string(),
+            save_failed: EditorTranslationKeys::SaveFailed.english_text().to_string(),
+            file_saved: EditorTranslationKeys::FileSaved.english_text().to_string(),
+            no_open_files: EditorTranslationKeys::NoOpenFiles.english_text().to_string(),
+            count_text: EditorTranslationKeys::CountText.english_text().to_string(),
+            words: EditorTranslationKeys::Words.english_text().to_string(),
+            lines: EditorTranslationKeys::Lines.english_text().to_string(),
+            characters: EditorTranslationKeys::Characters.english_text().to_string(),
+            selection: EditorTranslationKeys::Selection.english_text().to_string(),
+            saved_with_problems: EditorTranslationKeys::SavedWithProblems.english_text().to_string(),
+            save_blocked: EditorTranslationKeys::SaveBlocked.english_text().to_string(),
+            no_completions: EditorTranslationKeys::NoCompletions.english_text().to_string(),
+            spaces: EditorTranslationKeys::Spaces.english_text().to_string(),
+            tabs: EditorTranslationKeys::Tabs.english_text().to_string(),
+        }
+    }
+}
+
+impl TabContent for TextEditor {
+    fn is_dirty(&self) -> bool {
+        self.is_dirty
+    }
+}
+
+fn content_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileOperation {
+    Open,
+    Save,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     DialogClosed,
-    IoError(io::ErrorKind),
+    Io {
+        operation: FileOperation,
+        path: PathBuf,
+        kind: io::ErrorKind,
+        /// The underlying error's own description, which is not localized
+        details: String,
+    },
+    /// Checking the file before saving found errors, and saves with errors are blocked
+    Invalid(Vec<ValidationError>),
 }
 
-async fn open_file() -> Result<(PathBuf, Arc<String>), Error> {
-    let picked_file = rfd::AsyncFileDialog::new()
-        .set_title("Open a text file...")
-        .pick_file()
+impl Error {
+    fn io(operation: FileOperation, path: &Path, error: io::Error) -> Self {
+        Error::Io {
+            operation,
+            path: path.to_path_buf(),
+            kind: error.kind(),
+            details: error.to_string(),
+        }
+    }
+
+    /// A localized explanation of the error for the user, or `None` if there is nothing to report
+    pub fn message(&self, translations: &EditorTranslations) -> Option<String> {
+        match self {
+            Error::DialogClosed => None,
+            Error::Io { operation, path, kind, details } => {
+                let summary = match (kind, operation) {
+                    (io::ErrorKind::NotFound, _) => &translations.file_not_found,
+                    (io::ErrorKind::PermissionDenied, _) => &translations.permission_denied,
+                    (_, FileOperation::Open) => &translations.open_failed,
+                    (_, FileOperation::Save) => &translations.save_failed,
+                };
+
+                Some(format!("{}: {} ({})", summary, path.display(), details))
+            }
+            Error::Invalid(problems) => {
+                let errors: Vec<_> = problems.iter()
+                    .filter(|problem| problem.is_error())
+                    .map(ToString::to_string)
+                    .collect();
+
+                Some(format!("{}: {}", translations.save_blocked, errors.join("; ")))
+            }
+        }
+    }
+}
+
+async fn open_file(dialogs: Arc<dyn DialogProvider>) -> Result<(PathBuf, Arc<String>), Error> {
+    let picked_file = dialogs.pick_file("Open a text file...")
         .await
         .ok_or(Error::DialogClosed)?;
     
@@ -251,30 +1026,369 @@ async fn load_file(
     let contents = tokio::fs::read_to_string(&path)
         .await
         .map(Arc::new)
-        .map_err(|error| Error::IoError(error.kind()))?;
+        .map_err(|error| Error::io(FileOperation::Open, &path, error))?;
     
     Ok((path, contents))
 }
 
 async fn save_file(
+    dialogs: Arc<dyn DialogProvider>,
     path: Option<PathBuf>,
     contents: String,
 ) -> Result<PathBuf, Error> {
     let path = if let Some(path) = path {
         path
     } else {
-        rfd::AsyncFileDialog::new()
-            .save_file()
+        dialogs.save_file("Save file as...", None)
             .await
-            .as_ref()
-            .map(rfd::FileHandle::path)
-            .map(Path::to_owned)
             .ok_or(Error::DialogClosed)?
     };
     
     tokio::fs::write(&path, contents)
         .await
-        .map_err(|error| Error::IoError(error.kind()))?;
+        .map_err(|error| Error::io(FileOperation::Save, &path, error))?;
     
     Ok(path)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::RwLock;
+    use crate::services::dialog_service::MockDialogProvider;
+    use crate::services::filesystem_service::FilesystemService;
+    use crate::services::translation_service::{TranslationProvider, TranslationService};
+
+    fn editor_with_file(contents: &str) -> TextEditor {
+        let (mut editor, _) = TextEditor::with_task(highlighter::Theme::SolarizedDark);
+        let _ = editor.update(Message::FileOpened(Ok((PathBuf::from("test.mcfunction"), Arc::new(contents.to_string())))));
+        editor
+    }
+
+    fn edit(editor: &mut TextEditor, edit: text_editor::Edit) {
+        let _ = editor.update(Message::ActionPerformed(text_editor::Action::Edit(edit)));
+    }
+
+    #[test]
+    fn test_skip_over_only_auto_closed() {
+        // Given a file where I typed a closing bracket myself
+        let mut editor = editor_with_file("}");
+        let _ = editor.update(Message::ActionPerformed(text_editor::Action::Move(text_editor::Motion::DocumentStart)));
+        assert!(editor.auto_closed.is_empty());
+
+        // When a pair is auto-closed before it
+        let _ = editor.update(Message::AutoClosePair('{', '}'));
+
+        // Then only the inserted closing bracket should be typed over, and it should match the opening bracket
+        assert_eq!(editor.auto_closed, vec![(0, 1)]);
+        assert_eq!(editor.matching_brackets, vec![(0, 1), (0, 0)]);
+
+        // When I move past it
+        let _ = editor.update(Message::SkipOverClosing);
+
+        // Then it should no longer be tracked
+        assert_eq!(editor.content.cursor_position(), (0, 2));
+        assert!(editor.auto_closed.is_empty());
+        assert_eq!(editor.content.text().trim_end(), "{}}");
+    }
+
+    #[test]
+    fn test_reverting_edit_clears_dirty() {
+        // Given an open file
+        let mut editor = editor_with_file("say hi");
+        assert!(!editor.is_dirty());
+
+        // When I type a character
+        edit(&mut editor, text_editor::Edit::Insert('!'));
+
+        // Then it should be dirty
+        assert!(editor.is_dirty());
+
+        // And when I delete it again
+        edit(&mut editor, text_editor::Edit::Backspace);
+
+        // Then it should no longer be dirty
+        assert!(!editor.is_dirty());
+    }
+
+    #[test]
+    fn test_completion() {
+        // Given a file typing an item with two possible completions
+        let mut editor = editor_with_file("give @s minecraft:diam");
+        let _ = editor.update(Message::ActionPerformed(text_editor::Action::Move(text_editor::Motion::DocumentEnd)));
+
+        // When I ask for completions
+        let _ = editor.update(Message::RequestCompletion);
+
+        // Then both should be offered
+        let (_, candidates) = editor.completions.clone().unwrap();
+        assert_eq!(candidates.iter().map(CompletionCandidate::text).collect::<Vec<_>>(), vec!["minecraft:diamond", "minecraft:diamond_sword"]);
+
+        // And choosing one should replace what was typed
+        let _ = editor.update(Message::CompletionChosen(candidates[1].clone()));
+        assert_eq!(editor.content.text().trim_end(), "give @s minecraft:diamond_sword");
+        assert!(editor.completions.is_none());
+    }
+
+    #[test]
+    fn test_non_edit_action_keeps_clean() {
+        // Given an open file
+        let mut editor = editor_with_file("say hi");
+
+        // When I only move the cursor
+        let _ = editor.update(Message::ActionPerformed(text_editor::Action::Move(text_editor::Motion::End)));
+
+        // Then it should not be dirty
+        assert!(!editor.is_dirty());
+    }
+
+    #[test]
+    fn test_read_only_blocks_edits_and_saves() {
+        // Given an open file from a read-only project
+        let mut editor = editor_with_file("say hi");
+        let _ = editor.update(Message::ReadOnlyChanged(true));
+
+        // When I type a character and try to save
+        edit(&mut editor, text_editor::Edit::Insert('!'));
+        let _ = editor.update(Message::SaveFile);
+
+        // Then the file should be unchanged, with no save in flight
+        assert!(!editor.content.text().contains('!'));
+        assert!(!editor.is_dirty());
+        assert!(editor.pending_save_hash.is_none());
+    }
+
+    #[test]
+    fn test_saved_content_becomes_clean_state() {
+        // Given an open file with an edit
+        let mut editor = editor_with_file("say hi");
+        edit(&mut editor, text_editor::Edit::Insert('!'));
+
+        // When the edit is saved
+        let _ = editor.update(Message::SaveFile);
+        let _ = editor.update(Message::FileSaved(Ok(PathBuf::from("test.mcfunction"))));
+
+        // Then it should be clean
+        assert!(!editor.is_dirty());
+
+        // And reverting to the previously saved content should now count as a change
+        edit(&mut editor, text_editor::Edit::Backspace);
+        assert!(editor.is_dirty());
+    }
+
+    #[tokio::test]
+    async fn test_strict_save_blocks_invalid_json() {
+        // Given an open JSON file, with saves of invalid files blocked
+        let mut editor = editor_with_file("say hi");
+        let _ = editor.update(Message::FileOpened(Ok((PathBuf::from("gem.json"), Arc::new("{}".to_string())))));
+        let _ = editor.update(Message::SaveValidationChanged(SaveValidation::Block));
+
+        // When I break the JSON and save
+        edit(&mut editor, text_editor::Edit::Insert('{'));
+        let _ = editor.update(Message::SaveFile);
+        let problems = save_check::check(editor.file.clone(), editor.content.text()).await;
+        let _ = editor.update(Message::SaveChecked(editor.content.text(), problems));
+
+        // Then nothing should be saved, and the file should still have unsaved changes
+        assert!(editor.pending_save_hash.is_none());
+        assert!(!editor.is_loading());
+        assert!(editor.is_dirty());
+
+        // And the reason should be shown
+        assert!(editor.status_message.as_deref().is_some_and(|message| message.starts_with("Not saved") && message.contains("File is not valid JSON")));
+    }
+
+    #[tokio::test]
+    async fn test_lenient_save_warns_about_invalid_json() {
+        // Given an open JSON file, with only warnings for invalid files
+        let mut editor = editor_with_file("say hi");
+        let _ = editor.update(Message::FileOpened(Ok((PathBuf::from("gem.json"), Arc::new("{}".to_string())))));
+        let _ = editor.update(Message::SaveValidationChanged(SaveValidation::Warn));
+
+        // When I break the JSON and save
+        edit(&mut editor, text_editor::Edit::Insert('{'));
+        let _ = editor.update(Message::SaveFile);
+        let problems = save_check::check(editor.file.clone(), editor.content.text()).await;
+        assert!(!problems.is_empty());
+        let _ = editor.update(Message::SaveChecked(editor.content.text(), problems));
+
+        // Then it should be saved anyway
+        assert!(editor.pending_save_hash.is_some());
+        assert!(editor.is_loading());
+    }
+
+    #[test]
+    fn test_file_format_kept_and_changeable() {
+        // Given an open file with CRLF line endings
+        let mut editor = editor_with_file("say hi\r\nsay bye\r\n");
+
+        // Then the editor should work with LF, remembering the file uses CRLF, and the file should be clean
+        assert_eq!(editor.content.text(), "say hi\nsay bye\n");
+        assert_eq!(editor.file_format.to_string(), "UTF-8 · CRLF");
+        assert!(!editor.is_dirty());
+
+        // And when I switch it to LF
+        let _ = editor.update(Message::FileFormatSelected(FileFormat::default()));
+
+        // Then the change should need saving
+        assert!(editor.is_dirty());
+    }
+
+    #[test]
+    fn test_indent_settings_follow_open_file() {
+        // Given an open mcfunction file with an override for JSON files
+        let mut editor = editor_with_file("say hi");
+        let json_settings = IndentSettings { tab_width: 8, soft_tabs: false };
+        let _ = editor.update(Message::IndentSettingsChanged("json".to_string(), json_settings));
+
+        // When I get the indent settings
+        // Then they should be the mcfunction defaults
+        assert_eq!(editor.indent_settings(), IndentSettings::for_extension("mcfunction"));
+
+        // And once a JSON file is open, the override should apply
+        let _ = editor.update(Message::FileOpened(Ok((PathBuf::from("pack.json"), Arc::new("{}".to_string())))));
+        assert_eq!(editor.indent_settings(), json_settings);
+    }
+
+    #[test]
+    fn test_editor_labels_resolve_to_english() {
+        // Given the bundled languages
+        let translation_service = TranslationService::try_with_default_language(Arc::new(RwLock::new(FilesystemService::new()))).unwrap();
+
+        for key in EditorTranslationKeys::all_variants() {
+            // When I translate each editor label
+            let translation = translation_service.translate(&key);
+
+            // Then it should resolve to its English default
+            assert_eq!(translation, key.english_text(), "Wrong translation for {}", key.key());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_file_error() {
+        // Given a path which doesn't exist
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("missing.mcfunction");
+
+        // When I try to load it
+        let result = load_file(path.clone()).await;
+
+        // Then the error should keep the path and reason
+        let Err(error) = result else {
+            panic!("Loading a missing file should fail");
+        };
+        assert!(matches!(&error, Error::Io { operation: FileOperation::Open, path: error_path, kind: io::ErrorKind::NotFound, .. } if *error_path == path));
+
+        // And it should explain itself to the user
+        let message = error.message(&EditorTranslations::default()).unwrap();
+        assert!(message.starts_with("File not found"));
+        assert!(message.contains(&path.display().to_string()));
+    }
+
+    #[test]
+    fn test_counts_selection_or_whole_file() {
+        // Given a multi-line readme
+        let mut editor = editor_with_file("# Gems\nAdds shiny gems\nMade by someone");
+
+        // When I count it without selecting anything
+        let (whole_file, is_selection) = editor.text_counts();
+
+        // Then the whole file should be counted
+        assert!(!is_selection);
+        assert_eq!(whole_file, TextCounts { words: 8, lines: 3, characters: 38 });
+
+        // And when I select the first line and the start of the second
+        let _ = editor.update(Message::ActionPerformed(text_editor::Action::Move(text_editor::Motion::DocumentStart)));
+        let _ = editor.update(Message::ActionPerformed(text_editor::Action::Select(text_editor::Motion::Down)));
+        for _ in 0..4 {
+            let _ = editor.update(Message::ActionPerformed(text_editor::Action::Select(text_editor::Motion::Right)));
+        }
+        let (selection, is_selection) = editor.text_counts();
+
+        // Then only the selection should be counted
+        assert!(is_selection);
+        assert_eq!(selection, TextCounts { words: 3, lines: 2, characters: 11 });
+    }
+
+    #[tokio::test]
+    async fn test_save_as_writes_to_chosen_path() {
+        // Given a new file, and a dialog where the user picks where to save it
+        let temp_dir = tempfile::tempdir().unwrap();
+        let chosen_path = temp_dir.path().join("load.mcfunction");
+
+        let mut dialogs = MockDialogProvider::new();
+        let returned_path = chosen_path.clone();
+        dialogs.expect_save_file()
+            .times(1)
+            .returning(move |_, _| Some(returned_path.clone()));
+
+        // When I save it
+        let result = save_file(Arc::new(dialogs), None, "say hi".to_string()).await;
+
+        // Then it should be written where the user chose
+        assert_eq!(result, Ok(chosen_path.clone()));
+        assert_eq!(std::fs::read_to_string(&chosen_path).unwrap(), "say hi");
+    }
+
+    #[tokio::test]
+    async fn test_save_as_cancelled() {
+        // Given a new file, and a dialog the user closes without choosing
+        let mut dialogs = MockDialogProvider::new();
+        dialogs.expect_save_file()
+            .times(1)
+            .returning(|_, _| None);
+
+        // When I save it
+        let result = save_file(Arc::new(dialogs), None, "say hi".to_string()).await;
+
+        // Then nothing should be written, and there should be nothing to tell the user
+        assert_eq!(result, Err(Error::DialogClosed));
+        assert_eq!(result.unwrap_err().message(&EditorTranslations::default()), None);
+    }
+
+    #[tokio::test]
+    async fn test_save_existing_file_skips_dialog() {
+        // Given a file which has been saved before
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("load.mcfunction");
+
+        let mut dialogs = MockDialogProvider::new();
+        dialogs.expect_save_file().never();
+
+        // When I save it again
+        let result = save_file(Arc::new(dialogs), Some(path.clone()), "say bye".to_string()).await;
+
+        // Then it should be written in place without asking where
+        assert_eq!(result, Ok(path.clone()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "say bye");
+    }
+
+    #[test]
+    fn test_failed_open_shows_message() {
+        // Given an open file
+        let mut editor = editor_with_file("say hi");
+
+        // When opening another file fails
+        let _ = editor.update(Message::FileOpened(Err(Error::io(
+            FileOperation::Open,
+            Path::new("locked.mcfunction"),
+            io::Error::from(io::ErrorKind::PermissionDenied),
+        ))));
+
+        // Then the original file should stay open, with the error shown
+        assert_eq!(editor.file(), Some(Path::new("test.mcfunction")));
+        assert!(editor.status_message.as_deref().is_some_and(|message| message.starts_with("Permission denied: locked.mcfunction")));
+    }
+
+    #[test]
+    fn test_opening_invalid_pack_info_shows_problems() {
+        // Given an editor
+        let mut editor = editor_with_file("say hi");
+
+        // When I open a pack.mcmeta with a malformed description
+        let contents = r#"{"pack": {"pack_format": 71, "description": null}}"#;
+        let _ = editor.update(Message::FileOpened(Ok((PathBuf::from("pack/pack.mcmeta"), Arc::new(contents.to_string())))));
+
+        // Then the problem should be shown against its field
+        assert_eq!(editor.status_message.as_deref(), Some("pack.description: Not a valid text component"));
+    }
+}
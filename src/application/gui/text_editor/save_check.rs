@@ -0,0 +1,113 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use crate::data::serialization::project::PACK_INFO_FILE;
+use crate::data::validation::{self, ValidationError, ValidationIssue};
+
+/// Whether files are checked before they're saved, and what a problem does to the save
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SaveValidation {
+    /// Files are saved without being checked
+    #[default]
+    Off,
+    /// Files are saved either way, with any problems shown
+    Warn,
+    /// Files with errors aren't saved. Warnings are only shown
+    Block,
+}
+
+impl SaveValidation {
+    pub const ALL: [SaveValidation; 3] = [SaveValidation::Off, SaveValidation::Warn, SaveValidation::Block];
+
+    /// Whether a file with these problems can still be saved
+    pub fn allows(&self, problems: &[ValidationError]) -> bool {
+        *self != SaveValidation::Block || !validation::has_errors(problems)
+    }
+}
+
+impl Display for SaveValidation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveValidation::Off => write!(f, "Don't check on save"),
+            SaveValidation::Warn => write!(f, "Warn on save"),
+            SaveValidation::Block => write!(f, "Block invalid saves"),
+        }
+    }
+}
+
+/// Parses the text as the pack's file would be when it's loaded, along with the checks shown while it's open.
+/// Large files can take a while, so this runs off the UI thread
+pub async fn check(path: Option<PathBuf>, text: String) -> Vec<ValidationError> {
+    tokio::task::spawn_blocking(move || check_text(path.as_deref(), &text))
+        .await
+        .unwrap_or_default()
+}
+
+/// Files of a kind with nothing to check, including new files which haven't been given a name yet, are always valid
+fn check_text(path: Option<&Path>, text: &str) -> Vec<ValidationError> {
+    let Some(path) = path else {
+        return Vec::new();
+    };
+
+    if path.file_name().is_some_and(|name| name == PACK_INFO_FILE) {
+        return validation::pack_info::validate_str(text);
+    }
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => validation::text_component::validate_str(text),
+        // Other metadata, such as a texture's animation, only has to be well formed
+        Some("mcmeta") => match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(_) => Vec::new(),
+            Err(error) => vec![ValidationError::error("", ValidationIssue::InvalidJson(error.to_string()))],
+        },
+        Some("mcfunction") => validation::function::validate_str(text),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case::valid_json("data/example/recipe/gem.json", r#"{"type": "minecraft:crafting_shaped"}"#, false)]
+    #[case::invalid_json("data/example/recipe/gem.json", r#"{"type": "minecraft:crafting_shaped""#, true)]
+    #[case::invalid_pack_info("pack.mcmeta", r#"{"pack": {"description": "Gems"}}"#, true)]
+    #[case::invalid_texture_meta("assets/example/textures/block/gem.png.mcmeta", "{\"animation\": ", true)]
+    #[case::invalid_function("data/example/function/load.mcfunction", "give @s stone[count=2", true)]
+    #[case::unchecked_kind("README.md", "{", false)]
+    #[tokio::test]
+    async fn test_check(#[case] path: &str, #[case] text: &str, #[case] has_errors: bool) {
+        // Given a file about to be saved
+        // When I check it
+        let problems = check(Some(PathBuf::from(path)), text.to_string()).await;
+
+        // Then it should only have errors if the game would refuse it
+        assert_eq!(validation::has_errors(&problems), has_errors, "Unexpected problems: {:?}", problems);
+    }
+
+    #[rstest]
+    #[case::off(SaveValidation::Off, true)]
+    #[case::warn(SaveValidation::Warn, true)]
+    #[case::block(SaveValidation::Block, false)]
+    fn test_allows_invalid_file(#[case] save_validation: SaveValidation, #[case] allowed: bool) {
+        // Given a file which isn't valid JSON
+        let problems = check_text(Some(Path::new("pack.mcmeta")), "{");
+
+        // When I decide whether to save it
+        // Then only a strict check should refuse it
+        assert_eq!(save_validation.allows(&problems), allowed);
+    }
+
+    #[test]
+    fn test_block_allows_warnings() {
+        // Given a pack.mcmeta which the game loads, but with a key it ignores
+        let problems = check_text(Some(Path::new("pack.mcmeta")), r#"{"pack": {"pack_format": 71, "description": "Gems"}, "extra": 1}"#);
+        assert!(!problems.is_empty());
+
+        // When I decide whether to save it strictly
+        // Then it should still be saved
+        assert!(SaveValidation::Block.allows(&problems));
+    }
+}
@@ -1,48 +1,226 @@
 // SPDX-License-Identifier: MPL-2.0
 
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use iced::{Element, Length, Task, Theme};
-use iced::widget::{Column, Container, pane_grid, PaneGrid};
+use std::time::{Duration, Instant};
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use iced::{event, Element, Event, Length, Subscription, Task, Theme};
+use iced::widget::{button, horizontal_space, pick_list, text, Column, Container, pane_grid, PaneGrid, Row};
 use iced::widget::pane_grid::Axis;
-use crate::application::app_context::AppContext;
+use crate::application::app_context::{AppContext, ProjectServiceContext};
+use crate::application::file_manager;
+use crate::application::logging::{self, LogEvent, LogLevel};
 use crate::application::gui::header::Header;
-use crate::application::gui::{header, text_editor};
-use crate::application::gui::text_editor::{highlighter, TextEditor};
+use crate::application::gui::{close_prompt, diagnostics, event_log, file_tree, header, high_contrast, new_project, pack_icon, text_editor};
+use crate::application::gui::diagnostics::{DiagnosticsPanel, DiagnosticsTranslations};
+use crate::application::gui::event_log::EventLogTranslations;
+use crate::application::gui::close_prompt::{CloseAction, CloseChoice, ClosePromptTranslations, CloseTarget};
+use crate::application::gui::file_tree::{FileTree, RenamedFile};
+use crate::application::gui::new_project::{NewProjectDialog, NewProjectTranslations};
+use crate::application::gui::pack_icon::{PackIconPanel, PackIconTranslations};
+use crate::application::gui::notifications::{Notification, NotificationID, NotificationQueue, NotificationTranslations};
+use crate::application::gui::tabs::{CloseTabOutcome, Tabs};
+use crate::application::gui::text_editor::{highlighter, EditorTranslations, TextEditor};
+use crate::application::gui::text_editor::save_check::SaveValidation;
+use crate::data::domain::install_context::InstallContext;
+use crate::data::domain::pack_icon::PackIcon;
+use crate::data::domain::project::{CasingFix, ExtensionFix, ProjectFile, ProjectID, ReferenceRewrite};
+use crate::data::domain::resource::resource::ResourceLocation;
+use crate::data::serialization::bom;
+use crate::data::domain::project_index::{IndexProgress, ProjectIndex};
+use crate::data::serialization::project::ImportReport;
+use crate::services::dialog_service::{DefaultDialogProvider, DialogProvider};
+use crate::services::lint_service::{LintDiagnostic, LintProvider, LintService};
+use crate::services::metrics_service::{Operation, OperationStats};
+use crate::services::project_service::{ImportProgress, ProjectServiceError, ZipPath};
+use crate::services::translation_service::LayoutDirection;
+
+/// Extensions which are opened in the editor when dropped onto the window
+const TEXT_FILE_EXTENSIONS: &[&str] = &["json", "mcfunction", "mcmeta", "txt"];
+
+/// Index progress updates which can queue up before the window handles them
+const INDEX_PROGRESS_BUFFER: usize = 16;
+
+/// How often shown notifications are checked for expiry
+const NOTIFICATION_TICK: Duration = Duration::from_millis(500);
+
+/// How often the event log is refreshed while it is open
+const EVENT_LOG_TICK: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone)]
 pub enum Message {
     // Global messages
     ThemeSelected(highlighter::Theme),
+    LogLevelSelected(LogLevel),
+    SaveValidationSelected(SaveValidation),
     
     // Main window messages
     ResizedPane(pane_grid::ResizeEvent),
     ClickedPane(pane_grid::Pane),
+    /// A file renamed from the file tree, and whether references to it should be rewritten
+    FileRenamed(RenamedFile, bool),
+    /// References to a renamed file were rewritten and saved, or couldn't be
+    ReferencesUpdated(Result<ReferenceRewrite, String>),
+    FileDropped(PathBuf),
+    /// Opens the OS file manager at the active editor's file
+    RevealInFileManager,
+    ImportProgressed(PathBuf, ImportProgress),
+    ZipImported(PathBuf, Result<(ProjectID, ImportReport), String>),
+    IndexProgressed(ProjectID, IndexProgress),
+    ProjectIndexed(ProjectID, Result<Arc<ProjectIndex>, String>),
+    
+    // Opening projects
+    /// Asks for a project folder and opens it. Read-only projects can be browsed, but not changed
+    OpenProject { read_only: bool },
+    /// The opened project, or `None` if no folder was chosen
+    ProjectOpened(Result<Option<ShownProject>, String>),
+    
+    // New projects
+    OpenNewProject,
+    NewProject(new_project::Message),
+    /// Where the new project's path sits within a Minecraft installation, used to suggest the type of project
+    NewProjectPathDetected(String, Option<InstallContext>),
+    /// Creates a project from the new project dialog, if its fields are valid
+    CreateProject,
+    CancelNewProject,
+    ProjectCreated(Result<ProjectID, String>),
+    NewProjectTranslationsUpdated(NewProjectTranslations),
+    
+    // Pack icon
+    /// Shows the icon of a newly created or imported project in the preview pane
+    PackIconLoaded(ProjectID, Result<Option<PackIcon>, String>),
+    /// The icon of a zip which is being imported, read from the archive so it shows before the import finishes
+    ZipPackIconLoaded(Result<Option<PackIcon>, String>),
+    /// Asks for an image to replace the project's icon with
+    ReplacePackIcon(ProjectID),
+    /// The new icon, or `None` if no image was chosen
+    PackIconReplaced(ProjectID, Result<Option<PackIcon>, String>),
+    PackIconTranslationsUpdated(PackIconTranslations),
+    
+    // Closing
+    CloseRequested(iced::window::Id),
+    /// Whether the shown project has unsaved changes, checked before the window closes
+    WindowCloseChecked(iced::window::Id, bool),
+    /// Closes the project shown in the file tree, e.g. from the File menu
+    CloseShownProject,
+    CloseProject(ProjectID),
+    ProjectClosed(ProjectID),
+    /// Asks the user what to do with unsaved work before closing
+    ConfirmClose(CloseTarget),
+    CloseChoiceMade(CloseChoice),
+    ClosePromptTranslationsUpdated(ClosePromptTranslations),
+    
+    // Tabs
+    SelectTab(usize),
+    /// Closes the tab at the index, first asking what to do with its unsaved changes if it has any
+    CloseTab(usize),
+    
+    // Notifications
+    Notify(Notification),
+    DismissNotification(NotificationID),
+    NotificationTick(Instant),
+    NotificationTranslationsUpdated(NotificationTranslations),
+    
+    // Event log
+    ToggleEventLog,
+    EventLogTick,
+    CopyEventLog,
+    EventLogTranslationsUpdated(EventLogTranslations),
+    OperationTimingsLoaded(Vec<(Operation, OperationStats)>),
+    
+    // Diagnostics
+    /// Shows or hides the problems found in the shown project, linting it when shown
+    ToggleDiagnostics,
+    LintProject(ProjectID),
+    ProjectLinted(ProjectID, Result<Vec<LintDiagnostic>, String>),
+    /// Renames the project's files whose extension doesn't match their kind, then saves it
+    FixExtensions(ProjectID),
+    ExtensionsFixed(ProjectID, Result<Vec<ExtensionFix>, String>),
+    /// Lowercases the project's file paths with uppercase letters in their resource locations, then saves it
+    FixCasing(ProjectID),
+    CasingFixed(ProjectID, Result<Vec<CasingFix>, String>),
+    DiagnosticsTranslationsUpdated(DiagnosticsTranslations),
+    
+    // Localization
+    LanguageChanged,
+    LayoutDirectionChanged(LayoutDirection),
     
     // Element messages
-    TextEditorMessage(text_editor::Message),
-    HeaderMessage(header::Message),
+    TextEditor(text_editor::Message),
+    Header(header::Message),
+    FileTree(file_tree::Message),
 }
 
 impl From<text_editor::Message> for Message {
     fn from(value: text_editor::Message) -> Self {
-        Message::TextEditorMessage(value)
+        Message::TextEditor(value)
     }
 }
 
 impl From<header::Message> for Message {
     fn from(value: header::Message) -> Self {
-        Message::HeaderMessage(value)
+        Message::Header(value)
     }
 }
 
 pub struct ApplicationWindow {
     theme: highlighter::Theme,
+    log_level: LogLevel,
+    save_validation: SaveValidation,
     
     panes: pane_grid::State<PaneState>,
     focus: Option<pane_grid::Pane>,
     
     header: Header,
-    text_editor: TextEditor,
+    file_tree: FileTree,
+    /// Each open file has its own editor. Results of an editor's file operations go to whichever tab is active when they arrive
+    tabs: Tabs<TextEditor>,
+    /// Kept to set up the editors of newly opened tabs
+    editor_translations: EditorTranslations,
+    /// The project opened from its folder, if any
+    project: Option<ShownProject>,
+    
+    notifications: NotificationQueue,
+    notification_translations: NotificationTranslations,
+    
+    /// Recent log events, while the event log is open
+    event_log: Option<Vec<LogEvent>>,
+    event_log_translations: EventLogTranslations,
+    /// How long the project service's slow operations last took, shown with the event log
+    operation_timings: Vec<(Operation, OperationStats)>,
+    
+    /// Problems found in the shown project, while the panel is open
+    diagnostics: Option<DiagnosticsPanel>,
+    diagnostics_translations: DiagnosticsTranslations,
+    /// Kept between lints so files which haven't changed aren't parsed again
+    lint_service: Arc<LintService>,
+    
+    /// Projects whose index is still being built, shown as an indicator until they are done
+    indexing: HashMap<ProjectID, IndexProgress>,
+    /// Index of the shown project, once it has been built
+    project_index: Option<Arc<ProjectIndex>>,
+    /// Dropped zips still being imported, shown with the stage each has reached
+    importing: HashMap<PathBuf, ImportProgress>,
+    
+    /// Follows the current language, for the window chrome only. The editor always stays left-to-right,
+    /// as pack files are written in Latin script whatever the interface language
+    layout_direction: LayoutDirection,
+    
+    new_project: Option<NewProjectDialog>,
+    new_project_translations: NewProjectTranslations,
+    
+    /// The icon of the project created or imported last, until projects can be shown in the window
+    pack_icon: Option<PackIconPanel>,
+    pack_icon_translations: PackIconTranslations,
+    dialogs: Arc<dyn DialogProvider>,
+    
+    close_prompt: Option<CloseTarget>,
+    close_prompt_translations: ClosePromptTranslations,
+    /// The window or tab to close once the active editor has finished saving
+    close_after_save: Option<CloseTarget>,
     
     app_context: Arc<AppContext>,
 }
@@ -71,31 +249,161 @@ impl ApplicationWindow {
         let theme = highlighter::Theme::SolarizedDark;
 
         let (header, header_message) = Header::with_task(app_context.clone());
+        let file_tree = FileTree::new(app_context.clone());
         let (text_editor, editor_message) = TextEditor::with_task(theme.clone());
+        let mut tabs = Tabs::default();
+        tabs.push(text_editor);
         
         let window = Self {
             theme,
+            log_level: LogLevel::default(),
+            save_validation: SaveValidation::default(),
             
             panes,
             focus: None,
             
             header,
-            text_editor,
+            file_tree,
+            tabs,
+            editor_translations: EditorTranslations::default(),
+            project: None,
+            
+            notifications: NotificationQueue::default(),
+            notification_translations: NotificationTranslations::default(),
+            
+            event_log: None,
+            event_log_translations: EventLogTranslations::default(),
+            operation_timings: Vec::new(),
+            
+            diagnostics: None,
+            diagnostics_translations: DiagnosticsTranslations::default(),
+            lint_service: Arc::new(LintService::new()),
+            
+            indexing: HashMap::new(),
+            project_index: None,
+            importing: HashMap::new(),
+            
+            layout_direction: LayoutDirection::default(),
+            
+            new_project: None,
+            new_project_translations: NewProjectTranslations::default(),
+            
+            pack_icon: None,
+            pack_icon_translations: PackIconTranslations::default(),
+            dialogs: Arc::new(DefaultDialogProvider::new()),
+            
+            close_prompt: None,
+            close_prompt_translations: ClosePromptTranslations::default(),
+            close_after_save: None,
             
             app_context,
         };
         
+        let translations = window.refresh_translations();
+        let language_changes = window.subscribe_language_changes();
+        
         (window, Task::batch([
             header_message,
             editor_message,
+            translations,
+            language_changes,
         ]))
     }
     
+    /// Translates the text of every part of the window, at startup and whenever the language changes
+    fn refresh_translations(&self) -> Task<Message> {
+        let editor_translations = Task::perform(
+            TextEditor::translate(self.app_context.translation_service_context().clone()),
+            |translations| text_editor::Message::TranslationsUpdated(Box::new(translations)).into()
+        );
+        
+        let notification_translations = Task::perform(
+            NotificationTranslations::translate(self.app_context.translation_service_context().clone()),
+            Message::NotificationTranslationsUpdated
+        );
+        
+        let close_prompt_translations = Task::perform(
+            ClosePromptTranslations::translate(self.app_context.translation_service_context().clone()),
+            Message::ClosePromptTranslationsUpdated
+        );
+        
+        let new_project_translations = Task::perform(
+            NewProjectTranslations::translate(self.app_context.translation_service_context().clone()),
+            Message::NewProjectTranslationsUpdated
+        );
+        
+        let event_log_translations = Task::perform(
+            EventLogTranslations::translate(self.app_context.translation_service_context().clone()),
+            Message::EventLogTranslationsUpdated
+        );
+        
+        let pack_icon_translations = Task::perform(
+            PackIconTranslations::translate(self.app_context.translation_service_context().clone()),
+            Message::PackIconTranslationsUpdated
+        );
+        
+        let diagnostics_translations = Task::perform(
+            DiagnosticsTranslations::translate(self.app_context.translation_service_context().clone()),
+            Message::DiagnosticsTranslationsUpdated
+        );
+        
+        let translation_service = self.app_context.translation_service_context().clone();
+        let layout_direction = Task::perform(
+            async move { translation_service.read().await.get_current_language().layout_direction() },
+            Message::LayoutDirectionChanged
+        );
+        
+        Task::batch([
+            self.header.refresh_translations(),
+            editor_translations,
+            notification_translations,
+            close_prompt_translations,
+            new_project_translations,
+            event_log_translations,
+            pack_icon_translations,
+            diagnostics_translations,
+            layout_direction,
+        ])
+    }
+    
+    /// Runs for the lifetime of the window, sending [`Message::LanguageChanged`] whenever the language is switched
+    fn subscribe_language_changes(&self) -> Task<Message> {
+        let translation_service = self.app_context.translation_service_context().clone();
+        
+        Task::run(
+            iced::stream::channel(1, move |mut output| async move {
+                let (sender, mut changes) = mpsc::unbounded();
+                translation_service.write().await.subscribe_language_changes(Box::new(move |_| {
+                    let _ = sender.unbounded_send(());
+                }));
+                
+                while changes.next().await.is_some() {
+                    let _ = output.send(Message::LanguageChanged).await;
+                }
+            }),
+            std::convert::identity
+        )
+    }
+    
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::ThemeSelected(theme) => {
                 self.theme = theme;
-                Task::none()
+                self.update_all_editors(text_editor::Message::ThemeChanged(theme))
+            }
+            Message::SaveValidationSelected(save_validation) => {
+                self.save_validation = save_validation;
+                self.update_all_editors(text_editor::Message::SaveValidationChanged(save_validation))
+            }
+            Message::LogLevelSelected(level) => {
+                match logging::set_level(level) {
+                    Ok(()) => {
+                        tracing::info!("Log level set to {}", level);
+                        self.log_level = level;
+                        Task::none()
+                    }
+                    Err(error) => Task::done(Message::Notify(Notification::error(error.to_string()))),
+                }
             }
             Message::ResizedPane(pane_grid::ResizeEvent { split, ratio }) => {
                 self.panes.resize(split, ratio);
@@ -105,21 +413,840 @@ impl ApplicationWindow {
                 self.focus = Some(pane);
                 Task::none()
             }
-            Message::TextEditorMessage(message) => self.text_editor.update(message),
-            Message::HeaderMessage(message) => self.header.update(message),
+            Message::FileRenamed(RenamedFile { from, to }, update_references) => {
+                for editor in self.tabs.iter_mut() {
+                    editor.rename_open_file(&from, &to);
+                }
+                self.move_project_file(&from, &to, update_references)
+            }
+            Message::ReferencesUpdated(result) => match result {
+                Ok(rewrite) if rewrite.references_rewritten == 0 => Task::none(),
+                Ok(rewrite) => {
+                    let message = format!("{}: {}", self.notification_translations.references_updated, rewrite.files_changed);
+                    Task::done(Message::Notify(Notification::success(message)))
+                }
+                Err(error) => Task::done(Message::Notify(Notification::error(error))),
+            },
+            Message::FileDropped(path) => {
+                match dispatch_drop(&path) {
+                    DropAction::OpenFile(path) => self.open_path(path),
+                    DropAction::ImportZip(path) => self.import_zip(path),
+                    DropAction::Unsupported(path) => {
+                        let message = format!("{}: {}", self.notification_translations.unsupported_file, path.display());
+                        Task::done(Message::Notify(Notification::error(message)))
+                    }
+                }
+            }
+            Message::RevealInFileManager => {
+                let Some(file) = self.tabs.active().and_then(|editor| editor.file()) else {
+                    return Task::done(Message::Notify(Notification::info(self.notification_translations.nothing_to_reveal.clone())));
+                };
+                
+                match file_manager::reveal(file) {
+                    Ok(()) => Task::none(),
+                    Err(error) => Task::done(Message::Notify(Notification::error(error.to_string()))),
+                }
+            }
+            Message::ImportProgressed(path, progress) => {
+                // Progress can arrive after the import finished, as it is sent without waiting
+                if let Some(current) = self.importing.get_mut(&path) {
+                    *current = progress;
+                }
+                Task::none()
+            }
+            Message::ZipImported(path, result) => {
+                // TODO: Open the imported project once projects can be shown in the window
+                self.importing.remove(&path);
+                
+                // The import indexes the project itself, so there's nothing more to start
+                let notification = match &result {
+                    Ok((_, report)) if report.is_empty() => Notification::success(self.notification_translations.project_imported.clone()),
+                    Ok((_, report)) => Notification::info(format!("{}: {}", self.notification_translations.imported_with_warnings, report)),
+                    Err(error) => Notification::error(format!("{}: {}", self.notification_translations.import_failed, error)),
+                };
+                
+                match result {
+                    Ok((project_id, _)) => Task::batch([Task::done(Message::Notify(notification)), self.load_pack_icon(project_id)]),
+                    Err(_) => Task::done(Message::Notify(notification)),
+                }
+            }
+            Message::IndexProgressed(project_id, progress) => {
+                // Progress can arrive after the index finished, as it is sent without waiting
+                if let Some(current) = self.indexing.get_mut(&project_id) {
+                    *current = progress;
+                }
+                Task::none()
+            }
+            Message::ProjectIndexed(project_id, result) => {
+                self.indexing.remove(&project_id);
+                
+                match result {
+                    Ok(index) => match &self.project {
+                        Some(project) if project.id == project_id => {
+                            self.project_index = Some(index.clone());
+                            self.update_all_editors(text_editor::Message::ProjectIndexChanged(Some((project.path.clone(), index))))
+                        }
+                        _ => Task::none(),
+                    },
+                    Err(error) => Task::done(Message::Notify(Notification::error(error))),
+                }
+            }
+            Message::OpenProject { read_only } => {
+                let dialogs = self.dialogs.clone();
+                let project_service = self.app_context.project_service_context().clone();
+                let title = self.notification_translations.pick_project.clone();
+                
+                Task::perform(
+                    async move {
+                        let Some(path) = dialogs.pick_folder(&title).await else {
+                            return Ok(None);
+                        };
+                        
+                        let project_service = project_service.read().await;
+                        let opened = match read_only {
+                            true => project_service.open_project_read_only(&path).await,
+                            false => project_service.open_project(&path).await,
+                        };
+                        
+                        opened.map(|id| Some(ShownProject { id, path, read_only }))
+                            .map_err(|error| error.to_string())
+                    },
+                    Message::ProjectOpened
+                )
+            }
+            Message::ProjectOpened(result) => match result {
+                Ok(Some(project)) => {
+                    let read_only = self.set_read_only(project.read_only);
+                    let tree = self.file_tree.update(file_tree::Message::RootSelected(project.path.clone()));
+                    let index = Task::batch([self.clear_project_index(), self.index_project(project.id)]);
+                    
+                    // Only one project is shown at a time, so the one it replaces is closed
+                    let diagnostics = match &mut self.diagnostics {
+                        Some(panel) => {
+                            *panel = DiagnosticsPanel::new(project.id);
+                            self.lint_project(project.id)
+                        }
+                        None => Task::none(),
+                    };
+                    
+                    let close_previous = match self.project.replace(project) {
+                        Some(previous) => Task::done(Message::CloseProject(previous.id)),
+                        None => Task::none(),
+                    };
+                    
+                    Task::batch([read_only, tree, index, diagnostics, close_previous])
+                }
+                Ok(None) => Task::none(),
+                Err(error) => Task::done(Message::Notify(Notification::error(format!("{}: {}", self.notification_translations.open_failed, error)))),
+            },
+            Message::OpenNewProject => {
+                self.new_project.get_or_insert_with(NewProjectDialog::default);
+                Task::none()
+            }
+            Message::NewProject(message) => {
+                let changed_path = match &message {
+                    new_project::Message::PathChanged(path) => Some(path.clone()),
+                    _ => None,
+                };
+                
+                if let Some(dialog) = &mut self.new_project {
+                    dialog.update(message);
+                }
+                
+                match changed_path {
+                    Some(path) => self.detect_install_context(path),
+                    None => Task::none(),
+                }
+            }
+            Message::NewProjectPathDetected(path, context) => {
+                if let (Some(dialog), Some(context)) = (&mut self.new_project, context) {
+                    dialog.install_context_detected(&path, &context);
+                }
+                Task::none()
+            }
+            Message::CreateProject => {
+                let Some(dialog) = &mut self.new_project else {
+                    return Task::none();
+                };
+                
+                let settings = match dialog.settings() {
+                    Ok(settings) => settings,
+                    Err(error) => {
+                        dialog.set_error(error.to_string());
+                        return Task::none();
+                    }
+                };
+                
+                let project_service = self.app_context.project_service_context().clone();
+                
+                Task::perform(
+                    new_project::create_project(project_service, self.dialogs.clone(), settings, dialog.template_id(), self.new_project_translations.clone()),
+                    Message::ProjectCreated
+                )
+            }
+            Message::CancelNewProject => {
+                self.new_project = None;
+                Task::none()
+            }
+            Message::ProjectCreated(result) => match result {
+                Ok(project_id) => {
+                    // TODO: Show the new project once projects can be shown in the window
+                    self.new_project = None;
+                    let notification = Notification::success(self.new_project_translations.created.clone());
+                    
+                    Task::batch([Task::done(Message::Notify(notification)), self.index_project(project_id), self.load_pack_icon(project_id)])
+                }
+                Err(error) => {
+                    if let Some(dialog) = &mut self.new_project {
+                        dialog.set_error(error);
+                    }
+                    Task::none()
+                }
+            },
+            Message::NewProjectTranslationsUpdated(translations) => {
+                self.new_project_translations = translations;
+                Task::none()
+            }
+            Message::PackIconLoaded(project_id, result) => match result {
+                Ok(icon) => {
+                    self.pack_icon = Some(PackIconPanel::new(project_id, icon));
+                    Task::none()
+                }
+                Err(error) => Task::done(Message::Notify(Notification::error(error))),
+            },
+            Message::ZipPackIconLoaded(result) => {
+                match result {
+                    // An imported project's own icon replaces the preview, so a late preview mustn't cover it
+                    Ok(icon) if self.pack_icon.as_ref().is_none_or(|panel| panel.project_id().is_none()) => {
+                        self.pack_icon = Some(PackIconPanel::preview(icon));
+                    }
+                    Ok(_) => {}
+                    // The import reports anything wrong with the zip itself
+                    Err(error) => tracing::debug!("Could not preview the zip's icon - {}", error),
+                }
+                Task::none()
+            }
+            Message::ReplacePackIcon(project_id) => {
+                let dialogs = self.dialogs.clone();
+                let project_service = self.app_context.project_service_context().clone();
+                let title = self.pack_icon_translations.pick_image.clone();
+                
+                Task::perform(
+                    pack_icon::replace_icon(dialogs, project_service, project_id, title),
+                    move |result| Message::PackIconReplaced(project_id, result)
+                )
+            }
+            Message::PackIconReplaced(project_id, result) => match result {
+                Ok(Some(icon)) => {
+                    let panel = self.pack_icon.as_mut().filter(|panel| panel.project_id() == Some(project_id));
+                    if let Some(panel) = panel {
+                        panel.set_icon(icon);
+                    }
+                    Task::done(Message::Notify(Notification::success(self.pack_icon_translations.replaced.clone())))
+                }
+                Ok(None) => Task::none(),
+                Err(error) => Task::done(Message::Notify(Notification::error(error))),
+            },
+            Message::PackIconTranslationsUpdated(translations) => {
+                self.pack_icon_translations = translations;
+                Task::none()
+            }
+            Message::CloseRequested(id) => {
+                let Some(project_id) = self.project.as_ref().map(|project| project.id) else {
+                    return Task::done(Message::WindowCloseChecked(id, false));
+                };
+                let project_service = self.app_context.project_service_context().clone();
+                
+                Task::perform(
+                    async move { project_service.read().await.has_unsaved_changes(project_id).await },
+                    // A project which can't be found has nothing left to save
+                    move |result| Message::WindowCloseChecked(id, result.unwrap_or(false))
+                )
+            }
+            Message::WindowCloseChecked(id, project_dirty) => {
+                if project_dirty || self.tabs.any_dirty() {
+                    self.close_prompt = Some(CloseTarget::Window(id));
+                    Task::none()
+                } else {
+                    iced::window::close(id)
+                }
+            }
+            Message::CloseShownProject => match &self.project {
+                Some(project) => Task::done(Message::CloseProject(project.id)),
+                None => Task::none(),
+            },
+            Message::CloseProject(project_id) => {
+                let project_service = self.app_context.project_service_context().clone();
+                
+                Task::perform(
+                    async move { project_service.read().await.close_project(project_id).await },
+                    move |result| match result {
+                        Ok(()) => Message::ProjectClosed(project_id),
+                        Err(ProjectServiceError::CannotCloseUnsavedChanges) => Message::ConfirmClose(CloseTarget::Project(project_id)),
+                        Err(error) => Message::Notify(Notification::error(error.to_string())),
+                    }
+                )
+            }
+            Message::ProjectClosed(project_id) => {
+                self.indexing.remove(&project_id);
+                if self.pack_icon.as_ref().is_some_and(|panel| panel.project_id() == Some(project_id)) {
+                    self.pack_icon = None;
+                }
+                if self.diagnostics.as_ref().is_some_and(|panel| panel.project_id() == project_id) {
+                    self.diagnostics = None;
+                }
+                
+                if self.project.as_ref().is_some_and(|project| project.id == project_id) {
+                    self.project = None;
+                    return Task::batch([
+                        self.file_tree.update(file_tree::Message::RootCleared),
+                        self.set_read_only(false),
+                        self.clear_project_index(),
+                    ]);
+                }
+                Task::none()
+            }
+            Message::ConfirmClose(target) => {
+                self.close_prompt = Some(target);
+                Task::none()
+            }
+            Message::CloseChoiceMade(choice) => {
+                let Some(target) = self.close_prompt.take() else {
+                    return Task::none();
+                };
+                
+                match close_prompt::resolve(target, choice) {
+                    CloseAction::KeepOpen => Task::none(),
+                    CloseAction::Close(CloseTarget::Window(id)) => iced::window::close(id),
+                    CloseAction::SaveThenClose(CloseTarget::Window(id)) => self.save_next_then_close(id),
+                    CloseAction::Close(CloseTarget::Tab(index)) => {
+                        self.tabs.remove(index);
+                        Task::none()
+                    }
+                    CloseAction::SaveThenClose(CloseTarget::Tab(index)) => {
+                        if !self.tabs.activate(index) {
+                            return Task::none();
+                        }
+                        
+                        self.close_after_save = Some(CloseTarget::Tab(index));
+                        self.update_active_editor(text_editor::Message::SaveFile)
+                    }
+                    CloseAction::Close(CloseTarget::Project(project_id)) => self.close_project_after(project_id, |project_service, project_id| async move {
+                        project_service.read().await.discard_changes(project_id).await
+                    }),
+                    CloseAction::SaveThenClose(CloseTarget::Project(project_id)) => self.close_project_after(project_id, |project_service, project_id| async move {
+                        project_service.read().await.save_project(project_id).await.map(|_| ())
+                    }),
+                }
+            }
+            Message::ClosePromptTranslationsUpdated(translations) => {
+                self.close_prompt_translations = translations;
+                Task::none()
+            }
+            Message::SelectTab(index) => {
+                self.tabs.activate(index);
+                Task::none()
+            }
+            Message::CloseTab(index) => {
+                if self.tabs.request_close(index) == CloseTabOutcome::NeedsConfirmation {
+                    self.close_prompt = Some(CloseTarget::Tab(index));
+                }
+                Task::none()
+            }
+            Message::Notify(notification) => {
+                self.notifications.push(notification, Instant::now());
+                Task::none()
+            }
+            Message::DismissNotification(id) => {
+                self.notifications.dismiss(id);
+                Task::none()
+            }
+            Message::NotificationTick(now) => {
+                self.notifications.expire(now);
+                Task::none()
+            }
+            Message::NotificationTranslationsUpdated(translations) => {
+                self.notification_translations = translations;
+                Task::none()
+            }
+            Message::ToggleEventLog => {
+                self.event_log = match self.event_log {
+                    Some(_) => None,
+                    None => Some(logging::recent_events()),
+                };
+                self.load_operation_timings()
+            }
+            Message::EventLogTick => {
+                if self.event_log.is_some() {
+                    self.event_log = Some(logging::recent_events());
+                    return self.load_operation_timings();
+                }
+                Task::none()
+            }
+            Message::CopyEventLog => match &self.event_log {
+                Some(events) => iced::clipboard::write(event_log::copy_text(events)),
+                None => Task::none(),
+            },
+            Message::EventLogTranslationsUpdated(translations) => {
+                self.event_log_translations = translations;
+                Task::none()
+            }
+            Message::OperationTimingsLoaded(timings) => {
+                self.operation_timings = timings;
+                Task::none()
+            }
+            Message::ToggleDiagnostics => match (&self.diagnostics, &self.project) {
+                (None, Some(project)) => {
+                    let project_id = project.id;
+                    self.diagnostics = Some(DiagnosticsPanel::new(project_id));
+                    self.lint_project(project_id)
+                }
+                _ => {
+                    self.diagnostics = None;
+                    Task::none()
+                }
+            },
+            Message::LintProject(project_id) => self.lint_project(project_id),
+            Message::ProjectLinted(project_id, result) => match result {
+                Ok(diagnostics) => {
+                    let panel = self.diagnostics.as_mut().filter(|panel| panel.project_id() == project_id);
+                    if let Some(panel) = panel {
+                        panel.set_diagnostics(diagnostics);
+                    }
+                    Task::none()
+                }
+                Err(error) => Task::done(Message::Notify(Notification::error(error))),
+            },
+            Message::FixExtensions(project_id) => {
+                let project_service = self.app_context.project_service_context().clone();
+                
+                Task::perform(
+                    diagnostics::fix_extensions(project_service, project_id),
+                    move |result| Message::ExtensionsFixed(project_id, result)
+                )
+            }
+            Message::ExtensionsFixed(project_id, result) => match result {
+                Ok(fixes) => {
+                    let moves = fixes.into_iter().map(|fix| (fix.from, fix.to)).collect();
+                    let notice = self.diagnostics_translations.extensions_fixed.clone();
+                    self.files_fixed(project_id, moves, notice)
+                }
+                Err(error) => Task::done(Message::Notify(Notification::error(error))),
+            },
+            Message::FixCasing(project_id) => {
+                let project_service = self.app_context.project_service_context().clone();
+                
+                Task::perform(
+                    diagnostics::fix_casing(project_service, project_id),
+                    move |result| Message::CasingFixed(project_id, result)
+                )
+            }
+            Message::CasingFixed(project_id, result) => match result {
+                Ok(fixes) => {
+                    let moves = fixes.into_iter().map(|fix| (fix.from, fix.to)).collect();
+                    let notice = self.diagnostics_translations.casing_fixed.clone();
+                    self.files_fixed(project_id, moves, notice)
+                }
+                Err(error) => Task::done(Message::Notify(Notification::error(error))),
+            },
+            Message::DiagnosticsTranslationsUpdated(translations) => {
+                self.diagnostics_translations = translations;
+                Task::none()
+            }
+            Message::LanguageChanged => self.refresh_translations(),
+            Message::LayoutDirectionChanged(layout_direction) => {
+                self.layout_direction = layout_direction;
+                Task::none()
+            }
+            Message::TextEditor(text_editor::Message::OpenPath(path)) => self.open_path(path),
+            Message::TextEditor(text_editor::Message::TranslationsUpdated(translations)) => {
+                self.editor_translations = (*translations).clone();
+                self.update_all_editors(text_editor::Message::TranslationsUpdated(translations))
+            }
+            Message::TextEditor(message @ (text_editor::Message::IndentSettingsChanged(..) | text_editor::Message::ReadOnlyChanged(_))) => {
+                self.update_all_editors(message)
+            }
+            Message::TextEditor(message) => {
+                let sync = match &message {
+                    text_editor::Message::FileSaved(Ok(path)) => self.sync_saved_file(path.clone()),
+                    text_editor::Message::ActionPerformed(action) if action.is_edit() => self.active_file_changed(),
+                    _ => Task::none(),
+                };
+                let is_save_result = matches!(message, text_editor::Message::FileSaved(_));
+                let is_save_check = matches!(message, text_editor::Message::SaveChecked(..));
+                
+                // With every tab closed, the empty state can still start a new file
+                if self.tabs.is_empty() && matches!(message, text_editor::Message::NewFile | text_editor::Message::OpenFile) {
+                    self.tabs.push(self.new_editor());
+                }
+                
+                let task = Task::batch([sync, self.update_active_editor(message)]);
+                // A blocked save ends at its check, without the file ever being written
+                let is_save_result = is_save_result || (is_save_check && !self.tabs.active().is_some_and(TextEditor::is_loading));
+                let saved = self.tabs.active().is_some_and(|editor| !editor.is_dirty());
+                
+                // Saving failed or was cancelled, or there are new edits, so the window or tab has to stay open
+                match self.close_after_save.take() {
+                    Some(CloseTarget::Window(id)) if is_save_result && saved => Task::batch([task, self.save_next_then_close(id)]),
+                    Some(CloseTarget::Tab(_)) if is_save_result && saved => {
+                        if let Some(index) = self.tabs.active_index() {
+                            self.tabs.remove(index);
+                        }
+                        task
+                    }
+                    Some(target) if !is_save_result => {
+                        self.close_after_save = Some(target);
+                        task
+                    }
+                    _ => task,
+                }
+            }
+            Message::Header(message) => self.header.update(message),
+            Message::FileTree(message) => self.file_tree.update(message),
         }
     }
     
+    fn update_active_editor(&mut self, message: text_editor::Message) -> Task<Message> {
+        match self.tabs.active_mut() {
+            Some(editor) => editor.update(message),
+            None => Task::none(),
+        }
+    }
+    
+    /// An empty tab with the window's settings
+    fn new_editor(&self) -> TextEditor {
+        TextEditor::new(self.theme, self.editor_translations.clone())
+            .with_save_validation(self.save_validation)
+            .with_read_only(self.project.as_ref().is_some_and(|project| project.read_only))
+            .with_project_index(self.project.as_ref().map(|project| project.path.clone()).zip(self.project_index.clone()))
+    }
+    
+    /// Forgets the shown project's index, so that the tabs stop looking references up in it
+    fn clear_project_index(&mut self) -> Task<Message> {
+        match self.project_index.take() {
+            Some(_) => self.update_all_editors(text_editor::Message::ProjectIndexChanged(None)),
+            None => Task::none(),
+        }
+    }
+    
+    /// Stops or allows changes in the file tree and every tab, following the shown project
+    fn set_read_only(&mut self, read_only: bool) -> Task<Message> {
+        Task::batch([
+            self.file_tree.update(file_tree::Message::ReadOnlyChanged(read_only)),
+            self.update_all_editors(text_editor::Message::ReadOnlyChanged(read_only)),
+        ])
+    }
+    
+    /// Sends settings which apply to every open file to each tab
+    fn update_all_editors(&mut self, message: text_editor::Message) -> Task<Message> {
+        let tasks: Vec<_> = self.tabs.iter_mut()
+            .map(|editor| editor.update(message.clone()))
+            .collect();
+        Task::batch(tasks)
+    }
+    
+    /// Switches to the tab which already has the file open, or opens it in a new one
+    fn open_path(&mut self, path: PathBuf) -> Task<Message> {
+        if let Some(index) = self.tabs.position(|editor| editor.file() == Some(path.as_path())) {
+            self.tabs.activate(index);
+            return Task::none();
+        }
+        
+        self.tabs.push(self.new_editor());
+        self.update_active_editor(text_editor::Message::OpenPath(path))
+    }
+    
+    /// The shown project and the file's path relative to its root, if the file is in it and the project can be changed
+    fn project_file(&self, path: &Path) -> Option<(ProjectID, PathBuf)> {
+        let project = self.project.as_ref().filter(|project| !project.read_only)?;
+        let relative_path = path.strip_prefix(&project.path).ok()?;
+        Some((project.id, relative_path.to_path_buf()))
+    }
+    
+    /// Passes a file the editor saved to the shown project, so saving the project doesn't write the old contents back
+    fn sync_saved_file(&self, path: PathBuf) -> Task<Message> {
+        let Some((project_id, relative_path)) = self.project_file(&path) else {
+            return Task::none();
+        };
+        self.lint_service.file_changed(&relative_path);
+        let project_service = self.app_context.project_service_context().clone();
+        let filesystem = self.app_context.filesystem_service_context().clone();
+        
+        Task::future(async move {
+            let result: Result<(), String> = async {
+                let contents = filesystem.read().await.read_file(&path).await.map_err(|error| error.to_string())?;
+                let mut contents = String::from_utf8(contents).map_err(|error| error.to_string())?;
+                // The editor's encoding decides whether the project writes the file with a BOM from now on
+                let bom = bom::strip(&mut contents);
+                
+                project_service.read().await.file_saved(project_id, &relative_path, contents, bom).await.map_err(|error| error.to_string())
+            }.await;
+            
+            if let Err(error) = result {
+                tracing::warn!("Could not pass {} to its project - {}", path.display(), error);
+            }
+        }).discard()
+    }
+    
+    /// Lets the shown project and the linter forget what they parsed from the active file once the editor starts changing it.
+    /// Only the first edit since opening or saving is passed on, as the file stays changed until it is saved
+    fn active_file_changed(&self) -> Task<Message> {
+        let Some((_, relative_path)) = self.tabs.active()
+            .filter(|editor| !editor.is_dirty())
+            .and_then(TextEditor::file)
+            .and_then(|path| self.project_file(path)) else {
+            return Task::none();
+        };
+        self.lint_service.file_changed(&relative_path);
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::future(async move {
+            project_service.read().await.file_changed(&relative_path).await;
+        }).discard()
+    }
+    
+    /// Follows a file renamed on disk in the shown project, optionally rewriting references to it, then saves the project
+    /// so that the rewritten files reach the disk too
+    fn move_project_file(&self, from: &Path, to: &Path, update_references: bool) -> Task<Message> {
+        let (Some((project_id, from)), Some((_, to))) = (self.project_file(from), self.project_file(to)) else {
+            return Task::none();
+        };
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::perform(
+            async move {
+                let project_service = project_service.read().await;
+                project_service.move_file(project_id, &from, &to).await?;
+                
+                let locations = (resource_location(&from), resource_location(&to));
+                let rewrite = match locations {
+                    (Some(from), Some(to)) if update_references && from != to => {
+                        let rewriter = move |location: &ResourceLocation| (*location == from).then(|| to.clone());
+                        project_service.rewrite_references(project_id, &rewriter).await?
+                    }
+                    _ => ReferenceRewrite::default(),
+                };
+                
+                project_service.save_project(project_id).await?;
+                Ok(rewrite)
+            },
+            |result: Result<ReferenceRewrite, ProjectServiceError>| Message::ReferencesUpdated(result.map_err(|error| error.to_string()))
+        )
+    }
+    
+    /// Saves the tabs with unsaved changes one at a time, closing the window once none are left
+    fn save_next_then_close(&mut self, id: iced::window::Id) -> Task<Message> {
+        match self.tabs.position(|editor| editor.is_dirty()) {
+            Some(index) => {
+                self.tabs.activate(index);
+                self.close_after_save = Some(CloseTarget::Window(id));
+                self.update_active_editor(text_editor::Message::SaveFile)
+            }
+            None => self.save_project_then_close(id),
+        }
+    }
+    
+    /// Saves the shown project if it has unsaved changes, then closes the window. If the save fails the window stays open
+    fn save_project_then_close(&self, id: iced::window::Id) -> Task<Message> {
+        let Some(project_id) = self.project.as_ref().map(|project| project.id) else {
+            return iced::window::close(id);
+        };
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::future(async move {
+            let project_service = project_service.read().await;
+            if project_service.has_unsaved_changes(project_id).await? {
+                project_service.save_project(project_id).await?;
+            }
+            Ok::<_, ProjectServiceError>(())
+        }).then(move |result| match result {
+            Ok(()) => iced::window::close(id),
+            Err(error) => Task::done(Message::Notify(Notification::error(error.to_string()))),
+        })
+    }
+    
+    /// Imports and indexes a dropped zip in the background, showing which stage it has reached
+    /// and previewing its icon until the project is ready
+    fn import_zip(&mut self, path: PathBuf) -> Task<Message> {
+        self.importing.insert(path.clone(), ImportProgress::Extracting);
+        let project_service = self.app_context.project_service_context().clone();
+        let preview = self.preview_zip_icon(path.clone());
+        
+        let import = Task::run(
+            iced::stream::channel(INDEX_PROGRESS_BUFFER, move |mut output| async move {
+                let mut progress_output = output.clone();
+                let progress_path = path.clone();
+                let on_progress = Box::new(move |progress| {
+                    // Dropping an update when the window is behind is fine, as a newer one will follow
+                    let _ = progress_output.try_send(Message::ImportProgressed(progress_path.clone(), progress));
+                });
+                
+                let result = project_service.read().await
+                    .import_zip_with_progress(ZipPath::Single(path.clone()), on_progress).await
+                    .map_err(|error| error.to_string());
+                
+                let _ = output.send(Message::ZipImported(path, result)).await;
+            }),
+            std::convert::identity
+        );
+        
+        Task::batch([preview, import])
+    }
+    
+    fn preview_zip_icon(&self, path: PathBuf) -> Task<Message> {
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::perform(
+            async move {
+                project_service.read().await
+                    .zip_pack_icon(&path).await
+                    .map_err(|error| error.to_string())
+            },
+            Message::ZipPackIconLoaded
+        )
+    }
+    
+    /// Builds the project's index in the background, so the window stays responsive on large packs
+    fn index_project(&mut self, project_id: ProjectID) -> Task<Message> {
+        self.indexing.insert(project_id, IndexProgress { files_indexed: 0, total_files: 0 });
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::run(
+            iced::stream::channel(INDEX_PROGRESS_BUFFER, move |mut output| async move {
+                let mut progress_output = output.clone();
+                let on_progress = Box::new(move |progress| {
+                    // Dropping an update when the window is behind is fine, as a newer one will follow
+                    let _ = progress_output.try_send(Message::IndexProgressed(project_id, progress));
+                });
+                
+                let result = project_service.read().await
+                    .index_project(project_id, on_progress).await
+                    .map_err(|error| error.to_string());
+                
+                let _ = output.send(Message::ProjectIndexed(project_id, result)).await;
+            }),
+            std::convert::identity
+        )
+    }
+    
+    /// Follows the files a fix moved, given as `(from, to)` relative to the project root, in the open tabs and the file tree,
+    /// then lints the project again
+    fn files_fixed(&mut self, project_id: ProjectID, moves: Vec<(PathBuf, PathBuf)>, notice: String) -> Task<Message> {
+        let Some(project) = self.project.as_ref().filter(|project| project.id == project_id) else {
+            return Task::none();
+        };
+        let root = project.path.clone();
+        
+        for (from, to) in &moves {
+            self.lint_service.file_changed(from);
+            for editor in self.tabs.iter_mut() {
+                editor.rename_open_file(&root.join(from), &root.join(to));
+            }
+        }
+        
+        let notification = Notification::success(format!("{}: {}", notice, moves.len()));
+        Task::batch([
+            Task::done(Message::Notify(notification)),
+            self.file_tree.update(file_tree::Message::RootSelected(root)),
+            self.lint_project(project_id),
+        ])
+    }
+    
+    fn detect_install_context(&self, path: String) -> Task<Message> {
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::perform(
+            async move {
+                let context = project_service.read().await.detect_install_context(Path::new(&path));
+                (path, context)
+            },
+            |(path, context)| Message::NewProjectPathDetected(path, context)
+        )
+    }
+    
+    fn lint_project(&self, project_id: ProjectID) -> Task<Message> {
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::perform(
+            diagnostics::lint(project_service, self.lint_service.clone(), project_id),
+            move |result| Message::ProjectLinted(project_id, result)
+        )
+    }
+    
+    fn load_pack_icon(&self, project_id: ProjectID) -> Task<Message> {
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::perform(
+            async move {
+                project_service.read().await
+                    .pack_icon(project_id).await
+                    .map_err(|error| error.to_string())
+            },
+            move |result| Message::PackIconLoaded(project_id, result)
+        )
+    }
+    
+    fn load_operation_timings(&self) -> Task<Message> {
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::perform(
+            async move {
+                let project_service = project_service.read().await;
+                
+                Operation::ALL.into_iter()
+                    .filter_map(|operation| project_service.metrics().stats(operation).map(|stats| (operation, stats)))
+                    .collect()
+            },
+            Message::OperationTimingsLoaded
+        )
+    }
+    
+    /// Runs `before_close` on the project, then closes it if that succeeded
+    fn close_project_after<F, Fut>(&self, project_id: ProjectID, before_close: F) -> Task<Message>
+    where
+        F: FnOnce(ProjectServiceContext, ProjectID) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), ProjectServiceError>> + Send,
+    {
+        let project_service = self.app_context.project_service_context().clone();
+        
+        Task::perform(
+            async move {
+                before_close(project_service.clone(), project_id).await?;
+                project_service.read().await.close_project(project_id).await
+            },
+            move |result| match result {
+                Ok(()) => Message::ProjectClosed(project_id),
+                Err(error) => Message::Notify(Notification::error(error.to_string())),
+            }
+        )
+    }
+    
     pub fn view(&self) -> Element<Message> {
         let header_menu = Container::new(self.header.view());
-        let action_menu = Container::new(iced::widget::text("Action Menu"));
+        let action_menu_items = [
+            Some(text("Action Menu").into()),
+            Some(horizontal_space().into()),
+            self.import_indicator(),
+            self.indexing_indicator(),
+            Some(button(text(self.event_log_translations.title.as_str())).style(button::secondary).on_press(Message::ToggleEventLog).into()),
+            Some(button(text(self.diagnostics_translations.title.as_str())).style(button::secondary)
+                .on_press_maybe((self.diagnostics.is_some() || self.project.is_some()).then_some(Message::ToggleDiagnostics)).into()),
+            Some(pick_list(LogLevel::ALL, Some(self.log_level), Message::LogLevelSelected).into()),
+            Some(pick_list(highlighter::Theme::ALL, Some(self.theme), Message::ThemeSelected).into()),
+            Some(pick_list(SaveValidation::ALL, Some(self.save_validation), Message::SaveValidationSelected).into()),
+        ].into_iter().flatten().collect();
+        let action_menu = Container::new(Row::with_children(self.layout_direction.arrange(action_menu_items))
+            .padding([5, 10]));
         
         let main_view = PaneGrid::new(&self.panes, |pane, state, is_maximized| {
             pane_grid::Content::new(
                 match state.pane_type {
-                    PaneType::FileTree => Container::new(iced::widget::text("File Tree")),
-                    PaneType::MainContent => Container::new(self.text_editor.view()),
-                    PaneType::Preview => Container::new(iced::widget::text("Preview")),
+                    PaneType::FileTree => Container::new(self.file_tree.view()),
+                    PaneType::MainContent => Container::new(self.main_content()),
+                    PaneType::Preview => match &self.pack_icon {
+                        Some(panel) => Container::new(panel.view(&self.pack_icon_translations)),
+                        None => Container::new(iced::widget::text("Preview")),
+                    },
                 })
         })
             .width(Length::Fill)
@@ -130,6 +1257,11 @@ impl ApplicationWindow {
         let total_window = Column::new()
             .push(header_menu)
             .push(action_menu)
+            .push_maybe(self.new_project.as_ref().map(|dialog| Container::new(dialog.view(&self.new_project_translations)).padding([5, 10])))
+            .push_maybe(self.close_prompt.map(|_| Container::new(close_prompt::view(&self.close_prompt_translations)).padding([5, 10])))
+            .push_maybe((!self.notifications.is_empty()).then(|| Container::new(self.notifications.view()).padding([5, 10])))
+            .push_maybe(self.event_log.as_ref().map(|events| Container::new(event_log::view(events, &self.operation_timings, &self.event_log_translations)).padding([5, 10])))
+            .push_maybe(self.diagnostics.as_ref().map(|panel| Container::new(panel.view(&self.diagnostics_translations)).padding([5, 10])))
             .push(main_view);
         
         Container::new(total_window)
@@ -138,8 +1270,85 @@ impl ApplicationWindow {
             .into()
     }
     
+    fn main_content(&self) -> Element<Message> {
+        let Some(editor) = self.tabs.active() else {
+            return Column::new()
+                .push(text(self.editor_translations.no_open_files.as_str()))
+                .push(button(text(self.editor_translations.new_file.as_str())).on_press(text_editor::Message::NewFile.into()))
+                .push(button(text(self.editor_translations.open_file.as_str())).style(button::secondary).on_press(text_editor::Message::OpenFile.into()))
+                .spacing(10)
+                .padding(10)
+                .into();
+        };
+        
+        let tab_bar = self.tabs.iter()
+            .enumerate()
+            .map(|(index, editor)| {
+                let title = if editor.is_dirty() { format!("{} *", editor.title()) } else { editor.title() };
+                let style = if self.tabs.active_index() == Some(index) { button::primary } else { button::secondary };
+                
+                Row::new()
+                    .push(button(text(title)).style(style).on_press(Message::SelectTab(index)))
+                    .push(button(text("×")).style(style).on_press(Message::CloseTab(index)))
+                    .into()
+            })
+            .collect::<Vec<_>>();
+        
+        Column::new()
+            .push(Row::with_children(tab_bar).spacing(5).padding([5, 10]))
+            .push(editor.view())
+            .into()
+    }
+    
+    fn import_indicator(&self) -> Option<Element<Message>> {
+        let translations = &self.notification_translations;
+        let stages: Vec<_> = self.importing.values()
+            .filter_map(|progress| match progress {
+                ImportProgress::Extracting => Some(translations.extracting.clone()),
+                ImportProgress::Parsing => Some(translations.parsing.clone()),
+                ImportProgress::Indexing(progress) => Some(format!("{} {}/{}", translations.indexing, progress.files_indexed, progress.total_files)),
+                ImportProgress::Done => None,
+            })
+            .collect();
+        
+        (!stages.is_empty()).then(|| text(stages.join(", ")).into())
+    }
+    
+    fn indexing_indicator(&self) -> Option<Element<Message>> {
+        let (files_indexed, total_files) = self.indexing.values()
+            .fold((0, 0), |(indexed, total), progress| (indexed + progress.files_indexed, total + progress.total_files));
+        
+        (!self.indexing.is_empty()).then(|| text(format!("{} {}/{}", self.notification_translations.indexing, files_indexed, total_files))
+            .into())
+    }
+    
+    pub fn subscription(&self) -> Subscription<Message> {
+        let window_events = event::listen_with(|event, _status, window| match event {
+            Event::Window(iced::window::Event::FileDropped(path)) => Some(Message::FileDropped(path)),
+            Event::Window(iced::window::Event::CloseRequested) => Some(Message::CloseRequested(window)),
+            _ => None,
+        });
+        
+        // Only tick while there is something to expire
+        let notification_ticks = if self.notifications.is_empty() {
+            Subscription::none()
+        } else {
+            iced::time::every(NOTIFICATION_TICK).map(Message::NotificationTick)
+        };
+        
+        let event_log_ticks = if self.event_log.is_some() {
+            iced::time::every(EVENT_LOG_TICK).map(|_| Message::EventLogTick)
+        } else {
+            Subscription::none()
+        };
+        
+        Subscription::batch([window_events, notification_ticks, event_log_ticks])
+    }
+    
     pub fn theme(&self) -> Theme {
-        if self.theme.is_dark() {
+        if self.theme == highlighter::Theme::HighContrast {
+            high_contrast::iced_theme()
+        } else if self.theme.is_dark() {
             Theme::Dark
         } else {
             Theme::Light
@@ -147,6 +1356,43 @@ impl ApplicationWindow {
     }
 }
 
+/// The resource a file defines, given its path relative to the pack root
+fn resource_location(path: &Path) -> Option<ResourceLocation> {
+    ProjectFile { path, contents: "" }.resource_location()
+}
+
+//------------//
+
+/// A project opened from its folder, and whether it was opened read-only
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShownProject {
+    id: ProjectID,
+    path: PathBuf,
+    read_only: bool,
+}
+
+//------------//
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DropAction {
+    OpenFile(PathBuf),
+    ImportZip(PathBuf),
+    Unsupported(PathBuf),
+}
+
+/// Decides what to do with a path dropped onto the window, based on its extension
+pub(crate) fn dispatch_drop(path: &Path) -> DropAction {
+    let extension = path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("zip") => DropAction::ImportZip(path.to_path_buf()),
+        Some(extension) if TEXT_FILE_EXTENSIONS.contains(&extension) => DropAction::OpenFile(path.to_path_buf()),
+        _ => DropAction::Unsupported(path.to_path_buf()),
+    }
+}
+
 //------------//
 
 #[derive(Debug, Clone)]
@@ -169,4 +1415,27 @@ enum PaneType {
     FileTree,
     MainContent,
     Preview
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case::json("data/pack.json", DropAction::OpenFile(PathBuf::from("data/pack.json")))]
+    #[case::mcfunction("load.mcfunction", DropAction::OpenFile(PathBuf::from("load.mcfunction")))]
+    #[case::mcmeta("pack.mcmeta", DropAction::OpenFile(PathBuf::from("pack.mcmeta")))]
+    #[case::zip("pack.zip", DropAction::ImportZip(PathBuf::from("pack.zip")))]
+    #[case::uppercase_zip("PACK.ZIP", DropAction::ImportZip(PathBuf::from("PACK.ZIP")))]
+    #[case::image("pack.png", DropAction::Unsupported(PathBuf::from("pack.png")))]
+    #[case::no_extension("data", DropAction::Unsupported(PathBuf::from("data")))]
+    fn test_dispatch_drop(#[case] path: &str, #[case] expected: DropAction) {
+        // Given a path dropped onto the window
+        // When I dispatch it
+        let action = dispatch_drop(Path::new(path));
+
+        // Then it should map to the right action
+        assert_eq!(action, expected);
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
+
+/// How many of the most recent events the window's event log keeps
+pub const EVENT_LOG_CAPACITY: usize = 200;
+
+/// Set once logging is set up, so the level can be changed from anywhere afterwards
+static LOG_LEVEL_HANDLE: OnceLock<LogLevelHandle> = OnceLock::new();
+
+/// Set once logging is set up, so the window can show what has been logged
+static EVENT_LOG: OnceLock<EventLog> = OnceLock::new();
+
+/// How much the editor logs about itself. Other crates only ever log warnings and errors, unless the level is lower still
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+
+    fn directive(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+
+    /// Filter directives logging the editor at this level
+    pub fn filter_directives(self) -> String {
+        format!("gaea={},{}", self.directive(), self.min(LogLevel::Warn).directive())
+    }
+}
+
+impl Default for LogLevel {
+    /// The editor's level when it starts, unless `RUST_LOG` says otherwise. Debug builds log in more detail, as they are only run by developers
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            LogLevel::Debug
+        } else {
+            LogLevel::Info
+        }
+    }
+}
+
+impl Display for LogLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogLevel::Error => write!(f, "Error"),
+            LogLevel::Warn => write!(f, "Warning"),
+            LogLevel::Info => write!(f, "Info"),
+            LogLevel::Debug => write!(f, "Debug"),
+            LogLevel::Trace => write!(f, "Trace"),
+        }
+    }
+}
+
+/// Swaps the filter of a running subscriber, so logs can be made more detailed without restarting
+#[derive(Debug, Clone)]
+pub struct LogLevelHandle(reload::Handle<EnvFilter, Registry>);
+
+impl LogLevelHandle {
+    pub fn new(handle: reload::Handle<EnvFilter, Registry>) -> Self {
+        Self(handle)
+    }
+
+    pub fn set_level(&self, level: LogLevel) -> Result<(), reload::Error> {
+        self.0.reload(EnvFilter::new(level.filter_directives()))
+    }
+}
+
+/// Makes the handle of the global subscriber available to [`set_level`]. Only the first call has any effect
+pub fn install(handle: LogLevelHandle) {
+    let _ = LOG_LEVEL_HANDLE.set(handle);
+}
+
+/// Changes the level of the global subscriber, if logging has been set up
+pub fn set_level(level: LogLevel) -> Result<(), reload::Error> {
+    match LOG_LEVEL_HANDLE.get() {
+        Some(handle) => handle.set_level(level),
+        None => Ok(()),
+    }
+}
+
+/// An event as shown in the window's event log
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LogEvent {
+    pub level: Level,
+    pub target: String,
+    /// The event's message, followed by any other fields it was logged with
+    pub message: String,
+}
+
+impl Display for LogEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}: {}", self.level, self.target, self.message)
+    }
+}
+
+/// The most recent events which got through the filter, oldest first. Once full, each new event drops the oldest
+#[derive(Debug, Clone)]
+pub struct EventLog {
+    capacity: usize,
+    events: Arc<Mutex<VecDeque<LogEvent>>>,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            events: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    pub fn push(&self, event: LogEvent) {
+        // A panic while holding the lock can't leave the buffer half written, so carry on logging rather than panic again
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    pub fn events(&self) -> Vec<LogEvent> {
+        self.events.lock().unwrap_or_else(PoisonError::into_inner).iter().cloned().collect()
+    }
+
+    /// A layer writing every event it sees into this log
+    pub fn layer(&self) -> EventLogLayer {
+        EventLogLayer(self.clone())
+    }
+}
+
+pub struct EventLogLayer(EventLog);
+
+impl<S: Subscriber> Layer<S> for EventLogLayer {
+    fn on_event(&self, event: &Event<'_>, _context: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        self.0.push(LogEvent {
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.into_message(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl MessageVisitor {
+    fn into_message(self) -> String {
+        [self.message].into_iter()
+            .chain(self.fields)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+/// Makes the global subscriber's event log available to [`recent_events`]. Only the first call has any effect
+pub fn install_event_log(event_log: EventLog) {
+    let _ = EVENT_LOG.set(event_log);
+}
+
+/// The events logged most recently, oldest first, or none if logging hasn't been set up
+pub fn recent_events() -> Vec<LogEvent> {
+    EVENT_LOG.get().map(EventLog::events).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing_subscriber::layer::SubscriberExt;
+    use super::*;
+
+    #[test]
+    fn test_reload_changes_level() {
+        // Given a subscriber logging the editor at info
+        let (filter, handle) = reload::Layer::new(EnvFilter::new(LogLevel::Info.filter_directives()));
+        let handle = LogLevelHandle::new(handle);
+        let subscriber = Registry::default().with(filter);
+
+        tracing::subscriber::with_default(subscriber, || {
+            assert!(tracing::enabled!(target: "gaea_mc::services", Level::INFO));
+            assert!(!tracing::enabled!(target: "gaea_mc::services", Level::DEBUG));
+
+            // When I raise the level to debug
+            handle.set_level(LogLevel::Debug).unwrap();
+
+            // Then debug logs from the editor should be let through, but not from other crates
+            assert!(tracing::enabled!(target: "gaea_mc::services", Level::DEBUG));
+            assert!(!tracing::enabled!(target: "iced::window", Level::DEBUG));
+
+            // And lowering it to errors should stop even warnings
+            handle.set_level(LogLevel::Error).unwrap();
+            assert!(!tracing::enabled!(target: "gaea_mc::services", Level::WARN));
+        });
+    }
+
+    #[test]
+    fn test_event_log_keeps_recent_events() {
+        // Given a subscriber writing into an event log with room for two events
+        let event_log = EventLog::new(2);
+        let subscriber = Registry::default().with(event_log.layer());
+
+        // When three events are logged
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "gaea_mc::services", "Loaded language en_us");
+            tracing::warn!(target: "gaea_mc::services", file = "xx_xx.json", "Skipped language file");
+            tracing::error!(target: "gaea_mc::services", "Could not read pack.mcmeta");
+        });
+
+        // Then only the last two should be kept, oldest first, with their fields
+        assert_eq!(event_log.events(), vec![
+            LogEvent { level: Level::WARN, target: "gaea_mc::services".to_string(), message: "Skipped language file file=xx_xx.json".to_string() },
+            LogEvent { level: Level::ERROR, target: "gaea_mc::services".to_string(), message: "Could not read pack.mcmeta".to_string() },
+        ]);
+    }
+}
@@ -1,3 +1,8 @@
 pub mod gui;
 mod render;
-pub mod app_context;
\ No newline at end of file
+pub mod app_context;
+pub mod cli;
+pub mod file_manager;
+pub mod logging;
+#[cfg(feature = "translation-export")]
+pub mod translation_export;
\ No newline at end of file
@@ -0,0 +1,30 @@
+use std::path::Path;
+use crate::application::gui;
+use crate::data::domain::pack_info::PackInfoTranslationKeys;
+use crate::services::filesystem_service::{FileWriteOptions, FilesystemProvider};
+use crate::services::translation_service::{self, TranslationError};
+
+/// Command line flag which exports the English skeleton instead of starting the app
+pub const EXPORT_TRANSLATIONS_FLAG: &str = "--export-translations";
+
+/// English defaults for every translation key used by the app
+pub fn app_english_defaults() -> Vec<(&'static str, &'static str)> {
+    [
+        gui::english_defaults(),
+        translation_service::english_defaults::<PackInfoTranslationKeys>(),
+    ].concat()
+}
+
+/// Adds any keys missing from the language file at `path`, creating it if needed
+pub async fn export_english_skeleton(filesystem: &(dyn FilesystemProvider + Send + Sync), path: &Path) -> Result<(), TranslationError> {
+    let existing = if filesystem.file_exists(path).await? {
+        Some(filesystem.read_file(path).await?)
+    } else {
+        None
+    };
+
+    let skeleton = translation_service::merge_language_skeleton(existing.as_deref(), &app_english_defaults())?;
+    filesystem.write_file(path, &skeleton, FileWriteOptions::Overwrite).await?;
+
+    Ok(())
+}
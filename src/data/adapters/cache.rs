@@ -0,0 +1,122 @@
+use std::fmt::{Debug, Formatter};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use dashmap::DashMap;
+
+/// Parsed files, keyed by path and checked against a hash of their contents,
+/// so validating the same pack again only re-parses the files which changed
+pub struct ParseCache<T> {
+    entries: DashMap<PathBuf, CachedParse<T>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+struct CachedParse<T> {
+    content_hash: u64,
+    value: Arc<T>,
+}
+
+/// Counters for debugging how much the cache is saving
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl<T> ParseCache<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the cached value for the file if its contents haven't changed, otherwise parses and caches it
+    pub fn get_or_parse(&self, path: &Path, contents: &[u8], parse: impl FnOnce() -> T) -> Arc<T> {
+        let content_hash = hash_contents(contents);
+
+        if let Some(cached) = self.entries.get(path).filter(|cached| cached.content_hash == content_hash) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return cached.value.clone();
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = Arc::new(parse());
+        self.entries.insert(path.to_path_buf(), CachedParse { content_hash, value: value.clone() });
+
+        value
+    }
+
+    /// Drops the cached parse of a file, for when it changes or is removed
+    pub fn invalidate(&self, path: &Path) {
+        self.entries.remove(path);
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.len(),
+        }
+    }
+}
+
+impl<T> Default for ParseCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for ParseCache<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParseCache").field("stats", &self.stats()).finish()
+    }
+}
+
+fn hash_contents(contents: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_contents_served_from_cache() {
+        // Given a file which has already been parsed
+        let cache = ParseCache::new();
+        let path = Path::new("data/test/recipe/stone.json");
+        cache.get_or_parse(path, b"{}", || 1);
+
+        // When it is parsed again with the same contents
+        let value = cache.get_or_parse(path, b"{}", || panic!("should not re-parse unchanged contents"));
+
+        // Then the cached value should be returned
+        assert_eq!(*value, 1);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 1, entries: 1 });
+    }
+
+    #[test]
+    fn test_changed_contents_reparsed() {
+        // Given a file which has already been parsed
+        let cache = ParseCache::new();
+        let path = Path::new("data/test/recipe/stone.json");
+        cache.get_or_parse(path, b"{}", || 1);
+
+        // When its contents change, or it is invalidated
+        let changed = cache.get_or_parse(path, br#"{"type": "minecraft:smelting"}"#, || 2);
+        cache.invalidate(path);
+        let invalidated = cache.get_or_parse(path, br#"{"type": "minecraft:smelting"}"#, || 3);
+
+        // Then it should be parsed again each time
+        assert_eq!(*changed, 2);
+        assert_eq!(*invalidated, 3);
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 3, entries: 1 });
+    }
+}
@@ -2,16 +2,21 @@ use std::convert::Infallible;
 use std::error::Error;
 use std::ops::Deref;
 use std::sync::Arc;
-use tokio::sync::{RwLock, RwLockReadGuard};
 use crate::data::adapters::project::ProjectAdapter;
 use crate::data::adapters::resource_location::ResourceLocationAdapter;
+use crate::data::adapters::structure::StructureAdapter;
 use crate::data::{domain, serialization};
 use crate::data::adapters::pack_info::PackInfoAdapter;
+use crate::data::domain::compatibility::FeatureVersion;
+use crate::data::domain::project::ProjectType;
+use crate::data::serialization::json::JsonStyle;
 use crate::repositories::adapter_repo;
-use crate::repositories::adapter_repo::{AdapterProvider, AdapterProviderContext};
+use crate::repositories::adapter_repo::{AdapterProvider, AdapterProviderContext, AdapterRegistrationBuilder};
 
-mod pack_info;
+pub mod cache;
+pub mod pack_info;
 mod resource_location;
+mod structure;
 pub mod project;
 
 #[async_trait::async_trait]
@@ -24,51 +29,207 @@ where
     type SerializedConversionError: AdapterError;
     
     async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(
-        serialized: AdapterInput<&Serialized>,
+        serialized: AdapterInput<'_, Serialized>,
         context: AdapterProviderContext<'_, AdpProvider>
     ) -> Result<Domain, Self::ConversionError>;
     async fn serialize<AdpProvider: AdapterProvider + ?Sized>(
-        domain: AdapterInput<&Domain>,
+        domain: AdapterInput<'_, Domain>,
         context: AdapterProviderContext<'_, AdpProvider>
     ) -> Result<Serialized, Self::SerializedConversionError>;
+
+    /// Where in a pack the files this adapter converts live, and the oldest version of the game which loads them,
+    /// for file types which weren't always in the game. Counted towards the version a project requires
+    fn min_version() -> Option<FeatureVersion> {
+        None
+    }
 }
 
 pub trait AdapterError: Error + Send + Sync {}
 
 impl AdapterError for Infallible {}
 
-pub struct AdapterInput<T>(Arc<T>);
+/// What an adapter converts, either borrowed from the caller or owned by the input.
+/// Derefs to the value itself whichever it holds, so adapters read both the same way
+pub struct AdapterInput<'a, T> {
+    inner: InputValue<'a, T>,
+    json_style: JsonStyle,
+}
+
+enum InputValue<'a, T> {
+    Borrowed(&'a T),
+    Owned(Arc<T>),
+}
+
+impl<'a, T> AdapterInput<'a, T> {
+    pub fn new(inner: &'a T) -> Self {
+        Self::with_value(InputValue::Borrowed(inner))
+    }
 
-impl<T> AdapterInput<T> {
-    pub fn new(inner: T) -> Self {
-        Self(Arc::new(inner))
+    /// For values built only to be converted, which have nothing to borrow them from
+    pub fn owned(inner: T) -> Self {
+        Self::with_arc(Arc::new(inner))
     }
     
     pub fn with_arc(arc: Arc<T>) -> Self {
-        Self(arc)
+        Self::with_value(InputValue::Owned(arc))
+    }
+
+    fn with_value(inner: InputValue<'a, T>) -> Self {
+        Self {
+            inner,
+            json_style: JsonStyle::default(),
+        }
+    }
+
+    /// Sets how adapters should write any JSON they serialize
+    pub fn with_json_style(self, json_style: JsonStyle) -> Self {
+        Self {
+            json_style,
+            ..self
+        }
+    }
+
+    pub fn json_style(&self) -> JsonStyle {
+        self.json_style
     }
 }
 
-impl<T> Deref for AdapterInput<T> {
+impl<T> Deref for AdapterInput<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        match &self.inner {
+            InputValue::Borrowed(inner) => inner,
+            InputValue::Owned(inner) => inner,
+        }
     }
 }
 
-impl<T> Clone for AdapterInput<T> {
+impl<T> Clone for AdapterInput<'_, T> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        let inner = match &self.inner {
+            InputValue::Borrowed(inner) => InputValue::Borrowed(*inner),
+            InputValue::Owned(inner) => InputValue::Owned(inner.clone()),
+        };
+
+        Self {
+            inner,
+            json_style: self.json_style,
+        }
     }
 }
 
+/// Registers the adapters every kind of project needs, which is all of them, as combined projects hold both kinds of pack
 pub fn register_default_adapters<AdapterProvider: adapter_repo::AdapterProvider + Send + Sync + 'static>(provider: &mut AdapterProvider) -> &AdapterProvider {
-    provider.register::<ProjectAdapter, project::SerializedType, project::DomainType>();
+    default_adapter_registrations(provider);
+    provider
+}
+
+/// Registers the default adapters, returning the builder so that more can be added without replacing them
+pub fn default_adapter_registrations<AdapterProvider: adapter_repo::AdapterProvider + Send + Sync + 'static>(provider: &AdapterProvider) -> AdapterRegistrationBuilder<'_, AdapterProvider> {
+    adapter_registrations_for(provider, ProjectType::Combined)
+}
+
+/// Registers only the default adapters a project of the given type needs, e.g. leaving out structures for resource packs.
+/// Returns the builder so that more can be added without replacing them
+pub fn adapter_registrations_for<AdapterProvider: adapter_repo::AdapterProvider + Send + Sync + 'static>(provider: &AdapterProvider, project_type: ProjectType) -> AdapterRegistrationBuilder<'_, AdapterProvider> {
+    let mut builder = AdapterRegistrationBuilder::new(provider);
     
-    provider.register::<PackInfoAdapter, pack_info::SerializedType, pack_info::DomainType>();
+    // Every pack is a project with a pack.mcmeta, and refers to resources by location
+    builder.register_default::<ProjectAdapter, project::SerializedType, project::DomainType>();
     
-    provider.register::<ResourceLocationAdapter, resource_location::SerializedType, resource_location::DomainType>();
+    builder.register_default::<PackInfoAdapter, pack_info::SerializedType, pack_info::DomainType>();
     
-    provider
-}
\ No newline at end of file
+    builder.register_default::<ResourceLocationAdapter, resource_location::SerializedType, resource_location::DomainType>();
+    
+    if matches!(project_type, ProjectType::DataPack | ProjectType::Combined) {
+        builder.register_default::<StructureAdapter, structure::SerializedType, structure::DomainType>();
+    }
+    
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use once_cell::sync::Lazy;
+    use rstest::rstest;
+    use tokio::sync::RwLock;
+    use crate::data::adapters::resource_location::ResourceLocationAdapter;
+    use crate::repositories::adapter_repo::AdapterRepository;
+    use crate::data::serialization::json::{JsonLayout, KeyOrder};
+    use crate::services::project_service::DefaultAdapterProvider;
+    use super::*;
+
+    static ADAPTER_PROVIDER: Lazy<RwLock<DefaultAdapterProvider>> = Lazy::new(|| RwLock::new(DefaultAdapterProvider::new()));
+
+    async fn adapter_context<'a>() -> AdapterProviderContext<'a, AdapterRepository> {
+        AdapterProviderContext::new(ADAPTER_PROVIDER.read().await)
+    }
+
+    #[rstest]
+    #[case::data_pack(ProjectType::DataPack, true)]
+    #[case::resource_pack(ProjectType::ResourcePack, false)]
+    #[case::combined(ProjectType::Combined, true)]
+    fn test_adapters_for_project_type(#[case] project_type: ProjectType, #[case] has_structures: bool) {
+        // Given an empty adapter provider
+        let provider = AdapterRepository::new();
+
+        // When I register the default adapters for a type of project
+        let registrations = adapter_registrations_for(&provider, project_type);
+
+        // Then every project should get the shared adapters, but only projects with a data pack the structure adapter
+        let mut expected = BTreeSet::from([
+            std::any::type_name::<ProjectAdapter>(),
+            std::any::type_name::<PackInfoAdapter>(),
+            std::any::type_name::<ResourceLocationAdapter>(),
+        ]);
+        if has_structures {
+            expected.insert(std::any::type_name::<StructureAdapter>());
+        }
+
+        assert_eq!(registrations.registered_adapters(), expected);
+    }
+
+    #[test]
+    fn test_default_adapters_cover_combined_projects() {
+        // Given an empty adapter provider
+        let provider = AdapterRepository::new();
+
+        // When I register the default adapters without a project type
+        let registrations = default_adapter_registrations(&provider);
+
+        // Then they should be the set for combined projects, which covers every other type
+        let combined_provider = AdapterRepository::new();
+        assert_eq!(registrations.registered_adapters(), adapter_registrations_for(&combined_provider, ProjectType::Combined).registered_adapters());
+    }
+
+    #[tokio::test]
+    async fn test_borrowed_and_owned_inputs() {
+        // Given the same resource location, borrowed into one input and owned by another
+        let borrowed = resource_location::SerializedType::new("example:gem");
+        let borrowed_input = AdapterInput::new(&borrowed);
+        let owned_input = AdapterInput::owned(resource_location::SerializedType::new("example:gem"));
+
+        // When an adapter reads each of them
+        let from_borrowed = ResourceLocationAdapter::deserialize(borrowed_input, adapter_context().await).await.unwrap();
+        let from_owned = ResourceLocationAdapter::deserialize(owned_input, adapter_context().await).await.unwrap();
+
+        // Then both should convert to the same value
+        assert_eq!(from_borrowed, from_owned);
+        assert_eq!(from_owned.to_string(), "example:gem");
+    }
+
+    #[test]
+    fn test_clone_keeps_value_and_style() {
+        // Given an owned input with a JSON style set
+        let input = AdapterInput::owned(String::from("example:gem")).with_json_style(JsonStyle::Reformat { layout: JsonLayout::Compact, key_order: KeyOrder::Alphabetical });
+
+        // When I clone it
+        let clone = input.clone();
+
+        // Then the clone should deref to the same value, with the same style
+        assert_eq!(*clone, *input);
+        assert_eq!(clone.json_style(), JsonStyle::Reformat { layout: JsonLayout::Compact, key_order: KeyOrder::Alphabetical });
+    }
+}
@@ -16,7 +16,7 @@ impl Adapter<SerializedType, DomainType> for PackInfoAdapter {
     type SerializedConversionError = PackInfoSerializationError;
 
     async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(
-        serialized: AdapterInput<&SerializedType>,
+        serialized: AdapterInput<'_, SerializedType>,
         _context: AdapterProviderContext<'_, AdpProvider>
     ) -> Result<DomainType, Self::ConversionError> {
         let pack_info = &*serialized;
@@ -28,38 +28,51 @@ impl Adapter<SerializedType, DomainType> for PackInfoAdapter {
 
         // By the pack.mcmeta spec, pack format must be included within supported formats
         if let Some(supported_formats) = supported_formats {
-            if !match supported_formats {
-                PackFormat::Single(format) => *format == pack_format,
-                PackFormat::Range(min, max) => pack_format >= *min && pack_format <= *max,
-                PackFormat::Object { min_inclusive, max_inclusive } => pack_format >= *min_inclusive && pack_format <= *max_inclusive,
-            } {
-                return Err(PackInfoDeserializationError::InvalidPackFormat(pack_format as u8, supported_formats.clone()))
+            let (min, max) = supported_formats.bounds();
+            if pack_format < min || pack_format > max {
+                return Err(PackInfoDeserializationError::InvalidPackFormat(pack_format, supported_formats.clone()))
             }
         }
-        
+
+        let supported_formats = supported_formats.as_ref().map(|supported_formats| {
+            let (min, max) = supported_formats.bounds();
+            match (u8::try_from(min), u8::try_from(max)) {
+                (Ok(min), Ok(max)) => Ok((min, max)),
+                _ => Err(PackInfoDeserializationError::InvalidPackFormat(pack_format, supported_formats.clone())),
+            }
+        }).transpose()?;
+
+
+        let format = u8::try_from(pack_format)
+            .map_err(|_| PackInfoDeserializationError::NoValidFormatFound(pack_format))?;
+
         Ok (PackInfoSerializationInput {
             description: description.into(),
-            format: pack_format as u8,
+            format,
+            supported_formats,
         })
     }
 
     async fn serialize<AdpProvider: AdapterProvider + ?Sized>(
-        domain: AdapterInput<&DomainType>,
+        domain: AdapterInput<'_, DomainType>,
         _context: AdapterProviderContext<'_, AdpProvider>
     ) -> Result<SerializedType, Self::SerializedConversionError> {
-        let description = &domain.description;
-        let format = domain.format;
+        Ok((&*domain).into())
+    }
+}
 
+impl From<&PackInfoSerializationInput> for SerializedPackInfo {
+    fn from(value: &PackInfoSerializationInput) -> Self {
         let pack = PackData::new(
-            description.clone().into(),
-            format as u32,
+            value.description.clone().into(),
+            value.format as u32,
             None
         );
 
-        Ok(SerializedPackInfo::new(
+        SerializedPackInfo::new(
             pack,
             None, None, None, None
-        ))
+        )
     }
 }
 
@@ -67,6 +80,9 @@ impl Adapter<SerializedType, DomainType> for PackInfoAdapter {
 pub struct PackInfoSerializationInput {
     pub description: PackDescription,
     pub format: u8,
+    /// The lowest and highest formats from `supported_formats`, if the pack declares any besides its own format
+    #[new(default)]
+    pub supported_formats: Option<(u8, u8)>,
 }
 
 impl Into<PackInfo> for PackInfoSerializationInput {
@@ -78,9 +94,9 @@ impl Into<PackInfo> for PackInfoSerializationInput {
 #[derive(Debug, thiserror::Error)]
 pub enum PackInfoDeserializationError {
     #[error("No valid format found for pack format {0}!")]
-    NoValidFormatFound(u8),
-    #[error("Pack format {0} is not within supported formats {1:?}!")]
-    InvalidPackFormat(u8, PackFormat),
+    NoValidFormatFound(u32),
+    #[error("Pack format {0} can't be used with supported formats {1:?}!")]
+    InvalidPackFormat(u32, PackFormat),
 }
 impl AdapterError for PackInfoDeserializationError {}
 
@@ -128,7 +144,8 @@ mod tests {
             // It should deserialize properly
             let PackInfoSerializationInput {
                 description,
-                format
+                format,
+                ..
             } = pack_data;
 
             assert!(matches!(description, PackDescription::String(text) if text == "Test desccription"));
@@ -161,7 +178,8 @@ mod tests {
             // It should deserialize properly
             let PackInfoSerializationInput {
                 description,
-                format
+                format,
+                ..
             } = pack_data;
 
             assert!(matches!(description, PackDescription::String(text) if text == "Test desccription"));
@@ -265,6 +283,61 @@ mod tests {
             assert!(result.is_err());
             assert!(matches!(result, Err(PackInfoDeserializationError::InvalidPackFormat(_, _))));
         }
+
+        #[tokio::test]
+        async fn test_pack_info_adapter_deser_pack_format_out_of_range() {
+            // Given serialized pack info with a pack format too large for any version
+            let pack = PackData::new(
+                TextComponent::String("Test desccription".to_string()),
+                300,
+                None
+            );
+
+            let pack_info = SerializedPackInfo::new(
+                pack,
+                None, None, None, None,
+            );
+            let pack_info = AdapterInput::new(&pack_info);
+
+            let repo = AdapterRepository::create_repo().await;
+            let context = AdapterRepository::context_from_repo(&repo).await;
+
+            // When I deserialize it
+            let result = PackInfoAdapter::deserialize(pack_info, context).await;
+
+            // It should return an error instead of truncating the format
+            assert!(matches!(result, Err(PackInfoDeserializationError::NoValidFormatFound(300))));
+        }
+
+        #[tokio::test]
+        async fn test_pack_info_adapter_deser_supported_format_out_of_range() {
+            // Given serialized pack info whose supported formats reach past what a pack format can hold
+            let pack_format = &*versions::D48;
+
+            let pack = PackData::new(
+                TextComponent::String("Test desccription".to_string()),
+                pack_format.get_format_id() as u32,
+                Some(PackFormat::Range(
+                    pack_format.get_format_id() as u32,
+                    300,
+                ))
+            );
+
+            let pack_info = SerializedPackInfo::new(
+                pack,
+                None, None, None, None,
+            );
+            let pack_info = AdapterInput::new(&pack_info);
+
+            let repo = AdapterRepository::create_repo().await;
+            let context = AdapterRepository::context_from_repo(&repo).await;
+
+            // When I deserialize it
+            let result = PackInfoAdapter::deserialize(pack_info, context).await;
+
+            // It should return an error instead of truncating the bound
+            assert!(matches!(result, Err(PackInfoDeserializationError::InvalidPackFormat(48, PackFormat::Range(48, 300)))));
+        }
     }
     
     mod serialize {
@@ -1,11 +1,21 @@
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use mc_version::{MinecraftVersion, PackFormat};
+use serde_json::Value;
 use crate::data::adapters;
 use crate::data::adapters::{Adapter, AdapterError, AdapterInput};
+use crate::data::adapters::cache::ParseCache;
 use crate::data::adapters::pack_info::{PackInfoSerializationInput};
+use crate::data::domain::pack_icon::PackIcon;
+use crate::data::domain::pack_path::PackRelativePath;
 use crate::data::domain::project::{PackInfoProjectData, Project as DomainProject};
+use crate::data::domain::structure::Structure as DomainStructure;
 use crate::data::domain::versions;
+use crate::data::serialization::json::JsonStyle;
 use crate::data::serialization::pack_info::PackInfo;
-use crate::data::serialization::project::{Project as SerializedProject, SerializedProjectType};
+use crate::data::serialization::project::{Project as SerializedProject, SerializedProjectType, PACK_ICON_FILE, PACK_INFO_FILE};
+use crate::data::serialization::structure::Structure as SerializedStructure;
 use crate::repositories::adapter_repo::{AdapterProvider, AdapterRepoError};
 use crate::repositories::adapter_repo::AdapterProviderContext;
 
@@ -20,10 +30,10 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
     type SerializedConversionError = ProjectSerializeError;
 
     async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(
-        serialized: AdapterInput<&SerializedType>,
+        serialized: AdapterInput<'_, SerializedType>,
         context: AdapterProviderContext<'_, AdpProvider>,
     ) -> Result<DomainType, Self::ConversionError> {
-        let serialized_project = *serialized;
+        let serialized_project = &*serialized;
         
         let deserialize_pack_info = async |pack_info: &PackInfo| -> Result<_, ProjectDeserializeError> {
             let pack_info_input = AdapterInput::new(pack_info);
@@ -48,15 +58,19 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
                 let format = deserialized_pack_info.format;
                 let pack_info = PackInfoProjectData::Data(deserialized_pack_info.into());
                 
-                let format = *versions::DATA_FORMAT_MAP.get(&format)
-                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid data format {}", format)))?
-                    .value();
+                // Packs for versions the editor doesn't know yet are opened as the newest version before them
+                let format = versions::closest_data_format(format)
+                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid data format {}", format)))?;
 
                 DomainProject::new(
                     name.clone(),
                     format.into(),
                     pack_info,
-                )
+                ).with_files(pack_files(project))
+                    .with_structures(deserialize_structures(project, context.clone()).await?)
+                    .with_bom_files(project.bom_files().iter().cloned())
+                    .with_pack_icon(project.pack_icon().clone().map(PackIcon::unchecked))
+                    .with_binary_files(project.binary_files().clone())
             }
             SerializedProjectData::Resource(project) => {
                 if !matches!(project.project_type(), SerializedProjectType::Resource) {
@@ -72,15 +86,18 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
                 let format = deserialized_pack_info.format;
                 let pack_info = PackInfoProjectData::Resource(deserialized_pack_info.into());
 
-                let format = *versions::RESOURCE_FORMAT_MAP.get(&format)
-                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid resource format {}", format)))?
-                    .value();
+                let format = versions::closest_resource_format(format)
+                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid resource format {}", format)))?;
 
                 DomainProject::new(
                     name.clone(),
                     format.into(),
                     pack_info,
-                )
+                ).with_files(pack_files(project))
+                    .with_structures(deserialize_structures(project, context.clone()).await?)
+                    .with_bom_files(project.bom_files().iter().cloned())
+                    .with_pack_icon(project.pack_icon().clone().map(PackIcon::unchecked))
+                    .with_binary_files(project.binary_files().clone())
             }
             SerializedProjectData::Combined {
                 data_project,
@@ -101,48 +118,39 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
                 let data_pack_info = &*data_project.pack_info().read().await;
                 let deserialized_data_pack_info = deserialize_pack_info(data_pack_info).await?;
                 let data_format = deserialized_data_pack_info.format;
-                let data_format = *versions::DATA_FORMAT_MAP.get(&data_format)
-                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid data format {}", data_format)))?
-                    .value();
+                let data_format = versions::closest_data_format(data_format)
+                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid data format {}", data_format)))?;
 
                 let resource_pack_info = &*resource_project.pack_info().read().await;
                 let deserialized_resource_pack_info = deserialize_pack_info(resource_pack_info).await?;
                 let resource_format = deserialized_resource_pack_info.format;
-                let resource_format = *versions::RESOURCE_FORMAT_MAP.get(&resource_format)
-                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid resource format {}", resource_format)))?
-                    .value();
-
-                // Scoped to keep non thread safe read guards from crossing await boundaries
-                let min_mc_version = {
-                    let data_mc_versions = data_format.get_versions();
-                    let data_mc_versions = data_mc_versions.read().expect("Failed to read data format mc versions");
-
-                    let resource_mc_versions = resource_format.get_versions();
-                    let resource_mc_versions = &*resource_mc_versions.read().expect("Failed to read resource format mc versions");
-
-                    let mc_versions: HashSet<_> = data_mc_versions.iter()
-                        // Version lists are always on the order of single digits, so search performance is not relevant
-                        .filter(|mc_version| resource_mc_versions.contains(mc_version)) 
-                        .cloned()
-                        .collect();
-
-                    if mc_versions.len() == 0 {
-                        return Err(ProjectDeserializeError::InvalidVersion(
-                            format!("No common mc versions between data and resource packs! Data format: {}, Resource format: {}",
-                                    data_format.get_format_id(),
-                                    resource_format.get_format_id())
-                        ));
-                    }
-                    
-                    let mut mc_versions = mc_versions.iter()
-                        .collect::<Vec<_>>();
-                    
-                    mc_versions.sort();
-                    **mc_versions.first().unwrap()
+                let resource_format = versions::closest_resource_format(resource_format)
+                    .ok_or(ProjectDeserializeError::InvalidVersion(format!("Invalid resource format {}", resource_format)))?;
+
+                let data_versions = supported_versions(data_format, deserialized_data_pack_info.supported_formats, versions::all_data_formats());
+                let resource_versions = supported_versions(resource_format, deserialized_resource_pack_info.supported_formats, versions::all_resource_formats());
+
+                // Versions either pack was made for come first, then older versions, as the project targets a single version
+                let mc_version = data_versions.intersection(&resource_versions)
+                    .copied()
+                    .min_by_key(|version| {
+                        let declared = [data_format, resource_format].iter()
+                            .filter(|format| format.contains_version(version))
+                            .count();
+                        (Reverse(declared), *version)
+                    });
+
+                let Some(mc_version) = mc_version else {
+                    return Err(ProjectDeserializeError::NoCommonVersion(FormatMismatch {
+                        data_format: data_format.get_format_id(),
+                        resource_format: resource_format.get_format_id(),
+                        data_versions,
+                        resource_versions,
+                    }));
                 };
 
                 let name = data_project.name();
-                let project_version = min_mc_version.into();
+                let project_version = mc_version.into();
                 
                 DomainProject::new(
                     name.clone(),
@@ -151,7 +159,11 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
                         data_info: deserialized_data_pack_info.into(),
                         resource_info: deserialized_resource_pack_info.into(),
                     },
-                )
+                ).with_files(pack_files(data_project).chain(pack_files(resource_project)))
+                    .with_structures(deserialize_structures(data_project, context.clone()).await?)
+                    .with_bom_files(data_project.bom_files().iter().chain(resource_project.bom_files()).cloned())
+                    .with_pack_icon(data_project.pack_icon().clone().or_else(|| resource_project.pack_icon().clone()).map(PackIcon::unchecked))
+                    .with_binary_files(data_project.binary_files().clone().into_iter().chain(resource_project.binary_files().clone()))
             }
         };
         
@@ -159,11 +171,12 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
     }
 
     async fn serialize<AdpProvider: AdapterProvider + ?Sized>(
-        domain: AdapterInput<&DomainType>,
+        domain: AdapterInput<'_, DomainType>,
         context: AdapterProviderContext<'_, AdpProvider>,
     ) -> Result<SerializedType, ProjectSerializeError> {
-        let project = domain.0;
+        let project = &*domain;
         let project_version= project.project_version();
+        let json_style = domain.json_style();
         
         match project.pack_info() {
             // TODO: Add more complete format handling
@@ -173,7 +186,15 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
 
                 let serialized_pack_info = serialize_pack_info(&pack_info_domain_data, context.clone()).await?;
                 
-                Ok(SerializedProjectData::Data(SerializedProject::new(SerializedProjectType::Data, serialized_pack_info)))
+                Ok(SerializedProjectData::Data(
+                    SerializedProject::new(SerializedProjectType::Data, serialized_pack_info)
+                        .with_json_style(json_style)
+                        .with_files(files_for_pack(project, SerializedProjectType::Data, json_style, context.parse_cache()))
+                        .with_bom_files(project.bom_files().iter().map(|path| pack_entry_path(path)).collect())
+                        .with_pack_icon(exported_pack_icon(project))
+                        .with_binary_files(binary_files_for_pack(project, SerializedProjectType::Data))
+                        .with_structures(serialize_structures(project, context.clone()).await?)
+                ))
             }
             PackInfoProjectData::Resource(pack_info) => {
                 let data_format = versions::get_resourcepack_format_for_version(project_version.get_base_resource_mc_version());
@@ -181,7 +202,14 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
 
                 let serialized_pack_info = serialize_pack_info(&pack_info_domain_data, context.clone()).await?;
 
-                Ok(SerializedProjectData::Resource(SerializedProject::new(SerializedProjectType::Resource, serialized_pack_info)))
+                Ok(SerializedProjectData::Resource(
+                    SerializedProject::new(SerializedProjectType::Resource, serialized_pack_info)
+                        .with_json_style(json_style)
+                        .with_files(files_for_pack(project, SerializedProjectType::Resource, json_style, context.parse_cache()))
+                        .with_bom_files(project.bom_files().iter().map(|path| pack_entry_path(path)).collect())
+                        .with_pack_icon(exported_pack_icon(project))
+                        .with_binary_files(binary_files_for_pack(project, SerializedProjectType::Resource))
+                ))
             }
             PackInfoProjectData::Combined { data_info, resource_info } => {
                 let data_format = versions::get_datapack_format_for_version(project_version.get_base_data_mc_version());
@@ -195,14 +223,137 @@ impl Adapter<SerializedType, DomainType> for ProjectAdapter {
                 let serialized_resource_pack_info = serialize_pack_info(&pack_info_domain_data, context.clone()).await?;
                 
                 Ok(SerializedProjectData::Combined {
-                    data_project: SerializedProject::new(SerializedProjectType::Data, serialized_data_pack_info),
-                    resource_project: SerializedProject::new(SerializedProjectType::Resource, serialized_resource_pack_info),
+                    data_project: SerializedProject::new(SerializedProjectType::Data, serialized_data_pack_info)
+                        .with_json_style(json_style)
+                        .with_files(files_for_pack(project, SerializedProjectType::Data, json_style, context.parse_cache()))
+                        .with_bom_files(project.bom_files().iter().map(|path| pack_entry_path(path)).collect())
+                        .with_pack_icon(exported_pack_icon(project))
+                        .with_binary_files(binary_files_for_pack(project, SerializedProjectType::Data))
+                        .with_structures(serialize_structures(project, context.clone()).await?),
+                    resource_project: SerializedProject::new(SerializedProjectType::Resource, serialized_resource_pack_info)
+                        .with_json_style(json_style)
+                        .with_files(files_for_pack(project, SerializedProjectType::Resource, json_style, context.parse_cache()))
+                        .with_bom_files(project.bom_files().iter().map(|path| pack_entry_path(path)).collect())
+                        .with_pack_icon(exported_pack_icon(project))
+                        .with_binary_files(binary_files_for_pack(project, SerializedProjectType::Resource)),
                 })
             }
         }
     }
 }
 
+/// Versions a pack can be loaded in: those of every format within its `supported_formats`,
+/// or only those of its own format if it doesn't declare any
+fn supported_versions(format: &PackFormat, supported_formats: Option<(u8, u8)>, all_formats: Vec<&'static PackFormat>) -> BTreeSet<MinecraftVersion> {
+    let Some((min, max)) = supported_formats else {
+        return format.get_versions().iter().copied().collect();
+    };
+
+    all_formats.into_iter()
+        .filter(|format| (min..=max).contains(&format.get_format_id()))
+        .flat_map(|format| format.get_versions().iter().copied())
+        .collect()
+}
+
+/// Files of a serialized pack, without the `pack.mcmeta` which is represented by the pack info instead
+fn pack_files(project: &SerializedProject) -> impl Iterator<Item = (PathBuf, String)> + '_ {
+    project.files().iter()
+        .filter(|(path, _)| path.as_path() != Path::new(PACK_INFO_FILE))
+        .map(|(path, contents)| (path.clone(), contents.clone()))
+}
+
+/// Files of the domain project which belong in a pack of the given type, with JSON rewritten in the given style.
+/// Files outside of both `data` and `assets`, such as `pack.png`, belong to every pack, unless the project ignores them.
+/// Paths are `/`-separated as zip entries need, even where the project's own paths use the platform's separator
+fn files_for_pack(project: &DomainProject, pack_type: SerializedProjectType, json_style: JsonStyle, parse_cache: &ParseCache<Option<Value>>) -> HashMap<PathBuf, String> {
+    let excluded_root = excluded_root(pack_type);
+    let export_ignore = project.export_ignore();
+
+    project.iter_files()
+        .filter(|file| !file.path.starts_with(excluded_root) && !export_ignore.is_ignored(file.path))
+        .map(|file| (pack_entry_path(file.path), styled_contents(file.path, file.contents, json_style, parse_cache)))
+        .collect()
+}
+
+/// Binary files of the domain project which belong in a pack of the given type, picked the same way as [`files_for_pack`]
+fn binary_files_for_pack(project: &DomainProject, pack_type: SerializedProjectType) -> HashMap<PathBuf, Vec<u8>> {
+    let excluded_root = excluded_root(pack_type);
+    let export_ignore = project.export_ignore();
+
+    project.binary_files().iter()
+        .filter(|(path, _)| !path.starts_with(excluded_root) && !export_ignore.is_ignored(path))
+        .map(|(path, data)| (pack_entry_path(path), data.clone()))
+        .collect()
+}
+
+/// The root of the other kind of pack, whose files are left out
+fn excluded_root(pack_type: SerializedProjectType) -> &'static str {
+    match pack_type {
+        SerializedProjectType::Data => "assets",
+        SerializedProjectType::Resource => "data",
+    }
+}
+
+/// The icon is written to every pack of the project, unless the project ignores it
+fn exported_pack_icon(project: &DomainProject) -> Option<Vec<u8>> {
+    if project.export_ignore().is_ignored(Path::new(PACK_ICON_FILE)) {
+        return None;
+    }
+
+    project.pack_icon().as_ref().map(|icon| icon.data().to_vec())
+}
+
+/// The project's paths all come from inside a pack, so one which can't be normalized, such as one with uppercase letters
+/// the lint already reports, is written as it is
+fn pack_entry_path(path: &Path) -> PathBuf {
+    PackRelativePath::try_from(path)
+        .map_or_else(|_| path.to_path_buf(), |path| path.as_path().to_path_buf())
+}
+
+/// JSON files are rewritten in the export's style, unless it keeps them as they were. Anything which doesn't parse is
+/// left as it was, since it's already reported by validation and rewriting it could lose the user's work.
+/// Files unchanged since the last export are taken from the parse cache rather than parsed again
+fn styled_contents(path: &Path, contents: &str, json_style: JsonStyle, parse_cache: &ParseCache<Option<Value>>) -> String {
+    let is_json = path.extension().is_some_and(|extension| extension == "json" || extension == "mcmeta");
+    if json_style == JsonStyle::Preserve || !is_json {
+        return contents.to_string();
+    }
+
+    let parsed = parse_cache.get_or_parse(path, contents.as_bytes(), || serde_json::from_str(contents).ok());
+    parsed.as_ref().as_ref()
+        .and_then(|value| json_style.serialize(value).ok())
+        .unwrap_or_else(|| contents.to_string())
+}
+
+/// Structures only belong to data packs, so resource packs never have any to convert
+async fn deserialize_structures<AdpProvider: AdapterProvider + ?Sized>(
+    project: &SerializedProject,
+    context: AdapterProviderContext<'_, AdpProvider>,
+) -> Result<BTreeMap<PathBuf, DomainStructure>, ProjectDeserializeError> {
+    let mut structures = BTreeMap::new();
+    for (path, structure) in project.structures() {
+        let structure = context.deserialize(AdapterInput::new(structure)).await
+            .map_err(ProjectDeserializeError::Structure)?;
+        structures.insert(path.clone(), structure);
+    }
+    Ok(structures)
+}
+
+async fn serialize_structures<AdpProvider: AdapterProvider + ?Sized>(
+    project: &DomainProject,
+    context: AdapterProviderContext<'_, AdpProvider>,
+) -> Result<HashMap<PathBuf, SerializedStructure>, ProjectSerializeError> {
+    let export_ignore = project.export_ignore();
+
+    let mut structures = HashMap::new();
+    for (path, structure) in project.structures().iter().filter(|(path, _)| !export_ignore.is_ignored(path)) {
+        let structure = context.serialize(AdapterInput::new(structure)).await
+            .map_err(ProjectSerializeError::Structure)?;
+        structures.insert(pack_entry_path(path), structure);
+    }
+    Ok(structures)
+}
+
 async fn serialize_pack_info<AdpProvider: AdapterProvider + ?Sized>(
     pack_info: &adapters::pack_info::DomainType,
     context: AdapterProviderContext<'_, AdpProvider>,
@@ -218,17 +369,34 @@ async fn serialize_pack_info<AdpProvider: AdapterProvider + ?Sized>(
 pub enum ProjectDeserializeError {
     #[error("Error deserializing pack info! {}", .0)]
     PackInfo(AdapterRepoError),
+    #[error("Error deserializing structure! {}", .0)]
+    Structure(AdapterRepoError),
     #[error("Invalid pack format! {}", .0)]
     InvalidVersion(String),
     #[error("Mismatched project type! {}", .0)]
     MismatchedType(String),
+    #[error("No common mc versions between data and resource packs! Data format: {}, Resource format: {}", .0.data_format, .0.resource_format)]
+    NoCommonVersion(FormatMismatch),
 }
 impl AdapterError for ProjectDeserializeError {}
 
+/// Formats of a combined project's packs which no version of the game supports together
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FormatMismatch {
+    pub data_format: u8,
+    pub resource_format: u8,
+    /// Versions supporting the data pack, including those its supported formats add
+    pub data_versions: BTreeSet<MinecraftVersion>,
+    /// Versions supporting the resource pack, including those its supported formats add
+    pub resource_versions: BTreeSet<MinecraftVersion>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ProjectSerializeError {
     #[error("Error serializing pack info! {}", .0)]
     PackInfo(AdapterRepoError),
+    #[error("Error serializing structure! {}", .0)]
+    Structure(AdapterRepoError),
 }
 impl AdapterError for ProjectSerializeError {}
 
@@ -249,7 +417,7 @@ mod test {
         use map_tuple::{TupleMap0, TupleMap1};
         use rstest::fixture;
         use crate::data::adapters::register_default_adapters;
-        use crate::data::serialization::pack_info::PackData;
+        use crate::data::serialization::pack_info::{PackData, PackFormat as SerializedPackFormat};
         use crate::data::serialization::text_component::TextComponent;
         use crate::repositories::adapter_repo::AdapterRepository;
         use super::*;
@@ -407,6 +575,29 @@ mod test {
             assert!(matches!(result.unwrap_err(), ProjectDeserializeError::InvalidVersion(_)));
         }
         
+        #[rstest::rstest]
+        #[case::newer("99", *versions::V1_21_7)]
+        #[case::between_known("75", *versions::V1_21_5)]
+        #[tokio::test]
+        async fn test_deser_pack_unknown_format(
+            #[case] format: &str,
+            #[case] expected_version: MinecraftVersion,
+        ) {
+            // Given a pack zip with a format no known version uses
+            let serialized = serialized("data", format);
+
+            let repo = AdapterRepository::create_repo().await;
+            register_default_adapters(&mut *repo.write().await);
+
+            let context = AdapterRepository::context_from_repo(&repo).await;
+
+            // When I deserialize it
+            let project = ProjectAdapter::deserialize(AdapterInput::new(&serialized), context).await.unwrap();
+
+            // Then it should target the newest known version before that format
+            assert_eq!(project.project_version().get_base_data_mc_version(), expected_version);
+        }
+        
         #[tokio::test]
         async fn test_deser_pack_mismatched_types() {
             // Given a pack with a mismatched type and inner labeled type
@@ -477,9 +668,63 @@ mod test {
             // When I deserialize them together
             let result = ProjectAdapter::deserialize(AdapterInput::new(&serialized), context).await;
             
-            // Then it should return an error
-            assert!(result.is_err());
-            assert!(matches!(result.unwrap_err(), ProjectDeserializeError::InvalidVersion(_)));
+            // Then it should say which formats and versions didn't match
+            let Err(ProjectDeserializeError::NoCommonVersion(mismatch)) = result else {
+                panic!("Expected no common version, got {:?}", result);
+            };
+            assert_eq!((mismatch.data_format, mismatch.resource_format), (4, 34));
+            assert!(mismatch.data_versions.contains(&*versions::V1_13));
+            assert!(mismatch.resource_versions.contains(&*versions::V1_21));
+            assert!(mismatch.data_versions.is_disjoint(&mismatch.resource_versions));
+        }
+
+        fn combined_with_supported_formats(
+            (data_format, data_supported): (u32, Option<(u32, u32)>),
+            (resource_format, resource_supported): (u32, Option<(u32, u32)>),
+        ) -> SerializedProjectData {
+            let project = |name: &str, project_type, format, supported: Option<(u32, u32)>| SerializedProject::with_name(
+                name.to_string(),
+                project_type,
+                PackInfo::new(
+                    PackData::new(
+                        TextComponent::String(format!("{} description", name)),
+                        format,
+                        supported.map(|(min, max)| SerializedPackFormat::Range(min, max)),
+                    ),
+                    None, None, None, None
+                )
+            );
+
+            SerializedProjectData::Combined {
+                data_project: project("Test Data Pack", SerializedProjectType::Data, data_format, data_supported),
+                resource_project: project("Test Resource Pack", SerializedProjectType::Resource, resource_format, resource_supported),
+            }
+        }
+
+        #[rstest::rstest]
+        #[case::data_supports_resource_version((61, Some((48, 61))), (34, None), *versions::V1_21)]
+        #[case::prefers_declared_format((48, Some((48, 71))), (55, Some((46, 55))), *versions::V1_21_5)]
+        #[case::narrowed_to_resource_format((48, Some((48, 81))), (64, None), *versions::V1_21_7)]
+        #[case::shared_declared_version_kept((48, Some((48, 57))), (34, Some((34, 42))), *versions::V1_21)]
+        #[tokio::test]
+        async fn test_deser_combined_pack_supported_formats(
+            #[case] data: (u32, Option<(u32, u32)>),
+            #[case] resource: (u32, Option<(u32, u32)>),
+            #[case] expected: MinecraftVersion,
+        ) {
+            // Given a pair of packs declaring supported formats
+            let serialized = combined_with_supported_formats(data, resource);
+
+            let repo = AdapterRepository::create_repo().await;
+            register_default_adapters(&mut *repo.write().await);
+
+            let context = AdapterRepository::context_from_repo(&repo).await;
+
+            // When I deserialize them together
+            let project = ProjectAdapter::deserialize(AdapterInput::new(&serialized), context).await.unwrap();
+
+            // Then the version should be one both packs support, preferring ones their own formats were made for
+            assert_eq!(project.project_version().get_base_data_mc_version(), expected);
         }
 
         #[tokio::test]
@@ -528,9 +773,11 @@ mod test {
     
     mod serialize {
         use rstest::fixture;
+        use crate::data::adapters::cache::CacheStats;
         use crate::data::adapters::register_default_adapters;
         use crate::data::domain::pack_info::PackDescription;
         use crate::data::domain::project::{ProjectSettings, ProjectVersion};
+        use crate::data::serialization::json::{JsonLayout, KeyOrder};
         use crate::repositories::adapter_repo::AdapterRepository;
         use super::*;
         
@@ -645,5 +892,96 @@ mod test {
                 _ => panic!("Expected combined pack serialization"),
             }
         }
+
+        #[rstest::rstest]
+        #[tokio::test]
+        async fn test_ser_json_style(
+            // Given a data pack with a JSON file and a function
+            data_project: DomainType
+        ) {
+            let recipe = r#"{"result": {"id": "minecraft:stone"}, "type": "minecraft:smelting"}"#;
+            let function = "say  hi";
+            let data_project = data_project.with_files([
+                (PathBuf::from("data/test/recipe/stone.json"), recipe.to_string()),
+                (PathBuf::from("data/test/function/hi.mcfunction"), function.to_string()),
+            ]);
+
+            let repo = AdapterRepository::create_repo().await;
+            register_default_adapters(&mut *repo.write().await);
+
+            // When I serialize it compact, pretty, and as it was written
+            let compact_style = JsonStyle::Reformat { layout: JsonLayout::Compact, key_order: KeyOrder::Vanilla };
+            let pretty_style = JsonStyle::Reformat { layout: JsonLayout::Pretty, key_order: KeyOrder::Vanilla };
+            let compact = ProjectAdapter::serialize(AdapterInput::new(&data_project).with_json_style(compact_style), AdapterRepository::context_from_repo(&repo).await).await.unwrap();
+            let pretty = ProjectAdapter::serialize(AdapterInput::new(&data_project).with_json_style(pretty_style), AdapterRepository::context_from_repo(&repo).await).await.unwrap();
+            let preserved = ProjectAdapter::serialize(AdapterInput::new(&data_project), AdapterRepository::context_from_repo(&repo).await).await.unwrap();
+
+            // Then only the JSON should be rewritten, in each style, parsed once for both and not at all when preserved
+            assert_eq!(repo.read().await.parse_cache().stats(), CacheStats { hits: 1, misses: 1, entries: 1 });
+
+            let (SerializedType::Data(compact), SerializedType::Data(pretty), SerializedType::Data(preserved)) = (compact, pretty, preserved) else {
+                panic!("Expected data pack serialization");
+            };
+
+            let recipe_path = Path::new("data/test/recipe/stone.json");
+            assert_eq!(compact.files()[recipe_path], r#"{"type":"minecraft:smelting","result":{"id":"minecraft:stone"}}"#);
+            assert_eq!(pretty.files()[recipe_path], pretty_style.reformat(recipe).unwrap());
+            assert!(pretty.files()[recipe_path].starts_with("{\n  \"type\""));
+            assert_eq!(preserved.files()[recipe_path], recipe);
+
+            let function_path = Path::new("data/test/function/hi.mcfunction");
+            assert_eq!(compact.files()[function_path], function);
+            assert_eq!(pretty.files()[function_path], function);
+        }
+
+        #[rstest::rstest]
+        #[tokio::test]
+        async fn test_ser_keeps_bom(
+            // Given a data pack with a file which had a byte order mark when it was imported
+            data_project: DomainType
+        ) {
+            let recipe_path = PathBuf::from("data/test/recipe/stone.json");
+            let data_project = data_project.with_files([(recipe_path.clone(), "{}".to_string())])
+                .with_bom_files([recipe_path.clone()]);
+
+            let repo = AdapterRepository::create_repo().await;
+            register_default_adapters(&mut *repo.write().await);
+
+            // When I serialize it
+            let serialized = ProjectAdapter::serialize(AdapterInput::new(&data_project), AdapterRepository::context_from_repo(&repo).await).await.unwrap();
+
+            // Then the mark should be kept for zipping
+            let SerializedType::Data(serialized) = serialized else {
+                panic!("Expected data pack serialization");
+            };
+            assert!(serialized.bom_files().contains(&recipe_path));
+        }
+
+        #[rstest::rstest]
+        #[tokio::test]
+        async fn test_ser_binary_files(
+            // Given a combined project with a texture and a binary file in its data pack
+            combined_project: DomainType
+        ) {
+            let texture_path = PathBuf::from("assets/test/textures/item/gem.png");
+            let sound_path = PathBuf::from("data/test/sounds/chime.ogg");
+            let combined_project = combined_project.with_binary_files([
+                (texture_path.clone(), vec![0x89, b'P', b'N', b'G', 0xff]),
+                (sound_path.clone(), vec![b'O', b'g', b'g', b'S', 0xff]),
+            ]);
+
+            let repo = AdapterRepository::create_repo().await;
+            register_default_adapters(&mut *repo.write().await);
+
+            // When I serialize it
+            let serialized = ProjectAdapter::serialize(AdapterInput::new(&combined_project), AdapterRepository::context_from_repo(&repo).await).await.unwrap();
+
+            // Then each file should be kept as bytes in the pack it belongs to
+            let SerializedType::Combined { data_project, resource_project } = serialized else {
+                panic!("Expected combined pack serialization");
+            };
+            assert_eq!(data_project.binary_files().keys().collect::<Vec<_>>(), vec![&sound_path]);
+            assert_eq!(resource_project.binary_files()[&texture_path], vec![0x89, b'P', b'N', b'G', 0xff]);
+        }
     }
-}
\ No newline at end of file
+}
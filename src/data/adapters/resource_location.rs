@@ -15,14 +15,14 @@ impl Adapter<SerializedType, DomainType> for ResourceLocationAdapter {
     type SerializedConversionError = Infallible;
 
     async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(
-        serialized: AdapterInput<&SerializedType>,
+        serialized: AdapterInput<'_, SerializedType>,
         _context: AdapterProviderContext<'_, AdpProvider>
     ) -> Result<DomainType, Self::ConversionError> {
         DomainResourceLocation::from_str(serialized.to_string().as_str())
     }
 
     async fn serialize<AdpProvider: AdapterProvider + ?Sized>(
-        domain: AdapterInput<&DomainType>,
+        domain: AdapterInput<'_, DomainType>,
         _context: AdapterProviderContext<'_, AdpProvider>
     ) -> Result<SerializedType, Infallible> {
         Ok(SerializationResourceLocation::new(domain.to_string().as_str()))
@@ -0,0 +1,71 @@
+use std::convert::Infallible;
+use crate::data::adapters::{Adapter, AdapterInput};
+use crate::data::domain::structure::Structure as DomainStructure;
+use crate::data::serialization::structure::Structure as SerializationStructure;
+use crate::repositories::adapter_repo::{AdapterProvider, AdapterProviderContext};
+
+pub type SerializedType = SerializationStructure;
+pub type DomainType = DomainStructure;
+
+/// Passes structure files through byte for byte, so their gzip framing survives an import and export
+pub struct StructureAdapter;
+#[async_trait::async_trait]
+impl Adapter<SerializedType, DomainType> for StructureAdapter {
+    type ConversionError = Infallible;
+    type SerializedConversionError = Infallible;
+
+    async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(
+        serialized: AdapterInput<'_, SerializedType>,
+        _context: AdapterProviderContext<'_, AdpProvider>
+    ) -> Result<DomainType, Self::ConversionError> {
+        Ok(DomainStructure::new(serialized.bytes().to_vec()))
+    }
+
+    async fn serialize<AdpProvider: AdapterProvider + ?Sized>(
+        domain: AdapterInput<'_, DomainType>,
+        _context: AdapterProviderContext<'_, AdpProvider>
+    ) -> Result<SerializedType, Self::SerializedConversionError> {
+        Ok(SerializationStructure::new(domain.data().to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use flate2::Compression;
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+    use once_cell::sync::Lazy;
+    use tokio::sync::RwLock;
+    use crate::repositories::adapter_repo::AdapterRepository;
+    use crate::services::project_service::DefaultAdapterProvider;
+    use super::*;
+
+    static ADAPTER_PROVIDER: Lazy<RwLock<DefaultAdapterProvider>> = Lazy::new(|| RwLock::new(DefaultAdapterProvider::new()));
+
+    async fn adapter_context<'a>() -> AdapterProviderContext<'a, AdapterRepository> {
+        AdapterProviderContext::new(ADAPTER_PROVIDER.read().await)
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        // Given a small gzipped structure
+        let nbt = [0x0a, 0x00, 0x00, 0x03, 0x00, 0x0b, b'D', b'a', b't', b'a', b'V', b'e', b'r', b's', b'i', b'o', b'n', 0x00, 0x00, 0x0f, 0x7d, 0x00];
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&nbt).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        // When it is read into the project and written back out
+        let serialized = SerializationStructure::new(bytes.clone());
+        let domain = StructureAdapter::deserialize(AdapterInput::new(&serialized), adapter_context().await).await.unwrap();
+        let round_tripped = StructureAdapter::serialize(AdapterInput::new(&domain), adapter_context().await).await.unwrap();
+
+        // Then the bytes should be unchanged, and still decompress to the same NBT
+        assert!(domain.is_gzipped());
+        assert_eq!(round_tripped.bytes(), bytes.as_slice());
+
+        let mut decoded = Vec::new();
+        GzDecoder::new(round_tripped.bytes()).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, nbt);
+    }
+}
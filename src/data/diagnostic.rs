@@ -0,0 +1,168 @@
+//! A common shape for everything the editor reports about a pack, so that lint, import and
+//! compatibility findings can be printed together, or handed to other tools as JSON.
+//!
+//! The JSON schema is stable: fields are only ever added, and `line` is always present,
+//! as `null` where a finding applies to a whole file.
+//!
+//! ```json
+//! {"version": 1, "diagnostics": [{"path": "data/example/function/load.mcfunction", "line": null, "severity": "warning", "rule": "unused-function", "message": "..."}]}
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+/// Bumped whenever a field changes meaning or is removed
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Path of the offending file, relative to the pack root
+    pub path: PathBuf,
+    /// Line of the file, counting from 1, if the finding is on one line
+    pub line: Option<usize>,
+    pub severity: DiagnosticSeverity,
+    /// Identifier of what was checked, e.g. `unused-function`
+    pub rule: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticSeverity::Info => write!(f, "info"),
+            DiagnosticSeverity::Warning => write!(f, "warning"),
+            DiagnosticSeverity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} [{}] {}", self.severity, self.rule, self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// The line, counting from 1, on which `text` first appears in a file's contents
+pub fn line_of(contents: &str, text: &str) -> Option<usize> {
+    let offset = contents.find(text)?;
+    Some(contents[..offset].matches('\n').count() + 1)
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// One diagnostic per line, for reading in a terminal
+    #[default]
+    Human,
+    /// A single JSON object, see the [module documentation](self)
+    Json,
+}
+
+#[derive(Serialize)]
+struct DiagnosticsOutput<'a> {
+    version: u32,
+    diagnostics: &'a [Diagnostic],
+}
+
+/// Writes diagnostics in the given format, sorted by path so that output is stable between runs
+pub fn render(diagnostics: &[Diagnostic], format: OutputFormat) -> String {
+    let mut diagnostics = diagnostics.to_vec();
+    diagnostics.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+
+    match format {
+        OutputFormat::Human => diagnostics.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Json => serde_json::to_string(&DiagnosticsOutput { version: SCHEMA_VERSION, diagnostics: &diagnostics })
+            .expect("Diagnostics should always serialize"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostics() -> Vec<Diagnostic> {
+        vec![
+            Diagnostic {
+                path: PathBuf::from("data/example/function/setup.mcfunction"),
+                line: Some(3),
+                severity: DiagnosticSeverity::Error,
+                rule: "invalid-resource-location".to_string(),
+                message: "`Example:Stone` is not a valid resource location".to_string(),
+            },
+            Diagnostic {
+                path: PathBuf::from("data/example/advancement/root.json"),
+                line: None,
+                severity: DiagnosticSeverity::Warning,
+                rule: "advancement-without-criteria".to_string(),
+                message: "Advancement has no criteria".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_json() {
+        // Given diagnostics from a few files
+        // When I render them as JSON
+        let json = render(&diagnostics(), OutputFormat::Json);
+
+        // Then they should be sorted by path, with every field present
+        assert_eq!(json, concat!(
+            r#"{"version":1,"diagnostics":["#,
+            r#"{"path":"data/example/advancement/root.json","line":null,"severity":"warning","rule":"advancement-without-criteria","message":"Advancement has no criteria"},"#,
+            r#"{"path":"data/example/function/setup.mcfunction","line":3,"severity":"error","rule":"invalid-resource-location","message":"`Example:Stone` is not a valid resource location"}"#,
+            r#"]}"#,
+        ));
+    }
+
+    #[rstest::rstest]
+    #[case::first_line("{\"result\": {}}", Some(1))]
+    #[case::later_line("{\n  \"type\": \"minecraft:smelting\",\n  \"result\": {}\n}", Some(3))]
+    #[case::missing("{}", None)]
+    fn test_line_of(#[case] contents: &str, #[case] expected: Option<usize>) {
+        // Given a file's contents
+        // When I look for a field in it
+        // Then the line it is on should be found
+        assert_eq!(line_of(contents, "\"result\""), expected);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        // Given a diagnostic written as JSON
+        let diagnostic = diagnostics().remove(0);
+        let json = serde_json::to_string(&diagnostic).unwrap();
+
+        // When I read it back
+        let read: Diagnostic = serde_json::from_str(&json).unwrap();
+
+        // Then it should be unchanged
+        assert_eq!(read, diagnostic);
+    }
+
+    #[test]
+    fn test_render_human() {
+        // Given diagnostics from a few files
+        // When I render them for reading
+        let text = render(&diagnostics(), OutputFormat::Human);
+
+        // Then there should be one per line, with the line number where there is one
+        assert_eq!(text, concat!(
+            "warning [advancement-without-criteria] data/example/advancement/root.json: Advancement has no criteria\n",
+            "error [invalid-resource-location] data/example/function/setup.mcfunction:3: `Example:Stone` is not a valid resource location",
+        ));
+    }
+}
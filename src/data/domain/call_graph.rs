@@ -0,0 +1,304 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use crate::data::domain::project::{Project, ProjectFile, VANILLA_NAMESPACE};
+use crate::data::domain::resource::resource::ResourceLocation;
+
+const FUNCTION_KINDS: &[&str] = &["function", "functions"];
+const FUNCTION_TAG_KINDS: &[&str] = &["tags/function", "tags/functions"];
+const ADVANCEMENT_KINDS: &[&str] = &["advancement", "advancements"];
+
+/// Function tags which the game runs by itself
+const ENTRY_POINT_TAGS: &[&str] = &["load", "tick"];
+
+/// Calls between the `.mcfunction` files of a project, including calls made through function tags
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// Functions defined in the project, and the file defining each of them
+    definitions: HashMap<ResourceLocation, PathBuf>,
+    calls: HashMap<ResourceLocation, HashSet<ResourceLocation>>,
+    /// Functions run by the game rather than by other functions, such as those in `#minecraft:load`
+    entry_points: HashSet<ResourceLocation>,
+}
+
+impl CallGraph {
+    pub fn from_project(project: &Project) -> Self {
+        let tags = function_tags(project);
+        let expand = |target: FunctionTarget| -> Vec<ResourceLocation> {
+            match target {
+                FunctionTarget::Function(function) => vec![function],
+                FunctionTarget::Tag(tag) => tags.get(&tag).cloned().unwrap_or_default(),
+            }
+        };
+
+        let mut graph = Self::default();
+
+        for file in project.iter_files() {
+            let Some(location) = resource_of_kind(&file, FUNCTION_KINDS) else {
+                continue;
+            };
+
+            let callees = parse_function_calls(file.contents)
+                .into_iter()
+                .flat_map(&expand)
+                .collect();
+
+            graph.definitions.insert(location.clone(), file.path.to_path_buf());
+            graph.calls.insert(location, callees);
+        }
+
+        for tag in ENTRY_POINT_TAGS {
+            let tag = ResourceLocation::new(VANILLA_NAMESPACE, tag).expect("Entry point tags are valid resource locations");
+            graph.entry_points.extend(tags.get(&tag).cloned().unwrap_or_default());
+        }
+
+        graph.entry_points.extend(advancement_rewards(project));
+
+        graph
+    }
+
+    /// The file defining a function, relative to the pack root
+    pub fn definition(&self, function: &ResourceLocation) -> Option<&PathBuf> {
+        self.definitions.get(function)
+    }
+
+    /// Functions called directly by `function`, sorted by name
+    pub fn callees(&self, function: &ResourceLocation) -> Vec<&ResourceLocation> {
+        sorted(self.calls.get(function).into_iter().flatten())
+    }
+
+    /// Functions which directly call `function`, sorted by name
+    pub fn callers(&self, function: &ResourceLocation) -> Vec<&ResourceLocation> {
+        sorted(self.calls.iter()
+            .filter(|(_, callees)| callees.contains(function))
+            .map(|(caller, _)| caller))
+    }
+
+    /// Everything `function` calls, directly or indirectly, depth first with the depth of each call.
+    /// Recursive calls are listed once, without descending into them again
+    pub fn call_hierarchy(&self, function: &ResourceLocation) -> Vec<(usize, &ResourceLocation)> {
+        let mut hierarchy = Vec::new();
+        let mut stack: Vec<_> = self.callees(function).into_iter().rev().map(|callee| (1, callee)).collect();
+        let mut ancestors: Vec<&ResourceLocation> = vec![function];
+
+        while let Some((depth, callee)) = stack.pop() {
+            ancestors.truncate(depth);
+            hierarchy.push((depth, callee));
+
+            if ancestors.contains(&callee) {
+                continue;
+            }
+
+            ancestors.push(callee);
+            stack.extend(self.callees(callee).into_iter().rev().map(|next| (depth + 1, next)));
+        }
+
+        hierarchy
+    }
+
+    /// Functions defined in the project which can't be reached from any entry point, sorted by name
+    pub fn unused_functions(&self) -> Vec<&ResourceLocation> {
+        let mut reachable = HashSet::new();
+        let mut stack: Vec<_> = self.entry_points.iter().collect();
+
+        while let Some(function) = stack.pop() {
+            if reachable.insert(function) {
+                stack.extend(self.calls.get(function).into_iter().flatten());
+            }
+        }
+
+        sorted(self.definitions.keys().filter(|function| !reachable.contains(function)))
+    }
+}
+
+fn sorted<'a>(functions: impl IntoIterator<Item = &'a ResourceLocation>) -> Vec<&'a ResourceLocation> {
+    let mut functions: Vec<_> = functions.into_iter().collect();
+    functions.sort_by_key(|function| function.to_string());
+    functions
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FunctionTarget {
+    Function(ResourceLocation),
+    Tag(ResourceLocation),
+}
+
+/// Finds every `function <target>` in an mcfunction file, including those after `execute ... run`,
+/// skipping comments and targets which aren't valid resource locations
+pub fn parse_function_calls(contents: &str) -> Vec<FunctionTarget> {
+    contents.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(|line| {
+            let tokens: Vec<_> = line.split_whitespace().collect();
+
+            tokens.windows(2)
+                .filter(|pair| pair[0] == "function")
+                .filter_map(|pair| parse_target(pair[1]))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn parse_target(token: &str) -> Option<FunctionTarget> {
+    match token.strip_prefix('#') {
+        Some(tag) => ResourceLocation::from_str(tag).ok().map(FunctionTarget::Tag),
+        None => ResourceLocation::from_str(token).ok().map(FunctionTarget::Function),
+    }
+}
+
+fn resource_of_kind(file: &ProjectFile, kinds: &[&str]) -> Option<ResourceLocation> {
+    file.resource_kind()
+        .filter(|kind| kinds.contains(&kind.as_str()))
+        .and(file.resource_location())
+}
+
+/// The only part of a tag file the call graph reads, so generated tags aren't parsed in full
+#[derive(serde::Deserialize)]
+struct TagView {
+    values: Option<serde_json::Value>,
+}
+
+#[derive(serde::Deserialize)]
+struct AdvancementView {
+    rewards: Option<serde_json::Value>,
+}
+
+/// Function tags, resolved to the functions they run, including through nested tags
+fn function_tags(project: &Project) -> HashMap<ResourceLocation, Vec<ResourceLocation>> {
+    let raw_tags: HashMap<_, _> = project.iter_files()
+        .filter_map(|file| {
+            let tag = resource_of_kind(&file, FUNCTION_TAG_KINDS)?;
+            let json: TagView = serde_json::from_str(file.contents).ok()?;
+
+            let values = json.values?.as_array()?.iter()
+                .filter_map(|value| match value {
+                    // Entries are either an id, or an object with an id and whether it is required
+                    serde_json::Value::Object(entry) => entry.get("id")?.as_str(),
+                    value => value.as_str(),
+                })
+                .filter_map(parse_target)
+                .collect::<Vec<_>>();
+
+            Some((tag, values))
+        })
+        .collect();
+
+    raw_tags.keys()
+        .map(|tag| {
+            let mut functions = Vec::new();
+            let mut visited = HashSet::new();
+            let mut stack = vec![tag];
+
+            while let Some(tag) = stack.pop() {
+                if !visited.insert(tag) {
+                    continue;
+                }
+
+                for value in raw_tags.get(tag).into_iter().flatten() {
+                    match value {
+                        FunctionTarget::Function(function) => functions.push(function.clone()),
+                        FunctionTarget::Tag(nested) => stack.push(nested),
+                    }
+                }
+            }
+
+            (tag.clone(), functions)
+        })
+        .collect()
+}
+
+/// Functions run as advancement rewards
+fn advancement_rewards(project: &Project) -> Vec<ResourceLocation> {
+    project.iter_files()
+        .filter(|file| resource_of_kind(file, ADVANCEMENT_KINDS).is_some())
+        .filter_map(|file| {
+            let json: AdvancementView = serde_json::from_str(file.contents).ok()?;
+            let function = json.rewards.as_ref()?.get("function")?.as_str()?;
+            ResourceLocation::from_str(function).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(location: &str) -> ResourceLocation {
+        ResourceLocation::from_str(location).unwrap()
+    }
+
+    fn test_project() -> Project {
+        let files = [
+            ("data/minecraft/tags/function/load.json", r#"{"values": ["test:load"]}"#),
+            ("data/minecraft/tags/function/tick.json", r##"{"values": ["#test:every_tick"]}"##),
+            ("data/test/tags/function/every_tick.json", r#"{"values": [{"id": "test:tick", "required": false}]}"#),
+            ("data/test/function/load.mcfunction", "# Set up scores\nfunction test:setup\nscoreboard objectives add test dummy"),
+            ("data/test/function/setup.mcfunction", "say setting up\n#function test:unused"),
+            ("data/test/function/tick.mcfunction", "execute as @a at @s if entity @s[tag=active] run function test:player/tick"),
+            ("data/test/function/player/tick.mcfunction", "schedule function test:player/tick 1t\nfunction minecraft:vanilla"),
+            ("data/test/function/unused.mcfunction", "function test:also_unused"),
+            ("data/test/function/also_unused.mcfunction", "say nobody calls me"),
+            ("data/test/function/reward.mcfunction", "say well done"),
+            ("data/test/advancement/reward.json", r#"{"criteria": {}, "rewards": {"function": "test:reward"}}"#),
+        ];
+
+        Project::test_with_files(&files)
+    }
+
+    #[test]
+    fn test_parse_function_calls() {
+        // Given an mcfunction file with direct, conditional, commented, and tag calls
+        let contents = "function test:a\n  # function test:commented\nexecute if score @s x matches 1 run function test:b\nfunction #test:tag\nsay function";
+
+        // When I parse the calls
+        let calls = parse_function_calls(contents);
+
+        // Then only the real calls should be found
+        assert_eq!(calls, vec![
+            FunctionTarget::Function(location("test:a")),
+            FunctionTarget::Function(location("test:b")),
+            FunctionTarget::Tag(location("test:tag")),
+        ]);
+    }
+
+    #[test]
+    fn test_call_edges() {
+        // Given a project with functions calling each other
+        let graph = CallGraph::from_project(&test_project());
+
+        // When I query calls to and from a function
+        // Then the edges should match the function bodies
+        assert_eq!(graph.callees(&location("test:load")), vec![&location("test:setup")]);
+        assert_eq!(graph.callees(&location("test:player/tick")), vec![&location("minecraft:vanilla"), &location("test:player/tick")]);
+        assert_eq!(graph.callers(&location("test:player/tick")), vec![&location("test:player/tick"), &location("test:tick")]);
+    }
+
+    #[test]
+    fn test_call_hierarchy() {
+        // Given a project with nested and recursive calls
+        let graph = CallGraph::from_project(&test_project());
+
+        // When I get the call hierarchy of the tick function
+        let hierarchy = graph.call_hierarchy(&location("test:tick"));
+
+        // Then it should list each call once per path, without following recursion forever
+        assert_eq!(hierarchy, vec![
+            (1, &location("test:player/tick")),
+            (2, &location("minecraft:vanilla")),
+            (2, &location("test:player/tick")),
+        ]);
+    }
+
+    #[test]
+    fn test_unused_functions() {
+        // Given a project with functions reachable through tags and advancements, and some which aren't
+        let graph = CallGraph::from_project(&test_project());
+
+        // When I look for unused functions
+        let unused = graph.unused_functions();
+
+        // Then only the unreachable ones should be reported, even if they call each other
+        assert_eq!(unused, vec![&location("test:also_unused"), &location("test:unused")]);
+    }
+}
@@ -0,0 +1,441 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Component, Path, PathBuf};
+use mc_version::{MinecraftVersion, PackFormat};
+use once_cell::sync::Lazy;
+use crate::data::diagnostic::{self, Diagnostic, DiagnosticSeverity};
+use crate::data::domain::resource::resource::ResourceCategory;
+use crate::data::domain::versions;
+
+/// Something a pack can contain which is only understood by some versions of the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackFeature {
+    /// A directory below `<category>/<namespace>/`, such as `recipe` or `tags/function`
+    Directory(ResourceCategory, &'static str),
+    /// The `type` of a recipe, such as `minecraft:crafting_transmute`
+    RecipeType(&'static str),
+}
+
+impl Display for PackFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackFeature::Directory(_, directory) => write!(f, "directory `{}`", directory),
+            PackFeature::RecipeType(recipe_type) => write!(f, "recipe type `{}`", recipe_type),
+        }
+    }
+}
+
+/// The versions a feature is available in, from `since` up to but not including `removed_in`
+#[derive(Debug, Clone)]
+pub struct CompatibilityRule {
+    pub feature: PackFeature,
+    pub since: Option<MinecraftVersion>,
+    pub removed_in: Option<MinecraftVersion>,
+}
+
+impl CompatibilityRule {
+    fn since(feature: PackFeature, version: MinecraftVersion) -> Self {
+        Self { feature, since: Some(version), removed_in: None }
+    }
+
+    fn removed_in(feature: PackFeature, version: MinecraftVersion) -> Self {
+        Self { feature, since: None, removed_in: Some(version) }
+    }
+
+    /// Returns the issue with using this feature in a pack of the given format, if any.
+    /// A format can span several versions, so the feature must be available in all of them
+    fn check(&self, format: &PackFormat) -> Option<CompatibilityIssue> {
+        if let (Some(since), Some(min_version)) = (self.since, format.min_version()) {
+            if min_version.is_before(&since) {
+                return Some(CompatibilityIssue::NotYetAvailable { feature: self.feature.clone(), since });
+            }
+        }
+
+        if let (Some(removed_in), Some(max_version)) = (self.removed_in, format.max_version()) {
+            if max_version.is_at_least(&removed_in) {
+                return Some(CompatibilityIssue::NoLongerAvailable { feature: self.feature.clone(), removed_in });
+            }
+        }
+
+        None
+    }
+}
+
+/// Data directories which were renamed from plural to singular in 1.21, as `(plural, singular)`
+const RENAMED_DATA_DIRECTORIES: &[(&str, &str)] = &[
+    ("functions", "function"),
+    ("recipes", "recipe"),
+    ("advancements", "advancement"),
+    ("loot_tables", "loot_table"),
+    ("predicates", "predicate"),
+    ("item_modifiers", "item_modifier"),
+    ("structures", "structure"),
+    ("tags/functions", "tags/function"),
+    ("tags/blocks", "tags/block"),
+    ("tags/items", "tags/item"),
+    ("tags/entity_types", "tags/entity_type"),
+    ("tags/fluids", "tags/fluid"),
+    ("tags/game_events", "tags/game_event"),
+];
+
+/// The name a data directory had before the 1.21 rename, e.g. `functions` for `function`
+pub fn legacy_data_directory(directory: &str) -> Option<&'static str> {
+    RENAMED_DATA_DIRECTORIES.iter()
+        .find(|(_, singular)| *singular == directory)
+        .map(|(plural, _)| *plural)
+}
+
+pub static COMPATIBILITY_RULES: Lazy<Vec<CompatibilityRule>> = Lazy::new(|| {
+    let data = |directory| PackFeature::Directory(ResourceCategory::Data, directory);
+    let asset = |directory| PackFeature::Directory(ResourceCategory::Asset, directory);
+
+    let mut rules = Vec::new();
+
+    for (plural, singular) in RENAMED_DATA_DIRECTORIES {
+        rules.push(CompatibilityRule::removed_in(data(plural), *versions::V1_21));
+        rules.push(CompatibilityRule::since(data(singular), *versions::V1_21));
+    }
+
+    rules.extend([
+        CompatibilityRule::since(data("trim_material"), *versions::V1_19_4),
+        CompatibilityRule::since(data("trim_pattern"), *versions::V1_19_4),
+        CompatibilityRule::since(data("wolf_variant"), *versions::V1_20_5),
+        CompatibilityRule::since(data("banner_pattern"), *versions::V1_20_5),
+        CompatibilityRule::since(data("enchantment"), *versions::V1_21),
+        CompatibilityRule::since(data("enchantment_provider"), *versions::V1_21),
+        CompatibilityRule::since(data("jukebox_song"), *versions::V1_21),
+        CompatibilityRule::since(data("painting_variant"), *versions::V1_21),
+        CompatibilityRule::since(data("instrument"), *versions::V1_21_2),
+        CompatibilityRule::since(data("cat_variant"), *versions::V1_21_5),
+        CompatibilityRule::since(data("chicken_variant"), *versions::V1_21_5),
+        CompatibilityRule::since(data("cow_variant"), *versions::V1_21_5),
+        CompatibilityRule::since(data("frog_variant"), *versions::V1_21_5),
+        CompatibilityRule::since(data("pig_variant"), *versions::V1_21_5),
+        CompatibilityRule::since(data("dialog"), *versions::V1_21_6),
+        CompatibilityRule::since(asset("items"), *versions::V1_21_4),
+        CompatibilityRule::since(asset("equipment"), *versions::V1_21_4),
+        CompatibilityRule::since(PackFeature::RecipeType("minecraft:smithing_transform"), *versions::V1_19_4),
+        CompatibilityRule::since(PackFeature::RecipeType("minecraft:smithing_trim"), *versions::V1_19_4),
+        CompatibilityRule::removed_in(PackFeature::RecipeType("minecraft:smithing"), *versions::V1_20),
+        CompatibilityRule::since(PackFeature::RecipeType("minecraft:crafting_decorated_pot"), *versions::V1_20),
+        CompatibilityRule::since(PackFeature::RecipeType("minecraft:crafting_transmute"), *versions::V1_21_2),
+    ]);
+
+    rules
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompatibilityIssue {
+    NotYetAvailable { feature: PackFeature, since: MinecraftVersion },
+    NoLongerAvailable { feature: PackFeature, removed_in: MinecraftVersion },
+}
+
+impl Display for CompatibilityIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompatibilityIssue::NotYetAvailable { feature, since } => write!(f, "{} requires Minecraft {} or newer", feature, since),
+            CompatibilityIssue::NoLongerAvailable { feature, removed_in } => write!(f, "{} was removed in Minecraft {}", feature, removed_in),
+        }
+    }
+}
+
+/// A feature of a pack and the version of the game which added it. File types with their own adapter declare theirs
+/// through [`Adapter::min_version`](crate::data::adapters::Adapter::min_version)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureVersion {
+    pub feature: PackFeature,
+    pub since: MinecraftVersion,
+}
+
+/// A file in an exported pack which uses a feature unavailable in the pack's declared format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatibilityWarning {
+    pub path: PathBuf,
+    /// Line of the file, counting from 1, for features used by a field rather than by where the file is
+    pub line: Option<usize>,
+    pub pack_format: u8,
+    pub issue: CompatibilityIssue,
+}
+
+impl Display for CompatibilityWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} (pack format {})", self.path.display(), self.issue, self.pack_format)
+    }
+}
+
+impl CompatibilityIssue {
+    pub fn feature(&self) -> &PackFeature {
+        match self {
+            CompatibilityIssue::NotYetAvailable { feature, .. } | CompatibilityIssue::NoLongerAvailable { feature, .. } => feature,
+        }
+    }
+
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            CompatibilityIssue::NotYetAvailable { .. } => "not-yet-available",
+            CompatibilityIssue::NoLongerAvailable { .. } => "no-longer-available",
+        }
+    }
+}
+
+impl From<CompatibilityWarning> for Diagnostic {
+    fn from(warning: CompatibilityWarning) -> Self {
+        Self {
+            path: warning.path,
+            line: warning.line,
+            severity: DiagnosticSeverity::Warning,
+            rule: warning.issue.rule_id().to_string(),
+            message: format!("{} (pack format {})", warning.issue, warning.pack_format),
+        }
+    }
+}
+
+/// Checks every file of a pack against [`COMPATIBILITY_RULES`] for the given pack format.
+/// Warnings are sorted by path, so that they are reported in a stable order
+pub fn check_compatibility(files: &HashMap<PathBuf, String>, format: &PackFormat) -> Vec<CompatibilityWarning> {
+    let mut warnings: Vec<_> = files.iter()
+        .flat_map(|(path, content)| {
+            let features = features_used(path, content, COMPATIBILITY_RULES.iter().map(|rule| &rule.feature));
+
+            COMPATIBILITY_RULES.iter()
+                .filter(move |rule| features.contains(&rule.feature))
+                .filter_map(|rule| rule.check(format))
+                .map(|issue| CompatibilityWarning {
+                    path: path.clone(),
+                    line: match issue.feature() {
+                        PackFeature::RecipeType(_) => diagnostic::line_of(content, "\"type\""),
+                        PackFeature::Directory(..) => None,
+                    },
+                    pack_format: format.get_format_id(),
+                    issue,
+                })
+        })
+        .collect();
+
+    warnings.sort_by(|a, b| a.path.cmp(&b.path));
+    warnings
+}
+
+/// The oldest version of the game able to load a pack, and the file and feature which need it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredVersion {
+    pub version: MinecraftVersion,
+    pub path: PathBuf,
+    pub feature: PackFeature,
+}
+
+impl Display for RequiredVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Minecraft {} or newer, for the {} in {}", self.version, self.feature, self.path.display())
+    }
+}
+
+/// The newest version in which any feature used by the files was added, whatever format the pack declares.
+/// Features are the ones the registered adapters declare, see [`Adapter::min_version`](crate::data::adapters::Adapter::min_version),
+/// on top of the built-in ones in [`COMPATIBILITY_RULES`].
+/// This is `None` when every feature used is in all supported versions. Of several files needing that version, the first by path is reported
+pub fn required_version<'a>(files: impl IntoIterator<Item = (&'a Path, &'a str)>, adapter_versions: &[FeatureVersion]) -> Option<RequiredVersion> {
+    let feature_versions: Vec<_> = COMPATIBILITY_RULES.iter()
+        .filter_map(|rule| rule.since.map(|since| FeatureVersion { feature: rule.feature.clone(), since }))
+        .chain(adapter_versions.iter().cloned())
+        .collect();
+    let feature_versions = &feature_versions;
+
+    files.into_iter()
+        .flat_map(|(path, content)| {
+            let features = features_used(path, content, feature_versions.iter().map(|feature_version| &feature_version.feature));
+
+            feature_versions.iter()
+                .filter(move |feature_version| features.contains(&feature_version.feature))
+                .map(move |feature_version| RequiredVersion {
+                    version: feature_version.since,
+                    path: path.to_path_buf(),
+                    feature: feature_version.feature.clone(),
+                })
+        })
+        .min_by(|a, b| b.version.cmp(&a.version).then_with(|| a.path.cmp(&b.path)))
+}
+
+/// Which of the given features a single file makes use of
+fn features_used<'f>(path: &Path, content: &str, candidates: impl IntoIterator<Item = &'f PackFeature>) -> Vec<PackFeature> {
+    let components: Vec<_> = path.components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => segment.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    // Only files inside a namespace, i.e. `<category>/<namespace>/<directory>/...`, can use features
+    let (category, directory) = match components.as_slice() {
+        ["data", _, directory @ .., _] => (ResourceCategory::Data, directory),
+        ["assets", _, directory @ .., _] => (ResourceCategory::Asset, directory),
+        _ => return Vec::new(),
+    };
+
+    let is_recipe = category == ResourceCategory::Data
+        && matches!(directory.first(), Some(&"recipe") | Some(&"recipes"));
+    let recipe_type = if is_recipe { recipe_type(content) } else { None };
+
+    candidates.into_iter()
+        .filter(|feature| match feature {
+            PackFeature::Directory(feature_category, feature_directory) => {
+                let feature_components: Vec<_> = feature_directory.split('/').collect();
+                *feature_category == category && directory.starts_with(&feature_components)
+            }
+            PackFeature::RecipeType(feature_type) => recipe_type.as_deref() == Some(*feature_type),
+        })
+        .cloned()
+        .collect()
+}
+
+/// The namespaced `type` of a recipe file, defaulting to the `minecraft` namespace
+fn recipe_type(content: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    let recipe_type = json.get("type")?.as_str()?;
+
+    if recipe_type.contains(':') {
+        Some(recipe_type.to_string())
+    } else {
+        Some(format!("minecraft:{}", recipe_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> HashMap<PathBuf, String> {
+        entries.iter()
+            .map(|(path, content)| (PathBuf::from(path), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_recipe_type_too_new() {
+        // Given a transmute recipe, which was added in 1.21.2
+        let files = files(&[
+            ("data/test/recipe/dye.json", r#"{"type": "minecraft:crafting_transmute"}"#),
+        ]);
+
+        // When I check it against the 1.21 format
+        let warnings = check_compatibility(&files, versions::get_datapack_format_for_version(*versions::V1_21));
+
+        // Then it should warn that the recipe type is too new
+        assert_eq!(warnings, vec![CompatibilityWarning {
+            path: PathBuf::from("data/test/recipe/dye.json"),
+            line: Some(1),
+            pack_format: 48,
+            issue: CompatibilityIssue::NotYetAvailable {
+                feature: PackFeature::RecipeType("minecraft:crafting_transmute"),
+                since: *versions::V1_21_2,
+            },
+        }]);
+    }
+
+    #[test]
+    fn test_recipe_type_without_namespace() {
+        // Given a recipe type without a namespace
+        let files = files(&[
+            ("data/test/recipe/dye.json", r#"{"type": "crafting_transmute"}"#),
+        ]);
+
+        // When I check it against a format which supports it
+        let warnings = check_compatibility(&files, versions::get_datapack_format_for_version(*versions::V1_21_4));
+
+        // Then there should be no warnings
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_renamed_directories() {
+        // Given a pack using both the plural and singular function directories
+        let files = files(&[
+            ("data/test/functions/old.mcfunction", "say old"),
+            ("data/test/function/new.mcfunction", "say new"),
+        ]);
+
+        // When I check it against a format from before the rename
+        let old_warnings = check_compatibility(&files, versions::get_datapack_format_for_version(*versions::V1_20_4));
+
+        // Then only the singular directory should be flagged
+        assert_eq!(old_warnings.len(), 1);
+        assert_eq!(old_warnings[0].path, PathBuf::from("data/test/function/new.mcfunction"));
+
+        // And when I check it against a format from after the rename
+        let new_warnings = check_compatibility(&files, versions::get_datapack_format_for_version(*versions::V1_21));
+
+        // Then only the plural directory should be flagged
+        assert_eq!(new_warnings.len(), 1);
+        assert_eq!(new_warnings[0].path, PathBuf::from("data/test/functions/old.mcfunction"));
+        assert!(matches!(new_warnings[0].issue, CompatibilityIssue::NoLongerAvailable { .. }));
+    }
+
+    #[test]
+    fn test_nested_tag_directory() {
+        // Given a function tag in the pre-1.21 layout
+        let files = files(&[
+            ("data/minecraft/tags/functions/load.json", r#"{"values": []}"#),
+        ]);
+
+        // When I check it against the 1.21 format
+        let warnings = check_compatibility(&files, versions::get_datapack_format_for_version(*versions::V1_21));
+
+        // Then it should be flagged
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].issue, CompatibilityIssue::NoLongerAvailable {
+            feature: PackFeature::Directory(ResourceCategory::Data, "tags/functions"),
+            removed_in: *versions::V1_21,
+        });
+    }
+
+    #[test]
+    fn test_required_version() {
+        // Given a pack with an old function, a trim recipe from 1.19.4 and a transmute recipe from 1.21.2
+        let files = files(&[
+            ("data/test/function/load.mcfunction", "say hi"),
+            ("data/test/recipe/trim.json", r#"{"type": "minecraft:smithing_trim"}"#),
+            ("data/test/recipe/dye.json", r#"{"type": "minecraft:crafting_transmute"}"#),
+        ]);
+
+        // When I work out the version it requires
+        let required = required_version(files.iter().map(|(path, content)| (path.as_path(), content.as_str())), &[]);
+
+        // Then the transmute recipe should raise it to 1.21.2
+        assert_eq!(required, Some(RequiredVersion {
+            version: *versions::V1_21_2,
+            path: PathBuf::from("data/test/recipe/dye.json"),
+            feature: PackFeature::RecipeType("minecraft:crafting_transmute"),
+        }));
+    }
+
+    #[test]
+    fn test_required_version_without_new_features() {
+        // Given a pack which only uses features every supported version has
+        let files = files(&[
+            ("data/test/functions/load.mcfunction", "say hi"),
+            ("data/test/recipes/stick.json", r#"{"type": "minecraft:crafting_shaped"}"#),
+        ]);
+
+        // When I work out the version it requires
+        let required = required_version(files.iter().map(|(path, content)| (path.as_path(), content.as_str())), &[]);
+
+        // Then it shouldn't require any
+        assert_eq!(required, None);
+    }
+
+    #[test]
+    fn test_files_outside_namespaces_ignored() {
+        // Given files at the root of the pack and a namespace's own directory
+        let files = files(&[
+            ("pack.png", ""),
+            ("data/functions", ""),
+            ("assets/test/items", ""),
+        ]);
+
+        // When I check them against an old format
+        let warnings = check_compatibility(&files, versions::get_datapack_format_for_version(*versions::V1_13));
+
+        // Then none should be flagged
+        assert!(warnings.is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+use crate::data::domain::project_index::ProjectIndex;
+use crate::data::domain::resource::resource::ResourceLocation;
+use crate::data::domain::vanilla_registry::VanillaRegistry;
+
+/// What kind of resource location is expected where completion was asked for, so only resources which fit are suggested
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CompletionContext {
+    /// An item, e.g. in `give` or a recipe result
+    Item,
+    /// The target of `function`, either a function or a `#` function tag
+    FunctionCall,
+    /// A value of a tag of the given kind, e.g. `item` for `tags/item`, which may also be `#` another tag of the same kind
+    TagMember { kind: String },
+}
+
+impl CompletionContext {
+    /// The resource kind suggested in this context, as used by [`ProjectIndex`] and [`VanillaRegistry`]
+    fn kind(&self) -> &str {
+        match self {
+            CompletionContext::Item => "item",
+            CompletionContext::FunctionCall => "function",
+            CompletionContext::TagMember { kind } => kind,
+        }
+    }
+}
+
+/// Commands whose argument after the target is an item
+const ITEM_COMMANDS: &[&str] = &["give", "clear"];
+/// JSON fields holding an item, e.g. in a recipe result or an item predicate
+const ITEM_FIELDS: &[&str] = &["item", "id"];
+
+/// The resource location being typed at the end of `before_cursor`, and what kind of resource is expected there,
+/// judged by the command or field before it, or by the tag file being edited
+pub fn context_at(before_cursor: &str, file: Option<&Path>) -> Option<(String, CompletionContext)> {
+    let is_reference_char = |c: char| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.' | '/' | ':' | '#');
+
+    let start = before_cursor.rfind(|c: char| !is_reference_char(c)).map_or(0, |index| index + 1);
+    let (before, prefix) = before_cursor.split_at(start);
+    let before = before.trim_end();
+
+    let field = before.strip_suffix('"')
+        .map(|before| before.trim_end().trim_end_matches(':').trim_end())
+        .and_then(|before| before.strip_suffix('"'))
+        .map(|before| &before[before.rfind('"').map_or(0, |index| index + 1)..]);
+    let words: Vec<_> = before.split_whitespace().rev().take(2).collect();
+
+    let context = if before.ends_with("function") || field == Some("function") {
+        CompletionContext::FunctionCall
+    } else if field.is_some_and(|field| ITEM_FIELDS.contains(&field)) || words.get(1).is_some_and(|command| ITEM_COMMANDS.contains(command)) {
+        CompletionContext::Item
+    } else {
+        CompletionContext::TagMember { kind: file.and_then(tag_kind)? }
+    };
+
+    Some((prefix.to_string(), context))
+}
+
+/// The kind of resource a tag file lists, e.g. `item` for `data/example/tags/item/gems.json`
+fn tag_kind(file: &Path) -> Option<String> {
+    let components: Vec<_> = file.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+
+    components.windows(5)
+        .rev()
+        .find(|window| window[0] == "data" && window[2] == "tags")
+        .map(|window| window[3].to_string())
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum CompletionSource {
+    Project,
+    Vanilla,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CompletionCandidate {
+    pub location: ResourceLocation,
+    pub is_tag: bool,
+    pub source: CompletionSource,
+}
+
+impl CompletionCandidate {
+    /// The text to insert, with a leading `#` for tags
+    pub fn text(&self) -> String {
+        if self.is_tag {
+            format!("#{}", self.location)
+        } else {
+            self.location.to_string()
+        }
+    }
+
+    fn matches(&self, prefix: &str) -> bool {
+        let prefix = match prefix.strip_prefix('#') {
+            Some(prefix) if self.is_tag => prefix,
+            Some(_) => return false,
+            None => prefix,
+        };
+
+        match prefix.contains(':') {
+            true => self.location.to_string().starts_with(prefix),
+            // The namespace is often left out, so a bare prefix may be the start of either half
+            false => self.location.namespace().starts_with(prefix) || self.location.value().starts_with(prefix),
+        }
+    }
+}
+
+/// Suggests resource locations from both the game and the project. The project's index may still be building,
+/// in which case only vanilla is suggested until it's ready
+pub struct ResourceCompletion<'a> {
+    vanilla: &'a VanillaRegistry,
+    project: Option<&'a ProjectIndex>,
+}
+
+impl<'a> ResourceCompletion<'a> {
+    pub fn new(vanilla: &'a VanillaRegistry, project: Option<&'a ProjectIndex>) -> Self {
+        Self { vanilla, project }
+    }
+
+    /// Candidates fitting the context which start with the prefix, with the project's own resources first,
+    /// then resources before tags, then sorted by resource location. A project resource overriding a vanilla one is only listed once
+    pub fn complete(&self, prefix: &str, context: &CompletionContext) -> Vec<CompletionCandidate> {
+        let kind = context.kind();
+        let tag_kind = format!("tags/{}", kind);
+
+        let mut candidates: BTreeMap<(bool, &ResourceLocation), CompletionSource> = BTreeMap::new();
+
+        for (source, is_tag, locations) in [
+            (CompletionSource::Vanilla, false, self.vanilla_resources(kind)),
+            (CompletionSource::Vanilla, true, self.vanilla_resources(&tag_kind)),
+            (CompletionSource::Project, false, self.project_resources(kind)),
+            (CompletionSource::Project, true, self.project_resources(&tag_kind)),
+        ] {
+            for location in locations {
+                candidates.insert((is_tag, location), source);
+            }
+        }
+
+        let mut candidates: Vec<_> = candidates.into_iter()
+            .map(|((is_tag, location), source)| CompletionCandidate { location: location.clone(), is_tag, source })
+            .filter(|candidate| candidate.matches(prefix))
+            .collect();
+
+        candidates.sort_by(|a, b| a.source.cmp(&b.source)
+            .then_with(|| a.is_tag.cmp(&b.is_tag))
+            .then_with(|| a.location.cmp_vanilla_first(&b.location)));
+
+        candidates
+    }
+
+    fn vanilla_resources(&self, kind: &str) -> Vec<&'a ResourceLocation> {
+        self.vanilla.resources(kind).collect()
+    }
+
+    fn project_resources(&self, kind: &str) -> Vec<&'a ResourceLocation> {
+        self.project.map_or_else(Vec::new, |project| project.resources(kind).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::domain::project::Project;
+
+    fn project_index(files: &[(&str, &str)]) -> ProjectIndex {
+        let project = Project::test_with_files(files);
+
+        ProjectIndex::build(&project, |_| {})
+    }
+
+    fn vanilla_registry() -> VanillaRegistry {
+        VanillaRegistry::from_entries([
+            ("item", &["stick", "stone", "diamond"][..]),
+            ("tags/item", &["logs"][..]),
+            ("tags/function", &["load", "tick"][..]),
+        ])
+    }
+
+    fn texts(candidates: &[CompletionCandidate]) -> Vec<String> {
+        candidates.iter().map(CompletionCandidate::text).collect()
+    }
+
+    #[test]
+    fn test_function_call_suggests_functions_not_items() {
+        // Given vanilla items and function tags, and a project with functions and an item tag
+        let vanilla = vanilla_registry();
+        let index = project_index(&[
+            ("data/example/function/setup.mcfunction", "say hi"),
+            ("data/example/function/util/spawn.mcfunction", "say hi"),
+            ("data/example/tags/item/gems.json", r#"{"values": []}"#),
+        ]);
+        let completion = ResourceCompletion::new(&vanilla, Some(&index));
+
+        // When I complete the target of a function call
+        let candidates = completion.complete("", &CompletionContext::FunctionCall);
+
+        // Then the project's functions should come first, then function tags, without any items or item tags
+        assert_eq!(texts(&candidates), vec!["example:setup", "example:util/spawn", "#minecraft:load", "#minecraft:tick"]);
+        assert_eq!(candidates[0].source, CompletionSource::Project);
+        assert_eq!(candidates[2].source, CompletionSource::Vanilla);
+    }
+
+    #[test]
+    fn test_item_suggests_items_and_item_tags() {
+        // Given the same registry and project
+        let vanilla = vanilla_registry();
+        let index = project_index(&[
+            ("data/example/function/setup.mcfunction", "say hi"),
+            ("data/example/tags/item/gems.json", r#"{"values": []}"#),
+        ]);
+        let completion = ResourceCompletion::new(&vanilla, Some(&index));
+
+        // When I complete an item slot
+        let candidates = completion.complete("", &CompletionContext::Item);
+
+        // Then items and item tags should be suggested, but no functions
+        assert_eq!(texts(&candidates), vec!["#example:gems", "minecraft:diamond", "minecraft:stick", "minecraft:stone", "#minecraft:logs"]);
+    }
+
+    #[test]
+    fn test_prefix_filters_candidates() {
+        // Given a project overriding a vanilla function tag
+        let vanilla = vanilla_registry();
+        let index = project_index(&[
+            ("data/example/function/tick.mcfunction", "say hi"),
+            ("data/minecraft/tags/function/tick.json", r#"{"values": ["example:tick"]}"#),
+        ]);
+        let completion = ResourceCompletion::new(&vanilla, Some(&index));
+
+        // When I complete with a bare path, a full location, and a tag prefix
+        let bare = completion.complete("ti", &CompletionContext::FunctionCall);
+        let full = completion.complete("minecraft:", &CompletionContext::FunctionCall);
+        let tags = completion.complete("#", &CompletionContext::FunctionCall);
+
+        // Then each should only match the candidates starting with it, listing the overridden tag once as the project's
+        assert_eq!(texts(&bare), vec!["example:tick", "#minecraft:tick"]);
+        assert_eq!(bare[1].source, CompletionSource::Project);
+        assert_eq!(texts(&full), vec!["#minecraft:tick", "#minecraft:load"]);
+        assert_eq!(texts(&tags), vec!["#minecraft:tick", "#minecraft:load"]);
+    }
+
+    #[rstest::rstest]
+    #[case::function_call("execute as @a run function example:ti", None, Some(("example:ti", CompletionContext::FunctionCall)))]
+    #[case::function_field(r#"{"function": "exa"#, None, Some(("exa", CompletionContext::FunctionCall)))]
+    #[case::give("give @s minecraft:di", None, Some(("minecraft:di", CompletionContext::Item)))]
+    #[case::item_field(r#"  "result": {"id": ""#, None, Some(("", CompletionContext::Item)))]
+    #[case::tag_member(r##"  "values": ["#lo"##, Some("pack/data/example/tags/block/logs.json"), Some(("#lo", CompletionContext::TagMember { kind: "block".to_string() })))]
+    #[case::plain_text("say hel", None, None)]
+    fn test_context_at(#[case] before_cursor: &str, #[case] file: Option<&str>, #[case] expected: Option<(&str, CompletionContext)>) {
+        // Given the text before the cursor in a file
+        // When I look for what is being completed
+        let context = context_at(before_cursor, file.map(Path::new));
+
+        // Then the prefix and the expected kind of resource should be found
+        assert_eq!(context, expected.map(|(prefix, context)| (prefix.to_string(), context)));
+    }
+
+    #[test]
+    fn test_completes_vanilla_before_index_is_ready() {
+        // Given a project whose index hasn't been built yet
+        let vanilla = vanilla_registry();
+        let completion = ResourceCompletion::new(&vanilla, None);
+
+        // When I complete a tag member
+        let candidates = completion.complete("minecraft:st", &CompletionContext::TagMember { kind: "item".to_string() });
+
+        // Then vanilla should still be suggested
+        assert_eq!(texts(&candidates), vec!["minecraft:stick", "minecraft:stone"]);
+    }
+}
@@ -0,0 +1,109 @@
+use std::path::Path;
+use glob::{MatchOptions, Pattern};
+
+/// File at the root of a project listing what to leave out of its exports, one glob pattern per line
+pub const EXPORT_IGNORE_FILE: &str = ".gaeaignore";
+
+/// Left out of every export: the editor's own files beside the project, and the ignore file itself
+const DEFAULT_PATTERNS: [&str; 2] = [".gaea/", EXPORT_IGNORE_FILE];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Files of a project which are kept while working on it, but not shipped in the pack, such as notes or source templates.
+///
+/// Patterns follow `.gitignore`: blank lines and lines starting with `#` are skipped, a pattern without a `/`
+/// matches at any depth, a leading `/` anchors it to the pack root, and a trailing `/` matches everything in a directory
+#[derive(Debug, Clone)]
+pub struct ExportIgnore {
+    patterns: Vec<Pattern>,
+}
+
+impl Default for ExportIgnore {
+    fn default() -> Self {
+        Self::parse("")
+    }
+}
+
+impl ExportIgnore {
+    /// Reads the contents of an ignore file, on top of the default patterns. Patterns which aren't valid globs are skipped
+    pub fn parse(contents: &str) -> Self {
+        let patterns = DEFAULT_PATTERNS.into_iter()
+            .chain(contents.lines().map(str::trim))
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| match Pattern::new(&to_glob(line)) {
+                Ok(pattern) => Some(pattern),
+                Err(error) => {
+                    tracing::warn!("Skipping invalid pattern `{}` in {}: {}", line, EXPORT_IGNORE_FILE, error);
+                    None
+                }
+            })
+            .collect();
+
+        Self { patterns }
+    }
+
+    /// Whether a file, given its path relative to the pack root, is left out of exports
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.patterns.iter().any(|pattern| pattern.matches_path_with(path, MATCH_OPTIONS))
+    }
+}
+
+fn to_glob(line: &str) -> String {
+    let (line, directory) = match line.strip_suffix('/') {
+        Some(line) => (line, true),
+        None => (line, false),
+    };
+    let (line, anchored) = match line.strip_prefix('/') {
+        Some(line) => (line, true),
+        None => (line, line.contains('/')),
+    };
+
+    let mut glob = if anchored { line.to_string() } else { format!("**/{}", line) };
+    if directory {
+        glob.push_str("/**");
+    }
+    glob
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const IGNORE_FILE: &str = "# Working files\nnotes.md\n*.psd\n/templates/\ndata/example/function/debug_*.mcfunction\n";
+
+    #[rstest]
+    #[case::default_settings(".gaea/project.json", true)]
+    #[case::ignore_file(".gaeaignore", true)]
+    #[case::name_at_root("notes.md", true)]
+    #[case::name_in_directory("assets/example/notes.md", true)]
+    #[case::extension("assets/example/textures/block/ruby.psd", true)]
+    #[case::anchored_directory("templates/recipe.json", true)]
+    #[case::anchored_directory_elsewhere("data/templates/recipe.json", false)]
+    #[case::path_pattern("data/example/function/debug_spawn.mcfunction", true)]
+    #[case::path_pattern_no_crossing("data/example/function/debug/spawn.mcfunction", false)]
+    #[case::comment("# Working files", false)]
+    #[case::pack_file("data/example/function/load.mcfunction", false)]
+    fn test_is_ignored(#[case] path: &str, #[case] expected: bool) {
+        // Given an ignore file with a few kinds of pattern
+        let ignore = ExportIgnore::parse(IGNORE_FILE);
+
+        // When I check a path
+        // Then it should only be ignored if a pattern matches it
+        assert_eq!(ignore.is_ignored(Path::new(path)), expected);
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_skipped() {
+        // Given an ignore file with a broken pattern
+        let ignore = ExportIgnore::parse("[notes\nscratch.txt");
+
+        // Then the rest should still apply
+        assert!(ignore.is_ignored(Path::new("scratch.txt")));
+        assert!(!ignore.is_ignored(Path::new("[notes")));
+    }
+}
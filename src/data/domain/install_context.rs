@@ -0,0 +1,144 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use crate::data::domain::project::ProjectType;
+
+const SAVES_DIR: &str = "saves";
+const DATAPACKS_DIR: &str = "datapacks";
+const RESOURCEPACKS_DIR: &str = "resourcepacks";
+
+/// Where a path sits within a Minecraft installation, used to suggest a project type and export destination
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InstallContext {
+    /// Inside `<minecraft>/saves/<world>/datapacks`
+    WorldDatapacks {
+        minecraft_dir: PathBuf,
+        world: String,
+    },
+    /// Inside `<minecraft>/resourcepacks`
+    ResourcePacks {
+        minecraft_dir: PathBuf,
+    },
+}
+
+impl InstallContext {
+    pub fn minecraft_dir(&self) -> &Path {
+        match self {
+            InstallContext::WorldDatapacks { minecraft_dir, .. } => minecraft_dir,
+            InstallContext::ResourcePacks { minecraft_dir } => minecraft_dir,
+        }
+    }
+
+    pub fn project_type(&self) -> ProjectType {
+        match self {
+            InstallContext::WorldDatapacks { .. } => ProjectType::DataPack,
+            InstallContext::ResourcePacks { .. } => ProjectType::ResourcePack,
+        }
+    }
+
+    /// The directory the game loads packs of this kind from
+    pub fn export_dir(&self) -> PathBuf {
+        match self {
+            InstallContext::WorldDatapacks { minecraft_dir, world } => minecraft_dir.join(SAVES_DIR).join(world).join(DATAPACKS_DIR),
+            InstallContext::ResourcePacks { minecraft_dir } => minecraft_dir.join(RESOURCEPACKS_DIR),
+        }
+    }
+
+    /// Recognizes the layout of an installation from the path alone, so installs in launcher instances
+    /// or other non-default locations are found as well
+    pub fn detect(path: &Path) -> Option<Self> {
+        path.ancestors().find_map(|ancestor| {
+            let parent = ancestor.parent()?;
+
+            if is_named(ancestor, DATAPACKS_DIR) {
+                let saves = parent.parent()?;
+                if !is_named(saves, SAVES_DIR) {
+                    return None;
+                }
+
+                return Some(InstallContext::WorldDatapacks {
+                    minecraft_dir: saves.parent()?.to_path_buf(),
+                    world: parent.file_name()?.to_string_lossy().into_owned(),
+                });
+            }
+
+            is_named(ancestor, RESOURCEPACKS_DIR)
+                .then(|| InstallContext::ResourcePacks { minecraft_dir: parent.to_path_buf() })
+        })
+    }
+}
+
+/// Where the vanilla launcher installs the game on this platform, if it can be worked out from the environment
+pub fn default_minecraft_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(|app_data| PathBuf::from(app_data).join(".minecraft"))
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support/minecraft"))
+    } else {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".minecraft"))
+    }
+}
+
+/// Windows and macOS filesystems are case-insensitive by default, so `Saves` is the same folder as `saves` there
+fn is_named(path: &Path, name: &str) -> bool {
+    path.file_name()
+        .and_then(OsStr::to_str)
+        .is_some_and(|file_name| if cfg!(any(target_os = "windows", target_os = "macos")) {
+            file_name.eq_ignore_ascii_case(name)
+        } else {
+            file_name == name
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::world_datapacks("/home/steve/.minecraft/saves/My World/datapacks", Some(InstallContext::WorldDatapacks {
+        minecraft_dir: PathBuf::from("/home/steve/.minecraft"),
+        world: "My World".to_string(),
+    }))]
+    #[case::inside_datapack("/home/steve/.minecraft/saves/My World/datapacks/my_pack/data", Some(InstallContext::WorldDatapacks {
+        minecraft_dir: PathBuf::from("/home/steve/.minecraft"),
+        world: "My World".to_string(),
+    }))]
+    #[case::launcher_instance("/games/instances/Modded/minecraft/resourcepacks/my_pack", Some(InstallContext::ResourcePacks {
+        minecraft_dir: PathBuf::from("/games/instances/Modded/minecraft"),
+    }))]
+    #[case::datapacks_outside_saves("/home/steve/projects/datapacks/my_pack", None)]
+    #[case::unrelated("/home/steve/projects/my_pack", None)]
+    fn test_detect(#[case] path: &str, #[case] expected: Option<InstallContext>) {
+        // Given a path which may be inside an installation
+        // When I detect its context
+        let context = InstallContext::detect(Path::new(path));
+
+        // Then the installation should only be recognized from its layout
+        assert_eq!(context, expected);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        // Given a path inside a world's datapacks
+        let context = InstallContext::detect(Path::new("/home/steve/.minecraft/saves/My World/datapacks/my_pack")).unwrap();
+
+        // Then it should suggest a data pack, exported to that world
+        assert_eq!(context.project_type(), ProjectType::DataPack);
+        assert_eq!(context.export_dir(), PathBuf::from("/home/steve/.minecraft/saves/My World/datapacks"));
+        assert_eq!(context.minecraft_dir(), Path::new("/home/steve/.minecraft"));
+    }
+
+    #[test]
+    fn test_default_minecraft_dir_is_recognized() {
+        // Given the default installation for this platform
+        let Some(minecraft_dir) = default_minecraft_dir() else {
+            return;
+        };
+
+        // When I detect the context of its resource packs folder
+        let context = InstallContext::detect(&minecraft_dir.join(RESOURCEPACKS_DIR));
+
+        // Then it should be recognized as that installation
+        assert_eq!(context, Some(InstallContext::ResourcePacks { minecraft_dir }));
+    }
+}
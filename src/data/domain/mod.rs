@@ -2,3 +2,15 @@ pub mod project;
 pub mod resource;
 pub mod pack_info;
 pub mod versions;
+pub mod compatibility;
+pub mod call_graph;
+pub mod project_settings_file;
+pub mod install_context;
+pub mod project_index;
+pub mod export_ignore;
+pub mod search;
+pub mod structure;
+pub mod vanilla_registry;
+pub mod completion;
+pub mod pack_path;
+pub mod project_template;pub mod pack_icon;
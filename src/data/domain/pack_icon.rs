@@ -0,0 +1,151 @@
+use std::fmt::{Display, Formatter};
+
+/// Every PNG file starts with these bytes
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// The signature, then the `IHDR` chunk's length and type, then its width and height as big-endian integers
+const IHDR_TYPE_RANGE: std::ops::Range<usize> = 12..16;
+const WIDTH_RANGE: std::ops::Range<usize> = 16..20;
+const HEIGHT_RANGE: std::ops::Range<usize> = 20..24;
+
+/// A pack's `pack.png`, shown beside the pack in the game's pack list
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct PackIcon(Vec<u8>);
+
+impl PackIcon {
+    /// Refuses anything which isn't a PNG. Dimensions the game scales badly are allowed, see [`PackIcon::warnings`]
+    pub fn new(data: Vec<u8>) -> Result<Self, PackIconError> {
+        png_dimensions(&data)?;
+        Ok(Self(data))
+    }
+
+    /// An icon as it was found in a pack, which is kept byte for byte even if the game couldn't show it
+    pub fn unchecked(data: Vec<u8>) -> Self {
+        Self(data)
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Width and height in pixels, or `None` if the data isn't a PNG
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        png_dimensions(&self.0).ok()
+    }
+
+    /// Ways the icon differs from the square, power of two size the game expects
+    pub fn warnings(&self) -> Vec<PackIconWarning> {
+        let Some((width, height)) = self.dimensions() else {
+            return vec![PackIconWarning::Unreadable];
+        };
+
+        let mut warnings = Vec::new();
+        if width != height {
+            warnings.push(PackIconWarning::NotSquare { width, height });
+        }
+        if !width.is_power_of_two() || !height.is_power_of_two() {
+            warnings.push(PackIconWarning::NotPowerOfTwo { width, height });
+        }
+        warnings
+    }
+}
+
+/// Reads the dimensions from the header every PNG starts with, without decoding the image
+fn png_dimensions(data: &[u8]) -> Result<(u32, u32), PackIconError> {
+    if data.len() < HEIGHT_RANGE.end || data[..PNG_SIGNATURE.len()] != PNG_SIGNATURE || &data[IHDR_TYPE_RANGE] != b"IHDR" {
+        return Err(PackIconError::NotPng);
+    }
+
+    let read_u32 = |range: std::ops::Range<usize>| u32::from_be_bytes(data[range].try_into().expect("Range should be four bytes"));
+    let (width, height) = (read_u32(WIDTH_RANGE), read_u32(HEIGHT_RANGE));
+
+    if width == 0 || height == 0 {
+        return Err(PackIconError::NotPng);
+    }
+
+    Ok((width, height))
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PackIconWarning {
+    /// Not a PNG, which is only kept from an imported pack, since [`PackIcon::new`] refuses these
+    Unreadable,
+    NotSquare { width: u32, height: u32 },
+    NotPowerOfTwo { width: u32, height: u32 },
+}
+
+impl Display for PackIconWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackIconWarning::Unreadable => write!(f, "The pack icon is not a PNG, so the game won't show it"),
+            PackIconWarning::NotSquare { width, height } => write!(f, "The pack icon is {}x{}, so the game will stretch it to a square", width, height),
+            PackIconWarning::NotPowerOfTwo { width, height } => write!(f, "The pack icon is {}x{}, which may look blurry, as the game expects a power of two such as 64x64", width, height),
+        }
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum PackIconError {
+    #[error("The pack icon must be a PNG image!")]
+    NotPng,
+}
+
+#[cfg(test)]
+pub(crate) fn test_png(width: u32, height: u32) -> Vec<u8> {
+    let mut data = PNG_SIGNATURE.to_vec();
+    data.extend(13u32.to_be_bytes());
+    data.extend(b"IHDR");
+    data.extend(width.to_be_bytes());
+    data.extend(height.to_be_bytes());
+    // Bit depth, colour type, compression, filter and interlacing, then a placeholder checksum
+    data.extend([8, 6, 0, 0, 0, 0, 0, 0, 0]);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case::standard(test_png(64, 64), vec![])]
+    #[case::large_standard(test_png(512, 512), vec![])]
+    #[case::not_square(test_png(64, 32), vec![PackIconWarning::NotSquare { width: 64, height: 32 }])]
+    #[case::odd_dimensions(test_png(100, 100), vec![PackIconWarning::NotPowerOfTwo { width: 100, height: 100 }])]
+    #[case::odd_rectangle(test_png(100, 64), vec![
+        PackIconWarning::NotSquare { width: 100, height: 64 },
+        PackIconWarning::NotPowerOfTwo { width: 100, height: 64 },
+    ])]
+    fn test_valid_icon(#[case] data: Vec<u8>, #[case] expected: Vec<PackIconWarning>) {
+        // Given a PNG
+        // When I use it as a pack icon
+        let icon = PackIcon::new(data).unwrap();
+
+        // Then it should be accepted, with a warning for any size the game doesn't expect
+        assert_eq!(icon.warnings(), expected);
+    }
+
+    #[rstest]
+    #[case::empty(vec![])]
+    #[case::jpeg(vec![0xff, 0xd8, 0xff, 0xe0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00, 0x01, 0x01, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00])]
+    #[case::truncated(test_png(64, 64)[..20].to_vec())]
+    #[case::zero_size(test_png(0, 64))]
+    fn test_invalid_icon(#[case] data: Vec<u8>) {
+        // Given data which isn't a usable PNG
+        // When I use it as a pack icon
+        let result = PackIcon::new(data);
+
+        // Then it should be refused
+        assert!(matches!(result, Err(PackIconError::NotPng)));
+    }
+
+    #[test]
+    fn test_unchecked_icon() {
+        // Given an icon from an imported pack which isn't a PNG
+        let icon = PackIcon::unchecked(b"not an image".to_vec());
+
+        // When I check it
+        // Then it should be kept, but warned about
+        assert_eq!(icon.data(), b"not an image");
+        assert_eq!(icon.warnings(), vec![PackIconWarning::Unreadable]);
+    }
+}
@@ -11,6 +11,12 @@ pub struct PackInfo {
     datapack_info: Option<DatapackInfo>,
 }
 
+impl PackInfo {
+    pub fn set_description(&mut self, description: PackDescription) {
+        self.description = description;
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, translation_macro::TranslationKey)]
 pub enum PackInfoTranslationKeys {
     #[translation(en_us = "A Resource Pack for Minecraft")]
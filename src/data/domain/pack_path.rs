@@ -0,0 +1,183 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Component, Path, PathBuf};
+
+/// A path inside a pack, relative to its root, e.g. `data/example/function/load.mcfunction`.
+///
+/// Always written with `/` between segments, as in zip entries and resource locations, whatever the platform,
+/// so it can't be mixed up with an OS path. Paths with uppercase letters are refused, since the game doesn't load them
+/// on every platform; packs containing them are reported by the lint and fixed by the casing fix instead
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PackRelativePath(String);
+
+impl PackRelativePath {
+    /// Normalizes a path written with either separator, dropping `.` and empty segments.
+    /// Paths which could leave the pack, with `..`, a root or a drive, are refused, as are ones with uppercase letters
+    pub fn new(path: &str) -> Result<Self, PackPathError> {
+        let mut segments = Vec::new();
+
+        for segment in path.split(['/', '\\']) {
+            match segment {
+                "" | "." => {},
+                ".." => return Err(PackPathError::OutsidePack(path.to_string())),
+                // Drive letters such as `C:` are not recognised as prefixes on non-Windows platforms
+                segment if segments.is_empty() && segment.contains(':') => return Err(PackPathError::OutsidePack(path.to_string())),
+                segment => segments.push(segment),
+            }
+        }
+
+        if segments.is_empty() || path.starts_with(['/', '\\']) {
+            return Err(PackPathError::OutsidePack(path.to_string()));
+        }
+
+        if path.chars().any(char::is_uppercase) {
+            return Err(PackPathError::NotLowercase(path.to_string()));
+        }
+
+        Ok(Self(segments.join("/")))
+    }
+
+    /// The path of an OS file below the pack's root directory
+    pub fn from_os_path(root: &Path, path: &Path) -> Result<Self, PackPathError> {
+        let relative_path = path.strip_prefix(root).map_err(|_| PackPathError::NotInRoot(path.to_path_buf()))?;
+        Self::try_from(relative_path)
+    }
+
+    /// Where the file lives below the pack's root directory, using the platform's separator
+    pub fn to_os_path(&self, root: &Path) -> PathBuf {
+        self.segments().fold(root.to_path_buf(), |path, segment| path.join(segment))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The `/`-separated path, e.g. for a zip entry name. Unlike [`PackRelativePath::to_os_path`], never uses `\`
+    pub fn as_path(&self) -> &Path {
+        Path::new(&self.0)
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split('/')
+    }
+}
+
+impl TryFrom<&str> for PackRelativePath {
+    type Error = PackPathError;
+
+    fn try_from(path: &str) -> Result<Self, Self::Error> {
+        Self::new(path)
+    }
+}
+
+/// Like [`PackRelativePath::new`], for a path which is already relative to the pack root, such as a project's file keys
+impl TryFrom<&Path> for PackRelativePath {
+    type Error = PackPathError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        let segments = path.components()
+            .map(|component| match component {
+                Component::Normal(segment) => segment.to_str().ok_or_else(|| PackPathError::NotUnicode(path.to_path_buf())),
+                _ => Err(PackPathError::OutsidePack(path.display().to_string())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::new(&segments.join("/"))
+    }
+}
+
+impl Display for PackRelativePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum PackPathError {
+    #[error("{0:?} leads outside of the pack!")]
+    OutsidePack(String),
+    #[error("{0:?} is not inside the pack's root directory!")]
+    NotInRoot(PathBuf),
+    #[error("{0:?} is not valid unicode!")]
+    NotUnicode(PathBuf),
+    #[error("{0:?} has uppercase letters, which the game can't load on every platform!")]
+    NotLowercase(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case::already_normal("data/example/function/load.mcfunction", "data/example/function/load.mcfunction")]
+    #[case::windows_separators("data\\example\\function\\load.mcfunction", "data/example/function/load.mcfunction")]
+    #[case::mixed_separators("data\\example/function\\load.mcfunction", "data/example/function/load.mcfunction")]
+    #[case::current_and_empty_segments("./data//example/./pack.png", "data/example/pack.png")]
+    #[case::trailing_separator("data/example/", "data/example")]
+    fn test_normalize(#[case] path: &str, #[case] expected: &str) {
+        // Given a pack path written in any of the ways a platform or archive might write it
+        // When I normalize it
+        let path = PackRelativePath::new(path).unwrap();
+
+        // Then it should be `/`-separated without any redundant segments
+        assert_eq!(path.as_str(), expected);
+    }
+
+    #[rstest]
+    #[case::parent("data/../../secret.txt")]
+    #[case::windows_parent("data\\..\\..\\secret.txt")]
+    #[case::absolute("/data/example/pack.png")]
+    #[case::windows_absolute("\\data\\example\\pack.png")]
+    #[case::drive("C:\\data\\example\\pack.png")]
+    #[case::empty("./")]
+    fn test_refuse_paths_outside_pack(#[case] path: &str) {
+        // Given a path which doesn't point at a file inside the pack
+        // When I make a pack path of it
+        let result = PackRelativePath::new(path);
+
+        // Then it should be refused
+        assert!(matches!(result, Err(PackPathError::OutsidePack(_))), "{:?} was accepted", path);
+    }
+
+    #[test]
+    fn test_os_path_round_trip() {
+        // Given a file below a pack's root directory
+        let root = Path::new("packs").join("gems");
+        let file = root.join("data").join("example").join("recipe").join("gem.json");
+
+        // When I convert it to a pack path and back
+        let path = PackRelativePath::from_os_path(&root, &file).unwrap();
+
+        // Then the pack path should be `/`-separated, and convert back to the same file
+        assert_eq!(path.as_str(), "data/example/recipe/gem.json");
+        assert_eq!(path.to_os_path(&root), file);
+        assert_eq!(path.as_path().to_str(), Some("data/example/recipe/gem.json"));
+    }
+
+    #[rstest]
+    #[case::namespace("data/Example/recipe/gem.json")]
+    #[case::file_name("data\\example\\recipe\\Gem.json")]
+    fn test_refuse_uppercase(#[case] path: &str) {
+        // Given a path with uppercase letters in it
+        // When I make a pack path of it, from a string or a relative path
+        let from_str = PackRelativePath::try_from(path);
+        let from_path = PackRelativePath::try_from(Path::new(&path.replace('\\', std::path::MAIN_SEPARATOR_STR)));
+
+        // Then both should be refused
+        assert!(matches!(from_str, Err(PackPathError::NotLowercase(_))), "{:?} was accepted", path);
+        assert!(matches!(from_path, Err(PackPathError::NotLowercase(_))), "{:?} was accepted", path);
+    }
+
+    #[test]
+    fn test_os_path_outside_root() {
+        // Given a file which isn't below the pack's root directory
+        let root = Path::new("packs").join("gems");
+        let file = Path::new("packs").join("other").join("pack.mcmeta");
+
+        // When I convert it to a pack path
+        let result = PackRelativePath::from_os_path(&root, &file);
+
+        // Then it should be refused
+        assert!(matches!(result, Err(PackPathError::NotInRoot(_))));
+    }
+}
@@ -1,8 +1,18 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 use mc_version::{MinecraftVersion, PackFormat};
 use uuid::{NoContext, Timestamp, Uuid};
+use crate::data::domain::compatibility::{self, FeatureVersion, RequiredVersion};
+use crate::data::domain::export_ignore::{ExportIgnore, EXPORT_IGNORE_FILE};
+use crate::data::domain::pack_icon::PackIcon;
 use crate::data::domain::pack_info::{PackDescription, PackInfo};
+use crate::data::domain::project_settings_file::ProjectSettingsFile;
+use crate::data::domain::resource::resource::ResourceLocation;
+use crate::data::domain::structure::Structure;
 use crate::data::domain::versions;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, getset::Getters)]
@@ -16,8 +26,26 @@ pub struct Project {
 
     pack_info: PackInfoProjectData,
 
+    /// File contents keyed by their path relative to the pack root, e.g. `data/<namespace>/function/load.mcfunction`.
+    /// Combined projects keep the files of both packs here, split by their `data` or `assets` root
+    files: BTreeMap<PathBuf, String>,
+    /// Structure files, which are binary and so kept apart from the text files above
+    structures: BTreeMap<PathBuf, Structure>,
+    /// The pack's `pack.png`, which is binary like structures. Combined projects share one icon between both packs
+    pack_icon: Option<PackIcon>,
+    /// Every other file which isn't text, such as textures, sounds and fonts. They can't be edited, but are kept to be saved and exported
+    binary_files: BTreeMap<PathBuf, Vec<u8>>,
+    /// Files which started with a UTF-8 byte order mark when loaded. It's stripped from their contents for editing,
+    /// and written back on save and export so the files stay byte for byte the same
+    bom_files: BTreeSet<PathBuf>,
+
+    /// Editor state kept beside the project, see [`ProjectSettingsFile`]
+    settings_file: ProjectSettingsFile,
+
     // TODO: make this more comprehensive
     has_unsaved_changes: bool,
+    /// Opened for inspection only, e.g. a vanilla or reference pack, so it must not be edited or saved
+    read_only: bool,
 }
 
 impl Project {
@@ -29,7 +57,14 @@ impl Project {
             path: None,
             project_version,
             pack_info,
+            files: BTreeMap::new(),
+            structures: BTreeMap::new(),
+            bom_files: BTreeSet::new(),
+            pack_icon: None,
+            binary_files: BTreeMap::new(),
+            settings_file: ProjectSettingsFile::default(),
             has_unsaved_changes: false,
+            read_only: false,
         }
     }
 
@@ -41,14 +76,28 @@ impl Project {
                 Self {
                     name, id, path, project_version,
                     pack_info: PackInfoProjectData::Data(PackInfo::new(description, None)),
+                    files: BTreeMap::new(),
+                    structures: BTreeMap::new(),
+                    bom_files: BTreeSet::new(),
+                    pack_icon: None,
+                    binary_files: BTreeMap::new(),
+                    settings_file: ProjectSettingsFile::default(),
                     has_unsaved_changes: false,
+                    read_only: false,
                 }
             }
             ProjectSettings::ResourcePack { name, description, path, project_version } => {
                 Self {
                     name, id, path, project_version,
                     pack_info: PackInfoProjectData::Resource(PackInfo::new(description, None)),
+                    files: BTreeMap::new(),
+                    structures: BTreeMap::new(),
+                    bom_files: BTreeSet::new(),
+                    pack_icon: None,
+                    binary_files: BTreeMap::new(),
+                    settings_file: ProjectSettingsFile::default(),
                     has_unsaved_changes: false,
+                    read_only: false,
                 }
             }
             ProjectSettings::Combined { name, data_description, resource_description, path, project_version } => {
@@ -60,12 +109,385 @@ impl Project {
                         resource_info: PackInfo::new(resource_description, None),
                     },
 
+                    files: BTreeMap::new(),
+                    structures: BTreeMap::new(),
+                    bom_files: BTreeSet::new(),
+                    pack_icon: None,
+                    binary_files: BTreeMap::new(),
+                    settings_file: ProjectSettingsFile::default(),
                     has_unsaved_changes: false,
+                    read_only: false,
                 }
             }
         }
     }
 
+    /// Moves the project to a new location, which counts as an unsaved change until it is written there
+    pub fn set_path(&mut self, path: Option<PathBuf>) {
+        self.path = path;
+        self.flag_unsaved_changes();
+    }
+
+    /// Sets where an opened project lives, which unlike [`Project::set_path`] is not a change to save
+    pub fn with_path(self, path: PathBuf) -> Self {
+        Self {
+            path: Some(path),
+            ..self
+        }
+    }
+
+    pub fn with_files(self, files: impl IntoIterator<Item = (PathBuf, String)>) -> Self {
+        Self {
+            files: files.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// A data pack for the latest version holding the given files, as `(path, contents)` pairs
+    #[cfg(test)]
+    pub fn test_with_files(files: &[(&str, &str)]) -> Self {
+        Self::from_settings(ProjectSettings::DataPack {
+            name: "Test Project".to_string(),
+            description: PackDescription::String("Test Description".to_string()),
+            path: None,
+            project_version: ProjectVersion { version: versions::latest() },
+        }).with_files(files.iter().map(|(path, contents)| (PathBuf::from(path), contents.to_string())))
+    }
+
+    pub fn with_structures(self, structures: impl IntoIterator<Item = (PathBuf, Structure)>) -> Self {
+        Self {
+            structures: structures.into_iter().collect(),
+            ..self
+        }
+    }
+
+    pub fn with_pack_icon(self, pack_icon: Option<PackIcon>) -> Self {
+        Self {
+            pack_icon,
+            ..self
+        }
+    }
+
+    pub fn with_binary_files(self, binary_files: impl IntoIterator<Item = (PathBuf, Vec<u8>)>) -> Self {
+        Self {
+            binary_files: binary_files.into_iter().collect(),
+            ..self
+        }
+    }
+
+    pub fn with_bom_files(self, bom_files: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            bom_files: bom_files.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// What to leave out of exports, read from the project's [`EXPORT_IGNORE_FILE`] if it has one
+    pub fn export_ignore(&self) -> ExportIgnore {
+        ExportIgnore::parse(self.files.get(Path::new(EXPORT_IGNORE_FILE)).map_or("", String::as_str))
+    }
+
+    pub fn has_bom(&self, path: &Path) -> bool {
+        self.bom_files.contains(path)
+    }
+
+    /// Adds or replaces a file, given its path relative to the pack root
+    pub fn set_file(&mut self, path: PathBuf, contents: String) {
+        self.files.insert(path, contents);
+        self.flag_unsaved_changes();
+    }
+
+    pub fn remove_file(&mut self, path: &Path) -> Option<String> {
+        let removed = self.files.remove(path);
+        if removed.is_some() {
+            self.flag_unsaved_changes();
+        }
+        removed
+    }
+
+    /// Takes in a file which was written straight to the project's folder, e.g. by the editor, along with whether it was
+    /// written with a byte order mark. It already matches what's saved, so unlike [`Project::set_file`] it isn't an unsaved change
+    pub fn set_saved_file(&mut self, path: PathBuf, contents: String, bom: bool) {
+        if bom {
+            self.bom_files.insert(path.clone());
+        } else {
+            self.bom_files.remove(&path);
+        }
+
+        self.files.insert(path, contents);
+    }
+
+    /// Moves a file of any kind to another path. References to it are left as they are, see [`Project::rewrite_references`]
+    pub fn move_file(&mut self, from: &Path, to: &Path) -> Result<(), MoveFileError> {
+        let exists = |path: &Path| self.files.contains_key(path) || self.structures.contains_key(path) || self.binary_files.contains_key(path);
+        if !exists(from) {
+            return Err(MoveFileError::NotFound(from.to_path_buf()));
+        }
+        if exists(to) {
+            return Err(MoveFileError::Collision(to.to_path_buf()));
+        }
+
+        if let Some(contents) = self.files.remove(from) {
+            self.files.insert(to.to_path_buf(), contents);
+        } else if let Some(structure) = self.structures.remove(from) {
+            self.structures.insert(to.to_path_buf(), structure);
+        } else if let Some(data) = self.binary_files.remove(from) {
+            self.binary_files.insert(to.to_path_buf(), data);
+        }
+
+        if self.bom_files.remove(from) {
+            self.bom_files.insert(to.to_path_buf());
+        }
+
+        self.flag_unsaved_changes();
+        Ok(())
+    }
+
+    /// Lazily iterates over every file in the project, ordered by path.
+    /// This is the shared traversal for tooling such as linting, search, and export
+    pub fn iter_files(&self) -> impl Iterator<Item = ProjectFile<'_>> {
+        self.files.iter()
+            .map(|(path, contents)| ProjectFile { path, contents })
+    }
+
+    /// All namespaces with at least one file under `data/` or `assets/`
+    pub fn namespaces(&self) -> BTreeSet<&str> {
+        self.iter_files()
+            .filter_map(|file| file.namespace())
+            .collect()
+    }
+
+    /// Figures about the project as it is now. The required version counts the features adapters declare, see [`compatibility::required_version`].
+    /// Files in overlays don't count towards it, since they're only loaded by the versions their overlay is active in
+    pub fn stats(&self, adapter_versions: &[FeatureVersion]) -> ProjectStats {
+        ProjectStats {
+            files: self.files.len() + self.structures.len() + self.binary_files.len(),
+            namespaces: self.namespaces().len(),
+            required_version: compatibility::required_version(self.iter_files().map(|file| (file.path, file.contents)), adapter_versions),
+        }
+    }
+
+    /// Moves every file under `data/<from>/` and `assets/<from>/` to the `to` namespace,
+    /// and rewrites references to resources in the old namespace. Returns the number of files which were moved
+    pub fn rename_namespace(&mut self, from: &str, to: &str) -> Result<usize, NamespaceRenameError> {
+        for namespace in [from, to] {
+            if namespace == VANILLA_NAMESPACE {
+                return Err(NamespaceRenameError::Vanilla);
+            }
+            if !is_valid_namespace(namespace) {
+                return Err(NamespaceRenameError::InvalidNamespace(namespace.to_string()));
+            }
+        }
+
+        let namespaces = self.namespaces();
+        if !namespaces.contains(from) {
+            return Err(NamespaceRenameError::NamespaceNotFound(from.to_string()));
+        }
+        if namespaces.contains(to) {
+            return Err(NamespaceRenameError::NamespaceAlreadyExists(to.to_string()));
+        }
+
+        let mut moved = 0;
+        self.files = std::mem::take(&mut self.files).into_iter()
+            .map(|(path, contents)| match namespace_of(&path) {
+                Some(namespace) if namespace == from => {
+                    moved += 1;
+                    (with_namespace(&path, to), contents)
+                }
+                _ => (path, contents),
+            })
+            .collect();
+
+        self.rewrite_references(|reference| {
+            let location = ResourceLocation::from_str(reference).ok()?;
+            (location.namespace() == from).then(|| format!("{}:{}", to, location.value()))
+        });
+
+        self.flag_unsaved_changes();
+        Ok(moved)
+    }
+
+    /// Replaces references to resources across every file. Each location written with its namespace, e.g. `example:gem`,
+    /// is passed to `rewrite`, and replaced wherever it gives a new one. Locations without a namespace are left alone,
+    /// as they can't be told apart from other text
+    pub fn rewrite_references(&mut self, rewrite: impl Fn(&str) -> Option<String>) -> ReferenceRewrite {
+        let mut summary = ReferenceRewrite::default();
+
+        for contents in self.files.values_mut() {
+            let mut rewritten = String::with_capacity(contents.len());
+            let mut last_end = 0;
+            let mut references_rewritten = 0;
+
+            for span in reference_spans(contents) {
+                let reference = &contents[span.clone()];
+                let Some(replacement) = rewrite(reference).filter(|replacement| replacement != reference) else {
+                    continue;
+                };
+
+                rewritten.push_str(&contents[last_end..span.start]);
+                rewritten.push_str(&replacement);
+                last_end = span.end;
+                references_rewritten += 1;
+            }
+
+            if references_rewritten == 0 {
+                continue;
+            }
+
+            rewritten.push_str(&contents[last_end..]);
+            *contents = rewritten;
+
+            summary.files_changed += 1;
+            summary.references_rewritten += references_rewritten;
+        }
+
+        if summary.files_changed > 0 {
+            self.flag_unsaved_changes();
+        }
+        summary
+    }
+
+    /// Namespaced files whose paths have uppercase letters, which the game only loads on case-insensitive filesystems,
+    /// each paired with the lowercase path it should have
+    pub fn casing_fixes(&self) -> Vec<CasingFix> {
+        self.iter_files()
+            .filter(|file| file.namespace().is_some() && file.path.to_str().is_some_and(|path| path.chars().any(char::is_uppercase)))
+            .filter_map(|file| {
+                let to = PathBuf::from(file.path.to_str()?.to_lowercase());
+                Some(CasingFix { from: file.path.to_path_buf(), to })
+            })
+            .collect()
+    }
+
+    /// Lowercases the paths of every file found by [`Project::casing_fixes`], and rewrites references to them.
+    /// Nothing is changed if any of the lowercase paths would collide with another file
+    pub fn fix_casing(&mut self) -> Result<Vec<CasingFix>, CasingFixError> {
+        let fixes = self.casing_fixes();
+
+        let mut targets = BTreeSet::new();
+        for fix in &fixes {
+            let collides_with_existing = self.files.contains_key(&fix.to) && !fixes.iter().any(|other| other.from == fix.to);
+
+            if collides_with_existing || !targets.insert(&fix.to) {
+                return Err(CasingFixError::Collision(fix.to.clone()));
+            }
+        }
+
+        if fixes.is_empty() {
+            return Ok(fixes);
+        }
+
+        // References are written the same way as the path, e.g. `MyPack:Util/Setup` for `data/MyPack/function/Util/Setup.mcfunction`
+        let references: HashMap<_, _> = fixes.iter()
+            .filter_map(|fix| {
+                let file = ProjectFile { path: &fix.from, contents: "" };
+                let (_, value) = file.split_resource_path()?;
+                let reference = format!("{}:{}", file.namespace()?, value);
+                let lowercase = reference.to_lowercase();
+
+                Some((reference, lowercase))
+            })
+            .collect();
+
+        self.files = std::mem::take(&mut self.files).into_iter()
+            .map(|(path, contents)| {
+                let path = fixes.iter()
+                    .find(|fix| fix.from == path)
+                    .map_or(path, |fix| fix.to.clone());
+
+                (path, contents)
+            })
+            .collect();
+
+        self.rewrite_references(|reference| references.get(reference).cloned());
+        self.flag_unsaved_changes();
+        Ok(fixes)
+    }
+
+    /// Data pack files whose extension doesn't match their resource kind, such as a `.txt` under `function/`,
+    /// each paired with the path it should have
+    pub fn extension_fixes(&self) -> Vec<ExtensionFix> {
+        self.iter_files()
+            .filter_map(|file| {
+                let expected = file.expected_extension()?;
+                (file.path.extension() != Some(expected.as_ref()))
+                    .then(|| ExtensionFix { from: file.path.to_path_buf(), to: file.path.with_extension(expected) })
+            })
+            .collect()
+    }
+
+    /// Renames every file found by [`Project::extension_fixes`]. The extension isn't part of a resource location,
+    /// so references to the files stay as they are. Nothing is changed if any of the new paths would collide with another file
+    pub fn fix_extensions(&mut self) -> Result<Vec<ExtensionFix>, ExtensionFixError> {
+        let fixes = self.extension_fixes();
+
+        let mut targets = BTreeSet::new();
+        for fix in &fixes {
+            if self.files.contains_key(&fix.to) || !targets.insert(&fix.to) {
+                return Err(ExtensionFixError::Collision(fix.to.clone()));
+            }
+        }
+
+        if fixes.is_empty() {
+            return Ok(fixes);
+        }
+
+        for fix in &fixes {
+            if let Some(contents) = self.files.remove(&fix.from) {
+                self.files.insert(fix.to.clone(), contents);
+            }
+        }
+
+        self.flag_unsaved_changes();
+        Ok(fixes)
+    }
+
+    /// The description in the given pack's `pack.mcmeta`, if the project has that pack
+    pub fn description(&self, pack: PackHalf) -> Option<&PackDescription> {
+        self.pack_info_for(pack).map(PackInfo::description)
+    }
+
+    /// Replaces the description of one pack. Combined projects keep the other pack's description as it was
+    pub fn set_description(&mut self, pack: PackHalf, description: PackDescription) -> Result<(), DescriptionError> {
+        let project_type = self.project_type();
+        let pack_info = match (&mut self.pack_info, pack) {
+            (PackInfoProjectData::Data(info), PackHalf::Data) => info,
+            (PackInfoProjectData::Resource(info), PackHalf::Resource) => info,
+            (PackInfoProjectData::Combined { data_info, .. }, PackHalf::Data) => data_info,
+            (PackInfoProjectData::Combined { resource_info, .. }, PackHalf::Resource) => resource_info,
+            _ => return Err(DescriptionError::NoSuchPack(project_type, pack)),
+        };
+
+        pack_info.set_description(description);
+        self.flag_unsaved_changes();
+        Ok(())
+    }
+
+    /// Replaces the pack's icon, written as `pack.png` on save and export
+    pub fn set_pack_icon(&mut self, pack_icon: PackIcon) {
+        self.pack_icon = Some(pack_icon);
+        self.flag_unsaved_changes();
+    }
+
+    fn pack_info_for(&self, pack: PackHalf) -> Option<&PackInfo> {
+        match (&self.pack_info, pack) {
+            (PackInfoProjectData::Data(info), PackHalf::Data) => Some(info),
+            (PackInfoProjectData::Resource(info), PackHalf::Resource) => Some(info),
+            (PackInfoProjectData::Combined { data_info, .. }, PackHalf::Data) => Some(data_info),
+            (PackInfoProjectData::Combined { resource_info, .. }, PackHalf::Resource) => Some(resource_info),
+            _ => None,
+        }
+    }
+
+    /// Editor state isn't part of the pack, so changing it doesn't count as an unsaved change
+    pub fn set_settings_file(&mut self, settings_file: ProjectSettingsFile) {
+        self.settings_file = settings_file;
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
     pub fn flag_unsaved_changes(&mut self) {
         self.has_unsaved_changes = true;
     }
@@ -84,8 +506,7 @@ impl Project {
 
     fn generate_id() -> ProjectID {
         let timestamp = Timestamp::from_unix(NoContext, SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(), 0);
-        let id = Uuid::new_v7(timestamp);
-        id
+        ProjectID(Uuid::new_v7(timestamp))
     }
 }
 
@@ -143,6 +564,14 @@ impl ProjectSettings {
             Self::Combined { path, .. } => path,
         }.as_ref()
     }
+
+    pub fn project_version(&self) -> &ProjectVersion {
+        match self {
+            Self::DataPack { project_version, .. } => project_version,
+            Self::ResourcePack { project_version, .. } => project_version,
+            Self::Combined { project_version, .. } => project_version,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -189,7 +618,7 @@ impl From<&MinecraftVersion> for ProjectVersion {
 
 impl From<&PackFormat> for ProjectVersion {
     fn from(value: &PackFormat) -> Self {
-        let version = value.get_versions().read().unwrap().iter().next().unwrap().clone();
+        let version = value.min_version().unwrap();
 
         Self {
             version
@@ -197,7 +626,300 @@ impl From<&PackFormat> for ProjectVersion {
     }
 }
 
-pub type ProjectID = Uuid;
+/// Identifies a project while it's open. Formatted as a lowercase, hyphenated UUID, the same form it's serialized in,
+/// so it can be written to logs or saved state and parsed back to the same id
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct ProjectID(Uuid);
+
+impl ProjectID {
+    pub const fn nil() -> Self {
+        Self(Uuid::nil())
+    }
+}
+
+impl Display for ProjectID {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.hyphenated())
+    }
+}
+
+impl FromStr for ProjectID {
+    type Err = ProjectIDParseError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Uuid::try_parse(id)
+            .map(Self)
+            .map_err(|error| ProjectIDParseError { id: id.to_string(), reason: error.to_string() })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+#[error("{id:?} is not a project id! {reason}")]
+pub struct ProjectIDParseError {
+    id: String,
+    reason: String,
+}
+
+/// A file in a project, borrowed from the project
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ProjectFile<'a> {
+    /// Path relative to the pack root
+    pub path: &'a Path,
+    pub contents: &'a str,
+}
+
+impl<'a> ProjectFile<'a> {
+    pub fn namespace(&self) -> Option<&'a str> {
+        namespace_of(self.path)
+    }
+
+    /// The kind of resource this file defines, e.g. `function`, `tags/item` or `textures`
+    pub fn resource_kind(&self) -> Option<String> {
+        self.split_resource_path().map(|(kind, _)| kind)
+    }
+
+    /// The resource location this file defines, e.g. `data/example/function/util/setup.mcfunction`
+    /// defines `example:util/setup`. Files outside of a namespace don't define a resource
+    pub fn resource_location(&self) -> Option<ResourceLocation> {
+        let namespace = self.namespace()?;
+        let (_, value) = self.split_resource_path()?;
+
+        ResourceLocation::new(namespace, &value).ok()
+    }
+
+    /// The extension the game reads files of this file's resource kind with, e.g. `mcfunction` for functions.
+    /// Files with any other extension are silently skipped when the pack loads. Only known data pack kinds have one
+    pub fn expected_extension(&self) -> Option<&'static str> {
+        let (_, file) = self.split_origin();
+        if !file.path.starts_with("data") {
+            return None;
+        }
+
+        let kind = file.resource_kind()?;
+        let (group, _) = kind.split_once('/').unwrap_or((&kind, ""));
+
+        match group {
+            "function" | "functions" => Some("mcfunction"),
+            "tags" | "worldgen" => Some("json"),
+            kind if JSON_RESOURCE_KINDS.contains(&kind) => Some("json"),
+            _ => None,
+        }
+    }
+
+    /// Where the file sits in the pack, and the file as the game sees it once its overlay is applied.
+    /// Files in an overlay live under the overlay's directory, e.g. `v2/data/example/recipe/gem.json`
+    /// overrides `data/example/recipe/gem.json` whenever the `v2` overlay is active
+    pub fn split_origin(&self) -> (FileOrigin<'a>, ProjectFile<'a>) {
+        let mut components = self.path.components();
+
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(overlay)), Some(Component::Normal(root))) if root == "data" || root == "assets" => {
+                let Some(overlay) = overlay.to_str().filter(|overlay| *overlay != "data" && *overlay != "assets") else {
+                    return (FileOrigin::Base, *self);
+                };
+                let path = self.path.strip_prefix(overlay).expect("Overlay is the first component of the path");
+
+                (FileOrigin::Overlay(overlay), ProjectFile { path, contents: self.contents })
+            }
+            _ => (FileOrigin::Base, *self),
+        }
+    }
+
+    /// Splits the part of the path after the namespace into the resource kind and the resource's own path
+    fn split_resource_path(&self) -> Option<(String, String)> {
+        let segments: Vec<_> = self.path.iter()
+            .skip(2)
+            .map(|segment| segment.to_str())
+            .collect::<Option<_>>()?;
+
+        // Tags and worldgen resources are grouped by the registry they belong to
+        let kind_length = match segments.first() {
+            Some(&"tags") | Some(&"worldgen") => 2,
+            _ => 1,
+        };
+
+        if segments.len() <= kind_length {
+            return None;
+        }
+
+        let kind = segments[..kind_length].join("/");
+        let value = Path::new(&segments[kind_length..].join("/")).with_extension("");
+
+        Some((kind, value.to_str()?.to_string()))
+    }
+}
+
+/// See [`Project::stats`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectStats {
+    pub files: usize,
+    pub namespaces: usize,
+    /// The oldest version of the game able to load the project, if it uses anything newer than the oldest supported version
+    pub required_version: Option<RequiredVersion>,
+}
+
+/// The part of a pack a file belongs to, see [`ProjectFile::split_origin`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FileOrigin<'a> {
+    Base,
+    /// The directory of an overlay listed in the pack's `pack.mcmeta`
+    Overlay(&'a str),
+}
+
+impl Display for FileOrigin<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileOrigin::Base => write!(f, "the base pack"),
+            FileOrigin::Overlay(overlay) => write!(f, "overlay {}", overlay),
+        }
+    }
+}
+
+pub const VANILLA_NAMESPACE: &str = "minecraft";
+
+#[derive(Debug, thiserror::Error)]
+pub enum NamespaceRenameError {
+    #[error("The {VANILLA_NAMESPACE} namespace cannot be renamed, or renamed to!")]
+    Vanilla,
+    #[error("Invalid namespace: {0:?}!")]
+    InvalidNamespace(String),
+    #[error("Namespace {0:?} does not exist in this project!")]
+    NamespaceNotFound(String),
+    #[error("Namespace {0:?} already exists in this project!")]
+    NamespaceAlreadyExists(String),
+}
+
+fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty() && namespace.chars().all(is_namespace_char)
+}
+
+fn is_namespace_char(c: char) -> bool {
+    matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.')
+}
+
+/// Characters of a resource location as they may be written, including uppercase letters which the game refuses,
+/// so that such references can still be found to fix
+fn is_written_namespace_char(c: char) -> bool {
+    is_namespace_char(c) || c.is_ascii_uppercase()
+}
+
+fn is_written_path_char(c: char) -> bool {
+    is_written_namespace_char(c) || c == '/'
+}
+
+/// The namespace of a file at `data/<namespace>/...` or `assets/<namespace>/...`
+/// Data pack resource kinds which are read from JSON files, besides tags and worldgen which are grouped by registry.
+/// Older versions use the plural names
+const JSON_RESOURCE_KINDS: &[&str] = &[
+    "advancement", "advancements",
+    "recipe", "recipes",
+    "loot_table", "loot_tables",
+    "predicate", "predicates",
+    "item_modifier", "item_modifiers",
+    "banner_pattern", "chat_type", "damage_type", "dimension", "dimension_type", "enchantment", "enchantment_provider",
+    "instrument", "jukebox_song", "painting_variant", "trim_material", "trim_pattern", "wolf_variant",
+];
+
+fn namespace_of(path: &Path) -> Option<&str> {
+    let mut components = path.components();
+
+    match (components.next(), components.next(), components.next()) {
+        (Some(Component::Normal(root)), Some(Component::Normal(namespace)), Some(_)) if root == "data" || root == "assets" => {
+            namespace.to_str()
+        }
+        _ => None,
+    }
+}
+
+fn with_namespace(path: &Path, namespace: &str) -> PathBuf {
+    let mut components = path.components();
+    let root = components.next().expect("Namespaced paths have a root");
+    components.next();
+
+    PathBuf::from(root.as_os_str()).join(namespace).join(components.as_path())
+}
+
+/// Where each resource location written as `namespace:path` sits in some text. Each is as long as the characters
+/// around the colon allow, so `my_upstream:load` is never taken to contain `upstream:load`
+pub fn reference_spans(contents: &str) -> Vec<Range<usize>> {
+    let bytes = contents.as_bytes();
+    let mut spans = Vec::new();
+    let mut last_end = 0;
+
+    for (colon, _) in contents.match_indices(':') {
+        if colon < last_end {
+            continue;
+        }
+
+        // Namespace and path characters are all ASCII, so each is a single byte
+        let start = bytes[last_end..colon].iter()
+            .rposition(|&byte| !is_written_namespace_char(byte as char))
+            .map_or(last_end, |index| last_end + index + 1);
+        let end = bytes[colon + 1..].iter()
+            .position(|&byte| !is_written_path_char(byte as char))
+            .map_or(bytes.len(), |index| colon + 1 + index);
+
+        if start < colon && colon + 1 < end {
+            spans.push(start..end);
+            last_end = end;
+        }
+    }
+
+    spans
+}
+
+/// How much [`Project::rewrite_references`] changed
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ReferenceRewrite {
+    pub files_changed: usize,
+    pub references_rewritten: usize,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CasingFix {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MoveFileError {
+    #[error("No file at {0:?}!")]
+    NotFound(PathBuf),
+    #[error("Moving would overwrite {0:?}!")]
+    Collision(PathBuf),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CasingFixError {
+    #[error("Lowercasing would overwrite {0:?}!")]
+    Collision(PathBuf),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExtensionFix {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExtensionFixError {
+    #[error("Renaming would overwrite {0:?}!")]
+    Collision(PathBuf),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DescriptionError {
+    #[error("{0:?} project has no {1:?} pack to describe!")]
+    NoSuchPack(ProjectType, PackHalf),
+}
+
+/// One of the packs making up a project. Combined projects have both, each with its own `pack.mcmeta`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PackHalf {
+    Data,
+    Resource,
+}
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum ProjectType {
@@ -271,4 +993,298 @@ impl Project {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[test]
+    fn test_iter_files() {
+        // Given a small project with data, assets, and a file at the pack root
+        let project = Project::from_settings(ProjectSettings::Combined {
+            name: "Test Project".to_string(),
+            data_description: PackDescription::String("Data".to_string()),
+            resource_description: PackDescription::String("Resources".to_string()),
+            path: None,
+            project_version: ProjectVersion { version: versions::latest() },
+        }).with_files([
+            (PathBuf::from("data/example/function/util/setup.mcfunction"), "say setup".to_string()),
+            (PathBuf::from("data/example/tags/function/load.json"), "{}".to_string()),
+            (PathBuf::from("assets/example/textures/block/gem.png"), "".to_string()),
+            (PathBuf::from("pack.png"), "".to_string()),
+        ]);
+
+        // When I iterate over its files
+        let files: BTreeSet<_> = project.iter_files()
+            .map(|file| (file.path.to_path_buf(), file.resource_kind(), file.resource_location().map(|location| location.to_string())))
+            .collect();
+
+        // Then every file should be yielded with the resource it defines
+        assert_eq!(files, BTreeSet::from([
+            (PathBuf::from("assets/example/textures/block/gem.png"), Some("textures".to_string()), Some("example:block/gem".to_string())),
+            (PathBuf::from("data/example/function/util/setup.mcfunction"), Some("function".to_string()), Some("example:util/setup".to_string())),
+            (PathBuf::from("data/example/tags/function/load.json"), Some("tags/function".to_string()), Some("example:load".to_string())),
+            (PathBuf::from("pack.png"), None, None),
+        ]));
+    }
+
+    #[test]
+    fn test_set_combined_resource_description() {
+        // Given a combined project
+        let mut project = Project::from_settings(ProjectSettings::Combined {
+            name: "Test Project".to_string(),
+            data_description: PackDescription::String("Data".to_string()),
+            resource_description: PackDescription::String("Resources".to_string()),
+            path: None,
+            project_version: ProjectVersion { version: versions::latest() },
+        });
+
+        // When I change the resource pack's description
+        project.set_description(PackHalf::Resource, PackDescription::String("New Resources".to_string())).unwrap();
+
+        // Then only the resource pack info should change
+        assert_eq!(project.pack_info(), &PackInfoProjectData::Combined {
+            data_info: PackInfo::new(PackDescription::String("Data".to_string()), None),
+            resource_info: PackInfo::new(PackDescription::String("New Resources".to_string()), None),
+        });
+        assert_eq!(project.description(PackHalf::Data), Some(&PackDescription::String("Data".to_string())));
+        assert!(project.has_unsaved_changes);
+    }
+
+    #[test]
+    fn test_fix_casing() {
+        // Given a project with an uppercase namespace and function name, referenced from a tag
+        let mut project = Project::test_with_files(&[
+            ("data/MyPack/function/Util/Setup.mcfunction", "function MyPack:Util/Setup_more"),
+            ("data/MyPack/function/Util/Setup_more.mcfunction", "say more"),
+            ("data/minecraft/tags/function/load.json", r#"{"values": ["MyPack:Util/Setup"]}"#),
+        ]);
+
+        // When I look for casing problems
+        let fixes = project.casing_fixes();
+
+        // Then only the uppercase files should be found
+        assert_eq!(fixes.iter().map(|fix| fix.from.to_str().unwrap()).collect::<Vec<_>>(), vec![
+            "data/MyPack/function/Util/Setup.mcfunction",
+            "data/MyPack/function/Util/Setup_more.mcfunction",
+        ]);
+
+        // And when I fix them
+        project.fix_casing().unwrap();
+
+        // Then the files should be moved, and references to them rewritten without touching longer names
+        assert_eq!(project.files().keys().map(|path| path.to_str().unwrap()).collect::<Vec<_>>(), vec![
+            "data/minecraft/tags/function/load.json",
+            "data/mypack/function/util/setup.mcfunction",
+            "data/mypack/function/util/setup_more.mcfunction",
+        ]);
+        assert_eq!(project.files()[Path::new("data/minecraft/tags/function/load.json")], r#"{"values": ["mypack:util/setup"]}"#);
+        assert_eq!(project.files()[Path::new("data/mypack/function/util/setup.mcfunction")], "function mypack:util/setup_more");
+        assert!(project.has_unsaved_changes);
+    }
+
+    #[test]
+    fn test_fix_casing_collision() {
+        // Given a project where lowercasing one file would overwrite another
+        let mut project = Project::test_with_files(&[
+            ("data/test/function/Setup.mcfunction", "say upper"),
+            ("data/test/function/setup.mcfunction", "say lower"),
+        ]);
+
+        // When I fix the casing
+        let result = project.fix_casing();
+
+        // Then it should be refused without changing anything
+        assert!(matches!(result, Err(CasingFixError::Collision(path)) if path == Path::new("data/test/function/setup.mcfunction")));
+        assert_eq!(project.files().len(), 2);
+        assert!(!project.has_unsaved_changes);
+    }
+
+    #[rstest]
+    #[case::function_call("function example:util/setup", &["example:util/setup"])]
+    #[case::json_values(r##"{"values": ["example:load", "#minecraft:tick"]}"##, &["example:load", "minecraft:tick"])]
+    #[case::no_namespace("function setup", &[])]
+    #[case::scoreboard("scoreboard objectives add example_score dummy", &[])]
+    #[case::chained_colons("a:b:c", &["a:b"])]
+    #[case::empty_path("example: gem", &[])]
+    #[case::uppercase("function MyPack:Util/Setup", &["MyPack:Util/Setup"])]
+    fn test_reference_spans(#[case] contents: &str, #[case] expected: &[&str]) {
+        // Given some text
+        // When I find the references in it
+        let references: Vec<_> = reference_spans(contents).into_iter().map(|span| &contents[span]).collect();
+
+        // Then only locations written with their namespace should be found
+        assert_eq!(references, expected);
+    }
+
+    #[test]
+    fn test_rewrite_references() {
+        // Given a project referring to a few functions, some more than once
+        let mut project = Project::test_with_files(&[
+            ("data/example/function/load.mcfunction", "function example:setup\nfunction example:tick\nfunction example:setup"),
+            ("data/example/function/tick.mcfunction", "say tick"),
+            ("data/minecraft/tags/function/load.json", r#"{"values": ["example:load"]}"#),
+        ]);
+
+        // When I rewrite only the references to one of them
+        let summary = project.rewrite_references(|reference| (reference == "example:setup").then(|| "example:init".to_string()));
+
+        // Then only those references should change
+        assert_eq!(summary, ReferenceRewrite { files_changed: 1, references_rewritten: 2 });
+        assert_eq!(project.files()[Path::new("data/example/function/load.mcfunction")], "function example:init\nfunction example:tick\nfunction example:init");
+        assert_eq!(project.files()[Path::new("data/minecraft/tags/function/load.json")], r#"{"values": ["example:load"]}"#);
+        assert!(project.has_unsaved_changes);
+    }
+
+    #[test]
+    fn test_rewrite_references_unchanged() {
+        // Given a project
+        let mut project = Project::test_with_files(&[("data/example/function/load.mcfunction", "function example:setup")]);
+
+        // When a rewrite gives every reference the location it already has
+        let summary = project.rewrite_references(|reference| Some(reference.to_string()));
+
+        // Then nothing should count as changed
+        assert_eq!(summary, ReferenceRewrite::default());
+        assert!(!project.has_unsaved_changes);
+    }
+
+    #[test]
+    fn test_fix_extensions() {
+        // Given a function saved as text, a function in a recipe folder, and files which are fine
+        let mut project = Project::test_with_files(&[
+            ("data/test/function/setup.txt", "say hi"),
+            ("data/test/recipe/gem.mcfunction", r#"{"type": "minecraft:crafting_shapeless"}"#),
+            ("data/test/function/load.mcfunction", "function test:setup"),
+            ("data/test/tags/item/gems.json", r#"{"values": []}"#),
+            ("data/test/readme.txt", "Not a resource"),
+            ("assets/test/textures/item/gem.txt", "Not a data pack file"),
+        ]);
+
+        // When I look for extension problems
+        let fixes = project.extension_fixes();
+
+        // Then only the mismatched resources should be found, paired with the extension their kind uses
+        assert_eq!(fixes, vec![
+            ExtensionFix { from: PathBuf::from("data/test/function/setup.txt"), to: PathBuf::from("data/test/function/setup.mcfunction") },
+            ExtensionFix { from: PathBuf::from("data/test/recipe/gem.mcfunction"), to: PathBuf::from("data/test/recipe/gem.json") },
+        ]);
+
+        // And when I fix them
+        project.fix_extensions().unwrap();
+
+        // Then the files should be renamed, keeping their contents
+        assert_eq!(project.files()[Path::new("data/test/function/setup.mcfunction")], "say hi");
+        assert!(project.files().contains_key(Path::new("data/test/recipe/gem.json")));
+        assert!(!project.files().contains_key(Path::new("data/test/function/setup.txt")));
+        assert!(project.has_unsaved_changes);
+    }
+
+    #[test]
+    fn test_fix_extensions_collision() {
+        // Given a function and a leftover text copy of it
+        let mut project = Project::test_with_files(&[
+            ("data/test/function/setup.txt", "say old"),
+            ("data/test/function/setup.mcfunction", "say new"),
+        ]);
+
+        // When I fix the extensions
+        let result = project.fix_extensions();
+
+        // Then it should be refused without changing anything
+        assert!(matches!(result, Err(ExtensionFixError::Collision(path)) if path == Path::new("data/test/function/setup.mcfunction")));
+        assert_eq!(project.files()[Path::new("data/test/function/setup.mcfunction")], "say new");
+        assert!(!project.has_unsaved_changes);
+    }
+
+    #[test]
+    fn test_move_file() {
+        // Given a function written with a byte order mark, and a texture
+        let mut project = Project::test_with_files(&[("data/test/function/setup.mcfunction", "say hi")])
+            .with_bom_files([PathBuf::from("data/test/function/setup.mcfunction")])
+            .with_binary_files([(PathBuf::from("assets/test/textures/item/gem.png"), vec![0x89, 0x50])]);
+
+        // When I move both
+        project.move_file(Path::new("data/test/function/setup.mcfunction"), Path::new("data/test/function/init.mcfunction")).unwrap();
+        project.move_file(Path::new("assets/test/textures/item/gem.png"), Path::new("assets/test/textures/item/ruby.png")).unwrap();
+
+        // Then each should be at its new path, keeping its contents and byte order mark
+        assert_eq!(project.files()[Path::new("data/test/function/init.mcfunction")], "say hi");
+        assert!(project.has_bom(Path::new("data/test/function/init.mcfunction")));
+        assert!(!project.files().contains_key(Path::new("data/test/function/setup.mcfunction")));
+        assert_eq!(project.binary_files()[Path::new("assets/test/textures/item/ruby.png")], vec![0x89, 0x50]);
+        assert!(project.has_unsaved_changes);
+    }
+
+    #[rstest]
+    #[case::missing("data/test/function/missing.mcfunction", "data/test/function/new.mcfunction")]
+    #[case::collision("data/test/function/setup.mcfunction", "data/test/function/load.mcfunction")]
+    fn test_move_file_refused(#[case] from: &str, #[case] to: &str) {
+        // Given two functions
+        let mut project = Project::test_with_files(&[
+            ("data/test/function/setup.mcfunction", "say setup"),
+            ("data/test/function/load.mcfunction", "say load"),
+        ]);
+
+        // When I move a file which doesn't exist, or onto another file
+        let result = project.move_file(Path::new(from), Path::new(to));
+
+        // Then it should be refused without changing anything
+        assert!(result.is_err());
+        assert_eq!(project.files().len(), 2);
+        assert!(!project.has_unsaved_changes);
+    }
+
+    #[test]
+    fn test_stats_required_version() {
+        // Given a project using the singular directories from 1.21
+        let mut project = Project::test_with_files(&[
+            ("data/test/function/load.mcfunction", "say hi"),
+            ("data/test/recipe/trim.json", r#"{"type": "minecraft:smithing_trim"}"#),
+            ("data/other/tags/item/gems.json", r#"{"values": []}"#),
+        ]);
+        let before = project.stats(&[]).required_version.unwrap().version;
+
+        // When a recipe type added in 1.21.2 is used
+        project.set_file(PathBuf::from("data/test/recipe/dye.json"), r#"{"type": "minecraft:crafting_transmute"}"#.to_string());
+        let stats = project.stats(&[]);
+
+        // Then the required version should be raised from 1.21 to the recipe's
+        assert_eq!(before, *versions::V1_21);
+        assert_eq!(stats.files, 4);
+        assert_eq!(stats.namespaces, 2);
+        let required_version = stats.required_version.unwrap();
+        assert_eq!(required_version.version, *versions::V1_21_2);
+        assert_eq!(required_version.path, PathBuf::from("data/test/recipe/dye.json"));
+    }
+
+    #[test]
+    fn test_project_id_round_trip() {
+        // Given a new project's id
+        let id = Project::generate_test_id();
+
+        // When I format it, and serialize it, and parse both back
+        let formatted = id.to_string();
+        let serialized = serde_json::to_string(&id).unwrap();
+
+        // Then it should be written as a hyphenated UUID, and both should give back the same id
+        assert_eq!(formatted.len(), 36);
+        assert_eq!(formatted.matches('-').count(), 4);
+        assert_eq!(serialized, format!("\"{}\"", formatted));
+        assert_eq!(formatted.parse::<ProjectID>().unwrap(), id);
+        assert_eq!(serde_json::from_str::<ProjectID>(&serialized).unwrap(), id);
+        assert_eq!(ProjectID::nil().to_string(), "00000000-0000-0000-0000-000000000000");
+    }
+
+    #[test]
+    fn test_project_id_parse_invalid() {
+        // Given text which isn't a UUID
+        // When I parse it as a project id
+        let result = "not-a-project".parse::<ProjectID>();
+
+        // Then it should be refused
+        assert!(matches!(result, Err(ProjectIDParseError { id, .. }) if id == "not-a-project"));
+    }
+}
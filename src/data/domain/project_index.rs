@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use crate::data::domain::call_graph::CallGraph;
+use crate::data::domain::project::Project;
+use crate::data::domain::resource::resource::ResourceLocation;
+
+/// Resources defined by a project and the calls between its functions, for features which look things up across the whole pack.
+/// Built in the background after a project is opened, so anything using it should carry on without it until it's ready
+#[derive(Debug, Clone, Default)]
+pub struct ProjectIndex {
+    /// Files defining each resource, grouped by resource kind, e.g. `function` or `tags/item`
+    resources: BTreeMap<String, BTreeMap<ResourceLocation, Vec<PathBuf>>>,
+    call_graph: CallGraph,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct IndexProgress {
+    pub files_indexed: usize,
+    pub total_files: usize,
+}
+
+pub type IndexProgressCallback = Box<dyn FnMut(IndexProgress) + Send>;
+
+impl ProjectIndex {
+    /// Indexes every file of the project, reporting progress after each one
+    pub fn build(project: &Project, mut on_progress: impl FnMut(IndexProgress)) -> Self {
+        let total_files = project.files().len();
+        let mut resources: BTreeMap<String, BTreeMap<ResourceLocation, Vec<PathBuf>>> = BTreeMap::new();
+
+        for (files_indexed, file) in project.iter_files().enumerate() {
+            // Overlays define the same resources as the base pack, so their files are indexed as the game sees them
+            let (_, file_in_pack) = file.split_origin();
+            if let (Some(kind), Some(location)) = (file_in_pack.resource_kind(), file_in_pack.resource_location()) {
+                resources.entry(kind)
+                    .or_default()
+                    .entry(location)
+                    .or_default()
+                    .push(file.path.to_path_buf());
+            }
+
+            on_progress(IndexProgress { files_indexed: files_indexed + 1, total_files });
+        }
+
+        Self {
+            resources,
+            call_graph: CallGraph::from_project(project),
+        }
+    }
+
+    /// Resources of one kind, sorted by resource location
+    pub fn resources(&self, kind: &str) -> impl Iterator<Item = &ResourceLocation> {
+        self.resources.get(kind).into_iter().flat_map(BTreeMap::keys)
+    }
+
+    /// The files defining a resource, relative to the pack root
+    pub fn definitions(&self, kind: &str, location: &ResourceLocation) -> &[PathBuf] {
+        self.resources.get(kind)
+            .and_then(|locations| locations.get(location))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Resources defined by more than one file, with the files defining each
+    pub fn duplicates(&self) -> impl Iterator<Item = (&str, &ResourceLocation, &[PathBuf])> {
+        self.resources.iter()
+            .flat_map(|(kind, locations)| locations.iter().map(move |(location, paths)| (kind.as_str(), location, paths.as_slice())))
+            .filter(|(_, _, paths)| paths.len() > 1)
+    }
+
+    pub fn call_graph(&self) -> &CallGraph {
+        &self.call_graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_build_reports_progress() {
+        // Given a project with a few files
+        let project = Project::test_with_files(&[
+            ("data/example/function/load.mcfunction", "function example:setup"),
+            ("data/example/function/setup.mcfunction", "say hi"),
+            ("data/example/tags/item/gems.json", r#"{"values": []}"#),
+        ]);
+
+        // When I index it
+        let mut progress = Vec::new();
+        let index = ProjectIndex::build(&project, |update| progress.push(update));
+
+        // Then progress should be reported for every file, and its resources should be listed by kind
+        assert_eq!(progress.last(), Some(&IndexProgress { files_indexed: 3, total_files: 3 }));
+        assert_eq!(progress.len(), 3);
+
+        let setup = ResourceLocation::from_str("example:setup").unwrap();
+        assert_eq!(index.resources("function").map(ToString::to_string).collect::<Vec<_>>(), vec!["example:load", "example:setup"]);
+        assert_eq!(index.resources("tags/item").map(ToString::to_string).collect::<Vec<_>>(), vec!["example:gems"]);
+        assert_eq!(index.definitions("function", &setup), [PathBuf::from("data/example/function/setup.mcfunction")]);
+        assert_eq!(index.call_graph().callers(&setup), vec![&ResourceLocation::from_str("example:load").unwrap()]);
+    }
+
+    #[test]
+    fn test_overlay_files_indexed_as_duplicates() {
+        // Given a recipe which an overlay defines again
+        let project = Project::test_with_files(&[
+            ("data/example/recipe/gem.json", "{}"),
+            ("v2/data/example/recipe/gem.json", "{}"),
+            ("v2/data/example/recipe/other.json", "{}"),
+        ]);
+
+        // When I index it
+        let index = ProjectIndex::build(&project, |_| {});
+
+        // Then the overlay's files should be indexed under the resources they define, with only the redefined one duplicated
+        let gem = ResourceLocation::from_str("example:gem").unwrap();
+        assert_eq!(index.resources("recipe").map(ToString::to_string).collect::<Vec<_>>(), vec!["example:gem", "example:other"]);
+
+        let duplicates: Vec<_> = index.duplicates().collect();
+        assert_eq!(duplicates, vec![("recipe", &gem, [PathBuf::from("data/example/recipe/gem.json"), PathBuf::from("v2/data/example/recipe/gem.json")].as_slice())]);
+    }
+
+}
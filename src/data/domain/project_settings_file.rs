@@ -0,0 +1,34 @@
+use std::path::{Path, PathBuf};
+
+/// Where the settings file lives, relative to the project root
+pub const PROJECT_SETTINGS_FILE: &str = ".gaea/project.json";
+
+/// Editor state for a single project, restored when it is opened again.
+/// It is stored beside the pack contents rather than in them, so it is never exported
+#[derive(Debug, Clone, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ProjectSettingsFile {
+    /// Files open in tabs, relative to the project root, in tab order
+    pub open_tabs: Vec<PathBuf>,
+    pub active_tab: Option<PathBuf>,
+    /// Name of the theme to use for this project instead of the app's theme
+    pub theme: Option<String>,
+    pub word_wrap: bool,
+}
+
+impl ProjectSettingsFile {
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(PROJECT_SETTINGS_FILE)
+    }
+}
+
+impl Default for ProjectSettingsFile {
+    fn default() -> Self {
+        Self {
+            open_tabs: Vec::new(),
+            active_tab: None,
+            theme: None,
+            word_wrap: true,
+        }
+    }
+}
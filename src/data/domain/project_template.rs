@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use once_cell::sync::Lazy;
+use crate::data::domain::compatibility;
+use crate::data::domain::project::{ProjectSettings, ProjectType};
+use crate::data::domain::versions;
+
+/// Placeholder in template paths and contents, replaced by the namespace made from the project's name
+const NAMESPACE_PLACEHOLDER: &str = "${namespace}";
+/// Namespace used when nothing of the project's name can be used in one
+const FALLBACK_NAMESPACE: &str = "example";
+
+/// Templates compiled into the executable. Each is a JSON file listing the files it starts a project with
+static EMBEDDED_TEMPLATES: &[&str] = &[
+    include_str!("../../../resources/assets/templates/data_pack.json"),
+    include_str!("../../../resources/assets/templates/resource_pack.json"),
+];
+
+pub static PROJECT_TEMPLATES: Lazy<Vec<ProjectTemplate>> = Lazy::new(|| {
+    EMBEDDED_TEMPLATES.iter()
+        .map(|template| serde_json::from_str(template).expect("Embedded project templates should be valid"))
+        .collect()
+});
+
+/// Which pack a template's files belong in
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplatePack {
+    Data,
+    Resource,
+}
+
+/// Starter files for a new project, e.g. a data pack with functions hooked up to the `minecraft:load` and `minecraft:tick` tags
+#[derive(Debug, Clone, serde::Deserialize, getset::Getters)]
+#[getset(get = "pub")]
+pub struct ProjectTemplate {
+    id: String,
+    name: String,
+    pack: TemplatePack,
+    /// Contents keyed by path relative to the pack root, either of which may use the namespace placeholder
+    files: BTreeMap<String, String>,
+}
+
+impl ProjectTemplate {
+    pub fn find(id: &str) -> Result<&'static ProjectTemplate, TemplateError> {
+        PROJECT_TEMPLATES.iter()
+            .find(|template| template.id == id)
+            .ok_or_else(|| TemplateError::UnknownTemplate(id.to_string()))
+    }
+
+    /// Whether a project of the given type has the pack the template's files go in
+    pub fn fits(&self, project_type: ProjectType) -> bool {
+        matches!(
+            (self.pack, project_type),
+            (TemplatePack::Data, ProjectType::DataPack | ProjectType::Combined)
+                | (TemplatePack::Resource, ProjectType::ResourcePack | ProjectType::Combined)
+        )
+    }
+
+    /// The template's files for a new project. Their namespace is made from the project's name, and data directories
+    /// take the plural names they had before 1.21 when the project is for an older version
+    pub fn scaffold(&self, settings: &ProjectSettings) -> Result<Vec<(PathBuf, String)>, TemplateError> {
+        if !self.fits(project_type(settings)) {
+            return Err(TemplateError::WrongProjectType { template: self.id.clone(), project_type: project_type(settings) });
+        }
+
+        let namespace = namespace_for(settings.name());
+        let is_legacy = settings.project_version().version.is_before(&versions::V1_21);
+
+        Ok(self.files.iter()
+            .map(|(path, contents)| {
+                let path = path.replace(NAMESPACE_PLACEHOLDER, &namespace);
+                let path = if is_legacy { legacy_path(&path) } else { path };
+
+                (PathBuf::from(path), contents.replace(NAMESPACE_PLACEHOLDER, &namespace))
+            })
+            .collect())
+    }
+}
+
+fn project_type(settings: &ProjectSettings) -> ProjectType {
+    match settings {
+        ProjectSettings::DataPack { .. } => ProjectType::DataPack,
+        ProjectSettings::ResourcePack { .. } => ProjectType::ResourcePack,
+        ProjectSettings::Combined { .. } => ProjectType::Combined,
+    }
+}
+
+/// Lowercases the project's name and replaces anything a namespace can't contain, e.g. `My Gems!` gives `my_gems_`
+fn namespace_for(name: &str) -> String {
+    let namespace: String = name.trim()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| if matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.') { c } else { '_' })
+        .collect();
+
+    if namespace.chars().all(|c| c == '_') {
+        FALLBACK_NAMESPACE.to_string()
+    } else {
+        namespace
+    }
+}
+
+/// Renames the data directory of a path, such as `data/example/function/load.mcfunction`, to its pre-1.21 name
+fn legacy_path(path: &str) -> String {
+    let segments: Vec<_> = path.split('/').collect();
+
+    let ["data", namespace, rest @ ..] = segments.as_slice() else {
+        return path.to_string();
+    };
+
+    // Tag directories are renamed as a whole, e.g. `tags/function` to `tags/functions`
+    for length in [2, 1] {
+        if rest.len() <= length {
+            continue;
+        }
+
+        if let Some(legacy) = compatibility::legacy_data_directory(&rest[..length].join("/")) {
+            return format!("data/{}/{}/{}", namespace, legacy, rest[length..].join("/"));
+        }
+    }
+
+    path.to_string()
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TemplateError {
+    #[error("There is no project template called {0:?}!")]
+    UnknownTemplate(String),
+    #[error("Template {template:?} can't be used for a {project_type:?} project!")]
+    WrongProjectType { template: String, project_type: ProjectType },
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+    use crate::data::domain::pack_info::PackDescription;
+    use crate::data::domain::project::ProjectVersion;
+
+    #[test]
+    fn test_embedded_templates_load() {
+        // Given the templates compiled into the executable
+        // When I load them
+        let ids: Vec<_> = PROJECT_TEMPLATES.iter().map(|template| template.id.as_str()).collect();
+
+        // Then each should be found by its id
+        assert_eq!(ids, vec!["data_pack", "resource_pack"]);
+        assert!(ids.iter().all(|id| ProjectTemplate::find(id).is_ok()));
+    }
+
+    #[rstest]
+    #[case::spaces("My Gems", "my_gems")]
+    #[case::punctuation("Gems & Co.", "gems___co.")]
+    #[case::already_valid("gems-2", "gems-2")]
+    #[case::nothing_usable("!!!", FALLBACK_NAMESPACE)]
+    fn test_namespace_for(#[case] name: &str, #[case] expected: &str) {
+        // Given a project name
+        // When I make a namespace from it
+        // Then it should only contain characters a namespace can
+        assert_eq!(namespace_for(name), expected);
+    }
+
+    #[test]
+    fn test_scaffold_legacy_directories() {
+        // Given a data pack for a version from before the directories were renamed
+        let settings = ProjectSettings::DataPack {
+            name: "Gems".to_string(),
+            description: PackDescription::String("Gems".to_string()),
+            path: None,
+            project_version: ProjectVersion { version: *versions::V1_20_4 },
+        };
+
+        // When I scaffold the data pack template
+        let files = ProjectTemplate::find("data_pack").unwrap().scaffold(&settings).unwrap();
+
+        // Then the plural directory names should be used
+        let paths: Vec<_> = files.into_iter().map(|(path, _)| path).collect();
+        assert_eq!(paths, vec![
+            PathBuf::from("data/gems/functions/load.mcfunction"),
+            PathBuf::from("data/gems/functions/tick.mcfunction"),
+            PathBuf::from("data/minecraft/tags/functions/load.json"),
+            PathBuf::from("data/minecraft/tags/functions/tick.json"),
+        ]);
+    }
+
+    #[test]
+    fn test_scaffold_wrong_project_type() {
+        // Given a resource pack
+        let settings = ProjectSettings::ResourcePack {
+            name: "Gems".to_string(),
+            description: PackDescription::String("Gems".to_string()),
+            path: None,
+            project_version: ProjectVersion { version: versions::latest() },
+        };
+
+        // When I scaffold a data pack template for it
+        let result = ProjectTemplate::find("data_pack").unwrap().scaffold(&settings);
+
+        // Then it should be refused
+        assert!(matches!(result, Err(TemplateError::WrongProjectType { project_type: ProjectType::ResourcePack, .. })));
+    }
+}
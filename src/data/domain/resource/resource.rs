@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::convert::Infallible;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
@@ -5,6 +6,7 @@ use std::str::FromStr;
 use regex::Regex;
 use serde::{Deserializer, Serializer};
 use uuid::Uuid;
+use crate::data::domain::project::VANILLA_NAMESPACE;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct ResourceLocation {
@@ -23,12 +25,44 @@ impl ResourceLocation {
             .ok_or(ResourceLocationError(format!("Invalid resource location: {}", loc)))
     }
 
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
     fn validate(s: &str) -> bool {
-        let regex = Regex::new(r"^[a-z0-9]+:[a-z0-9.\-/]+$").unwrap();
+        let regex = Regex::new(r"^[a-z0-9_.\-]+:[a-z0-9_.\-/]+$").unwrap();
         regex.is_match(s)
     }
 }
 
+impl ResourceLocation {
+    /// Orders like [`Ord`], but with resources in the `minecraft` namespace before any other namespace
+    pub fn cmp_vanilla_first(&self, other: &Self) -> Ordering {
+        let is_custom = |location: &Self| location.namespace != VANILLA_NAMESPACE;
+
+        is_custom(self).cmp(&is_custom(other))
+            .then_with(|| self.cmp(other))
+    }
+}
+
+/// Sorts by namespace, then by path one segment at a time, so that everything in a folder is listed together
+impl Ord for ResourceLocation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.namespace.cmp(&other.namespace)
+            .then_with(|| self.value.split('/').cmp(other.value.split('/')))
+    }
+}
+
+impl PartialOrd for ResourceLocation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Display for ResourceLocation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}:{}", self.namespace, self.value)
@@ -77,6 +111,7 @@ impl<'de> serde::Deserialize<'de> for ResourceLocation {
 #[error("{0}")]
 pub struct ResourceLocationError(String);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResourceCategory {
     Asset,
     Data,
@@ -175,4 +210,63 @@ impl<T: ResourcePath + ResourceData> Resource<T> {
     }
 }
 
-pub type ResourceID = Uuid;
\ No newline at end of file
+pub type ResourceID = Uuid;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locations(locations: &[&str]) -> Vec<ResourceLocation> {
+        locations.iter().map(|location| location.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_sort_by_namespace_then_path() {
+        // Given locations across several namespaces, with nested paths
+        let mut sorted = locations(&[
+            "zeta:stone",
+            "alpha:block_stone",
+            "minecraft:stone",
+            "alpha:block/stone",
+            "alpha:block/dirt",
+            "alpha:block/nested/stone",
+            "alpha:block.stone",
+        ]);
+
+        // When I sort them
+        sorted.sort();
+
+        // Then they should be ordered by namespace, with each folder's contents kept together
+        assert_eq!(sorted, locations(&[
+            "alpha:block/dirt",
+            "alpha:block/nested/stone",
+            "alpha:block/stone",
+            "alpha:block.stone",
+            "alpha:block_stone",
+            "minecraft:stone",
+            "zeta:stone",
+        ]));
+    }
+
+    #[test]
+    fn test_sort_vanilla_first() {
+        // Given locations in the vanilla namespace and namespaces either side of it
+        let mut sorted = locations(&[
+            "zeta:stone",
+            "minecraft:stone",
+            "alpha:stone",
+            "minecraft:block/stone",
+        ]);
+
+        // When I sort them with vanilla first
+        sorted.sort_by(ResourceLocation::cmp_vanilla_first);
+
+        // Then the vanilla namespace should come first, and the rest as usual
+        assert_eq!(sorted, locations(&[
+            "minecraft:block/stone",
+            "minecraft:stone",
+            "alpha:stone",
+            "zeta:stone",
+        ]));
+    }
+}
@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Text to find across the files of a project
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SearchQuery {
+    pub text: String,
+    pub case_sensitive: bool,
+}
+
+/// One occurrence of the query. Lines and columns count from 0, as in the editor, with columns in characters
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SearchMatch {
+    /// Path of the file, relative to the pack root
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// The whole line the match is on, to show it in context
+    pub line_text: String,
+}
+
+/// How far a search got, once it has finished or been cancelled
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct SearchSummary {
+    pub files_searched: usize,
+    pub total_files: usize,
+    pub matches: usize,
+    pub cancelled: bool,
+}
+
+/// Receives the matches of each file as soon as it has been searched, in no particular order of files
+pub type SearchMatchCallback = Box<dyn FnMut(Vec<SearchMatch>) + Send>;
+
+/// Shared between a search and whatever started it, so the search can be stopped part way through
+#[derive(Debug, Clone, Default)]
+pub struct SearchCancellation(Arc<AtomicBool>);
+
+impl SearchCancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl SearchQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into(), case_sensitive: false }
+    }
+
+    /// Every occurrence of the query in one file, in order. An empty query matches nothing
+    pub fn find_in_file(&self, path: &Path, contents: &str) -> Vec<SearchMatch> {
+        if self.text.is_empty() {
+            return Vec::new();
+        }
+
+        let needle = self.normalize(&self.text);
+
+        contents.lines()
+            .enumerate()
+            .flat_map(|(line, line_text)| {
+                let haystack = self.normalize(line_text);
+                haystack.match_indices(&needle)
+                    .map(|(offset, _)| SearchMatch {
+                        path: path.to_path_buf(),
+                        line,
+                        column: haystack[..offset].chars().count(),
+                        line_text: line_text.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        if self.case_sensitive {
+            text.to_string()
+        } else {
+            text.to_lowercase()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_in_file() {
+        // Given a function mentioning a resource in a few places
+        let contents = "say Diamond\nfunction example:diamond\n# diamonds and DIAMOND";
+
+        // When I search it without regard to case
+        let matches = SearchQuery::new("diamond").find_in_file(Path::new("data/example/function/load.mcfunction"), contents);
+
+        // Then every occurrence should be found, with its position
+        let positions: Vec<_> = matches.iter().map(|found| (found.line, found.column)).collect();
+        assert_eq!(positions, vec![(0, 4), (1, 17), (2, 2), (2, 15)]);
+        assert_eq!(matches[1].line_text, "function example:diamond");
+
+        // And a case sensitive search should only find the exact text
+        let query = SearchQuery { text: "DIAMOND".to_string(), case_sensitive: true };
+        assert_eq!(query.find_in_file(Path::new("load.mcfunction"), contents).len(), 1);
+        assert!(SearchQuery::new("").find_in_file(Path::new("load.mcfunction"), contents).is_empty());
+    }
+}
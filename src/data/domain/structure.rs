@@ -0,0 +1,59 @@
+use std::path::Path;
+
+pub const STRUCTURE_EXTENSION: &str = "nbt";
+
+/// The first bytes of every gzip stream, which structure files always are when saved by the game
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A structure template saved by a structure block, as gzipped NBT.
+/// The editor doesn't decode NBT yet, so the bytes are kept exactly as they were read and written back untouched
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Structure {
+    data: Vec<u8>,
+}
+
+impl Structure {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Whether the data starts like a gzip stream, as anything else won't load in game
+    pub fn is_gzipped(&self) -> bool {
+        self.data.starts_with(&GZIP_MAGIC)
+    }
+}
+
+/// Whether a path relative to the pack root is a structure, under `data/<namespace>/structure`,
+/// or `structures` before 1.21
+pub fn is_structure_path(path: &Path) -> bool {
+    let mut components = path.components().map(|component| component.as_os_str());
+
+    components.next().is_some_and(|root| root == "data")
+        && components.next().is_some()
+        && components.next().is_some_and(|folder| folder == "structure" || folder == "structures")
+        && path.extension().is_some_and(|extension| extension == STRUCTURE_EXTENSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::structure("data/example/structure/house.nbt", true)]
+    #[case::nested("data/example/structure/village/house.nbt", true)]
+    #[case::before_1_21("data/example/structures/house.nbt", true)]
+    #[case::wrong_folder("data/example/function/house.nbt", false)]
+    #[case::wrong_extension("data/example/structure/house.json", false)]
+    #[case::resource_pack("assets/example/structure/house.nbt", false)]
+    fn test_is_structure_path(#[case] path: &str, #[case] expected: bool) {
+        // Given a path in a pack
+        // When I check if it is a structure
+        // Then only `.nbt` files in a namespace's structure folder should be
+        assert_eq!(is_structure_path(Path::new(path)), expected);
+    }
+}
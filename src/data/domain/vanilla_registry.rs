@@ -0,0 +1,60 @@
+use std::collections::{BTreeMap, BTreeSet};
+use once_cell::sync::Lazy;
+use crate::data::domain::project::VANILLA_NAMESPACE;
+use crate::data::domain::resource::resource::ResourceLocation;
+
+/// Entries which ship with the game, keyed by the same resource kinds as [`ProjectIndex`](crate::data::domain::project_index::ProjectIndex),
+/// e.g. `item` or `tags/function`, so that lookups can treat vanilla and the project alike
+#[derive(Debug, Clone, Default)]
+pub struct VanillaRegistry {
+    entries: BTreeMap<String, BTreeSet<ResourceLocation>>,
+}
+
+/// Built in vanilla entries, covering the registries the editor offers completions for
+pub static VANILLA_REGISTRY: Lazy<VanillaRegistry> = Lazy::new(|| VanillaRegistry::from_entries(BUILTIN_ENTRIES.iter().copied()));
+
+const BUILTIN_ENTRIES: &[(&str, &[&str])] = &[
+    ("item", &[
+        "air", "apple", "arrow", "bow", "bread", "coal", "cobblestone", "copper_ingot", "diamond", "diamond_sword",
+        "dirt", "emerald", "gold_ingot", "iron_ingot", "iron_pickaxe", "oak_log", "oak_planks", "redstone",
+        "stick", "stone", "torch",
+    ]),
+    ("block", &[
+        "air", "cobblestone", "dirt", "grass_block", "oak_log", "oak_planks", "stone", "torch", "water",
+    ]),
+    ("entity_type", &[
+        "armor_stand", "cow", "creeper", "item", "marker", "pig", "player", "skeleton", "zombie",
+    ]),
+    ("tags/item", &[
+        "coals", "logs", "planks", "stone_tool_materials", "swords",
+    ]),
+    ("tags/block", &[
+        "logs", "mineable/pickaxe", "planks",
+    ]),
+    ("tags/function", &[
+        "load", "tick",
+    ]),
+];
+
+impl VanillaRegistry {
+    /// Builds a registry from paths in the `minecraft` namespace, grouped by resource kind
+    pub fn from_entries<'a>(entries: impl IntoIterator<Item = (&'a str, &'a [&'a str])>) -> Self {
+        let entries = entries.into_iter()
+            .map(|(kind, paths)| {
+                let locations = paths.iter()
+                    .map(|path| ResourceLocation::new(VANILLA_NAMESPACE, path)
+                        // Panic because this can only result from a static bug and should never fail at runtime
+                        .unwrap_or_else(|_| panic!("Invalid vanilla {} entry {}", kind, path)))
+                    .collect();
+                (kind.to_string(), locations)
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Entries of one kind, sorted by resource location
+    pub fn resources(&self, kind: &str) -> impl Iterator<Item = &ResourceLocation> {
+        self.entries.get(kind).into_iter().flatten()
+    }
+}
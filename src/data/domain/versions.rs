@@ -1,5 +1,6 @@
 use mc_version::{MinecraftVersion, PackFormat};
 use mc_version_macro::define_versions;
+use crate::data::domain::project::ProjectVersion;
 
 define_versions! {
     data = [
@@ -60,30 +61,164 @@ macro_rules! latest_resource_format {
     };
 }
 
-// TODO: cache this as part of the macro for better performance
-
+/// Looks up the format for a version, preferring the newest format if a version has several
 pub fn get_datapack_format_for_version(version: MinecraftVersion) -> &'static PackFormat {
-    for format in &*DATA_FORMAT_MAP {
-        let format = *format.value();
+    find_datapack_format_for_version(version)
+        // Panic because this can only result from a static bug and should never fail at runtime
+        .unwrap_or_else(|| panic!("No datapack format found for version {}", version))
+}
+
+/// Looks up the format for a version, preferring the newest format if a version has several
+pub fn get_resourcepack_format_for_version(version: MinecraftVersion) -> &'static PackFormat {
+    find_resourcepack_format_for_version(version)
+        // Panic because this can only result from a static bug and should never fail at runtime
+        .unwrap_or_else(|| panic!("No resourcepack format found for version {}", version))
+}
+
+/// Like [`get_datapack_format_for_version`], for versions which may not have data packs, such as ones chosen by the user
+pub fn find_datapack_format_for_version(version: MinecraftVersion) -> Option<&'static PackFormat> {
+    VERSION_TO_DATA_FORMATS.get(&version)
+        .and_then(|formats| formats.iter().copied().max_by_key(|format| format.get_format_id()))
+}
+
+/// Like [`get_resourcepack_format_for_version`], for versions which may not have resource packs
+pub fn find_resourcepack_format_for_version(version: MinecraftVersion) -> Option<&'static PackFormat> {
+    VERSION_TO_RESOURCE_FORMATS.get(&version)
+        .and_then(|formats| formats.iter().copied().max_by_key(|format| format.get_format_id()))
+}
+
+/// Every known release, oldest first
+pub fn all_versions() -> Vec<&'static MinecraftVersion> {
+    let mut versions: Vec<_> = VERSION_MAP.iter().map(|entry| *entry.value()).collect();
+    versions.sort();
+    versions
+}
+
+/// Every known data pack format, oldest first
+pub fn all_data_formats() -> Vec<&'static PackFormat> {
+    sorted_formats(&DATA_FORMAT_MAP)
+}
+
+/// Every known resource pack format, oldest first
+pub fn all_resource_formats() -> Vec<&'static PackFormat> {
+    sorted_formats(&RESOURCE_FORMAT_MAP)
+}
+
+/// The newest known data pack format at or below `format`, for packs using formats the editor doesn't know, such as ones from newer versions
+pub fn closest_data_format(format: u8) -> Option<&'static PackFormat> {
+    closest_format(all_data_formats(), format)
+}
+
+/// Like [`closest_data_format`], for resource packs
+pub fn closest_resource_format(format: u8) -> Option<&'static PackFormat> {
+    closest_format(all_resource_formats(), format)
+}
+
+fn closest_format(formats: Vec<&'static PackFormat>, format: u8) -> Option<&'static PackFormat> {
+    formats.into_iter().rev().find(|known| known.get_format_id() <= format)
+}
+
+fn sorted_formats(formats: &dashmap::DashMap<u8, &'static PackFormat>) -> Vec<&'static PackFormat> {
+    let mut formats: Vec<_> = formats.iter().map(|entry| *entry.value()).collect();
+    formats.sort_by_key(|format| format.get_format_id());
+    formats
+}
+
+/// A Minecraft version which a new project can target, along with the formats the project would get
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SelectableVersion {
+    pub version: MinecraftVersion,
+    pub data_format: u8,
+    pub resource_format: u8,
+}
 
-        if format.get_versions().read().unwrap().contains(&version) {
-            return format
-        }
+impl SelectableVersion {
+    pub fn project_version(&self) -> ProjectVersion {
+        ProjectVersion { version: self.version }
     }
+}
 
-    // Panic because this can only result from a static bug and should never fail at runtime
-    panic!("No datapack format found for version {}", version)
+impl std::fmt::Display for SelectableVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.version)
+    }
 }
 
-pub fn get_resourcepack_format_for_version(version: MinecraftVersion) -> &'static PackFormat {
-    for format in &*RESOURCE_FORMAT_MAP {
-        let format = *format.value();
-        
-        if format.get_versions().read().unwrap().contains(&version) {
-            return format
-        }
+/// Every version with both a data and resource pack format, newest first, for picking a version when creating a project
+pub fn selectable_versions() -> Vec<SelectableVersion> {
+    let mut versions: Vec<_> = VERSION_MAP.iter()
+        .map(|entry| **entry.value())
+        .filter(|version| VERSION_TO_DATA_FORMATS.contains_key(version) && VERSION_TO_RESOURCE_FORMATS.contains_key(version))
+        .map(|version| SelectableVersion {
+            version,
+            data_format: get_datapack_format_for_version(version).get_format_id(),
+            resource_format: get_resourcepack_format_for_version(version).get_format_id(),
+        })
+        .collect();
+
+    versions.sort_by(|a, b| b.version.cmp(&a.version));
+    versions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_versions() {
+        // Given the known versions
+        // When I list them all
+        let versions = all_versions();
+
+        // Then they should run from the oldest to the latest, without repeats
+        assert_eq!(versions.first(), Some(&&*V1_13));
+        assert_eq!(versions.last(), Some(&&latest()));
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(versions.contains(&&*V1_18_2));
     }
-    
-    // Panic because this can only result from a static bug and should never fail at runtime
-    panic!("No resourcepack format found for version {}", version)
-}
\ No newline at end of file
+
+    #[test]
+    fn test_all_formats() {
+        // Given the known pack formats
+        // When I list them for each kind of pack
+        let data_formats: Vec<_> = all_data_formats().iter().map(|format| format.get_format_id()).collect();
+        let resource_formats: Vec<_> = all_resource_formats().iter().map(|format| format.get_format_id()).collect();
+
+        // Then they should be in order of format, and include ones only one kind of pack has
+        assert!(data_formats.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(resource_formats.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!((data_formats.first(), data_formats.last()), (Some(&4), Some(&81)));
+        assert!(data_formats.contains(&10) && !resource_formats.contains(&10));
+        assert!(resource_formats.contains(&13) && !data_formats.contains(&13));
+    }
+
+    #[test]
+    fn test_closest_formats() {
+        // Given known, in-between, newer and older formats
+        // When I look up the closest known format for each
+        let closest = |format| closest_data_format(format).map(|format| format.get_format_id());
+
+        // Then known formats should be themselves, and others the newest known format below them
+        assert_eq!(closest(48), Some(48));
+        assert_eq!(closest(75), Some(71));
+        assert_eq!(closest(200), Some(81));
+        assert_eq!(closest(1), None);
+        assert_eq!(closest_resource_format(10).map(|format| format.get_format_id()), Some(9));
+    }
+
+    #[test]
+    fn test_selectable_versions() {
+        // Given the known versions
+        // When I list the versions a project can be created for
+        let versions = selectable_versions();
+
+        // Then the newest should come first, each with the formats a new project would use
+        assert_eq!(versions.first(), Some(&SelectableVersion { version: latest(), data_format: 81, resource_format: 64 }));
+        assert_eq!(versions.last().map(|version| version.version), Some(*V1_13));
+        assert!(versions.windows(2).all(|pair| pair[0].version > pair[1].version));
+
+        let v1_18_2 = versions.iter().find(|version| version.version == *V1_18_2).unwrap();
+        assert_eq!((v1_18_2.data_format, v1_18_2.resource_format), (9, 8));
+        assert_eq!(v1_18_2.project_version(), ProjectVersion { version: *V1_18_2 });
+    }
+}
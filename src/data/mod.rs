@@ -1,3 +1,5 @@
 pub(crate) mod adapters;
+pub mod diagnostic;
 pub mod domain;
-pub(crate) mod serialization;
\ No newline at end of file
+pub(crate) mod serialization;
+pub mod validation;
\ No newline at end of file
@@ -1,6 +1,32 @@
+pub mod json;
 pub mod pack_info;
 pub mod project;
 
+/// UTF-8 byte order marks, which some Windows editors put at the start of text files, and which `serde_json` refuses
+pub(crate) mod bom {
+    pub const UTF8_BOM: char = '\u{feff}';
+
+    /// Removes a leading byte order mark, returning whether there was one
+    pub fn strip(contents: &mut String) -> bool {
+        if contents.starts_with(UTF8_BOM) {
+            contents.drain(..UTF8_BOM.len_utf8());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The bytes to write for a file, with its byte order mark put back if it had one
+    pub fn restore(contents: &str, had_bom: bool) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(contents.len() + UTF8_BOM.len_utf8());
+        if had_bom {
+            bytes.extend_from_slice(UTF8_BOM.to_string().as_bytes());
+        }
+        bytes.extend_from_slice(contents.as_bytes());
+        bytes
+    }
+}
+
 pub(crate) mod text_component {
     use std::fmt::Display;
     use crate::data::domain::pack_info::PackDescription;
@@ -58,4 +84,20 @@ pub(crate) mod resource_location {
             write!(f, "{}", self.0)
         }
     }
+}
+
+pub(crate) mod structure {
+    /// The raw bytes of a structure file, exactly as they are stored in the pack
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    pub struct Structure(Vec<u8>);
+
+    impl Structure {
+        pub fn new(data: Vec<u8>) -> Self {
+            Self(data)
+        }
+
+        pub fn bytes(&self) -> &[u8] {
+            &self.0
+        }
+    }
 }
\ No newline at end of file
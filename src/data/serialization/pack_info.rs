@@ -66,6 +66,17 @@ pub enum PackFormat {
     }
 }
 
+impl PackFormat {
+    /// The lowest and highest format included, however the range was written
+    pub fn bounds(&self) -> (u32, u32) {
+        match self {
+            PackFormat::Single(format) => (*format, *format),
+            PackFormat::Range(min, max) => (*min, *max),
+            PackFormat::Object { min_inclusive, max_inclusive } => (*min_inclusive, *max_inclusive),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize, derive_new::new, getset::Getters)]
 #[getset(get = "pub")]
 pub struct FilterPattern {
@@ -1,20 +1,58 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::ffi::{OsStr, OsString};
 use std::io::{Cursor, Read, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use sea_orm::Iden;
 use tokio::sync::RwLock;
 use zip::result::ZipError;
 use zip::write::{ExtendedFileOptions, FileOptions};
-use zip::ZipArchive;
+use zip::{DateTime, ZipArchive};
+use crate::data::diagnostic::{Diagnostic, DiagnosticSeverity};
+use crate::data::serialization::bom;
+use crate::data::serialization::json::JsonStyle;
+use crate::data::domain::structure;
 use crate::data::serialization::pack_info::PackInfo;
+use crate::data::serialization::structure::Structure;
+use crate::data::validation::{self, ValidationError, ValidationSeverity};
+
+pub const PACK_INFO_FILE: &str = "pack.mcmeta";
+pub const PACK_ICON_FILE: &str = "pack.png";
+
+/// Top level entries of a pack which the game loads, other than the folders of `data` and `assets`
+const KNOWN_ROOT_FILES: [&str; 2] = [PACK_INFO_FILE, PACK_ICON_FILE];
+
+/// Extensions of files which should always be text, so one which isn't valid UTF-8 is pointed out on import
+const TEXT_EXTENSIONS: [&str; 3] = ["json", "mcfunction", "mcmeta"];
+
+const EMPTY_PACK_MESSAGE: &str = "The pack has no resources besides its pack.mcmeta";
 
 #[async_trait::async_trait]
 pub trait ZippableProject {
-    async fn zip(&self) -> Result<Vec<u8>, SerializedProjectError>;
+    /// The files of the project's archive, uncompressed, in the order they're written. Names are `/`-separated, as zip entries need
+    async fn entries(&self) -> Result<Vec<(String, Cow<'_, [u8]>)>, SerializedProjectError>;
+    async fn zip(&self) -> Result<Vec<u8>, SerializedProjectError> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+        for (name, contents) in self.entries().await? {
+            zip.start_file::<&str, ExtendedFileOptions>(&name, entry_options())?;
+            zip.write_all(&contents)?;
+        }
+
+        Ok(zip.finish()?.into_inner())
+    }
     async fn extract(name: &str, zip_archive: ZipArchive<Cursor<Vec<u8>>>) -> Result<Self, SerializedProjectError> where Self: Sized;
+
+    /// Number of resources in the project, i.e. files inside a namespace. The `pack.mcmeta`, icon and anything else around them aren't counted
+    fn resource_count(&self) -> usize;
+
+    /// Whether the project has no resources at all, such as a pack with only a `pack.mcmeta`
+    fn is_empty(&self) -> bool {
+        self.resource_count() == 0
+    }
 }
 
 #[derive(Debug, Clone, getset::Getters)]
@@ -26,6 +64,20 @@ pub struct Project {
     pack_info: Arc<RwLock<PackInfo>>,
     
     files: HashMap<PathBuf, String>,
+    /// Binary structure files, which are kept as bytes rather than text
+    structures: HashMap<PathBuf, Structure>,
+    /// Text files which started with a UTF-8 byte order mark. It's stripped on extract, and written back when zipping
+    bom_files: HashSet<PathBuf>,
+    /// The `pack.png`, kept as bytes like structures
+    pack_icon: Option<Vec<u8>>,
+    /// Every other file which isn't text, such as textures, sounds and fonts, kept as bytes so they're written back unchanged
+    binary_files: HashMap<PathBuf, Vec<u8>>,
+    
+    /// Anything worked around while extracting the project, empty for projects which weren't extracted
+    import_report: ImportReport,
+    
+    /// How the `pack.mcmeta` is written when zipping
+    json_style: JsonStyle,
 }
 
 impl Project {
@@ -35,6 +87,12 @@ impl Project {
             project_type,
             pack_info: Arc::new(RwLock::new(pack_info)),
             files: HashMap::new(),
+            structures: HashMap::new(),
+            bom_files: HashSet::new(),
+            pack_icon: None,
+            binary_files: HashMap::new(),
+            import_report: ImportReport::default(),
+            json_style: JsonStyle::default(),
         }
     }
     
@@ -44,6 +102,235 @@ impl Project {
             ..Self::new(project_type, pack_info)
         }
     }
+
+    pub fn with_files(self, files: HashMap<PathBuf, String>) -> Self {
+        Self {
+            files,
+            ..self
+        }
+    }
+
+    pub fn with_structures(self, structures: HashMap<PathBuf, Structure>) -> Self {
+        Self {
+            structures,
+            ..self
+        }
+    }
+
+    pub fn with_bom_files(self, bom_files: HashSet<PathBuf>) -> Self {
+        Self {
+            bom_files,
+            ..self
+        }
+    }
+
+    pub fn with_pack_icon(self, pack_icon: Option<Vec<u8>>) -> Self {
+        Self {
+            pack_icon,
+            ..self
+        }
+    }
+
+    pub fn with_binary_files(self, binary_files: HashMap<PathBuf, Vec<u8>>) -> Self {
+        Self {
+            binary_files,
+            ..self
+        }
+    }
+
+    pub fn with_json_style(self, json_style: JsonStyle) -> Self {
+        Self {
+            json_style,
+            ..self
+        }
+    }
+
+    /// Sorts the files of a pack into text, structures, the icon and other binary files, as they were read from a zip
+    /// or a project's folder. Paths are relative to the pack root, and must already be known to stay inside it
+    pub fn from_entries(name: &str, project_type: SerializedProjectType, entries: impl IntoIterator<Item = (PathBuf, Vec<u8>)>) -> Result<Self, SerializedProjectError> {
+        let mut files = HashMap::new();
+        let mut structures = HashMap::new();
+        let mut bom_files = HashSet::new();
+        let mut pack_icon = None;
+        let mut binary_files = HashMap::new();
+        let mut import_report = ImportReport::default();
+
+        for (file_name, data) in entries {
+            if structure::is_structure_path(&file_name) {
+                structures.insert(file_name, Structure::new(data));
+                continue;
+            }
+
+            if file_name == Path::new(PACK_ICON_FILE) {
+                pack_icon = Some(data);
+                continue;
+            }
+
+            let mut content = match String::from_utf8(data) {
+                Ok(content) => content,
+                Err(error) => {
+                    let is_text_file = file_name.extension().is_some_and(|extension| TEXT_EXTENSIONS.iter().any(|text| extension == *text));
+                    if is_text_file {
+                        import_report.unreadable_files.push(file_name.clone());
+                    }
+
+                    binary_files.insert(file_name, error.into_bytes());
+                    continue;
+                }
+            };
+            
+            if !is_loaded_by_game(&file_name) {
+                import_report.unknown_files.push(file_name.clone());
+            }
+            
+            if bom::strip(&mut content) {
+                bom_files.insert(file_name.clone());
+            }
+            
+            files.insert(file_name, content);
+        }
+        
+        let pack_info = files.get(Path::new(PACK_INFO_FILE))
+            .ok_or_else(|| SerializedProjectError::InvalidZipFile(format!("No {} found!", PACK_INFO_FILE)))?;
+        import_report.suspect_fields = validate_pack_info(pack_info)?;
+        
+        let pack_info = serde_json::from_str(pack_info).map_err(|error| SerializedProjectError::InvalidZipFile(error.to_string()))?;
+        let pack_info = Arc::new(RwLock::new(pack_info));
+
+        Ok(Project {
+            name: name.to_string(),
+            project_type,
+            pack_info,
+            files,
+            structures,
+            bom_files,
+            pack_icon,
+            binary_files,
+            import_report,
+            json_style: JsonStyle::default(),
+        })
+    }
+
+    /// Whether the project has both a data pack's `data` and a resource pack's `assets`, as some archives bundle both
+    pub fn is_mixed(&self) -> bool {
+        let has_root = |root: &str| self.files.keys().chain(self.structures.keys()).chain(self.binary_files.keys()).any(|path| path.starts_with(root));
+        has_root("data") && has_root("assets")
+    }
+
+    /// Splits a mixed project into its data pack and its resource pack, keeping files outside of both roots, such as `pack.png`, in each.
+    /// The data pack keeps the project's pack info and import report, and the resource pack is given its own pack info
+    pub fn split_mixed(self, resource_pack_info: PackInfo) -> (Project, Project) {
+        let is_resource_file = |path: &PathBuf| !path.starts_with("data");
+
+        let resource_project = Project {
+            name: self.name.clone(),
+            project_type: SerializedProjectType::Resource,
+            pack_info: Arc::new(RwLock::new(resource_pack_info)),
+            files: self.files.iter()
+                .filter(|(path, _)| is_resource_file(path))
+                .map(|(path, content)| (path.clone(), content.clone()))
+                .collect(),
+            structures: HashMap::new(),
+            bom_files: self.bom_files.iter().filter(|path| is_resource_file(path)).cloned().collect(),
+            pack_icon: self.pack_icon.clone(),
+            binary_files: self.binary_files.iter()
+                .filter(|(path, _)| is_resource_file(path))
+                .map(|(path, data)| (path.clone(), data.clone()))
+                .collect(),
+            import_report: ImportReport::default(),
+            json_style: self.json_style,
+        };
+
+        let is_data_file = |path: &PathBuf| !path.starts_with("assets");
+
+        let data_project = Project {
+            project_type: SerializedProjectType::Data,
+            files: self.files.into_iter().filter(|(path, _)| is_data_file(path)).collect(),
+            binary_files: self.binary_files.into_iter().filter(|(path, _)| is_data_file(path)).collect(),
+            bom_files: self.bom_files.into_iter().filter(is_data_file).collect(),
+            ..self
+        };
+
+        (data_project, resource_project)
+    }
+}
+
+/// Entries of an imported archive which were accepted, but not exactly as they were,
+/// so the user can check the parts of a third-party pack the editor may not have understood
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ImportReport {
+    /// Files which should be text, such as functions, but aren't valid UTF-8. They're kept byte for byte, but can't be edited
+    pub unreadable_files: Vec<PathBuf>,
+    /// Files kept in the project, but outside of anywhere the game loads them from
+    pub unknown_files: Vec<PathBuf>,
+    /// Fields of the `pack.mcmeta` which the game would ignore rather than refuse
+    pub suspect_fields: Vec<ValidationError>,
+    /// The pack has no resources, so it imported as an empty project. Usually the wrong archive was picked
+    pub empty_pack: bool,
+}
+
+impl ImportReport {
+    pub fn is_empty(&self) -> bool {
+        self.unreadable_files.is_empty() && self.unknown_files.is_empty() && self.suspect_fields.is_empty() && !self.empty_pack
+    }
+
+    /// Combines the reports of both halves of a combined import
+    pub fn merge(mut self, other: ImportReport) -> Self {
+        self.unreadable_files.extend(other.unreadable_files);
+        self.unknown_files.extend(other.unknown_files);
+        self.suspect_fields.extend(other.suspect_fields);
+        self.empty_pack |= other.empty_pack;
+        self
+    }
+
+    /// Everything in the report as diagnostics. Suspect fields are reported against the `pack.mcmeta`
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let unreadable = self.unreadable_files.iter().map(|path| Diagnostic {
+            path: path.clone(),
+            line: None,
+            severity: DiagnosticSeverity::Warning,
+            rule: "unreadable-file".to_string(),
+            message: "Not valid UTF-8 text, so it's kept as it was but can't be edited".to_string(),
+        });
+        let unknown = self.unknown_files.iter().map(|path| Diagnostic {
+            path: path.clone(),
+            line: None,
+            severity: DiagnosticSeverity::Info,
+            rule: "unknown-file".to_string(),
+            message: "Not anywhere the game loads files from".to_string(),
+        });
+        let suspect = self.suspect_fields.iter().map(|field| Diagnostic {
+            path: PathBuf::from(PACK_INFO_FILE),
+            line: None,
+            severity: match field.severity {
+                ValidationSeverity::Warning => DiagnosticSeverity::Warning,
+                ValidationSeverity::Error => DiagnosticSeverity::Error,
+            },
+            rule: "pack-info".to_string(),
+            message: field.to_string(),
+        });
+        let empty = self.empty_pack.then(|| Diagnostic {
+            path: PathBuf::from(PACK_INFO_FILE),
+            line: None,
+            severity: DiagnosticSeverity::Warning,
+            rule: "empty-pack".to_string(),
+            message: EMPTY_PACK_MESSAGE.to_string(),
+        });
+
+        unreadable.chain(unknown).chain(suspect).chain(empty).collect()
+    }
+}
+
+impl Display for ImportReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let entries = self.unreadable_files.iter()
+            .chain(&self.unknown_files)
+            .map(|path| path.display().to_string())
+            .chain(self.suspect_fields.iter().map(ToString::to_string))
+            .chain(self.empty_pack.then(|| EMPTY_PACK_MESSAGE.to_string()));
+
+        write!(f, "{}", entries.collect::<Vec<_>>().join(", "))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -54,64 +341,155 @@ pub enum SerializedProjectType {
 
 #[async_trait::async_trait]
 impl ZippableProject for Project {
-    async fn zip(&self) -> Result<Vec<u8>, SerializedProjectError> {
-        let buffer = Cursor::new(Vec::new());
-        let mut zip = zip::ZipWriter::new(buffer);
+    async fn entries(&self) -> Result<Vec<(String, Cow<'_, [u8]>)>, SerializedProjectError> {
+        let pack_info = self.json_style.serialize(&*self.pack_info.read().await)?;
+        validate_pack_info(&pack_info)?;
         
-        for (path, content) in &self.files {
-            zip.start_file::<&str, ExtendedFileOptions>(path.to_str().unwrap(), FileOptions::default())?;
-            zip.write_all(content.as_bytes())?;
-        }
+        // Entries are written sorted by path with a fixed timestamp, so zipping the same project twice gives the same archive.
+        // The pack info is written from `pack_info` last, even when it was also extracted as a file
+        let mut entries: Vec<(&Path, Cow<[u8]>)> = self.files.iter()
+            .filter(|(path, _)| path.as_path() != Path::new(PACK_INFO_FILE))
+            .map(|(path, content)| (path.as_path(), Cow::Owned(bom::restore(content, self.bom_files.contains(path)))))
+            .chain(self.structures.iter().map(|(path, structure)| (path.as_path(), Cow::Borrowed(structure.bytes()))))
+            .chain(self.pack_icon.iter().map(|icon| (Path::new(PACK_ICON_FILE), Cow::Borrowed(icon.as_slice()))))
+            .chain(self.binary_files.iter().map(|(path, data)| (path.as_path(), Cow::Borrowed(data.as_slice()))))
+            .collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
         
-        zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default())?;
-        zip.write_all(serde_json::to_string(&*self.pack_info.read().await).unwrap().as_bytes())?;
-
-        let zip_data = zip.finish()?;
-        Ok(zip_data.into_inner())
+        let pack_info = bom::restore(&pack_info, self.bom_files.contains(Path::new(PACK_INFO_FILE)));
+        
+        entries.into_iter()
+            .map(|(path, content)| match path.to_str() {
+                Some(name) => Ok((name.to_string(), content)),
+                None => Err(SerializedProjectError::NotUnicode(path.to_path_buf())),
+            })
+            .chain([Ok((PACK_INFO_FILE.to_string(), Cow::Owned(pack_info)))])
+            .collect()
     }
     
     async fn extract(name: &str, mut zip_archive: ZipArchive<Cursor<Vec<u8>>>) -> Result<Self, SerializedProjectError> {
-        // TODO: More proper file handling
-        let mut files = HashMap::new();
-
-        let has_data_dir = zip_archive.by_name("data/").is_ok();
-        let has_assets_dir = zip_archive.by_name("assets/").is_ok();
+        // Checked before reading anything, so that e.g. a mod jar is refused for what it is rather than for a missing `pack.mcmeta`
+        let names: Vec<_> = zip_archive.file_names().map(|name| name.replace('\\', "/")).collect();
+        let project_type = pack_type(name, names.iter().map(String::as_str))?;
 
+        let mut entries = Vec::with_capacity(zip_archive.len());
         for i in 0..zip_archive.len() {
             let mut file = zip_archive.by_index(i)?;
-            let mut content = String::new();
+
+            // Validate every entry, including directories, so a malicious archive is refused outright
+            let file_name = validate_entry_path(file.name())?;
             
             if file.is_dir() {
                 continue;
             }
 
-            let file_name = file.enclosed_name().ok_or(SerializedProjectError::InvalidZipFile(format!("Unsafe path in zip file!: {0}", file.name())))?;
-            
-            file.read_to_string(&mut content)?;
-            files.insert(file_name, content);
-        }
-        
-        let pack_info = Arc::new(RwLock::new(serde_json::from_str(&files[Path::new("pack.mcmeta")]).unwrap()));
-        
-        let project_type = if has_data_dir {
-            SerializedProjectType::Data
-        }
-        else if has_assets_dir {
-            SerializedProjectType::Resource
+            let mut data = Vec::new();
+            file.read_to_end(&mut data)?;
+            entries.push((file_name, data));
         }
-        else { 
-            return Err(SerializedProjectError::InvalidZipFile("No data or assets directory found!".to_string()))
-        };
 
-        Ok(Project {
-            name: name.to_string(),
-            project_type,
-            pack_info,
-            files,
-        })
+        Self::from_entries(name, project_type, entries)
+    }
+
+    fn resource_count(&self) -> usize {
+        self.files.keys().chain(self.structures.keys()).chain(self.binary_files.keys())
+            .filter(|path| is_resource(path))
+            .count()
+    }
+}
+
+/// Tells a data pack from a resource pack by its `data` folder, refusing anything with neither a `data` nor an `assets` folder.
+/// Paths use `/`, with a trailing one for directories. Archives don't always have entries for their directories,
+/// so the paths of the files inside them count too
+pub fn pack_type<'a>(name: &str, paths: impl Iterator<Item = &'a str> + Clone) -> Result<SerializedProjectType, SerializedProjectError> {
+    let has_root_dir = |root: &str| paths.clone()
+        .any(|path| path.split('/').next() == Some(root) && path.len() > root.len());
+
+    if has_root_dir("data") {
+        Ok(SerializedProjectType::Data)
+    } else if has_root_dir("assets") {
+        Ok(SerializedProjectType::Resource)
+    } else {
+        Err(SerializedProjectError::NotAPack(name.to_string()))
+    }
+}
+
+/// Options for every exported entry. The timestamp is fixed rather than the time of export, so unchanged projects export unchanged archives
+pub(crate) fn entry_options() -> FileOptions<'static, ExtendedFileOptions> {
+    FileOptions::default().last_modified_time(DateTime::default())
+}
+
+/// Whether the file sits inside a namespace of `data` or `assets`, e.g. `data/<namespace>/function/load.mcfunction`
+fn is_resource(path: &Path) -> bool {
+    let mut components = path.components();
+    let root = components.next();
+
+    matches!(root, Some(root) if root.as_os_str() == "data" || root.as_os_str() == "assets") && components.count() >= 2
+}
+
+/// Whether the file is somewhere the game reads from a pack, so anything else can be pointed out on import
+fn is_loaded_by_game(path: &Path) -> bool {
+    let mut components = path.components();
+    let (Some(root), rest) = (components.next(), components.next()) else {
+        return false;
+    };
+
+    match rest {
+        Some(_) => root.as_os_str() == "data" || root.as_os_str() == "assets",
+        None => KNOWN_ROOT_FILES.iter().any(|known| root.as_os_str() == *known),
+    }
+}
+
+/// Refuses a `pack.mcmeta` which the game wouldn't load, returning anything it would only ignore
+fn validate_pack_info(contents: &str) -> Result<Vec<ValidationError>, SerializedProjectError> {
+    let (errors, warnings): (Vec<_>, Vec<_>) = validation::pack_info::validate_str(contents)
+        .into_iter()
+        .partition(ValidationError::is_error);
+    
+    for warning in &warnings {
+        tracing::warn!("{}: {}", PACK_INFO_FILE, warning);
+    }
+    
+    if errors.is_empty() {
+        Ok(warnings)
+    } else {
+        Err(SerializedProjectError::InvalidPackInfo(errors))
     }
 }
 
+/// Ensures a zip entry name stays inside the extraction root.
+/// Rejects any entry containing `..`, a root, or a drive prefix, treating `\` as a separator
+/// so that archives created on Windows cannot sneak traversal segments past the check.
+fn validate_entry_path(entry_name: &str) -> Result<PathBuf, SerializedProjectError> {
+    let unsafe_path = || SerializedProjectError::InvalidZipFile(format!("Unsafe path in zip file!: {0}", entry_name));
+    
+    if entry_name.contains('\0') {
+        return Err(unsafe_path());
+    }
+    
+    let normalized = entry_name.replace('\\', "/");
+    
+    // Drive letters such as `C:` are not recognised as prefixes on non-Windows platforms
+    if normalized.split('/').next().is_some_and(|segment| segment.contains(':')) {
+        return Err(unsafe_path());
+    }
+    
+    let mut path = PathBuf::new();
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(segment) => path.push(segment),
+            Component::CurDir => {},
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return Err(unsafe_path()),
+        }
+    }
+    
+    if path.as_os_str().is_empty() {
+        return Err(unsafe_path());
+    }
+    
+    Ok(path)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum SerializedProjectError {
     #[error(transparent)]
@@ -120,6 +498,14 @@ pub enum SerializedProjectError {
     IOError(#[from] std::io::Error),
     #[error("Invalid zip file: {0:?}")]
     InvalidZipFile(String),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid pack.mcmeta: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    InvalidPackInfo(Vec<ValidationError>),
+    #[error("{0} is not a data pack or resource pack, as it has no data or assets folder!")]
+    NotAPack(String),
+    #[error("{0:?} is not valid unicode, so it can't be written to a zip!")]
+    NotUnicode(PathBuf),
 }
 
 #[cfg(test)]
@@ -138,7 +524,13 @@ mod tests {
                 name: "Test project".to_string(),
                 project_type: SerializedProjectType::Data,
                 pack_info: pack_info.clone(),
-                files: HashMap::new()
+                files: HashMap::new(),
+                structures: HashMap::new(),
+                bom_files: HashSet::new(),
+                pack_icon: None,
+                binary_files: HashMap::new(),
+                import_report: ImportReport::default(),
+                json_style: JsonStyle::default(),
             };
 
             // When I serialize it
@@ -152,7 +544,7 @@ mod tests {
             let mut pack_info_file = zip_file.by_index(0).unwrap();
             let mut pack_info_content = String::new();
             pack_info_file.read_to_string(&mut pack_info_content).unwrap();
-            assert_eq!(pack_info_content, serde_json::to_string(&*pack_info.read().await).unwrap());
+            assert_eq!(pack_info_content, JsonStyle::default().serialize(&*pack_info.read().await).unwrap());
         }
 
         #[tokio::test]
@@ -169,7 +561,13 @@ mod tests {
                 name: "Test project".to_string(),
                 project_type: SerializedProjectType::Data,
                 pack_info: pack_info.clone(),
-                files
+                files,
+                structures: HashMap::new(),
+                bom_files: HashSet::new(),
+                pack_icon: None,
+                binary_files: HashMap::new(),
+                import_report: ImportReport::default(),
+                json_style: JsonStyle::default(),
             };
 
             // When I serialize it
@@ -185,6 +583,79 @@ mod tests {
             test_file.read_to_string(&mut test_file_content).unwrap();
             assert_eq!(test_file_content, expected_test_contents);
         }
+
+        #[tokio::test]
+        async fn test_zip_is_deterministic() {
+            // Given a project with files and a structure spread over several namespaces and resource kinds
+            let files: HashMap<PathBuf, String> = [
+                "data/zeta/function/tick.mcfunction",
+                "data/alpha/recipe/gem.json",
+                "data/alpha/function/load.mcfunction",
+                "data/alpha/function/util/setup.mcfunction",
+                "data/alpha/tags/function/load.json",
+            ].into_iter().map(|path| (PathBuf::from(path), "{}".to_string())).collect();
+            let structures = HashMap::from([(PathBuf::from("data/alpha/structure/house.nbt"), Structure::new(vec![0x1f, 0x8b]))]);
+
+            let project = Project {
+                name: "Test project".to_string(),
+                project_type: SerializedProjectType::Data,
+                pack_info: Arc::new(RwLock::new(PackInfo::default_data())),
+                files,
+                structures,
+                bom_files: HashSet::new(),
+                pack_icon: None,
+                binary_files: HashMap::new(),
+                import_report: ImportReport::default(),
+                json_style: JsonStyle::default(),
+            };
+
+            // When I serialize it twice
+            let first = project.zip().await.unwrap();
+            let second = project.zip().await.unwrap();
+
+            // Then both archives should be identical, with the entries sorted by path and the pack info last
+            assert_eq!(first, second);
+
+            let zip_file = ZipArchive::new(Cursor::new(first)).unwrap();
+            let names: Vec<_> = zip_file.file_names().collect();
+            assert_eq!(names, vec![
+                "data/alpha/function/load.mcfunction",
+                "data/alpha/function/util/setup.mcfunction",
+                "data/alpha/recipe/gem.json",
+                "data/alpha/structure/house.nbt",
+                "data/alpha/tags/function/load.json",
+                "data/zeta/function/tick.mcfunction",
+                PACK_INFO_FILE,
+            ]);
+        }
+
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn test_zip_non_unicode_path() {
+            use std::ffi::OsStr;
+            use std::os::unix::ffi::OsStrExt;
+
+            // Given a project with a file whose path isn't valid unicode
+            let path = PathBuf::from(OsStr::from_bytes(b"data/test/\xff.json"));
+            let project = Project {
+                name: "Test project".to_string(),
+                project_type: SerializedProjectType::Data,
+                pack_info: Arc::new(RwLock::new(PackInfo::default_data())),
+                files: HashMap::from([(path.clone(), "{}".to_string())]),
+                structures: HashMap::new(),
+                bom_files: HashSet::new(),
+                pack_icon: None,
+                binary_files: HashMap::new(),
+                import_report: ImportReport::default(),
+                json_style: JsonStyle::default(),
+            };
+
+            // When I serialize it
+            let result = project.zip().await;
+
+            // Then the path should be reported instead of panicking
+            assert!(matches!(result, Err(SerializedProjectError::NotUnicode(ref error_path)) if *error_path == path));
+        }
     }
     
     mod extract {
@@ -245,9 +716,211 @@ mod tests {
             assert_eq!(project.files.get(&PathBuf::from("data/test.json")).unwrap(), r#"{"test": "test"}"#);
         }
 
+        #[tokio::test]
+        async fn test_extract_reports_unknown_entries() {
+            // Given a zip file with files the game doesn't load, and one which isn't text
+            let pack_info = PackInfo::default_data();
+            let pack_info_string = serde_json::to_string(&pack_info).unwrap();
+
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(pack_info_string.as_bytes()).unwrap();
+
+            zip.add_directory::<&str, ExtendedFileOptions>("data", Default::default()).unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("data/test/function/test.mcfunction", FileOptions::default()).unwrap();
+            zip.write_all(b"say hi").unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("README.txt", FileOptions::default()).unwrap();
+            zip.write_all(b"Made by someone").unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("__MACOSX/data/._test", FileOptions::default()).unwrap();
+            zip.write_all(b"").unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("data/test/function/broken.mcfunction", FileOptions::default()).unwrap();
+            zip.write_all(&[0x73, 0x61, 0x79, 0xff, 0xfe]).unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("data/test/structure/house.nbt", FileOptions::default()).unwrap();
+            zip.write_all(&[0x1f, 0x8b, 0x08, 0x00, 0xff, 0xfe]).unwrap();
+
+            let zip_data = zip.finish().unwrap();
+            let zip_archive = ZipArchive::new(zip_data).unwrap();
+
+            // When I deserialize it
+            let project = Project::extract("Test Project", zip_archive).await.unwrap();
+
+            // Then the unknown files should be kept, but reported, the broken text file kept as bytes but reported, and the structure kept as it was
+            let mut unknown_files = project.import_report.unknown_files.clone();
+            unknown_files.sort();
+            assert_eq!(unknown_files, vec![PathBuf::from("README.txt"), PathBuf::from("__MACOSX/data/._test")]);
+            assert!(project.files.contains_key(&PathBuf::from("README.txt")));
+
+            assert_eq!(project.import_report.unreadable_files, vec![PathBuf::from("data/test/function/broken.mcfunction")]);
+            assert_eq!(project.binary_files[&PathBuf::from("data/test/function/broken.mcfunction")], [0x73, 0x61, 0x79, 0xff, 0xfe]);
+            assert_eq!(project.structures[&PathBuf::from("data/test/structure/house.nbt")].bytes(), [0x1f, 0x8b, 0x08, 0x00, 0xff, 0xfe]);
+        }
+
+        #[tokio::test]
+        async fn test_pack_icon_round_trip() {
+            // Given a project with an icon, which isn't text
+            let icon = vec![0x89, b'P', b'N', b'G', 0xff, 0xfe];
+            let project = Project::new(SerializedProjectType::Data, PackInfo::default_data())
+                .with_files(HashMap::from([(PathBuf::from("data/test/function/test.mcfunction"), "say hi".to_string())]))
+                .with_pack_icon(Some(icon.clone()));
+
+            // When I zip and extract it
+            let zip_data = project.zip().await.unwrap();
+            let extracted = Project::extract("Test Project", ZipArchive::new(Cursor::new(zip_data)).unwrap()).await.unwrap();
+
+            // Then the icon should be kept byte for byte, rather than skipped
+            assert_eq!(extracted.pack_icon, Some(icon));
+            assert!(extracted.import_report.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_binary_file_round_trip() {
+            // Given a resource pack with a texture, which isn't text
+            let texture = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0xff, 0x00];
+            let project = Project::new(SerializedProjectType::Resource, PackInfo::default_resource())
+                .with_binary_files(HashMap::from([(PathBuf::from("assets/test/textures/item/gem.png"), texture.clone())]));
+
+            // When I zip and extract it
+            let zip_data = project.zip().await.unwrap();
+            let extracted = Project::extract("Test Project", ZipArchive::new(Cursor::new(zip_data)).unwrap()).await.unwrap();
+
+            // Then the texture should be kept byte for byte, without anything to report
+            assert_eq!(extracted.binary_files[&PathBuf::from("assets/test/textures/item/gem.png")], texture);
+            assert!(extracted.import_report.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_extract_strips_bom() {
+            // Given a zip whose pack info and a recipe start with a byte order mark, as some Windows editors write them
+            let pack_info_string = serde_json::to_string(&PackInfo::default_data()).unwrap();
+            let recipe = r#"{"type": "minecraft:smelting"}"#;
+
+            let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(format!("\u{feff}{}", pack_info_string).as_bytes()).unwrap();
+
+            zip.add_directory::<&str, ExtendedFileOptions>("data", Default::default()).unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("data/test/recipe/stone.json", FileOptions::default()).unwrap();
+            zip.write_all(format!("\u{feff}{}", recipe).as_bytes()).unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("data/test/function/hi.mcfunction", FileOptions::default()).unwrap();
+            zip.write_all(b"say hi").unwrap();
+
+            let zip_archive = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+            // When I deserialize it
+            let project = Project::extract("Test Project", zip_archive).await.unwrap();
+
+            // Then the files should load without the mark, which is remembered for each of them
+            let recipe_path = PathBuf::from("data/test/recipe/stone.json");
+            assert_eq!(project.files[&recipe_path], recipe);
+            assert!(serde_json::from_str::<serde_json::Value>(&project.files[&recipe_path]).is_ok());
+            assert_eq!(project.bom_files, HashSet::from([PathBuf::from(PACK_INFO_FILE), recipe_path.clone()]));
+
+            // And zipping it again should put the mark back on only those files
+            let mut zipped = ZipArchive::new(Cursor::new(project.zip().await.unwrap())).unwrap();
+            let mut read_entry = |name: &str| {
+                let mut bytes = Vec::new();
+                zipped.by_name(name).unwrap().read_to_end(&mut bytes).unwrap();
+                bytes
+            };
+
+            assert_eq!(read_entry("data/test/recipe/stone.json"), format!("\u{feff}{}", recipe).into_bytes());
+            assert!(read_entry(PACK_INFO_FILE).starts_with("\u{feff}".as_bytes()));
+            assert_eq!(read_entry("data/test/function/hi.mcfunction"), b"say hi");
+        }
+
+        #[tokio::test]
+        async fn test_extract_clean_pack_has_empty_report() {
+            // Given a zip file with only files the game loads
+            let pack_info = PackInfo::default_data();
+            let pack_info_string = serde_json::to_string(&pack_info).unwrap();
+
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(pack_info_string.as_bytes()).unwrap();
+
+            zip.add_directory::<&str, ExtendedFileOptions>("data", Default::default()).unwrap();
+
+            zip.start_file::<&str, ExtendedFileOptions>("data/test.json", FileOptions::default()).unwrap();
+            zip.write_all(r#"{"test": "test"}"#.as_bytes()).unwrap();
+
+            let zip_data = zip.finish().unwrap();
+            let zip_archive = ZipArchive::new(zip_data).unwrap();
+
+            // When I deserialize it
+            let project = Project::extract("Test Project", zip_archive).await.unwrap();
+
+            // Then there should be nothing to report
+            assert!(project.import_report.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_extract_unknown_pack_format() {
+            // Given a zip file whose pack.mcmeta has a pack format no known version uses, e.g. from a newer version
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(r#"{"pack": {"pack_format": 3000, "description": "Test Pack"}}"#.as_bytes()).unwrap();
+
+            zip.add_directory::<&str, ExtendedFileOptions>("data", Default::default()).unwrap();
+
+            let zip_data = zip.finish().unwrap();
+            let zip_archive = ZipArchive::new(zip_data).unwrap();
+
+            // When I deserialize it
+            let project = Project::extract("Test Project", zip_archive).await.unwrap();
+
+            // Then it should be imported, with the format reported as a warning
+            let suspect_fields = &project.import_report.suspect_fields;
+            assert_eq!(suspect_fields.len(), 1);
+            assert_eq!(suspect_fields[0].field, "pack.pack_format");
+            assert!(!suspect_fields[0].is_error());
+        }
+
+        #[tokio::test]
+        async fn test_extract_invalid_pack_info() {
+            // Given a zip file whose pack.mcmeta has no description
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(r#"{"pack": {"pack_format": 71}}"#.as_bytes()).unwrap();
+
+            zip.add_directory::<&str, ExtendedFileOptions>("data", Default::default()).unwrap();
+
+            let zip_data = zip.finish().unwrap();
+            let zip_archive = ZipArchive::new(zip_data).unwrap();
+
+            // When I deserialize it
+            let result = Project::extract("Test Project", zip_archive).await;
+
+            // It should be refused, pointing at the bad field
+            let Err(SerializedProjectError::InvalidPackInfo(errors)) = result else {
+                panic!("Expected invalid pack info, got {:?}", result);
+            };
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].field, "pack.description");
+        }
+
         #[rstest]
         #[case::path_escapes_zip("../escapes_zip")]
         #[case::absolute_path("/absolute_path")]
+        #[case::nested_escape("../../etc/passwd")]
+        #[case::escape_after_normal_segment("data/../../escapes_zip")]
+        #[case::windows_separator("..\\..\\escapes_zip")]
+        #[case::windows_drive("C:/Windows/escapes_zip")]
         #[tokio::test]
         async fn test_unsafe_path(#[case] unsafe_path: &str) {
             // Given a zip with an unsafe path
@@ -275,6 +948,169 @@ mod tests {
             assert!(matches!(result.unwrap_err(), SerializedProjectError::InvalidZipFile(_)));
         }
         
+        #[tokio::test]
+        async fn test_unsafe_directory_path() {
+            // Given a zip with a directory entry that escapes the archive
+            let pack_info = PackInfo::default_data();
+            let pack_info_string = serde_json::to_string(&pack_info).unwrap();
+
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(pack_info_string.as_bytes()).unwrap();
+
+            zip.add_directory::<&str, ExtendedFileOptions>("data", Default::default()).unwrap();
+            zip.add_directory::<&str, ExtendedFileOptions>("../../escapes_zip", Default::default()).unwrap();
+
+            let zip_data = zip.finish().unwrap();
+            let zip_archive = ZipArchive::new(zip_data).unwrap();
+
+            // When I deserialize it
+            let result = Project::extract("Test Project", zip_archive).await;
+
+            // Then it should be refused
+            assert!(matches!(result, Err(SerializedProjectError::InvalidZipFile(_))));
+        }
+        
+        #[tokio::test]
+        async fn test_extract_mod_jar() {
+            // Given a mod jar, which has a pack.mcmeta but neither a data nor an assets folder
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            for (name, contents) in [
+                ("META-INF/MANIFEST.MF", "Manifest-Version: 1.0"),
+                ("fabric.mod.json", r#"{"id": "example"}"#),
+                ("pack.mcmeta", r#"{"pack": {"pack_format": 48, "description": "Example Mod"}}"#),
+                ("com/example/ExampleMod.class", ""),
+            ] {
+                zip.start_file::<&str, ExtendedFileOptions>(name, FileOptions::default()).unwrap();
+                zip.write_all(contents.as_bytes()).unwrap();
+            }
+
+            let zip_data = zip.finish().unwrap();
+            let zip_archive = ZipArchive::new(zip_data).unwrap();
+
+            // When I deserialize it
+            let result = Project::extract("example-mod", zip_archive).await;
+
+            // Then it should be refused as not being a pack at all
+            assert!(matches!(result, Err(SerializedProjectError::NotAPack(name)) if name == "example-mod"));
+        }
+
+        #[tokio::test]
+        async fn test_extract_without_directory_entries() {
+            // Given a resource pack zipped without entries for its directories
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(serde_json::to_string(&PackInfo::default_resource()).unwrap().as_bytes()).unwrap();
+            zip.start_file::<&str, ExtendedFileOptions>("assets/test/lang/en_us.json", FileOptions::default()).unwrap();
+            zip.write_all(b"{}").unwrap();
+
+            let zip_archive = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+            // When I deserialize it
+            let project = Project::extract("Test Project", zip_archive).await.unwrap();
+
+            // Then it should be recognised from its files
+            assert!(matches!(project.project_type, SerializedProjectType::Resource));
+        }
+
+        #[rstest]
+        #[case::only_pack_info(&[], 0)]
+        #[case::only_root_files(&[("pack.png", "icon"), ("README.txt", "Read me")], 0)]
+        #[case::resources(&[("data/test/function/load.mcfunction", "say hi"), ("data/test/recipe/stone.json", "{}"), ("README.txt", "Read me")], 2)]
+        #[tokio::test]
+        async fn test_resource_count(#[case] entries: &[(&str, &str)], #[case] expected: usize) {
+            // Given a data pack with the given files besides its pack.mcmeta
+            let buffer = Cursor::new(Vec::new());
+            let mut zip = ZipWriter::new(buffer);
+
+            zip.start_file::<&str, ExtendedFileOptions>("pack.mcmeta", FileOptions::default()).unwrap();
+            zip.write_all(serde_json::to_string(&PackInfo::default_data()).unwrap().as_bytes()).unwrap();
+            zip.add_directory::<&str, ExtendedFileOptions>("data", Default::default()).unwrap();
+
+            for (name, contents) in entries {
+                zip.start_file::<&str, ExtendedFileOptions>(*name, FileOptions::default()).unwrap();
+                zip.write_all(contents.as_bytes()).unwrap();
+            }
+
+            let zip_archive = ZipArchive::new(zip.finish().unwrap()).unwrap();
+
+            // When I extract it
+            let project = Project::extract("Test Project", zip_archive).await.unwrap();
+
+            // Then only the files inside a namespace should count as resources
+            assert_eq!(project.resource_count(), expected);
+            assert_eq!(project.is_empty(), expected == 0);
+        }
+
         // TODO: test data vs resource pack detection based on structure
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_report_diagnostics() {
+        // Given a report with one of each finding
+        let report = ImportReport {
+            unreadable_files: vec![PathBuf::from("data/test/function/broken.mcfunction")],
+            unknown_files: vec![PathBuf::from("README.txt")],
+            suspect_fields: vec![ValidationError::warning("pack.description", validation::ValidationIssue::InvalidTextComponent)],
+            empty_pack: true,
+        };
+
+        // When I convert it to diagnostics
+        let diagnostics = report.diagnostics();
+
+        // Then each should have its own rule, with suspect fields reported against the pack info
+        let summary: Vec<_> = diagnostics.iter()
+            .map(|diagnostic| (diagnostic.path.to_str().unwrap(), diagnostic.severity, diagnostic.rule.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("data/test/function/broken.mcfunction", DiagnosticSeverity::Warning, "unreadable-file"),
+            ("README.txt", DiagnosticSeverity::Info, "unknown-file"),
+            (PACK_INFO_FILE, DiagnosticSeverity::Warning, "pack-info"),
+            (PACK_INFO_FILE, DiagnosticSeverity::Warning, "empty-pack"),
+        ]);
+        assert_eq!(diagnostics[2].message, "pack.description: Not a valid text component");
+    }
+
+    #[tokio::test]
+    async fn test_split_mixed() {
+        // Given a project with both data and assets, and files outside of both
+        let project = Project::with_name("Test project".to_string(), SerializedProjectType::Data, PackInfo::default_data())
+            .with_files(HashMap::from([
+                (PathBuf::from(PACK_INFO_FILE), "{}".to_string()),
+                (PathBuf::from("pack.png"), "png".to_string()),
+                (PathBuf::from("data/test/function/load.mcfunction"), "say hi".to_string()),
+                (PathBuf::from("assets/test/lang/en_us.json"), "{}".to_string()),
+            ]));
+        assert!(project.is_mixed());
+
+        // When I split it
+        let (data_project, resource_project) = project.split_mixed(PackInfo::default_resource());
+
+        // Then each pack should have its own files and the files outside of both, and neither should be mixed
+        let paths = |project: &Project| {
+            let mut paths: Vec<_> = project.files.keys().cloned().collect();
+            paths.sort();
+            paths
+        };
+        assert_eq!(paths(&data_project), vec![
+            PathBuf::from("data/test/function/load.mcfunction"),
+            PathBuf::from(PACK_INFO_FILE),
+            PathBuf::from("pack.png"),
+        ]);
+        assert_eq!(paths(&resource_project), vec![
+            PathBuf::from("assets/test/lang/en_us.json"),
+            PathBuf::from(PACK_INFO_FILE),
+            PathBuf::from("pack.png"),
+        ]);
+
+        assert!(matches!(resource_project.project_type, SerializedProjectType::Resource));
+        assert_eq!(*resource_project.pack_info.read().await, PackInfo::default_resource());
+        assert!(!data_project.is_mixed() && !resource_project.is_mixed());
+    }
+}
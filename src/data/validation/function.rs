@@ -0,0 +1,153 @@
+use crate::data::validation::{ValidationError, ValidationIssue};
+
+/// Commands whose last argument is free text running to the end of the line, which may hold unmatched brackets or quotes
+const MESSAGE_COMMANDS: &[&str] = &["say", "me", "msg", "tell", "w", "teammsg", "tm"];
+
+/// Checks the brackets and quoted strings of each command in an `.mcfunction` file, returning every problem found.
+/// The game refuses the whole function if any command fails to parse, so these are all errors.
+/// Commands aren't checked any further, so the fields are only named by the line the command starts on, such as `line 3`
+pub fn validate_str(contents: &str) -> Vec<ValidationError> {
+    commands(contents).into_iter()
+        .filter_map(|(line, command)| validate_command(&command)
+            .map(|issue| ValidationError::error(format!("line {}", line), issue)))
+        .collect()
+}
+
+/// Each command with the line number it starts on, joining lines continued with a trailing backslash
+fn commands(contents: &str) -> Vec<(usize, String)> {
+    let mut commands = Vec::new();
+    let mut continued: Option<(usize, String)> = None;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        let (start, mut command) = match continued.take() {
+            Some((start, command)) => (start, command),
+            None if line.is_empty() || line.starts_with('#') => continue,
+            None => (index + 1, String::new()),
+        };
+
+        match line.strip_suffix('\\') {
+            Some(line) => {
+                command.push_str(line);
+                continued = Some((start, command));
+            }
+            None => {
+                command.push_str(line);
+                commands.push((start, command));
+            }
+        }
+    }
+
+    commands.extend(continued);
+    commands
+}
+
+fn validate_command(command: &str) -> Option<ValidationIssue> {
+    // Macro lines are marked with a leading `$`, and otherwise read like any other command
+    let command = command.strip_prefix('$').unwrap_or(command);
+
+    let mut open_brackets = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut is_escaped = false;
+    let mut word_start = 0;
+    let mut previous_word = "";
+
+    for (index, c) in command.char_indices() {
+        if let Some(open_quote) = quote {
+            if is_escaped {
+                is_escaped = false;
+            } else if c == '\\' {
+                is_escaped = true;
+            } else if c == open_quote {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' if starts_argument(command, index) => quote = Some(c),
+            '{' | '[' | '(' => open_brackets.push(c),
+            '}' | ']' | ')' => {
+                if open_brackets.pop() != Some(opening_bracket(c)) {
+                    return Some(ValidationIssue::UnexpectedBracket(c));
+                }
+            }
+            c if c.is_whitespace() && open_brackets.is_empty() => {
+                let word = &command[word_start..index];
+
+                // The rest of the line is the message, which can say anything
+                if MESSAGE_COMMANDS.contains(&word) && matches!(previous_word, "" | "run") {
+                    return None;
+                }
+
+                if !word.is_empty() {
+                    previous_word = word;
+                }
+                word_start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    if quote.is_some() {
+        Some(ValidationIssue::UnclosedString)
+    } else {
+        open_brackets.last().map(|bracket| ValidationIssue::UnclosedBracket(*bracket))
+    }
+}
+
+/// Quotes only start a string at the start of an argument, so apostrophes within a word, as in `it's`, are left alone
+fn starts_argument(command: &str, index: usize) -> bool {
+    command[..index].chars()
+        .next_back()
+        .is_none_or(|previous| previous.is_whitespace() || matches!(previous, '{' | '[' | '(' | ',' | ':' | '='))
+}
+
+fn opening_bracket(closing: char) -> char {
+    match closing {
+        '}' => '{',
+        ']' => '[',
+        _ => '(',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[rstest]
+    #[case::plain("say hi\nfunction example:load\n")]
+    #[case::nbt(r#"give @s diamond_sword[custom_name='{"text":"Gem"}']"#)]
+    #[case::json_text(r#"tellraw @a {"text":"[Gems] it's ready :)"}"#)]
+    #[case::message_with_brackets("say Done :)")]
+    #[case::executed_message("execute as @a run say [loading")]
+    #[case::apostrophe_in_word("say it's me")]
+    #[case::comment("# TODO: {\nsay hi")]
+    #[case::macro_line("$tp @s $(x) $(y) $(z)")]
+    #[case::continuation("summon zombie ~ ~ ~ {\\\n    CustomName:'\"Bob\"'\\\n}")]
+    fn test_valid_function(#[case] contents: &str) {
+        // Given a function which the game can load
+        // When I validate it
+        let errors = validate_str(contents);
+
+        // Then there should be no problems
+        assert_eq!(errors, vec![]);
+    }
+
+    #[rstest]
+    #[case::unclosed_bracket("say hi\ngive @s stone[count=2", "line 2", ValidationIssue::UnclosedBracket('['))]
+    #[case::unexpected_bracket("data merge entity @s {Tags:[]}}", "line 1", ValidationIssue::UnexpectedBracket('}'))]
+    #[case::mismatched_bracket("data merge entity @s {Tags:[}", "line 1", ValidationIssue::UnexpectedBracket('}'))]
+    #[case::unclosed_string(r#"tellraw @a {"text":"hi}"#, "line 1", ValidationIssue::UnclosedString)]
+    #[case::continued_line("\nsummon zombie ~ ~ ~ {\\\n    NoAI:1b\\\n", "line 2", ValidationIssue::UnclosedBracket('{'))]
+    fn test_invalid_function(#[case] contents: &str, #[case] field: &str, #[case] issue: ValidationIssue) {
+        // Given a function with a command the game can't parse
+        // When I validate it
+        let errors = validate_str(contents);
+
+        // Then the problem should be reported against the line the command starts on
+        assert_eq!(errors, vec![ValidationError::error(field, issue)]);
+    }
+}
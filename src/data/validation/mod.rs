@@ -0,0 +1,79 @@
+use std::fmt::{Display, Formatter};
+
+pub mod function;
+pub mod pack_info;
+pub mod text_component;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ValidationSeverity {
+    /// The game accepts the file, but probably not as intended
+    Warning,
+    /// The game refuses the file
+    Error,
+}
+
+/// A problem with one field of a file.
+/// Fields are named by their dotted path from the root of the file, such as `pack.pack_format`
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub struct ValidationError {
+    pub field: String,
+    pub severity: ValidationSeverity,
+    pub issue: ValidationIssue,
+}
+
+impl ValidationError {
+    pub fn error(field: impl Into<String>, issue: ValidationIssue) -> Self {
+        Self { field: field.into(), severity: ValidationSeverity::Error, issue }
+    }
+
+    pub fn warning(field: impl Into<String>, issue: ValidationIssue) -> Self {
+        Self { field: field.into(), severity: ValidationSeverity::Warning, issue }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == ValidationSeverity::Error
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.issue)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum ValidationIssue {
+    #[error("File is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("Required field is missing")]
+    MissingField,
+    #[error("Expected {0}")]
+    WrongType(&'static str),
+    #[error("{0} is not the pack format of any known version")]
+    UnknownPackFormat(u64),
+    #[error("Not a valid text component")]
+    InvalidTextComponent,
+    #[error("`{0}` is not a color name or a #RRGGBB hex color")]
+    InvalidColor(String),
+    #[error("Minimum format {min} is greater than maximum format {max}")]
+    InvertedFormatRange { min: u64, max: u64 },
+    #[error("Pack format {0} is not within the supported formats")]
+    OutsideSupportedFormats(u64),
+    #[error("Formats overlap with overlay `{0}`, so which files apply depends on the order of the overlays")]
+    OverlappingOverlay(String),
+    #[error("Formats {min} to {max} are all outside the pack's supported formats, so the overlay never applies")]
+    OverlayNeverApplies { min: u64, max: u64 },
+    #[error("Unknown key, which the game ignores")]
+    UnknownKey,
+    #[error("`{0}` is never closed")]
+    UnclosedBracket(char),
+    #[error("`{0}` doesn't close an open bracket")]
+    UnexpectedBracket(char),
+    #[error("Quoted string is never closed")]
+    UnclosedString,
+}
+
+/// Whether any of the problems would make the game refuse the file
+pub fn has_errors(errors: &[ValidationError]) -> bool {
+    errors.iter().any(ValidationError::is_error)
+}
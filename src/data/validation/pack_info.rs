@@ -0,0 +1,320 @@
+use serde_json::{Map, Value};
+use crate::data::domain::versions::{DATA_FORMAT_MAP, RESOURCE_FORMAT_MAP};
+use crate::data::validation::{text_component, ValidationError, ValidationIssue};
+
+/// Top level keys the game reads from `pack.mcmeta`
+const KNOWN_KEYS: &[&str] = &["pack", "features", "filter", "overlays", "language"];
+
+/// Checks the contents of a `pack.mcmeta` file, returning every problem found
+pub fn validate_str(contents: &str) -> Vec<ValidationError> {
+    match serde_json::from_str::<Value>(contents) {
+        Ok(json) => validate(&json),
+        Err(error) => vec![ValidationError::error("", ValidationIssue::InvalidJson(error.to_string()))],
+    }
+}
+
+/// Checks a parsed `pack.mcmeta` file, returning every problem found
+pub fn validate(json: &Value) -> Vec<ValidationError> {
+    let Some(root) = json.as_object() else {
+        return vec![ValidationError::error("", ValidationIssue::WrongType("an object"))];
+    };
+
+    let mut errors = Vec::new();
+
+    let supported_formats = match root.get("pack") {
+        Some(Value::Object(pack)) => validate_pack(pack, &mut errors),
+        Some(_) => {
+            errors.push(ValidationError::error("pack", ValidationIssue::WrongType("an object")));
+            None
+        }
+        None => {
+            errors.push(ValidationError::error("pack", ValidationIssue::MissingField));
+            None
+        }
+    };
+
+    if let Some(overlays) = root.get("overlays") {
+        validate_overlays(overlays, supported_formats, &mut errors);
+    }
+
+    errors.extend(root.keys()
+        .filter(|key| !KNOWN_KEYS.contains(&key.as_str()))
+        .map(|key| ValidationError::warning(key.as_str(), ValidationIssue::UnknownKey)));
+
+    errors
+}
+
+/// Checks the `pack` section, returning the range of formats the pack supports if it could be read
+fn validate_pack(pack: &Map<String, Value>, errors: &mut Vec<ValidationError>) -> Option<(u64, u64)> {
+    let pack_format = match pack.get("pack_format") {
+        Some(Value::Number(number)) => match number.as_u64() {
+            Some(format) => {
+                // Packs for versions newer than the editor knows about use formats it hasn't seen, so these still load
+                if !is_known_format(format) {
+                    errors.push(ValidationError::warning("pack.pack_format", ValidationIssue::UnknownPackFormat(format)));
+                }
+                Some(format)
+            }
+            None => {
+                errors.push(ValidationError::error("pack.pack_format", ValidationIssue::WrongType("a whole number")));
+                None
+            }
+        },
+        Some(_) => {
+            errors.push(ValidationError::error("pack.pack_format", ValidationIssue::WrongType("a whole number")));
+            None
+        }
+        None => {
+            errors.push(ValidationError::error("pack.pack_format", ValidationIssue::MissingField));
+            None
+        }
+    };
+
+    match pack.get("description") {
+        Some(description) => text_component::validate(description, "pack.description", errors),
+        None => errors.push(ValidationError::error("pack.description", ValidationIssue::MissingField)),
+    }
+
+    let supported_formats = pack.get("supported_formats")
+        .and_then(|supported_formats| format_range(supported_formats, "pack.supported_formats", errors));
+
+    // By the pack.mcmeta spec, pack format must be included within supported formats
+    if let (Some((min, max)), Some(format)) = (supported_formats, pack_format) {
+        if !(min..=max).contains(&format) {
+            errors.push(ValidationError::error("pack.supported_formats", ValidationIssue::OutsideSupportedFormats(format)));
+        }
+    }
+
+    supported_formats.or(pack_format.map(|format| (format, format)))
+}
+
+/// Checks the overlay entries, and that their format ranges neither overlap each other nor fall outside the formats the pack supports.
+/// The game applies every overlay matching its format in the order listed, so overlapping ranges make the files used depend on that order
+fn validate_overlays(overlays: &Value, supported_formats: Option<(u64, u64)>, errors: &mut Vec<ValidationError>) {
+    let Some(overlays) = overlays.as_object() else {
+        errors.push(ValidationError::error("overlays", ValidationIssue::WrongType("an object")));
+        return;
+    };
+
+    let entries = match overlays.get("entries") {
+        Some(Value::Array(entries)) => entries,
+        Some(_) => {
+            errors.push(ValidationError::error("overlays.entries", ValidationIssue::WrongType("a list")));
+            return;
+        }
+        None => {
+            errors.push(ValidationError::error("overlays.entries", ValidationIssue::MissingField));
+            return;
+        }
+    };
+
+    let mut ranges: Vec<(&str, (u64, u64))> = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let field = format!("overlays.entries[{}]", index);
+
+        let Some(entry) = entry.as_object() else {
+            errors.push(ValidationError::error(field, ValidationIssue::WrongType("an object")));
+            continue;
+        };
+
+        let directory = match entry.get("directory") {
+            Some(Value::String(directory)) => Some(directory.as_str()),
+            Some(_) => {
+                errors.push(ValidationError::error(format!("{}.directory", field), ValidationIssue::WrongType("a directory name")));
+                None
+            }
+            None => {
+                errors.push(ValidationError::error(format!("{}.directory", field), ValidationIssue::MissingField));
+                None
+            }
+        };
+
+        let formats_field = format!("{}.formats", field);
+        let formats = match entry.get("formats") {
+            Some(formats) => format_range(formats, &formats_field, errors),
+            None => {
+                errors.push(ValidationError::error(&formats_field, ValidationIssue::MissingField));
+                None
+            }
+        };
+
+        let (Some(directory), Some((min, max))) = (directory, formats) else {
+            continue;
+        };
+
+        if let Some((supported_min, supported_max)) = supported_formats {
+            if max < supported_min || min > supported_max {
+                errors.push(ValidationError::warning(&formats_field, ValidationIssue::OverlayNeverApplies { min, max }));
+            }
+        }
+
+        for (other, (other_min, other_max)) in &ranges {
+            if min <= *other_max && *other_min <= max {
+                errors.push(ValidationError::warning(&formats_field, ValidationIssue::OverlappingOverlay(other.to_string())));
+            }
+        }
+
+        ranges.push((directory, (min, max)));
+    }
+}
+
+fn is_known_format(format: u64) -> bool {
+    u8::try_from(format).is_ok_and(|format| DATA_FORMAT_MAP.contains_key(&format) || RESOURCE_FORMAT_MAP.contains_key(&format))
+}
+
+/// Reads a format range given as a single format, `[min, max]`, or `{"min_inclusive": min, "max_inclusive": max}`
+fn format_range(value: &Value, field: &str, errors: &mut Vec<ValidationError>) -> Option<(u64, u64)> {
+    const EXPECTED: &str = "a format, a [min, max] pair, or an object with min_inclusive and max_inclusive";
+
+    let (min, max) = match value {
+        Value::Number(format) => (format.as_u64(), format.as_u64()),
+        Value::Array(bounds) if bounds.len() == 2 => (bounds[0].as_u64(), bounds[1].as_u64()),
+        Value::Object(bounds) => (
+            bounds.get("min_inclusive").and_then(Value::as_u64),
+            bounds.get("max_inclusive").and_then(Value::as_u64),
+        ),
+        _ => (None, None),
+    };
+
+    let (Some(min), Some(max)) = (min, max) else {
+        errors.push(ValidationError::error(field, ValidationIssue::WrongType(EXPECTED)));
+        return None;
+    };
+
+    if min > max {
+        errors.push(ValidationError::error(field, ValidationIssue::InvertedFormatRange { min, max }));
+        return None;
+    }
+
+    Some((min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+    use crate::data::validation::{has_errors, ValidationSeverity};
+
+    #[test]
+    fn test_valid_pack_info() {
+        // Given a pack.mcmeta using every kind of supported formats and a rich description
+        let json = json!({
+            "pack": {
+                "pack_format": 71,
+                "description": [{"text": "Test ", "color": "gold"}, {"translate": "pack.description", "extra": ["!"]}],
+                "supported_formats": {"min_inclusive": 61, "max_inclusive": 71}
+            },
+            "overlays": {"entries": []}
+        });
+
+        // When I validate it
+        let errors = validate(&json);
+
+        // Then there should be no problems
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_unknown_pack_format() {
+        // Given a pack.mcmeta with a format no version uses
+        let json = json!({
+            "pack": {
+                "pack_format": 3000,
+                "description": "Test Pack"
+            }
+        });
+
+        // When I validate it
+        let errors = validate(&json);
+
+        // Then the format field should be warned about, without stopping the pack loading
+        assert_eq!(errors, vec![ValidationError::warning("pack.pack_format", ValidationIssue::UnknownPackFormat(3000))]);
+    }
+
+    #[test]
+    fn test_malformed_description() {
+        // Given a pack.mcmeta whose description has no content
+        let json = json!({
+            "pack": {
+                "pack_format": 71,
+                "description": {"color": "red"}
+            }
+        });
+
+        // When I validate it
+        let errors = validate(&json);
+
+        // Then the description field should be reported
+        assert_eq!(errors, vec![ValidationError::error("pack.description", ValidationIssue::InvalidTextComponent)]);
+    }
+
+    #[test]
+    fn test_supported_formats_and_unknown_keys() {
+        // Given a pack.mcmeta with an inverted supported formats range, a format outside another, and an unknown key
+        let inverted = json!({
+            "pack": {"pack_format": 71, "description": "Test Pack", "supported_formats": [71, 61]},
+            "credits": "Me"
+        });
+        let outside = json!({
+            "pack": {"pack_format": 71, "description": "Test Pack", "supported_formats": 61}
+        });
+
+        // When I validate them
+        let inverted_errors = validate(&inverted);
+        let outside_errors = validate(&outside);
+
+        // Then the range should be an error, and the unknown key only a warning
+        assert_eq!(inverted_errors, vec![
+            ValidationError::error("pack.supported_formats", ValidationIssue::InvertedFormatRange { min: 71, max: 61 }),
+            ValidationError::warning("credits", ValidationIssue::UnknownKey),
+        ]);
+        assert_eq!(outside_errors, vec![ValidationError::error("pack.supported_formats", ValidationIssue::OutsideSupportedFormats(71))]);
+
+        assert_eq!(inverted_errors[1].severity, ValidationSeverity::Warning);
+        assert!(has_errors(&inverted_errors));
+    }
+
+    #[test]
+    fn test_valid_overlays() {
+        // Given overlays with disjoint ranges, written in each of the ways a range can be
+        let json = json!({
+            "pack": {"pack_format": 71, "description": "Test Pack", "supported_formats": [48, 71]},
+            "overlays": {"entries": [
+                {"formats": [48, 57], "directory": "v1_21"},
+                {"formats": {"min_inclusive": 61, "max_inclusive": 70}, "directory": "v1_21_4"},
+                {"formats": 71, "directory": "v1_21_5"}
+            ]}
+        });
+
+        // When I validate it
+        let errors = validate(&json);
+
+        // Then there should be no problems
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_contradictory_overlays() {
+        // Given overlays where the second overlaps the first, the third never applies, and the last is missing its directory
+        let json = json!({
+            "pack": {"pack_format": 71, "description": "Test Pack", "supported_formats": [48, 71]},
+            "overlays": {"entries": [
+                {"formats": [48, 61], "directory": "old"},
+                {"formats": [57, 71], "directory": "new"},
+                {"formats": [15, 26], "directory": "ancient"},
+                {"formats": 71}
+            ]}
+        });
+
+        // When I validate it
+        let errors = validate(&json);
+
+        // Then each contradiction should be reported against the later entry, with only the missing directory an error
+        assert_eq!(errors, vec![
+            ValidationError::warning("overlays.entries[1].formats", ValidationIssue::OverlappingOverlay("old".to_string())),
+            ValidationError::warning("overlays.entries[2].formats", ValidationIssue::OverlayNeverApplies { min: 15, max: 26 }),
+            ValidationError::error("overlays.entries[3].directory", ValidationIssue::MissingField),
+        ]);
+    }
+}
@@ -0,0 +1,229 @@
+use serde_json::{Map, Value};
+use crate::data::validation::{ValidationError, ValidationIssue};
+
+/// Keys which give a text component its content, at least one of which must be present in an object component
+const CONTENT_KEYS: &[&str] = &["text", "translate", "score", "selector", "keybind", "nbt"];
+
+/// Keys whose value is a plain string
+const STRING_KEYS: &[&str] = &[
+    "type", "text", "translate", "fallback", "selector", "keybind", "nbt", "block", "entity", "storage", "source",
+    "font", "insertion",
+];
+
+const BOOLEAN_KEYS: &[&str] = &["bold", "italic", "underlined", "strikethrough", "obfuscated", "interpret"];
+
+/// Click and hover events, under both their older camel case names and the snake case names of newer versions
+const EVENT_KEYS: &[&str] = &["clickEvent", "hoverEvent", "click_event", "hover_event"];
+
+/// Keys whose value is itself a text component
+const COMPONENT_KEYS: &[&str] = &["separator"];
+
+/// Keys whose value is a list of text components
+const COMPONENT_LIST_KEYS: &[&str] = &["extra", "with"];
+
+const NAMED_COLORS: &[&str] = &[
+    "black", "dark_blue", "dark_green", "dark_aqua", "dark_red", "dark_purple", "gold", "gray",
+    "dark_gray", "blue", "green", "aqua", "red", "light_purple", "yellow", "white",
+];
+
+/// Checks a value which should be a text component, such as a pack description, adding every problem found.
+/// Text components are plain text, lists of components, or objects with some content and optionally more components after it
+pub fn validate(value: &Value, field: &str, errors: &mut Vec<ValidationError>) {
+    match value {
+        Value::String(_) | Value::Number(_) | Value::Bool(_) => {}
+        Value::Array(components) if components.is_empty() => {
+            errors.push(ValidationError::error(field, ValidationIssue::InvalidTextComponent));
+        }
+        Value::Array(components) => {
+            for (index, component) in components.iter().enumerate() {
+                validate(component, &format!("{}[{}]", field, index), errors);
+            }
+        }
+        Value::Object(component) if !has_content(component) => {
+            errors.push(ValidationError::error(field, ValidationIssue::InvalidTextComponent));
+        }
+        Value::Object(component) => validate_object(component, field, errors),
+        Value::Null => errors.push(ValidationError::error(field, ValidationIssue::InvalidTextComponent)),
+    }
+}
+
+/// Checks a JSON file which may have text components anywhere within it, such as an advancement's title
+/// or an item's custom name, returning every problem found in them
+pub fn validate_str(contents: &str) -> Vec<ValidationError> {
+    match serde_json::from_str::<Value>(contents) {
+        Ok(json) => validate_embedded(&json),
+        Err(error) => vec![ValidationError::error("", ValidationIssue::InvalidJson(error.to_string()))],
+    }
+}
+
+/// Finds the text component objects within a JSON value and checks each of them.
+/// Only objects are recognized, as a plain string can't be told apart from any other string
+pub fn validate_embedded(json: &Value) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    find_components(json, "", &mut errors);
+    errors
+}
+
+fn find_components(value: &Value, field: &str, errors: &mut Vec<ValidationError>) {
+    match value {
+        Value::Object(object) if looks_like_component(object) => validate_object(object, field, errors),
+        Value::Object(object) => {
+            for (key, value) in object {
+                find_components(value, &child_field(field, key), errors);
+            }
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter().enumerate() {
+                find_components(value, &format!("{}[{}]", field, index), errors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `text` and `translate` are rare enough elsewhere to mark a component by themselves. The other content keys
+/// are common words, so an object with one of them is only taken for a component if all its keys are component keys
+fn looks_like_component(object: &Map<String, Value>) -> bool {
+    object.contains_key("text")
+        || object.contains_key("translate")
+        || (has_content(object) && object.keys().all(|key| is_known_key(key)))
+}
+
+fn has_content(component: &Map<String, Value>) -> bool {
+    CONTENT_KEYS.iter().any(|key| component.contains_key(*key))
+}
+
+fn is_known_key(key: &str) -> bool {
+    [STRING_KEYS, BOOLEAN_KEYS, EVENT_KEYS, COMPONENT_KEYS, COMPONENT_LIST_KEYS, &["score", "color", "shadow_color"]]
+        .iter()
+        .any(|keys| keys.contains(&key))
+}
+
+fn validate_object(component: &Map<String, Value>, field: &str, errors: &mut Vec<ValidationError>) {
+    for (key, value) in component {
+        let key_field = child_field(field, key);
+        let key = key.as_str();
+
+        if STRING_KEYS.contains(&key) && !value.is_string() {
+            errors.push(ValidationError::error(key_field, ValidationIssue::WrongType("text")));
+        } else if BOOLEAN_KEYS.contains(&key) && !value.is_boolean() {
+            errors.push(ValidationError::error(key_field, ValidationIssue::WrongType("true or false")));
+        } else if EVENT_KEYS.contains(&key) && !value.is_object() {
+            errors.push(ValidationError::error(key_field, ValidationIssue::WrongType("an object")));
+        } else if COMPONENT_KEYS.contains(&key) {
+            validate(value, &key_field, errors);
+        } else if COMPONENT_LIST_KEYS.contains(&key) {
+            match value {
+                Value::Array(_) => validate(value, &key_field, errors),
+                _ => errors.push(ValidationError::error(key_field, ValidationIssue::WrongType("a list of text components"))),
+            }
+        } else if key == "score" {
+            let is_score = value.as_object().is_some_and(|score| {
+                score.get("name").is_some_and(Value::is_string) && score.get("objective").is_some_and(Value::is_string)
+            });
+            if !is_score {
+                errors.push(ValidationError::error(key_field, ValidationIssue::WrongType("an object with a name and an objective")));
+            }
+        } else if key == "color" {
+            match value.as_str() {
+                Some(color) if is_color(color) => {}
+                Some(color) => errors.push(ValidationError::error(key_field, ValidationIssue::InvalidColor(color.to_string()))),
+                None => errors.push(ValidationError::error(key_field, ValidationIssue::WrongType("text"))),
+            }
+        } else if key == "shadow_color" {
+            let is_shadow_color = match value {
+                Value::Number(color) => color.is_i64(),
+                Value::Array(channels) => channels.len() == 4 && channels.iter().all(Value::is_number),
+                _ => false,
+            };
+            if !is_shadow_color {
+                errors.push(ValidationError::error(key_field, ValidationIssue::WrongType("a whole number or [red, green, blue, alpha]")));
+            }
+        } else if !is_known_key(key) {
+            errors.push(ValidationError::warning(key_field, ValidationIssue::UnknownKey));
+        }
+    }
+}
+
+/// Named colors, or hex colors written as `#RRGGBB`
+fn is_color(color: &str) -> bool {
+    NAMED_COLORS.contains(&color)
+        || color.strip_prefix('#').is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn child_field(field: &str, key: &str) -> String {
+    if field.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", field, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+    use crate::data::validation::has_errors;
+
+    #[test]
+    fn test_valid_text_component() {
+        // Given an advancement whose title and description use styled text components
+        let json = json!({
+            "display": {
+                "icon": {"id": "minecraft:diamond"},
+                "title": {"translate": "advancement.example.root", "color": "#55FFFF", "bold": true, "with": [{"score": {"name": "@s", "objective": "level"}}]},
+                "description": [{"text": "Find a "}, {"text": "diamond", "color": "aqua", "hoverEvent": {"action": "show_text", "contents": "Shiny"}, "extra": ["!"]}]
+            },
+            "criteria": {"found": {"trigger": "minecraft:inventory_changed"}}
+        });
+
+        // When I validate the file
+        let errors = validate_embedded(&json);
+
+        // Then there should be no problems
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_malformed_text_component() {
+        // Given an item modifier setting a name with a bad color, a misspelled key, a flag that isn't a boolean, and empty extras
+        let json = json!({
+            "function": "minecraft:set_name",
+            "name": {"text": "Ruby", "color": "crimson", "itallic": true, "bold": "yes", "extra": []}
+        });
+
+        // When I validate the file
+        let errors = validate_embedded(&json);
+
+        // Then each field should be reported, with the unknown key only a warning
+        assert_eq!(errors, vec![
+            ValidationError::error("name.bold", ValidationIssue::WrongType("true or false")),
+            ValidationError::error("name.color", ValidationIssue::InvalidColor("crimson".to_string())),
+            ValidationError::error("name.extra", ValidationIssue::InvalidTextComponent),
+            ValidationError::warning("name.itallic", ValidationIssue::UnknownKey),
+        ]);
+        assert!(has_errors(&errors));
+    }
+
+    #[test]
+    fn test_nested_component_without_content() {
+        // Given a component whose extra holds an object with nothing to show
+        let json = json!({"description": {"text": "Gems", "extra": [{"text": " and "}, {"color": "red"}]}});
+
+        // When I validate the file
+        let errors = validate_embedded(&json);
+
+        // Then the object should be reported by its position in the list
+        assert_eq!(errors, vec![ValidationError::error("description.extra[1]", ValidationIssue::InvalidTextComponent)]);
+    }
+
+    #[test]
+    fn test_objects_which_are_not_components() {
+        // Given a loot table number provider, which shares a key with score components
+        let json = json!({"rolls": {"type": "minecraft:score", "target": "this", "score": "kills", "scale": 0.5}});
+
+        // When I validate it
+        // Then nothing should be reported
+        assert!(validate_embedded(&json).is_empty());
+    }
+}
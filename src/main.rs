@@ -4,13 +4,17 @@
 use iced::{Font, Task};
 use once_cell::sync::Lazy;
 use tokio::runtime::Runtime;
-use tracing_subscriber::{EnvFilter, Registry};
+use tracing_subscriber::{reload, EnvFilter, Registry};
 use tracing_subscriber::fmt::layer;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use crate::application::gui::window::ApplicationWindow;
 use crate::application::app_context::AppContextBuilder;
 use crate::application::gui::window;
+use crate::application::cli;
+use crate::application::logging::{self, EventLog, LogLevelHandle};
+#[cfg(feature = "translation-export")]
+use crate::application::translation_export;
 
 mod application;
 mod services;
@@ -25,8 +29,22 @@ pub static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
 pub fn main() -> iced::Result {
     setup_logging();
     
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = cli::CliCommand::parse(&args) {
+        std::process::exit(run_cli(command));
+    }
+    
+    #[cfg(feature = "translation-export")]
+    if std::env::args().any(|arg| arg == translation_export::EXPORT_TRANSLATIONS_FLAG) {
+        export_translations();
+        return Ok(());
+    }
+    
     iced::application("Gaea - Minecraft Resource and Datapack Editor", ApplicationWindow::update, ApplicationWindow::view)
         .theme(ApplicationWindow::theme)
+        .subscription(ApplicationWindow::subscription)
+        // Closing is decided by the window, so that unsaved work can be saved first
+        .exit_on_close_request(false)
         .font(include_bytes!("../resources/assets/fonts/icons.ttf").as_slice())
         .default_font(Font::DEFAULT)
         .run_with(create_application)
@@ -37,6 +55,42 @@ fn create_application() -> (ApplicationWindow, Task<window::Message>) {
     ApplicationWindow::new(app_context)
 }
 
+/// Runs a command line operation in place of the GUI, returning the exit code
+fn run_cli(command: Result<cli::CliCommand, cli::CliError>) -> i32 {
+    let filesystem = std::sync::Arc::new(tokio::sync::RwLock::new(services::filesystem_service::DefaultFilesystemProvider::new()));
+    let project_service = services::project_service::ProjectService::new(
+        services::project_service::DefaultProjectProvider::default(),
+        services::project_service::DefaultZipService::new(filesystem),
+        services::project_service::DefaultAdapterProvider::new(),
+    );
+    
+    // Polled outside of the runtime, as the project repository blocks on its locks,
+    // which tokio refuses to do from inside `block_on`
+    let _runtime = RUNTIME.enter();
+    let result = command.and_then(|command| iced::futures::executor::block_on(cli::run(command, &project_service)));
+    match result {
+        Ok(output) => {
+            println!("{}", output.text);
+            if output.success { 0 } else { 1 }
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            1
+        }
+    }
+}
+
+#[cfg(feature = "translation-export")]
+fn export_translations() {
+    let path = std::path::Path::new("./resources/assets/localization/en_us.json");
+    let filesystem = services::filesystem_service::FilesystemService::new();
+    
+    match RUNTIME.block_on(translation_export::export_english_skeleton(&filesystem, path)) {
+        Ok(()) => tracing::info!("Exported translation keys to {}", path.display()),
+        Err(error) => tracing::error!("Failed to export translation keys - {}", error),
+    }
+}
+
 fn setup_logging() {
     let filter_directives = if cfg!(debug_assertions) {
         "gaea=debug,iced=info,warn"
@@ -46,13 +100,24 @@ fn setup_logging() {
 
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(filter_directives));
+    
+    // Reloadable, so the level can be raised from the window when capturing logs for a bug report
+    let (env_filter, handle) = reload::Layer::new(env_filter);
+    logging::install(LogLevelHandle::new(handle));
+    
+    // Kept for the window's event log, so problems can be seen without a terminal
+    let event_log = EventLog::new(logging::EVENT_LOG_CAPACITY);
+    logging::install_event_log(event_log.clone());
 
+    // Logs go to stderr, so that the command line's output on stdout can be piped to other tools
     let fmt_layer = layer()
+        .with_writer(std::io::stderr)
         .with_target(true)
         .compact();
     
     Registry::default()
         .with(env_filter)
         .with(fmt_layer)
+        .with(event_log.layer())
         .init();
 }
\ No newline at end of file
@@ -1,10 +1,14 @@
 use std::any::{Any, TypeId};
-use std::fmt::{Debug, Formatter};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::sync::Arc;
 use dashmap::DashMap;
 use tokio::sync::{RwLock, RwLockReadGuard};
+use serde_json::Value;
 use crate::data::adapters::{Adapter, AdapterError, AdapterInput};
+use crate::data::adapters::cache::ParseCache;
+use crate::data::domain::compatibility::FeatureVersion;
 
 pub struct AdapterProviderContext<'a, AdpProvider: AdapterProvider + ?Sized>(pub Arc<RwLockReadGuard<'a, AdpProvider>>);
 
@@ -19,7 +23,7 @@ impl<'a, AdpProvider: AdapterProvider + ?Sized> AdapterProviderContext<'a, AdpPr
         Self(Arc::new(lock))
     }
 
-    pub async fn serialize<Domain, Serialized>(&self, domain: AdapterInput<&Domain>) -> Result<Serialized, AdapterRepoError>
+    pub async fn serialize<Domain, Serialized>(&self, domain: AdapterInput<'_, Domain>) -> Result<Serialized, AdapterRepoError>
     where
         Domain: Send + Sync + 'static,
         Serialized: Send + Sync + 'static
@@ -28,7 +32,7 @@ impl<'a, AdpProvider: AdapterProvider + ?Sized> AdapterProviderContext<'a, AdpPr
         adapter_repo.serialize(domain, self.clone()).await
     }
 
-    pub async fn deserialize<Serialized, Domain>(&self, serialized: AdapterInput<&Serialized>) -> Result<Domain, AdapterRepoError>
+    pub async fn deserialize<Serialized, Domain>(&self, serialized: AdapterInput<'_, Serialized>) -> Result<Domain, AdapterRepoError>
     where
         Domain: Send + Sync + 'static,
         Serialized: Send + Sync + 'static
@@ -36,6 +40,10 @@ impl<'a, AdpProvider: AdapterProvider + ?Sized> AdapterProviderContext<'a, AdpPr
         let adapter_repo = &*self.0;
         adapter_repo.deserialize(serialized, self.clone()).await
     }
+
+    pub fn parse_cache(&self) -> &ParseCache<Option<Value>> {
+        self.0.parse_cache()
+    }
 }
 
 #[async_trait::async_trait]
@@ -46,15 +54,21 @@ pub trait AdapterProvider: Send + Sync + 'static {
         Serialized: Send + Sync + 'static,
         Adp: Adapter<Serialized, Domain> + 'static + Send + Sync;
     
-    async fn serialize<Domain, Serialized>(&self, domain: AdapterInput<&Domain>, context: AdapterProviderContext<'_, Self>) -> Result<Serialized, AdapterRepoError>
+    async fn serialize<Domain, Serialized>(&self, domain: AdapterInput<'_, Domain>, context: AdapterProviderContext<'_, Self>) -> Result<Serialized, AdapterRepoError>
     where
         Domain: Send + Sync + 'static,
         Serialized: Send + Sync + 'static;
     
-    async fn deserialize<Serialized, Domain>(&self, serialized: AdapterInput<&Serialized>, context: AdapterProviderContext<'_, Self>) -> Result<Domain, AdapterRepoError>
+    async fn deserialize<Serialized, Domain>(&self, serialized: AdapterInput<'_, Serialized>, context: AdapterProviderContext<'_, Self>) -> Result<Domain, AdapterRepoError>
     where
         Domain: Send + Sync + 'static,
         Serialized: Send + Sync + 'static;
+
+    /// JSON files the adapters have already parsed, kept between conversions so unchanged files aren't parsed again
+    fn parse_cache(&self) -> &ParseCache<Option<Value>>;
+
+    /// What each registered adapter declared with [`Adapter::min_version`]
+    fn min_versions(&self) -> Vec<FeatureVersion>;
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -63,14 +77,124 @@ struct AdapterType {
     serialized: TypeId,
 }
 
+/// Registers adapters on a provider, refusing any which would replace a default or an adapter registered earlier.
+/// Passed to [`ProjectService::with_adapters`](crate::services::project_service::ProjectService::with_adapters)
+/// so features can add adapters for new file types without breaking the built-in ones
+pub struct AdapterRegistrationBuilder<'a, AdpProvider: AdapterProvider + ?Sized> {
+    provider: &'a AdpProvider,
+    /// The name of the adapter registered for each conversion, and whether it is a default
+    registered: HashMap<AdapterType, (&'static str, bool)>,
+    conflicts: Vec<AdapterConflict>,
+}
+
+impl<'a, AdpProvider: AdapterProvider + ?Sized> AdapterRegistrationBuilder<'a, AdpProvider> {
+    pub fn new(provider: &'a AdpProvider) -> Self {
+        Self {
+            provider,
+            registered: HashMap::new(),
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Registers one of the built-in adapters, which later registrations may not replace
+    pub fn register_default<Adp, Serialized, Domain>(&mut self) -> &mut Self
+    where
+        Domain: Send + Sync + 'static,
+        Serialized: Send + Sync + 'static,
+        Adp: Adapter<Serialized, Domain> + 'static + Send + Sync,
+    {
+        self.register_as::<Adp, Serialized, Domain>(true)
+    }
+
+    /// Registers an adapter for a new conversion. If the conversion already has an adapter,
+    /// that one is kept and the conflict is reported by [`AdapterRegistrationBuilder::finish`]
+    pub fn register<Adp, Serialized, Domain>(&mut self) -> &mut Self
+    where
+        Domain: Send + Sync + 'static,
+        Serialized: Send + Sync + 'static,
+        Adp: Adapter<Serialized, Domain> + 'static + Send + Sync,
+    {
+        self.register_as::<Adp, Serialized, Domain>(false)
+    }
+
+    fn register_as<Adp, Serialized, Domain>(&mut self, is_default: bool) -> &mut Self
+    where
+        Domain: Send + Sync + 'static,
+        Serialized: Send + Sync + 'static,
+        Adp: Adapter<Serialized, Domain> + 'static + Send + Sync,
+    {
+        let adapter_type = AdapterType {
+            domain: TypeId::of::<Domain>(),
+            serialized: TypeId::of::<Serialized>(),
+        };
+        let adapter = std::any::type_name::<Adp>();
+
+        if let Some(&(existing, existing_is_default)) = self.registered.get(&adapter_type) {
+            self.conflicts.push(AdapterConflict {
+                adapter,
+                existing,
+                existing_is_default,
+                serialized: std::any::type_name::<Serialized>(),
+                domain: std::any::type_name::<Domain>(),
+            });
+            return self;
+        }
+
+        self.provider.register::<Adp, Serialized, Domain>();
+        self.registered.insert(adapter_type, (adapter, is_default));
+        self
+    }
+
+    /// Names of the adapters registered so far
+    #[cfg(test)]
+    pub fn registered_adapters(&self) -> std::collections::BTreeSet<&'static str> {
+        self.registered.values().map(|(adapter, _)| *adapter).collect()
+    }
+
+    /// Reports every registration which was refused
+    pub fn finish(self) -> Result<(), AdapterRegistrationError> {
+        if self.conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(AdapterRegistrationError(self.conflicts))
+        }
+    }
+}
+
+/// An adapter which was not registered, as its conversion already had one
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AdapterConflict {
+    pub adapter: &'static str,
+    pub existing: &'static str,
+    pub existing_is_default: bool,
+    pub serialized: &'static str,
+    pub domain: &'static str,
+}
+
+impl Display for AdapterConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let existing_kind = if self.existing_is_default { "default adapter" } else { "adapter" };
+        write!(f, "{} converts between {} and {}, which the {} {} already does",
+            self.adapter, self.serialized, self.domain, existing_kind, self.existing)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Conflicting adapters: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+pub struct AdapterRegistrationError(pub Vec<AdapterConflict>);
+
 pub struct AdapterRepository {
-    adapters: DashMap<AdapterType, Box<dyn Any + Send + Sync>>
+    adapters: DashMap<AdapterType, Box<dyn Any + Send + Sync>>,
+    min_versions: DashMap<AdapterType, FeatureVersion>,
+    parse_cache: ParseCache<Option<Value>>,
 }
 
 impl AdapterRepository {
     pub fn new() -> Self {
         Self {
             adapters: DashMap::new(),
+            min_versions: DashMap::new(),
+            parse_cache: ParseCache::new(),
         }
     }
     
@@ -118,10 +242,15 @@ impl AdapterProvider for AdapterRepository {
 
         let adapter = AdapterWrapper::<Domain, Serialized, Self>::new::<Adp>();
 
+        if let Some(min_version) = Adp::min_version() {
+            self.min_versions.insert(adapter_type.clone(), min_version);
+        } else {
+            self.min_versions.remove(&adapter_type);
+        }
         self.adapters.insert(adapter_type, Box::new(adapter));
     }
     
-    async fn serialize<Domain, Serialized>(&self, domain: AdapterInput<&Domain>, context: AdapterProviderContext<'_, Self>) -> Result<Serialized, AdapterRepoError>
+    async fn serialize<Domain, Serialized>(&self, domain: AdapterInput<'_, Domain>, context: AdapterProviderContext<'_, Self>) -> Result<Serialized, AdapterRepoError>
     where
         Domain: Send + Sync + 'static,
         Serialized: Send + Sync + 'static,
@@ -131,8 +260,9 @@ impl AdapterProvider for AdapterRepository {
             serialized: TypeId::of::<Serialized>(),
         };
         
-        // Print the names of the Domain and Serialized types for debugging purposes
-        println!(
+        // Log the names of the Domain and Serialized types for debugging purposes.
+        // Traced rather than printed, since the command line's output is read by scripts
+        tracing::trace!(
             "Domain type: {:?}, Serialized type: {:?}",
             std::any::type_name::<Domain>(),
             std::any::type_name::<Serialized>()
@@ -144,7 +274,7 @@ impl AdapterProvider for AdapterRepository {
         adapter.serialize(domain, context).await
     }
 
-    async fn deserialize<Serialized, Domain>(&self, serialized: AdapterInput<&Serialized>, context: AdapterProviderContext<'_, Self>) -> Result<Domain, AdapterRepoError>
+    async fn deserialize<Serialized, Domain>(&self, serialized: AdapterInput<'_, Serialized>, context: AdapterProviderContext<'_, Self>) -> Result<Domain, AdapterRepoError>
     where
         Domain: Send + Sync + 'static,
         Serialized: Send + Sync + 'static,
@@ -159,6 +289,14 @@ impl AdapterProvider for AdapterRepository {
 
         adapter.deserialize(serialized, context).await
     }
+
+    fn parse_cache(&self) -> &ParseCache<Option<Value>> {
+        &self.parse_cache
+    }
+
+    fn min_versions(&self) -> Vec<FeatureVersion> {
+        self.min_versions.iter().map(|entry| entry.value().clone()).collect()
+    }
 }
 
 #[derive(Debug)]
@@ -197,11 +335,11 @@ where
         }
     }
 
-    async fn serialize(&self, domain: AdapterInput<&Domain>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, AdapterRepoError> {
+    async fn serialize(&self, domain: AdapterInput<'_, Domain>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, AdapterRepoError> {
         self.adapter.serialize(domain, context).await
     }
 
-    async fn deserialize(&self, serialized: AdapterInput<&Serialized>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, AdapterRepoError> {
+    async fn deserialize(&self, serialized: AdapterInput<'_, Serialized>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, AdapterRepoError> {
         self.adapter.deserialize(serialized, context).await
     }
 }
@@ -213,8 +351,8 @@ where
     Serialized: Send + Sync + 'static,
     AdpProvider: AdapterProvider + ?Sized,
 {
-    async fn serialize(&self, domain: AdapterInput<&Domain>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, AdapterRepoError>;
-    async fn deserialize(&self, serialized: AdapterInput<&Serialized>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, AdapterRepoError>;
+    async fn serialize(&self, domain: AdapterInput<'_, Domain>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, AdapterRepoError>;
+    async fn deserialize(&self, serialized: AdapterInput<'_, Serialized>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, AdapterRepoError>;
 }
 
 struct AdapterObjectImpl<Domain, Serialized, Adp, AdpProvider>
@@ -262,11 +400,11 @@ where
     Adp::SerializedConversionError: Send + Sync + 'static,
     AdpProvider: AdapterProvider + ?Sized,
 {
-    async fn serialize(&self, domain: AdapterInput<&Domain>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, AdapterRepoError> {
+    async fn serialize(&self, domain: AdapterInput<'_, Domain>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, AdapterRepoError> {
         Adp::serialize(domain, context).await.map_err(AdapterRepoError::serialization_error)
     }
 
-    async fn deserialize(&self, serialized: AdapterInput<&Serialized>, context: AdapterProviderContext<'_,AdpProvider>) -> Result<Domain, AdapterRepoError> {
+    async fn deserialize(&self, serialized: AdapterInput<'_, Serialized>, context: AdapterProviderContext<'_,AdpProvider>) -> Result<Domain, AdapterRepoError> {
         Adp::deserialize(serialized, context).await.map_err(AdapterRepoError::deserialization_error)
     }
 }
@@ -315,11 +453,11 @@ mod test {
         type ConversionError = Infallible;
         type SerializedConversionError = Infallible;
 
-        async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(_serialized: AdapterInput<&Serialized>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, Self::ConversionError> {
+        async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(_serialized: AdapterInput<'_, Serialized>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, Self::ConversionError> {
             Ok(Domain)
         }
 
-        async fn serialize<AdpProvider: AdapterProvider + ?Sized>(_domain: AdapterInput<&Domain>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, Self::SerializedConversionError> {
+        async fn serialize<AdpProvider: AdapterProvider + ?Sized>(_domain: AdapterInput<'_, Domain>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, Self::SerializedConversionError> {
             Ok(Serialized)
         }
     }
@@ -330,11 +468,11 @@ mod test {
         type ConversionError = TestAdapterError;
         type SerializedConversionError = TestAdapterError;
 
-        async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(_serialized: AdapterInput<&Serialized>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, Self::ConversionError> {
+        async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(_serialized: AdapterInput<'_, Serialized>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Domain, Self::ConversionError> {
             Err(TestAdapterError)
         }
 
-        async fn serialize<AdpProvider: AdapterProvider + ?Sized>(_domain: AdapterInput<&Domain>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, Self::SerializedConversionError> {
+        async fn serialize<AdpProvider: AdapterProvider + ?Sized>(_domain: AdapterInput<'_, Domain>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Serialized, Self::SerializedConversionError> {
             Err(TestAdapterError)
         }
     }
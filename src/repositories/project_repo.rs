@@ -1,15 +1,63 @@
+use std::collections::BTreeSet;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 use dashmap::DashMap;
 use tokio::sync::RwLock;
-use crate::data::domain::project::{Project, ProjectID};
+use crate::data::adapters::pack_info::PackInfoSerializationInput;
+use crate::data::domain::project::{PackInfoProjectData, Project, ProjectID};
+use crate::data::domain::project_settings_file::ProjectSettingsFile;
+use crate::data::serialization::bom;
+use crate::data::serialization::json::JsonStyle;
+use crate::data::serialization::pack_info::PackInfo as SerializedPackInfo;
+use crate::data::serialization;
+use crate::data::serialization::project::{Project as SerializedProject, SerializedProjectError, PACK_ICON_FILE, PACK_INFO_FILE};
 use crate::RUNTIME;
-use crate::services::filesystem_service::{DefaultFilesystemProvider, FilesystemProvider, FilesystemProviderError};
+use crate::services::filesystem_service::{DefaultFilesystemProvider, FileDeleteOptions, FileWriteOptions, FilesystemProvider, FilesystemProviderError};
 
 static PROJECT_EXTENSION: &str = "json";
 
+/// Where the editor keeps its own files inside a project, such as its settings file. Nothing in it is part of the pack
+pub const EDITOR_DIR: &str = ".gaea";
+
+/// Where a save is written inside the project before being moved into place,
+/// so a cancelled save never leaves a mix of old and new files behind
+pub const SAVE_STAGING_DIR: &str = ".gaea/saving";
+
+/// Directories inside the workspace directory, each holding one directory per project
+const AUTOSAVE_DIR: &str = "autosave";
+const SNAPSHOT_DIR: &str = "snapshots";
+
+/// How far a save has got, reported after each file is written
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SaveProgress {
+    pub files_written: usize,
+    pub total_files: usize,
+}
+
+pub type SaveProgressCallback = Box<dyn FnMut(SaveProgress) + Send>;
+
+/// Shared between a save and whatever started it, so the save can be stopped part way through
+#[derive(Debug, Clone, Default)]
+pub struct SaveCancellation(Arc<AtomicBool>);
+
+impl SaveCancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
 #[async_trait::async_trait]
 pub trait ProjectProvider {
     fn add_project(&self, project: Project, overwrite_existing: bool) -> Result<ProjectID>;
@@ -27,9 +75,32 @@ pub trait ProjectProvider {
         F: FnOnce(Arc<RwLock<Project>>) -> Pin<Box<dyn Future<Output = R> + Send + 'a>> + Send + Sync,
         R: Send + Sync;
 
-    async fn open_project(&self, path: &Path) -> Result<ProjectID>;
+    /// Reads a project's folder as if it were an extracted zip, leaving out the editor's own files, for the adapters to turn into a project
+    async fn read_project(&self, path: &Path) -> Result<SerializedProject>;
+    /// Adds a project read from its folder, refusing it if the project in that folder is already open
+    async fn open_project(&self, project: Project) -> Result<ProjectID>;
     fn close_project(&self, id: ProjectID) -> Result<()>;
     async fn save_project(&self, id: ProjectID) -> Result<PathBuf>;
+    /// Saves the project's files, reporting progress after each one. If cancelled, nothing already
+    /// in the project is changed and the partly written save is removed
+    async fn save_project_with_progress(&self, id: ProjectID, on_progress: SaveProgressCallback, cancellation: SaveCancellation) -> Result<PathBuf>;
+    async fn path_exists(&self, path: &Path) -> Result<bool>;
+
+    /// Reads the editor state stored beside a project, or the defaults if none has been stored yet
+    async fn read_settings_file(&self, project_root: &Path) -> Result<ProjectSettingsFile>;
+    async fn write_settings_file(&self, project_root: &Path, settings_file: &ProjectSettingsFile) -> Result<()>;
+
+    /// Where autosaves and snapshots are kept, away from the projects themselves so their folders stay clean
+    /// and projects in read-only folders can still be backed up
+    fn workspace_dir(&self) -> &Path;
+    fn set_workspace_dir(&mut self, workspace_dir: PathBuf);
+
+    /// Writes the project's current files to its autosave in the workspace directory, replacing the previous autosave.
+    /// Returns the directory the autosave was written to
+    async fn write_autosave(&self, id: ProjectID) -> Result<PathBuf>;
+    /// Writes the project's current files to a new snapshot in the workspace directory, keeping any earlier ones.
+    /// Returns the directory the snapshot was written to
+    async fn write_snapshot(&self, id: ProjectID) -> Result<PathBuf>;
 
     fn get_project_extension(&self) -> &'static str {
         PROJECT_EXTENSION
@@ -39,6 +110,9 @@ pub trait ProjectProvider {
 pub struct ProjectRepository<Filesystem: FilesystemProvider = DefaultFilesystemProvider> {
     filesystem_provider: Filesystem,
     projects: DashMap<ProjectID, Arc<RwLock<Project>>>,
+    /// Where each project was last saved, and the files written there, so the next save can delete any removed since
+    saved_files: DashMap<ProjectID, (PathBuf, BTreeSet<PathBuf>)>,
+    workspace_dir: PathBuf,
 }
 
 impl<Filesystem: FilesystemProvider> ProjectRepository<Filesystem> {
@@ -46,10 +120,21 @@ impl<Filesystem: FilesystemProvider> ProjectRepository<Filesystem> {
         Self {
             filesystem_provider: filesystem_provider,
             projects: DashMap::new(),
+            saved_files: DashMap::new(),
+            workspace_dir: default_workspace_dir(),
         }
     }
 }
 
+/// The user's local data directory for the app, e.g. `%LOCALAPPDATA%\Gaea\data\workspace` on Windows.
+/// Falls back to the temporary directory if the OS doesn't have a home directory for the user
+pub fn default_workspace_dir() -> PathBuf {
+    directories::ProjectDirs::from("", "", "Gaea")
+        .map(|dirs| dirs.data_local_dir().to_path_buf())
+        .unwrap_or_else(|| std::env::temp_dir().join("gaea"))
+        .join("workspace")
+}
+
 impl Default for ProjectRepository {
     fn default() -> Self {
         Self::with_filesystem(DefaultFilesystemProvider::new())
@@ -120,19 +205,272 @@ impl<Filesystem: FilesystemProvider> ProjectProvider for ProjectRepository<Files
         }
     }
 
-    async fn open_project(&self, path: &Path) -> Result<ProjectID> {
-        todo!()
+    async fn read_project(&self, path: &Path) -> Result<SerializedProject> {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let mut entry_names = Vec::new();
+        let mut entries = Vec::new();
+
+        for file in self.filesystem_provider.list_directory_recursive(path).await? {
+            let Ok(relative_path) = file.strip_prefix(path) else {
+                continue;
+            };
+            if relative_path.starts_with(EDITOR_DIR) {
+                continue;
+            }
+
+            let entry_name = relative_path.to_string_lossy().replace('\\', "/");
+            if self.filesystem_provider.is_directory(&file).await? {
+                entry_names.push(entry_name + "/");
+            } else {
+                entry_names.push(entry_name);
+                entries.push((relative_path.to_path_buf(), self.filesystem_provider.read_file(&file).await?));
+            }
+        }
+
+        let project_type = serialization::project::pack_type(&name, entry_names.iter().map(String::as_str))
+            .map_err(ProjectOpenError::InvalidProject)?;
+        SerializedProject::from_entries(&name, project_type, entries).map_err(|error| ProjectOpenError::InvalidProject(error).into())
+    }
+
+    async fn open_project(&self, project: Project) -> Result<ProjectID> {
+        let project_id = *project.id();
+        let Some(root) = project.path().clone() else {
+            return Err(ProjectRepoError::Save);
+        };
+
+        let open_projects: Vec<_> = self.projects.iter().map(|open_project| open_project.value().clone()).collect();
+        for open_project in open_projects {
+            if open_project.read().await.path().as_ref() == Some(&root) {
+                return Err(ProjectOpenError::AlreadyOpen.into());
+            }
+        }
+
+        // Everything read from the folder counts as saved there, so files removed before the first save are deleted by it
+        let files = file_contents(&project).into_iter().map(|(path, _)| path).collect();
+        self.saved_files.insert(project_id, (root, files));
+
+        self.projects.insert(project_id, Arc::new(RwLock::new(project)));
+        Ok(project_id)
     }
 
     fn close_project(&self, id: ProjectID) -> Result<()> {
-        todo!()
+        self.projects.remove(&id).ok_or(ProjectCloseError::FileNotOpen)?;
+        self.saved_files.remove(&id);
+        Ok(())
     }
 
     async fn save_project(&self, id: ProjectID) -> Result<PathBuf> {
-        todo!()
+        self.save_project_with_progress(id, Box::new(|_| {}), SaveCancellation::new()).await
+    }
+
+    async fn save_project_with_progress(&self, id: ProjectID, mut on_progress: SaveProgressCallback, cancellation: SaveCancellation) -> Result<PathBuf> {
+        let project = self.projects.get(&id)
+            .map(|project| project.value().clone())
+            .ok_or(ProjectCloseError::FileNotOpen)?;
+
+        let (root, files) = {
+            let project = project.read().await;
+            (project.path().clone().ok_or(ProjectRepoError::Save)?, file_contents(&project))
+        };
+
+        let staging = root.join(SAVE_STAGING_DIR);
+        let mut staged = Vec::with_capacity(files.len());
+
+        for (path, contents) in &files {
+            if cancellation.is_cancelled() {
+                self.discard_staged(&staging, &staged).await;
+                return Err(ProjectRepoError::SaveCancelled);
+            }
+
+            let staged_path = staging.join(path);
+            if let Some(parent) = staged_path.parent() {
+                self.filesystem_provider.create_directory_recursive(parent).await?;
+            }
+            self.filesystem_provider.write_file(&staged_path, contents, FileWriteOptions::Overwrite).await?;
+            staged.push(path.clone());
+
+            on_progress(SaveProgress { files_written: staged.len(), total_files: files.len() });
+        }
+
+        if cancellation.is_cancelled() {
+            self.discard_staged(&staging, &staged).await;
+            return Err(ProjectRepoError::SaveCancelled);
+        }
+
+        // Past this point the save is committed, and can no longer be cancelled
+        for path in &staged {
+            let destination = root.join(path);
+            if let Some(parent) = destination.parent() {
+                self.filesystem_provider.create_directory_recursive(parent).await?;
+            }
+            self.filesystem_provider.move_file(&staging.join(path), &destination).await?;
+        }
+        self.discard_staged(&staging, &staged).await;
+        self.delete_removed_files(id, &root, staged).await?;
+
+        Ok(root)
+    }
+
+    async fn path_exists(&self, path: &Path) -> Result<bool> {
+        self.filesystem_provider.file_exists(path).await.map_err(Into::into)
+    }
+
+    async fn read_settings_file(&self, project_root: &Path) -> Result<ProjectSettingsFile> {
+        let path = ProjectSettingsFile::path(project_root);
+
+        if !self.filesystem_provider.file_exists(&path).await? {
+            return Ok(ProjectSettingsFile::default());
+        }
+
+        let contents = self.filesystem_provider.read_file(&path).await?;
+        serde_json::from_slice(&contents).map_err(|error| ProjectRepoError::SettingsFile(error.to_string()))
+    }
+
+    async fn write_settings_file(&self, project_root: &Path, settings_file: &ProjectSettingsFile) -> Result<()> {
+        let path = ProjectSettingsFile::path(project_root);
+
+        if let Some(parent) = path.parent() {
+            self.filesystem_provider.create_directory_recursive(parent).await?;
+        }
+
+        let contents = serde_json::to_vec_pretty(settings_file).map_err(|error| ProjectRepoError::SettingsFile(error.to_string()))?;
+        self.filesystem_provider.write_file(&path, &contents, FileWriteOptions::Overwrite).await?;
+
+        Ok(())
+    }
+
+    fn workspace_dir(&self) -> &Path {
+        &self.workspace_dir
+    }
+
+    fn set_workspace_dir(&mut self, workspace_dir: PathBuf) {
+        self.workspace_dir = workspace_dir;
+    }
+
+    async fn write_autosave(&self, id: ProjectID) -> Result<PathBuf> {
+        let directory = self.workspace_dir.join(AUTOSAVE_DIR).join(id.to_string());
+
+        // Files removed from the project since the last autosave shouldn't come back when it's restored
+        if self.filesystem_provider.file_exists(&directory).await? {
+            self.delete_recursive(&directory).await?;
+        }
+
+        self.write_copy(id, &directory).await?;
+        Ok(directory)
+    }
+
+    async fn write_snapshot(&self, id: ProjectID) -> Result<PathBuf> {
+        let taken_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        let directory = self.workspace_dir.join(SNAPSHOT_DIR).join(id.to_string()).join(taken_at.to_string());
+
+        self.write_copy(id, &directory).await?;
+        Ok(directory)
+    }
+}
+
+impl<Filesystem: FilesystemProvider> ProjectRepository<Filesystem> {
+    /// Writes every file of the project below `directory`, as it would be saved
+    async fn write_copy(&self, id: ProjectID, directory: &Path) -> Result<()> {
+        let project = self.projects.get(&id)
+            .map(|project| project.value().clone())
+            .ok_or(ProjectCloseError::FileNotOpen)?;
+        let files = file_contents(&*project.read().await);
+
+        for (path, contents) in &files {
+            let destination = directory.join(path);
+            if let Some(parent) = destination.parent() {
+                self.filesystem_provider.create_directory_recursive(parent).await?;
+            }
+            self.filesystem_provider.write_file(&destination, contents, FileWriteOptions::Overwrite).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes a directory along with everything inside it
+    async fn delete_recursive(&self, directory: &Path) -> Result<()> {
+        // Directories are listed before their contents, so going backwards empties each one before it's removed
+        for path in self.filesystem_provider.list_directory_recursive(directory).await?.iter().rev() {
+            if self.filesystem_provider.is_directory(path).await? {
+                self.filesystem_provider.delete_directory(path).await?;
+            } else {
+                self.filesystem_provider.delete_file(path, FileDeleteOptions::AllowNonexistent).await?;
+            }
+        }
+
+        self.filesystem_provider.delete_directory(directory).await?;
+        Ok(())
+    }
+
+    /// Deletes files written by the project's previous save to the same place which it no longer has,
+    /// then remembers what this save wrote. Nothing else in the project's folder is touched
+    async fn delete_removed_files(&self, id: ProjectID, root: &Path, written: Vec<PathBuf>) -> Result<()> {
+        let written: BTreeSet<_> = written.into_iter().collect();
+
+        let previous = self.saved_files.insert(id, (root.to_path_buf(), written.clone()));
+        let Some((previous_root, previous_files)) = previous.filter(|(previous_root, _)| previous_root == root) else {
+            return Ok(());
+        };
+
+        for path in previous_files.difference(&written) {
+            self.filesystem_provider.delete_file(&previous_root.join(path), FileDeleteOptions::AllowNonexistent).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the staged files of a save, then the directories which held them.
+    /// Failures are ignored, since leftovers are overwritten by the next save
+    async fn discard_staged(&self, staging: &Path, staged: &[PathBuf]) {
+        for path in staged {
+            self.filesystem_provider.delete_file(&staging.join(path), FileDeleteOptions::AllowNonexistent).await.ok();
+        }
+
+        let mut directories: Vec<_> = staged.iter()
+            .flat_map(|path| path.ancestors().skip(1))
+            .filter(|directory| !directory.as_os_str().is_empty())
+            .map(|directory| staging.join(directory))
+            .collect();
+        // Deepest first, so each directory is empty by the time it is removed
+        directories.sort_by(|a, b| b.components().count().cmp(&a.components().count()).then_with(|| a.cmp(b)));
+        directories.dedup();
+        directories.push(staging.to_path_buf());
+
+        for directory in directories {
+            self.filesystem_provider.delete_directory(&directory).await.ok();
+        }
     }
 }
 
+/// Every file of the project as it's written to disk, with byte order marks put back, structures and other binary files
+/// as bytes, and the `pack.mcmeta` written from the pack info
+fn file_contents(project: &Project) -> Vec<(PathBuf, Vec<u8>)> {
+    let pack_info = bom::restore(&pack_info_contents(project), project.has_bom(Path::new(PACK_INFO_FILE)));
+
+    project.iter_files()
+        .filter(|file| file.path != Path::new(PACK_INFO_FILE))
+        .map(|file| (file.path.to_path_buf(), bom::restore(file.contents, project.has_bom(file.path))))
+        .chain(project.structures().iter().map(|(path, structure)| (path.clone(), structure.data().to_vec())))
+        .chain(project.pack_icon().iter().map(|icon| (PathBuf::from(PACK_ICON_FILE), icon.data().to_vec())))
+        .chain(project.binary_files().iter().map(|(path, data)| (path.clone(), data.clone())))
+        .chain([(PathBuf::from(PACK_INFO_FILE), pack_info)])
+        .collect()
+}
+
+/// The `pack.mcmeta` as the project adapter would export it. A combined project is saved to one folder like a zip with
+/// both `data` and `assets`, whose `pack.mcmeta` is read as the data pack's when the project is opened again
+fn pack_info_contents(project: &Project) -> String {
+    let (pack_info, format) = match project.pack_info() {
+        PackInfoProjectData::Data(pack_info) => (pack_info, project.project_version().get_data_format()),
+        PackInfoProjectData::Resource(pack_info) => (pack_info, project.project_version().get_resource_format()),
+        PackInfoProjectData::Combined { data_info, .. } => (data_info, project.project_version().get_data_format()),
+    };
+
+    let pack_info = SerializedPackInfo::from(&PackInfoSerializationInput::new(pack_info.description().clone(), format.get_format_id()));
+    // Serializing a pack info can't fail, as it has nothing but string keys
+    JsonStyle::default().serialize(&pack_info).unwrap()
+}
+
 pub type Result<T> = std::result::Result<T, ProjectRepoError>;
 
 #[derive(Debug, thiserror::Error)]
@@ -145,8 +483,12 @@ pub enum ProjectRepoError {
     Open(#[from] ProjectOpenError),
     #[error("Could not save project!")]
     Save,
+    #[error("Save was cancelled")]
+    SaveCancelled,
     #[error(transparent)]
     Close(#[from] ProjectCloseError),
+    #[error("Invalid project settings file: {0}")]
+    SettingsFile(String),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -161,6 +503,8 @@ pub enum ProjectCreationError {
 pub enum ProjectOpenError {
     #[error("File Already Open!")]
     AlreadyOpen,
+    #[error("Not a valid project! {0}")]
+    InvalidProject(SerializedProjectError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -172,4 +516,260 @@ pub enum ProjectCloseError {
 #[cfg(test)]
 mod test {
     use super::*;
-}
\ No newline at end of file
+    use crate::services::in_memory_filesystem::InMemoryFilesystem;
+
+    mod settings_file {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_settings_file_round_trip() {
+            // Given a project's editor state
+            let repository = ProjectRepository::with_filesystem(InMemoryFilesystem::new());
+            let project_root = Path::new("/projects/test");
+            let settings_file = ProjectSettingsFile {
+                open_tabs: vec![PathBuf::from("data/test/function/load.mcfunction"), PathBuf::from("pack.mcmeta")],
+                active_tab: Some(PathBuf::from("pack.mcmeta")),
+                theme: Some("Solarized Dark".to_string()),
+                word_wrap: false,
+            };
+
+            // When I write it and read it back
+            repository.write_settings_file(project_root, &settings_file).await.unwrap();
+            let read_back = repository.read_settings_file(project_root).await.unwrap();
+
+            // Then it should be unchanged, and stored in the project's settings directory
+            assert_eq!(read_back, settings_file);
+            assert!(repository.path_exists(&project_root.join(".gaea/project.json")).await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn test_missing_settings_file() {
+            // Given a project which has never stored its editor state
+            let repository = ProjectRepository::with_filesystem(InMemoryFilesystem::new());
+
+            // When I read it
+            let settings_file = repository.read_settings_file(Path::new("/projects/new")).await.unwrap();
+
+            // Then the defaults should be used
+            assert_eq!(settings_file, ProjectSettingsFile::default());
+        }
+    }
+
+    mod save {
+        use std::sync::Mutex;
+        use crate::data::domain::versions;
+        use super::*;
+
+        pub(super) fn repository_with_project() -> (ProjectRepository<InMemoryFilesystem>, ProjectID) {
+            let project = Project::test_with_files(&[
+                ("data/test/function/load.mcfunction", "say loaded"),
+                ("data/test/function/tick.mcfunction", "say tick"),
+                ("data/test/tags/function/load.json", r#"{"values": ["test:load"]}"#),
+            ]).with_path(PathBuf::from("/projects/test"));
+            let project_id = *project.id();
+
+            let repository = ProjectRepository::with_filesystem(InMemoryFilesystem::new());
+            repository.projects.insert(project_id, Arc::new(RwLock::new(project)));
+
+            (repository, project_id)
+        }
+
+        #[tokio::test]
+        async fn test_save_reports_progress() {
+            // Given a project with several files
+            let (repository, project_id) = repository_with_project();
+
+            // When I save it with progress reporting
+            let progress = Arc::new(Mutex::new(Vec::new()));
+            let reported = progress.clone();
+            let path = repository.save_project_with_progress(
+                project_id,
+                Box::new(move |update| reported.lock().unwrap().push(update.files_written)),
+                SaveCancellation::new(),
+            ).await.unwrap();
+
+            // Then progress should be reported for every file, and each file should be written in place
+            assert_eq!(path, PathBuf::from("/projects/test"));
+            assert_eq!(*progress.lock().unwrap(), vec![1, 2, 3, 4]);
+
+            let contents = repository.filesystem_provider.read_file(Path::new("/projects/test/data/test/function/tick.mcfunction")).await.unwrap();
+            assert_eq!(contents, b"say tick");
+            assert!(!repository.path_exists(&path.join(SAVE_STAGING_DIR)).await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn test_save_writes_pack_info() {
+            // Given a project, whose pack info isn't one of its files
+            let (repository, project_id) = repository_with_project();
+
+            // When I save it
+            let path = repository.save_project(project_id).await.unwrap();
+
+            // Then its pack.mcmeta should be written from the pack info
+            let pack_info = repository.filesystem_provider.read_file(&path.join(PACK_INFO_FILE)).await.unwrap();
+            let pack_info: serde_json::Value = serde_json::from_slice(&pack_info).unwrap();
+            assert_eq!(pack_info["pack"]["description"], "Test Description");
+            assert_eq!(pack_info["pack"]["pack_format"], versions::get_datapack_format_for_version(versions::latest()).get_format_id());
+        }
+
+        #[tokio::test]
+        async fn test_save_deletes_removed_files() {
+            // Given a saved project, in a folder with a file which isn't part of it
+            let (repository, project_id) = repository_with_project();
+            let unrelated = Path::new("/projects/test/notes.txt");
+            repository.filesystem_provider.create_directory_recursive(unrelated.parent().unwrap()).await.unwrap();
+            repository.filesystem_provider.write_file(unrelated, b"notes", FileWriteOptions::CreateNew).await.unwrap();
+            repository.save_project(project_id).await.unwrap();
+
+            // When a file is removed from the project and it's saved again
+            {
+                let project = repository.projects.get(&project_id).unwrap().value().clone();
+                project.write().await.remove_file(Path::new("data/test/function/tick.mcfunction"));
+            }
+            repository.save_project(project_id).await.unwrap();
+
+            // Then the removed file should be deleted, leaving the rest of the folder alone
+            assert!(!repository.path_exists(Path::new("/projects/test/data/test/function/tick.mcfunction")).await.unwrap());
+            assert!(repository.path_exists(Path::new("/projects/test/data/test/function/load.mcfunction")).await.unwrap());
+            assert!(repository.path_exists(unrelated).await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn test_cancelled_save_cleans_up() {
+            // Given a project which has been saved before
+            let (repository, project_id) = repository_with_project();
+            let saved_path = Path::new("/projects/test/data/test/function/load.mcfunction");
+            repository.filesystem_provider.create_directory_recursive(saved_path.parent().unwrap()).await.unwrap();
+            repository.filesystem_provider.write_file(saved_path, b"say old", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When the save is cancelled after its first file
+            let cancellation = SaveCancellation::new();
+            let cancel = cancellation.clone();
+            let result = repository.save_project_with_progress(project_id, Box::new(move |_| cancel.cancel()), cancellation).await;
+
+            // Then it should fail without changing the saved files or leaving the partial save behind
+            assert!(matches!(result, Err(ProjectRepoError::SaveCancelled)));
+            assert_eq!(repository.filesystem_provider.read_file(saved_path).await.unwrap(), b"say old");
+            assert!(!repository.path_exists(Path::new("/projects/test/data/test/function/tick.mcfunction")).await.unwrap());
+            assert!(!repository.path_exists(Path::new("/projects/test").join(SAVE_STAGING_DIR).as_path()).await.unwrap());
+        }
+    }
+
+    mod open {
+        use crate::data::serialization::project::SerializedProjectType;
+        use super::*;
+        use super::save::repository_with_project;
+
+        #[tokio::test]
+        async fn test_read_saved_project() {
+            // Given a project saved to its folder, along with its editor state
+            let (repository, project_id) = repository_with_project();
+            let path = repository.save_project(project_id).await.unwrap();
+            repository.write_settings_file(&path, &ProjectSettingsFile::default()).await.unwrap();
+
+            // When I read the folder back
+            let serialized_project = repository.read_project(&path).await.unwrap();
+
+            // Then it should hold the pack's files, named after the folder, without the editor's own files
+            assert_eq!(serialized_project.name(), "test");
+            assert!(matches!(serialized_project.project_type(), SerializedProjectType::Data));
+            assert_eq!(serialized_project.files().get(Path::new("data/test/function/tick.mcfunction")).unwrap(), "say tick");
+            assert!(serialized_project.files().keys().all(|file| !file.starts_with(EDITOR_DIR)));
+        }
+
+        #[tokio::test]
+        async fn test_read_folder_without_pack_info() {
+            // Given a folder which isn't a pack
+            let repository = ProjectRepository::with_filesystem(InMemoryFilesystem::new());
+            let path = Path::new("/projects/notes");
+            repository.filesystem_provider.create_directory_recursive(path).await.unwrap();
+            repository.filesystem_provider.write_file(&path.join("notes.txt"), b"notes", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I read it
+            let result = repository.read_project(path).await;
+
+            // Then it should be refused
+            assert!(matches!(result, Err(ProjectRepoError::Open(ProjectOpenError::InvalidProject(_)))));
+        }
+
+        #[tokio::test]
+        async fn test_open_and_close_project() {
+            // Given a project read from its folder
+            let (saved, project_id) = repository_with_project();
+            let project = saved.projects.get(&project_id).unwrap().value().read().await.clone();
+            let repository = ProjectRepository::with_filesystem(InMemoryFilesystem::new());
+
+            // When I open it twice, then close it
+            let opened = repository.open_project(project.clone()).await.unwrap();
+            let reopened = repository.open_project(project).await;
+            repository.close_project(opened).unwrap();
+
+            // Then it should only be open once, and be gone after closing
+            assert_eq!(opened, project_id);
+            assert!(matches!(reopened, Err(ProjectRepoError::Open(ProjectOpenError::AlreadyOpen))));
+            assert!(repository.projects.get(&project_id).is_none());
+            assert!(matches!(repository.close_project(project_id), Err(ProjectRepoError::Close(ProjectCloseError::FileNotOpen))));
+        }
+    }
+
+    mod workspace {
+        use super::*;
+
+        /// A project in a folder which is never written to, so anything written there would show up as a test failure
+        fn repository_with_workspace(files: &[(&str, &str)]) -> (ProjectRepository<InMemoryFilesystem>, ProjectID) {
+            let project = Project::test_with_files(files).with_path(PathBuf::from("/projects/test"));
+            let project_id = *project.id();
+
+            let mut repository = ProjectRepository::with_filesystem(InMemoryFilesystem::new());
+            repository.set_workspace_dir(PathBuf::from("/workspace"));
+            repository.projects.insert(project_id, Arc::new(RwLock::new(project)));
+
+            (repository, project_id)
+        }
+
+        #[tokio::test]
+        async fn test_autosave_written_to_workspace() {
+            // Given a project which was autosaved before one of its files was changed and another removed
+            let (repository, project_id) = repository_with_workspace(&[
+                ("data/test/function/load.mcfunction", "say loaded"),
+                ("data/test/function/old.mcfunction", "say old"),
+            ]);
+            repository.write_autosave(project_id).await.unwrap();
+            {
+                let project = repository.projects.get(&project_id).unwrap().value().clone();
+                let mut project = project.write().await;
+                project.set_file(PathBuf::from("data/test/function/load.mcfunction"), "say changed".to_string());
+                project.remove_file(Path::new("data/test/function/old.mcfunction"));
+            }
+
+            // When I autosave it again
+            let directory = repository.write_autosave(project_id).await.unwrap();
+
+            // Then the autosave should be under the workspace directory, matching the project as it is now
+            assert_eq!(directory, Path::new("/workspace/autosave").join(project_id.to_string()));
+            assert_eq!(repository.filesystem_provider.read_file(&directory.join("data/test/function/load.mcfunction")).await.unwrap(), b"say changed");
+            assert!(!repository.path_exists(&directory.join("data/test/function/old.mcfunction")).await.unwrap());
+            assert!(repository.path_exists(&directory.join(PACK_INFO_FILE)).await.unwrap());
+            assert!(!repository.path_exists(Path::new("/projects/test")).await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn test_snapshots_written_to_workspace() {
+            // Given a project
+            let (repository, project_id) = repository_with_workspace(&[("data/test/function/load.mcfunction", "say loaded")]);
+
+            // When I take two snapshots of it
+            let first = repository.write_snapshot(project_id).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+            let second = repository.write_snapshot(project_id).await.unwrap();
+
+            // Then both should be kept under the workspace directory, and nothing written beside the project
+            assert_ne!(first, second);
+            for snapshot in [&first, &second] {
+                assert!(snapshot.starts_with(Path::new("/workspace/snapshots").join(project_id.to_string())));
+                assert_eq!(repository.filesystem_provider.read_file(&snapshot.join("data/test/function/load.mcfunction")).await.unwrap(), b"say loaded");
+            }
+            assert!(!repository.path_exists(Path::new("/projects/test")).await.unwrap());
+        }
+    }
+}
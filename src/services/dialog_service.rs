@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+use rfd::{AsyncFileDialog, AsyncMessageDialog, MessageButtons, MessageDialogResult, MessageLevel};
+
+pub type DefaultDialogProvider = DialogService;
+
+/// Native dialogs asking the user for a file or a decision. Closing a dialog without choosing
+/// is always reported the same way, as `None` or `false`, so callers only have one kind of cancellation to handle
+#[async_trait::async_trait]
+pub trait DialogProvider: Send + Sync {
+    /// Asks for an existing file to open
+    async fn pick_file(&self, title: &str) -> Option<PathBuf>;
+    /// Asks for an existing folder, e.g. a project to open
+    async fn pick_folder(&self, title: &str) -> Option<PathBuf>;
+    /// Asks where to save a file, suggesting a file name if one is given
+    async fn save_file(&self, title: &str, file_name: Option<String>) -> Option<PathBuf>;
+    /// Asks a yes or no question, e.g. before discarding changes
+    async fn confirm(&self, title: &str, description: &str) -> bool;
+}
+
+/// Shows dialogs with the platform's own file pickers and message boxes
+pub struct DialogService;
+
+impl DialogService {
+    pub(crate) fn new() -> Self {
+        DialogService
+    }
+}
+
+#[async_trait::async_trait]
+impl DialogProvider for DialogService {
+    async fn pick_file(&self, title: &str) -> Option<PathBuf> {
+        AsyncFileDialog::new()
+            .set_title(title)
+            .pick_file()
+            .await
+            .map(|file| file.path().to_path_buf())
+    }
+
+    async fn pick_folder(&self, title: &str) -> Option<PathBuf> {
+        AsyncFileDialog::new()
+            .set_title(title)
+            .pick_folder()
+            .await
+            .map(|folder| folder.path().to_path_buf())
+    }
+
+    async fn save_file(&self, title: &str, file_name: Option<String>) -> Option<PathBuf> {
+        let dialog = AsyncFileDialog::new().set_title(title);
+        let dialog = match file_name {
+            Some(file_name) => dialog.set_file_name(file_name),
+            None => dialog,
+        };
+
+        dialog.save_file()
+            .await
+            .map(|file| file.path().to_path_buf())
+    }
+
+    async fn confirm(&self, title: &str, description: &str) -> bool {
+        let result = AsyncMessageDialog::new()
+            .set_level(MessageLevel::Warning)
+            .set_title(title)
+            .set_description(description)
+            .set_buttons(MessageButtons::YesNo)
+            .show()
+            .await;
+
+        result == MessageDialogResult::Yes
+    }
+}
+
+#[cfg(test)]
+mockall::mock! {
+    pub DialogProvider {}
+    #[async_trait::async_trait]
+    impl DialogProvider for DialogProvider {
+        async fn pick_file(&self, title: &str) -> Option<PathBuf>;
+        async fn pick_folder(&self, title: &str) -> Option<PathBuf>;
+        async fn save_file(&self, title: &str, file_name: Option<String>) -> Option<PathBuf>;
+        async fn confirm(&self, title: &str, description: &str) -> bool;
+    }
+}
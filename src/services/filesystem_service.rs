@@ -1,12 +1,20 @@
+use std::collections::VecDeque;
 use std::convert::Infallible;
 use std::error::Error;
 use std::fs::Metadata;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
 use tokio::fs::OpenOptions;
 use tokio::io::{self, AsyncReadExt, AsyncWriteExt};
 
 pub type Result<T> = std::result::Result<T, FilesystemProviderError>;
 
+/// How many directories deep a recursive operation goes before giving up, far deeper than any real pack
+pub const MAX_DIRECTORY_DEPTH: usize = 128;
+
 #[derive(Debug, thiserror::Error)]
 pub enum FilesystemProviderError {
     #[error(transparent)]
@@ -14,7 +22,9 @@ pub enum FilesystemProviderError {
     #[error(transparent)]
     JoinError(#[from] tokio::task::JoinError),
     #[error("Error in chunked reader callback: {0}")]
-    ChunkedReaderCallbackError(String)
+    ChunkedReaderCallbackError(String),
+    #[error("{} is nested more than {} directories deep!", .0.display(), MAX_DIRECTORY_DEPTH)]
+    TooDeep(PathBuf),
 }
 
 #[derive(Debug)]
@@ -60,6 +70,26 @@ pub trait FilesystemProvider: Send + Sync {
     async fn write_file(&self, path: &Path, content: &[u8], options: FileWriteOptions) -> Result<()>;
     async fn read_file(&self, path: &Path) -> Result<Vec<u8>>;
 
+    /// Writes a single gzip-compressed file, like `level.dat`, compressing the given contents.
+    /// This is for individually compressed files rather than archives
+    async fn write_file_gzip(&self, path: &Path, content: &[u8], options: FileWriteOptions) -> Result<()> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content)?;
+        let compressed = encoder.finish()?;
+
+        self.write_file(path, &compressed, options).await
+    }
+
+    /// Reads a single gzip-compressed file, returning its decompressed contents
+    async fn read_file_gzip(&self, path: &Path) -> Result<Vec<u8>> {
+        let compressed = self.read_file(path).await?;
+
+        let mut content = Vec::new();
+        GzDecoder::new(compressed.as_slice()).read_to_end(&mut content)?;
+
+        Ok(content)
+    }
+
     /// Read file in chunks
     /// Callback
     async fn read_file_chunked(
@@ -75,6 +105,61 @@ pub trait FilesystemProvider: Send + Sync {
     async fn create_directory_recursive(&self, path: &Path) -> Result<()>;
     async fn delete_directory(&self, path: &Path) -> Result<()>;
     async fn list_directory(&self, path: &Path) -> Result<Vec<PathBuf>>;
+
+    /// Lists every file and directory below `path`, with each directory before anything inside it.
+    /// The tree is walked with a queue rather than by recursing, so pathologically deep trees can't overflow the stack,
+    /// and anything nested more than [`MAX_DIRECTORY_DEPTH`] directories deep is refused
+    async fn list_directory_recursive(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let mut entries = Vec::new();
+        let mut pending = VecDeque::from([(path.to_path_buf(), 0)]);
+
+        while let Some((directory, depth)) = pending.pop_front() {
+            for entry in self.list_directory(&directory).await? {
+                if self.is_directory(&entry).await? {
+                    if depth >= MAX_DIRECTORY_DEPTH {
+                        return Err(FilesystemProviderError::TooDeep(entry));
+                    }
+                    pending.push_back((entry.clone(), depth + 1));
+                }
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Copies a directory and everything inside it, creating the destination if needed
+    async fn copy_directory(&self, source: &Path, destination: &Path) -> Result<()> {
+        let entries = self.list_directory_recursive(source).await?;
+        self.create_directory_recursive(destination).await?;
+
+        for entry in entries {
+            let target = destination.join(entry.strip_prefix(source).expect("Listed entries should be inside the directory listed"));
+
+            if self.is_directory(&entry).await? {
+                self.create_directory(&target).await?;
+            } else {
+                self.copy_file(&entry, &target).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Total size in bytes of the files below `path`.
+    /// Each file is read to find its size, so providers which can look sizes up should do that instead
+    async fn directory_size(&self, path: &Path) -> Result<u64> {
+        let mut size = 0;
+
+        for entry in self.list_directory_recursive(path).await? {
+            if !self.is_directory(&entry).await? {
+                size += self.read_file(&entry).await?.len() as u64;
+            }
+        }
+
+        Ok(size)
+    }
+
     async fn validate_path(&self, path: &Path) -> Result<PathValidationStatus>;
     async fn file_exists(&self, path: &Path) -> Result<bool>;
     async fn is_directory(&self, path: &Path) -> Result<bool>;
@@ -93,6 +178,7 @@ impl FilesystemProvider for FilesystemService {
         let file = match options {
             FileWriteOptions::Overwrite => {
                 file.truncate(true)
+                    .create(true)
             },
             FileWriteOptions::CreateNew => {
                 file.create_new(true)
@@ -233,6 +319,19 @@ impl FilesystemProvider for FilesystemService {
         Ok(result)
     }
 
+    async fn directory_size(&self, path: &Path) -> Result<u64> {
+        let mut size = 0;
+
+        for entry in self.list_directory_recursive(path).await? {
+            let metadata = tokio::fs::metadata(&entry).await?;
+            if metadata.is_file() {
+                size += metadata.len();
+            }
+        }
+
+        Ok(size)
+    }
+
     async fn validate_path(&self, path: &Path) -> Result<PathValidationStatus> {
         if path.exists() {
             Ok(PathValidationStatus::Valid {
@@ -440,6 +539,22 @@ mod tests {
             assert_eq!(new_content, tokio::fs::read(&path).await.unwrap().as_slice());
         }
 
+        #[rstest::rstest]
+        #[tokio::test]
+        #[serial(filesystem)]
+        async fn test_overwrite_missing_file(#[future] test_context: TestContext) {
+            // Given a file which doesn't exist yet
+            let ctx = test_context.await;
+            let path = ctx.path("test.txt");
+
+            // When I overwrite it
+            let content = b"Hello World";
+            ctx.service.write_file(&path, content, FileWriteOptions::Overwrite).await.unwrap();
+
+            // Then it should be created
+            assert_eq!(content, tokio::fs::read(&path).await.unwrap().as_slice());
+        }
+
         #[rstest::rstest]
         #[tokio::test]
         #[serial(filesystem)]
@@ -602,6 +717,42 @@ mod tests {
             assert_eq!(calls.load(Ordering::SeqCst), filesize_kb / 2);
         }
         
+        #[rstest::rstest]
+        #[tokio::test]
+        #[serial(filesystem)]
+        async fn test_read_write_file_gzip(#[future] test_context: TestContext) {
+            // Given some compressible content
+            let ctx = test_context.await;
+            let path = ctx.path("level.dat");
+            let content: Vec<u8> = (0..=255).cycle().take(64 * 1024).collect();
+
+            // When I write it compressed, then read it back
+            ctx.service.write_file_gzip(&path, &content, FileWriteOptions::CreateNew).await.unwrap();
+            let result = ctx.service.read_file_gzip(&path).await.unwrap();
+
+            // Then the file on disk should be gzipped, and reading it should give the original content
+            let on_disk = tokio::fs::read(&path).await.unwrap();
+            assert_eq!(&on_disk[..2], &[0x1f, 0x8b]);
+            assert!(on_disk.len() < content.len());
+            assert_eq!(result, content);
+        }
+
+        #[rstest::rstest]
+        #[tokio::test]
+        #[serial(filesystem)]
+        async fn test_read_file_gzip_not_compressed(#[future] test_context: TestContext) {
+            // Given a file which isn't gzipped
+            let ctx = test_context.await;
+            let path = ctx.path("test.txt");
+            tokio::fs::write(&path, b"Hello World").await.unwrap();
+
+            // When I try to read it as gzip
+            let result = ctx.service.read_file_gzip(&path).await;
+
+            // Then it should return an error
+            assert!(matches!(result, Err(FilesystemProviderError::IO(_))));
+        }
+
         #[derive(Debug, thiserror::Error)]
         #[error("Test error")]
         struct TestError;
@@ -977,6 +1128,79 @@ mod tests {
             assert!(contents.contains(&sub_dir_path));
         }
 
+        /// Creates `depth` directories nested inside each other below `root`, with a file in the deepest, returning its path
+        async fn create_nested_directories(root: &Path, depth: usize) -> PathBuf {
+            let deepest = (0..depth).fold(root.to_path_buf(), |path, _| path.join("d"));
+            tokio::fs::create_dir_all(&deepest).await.unwrap();
+
+            let file_path = deepest.join("deepest.txt");
+            tokio::fs::write(&file_path, b"Found it").await.unwrap();
+            file_path
+        }
+
+        #[rstest::rstest]
+        #[tokio::test]
+        #[serial(filesystem)]
+        async fn test_list_deep_directory(#[future] test_context: TestContext) {
+            // Given a directory nested as deep as recursive operations allow
+            let ctx = test_context.await;
+            let root = ctx.path("deep");
+            let file_path = create_nested_directories(&root, MAX_DIRECTORY_DEPTH).await;
+
+            // When I list everything inside it
+            let entries = ctx.service.list_directory_recursive(&root).await.unwrap();
+
+            // Then every directory should be listed before the file at the bottom
+            assert_eq!(entries.len(), MAX_DIRECTORY_DEPTH + 1);
+            assert_eq!(entries.last(), Some(&file_path));
+
+            // And its size should be that of the one file
+            assert_eq!(ctx.service.directory_size(&root).await.unwrap(), 8);
+        }
+
+        #[rstest::rstest]
+        #[tokio::test]
+        #[serial(filesystem)]
+        async fn test_too_deep_directory(#[future] test_context: TestContext) {
+            // Given a directory nested deeper than recursive operations allow
+            let ctx = test_context.await;
+            let root = ctx.path("deep");
+            create_nested_directories(&root, MAX_DIRECTORY_DEPTH + 20).await;
+
+            // When I list, copy and size it
+            let listed = ctx.service.list_directory_recursive(&root).await;
+            let copied = ctx.service.copy_directory(&root, &ctx.path("copy")).await;
+            let size = ctx.service.directory_size(&root).await;
+
+            // Then each should fail cleanly, with nothing copied
+            assert!(matches!(listed, Err(FilesystemProviderError::TooDeep(_))));
+            assert!(matches!(copied, Err(FilesystemProviderError::TooDeep(_))));
+            assert!(matches!(size, Err(FilesystemProviderError::TooDeep(_))));
+            assert!(!ctx.path("copy").exists());
+        }
+
+        #[rstest::rstest]
+        #[tokio::test]
+        #[serial(filesystem)]
+        async fn test_copy_directory(#[future] test_context: TestContext) {
+            // Given a directory with files and subdirectories
+            let ctx = test_context.await;
+            let source = ctx.path("pack");
+            tokio::fs::create_dir_all(source.join("data/example/function")).await.unwrap();
+            tokio::fs::create_dir_all(source.join("data/example/tags")).await.unwrap();
+            tokio::fs::write(source.join("pack.mcmeta"), b"{}").await.unwrap();
+            tokio::fs::write(source.join("data/example/function/load.mcfunction"), b"say hi").await.unwrap();
+
+            // When I copy it
+            let destination = ctx.path("copies/pack");
+            ctx.service.copy_directory(&source, &destination).await.unwrap();
+
+            // Then everything should be copied, including empty directories
+            assert_eq!(std::fs::read(destination.join("pack.mcmeta")).unwrap(), b"{}");
+            assert_eq!(std::fs::read(destination.join("data/example/function/load.mcfunction")).unwrap(), b"say hi");
+            assert!(destination.join("data/example/tags").is_dir());
+        }
+
         #[rstest::rstest]
         #[tokio::test]
         #[serial(filesystem)]
@@ -0,0 +1,578 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tokio::io;
+use crate::services::filesystem_service::{ChunkedFileReadResult, FileDeleteOptions, FileWriteOptions, FilesystemProvider, FilesystemProviderError, PathValidationStatus, Result};
+
+/// Filesystem provider which keeps everything in memory, for tests and ephemeral preview projects.
+/// Paths without a parent are treated as roots, which always exist
+#[derive(Debug, Default)]
+pub struct InMemoryFilesystem {
+    files: RwLock<HashMap<PathBuf, Vec<u8>>>,
+    directories: RwLock<HashSet<PathBuf>>,
+}
+
+impl InMemoryFilesystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.read().unwrap().contains_key(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        Self::is_root(path) || self.directories.read().unwrap().contains(path)
+    }
+
+    fn is_root(path: &Path) -> bool {
+        path.parent().is_none()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        self.is_file(path) || self.is_dir(path)
+    }
+
+    fn parent_exists(&self, path: &Path) -> bool {
+        match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => self.is_dir(parent),
+            _ => true,
+        }
+    }
+
+    fn ensure_parent_exists(&self, path: &Path) -> Result<()> {
+        if self.parent_exists(path) {
+            Ok(())
+        } else {
+            Err(io_error(io::ErrorKind::NotFound, "Parent directory does not exist", path))
+        }
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        if self.is_dir(path) {
+            return Err(io_error(io::ErrorKind::IsADirectory, "Path is a directory", path));
+        }
+
+        self.files.read().unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| io_error(io::ErrorKind::NotFound, "File does not exist", path))
+    }
+
+    /// Moves a directory along with everything inside it, as renaming one on disk does
+    fn move_directory(&self, source: &Path, destination: &Path) -> Result<()> {
+        if Self::is_root(source) {
+            return Err(io_error(io::ErrorKind::InvalidInput, "Roots cannot be moved", source));
+        }
+        if destination.starts_with(source) {
+            return Err(io_error(io::ErrorKind::InvalidInput, "Directory cannot be moved inside itself", destination));
+        }
+        if self.exists(destination) {
+            return Err(io_error(io::ErrorKind::AlreadyExists, "Path already exists", destination));
+        }
+        self.ensure_parent_exists(destination)?;
+
+        let moved = |path: &PathBuf| path.strip_prefix(source).ok().map(|relative| destination.join(relative));
+
+        let mut directories = self.directories.write().unwrap();
+        let moved_directories: Vec<_> = directories.iter()
+            .filter_map(|path| Some((path.clone(), moved(path)?)))
+            .collect();
+        for (from, to) in moved_directories {
+            directories.remove(&from);
+            directories.insert(to);
+        }
+
+        let mut files = self.files.write().unwrap();
+        let moved_files: Vec<_> = files.keys()
+            .filter_map(|path| Some((path.clone(), moved(path)?)))
+            .collect();
+        for (from, to) in moved_files {
+            let content = files.remove(&from).unwrap();
+            files.insert(to, content);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl FilesystemProvider for InMemoryFilesystem {
+    async fn write_file(&self, path: &Path, content: &[u8], options: FileWriteOptions) -> Result<()> {
+        if self.is_dir(path) {
+            return Err(io_error(io::ErrorKind::IsADirectory, "Path is a directory", path));
+        }
+        self.ensure_parent_exists(path)?;
+
+        let mut files = self.files.write().unwrap();
+
+        match (options, files.get_mut(path)) {
+            (FileWriteOptions::CreateNew, Some(_)) => {
+                return Err(io_error(io::ErrorKind::AlreadyExists, "File already exists", path));
+            }
+            (FileWriteOptions::AppendDontCreate, None) => {
+                return Err(io_error(io::ErrorKind::NotFound, "File does not exist", path));
+            }
+            (FileWriteOptions::Append | FileWriteOptions::AppendDontCreate, Some(existing)) => {
+                existing.extend_from_slice(content);
+            }
+            _ => {
+                files.insert(path.to_path_buf(), content.to_vec());
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
+        self.read(path)
+    }
+
+    async fn read_file_chunked(
+        &self,
+        path: &Path,
+        chunk_size: usize,
+        mut callback: Box<dyn FnMut(Vec<u8>) -> ChunkedFileReadResult + Send>,
+    ) -> Result<()> {
+        if chunk_size == 0 {
+            return Err(io_error(io::ErrorKind::InvalidInput, "Chunk size must be greater than 0", path));
+        }
+
+        let content = self.read(path)?;
+
+        for chunk in content.chunks(chunk_size) {
+            match callback(chunk.to_vec()) {
+                ChunkedFileReadResult::Continue => {}
+                ChunkedFileReadResult::Done => break,
+                ChunkedFileReadResult::Err(err) => return Err(FilesystemProviderError::ChunkedReaderCallbackError(err.to_string())),
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn delete_file(&self, path: &Path, options: FileDeleteOptions) -> Result<()> {
+        if self.is_dir(path) {
+            return Err(io_error(io::ErrorKind::IsADirectory, "Path is a directory", path));
+        }
+
+        let removed = self.files.write().unwrap().remove(path).is_some();
+
+        match options {
+            FileDeleteOptions::ErrorIfNotExists if !removed => {
+                Err(io_error(io::ErrorKind::NotFound, "File does not exist", path))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn copy_file(&self, source: &Path, destination: &Path) -> Result<()> {
+        let content = self.read(source)?;
+        self.write_file(destination, &content, FileWriteOptions::Overwrite).await
+    }
+
+    async fn move_file(&self, source: &Path, destination: &Path) -> Result<()> {
+        if self.is_dir(source) {
+            return self.move_directory(source, destination);
+        }
+
+        let content = self.read(source)?;
+        self.write_file(destination, &content, FileWriteOptions::Overwrite).await?;
+
+        if source != destination {
+            self.files.write().unwrap().remove(source);
+        }
+
+        Ok(())
+    }
+
+    async fn create_directory(&self, path: &Path) -> Result<()> {
+        if self.exists(path) {
+            return Err(io_error(io::ErrorKind::AlreadyExists, "Path already exists", path));
+        }
+        self.ensure_parent_exists(path)?;
+
+        self.directories.write().unwrap().insert(path.to_path_buf());
+        Ok(())
+    }
+
+    async fn create_directory_recursive(&self, path: &Path) -> Result<()> {
+        let ancestors: Vec<_> = path.ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty() && !Self::is_root(ancestor))
+            .collect();
+
+        if let Some(file) = ancestors.iter().find(|ancestor| self.is_file(ancestor)) {
+            return Err(io_error(io::ErrorKind::AlreadyExists, "Path is a file", file));
+        }
+
+        let mut directories = self.directories.write().unwrap();
+        for ancestor in ancestors {
+            directories.insert(ancestor.to_path_buf());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_directory(&self, path: &Path) -> Result<()> {
+        if !self.is_dir(path) || Self::is_root(path) {
+            return Err(io_error(io::ErrorKind::NotFound, "Directory does not exist", path));
+        }
+
+        if !self.list_directory(path).await?.is_empty() {
+            return Err(io_error(io::ErrorKind::DirectoryNotEmpty, "Directory is not empty", path));
+        }
+
+        self.directories.write().unwrap().remove(path);
+        Ok(())
+    }
+
+    async fn list_directory(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        if !self.is_dir(path) {
+            return Err(io_error(io::ErrorKind::NotFound, "Directory does not exist", path));
+        }
+
+        let is_child = |child: &&PathBuf| child.parent() == Some(path);
+
+        let mut entries: Vec<_> = self.directories.read().unwrap().iter()
+            .filter(is_child)
+            .cloned()
+            .collect();
+
+        entries.extend(self.files.read().unwrap().keys()
+            .filter(is_child)
+            .cloned());
+
+        Ok(entries)
+    }
+
+    async fn validate_path(&self, path: &Path) -> Result<PathValidationStatus> {
+        if self.exists(path) {
+            return Ok(PathValidationStatus::Valid {
+                is_file: self.is_file(path),
+            });
+        }
+
+        let missing_segment_index = path.ancestors()
+            .take_while(|ancestor| !self.exists(ancestor))
+            .count();
+
+        Ok(PathValidationStatus::Missing { missing_segment_index })
+    }
+
+    async fn file_exists(&self, path: &Path) -> Result<bool> {
+        Ok(self.exists(path))
+    }
+
+    async fn is_directory(&self, path: &Path) -> Result<bool> {
+        Ok(self.is_dir(path))
+    }
+
+    async fn get_metadata(&self, path: &Path) -> Result<Metadata> {
+        // std::fs::Metadata can only be produced by the OS
+        Err(io_error(io::ErrorKind::Unsupported, "Metadata is not available for in-memory files", path))
+    }
+}
+
+fn io_error(kind: io::ErrorKind, message: &str, path: &Path) -> FilesystemProviderError {
+    io::Error::new(kind, format!("{}: {}", message, path.display())).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use rstest::{fixture, rstest};
+    use super::*;
+
+    #[fixture]
+    fn filesystem() -> InMemoryFilesystem {
+        let filesystem = InMemoryFilesystem::new();
+        filesystem.directories.write().unwrap().insert(PathBuf::from("/root"));
+        filesystem
+    }
+
+    mod write {
+        use super::*;
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_write_and_read_file(filesystem: InMemoryFilesystem) {
+            // Given a file which does not exist
+            let path = Path::new("/root/test.txt");
+
+            // When I write it
+            filesystem.write_file(path, b"Hello World", FileWriteOptions::CreateNew).await.unwrap();
+
+            // Then it should be readable
+            assert_eq!(filesystem.read_file(path).await.unwrap(), b"Hello World");
+            assert!(filesystem.file_exists(path).await.unwrap());
+            assert!(!filesystem.is_directory(path).await.unwrap());
+        }
+
+        #[rstest]
+        #[case::create_new(FileWriteOptions::CreateNew, None)]
+        #[case::overwrite(FileWriteOptions::Overwrite, Some(b"Again".as_slice()))]
+        #[case::append(FileWriteOptions::Append, Some(b"HelloAgain".as_slice()))]
+        #[case::append_dont_create(FileWriteOptions::AppendDontCreate, Some(b"HelloAgain".as_slice()))]
+        #[tokio::test]
+        async fn test_write_existing_file(filesystem: InMemoryFilesystem, #[case] options: FileWriteOptions, #[case] expected: Option<&[u8]>) {
+            // Given a file which already exists
+            let path = Path::new("/root/test.txt");
+            filesystem.write_file(path, b"Hello", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I write to it
+            let result = filesystem.write_file(path, b"Again", options).await;
+
+            // Then it should follow the write options
+            match expected {
+                Some(expected) => {
+                    result.unwrap();
+                    assert_eq!(filesystem.read_file(path).await.unwrap(), expected);
+                }
+                None => {
+                    assert!(result.is_err());
+                    assert_eq!(filesystem.read_file(path).await.unwrap(), b"Hello");
+                }
+            }
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_append_dont_create_nonexistent(filesystem: InMemoryFilesystem) {
+            // Given a file which does not exist
+            let path = Path::new("/root/test.txt");
+
+            // When I append to it without creating it
+            let result = filesystem.write_file(path, b"Hello", FileWriteOptions::AppendDontCreate).await;
+
+            // Then it should return an error
+            assert!(result.is_err());
+            assert!(!filesystem.file_exists(path).await.unwrap());
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_write_missing_parent(filesystem: InMemoryFilesystem) {
+            // Given a path whose parent directory does not exist
+            let path = Path::new("/root/missing/test.txt");
+
+            // When I write to it
+            let result = filesystem.write_file(path, b"Hello", FileWriteOptions::Overwrite).await;
+
+            // Then it should return an error
+            assert!(result.is_err());
+        }
+    }
+
+    mod read {
+        use super::*;
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_read_file_nonexistent(filesystem: InMemoryFilesystem) {
+            // When I read a file which does not exist
+            let result = filesystem.read_file(Path::new("/root/missing.txt")).await;
+
+            // Then it should return an error
+            assert!(matches!(result, Err(FilesystemProviderError::IO(err)) if err.kind() == io::ErrorKind::NotFound));
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_read_file_chunked(filesystem: InMemoryFilesystem) {
+            // Given a file
+            let path = Path::new("/root/test.txt");
+            filesystem.write_file(path, b"Hello World", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I read it in chunks
+            let chunks = Arc::new(Mutex::new(Vec::new()));
+            let chunks_clone = chunks.clone();
+
+            filesystem.read_file_chunked(path, 4, Box::new(move |chunk| {
+                chunks_clone.lock().unwrap().push(chunk);
+                ChunkedFileReadResult::Continue
+            })).await.unwrap();
+
+            // Then every chunk should be passed to the callback in order
+            assert_eq!(*chunks.lock().unwrap(), vec![b"Hell".to_vec(), b"o Wo".to_vec(), b"rld".to_vec()]);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_read_file_chunked_abort(filesystem: InMemoryFilesystem) {
+            // Given a file
+            let path = Path::new("/root/test.txt");
+            filesystem.write_file(path, b"Hello World", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I stop reading after the first chunk
+            let chunks = Arc::new(Mutex::new(Vec::new()));
+            let chunks_clone = chunks.clone();
+
+            filesystem.read_file_chunked(path, 4, Box::new(move |chunk| {
+                chunks_clone.lock().unwrap().push(chunk);
+                ChunkedFileReadResult::Done
+            })).await.unwrap();
+
+            // Then no more chunks should be read
+            assert_eq!(*chunks.lock().unwrap(), vec![b"Hell".to_vec()]);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_read_file_chunked_error(filesystem: InMemoryFilesystem) {
+            // Given a file
+            let path = Path::new("/root/test.txt");
+            filesystem.write_file(path, b"Hello World", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When the callback returns an error
+            let result = filesystem.read_file_chunked(path, 4, Box::new(|_| {
+                ChunkedFileReadResult::Err(anyhow::anyhow!("Callback error"))
+            })).await;
+
+            // Then it should be returned
+            assert!(matches!(result, Err(FilesystemProviderError::ChunkedReaderCallbackError(_))));
+        }
+    }
+
+    mod other_file_ops {
+        use super::*;
+
+        #[rstest]
+        #[case::allow_nonexistent(FileDeleteOptions::AllowNonexistent, true)]
+        #[case::error_if_not_exists(FileDeleteOptions::ErrorIfNotExists, false)]
+        #[tokio::test]
+        async fn test_delete_nonexistent_file(filesystem: InMemoryFilesystem, #[case] options: FileDeleteOptions, #[case] should_succeed: bool) {
+            // When I delete a file which does not exist
+            let result = filesystem.delete_file(Path::new("/root/missing.txt"), options).await;
+
+            // Then it should follow the delete options
+            assert_eq!(result.is_ok(), should_succeed);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_delete_file(filesystem: InMemoryFilesystem) {
+            // Given a file
+            let path = Path::new("/root/test.txt");
+            filesystem.write_file(path, b"Hello", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I delete it
+            filesystem.delete_file(path, FileDeleteOptions::ErrorIfNotExists).await.unwrap();
+
+            // Then it should no longer exist
+            assert!(!filesystem.file_exists(path).await.unwrap());
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_move_file(filesystem: InMemoryFilesystem) {
+            // Given a file
+            let source = Path::new("/root/source.txt");
+            let destination = Path::new("/root/destination.txt");
+            filesystem.write_file(source, b"Hello", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I move it
+            filesystem.move_file(source, destination).await.unwrap();
+
+            // Then it should only exist at the destination
+            assert!(!filesystem.file_exists(source).await.unwrap());
+            assert_eq!(filesystem.read_file(destination).await.unwrap(), b"Hello");
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_validate_path_missing(filesystem: InMemoryFilesystem) {
+            // When I validate a path with two missing segments
+            let status = filesystem.validate_path(Path::new("/root/missing/test.txt")).await.unwrap();
+
+            // Then the missing segments should be counted
+            assert_eq!(status, PathValidationStatus::Missing { missing_segment_index: 2 });
+        }
+    }
+
+    mod directory_ops {
+        use super::*;
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_list_directory(filesystem: InMemoryFilesystem) {
+            // Given a directory containing files and a subdirectory
+            filesystem.create_directory_recursive(Path::new("/root/data/nested")).await.unwrap();
+            filesystem.write_file(Path::new("/root/data/a.json"), b"", FileWriteOptions::CreateNew).await.unwrap();
+            filesystem.write_file(Path::new("/root/data/nested/b.json"), b"", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I list it
+            let mut entries = filesystem.list_directory(Path::new("/root/data")).await.unwrap();
+            entries.sort();
+
+            // Then only its direct children should be listed
+            assert_eq!(entries, vec![PathBuf::from("/root/data/a.json"), PathBuf::from("/root/data/nested")]);
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_create_directory_missing_parent(filesystem: InMemoryFilesystem) {
+            // When I create a directory whose parent does not exist
+            let result = filesystem.create_directory(Path::new("/root/missing/nested")).await;
+
+            // Then it should return an error
+            assert!(result.is_err());
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_move_directory(filesystem: InMemoryFilesystem) {
+            // Given a directory containing a file and a subdirectory
+            filesystem.create_directory_recursive(Path::new("/root/data/nested")).await.unwrap();
+            filesystem.write_file(Path::new("/root/data/a.json"), b"a", FileWriteOptions::CreateNew).await.unwrap();
+            filesystem.write_file(Path::new("/root/data/nested/b.json"), b"b", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I move it
+            filesystem.move_file(Path::new("/root/data"), Path::new("/root/moved")).await.unwrap();
+
+            // Then everything inside it should move with it
+            assert!(!filesystem.file_exists(Path::new("/root/data")).await.unwrap());
+            assert!(filesystem.is_directory(Path::new("/root/moved/nested")).await.unwrap());
+            assert_eq!(filesystem.read_file(Path::new("/root/moved/a.json")).await.unwrap(), b"a");
+            assert_eq!(filesystem.read_file(Path::new("/root/moved/nested/b.json")).await.unwrap(), b"b");
+        }
+
+        #[rstest]
+        #[case::existing_destination("/root/other")]
+        #[case::inside_itself("/root/data/nested")]
+        #[tokio::test]
+        async fn test_move_directory_invalid_destination(filesystem: InMemoryFilesystem, #[case] destination: &str) {
+            // Given a directory, and another beside it
+            filesystem.create_directory(Path::new("/root/data")).await.unwrap();
+            filesystem.create_directory(Path::new("/root/other")).await.unwrap();
+
+            // When I move it somewhere it can't go
+            let result = filesystem.move_file(Path::new("/root/data"), Path::new(destination)).await;
+
+            // Then it should return an error and stay where it was
+            assert!(result.is_err());
+            assert!(filesystem.is_directory(Path::new("/root/data")).await.unwrap());
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_delete_directory(filesystem: InMemoryFilesystem) {
+            // Given a directory containing a file
+            let directory = Path::new("/root/data");
+            let file = Path::new("/root/data/a.json");
+            filesystem.create_directory(directory).await.unwrap();
+            filesystem.write_file(file, b"", FileWriteOptions::CreateNew).await.unwrap();
+
+            // When I delete it while it still has contents
+            let result = filesystem.delete_directory(directory).await;
+
+            // Then it should return an error
+            assert!(matches!(result, Err(FilesystemProviderError::IO(err)) if err.kind() == io::ErrorKind::DirectoryNotEmpty));
+
+            // And once it is empty, it should be deleted
+            filesystem.delete_file(file, FileDeleteOptions::ErrorIfNotExists).await.unwrap();
+            filesystem.delete_directory(directory).await.unwrap();
+            assert!(!filesystem.file_exists(directory).await.unwrap());
+        }
+    }
+}
@@ -0,0 +1,490 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::data::adapters::cache::{CacheStats, ParseCache};
+use crate::data::diagnostic::{self, Diagnostic, DiagnosticSeverity};
+use crate::data::domain::project::{Project, ProjectFile};
+use crate::data::domain::project_index::ProjectIndex;
+
+pub trait LintProvider {
+    /// Checks the project's files, looking resources and function calls up in its index
+    fn lint(&self, project: &Project, index: &ProjectIndex) -> Vec<LintDiagnostic>;
+
+    fn is_enabled(&self, rule: LintRule) -> bool;
+    fn set_enabled(&mut self, rule: LintRule, enabled: bool);
+
+    /// Forgets anything cached about a file, for when it is changed or removed on disk
+    fn file_changed(&self, path: &Path);
+}
+
+/// Runs best practice checks over a project's files. Every rule is enabled by default
+#[derive(Debug, Default)]
+pub struct LintService {
+    disabled_rules: HashSet<LintRule>,
+    /// JSON files which have already been parsed, so linting again skips the unchanged ones
+    parsed: ParseCache<Option<JsonFields>>,
+}
+
+impl LintService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_disabled_rules(rules: impl IntoIterator<Item = LintRule>) -> Self {
+        Self {
+            disabled_rules: rules.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.parsed.stats()
+    }
+}
+
+impl LintProvider for LintService {
+    fn lint(&self, project: &Project, index: &ProjectIndex) -> Vec<LintDiagnostic> {
+        let rules: Vec<_> = LintRule::ALL.iter()
+            .copied()
+            .filter(|rule| self.is_enabled(*rule))
+            .collect();
+
+        let mut diagnostics: Vec<_> = project.iter_files()
+            .flat_map(|file| {
+                rules.iter()
+                    .filter_map(move |rule| rule.check(&file, &self.parsed).map(|message| LintDiagnostic {
+                        rule: *rule,
+                        severity: rule.severity(),
+                        path: file.path.to_path_buf(),
+                        line: rule.line(&file),
+                        message,
+                    }))
+            })
+            .collect();
+
+        if self.is_enabled(LintRule::UnusedFunction) {
+            let call_graph = index.call_graph();
+
+            diagnostics.extend(call_graph.unused_functions().into_iter()
+                .filter_map(|function| Some(LintDiagnostic {
+                    rule: LintRule::UnusedFunction,
+                    severity: LintRule::UnusedFunction.severity(),
+                    path: call_graph.definition(function)?.clone(),
+                    line: None,
+                    message: format!("Function {} is never run", function),
+                })));
+        }
+
+        if self.is_enabled(LintRule::DuplicateResource) {
+            diagnostics.extend(duplicate_resources(index));
+        }
+
+        diagnostics
+    }
+
+    fn is_enabled(&self, rule: LintRule) -> bool {
+        !self.disabled_rules.contains(&rule)
+    }
+
+    fn set_enabled(&mut self, rule: LintRule, enabled: bool) {
+        if enabled {
+            self.disabled_rules.remove(&rule);
+        } else {
+            self.disabled_rules.insert(rule);
+        }
+    }
+
+    fn file_changed(&self, path: &Path) {
+        self.parsed.invalidate(path);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum LintRule {
+    /// A crafting recipe's result doesn't say how many items it gives
+    RecipeResultMissingCount,
+    /// An advancement has no criteria, so it can never be granted by playing
+    AdvancementWithoutCriteria,
+    /// A file path would produce a resource location which the game refuses, such as one with uppercase letters
+    InvalidResourceLocation,
+    /// A function isn't called from `#minecraft:load`, `#minecraft:tick`, an advancement, or another function which is
+    UnusedFunction,
+    /// More than one file defines the same resource, so all but one of them are silently ignored
+    DuplicateResource,
+    /// A resource file's extension doesn't match its kind, such as a `.txt` under `function/`, so the game silently skips it
+    WrongExtension,
+}
+
+impl LintRule {
+    pub const ALL: &'static [LintRule] = &[
+        LintRule::RecipeResultMissingCount,
+        LintRule::AdvancementWithoutCriteria,
+        LintRule::InvalidResourceLocation,
+        LintRule::UnusedFunction,
+        LintRule::DuplicateResource,
+        LintRule::WrongExtension,
+    ];
+
+    /// Stable identifier for settings and diagnostics output
+    pub fn id(&self) -> &'static str {
+        match self {
+            LintRule::RecipeResultMissingCount => "recipe-result-missing-count",
+            LintRule::AdvancementWithoutCriteria => "advancement-without-criteria",
+            LintRule::InvalidResourceLocation => "invalid-resource-location",
+            LintRule::UnusedFunction => "unused-function",
+            LintRule::DuplicateResource => "duplicate-resource",
+            LintRule::WrongExtension => "wrong-extension",
+        }
+    }
+
+    pub fn severity(&self) -> LintSeverity {
+        match self {
+            LintRule::RecipeResultMissingCount => LintSeverity::Info,
+            LintRule::AdvancementWithoutCriteria => LintSeverity::Warning,
+            LintRule::InvalidResourceLocation => LintSeverity::Error,
+            LintRule::UnusedFunction => LintSeverity::Warning,
+            LintRule::DuplicateResource => LintSeverity::Warning,
+            LintRule::WrongExtension => LintSeverity::Warning,
+        }
+    }
+
+    /// The line of a file breaking this rule which the problem is on, for rules about one field of the file
+    fn line(&self, file: &ProjectFile) -> Option<usize> {
+        match self {
+            LintRule::RecipeResultMissingCount => diagnostic::line_of(file.contents, "\"result\""),
+            LintRule::AdvancementWithoutCriteria => diagnostic::line_of(file.contents, "\"criteria\""),
+            _ => None,
+        }
+    }
+
+    /// Returns a message describing the problem if the file breaks this rule
+    fn check(&self, file: &ProjectFile, parsed: &ParseCache<Option<JsonFields>>) -> Option<String> {
+        match self {
+            LintRule::RecipeResultMissingCount => {
+                if !is_json_resource(file, &["recipe", "recipes"]) {
+                    return None;
+                }
+
+                let recipe = parse_fields(file, parsed);
+                let result = (*recipe).as_ref()?.result.as_ref()?;
+
+                // Results given as a plain item id, and cooking results, never carry a count
+                (result.is_object() && result.get("count").is_none())
+                    .then(|| "Recipe result has no count, so it defaults to 1".to_string())
+            }
+            LintRule::AdvancementWithoutCriteria => {
+                if !is_json_resource(file, &["advancement", "advancements"]) {
+                    return None;
+                }
+
+                let advancement = parse_fields(file, parsed);
+                let has_criteria = (*advancement).as_ref()
+                    .and_then(|advancement| advancement.criteria.as_ref())
+                    .and_then(|criteria| criteria.as_object())
+                    .is_some_and(|criteria| !criteria.is_empty());
+
+                (!has_criteria).then(|| "Advancement has no criteria".to_string())
+            }
+            LintRule::InvalidResourceLocation => {
+                file.namespace()?;
+
+                let path = file.path.to_string_lossy();
+                let is_valid_char = |c: char| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.' | '/');
+
+                if path.chars().all(is_valid_char) {
+                    None
+                } else if path.to_lowercase().chars().all(is_valid_char) {
+                    // Only works on case-insensitive filesystems, and can be fixed automatically
+                    Some(format!("{} contains uppercase letters, so it only loads on Windows and macOS. Fix the casing to lowercase it", path))
+                } else {
+                    Some(format!("{} contains characters which aren't allowed in resource locations", path))
+                }
+            }
+            LintRule::WrongExtension => {
+                let expected = file.expected_extension()?;
+                let kind = file.resource_kind()?;
+
+                (file.path.extension() != Some(expected.as_ref())).then(|| format!(
+                    "{} isn't loaded by the game, as {} files need the .{} extension. Fix the extension to rename it",
+                    file.path.display(), kind, expected,
+                ))
+            }
+            // Need the whole project rather than a single file, so they are checked by the service instead
+            LintRule::UnusedFunction | LintRule::DuplicateResource => None,
+        }
+    }
+}
+
+/// Reports every file defining a resource which another file also defines, in the same part of the pack or another.
+/// A file in an overlay replaces the base pack's file while the overlay is active, which is easy to do by accident
+fn duplicate_resources(index: &ProjectIndex) -> Vec<LintDiagnostic> {
+    index.duplicates()
+        .flat_map(|(kind, location, paths)| {
+            let files: Vec<_> = paths.iter()
+                .map(|path| (ProjectFile { path, contents: "" }.split_origin().0, path))
+                .collect();
+
+            files.iter()
+                .map(|(_, path)| {
+                    let others = files.iter()
+                        .filter(|(_, other)| other != path)
+                        .map(|(origin, other)| format!("{} in {}", other.display(), origin))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    LintDiagnostic {
+                        rule: LintRule::DuplicateResource,
+                        severity: LintRule::DuplicateResource.severity(),
+                        path: path.to_path_buf(),
+                        line: None,
+                        message: format!("The {} {} is also defined by {}", kind, location, others),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The fields the lint rules read, so large files aren't parsed in full
+#[derive(serde::Deserialize)]
+struct JsonFields {
+    /// Of a recipe
+    result: Option<serde_json::Value>,
+    /// Of an advancement
+    criteria: Option<serde_json::Value>,
+}
+
+fn parse_fields(file: &ProjectFile, parsed: &ParseCache<Option<JsonFields>>) -> Arc<Option<JsonFields>> {
+    parsed.get_or_parse(file.path, file.contents.as_bytes(), || serde_json::from_str(file.contents).ok())
+}
+
+fn is_json_resource(file: &ProjectFile, kinds: &[&str]) -> bool {
+    file.path.extension().is_some_and(|extension| extension == "json")
+        && file.resource_kind().is_some_and(|kind| kinds.contains(&kind.as_str()))
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LintSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LintDiagnostic {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    /// Path of the offending file, relative to the pack root
+    pub path: PathBuf,
+    /// Line of the file, counting from 1, if the problem is with one field of it
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Display for LintDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} [{}] {}: {}", self.severity, self.rule.id(), self.path.display(), self.message)
+    }
+}
+
+impl From<LintSeverity> for DiagnosticSeverity {
+    fn from(severity: LintSeverity) -> Self {
+        match severity {
+            LintSeverity::Info => DiagnosticSeverity::Info,
+            LintSeverity::Warning => DiagnosticSeverity::Warning,
+            LintSeverity::Error => DiagnosticSeverity::Error,
+        }
+    }
+}
+
+impl From<LintDiagnostic> for Diagnostic {
+    fn from(diagnostic: LintDiagnostic) -> Self {
+        Self {
+            path: diagnostic.path,
+            line: diagnostic.line,
+            severity: diagnostic.severity.into(),
+            rule: diagnostic.rule.id().to_string(),
+            message: diagnostic.message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use super::*;
+
+    #[test]
+    fn test_recipe_missing_count() {
+        // Given recipes with and without a result count
+        let project = Project::test_with_files(&[
+            ("data/test/recipe/no_count.json", r#"{"type": "minecraft:crafting_shapeless", "result": {"id": "minecraft:stone"}}"#),
+            ("data/test/recipe/count.json", r#"{"type": "minecraft:crafting_shapeless", "result": {"id": "minecraft:stone", "count": 4}}"#),
+            ("data/test/recipe/smelting.json", r#"{"type": "minecraft:smelting", "result": "minecraft:stone"}"#),
+        ]);
+
+        // When I lint the project
+        let diagnostics = LintService::new().lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then only the recipe without a count should be reported
+        assert_eq!(diagnostics, vec![LintDiagnostic {
+            rule: LintRule::RecipeResultMissingCount,
+            severity: LintSeverity::Info,
+            path: PathBuf::from("data/test/recipe/no_count.json"),
+            line: Some(1),
+            message: "Recipe result has no count, so it defaults to 1".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_invalid_resource_location() {
+        // Given a function with an uppercase name
+        let project = Project::test_with_files(&[
+            ("data/test/function/Setup.mcfunction", "say hi"),
+            ("data/test/function/setup.mcfunction", "say hi"),
+        ]);
+
+        // When I lint the project, ignoring that nothing runs the functions
+        let diagnostics = LintService::with_disabled_rules([LintRule::UnusedFunction]).lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then it should be reported as an error
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::InvalidResourceLocation);
+        assert_eq!(diagnostics[0].severity, LintSeverity::Error);
+        assert_eq!(diagnostics[0].path, PathBuf::from("data/test/function/Setup.mcfunction"));
+    }
+
+    #[test]
+    fn test_uppercase_namespace() {
+        // Given a function in a namespace written with uppercase letters
+        let project = Project::test_with_files(&[
+            ("data/MyPack/function/setup.mcfunction", "say hi"),
+        ]);
+
+        // When I lint the project, ignoring that nothing runs the function
+        let diagnostics = LintService::with_disabled_rules([LintRule::UnusedFunction]).lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then it should be reported as a casing problem which can be fixed
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::InvalidResourceLocation);
+        assert!(diagnostics[0].message.contains("uppercase"));
+    }
+
+    #[rstest]
+    #[case::text_function("data/test/function/setup.txt", "say hi")]
+    #[case::function_as_recipe("data/test/recipe/gem.mcfunction", r#"{"type": "minecraft:crafting_shapeless"}"#)]
+    fn test_wrong_extension(#[case] path: &str, #[case] contents: &str) {
+        // Given a resource saved with the wrong extension for its kind
+        let project = Project::test_with_files(&[(path, contents)]);
+
+        // When I lint the project, ignoring that nothing runs any functions
+        let diagnostics = LintService::with_disabled_rules([LintRule::UnusedFunction]).lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then it should be reported as a problem which can be fixed
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::WrongExtension);
+        assert_eq!(diagnostics[0].path, PathBuf::from(path));
+        assert!(diagnostics[0].message.contains("Fix the extension"));
+    }
+
+    #[test]
+    fn test_unused_function() {
+        // Given a load function, and a function which nothing runs
+        let project = Project::test_with_files(&[
+            ("data/minecraft/tags/function/load.json", r#"{"values": ["test:load"]}"#),
+            ("data/test/function/load.mcfunction", "say loaded"),
+            ("data/test/function/forgotten.mcfunction", "say forgotten"),
+        ]);
+
+        // When I lint the project
+        let diagnostics = LintService::new().lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then only the forgotten function should be reported
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, LintRule::UnusedFunction);
+        assert_eq!(diagnostics[0].path, PathBuf::from("data/test/function/forgotten.mcfunction"));
+    }
+
+    #[test]
+    fn test_duplicate_across_overlay() {
+        // Given a recipe in the base pack, which an overlay defines again
+        let project = Project::test_with_files(&[
+            ("data/test/recipe/gem.json", r#"{"type": "minecraft:smelting", "result": "minecraft:emerald"}"#),
+            ("v2/data/test/recipe/gem.json", r#"{"type": "minecraft:smelting", "result": "minecraft:diamond"}"#),
+            ("v2/data/test/recipe/other.json", r#"{"type": "minecraft:smelting", "result": "minecraft:stone"}"#),
+        ]);
+
+        // When I lint the project
+        let diagnostics = LintService::new().lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then both copies should be reported, each naming where the other comes from
+        let duplicates: Vec<_> = diagnostics.iter().filter(|diagnostic| diagnostic.rule == LintRule::DuplicateResource).collect();
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].path, PathBuf::from("data/test/recipe/gem.json"));
+        assert_eq!(duplicates[0].message, "The recipe test:gem is also defined by v2/data/test/recipe/gem.json in overlay v2");
+        assert_eq!(duplicates[1].path, PathBuf::from("v2/data/test/recipe/gem.json"));
+        assert_eq!(duplicates[1].message, "The recipe test:gem is also defined by data/test/recipe/gem.json in the base pack");
+    }
+
+    #[test]
+    fn test_same_location_different_kind_not_duplicate() {
+        // Given a recipe and an advancement with the same resource location
+        let project = Project::test_with_files(&[
+            ("data/test/recipe/gem.json", r#"{"type": "minecraft:smelting", "result": "minecraft:emerald"}"#),
+            ("data/test/advancement/gem.json", r#"{"criteria": {"tick": {"trigger": "minecraft:tick"}}}"#),
+        ]);
+
+        // When I lint the project
+        let diagnostics = LintService::new().lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then they shouldn't be reported as duplicates
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.rule != LintRule::DuplicateResource));
+    }
+
+    #[test]
+    fn test_disabled_rule() {
+        // Given an advancement without criteria
+        let project = Project::test_with_files(&[
+            ("data/test/advancement/root.json", r#"{"display": {}}"#),
+        ]);
+
+        // When I lint it with the rule enabled
+        let mut lint_service = LintService::new();
+        let enabled_diagnostics = lint_service.lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then it should be reported
+        assert_eq!(enabled_diagnostics.len(), 1);
+        assert_eq!(enabled_diagnostics[0].rule, LintRule::AdvancementWithoutCriteria);
+
+        // And when I disable the rule
+        lint_service.set_enabled(LintRule::AdvancementWithoutCriteria, false);
+        let disabled_diagnostics = lint_service.lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then it should no longer be reported
+        assert!(disabled_diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_relint_served_from_cache() {
+        // Given a project which has already been linted
+        let project = Project::test_with_files(&[
+            ("data/test/recipe/no_count.json", r#"{"type": "minecraft:crafting_shapeless", "result": {"id": "minecraft:stone"}}"#),
+            ("data/test/advancement/root.json", r#"{"display": {}}"#),
+        ]);
+        let lint_service = LintService::new();
+        let first_diagnostics = lint_service.lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // When it is linted again without changes
+        let second_diagnostics = lint_service.lint(&project, &ProjectIndex::build(&project, |_| {}));
+
+        // Then the files should not be parsed a second time
+        assert_eq!(first_diagnostics, second_diagnostics);
+        assert_eq!(lint_service.cache_stats(), CacheStats { hits: 2, misses: 2, entries: 2 });
+
+        // And when a file is reported as changed, it should be parsed again
+        lint_service.file_changed(Path::new("data/test/advancement/root.json"));
+        lint_service.lint(&project, &ProjectIndex::build(&project, |_| {}));
+        assert_eq!(lint_service.cache_stats().misses, 3);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+/// Service operations slow enough to be worth timing
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Operation {
+    Import,
+    Export,
+    Save,
+    Index,
+}
+
+impl Operation {
+    pub const ALL: [Operation; 4] = [Operation::Import, Operation::Export, Operation::Save, Operation::Index];
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Import => write!(f, "import"),
+            Operation::Export => write!(f, "export"),
+            Operation::Save => write!(f, "save"),
+            Operation::Index => write!(f, "index"),
+        }
+    }
+}
+
+/// Where the durations of timed operations are sent
+pub trait MetricsProvider {
+    fn record(&self, operation: Operation, duration: Duration);
+
+    /// Timings recorded so far for the operation, or `None` if it hasn't run yet
+    fn stats(&self, operation: Operation) -> Option<OperationStats>;
+}
+
+/// How long an operation has taken over every time it ran
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct OperationStats {
+    pub count: u32,
+    pub last: Duration,
+    pub total: Duration,
+}
+
+impl OperationStats {
+    pub fn average(&self) -> Duration {
+        self.total.checked_div(self.count).unwrap_or_default()
+    }
+}
+
+/// Keeps timings in memory for a debug panel or tests, and logs each one as it's recorded, such as `import took 1.8s`
+#[derive(Debug, Default)]
+pub struct MetricsService {
+    stats: Mutex<HashMap<Operation, OperationStats>>,
+}
+
+impl MetricsService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MetricsProvider for MetricsService {
+    fn record(&self, operation: Operation, duration: Duration) {
+        let mut stats = self.stats.lock().unwrap();
+        let stats = stats.entry(operation).or_default();
+        stats.count += 1;
+        stats.last = duration;
+        stats.total += duration;
+
+        tracing::debug!("{} took {:.1?}, averaging {:.1?} over {} runs", operation, duration, stats.average(), stats.count);
+    }
+
+    fn stats(&self, operation: Operation) -> Option<OperationStats> {
+        self.stats.lock().unwrap().get(&operation).copied()
+    }
+}
+
+/// Runs an operation within a tracing span named after it, recording how long it took whether or not it succeeded
+pub async fn timed<F: Future>(metrics: &(dyn MetricsProvider + Send + Sync), operation: Operation, future: F) -> F::Output {
+    let start = Instant::now();
+    let output = future.instrument(tracing::info_span!("operation", name = %operation)).await;
+    metrics.record(operation, start.elapsed());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timed_operation_recorded() {
+        // Given somewhere to record timings
+        let metrics = MetricsService::new();
+
+        // When an operation is timed
+        let output = timed(&metrics, Operation::Index, async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            "indexed"
+        }).await;
+
+        // Then its output should be passed through, and a non-zero duration recorded for it alone
+        assert_eq!(output, "indexed");
+
+        let stats = metrics.stats(Operation::Index).unwrap();
+        assert_eq!(stats.count, 1);
+        assert!(stats.last >= Duration::from_millis(5));
+        assert_eq!(metrics.stats(Operation::Import), None);
+    }
+
+    #[test]
+    fn test_stats_accumulate() {
+        // Given an operation which has run twice
+        let metrics = MetricsService::new();
+        metrics.record(Operation::Save, Duration::from_millis(100));
+        metrics.record(Operation::Save, Duration::from_millis(300));
+
+        // When I get its stats
+        let stats = metrics.stats(Operation::Save).unwrap();
+
+        // Then they should cover both runs
+        assert_eq!(stats, OperationStats { count: 2, last: Duration::from_millis(300), total: Duration::from_millis(400) });
+        assert_eq!(stats.average(), Duration::from_millis(200));
+    }
+}
@@ -1,7 +1,11 @@
 pub mod project_service;
 pub mod filesystem_service;
+pub mod in_memory_filesystem;
 pub mod translation_service;
 pub mod resource;
 pub mod download_service;
 pub mod undo_service;
-pub mod zip_service;
\ No newline at end of file
+pub mod zip_service;
+pub mod lint_service;
+pub mod dialog_service;
+pub mod metrics_service;
\ No newline at end of file
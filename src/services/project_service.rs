@@ -1,14 +1,33 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Debug;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
+use dashmap::DashMap;
 use tokio::sync::RwLock;
 use crate::data::adapters::{self, AdapterInput};
-use crate::data::adapters::project::SerializedProjectData;
-use crate::data::domain::project::{Project, ProjectID, ProjectSettings, ProjectType};
-use crate::data::serialization::project::{Project as SerializedProject, SerializedProjectType};
+use crate::data::adapters::project::{FormatMismatch, ProjectDeserializeError, SerializedProjectData};
+use crate::data::domain::compatibility::{self, CompatibilityWarning};
+use crate::data::domain::install_context::InstallContext;
+use crate::data::domain::pack_icon::{PackIcon, PackIconError, PackIconWarning};
+use crate::data::domain::pack_info::PackDescription;
+use crate::data::domain::project::{self, CasingFix, CasingFixError, DescriptionError, ExtensionFix, ExtensionFixError, MoveFileError, NamespaceRenameError, PackHalf, Project, ProjectID, ProjectSettings, ProjectStats, ProjectType, ReferenceRewrite};
+use crate::data::domain::project_index::{IndexProgress, IndexProgressCallback, ProjectIndex};
+use crate::data::domain::project_settings_file::ProjectSettingsFile;
+use crate::data::domain::project_template::{ProjectTemplate, TemplateError};
+use crate::data::domain::resource::resource::ResourceLocation;
+use crate::data::domain::search::{SearchCancellation, SearchMatchCallback, SearchQuery, SearchSummary};
+use crate::data::domain::versions;
+use crate::data::serialization::json::JsonStyle;
+use crate::data::serialization::pack_info::{PackData, PackInfo};
+use crate::data::serialization::structure::Structure;
+use crate::data::serialization::resource_location::ResourceLocation as SerializedResourceLocation;
+use crate::data::serialization::project::{ImportReport, Project as SerializedProject, SerializedProjectType, ZippableProject, PACK_ICON_FILE, PACK_INFO_FILE};
 use crate::repositories::adapter_repo;
-use crate::repositories::adapter_repo::{AdapterRepoError, AdapterRepository, AdapterProviderContext};
-use crate::repositories::project_repo::{self, ProjectRepoError, ProjectRepository};
+use crate::repositories::adapter_repo::{AdapterRepoError, AdapterRepository, AdapterProviderContext, AdapterRegistrationBuilder, AdapterRegistrationError};
+use crate::repositories::project_repo::{self, ProjectCreationError, ProjectRepoError, ProjectRepository, SaveCancellation, SaveProgressCallback};
+use crate::services::lint_service::{LintDiagnostic, LintProvider};
+use crate::services::metrics_service::{self, MetricsProvider, MetricsService, Operation};
 use crate::services::zip_service;
 use crate::services::zip_service::ZipService;
 
@@ -23,17 +42,132 @@ pub trait ProjectServiceProvider {
         settings: ProjectSettings,
         overwrite_existing: bool,
     ) -> Result<ProjectID>;
+    /// Creates a project the same way as [`ProjectServiceProvider::create_project`], starting it with a template's files
+    async fn create_from_template(
+        &self,
+        template_id: &str,
+        settings: ProjectSettings,
+        overwrite_existing: bool,
+    ) -> Result<ProjectID>;
 
     async fn open_project(&self, path: &Path) -> Result<ProjectID>;
+    /// Opens a project for inspection only. It can be browsed, but any edit or save fails with [`ProjectServiceError::ReadOnly`]
+    async fn open_project_read_only(&self, path: &Path) -> Result<ProjectID>;
     async fn close_project(&self, project_id: ProjectID) -> Result<()>;
-    async fn save_project(&self, project_id: ProjectID) -> Result<PathBuf>;
-    async fn import_zip(&self, path: ZipPath) -> Result<ProjectID>;
 
+    /// Whether the project has changes which haven't been saved yet, and so would stop it closing
+    async fn has_unsaved_changes(&self, project_id: ProjectID) -> Result<bool>;
+    /// Marks the project as having no unsaved changes without saving it, so it can be closed
+    async fn discard_changes(&self, project_id: ProjectID) -> Result<()>;
+
+    /// Editor state for the project, which is written beside it whenever it is saved or closed
+    async fn settings_file(&self, project_id: ProjectID) -> Result<ProjectSettingsFile>;
+    async fn set_settings_file(&self, project_id: ProjectID, settings_file: ProjectSettingsFile) -> Result<()>;
+    async fn save_project(&self, project_id: ProjectID) -> Result<PathBuf>;
+    /// Saves the project like [`ProjectServiceProvider::save_project`], reporting each file written.
+    /// Cancelling leaves the project's saved files and unsaved changes as they were
+    async fn save_project_with_progress(&self, project_id: ProjectID, on_progress: SaveProgressCallback, cancellation: SaveCancellation) -> Result<PathBuf>;
+
+    /// Sets where autosaves and snapshots are written. Defaults to a directory in the user's local app data, see [`project_repo::default_workspace_dir`]
+    async fn set_workspace_dir(&self, workspace_dir: PathBuf);
+    /// Writes the project as it is now to its autosave in the workspace directory, replacing the previous one.
+    /// Unlike saving, this works for read-only projects and those without a path, and leaves the unsaved changes flagged
+    async fn autosave_project(&self, project_id: ProjectID) -> Result<PathBuf>;
+    /// Writes the project as it is now to a new snapshot in the workspace directory, keeping earlier snapshots
+    async fn snapshot_project(&self, project_id: ProjectID) -> Result<PathBuf>;
+
+    /// Saves the project to a new path, which becomes the project's path from then on.
+    /// This is the fallback when saving fails with [`SaveError::NoPathSet`]
+    async fn save_project_as(&self, project_id: ProjectID, path: &Path, overwrite_existing: bool) -> Result<PathBuf>;
+    /// Imports a project from one or two zip files, returning anything in them the import had to work around
+    async fn import_zip(&self, path: ZipPath) -> Result<(ProjectID, ImportReport)>;
+    /// Imports only one pack from a zip file. A zip with both `data` and `assets` otherwise imports as a combined project,
+    /// and any other zip must already be the requested kind of pack
+    async fn import_zip_as(&self, path: &Path, pack: PackHalf) -> Result<(ProjectID, ImportReport)>;
+    /// Imports like [`ProjectServiceProvider::import_zip`], then indexes the project, reporting each stage as it begins.
+    /// Nothing more is reported once a stage fails
+    async fn import_zip_with_progress(&self, path: ZipPath, on_progress: ImportProgressCallback) -> Result<(ProjectID, ImportReport)>;
+
+    /// Moves all files in one namespace to another, rewriting references to resources in the old namespace.
+    /// Returns the number of files which were moved
+    async fn rename_namespace(&self, project_id: ProjectID, from: &str, to: &str) -> Result<usize>;
+    /// Rewrites references to resources across every file of the project. Each location written with its namespace is read
+    /// through the adapters and passed to `rewriter`, and replaced wherever it gives a new location
+    async fn rewrite_references(&self, project_id: ProjectID, rewriter: &ReferenceRewriter) -> Result<ReferenceRewrite>;
+    /// Moves a file to another path in the project, given both relative to the pack root. References to it are left as they are
+    async fn move_file(&self, project_id: ProjectID, from: &Path, to: &Path) -> Result<()>;
+    /// Takes in a file the editor saved straight to the project's folder, given relative to the pack root,
+    /// so that saving the project doesn't write its old contents back over it
+    async fn file_saved(&self, project_id: ProjectID, path: &Path, contents: String, bom: bool) -> Result<()>;
+    /// Forgets anything parsed from a file, given relative to the pack root, for when the editor starts changing it
+    async fn file_changed(&self, path: &Path);
+
+    /// Lowercases the paths of files with uppercase letters in their resource locations, rewriting references to them.
+    /// Returns the files which were moved
+    async fn fix_resource_casing(&self, project_id: ProjectID) -> Result<Vec<CasingFix>>;
+
+    /// Renames data pack files whose extension doesn't match their resource kind, e.g. a `.txt` under `function/`.
+    /// Returns the files which were renamed
+    async fn fix_resource_extensions(&self, project_id: ProjectID) -> Result<Vec<ExtensionFix>>;
+
+    /// Figures about the project as it is now, including the oldest version of the game able to load it
+    async fn project_stats(&self, project_id: ProjectID) -> Result<ProjectStats>;
+
+    /// Sets the description of one of the project's packs, written to that pack's `pack.mcmeta` on export
+    async fn set_description(&self, project_id: ProjectID, pack: PackHalf, description: PackDescription) -> Result<()>;
+    /// Replaces the project's `pack.png` with the given image, which must be a PNG.
+    /// Sizes the game doesn't expect are allowed, and returned as warnings
+    async fn set_pack_icon(&self, project_id: ProjectID, data: Vec<u8>) -> Result<Vec<PackIconWarning>>;
+    /// The project's `pack.png`, or `None` if it doesn't have one
+    async fn pack_icon(&self, project_id: ProjectID) -> Result<Option<PackIcon>>;
+    /// The `pack.png` of a zipped pack, read without importing the rest of it, or `None` if it doesn't have one
+    async fn zip_pack_icon(&self, path: &Path) -> Result<Option<PackIcon>>;
+
+    /// Where the path sits within a Minecraft installation, if anywhere, to suggest a project type and export destination
+    fn detect_install_context(&self, path: &Path) -> Option<InstallContext>;
+
+    /// Builds the project's index off the async runtime, reporting each file indexed.
+    /// Indexing again replaces the previous index, picking up any changes since
+    async fn index_project(&self, project_id: ProjectID, on_progress: IndexProgressCallback) -> Result<Arc<ProjectIndex>>;
+    /// The project's index, or `None` until it has been built
+    fn project_index(&self, project_id: ProjectID) -> Option<Arc<ProjectIndex>>;
+
+    /// How long imports, exports, saves and indexing have taken
+    fn metrics(&self) -> &(dyn MetricsProvider + Send + Sync);
+
+    /// Searches every file of the project as it is now, passing each file's matches to `on_match` as soon as they are found.
+    /// Files are searched a few at a time off the async runtime, and cancelling stops any more from being started
+    async fn find_in_project(&self, project_id: ProjectID, query: SearchQuery, on_match: SearchMatchCallback, cancellation: SearchCancellation) -> Result<SearchSummary>;
+
+    /// Runs the given linter over the project as it is now
+    async fn lint_project(&self, project_id: ProjectID, lint_provider: &(dyn LintProvider + Send + Sync)) -> Result<Vec<LintDiagnostic>>;
+
+    /// Exports the project to one or two zip files, returning any compatibility warnings for the project's pack format.
+    /// If [`ProjectZipData::strict_compatibility`] is set, any warnings fail the export with [`ZipError::Incompatible`] instead
     async fn export_zip(
         &self,
         zip_data: ProjectZipData,
         overwrite_existing: bool,
-    ) -> Result<()>;
+    ) -> Result<Vec<CompatibilityWarning>>;
+
+    /// Exports the project into the zip files it was exported to before, only rewriting the entries which changed since.
+    /// Unchanged entries keep their bytes and timestamps, and archives which don't exist yet are created
+    async fn update_export(&self, zip_data: ProjectZipData) -> Result<Vec<CompatibilityWarning>>;
+
+    /// Exports several projects one after another, carrying on past any which fail so that one failure doesn't hide the others.
+    /// Each outcome is returned beside its project, in the order given
+    async fn export_zips(
+        &self,
+        exports: Vec<ProjectZipData>,
+        overwrite_existing: bool,
+    ) -> Vec<(ProjectID, Result<Vec<CompatibilityWarning>>)>;
+
+    /// Works out what [`ProjectServiceProvider::export_zip`] would write, without touching the disk.
+    /// Fails in the same cases as the export would, other than those caused by writing the files
+    async fn plan_export(&self, zip_data: ProjectZipData) -> Result<ExportPlan>;
+    /// What exporting the project again would change compared to an earlier export read from the zip path or paths.
+    /// If the earlier export has both packs in one zip, a single-type project is compared with its own pack
+    async fn diff_against_export(&self, project_id: ProjectID, zip_path: ZipPath) -> Result<ExportDiff>;
 }
 
 pub struct ProjectService<
@@ -44,6 +178,8 @@ pub struct ProjectService<
     project_provider: Arc<RwLock<ProjectProvider>>,
     zip_provider: Arc<RwLock<ZipProvider>>,
     adapter_provider: Arc<RwLock<AdapterProvider>>,
+    indexes: DashMap<ProjectID, Arc<ProjectIndex>>,
+    metrics: Arc<dyn MetricsProvider + Send + Sync>,
 }
 
 impl<ProjectProvider, ZipProvider, AdapterProvider> ProjectService<ProjectProvider, ZipProvider, AdapterProvider>
@@ -63,27 +199,36 @@ where
             project_provider: Arc::new(RwLock::new(project_provider)),
             zip_provider: Arc::new(RwLock::new(zip_provider)),
             adapter_provider: Arc::new(RwLock::new(adapter_provider)),
+            indexes: DashMap::new(),
+            metrics: Arc::new(MetricsService::new()),
         }
     }
     
+    /// Adds adapters for new file types on top of the defaults.
+    /// Fails if any of them would replace a default adapter or one registered before it
     pub fn with_adapters(
         project_provider: ProjectProvider,
         zip_provider: ZipProvider,
-        mut adapter_provider: AdapterProvider,
-        adapter_register_fn: impl FnOnce(&mut AdapterProvider) -> (),
-    ) -> Self {
-        // Register default adapters first to make sure all adapters are covered
-        // Then any others can be re-registered
-        // This code should only be run once at app startup, so performance is not a concern,
-        // Plus the adapter wrappers stored by the repo are tiny anyway
-        adapters::register_default_adapters(&mut adapter_provider);
-        adapter_register_fn(&mut adapter_provider);
+        adapter_provider: AdapterProvider,
+        adapter_register_fn: impl FnOnce(&mut AdapterRegistrationBuilder<AdapterProvider>),
+    ) -> std::result::Result<Self, AdapterRegistrationError> {
+        // Register default adapters first, so the builder knows which conversions are already covered
+        let mut registrations = adapters::default_adapter_registrations(&adapter_provider);
+        adapter_register_fn(&mut registrations);
+        registrations.finish()?;
         
-        Self {
+        Ok(Self {
             project_provider: Arc::new(RwLock::new(project_provider)),
             zip_provider: Arc::new(RwLock::new(zip_provider)),
             adapter_provider: Arc::new(RwLock::new(adapter_provider)),
-        }
+            indexes: DashMap::new(),
+            metrics: Arc::new(MetricsService::new()),
+        })
+    }
+    
+    /// Sends operation timings somewhere other than the in-memory [`MetricsService`] each service starts with
+    pub fn with_metrics(self, metrics: Arc<dyn MetricsProvider + Send + Sync>) -> Self {
+        Self { metrics, ..self }
     }
     
     #[cfg(test)]
@@ -96,6 +241,8 @@ where
             project_provider: Arc::new(RwLock::new(project_provider)),
             zip_provider: Arc::new(RwLock::new(zip_provider)),
             adapter_provider: Arc::new(RwLock::new(adapter_provider)),
+            indexes: DashMap::new(),
+            metrics: Arc::new(MetricsService::new()),
         }
     }
 
@@ -111,77 +258,63 @@ where
         Ok(settings)
     }
 
+    /// Combined projects are exported as a data pack and a resource pack, so both halves need formats
+    /// which share a Minecraft version. Checked up front, the same as importing a combined project does
+    fn validate_project_version(settings: &ProjectSettings) -> Result<()> {
+        let ProjectSettings::Combined { project_version, .. } = settings else {
+            return Ok(());
+        };
+        let version = project_version.version;
+
+        let data_format = versions::find_datapack_format_for_version(version)
+            .ok_or_else(|| ProjectServiceError::InvalidVersion(format!("No data format for version {}", version)))?;
+        let resource_format = versions::find_resourcepack_format_for_version(version)
+            .ok_or_else(|| ProjectServiceError::InvalidVersion(format!("No resource format for version {}", version)))?;
+
+        if data_format.common_versions(resource_format).next().is_none() {
+            return Err(ProjectServiceError::InvalidVersion(format!(
+                "No common mc versions between data and resource packs! Data format: {}, Resource format: {}",
+                data_format.get_format_id(),
+                resource_format.get_format_id()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Replaces characters which aren't allowed in file names. Paths which climb out with `..` are refused rather than
+    /// sanitized, since there's no telling where they would end up
     fn sanitize_path(path: &Path) -> Result<PathBuf> {
+        if path.components().any(|component| component == Component::ParentDir) {
+            return Err(ProjectRepoError::Create(ProjectCreationError::InvalidPath(path.display().to_string())).into());
+        }
+
         let options = sanitize_filename::Options {
             replacement: "_",
             ..sanitize_filename::Options::default()
         };
 
-        let sanitized_path = path.iter().map(|path_segment| {
-            sanitize_filename::sanitize_with_options(
+        // Only the names are sanitized, so absolute paths keep their root or drive
+        let sanitized_path = path.components().map(|component| match component {
+            Component::Normal(path_segment) => sanitize_filename::sanitize_with_options(
                 path_segment.to_string_lossy(),
                 options.clone()
-            )
-        }).collect();
+            ).into(),
+            component => component.as_os_str().to_os_string(),
+        }).collect::<PathBuf>();
 
         Ok(sanitized_path)
     }
-}
-
-#[async_trait::async_trait]
-impl<ProjectProvider, ZipProvider, AdapterProvider> ProjectServiceProvider for ProjectService<ProjectProvider, ZipProvider, AdapterProvider>
-where
-    ProjectProvider: project_repo::ProjectProvider + Send + Sync + 'static,
-    ZipProvider: zip_service::ZipProvider<SerializedProject> + Send + Sync + 'static,
-    AdapterProvider: adapter_repo::AdapterProvider + Send + Sync + 'static,
-{
-    async fn create_project(
-        &self,
-        settings: ProjectSettings,
-        overwrite_existing: bool,
-    ) -> Result<ProjectID> {
-        let sanitized_settings = Self::sanitize_project_settings(settings)?;
-
-        let project = Project::from_settings(sanitized_settings);
-
-        let project_id = self.project_provider.read().await.add_project(project, overwrite_existing)?;
-        Ok(project_id)
-    }
-
-    async fn open_project(&self, path: &Path) -> Result<ProjectID> {
-        let provider = self.project_provider.read().await;
-        provider.open_project(path).await.map_err(Into::into)
-    }
-
-    async fn close_project(&self, project_id: ProjectID) -> Result<()> {
-        let project_provider = self.project_provider.read().await;
-        
-        project_provider.with_project(project_id, |project| {
-            if *project.has_unsaved_changes() {
-                return Err(ProjectServiceError::CannotCloseUnsavedChanges);
-            }
-            Ok(())
-        }).ok_or(ProjectServiceError::ProjectDoesNotExist)??;
-
-        project_provider.close_project(project_id)?;
-        Ok(())
-    }
-
-    async fn save_project(&self, project_id: ProjectID) -> Result<PathBuf> {
-        self.project_provider.read().await.save_project(project_id).await.map_err(Into::into)
-    }
 
-    async fn import_zip(&self, path: ZipPath) -> Result<ProjectID> {
+    /// Extracts the zip or zips of an import. A single zip with both `data` and `assets` is split into a combined project
+    async fn extract_zip_path(&self, path: ZipPath) -> Result<SerializedProjectData> {
         let serialized_project = match path {
             ZipPath::Single(path) => {
                 let zip_provider = self.zip_provider.read().await;
                 let result = zip_provider.extract(path.as_path()).await;
                 let serialized_project = result.map_err(ZipError::Zipping)?;
 
-                match serialized_project.project_type() {
-                    SerializedProjectType::Data => SerializedProjectData::Data(serialized_project),
-                    SerializedProjectType::Resource => SerializedProjectData::Resource(serialized_project),
-                }
+                Self::project_data(serialized_project).await
             }
             ZipPath::Combined { data_path, resource_path } => {
                 let (data_project, resource_project) = tokio::try_join!(
@@ -193,40 +326,205 @@ where
             }
         };
 
+        Ok(serialized_project)
+    }
+
+    /// Reads and deserializes the project in a folder, then opens it. Read-only projects are marked before they're opened,
+    /// so there's never a moment where they could be changed
+    async fn open(&self, path: &Path, read_only: bool) -> Result<ProjectID> {
+        let serialized_project = self.project_provider.read().await.read_project(path).await?;
+        let serialized_project = Self::project_data(serialized_project).await;
+        let mut project = self.deserialize_project(&serialized_project).await?.with_path(path.to_path_buf());
+        project.set_read_only(read_only);
+
+        let provider = self.project_provider.read().await;
+
+        // Losing the editor state isn't worth refusing to open the project over
+        match provider.read_settings_file(path).await {
+            Ok(settings_file) => project.set_settings_file(settings_file),
+            Err(error) => tracing::warn!("Could not restore editor state for {} - {}", path.display(), error),
+        }
+
+        let project_id = provider.open_project(project).await?;
+        Ok(project_id)
+    }
+
+    /// Sorts a single pack by its type, splitting one with both `data` and `assets` into a combined project
+    async fn project_data(serialized_project: SerializedProject) -> SerializedProjectData {
+        if serialized_project.is_mixed() {
+            let (data_project, resource_project) = Self::split_mixed(serialized_project).await;
+            SerializedProjectData::Combined { data_project, resource_project }
+        } else {
+            match serialized_project.project_type() {
+                SerializedProjectType::Data => SerializedProjectData::Data(serialized_project),
+                SerializedProjectType::Resource => SerializedProjectData::Resource(serialized_project),
+            }
+        }
+    }
+
+    async fn deserialize_project(&self, serialized_project: &SerializedProjectData) -> Result<Project> {
         let adapter_context = AdapterProviderContext::new(self.adapter_provider.read().await);
-        let serialize_input = AdapterInput::new(&serialized_project);
-        
-        let project: Project = self.adapter_provider.read().await.deserialize(serialize_input, adapter_context).await.map_err(ZipError::Deserialization)?;
+        let serialize_input = AdapterInput::new(serialized_project);
+
+        let project = self.adapter_provider.read().await.deserialize(serialize_input, adapter_context).await.map_err(Self::deserialization_error)?;
+        Ok(project)
+    }
+
+    /// Deserializes the extracted zips and adds the project, reporting what the import had to work around
+    async fn finish_import(&self, serialized_project: SerializedProjectData) -> Result<(ProjectID, ImportReport)> {
+        let project = self.deserialize_project(&serialized_project).await?;
         let project_id = *project.id();
 
+        let report = |project: &SerializedProject| ImportReport {
+            empty_pack: project.is_empty(),
+            ..project.import_report().clone()
+        };
+        let import_report = match serialized_project {
+            SerializedProjectData::Data(project) | SerializedProjectData::Resource(project) => report(&project),
+            SerializedProjectData::Combined { data_project, resource_project } => {
+                report(&data_project).merge(report(&resource_project))
+            }
+        };
+
         // TODO: Maybe prevent accidental duplicate importing somehow?
         let project_provider = self.project_provider.write().await;
         project_provider.add_project(project, false)?;
-        Ok(project_id)
+        Ok((project_id, import_report))
     }
 
-    async fn export_zip(
+    /// Raises a combined project's packs having no version in common to its own error, suggesting how to fix them
+    fn deserialization_error(error: AdapterRepoError) -> ZipError {
+        let cause = match &error {
+            AdapterRepoError::DeserializationError(cause) => (&**cause as &(dyn std::error::Error + 'static)).downcast_ref::<ProjectDeserializeError>(),
+            _ => None,
+        };
+
+        match cause {
+            Some(ProjectDeserializeError::NoCommonVersion(mismatch)) => ZipError::NoCommonVersion {
+                suggested_resource_format: suggest_resource_format(mismatch),
+                mismatch: mismatch.clone(),
+            },
+            _ => ZipError::Deserialization(error),
+        }
+    }
+
+    /// Splits a zip with both `data` and `assets` into its data and resource packs. Its `pack.mcmeta` can only give one format,
+    /// which is taken as the data pack's since the game loads the archive as a data pack, so the resource pack is given
+    /// the resource format of the oldest version with that data format
+    async fn split_mixed(serialized_project: SerializedProject) -> (SerializedProject, SerializedProject) {
+        let resource_pack_info = {
+            let pack_info = serialized_project.pack_info().read().await;
+            let data_format = *pack_info.pack().pack_format();
+
+            let version = u8::try_from(data_format).ok()
+                .and_then(|format| versions::DATA_FORMAT_MAP.get(&format).and_then(|format| format.min_version()));
+
+            match version.and_then(versions::find_resourcepack_format_for_version) {
+                Some(resource_format) => PackInfo::new(
+                    PackData::new(pack_info.pack().description().clone(), resource_format.get_format_id() as u32, None),
+                    None, None, None, None,
+                ),
+                // Left for deserialization to report the unknown format
+                None => pack_info.clone(),
+            }
+        };
+
+        serialized_project.split_mixed(resource_pack_info)
+    }
+
+    /// Read-only projects refuse every change, so they can be inspected without any risk of overwriting them
+    fn check_writable(project_provider: &ProjectProvider, project_id: ProjectID) -> Result<()> {
+        let read_only = project_provider.with_project(project_id, |project| *project.read_only())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)?;
+
+        if read_only {
+            return Err(ProjectServiceError::ReadOnly);
+        }
+
+        Ok(())
+    }
+
+    fn check_can_save(project_provider: &ProjectProvider, project_id: ProjectID) -> Result<()> {
+        Self::check_writable(project_provider, project_id)?;
+
+        // A missing path takes priority, since the caller needs to prompt for one regardless of changes
+        project_provider.with_project(project_id, |project| {
+            if project.path().is_none() {
+                return Err(SaveError::NoPathSet);
+            }
+            if !*project.has_unsaved_changes() {
+                return Err(SaveError::NoChangesToSave);
+            }
+            Ok(())
+        }).ok_or(ProjectServiceError::ProjectDoesNotExist)??;
+
+        Ok(())
+    }
+
+    async fn finish_save(project_provider: &ProjectProvider, project_id: ProjectID) -> Result<()> {
+        project_provider.with_project_mut(project_id, |project| project.clear_unsaved_changes());
+        Self::write_settings_file(project_provider, project_id).await
+    }
+
+    /// Writes the project's editor state beside it. Projects without a path have nowhere to keep it yet
+    async fn write_settings_file(project_provider: &ProjectProvider, project_id: ProjectID) -> Result<()> {
+        let (path, settings_file) = project_provider.with_project(project_id, |project| {
+            (project.path().clone(), project.settings_file().clone())
+        }).ok_or(ProjectServiceError::ProjectDoesNotExist)?;
+
+        if let Some(path) = path {
+            project_provider.write_settings_file(&path, &settings_file).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Serializes a project for export and checks it against its pack format, without writing anything
+    async fn serialize_for_export(
         &self,
-        zip_data: ProjectZipData,
-        overwrite_existing: bool,
-    ) -> Result<()> {
-        let (serialized_project, project_type) = {
+        project_id: ProjectID,
+        strict_compatibility: bool,
+        json_style: JsonStyle,
+    ) -> Result<(SerializedProjectData, ProjectType, Vec<CompatibilityWarning>)> {
+        let (serialized_project, project_type, project_version) = {
             let project_provider = self.project_provider.read().await;
 
             let adapter_provider = self.adapter_provider.read().await;
             let adapter_context = AdapterProviderContext::new(self.adapter_provider.read().await);
 
-            project_provider.with_project_async(zip_data.project_id, |project: Arc<RwLock<Project>>| {
+            project_provider.with_project_async(project_id, |project: Arc<RwLock<Project>>| {
                 Box::pin(async move {
                     let project_lock = &*project.read().await;
-                    let project_input = AdapterInput::new(project_lock);
+                    let project_input = AdapterInput::new(project_lock).with_json_style(json_style);
 
                     let serialized_project = adapter_provider.serialize(project_input, adapter_context).await.map_err(ZipError::Serialization)?;
 
-                    Ok::<_, ProjectServiceError>((serialized_project, project.read().await.project_type().clone()))
+                    Ok::<_, ProjectServiceError>((serialized_project, project_lock.project_type(), project_lock.project_version().clone()))
                 })
             }).await.ok_or(ProjectServiceError::ProjectDoesNotExist)?
         }?;
+        tracing::debug!("Parse cache after serializing: {:?}", self.adapter_provider.read().await.parse_cache().stats());
+
+        let warnings = match &serialized_project {
+            SerializedProjectData::Data(project) => compatibility::check_compatibility(project.files(), project_version.get_data_format()),
+            SerializedProjectData::Resource(project) => compatibility::check_compatibility(project.files(), project_version.get_resource_format()),
+            SerializedProjectData::Combined { data_project, resource_project } => {
+                let mut warnings = compatibility::check_compatibility(data_project.files(), project_version.get_data_format());
+                warnings.extend(compatibility::check_compatibility(resource_project.files(), project_version.get_resource_format()));
+                warnings
+            }
+        };
+
+        if strict_compatibility && !warnings.is_empty() {
+            return Err(ZipError::Incompatible(warnings).into());
+        }
+
+        Ok((serialized_project, project_type, warnings))
+    }
+
+    /// Serializes the project and writes it to the zip paths given, cleaning up after any archive which fails
+    async fn write_archives(&self, zip_data: ProjectZipData, write: ArchiveWrite) -> Result<Vec<CompatibilityWarning>> {
+        let (serialized_project, project_type, warnings) = self.serialize_for_export(zip_data.project_id, zip_data.strict_compatibility, zip_data.json_style).await?;
 
         // TODO: Look into verifying this at compile time somehow?
         match (&zip_data.path, &serialized_project) {
@@ -234,35 +532,35 @@ where
                 ZipPath::Single(path),
                 SerializedProjectData::Data(project) | SerializedProjectData::Resource(project),
             ) => {
-                let result = self.zip_provider.read().await.zip(path, project, overwrite_existing).await.map_err(ZipError::Zipping);
+                let result = self.write_archive(path, project, write).await;
 
-                if let Err(_) = result {
-                    self.zip_provider.read().await.cleanup_file(path).await.map_err(ZipError::Zipping)?;
+                if result.is_err() {
+                    self.cleanup_archive(path, write).await?;
                 }
 
                 result?;
 
-                Ok(())
+                Ok(warnings)
             }
             (
                 ZipPath::Combined { data_path, resource_path },
                 SerializedProjectData::Combined { data_project, resource_project},
             ) => {
                 let (data_result, resource_result) = tokio::join!(
-                    async { self.zip_provider.read().await.zip(data_path, data_project, overwrite_existing).await.map_err(ZipError::Zipping) },
-                    async { self.zip_provider.read().await.zip(resource_path, resource_project, overwrite_existing).await.map_err(ZipError::Zipping) }
+                    async { self.write_archive(data_path, data_project, write).await },
+                    async { self.write_archive(resource_path, resource_project, write).await }
                 );
 
                 let (data_cleanup_result, resource_cleanup_result) = tokio::join!(
                     async {
-                        if let Err(_) = data_result {
-                            self.zip_provider.read().await.cleanup_file(data_path).await.map_err(ZipError::Zipping)?;
+                        if data_result.is_err() {
+                            self.cleanup_archive(data_path, write).await?;
                         }
                         Ok::<(), ZipError>(())
                     },
                     async {
-                        if let Err(_) = resource_result {
-                            self.zip_provider.read().await.cleanup_file(resource_path).await.map_err(ZipError::Zipping)?;
+                        if resource_result.is_err() {
+                            self.cleanup_archive(resource_path, write).await?;
                         }
                         Ok::<(), ZipError>(())
                     },
@@ -275,1120 +573,3230 @@ where
                 data_cleanup_result?;
                 resource_cleanup_result?;
 
-                Ok(())
+                Ok(warnings)
             }
             _ => {
                 Err(ZipError::MismatchedPaths(project_type, zip_data.path))?
             }
         }
     }
-}
 
-type Result<T> = std::result::Result<T, ProjectServiceError>;
+    async fn write_archive(&self, path: &Path, project: &SerializedProject, write: ArchiveWrite) -> std::result::Result<(), ZipError> {
+        let zip_provider = self.zip_provider.read().await;
 
-#[derive(Debug, thiserror::Error)]
-pub enum ProjectServiceError {
-    #[error(transparent)]
-    RepoError(#[from] ProjectRepoError),
-    #[error("Cannot close with unsaved changes!")]
-    CannotCloseUnsavedChanges,
-    #[error("Project does not exist!")]
-    ProjectDoesNotExist,
-    #[error(transparent)]
-    Save(#[from] SaveError),
-    #[error(transparent)]
-    Zip(#[from] ZipError),
-}
+        match write {
+            ArchiveWrite::Create { overwrite_existing } => zip_provider.zip(path, project, overwrite_existing).await.map_err(ZipError::Zipping),
+            ArchiveWrite::Update => {
+                let update = zip_provider.update_zip(path, project).await.map_err(ZipError::Zipping)?;
+                tracing::debug!(
+                    "Updated {}: {} unchanged, {} changed, {} added, {} removed",
+                    path.display(), update.unchanged.len(), update.changed.len(), update.added.len(), update.removed.len()
+                );
+                Ok(())
+            }
+        }
+    }
 
-#[derive(Debug, thiserror::Error)]
-pub enum SaveError {
-    #[error("No changes to save!")]
-    NoChangesToSave,
-    #[error("No filepath set for project!")]
-    NoPathSet
+    /// Removes a half written archive. An update only writes once it has the whole archive, so a failed one leaves the old archive as it was
+    async fn cleanup_archive(&self, path: &Path, write: ArchiveWrite) -> std::result::Result<(), ZipError> {
+        match write {
+            ArchiveWrite::Create { .. } => self.zip_provider.read().await.cleanup_file(path).await.map_err(ZipError::Zipping),
+            ArchiveWrite::Update => Ok(()),
+        }
+    }
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum ZipError {
-    #[error("Mismatched zip export data and project type! Project type was {project_type:?}, zip export type was {zip_type}", 
-            project_type = .0, 
-            zip_type = type_name_of(.1))]
-    MismatchedPaths(ProjectType, ZipPath),
-    #[error(transparent)]
-    Zipping(zip_service::ZipError),
-    #[error(transparent)]
-    Deserialization(AdapterRepoError),
-    #[error(transparent)]
-    Serialization(AdapterRepoError),
-}
+#[async_trait::async_trait]
+impl<ProjectProvider, ZipProvider, AdapterProvider> ProjectServiceProvider for ProjectService<ProjectProvider, ZipProvider, AdapterProvider>
+where
+    ProjectProvider: project_repo::ProjectProvider + Send + Sync + 'static,
+    ZipProvider: zip_service::ZipProvider<SerializedProject> + Send + Sync + 'static,
+    AdapterProvider: adapter_repo::AdapterProvider + Send + Sync + 'static,
+{
+    async fn create_project(
+        &self,
+        settings: ProjectSettings,
+        overwrite_existing: bool,
+    ) -> Result<ProjectID> {
+        let sanitized_settings = Self::sanitize_project_settings(settings)?;
+        Self::validate_project_version(&sanitized_settings)?;
 
-#[derive(Debug)]
-pub enum ZipPath {
-    Single(PathBuf),
-    Combined{
-        data_path: PathBuf,
-        resource_path: PathBuf
+        let project = Project::from_settings(sanitized_settings);
+
+        let project_id = self.project_provider.read().await.add_project(project, overwrite_existing)?;
+        Ok(project_id)
     }
-}
 
-pub struct ProjectZipData {
-    pub project_id: ProjectID,
-    pub path: ZipPath,
-}
+    async fn create_from_template(
+        &self,
+        template_id: &str,
+        settings: ProjectSettings,
+        overwrite_existing: bool,
+    ) -> Result<ProjectID> {
+        let template = ProjectTemplate::find(template_id)?;
 
-fn type_name_of<T>(_: &T) -> &'static str {
-    std::any::type_name::<T>()
-}
+        let sanitized_settings = Self::sanitize_project_settings(settings)?;
+        Self::validate_project_version(&sanitized_settings)?;
 
-//------ Tests ------//
+        let files = template.scaffold(&sanitized_settings)?;
+        let project = Project::from_settings(sanitized_settings).with_files(files);
 
-#[cfg(test)]
-mod test {
-    use std::convert::Infallible;
-    use std::future::Future;
-    use std::io;
-    use std::path::{Path, PathBuf};
-    use std::pin::Pin;
-    use std::sync::Arc;
-    use anyhow::anyhow;
-    use once_cell::sync::Lazy;
-    use sea_orm::Iden;
-    use tokio::sync::RwLock;
-    use crate::data::adapters::{Adapter, AdapterError, AdapterInput};
-    use crate::data::adapters::project::{ProjectDeserializeError, SerializedProjectData};
-    use crate::data::domain::pack_info::PackDescription;
-    use crate::data::domain::project::{Project, ProjectDescription, ProjectID, ProjectSettings, ProjectType, ProjectVersion};
-    use crate::data::domain::versions;
-    use crate::data::serialization::pack_info::{PackData, PackInfo};
-    use crate::data::serialization::project::{Project as SerializedProject, SerializedProjectType};
-    use crate::repositories::adapter_repo::{AdapterProvider, AdapterProviderContext, AdapterRepoError};
-    use crate::repositories::project_repo;
-    use crate::repositories::project_repo::{ProjectCloseError, ProjectCreationError, ProjectOpenError, ProjectProvider, ProjectRepoError};
-    use crate::services::filesystem_service::FilesystemProviderError;
-    use crate::services::project_service::{DefaultAdapterProvider, ProjectService, ProjectServiceError, ProjectServiceProvider};
-    use crate::services::zip_service::{self, ZipProvider};
+        let project_id = self.project_provider.read().await.add_project(project, overwrite_existing)?;
+        Ok(project_id)
+    }
 
-    #[derive(Debug, Default)]
-    struct ProjectProviderCallTracker {
-        add_project_calls: usize,
-        open_project_calls: usize,
-        close_project_calls: usize,
-        save_project_calls: usize,
+    async fn open_project(&self, path: &Path) -> Result<ProjectID> {
+        self.open(path, false).await
     }
 
-    #[derive(Debug, Default, Copy, Clone)]
-    struct MockProjectProviderSettings {
-        fail_calls: bool,
+    async fn open_project_read_only(&self, path: &Path) -> Result<ProjectID> {
+        self.open(path, true).await
     }
 
-    #[derive(Default)]
-    struct MockProjectProvider {
-        project: std::sync::RwLock<Option<Project>>,
-        is_project_open: std::sync::RwLock<bool>,
-
-        call_tracker: std::sync::RwLock<ProjectProviderCallTracker>,
-        settings: MockProjectProviderSettings,
-    }
-
-    impl MockProjectProvider {
-        fn with_project(project: Project) -> Self {
-            Self {
-                project: std::sync::RwLock::new(Some(project)),
-                ..Self::default()
+    async fn close_project(&self, project_id: ProjectID) -> Result<()> {
+        let project_provider = self.project_provider.read().await;
+        
+        let read_only = project_provider.with_project(project_id, |project| {
+            if *project.has_unsaved_changes() {
+                return Err(ProjectServiceError::CannotCloseUnsavedChanges);
             }
-        }
+            Ok(*project.read_only())
+        }).ok_or(ProjectServiceError::ProjectDoesNotExist)??;
 
-        fn with_open_project(project: Project) -> Self {
-            Self {
-                project: std::sync::RwLock::new(Some(project)),
-                is_project_open: std::sync::RwLock::new(true),
-                ..Self::default()
-            }
-        }
-        
-        fn with_settings(settings: MockProjectProviderSettings) -> Self {
-            Self {
-                settings,
-                ..Self::default()
+        // Read-only projects are never written to, not even for the editor's own state
+        if !read_only {
+            if let Err(error) = Self::write_settings_file(&*project_provider, project_id).await {
+                tracing::warn!("Could not store editor state for project {} - {}", project_id, error);
             }
         }
+
+        project_provider.close_project(project_id)?;
+        self.indexes.remove(&project_id);
+        Ok(())
     }
 
-    #[async_trait::async_trait]
-    impl ProjectProvider for MockProjectProvider {
-        fn add_project(&self, project: Project, overwrite_existing: bool) -> project_repo::Result<ProjectID> {
-            self.call_tracker.write().unwrap().add_project_calls += 1;
+    async fn has_unsaved_changes(&self, project_id: ProjectID) -> Result<bool> {
+        let project_provider = self.project_provider.read().await;
 
-            if let Some(existing_project) = self.project.read().unwrap().as_ref() {
-                if !overwrite_existing && existing_project.path() == project.path() {
-                    return Err(ProjectRepoError::Create(ProjectCreationError::FileAlreadyExists))
-                }
-            }
-            
-            if self.settings.fail_calls {
-                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!"))));
-            }
+        project_provider.with_project(project_id, |project| *project.has_unsaved_changes())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
+    }
 
-            let id = *project.id();
+    async fn discard_changes(&self, project_id: ProjectID) -> Result<()> {
+        let project_provider = self.project_provider.read().await;
 
-            let mut stored_project = self.project.write().unwrap();
-            *stored_project = Some(project);
-            Ok(id)
-        }
+        project_provider.with_project_mut(project_id, |project| project.clear_unsaved_changes())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
+    }
 
-        fn with_project<F, R>(&self, _project_id: ProjectID, callback: F) -> Option<R>
-        where
-            F: FnOnce(&Project) -> R
-        {
-            if let Some(project) = self.project.read().unwrap().as_ref() {
-                Some(callback(project))
-            }
-            else {
-                None
-            }
+    async fn settings_file(&self, project_id: ProjectID) -> Result<ProjectSettingsFile> {
+        let project_provider = self.project_provider.read().await;
+
+        project_provider.with_project(project_id, |project| project.settings_file().clone())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
+    }
+
+    async fn set_settings_file(&self, project_id: ProjectID, settings_file: ProjectSettingsFile) -> Result<()> {
+        let project_provider = self.project_provider.read().await;
+
+        project_provider.with_project_mut(project_id, |project| project.set_settings_file(settings_file))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
+    }
+
+    async fn save_project(&self, project_id: ProjectID) -> Result<PathBuf> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_can_save(&*project_provider, project_id)?;
+
+        let path = metrics_service::timed(&*self.metrics, Operation::Save, project_provider.save_project(project_id)).await?;
+        Self::finish_save(&*project_provider, project_id).await?;
+
+        Ok(path)
+    }
+
+    async fn save_project_with_progress(&self, project_id: ProjectID, on_progress: SaveProgressCallback, cancellation: SaveCancellation) -> Result<PathBuf> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_can_save(&*project_provider, project_id)?;
+
+        let path = metrics_service::timed(
+            &*self.metrics,
+            Operation::Save,
+            project_provider.save_project_with_progress(project_id, on_progress, cancellation),
+        ).await?;
+        Self::finish_save(&*project_provider, project_id).await?;
+
+        Ok(path)
+    }
+
+    async fn set_workspace_dir(&self, workspace_dir: PathBuf) {
+        self.project_provider.write().await.set_workspace_dir(workspace_dir);
+    }
+
+    async fn autosave_project(&self, project_id: ProjectID) -> Result<PathBuf> {
+        let path = self.project_provider.read().await.write_autosave(project_id).await?;
+        Ok(path)
+    }
+
+    async fn snapshot_project(&self, project_id: ProjectID) -> Result<PathBuf> {
+        let path = self.project_provider.read().await.write_snapshot(project_id).await?;
+        Ok(path)
+    }
+
+    async fn save_project_as(&self, project_id: ProjectID, path: &Path, overwrite_existing: bool) -> Result<PathBuf> {
+        let path = Self::sanitize_path(path)?;
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
+
+        let (previous_path, had_unsaved_changes) = project_provider.with_project(project_id, |project| {
+            (project.path().clone(), *project.has_unsaved_changes())
+        }).ok_or(ProjectServiceError::ProjectDoesNotExist)?;
+
+        // Saving over the project's own location is not a collision
+        if previous_path.as_ref() != Some(&path) && !overwrite_existing && project_provider.path_exists(&path).await? {
+            return Err(SaveError::FileAlreadyExists(path).into());
         }
 
-        fn with_project_mut<F, R>(&self, _project_id: ProjectID, callback: F) -> Option<R>
-        where
-            F: FnOnce(&mut Project) -> R
-        {
-            if let Some(project) = self.project.read().unwrap().as_ref() {
-                let mut project = project.clone();
-                let ret = Some(callback(&mut project));
-                self.project.write().unwrap().replace(project);
+        project_provider.with_project_mut(project_id, |project| project.set_path(Some(path.clone())));
 
-                ret
+        match metrics_service::timed(&*self.metrics, Operation::Save, project_provider.save_project(project_id)).await {
+            Ok(saved_path) => {
+                project_provider.with_project_mut(project_id, |project| project.clear_unsaved_changes());
+                Ok(saved_path)
             }
-            else {
-                None
+            Err(error) => {
+                // Restore the previous state so a failed save-as doesn't silently move the project
+                project_provider.with_project_mut(project_id, |project| {
+                    project.set_path(previous_path);
+                    if !had_unsaved_changes {
+                        project.clear_unsaved_changes();
+                    }
+                });
+                Err(error.into())
             }
         }
+    }
 
-        async fn with_project_async<'a, F, R>(&self, _project_id: ProjectID, callback: F) -> Option<R>
-        where
-            F: FnOnce(Arc<RwLock<Project>>) -> Pin<Box<dyn Future<Output = R> + Send + 'a>> + Send + Sync,
-            R: Send + Sync,
-        {
-            let project = {
-                if let Some(project) = self.project.read().unwrap().as_ref() {
-                    project.clone()
+    async fn import_zip(&self, path: ZipPath) -> Result<(ProjectID, ImportReport)> {
+        metrics_service::timed(&*self.metrics, Operation::Import, async {
+            let serialized_project = self.extract_zip_path(path).await?;
+            self.finish_import(serialized_project).await
+        }).await
+    }
+
+    async fn import_zip_with_progress(&self, path: ZipPath, on_progress: ImportProgressCallback) -> Result<(ProjectID, ImportReport)> {
+        // Shared with indexing, which reports from its own thread
+        let on_progress = Arc::new(std::sync::Mutex::new(on_progress));
+        let report = |progress| (on_progress.lock().unwrap())(progress);
+
+        // Indexing is timed on its own
+        let (project_id, import_report) = metrics_service::timed(&*self.metrics, Operation::Import, async {
+            report(ImportProgress::Extracting);
+            let serialized_project = self.extract_zip_path(path).await?;
+
+            report(ImportProgress::Parsing);
+            self.finish_import(serialized_project).await
+        }).await?;
+
+        let index_progress = on_progress.clone();
+        self.index_project(project_id, Box::new(move |progress| (index_progress.lock().unwrap())(ImportProgress::Indexing(progress)))).await?;
+
+        report(ImportProgress::Done);
+        Ok((project_id, import_report))
+    }
+
+    async fn import_zip_as(&self, path: &Path, pack: PackHalf) -> Result<(ProjectID, ImportReport)> {
+        metrics_service::timed(&*self.metrics, Operation::Import, async {
+            let serialized_project = {
+                let zip_provider = self.zip_provider.read().await;
+                zip_provider.extract(path).await.map_err(ZipError::Zipping)?
+            };
+
+            let serialized_project = if serialized_project.is_mixed() {
+                let (data_project, resource_project) = Self::split_mixed(serialized_project).await;
+                match pack {
+                    PackHalf::Data => SerializedProjectData::Data(data_project),
+                    PackHalf::Resource => SerializedProjectData::Resource(resource_project),
                 }
-                else {
-                    return None;
+            } else {
+                match (pack, serialized_project.project_type()) {
+                    (PackHalf::Data, SerializedProjectType::Data) => SerializedProjectData::Data(serialized_project),
+                    (PackHalf::Resource, SerializedProjectType::Resource) => SerializedProjectData::Resource(serialized_project),
+                    (pack, _) => return Err(ZipError::MissingPack(pack).into()),
                 }
             };
 
-            Some(callback(Arc::new(RwLock::new(project))).await)
-        }
+            self.finish_import(serialized_project).await
+        }).await
+    }
 
+    async fn rename_namespace(&self, project_id: ProjectID, from: &str, to: &str) -> Result<usize> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
 
-        async fn open_project(&self, _path: &Path) -> project_repo::Result<ProjectID> {
-            self.call_tracker.write().unwrap().open_project_calls += 1;
+        let moved = project_provider.with_project_mut(project_id, |project| project.rename_namespace(from, to))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)??;
 
-            if self.settings.fail_calls {
-                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!"))));
-            }
+        Ok(moved)
+    }
 
-            if *self.is_project_open.read().unwrap() {
-                return Err(ProjectRepoError::Open(ProjectOpenError::AlreadyOpen));
-            }
+    async fn rewrite_references(&self, project_id: ProjectID, rewriter: &ReferenceRewriter) -> Result<ReferenceRewrite> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
+
+        let references: BTreeSet<String> = project_provider
+            .with_project(project_id, |project| project.iter_files()
+                .flat_map(|file| project::reference_spans(file.contents).into_iter().map(|span| file.contents[span].to_string()))
+                .collect())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)?;
+
+        // Each distinct reference is only converted once, however many files it's written in
+        let adapter_provider = self.adapter_provider.read().await;
+        let mut replacements = HashMap::new();
+        for reference in references {
+            let serialized = AdapterInput::owned(SerializedResourceLocation::new(&reference));
+            let context = AdapterProviderContext::new(self.adapter_provider.read().await);
+
+            let location = match adapter_provider.deserialize::<_, ResourceLocation>(serialized, context).await {
+                Ok(location) => location,
+                // Text which isn't a valid location, such as a URL, isn't a reference
+                Err(AdapterRepoError::DeserializationError(_)) => continue,
+                Err(error) => return Err(ProjectServiceError::ResourceLocation(error)),
+            };
+            let Some(rewritten) = rewriter(&location) else {
+                continue;
+            };
 
-            match self.project.read().unwrap().as_ref() {
-                Some(project) => {
-                    let mut is_project_open = self.is_project_open.write().unwrap();
-                    *is_project_open = true;
-                    Ok(*project.id())
-                },
-                None => Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!")))),
-            }
+            let context = AdapterProviderContext::new(self.adapter_provider.read().await);
+            let rewritten: SerializedResourceLocation = adapter_provider.serialize(AdapterInput::owned(rewritten), context).await
+                .map_err(ProjectServiceError::ResourceLocation)?;
+            replacements.insert(reference, rewritten.to_string());
         }
 
-        fn close_project(&self, _project_id: ProjectID) -> project_repo::Result<()> {
-            self.call_tracker.write().unwrap().close_project_calls += 1;
+        project_provider.with_project_mut(project_id, |project| project.rewrite_references(|reference| replacements.get(reference).cloned()))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
+    }
 
-            if self.settings.fail_calls {
-                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!"))));
-            }
+    async fn move_file(&self, project_id: ProjectID, from: &Path, to: &Path) -> Result<()> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
 
-            if !*self.is_project_open.read().unwrap() {
-                return Err(ProjectRepoError::Close(ProjectCloseError::FileNotOpen));
-            }
+        project_provider.with_project_mut(project_id, |project| project.move_file(from, to))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)??;
 
-            let mut is_project_open = self.is_project_open.write().unwrap();
-            *is_project_open = false;
-            Ok(())
-        }
+        Ok(())
+    }
 
-        async fn save_project(&self, _project_id: ProjectID) -> project_repo::Result<PathBuf> {
-            self.call_tracker.write().unwrap().save_project_calls += 1;
+    async fn file_saved(&self, project_id: ProjectID, path: &Path, contents: String, bom: bool) -> Result<()> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
 
-            if self.settings.fail_calls {
-                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!"))));
-            }
+        project_provider.with_project_mut(project_id, |project| project.set_saved_file(path.to_path_buf(), contents, bom))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)?;
+        self.file_changed(path).await;
 
-            if !*self.is_project_open.read().unwrap() {
-                return Err(ProjectRepoError::Close(ProjectCloseError::FileNotOpen));
-            }
+        Ok(())
+    }
 
-            self.project.read().unwrap().clone()
-                .map(|project| project.path().clone())
-                .flatten()
-                .ok_or(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::NotFound, "Project not found"))))
-        }
+    async fn file_changed(&self, path: &Path) {
+        self.adapter_provider.read().await.parse_cache().invalidate(path);
     }
 
-    #[derive(Debug, Default)]
-    struct ZipProviderCallTracker {
-        extract_calls: usize,
-        zip_calls: usize,
-        cleanup_calls: usize,
+    async fn fix_resource_casing(&self, project_id: ProjectID) -> Result<Vec<CasingFix>> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
+
+        let fixes = project_provider.with_project_mut(project_id, |project| project.fix_casing())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)??;
+
+        Ok(fixes)
     }
-    
-    #[derive(Debug, Default)]
-    struct MockZipProviderSettings {
-        fail_extract: bool,
-        fail_zip: bool,
-        fail_cleanup: bool,
-        project_already_exists: bool,
+
+    async fn fix_resource_extensions(&self, project_id: ProjectID) -> Result<Vec<ExtensionFix>> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
+
+        let fixes = project_provider.with_project_mut(project_id, |project| project.fix_extensions())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)??;
+
+        Ok(fixes)
     }
-    
-    #[derive(Debug, Default)]
-    struct MockZipProvider {
-        serialized_project: Option<SerializedProject>,
-        settings: std::sync::RwLock<MockZipProviderSettings>,
-        call_tracker: std::sync::RwLock<ZipProviderCallTracker>,
+
+    async fn project_stats(&self, project_id: ProjectID) -> Result<ProjectStats> {
+        let project_provider = self.project_provider.read().await;
+        let adapter_versions = self.adapter_provider.read().await.min_versions();
+
+        project_provider.with_project(project_id, |project| project.stats(&adapter_versions))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
     }
 
-    impl MockZipProvider {
-        fn with_project(serialized_project: SerializedProject) -> Self {
-            Self {
-                serialized_project: Some(serialized_project),
-                ..Self::default()
-            }
-        }
-        
-        fn settings(self, settings: MockZipProviderSettings) -> Self {
-            {
-                *self.settings.write().unwrap() = settings;
-            }
-            self
-        }
+    async fn set_description(&self, project_id: ProjectID, pack: PackHalf, description: PackDescription) -> Result<()> {
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
+
+        project_provider.with_project_mut(project_id, |project| project.set_description(pack, description))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)??;
+
+        Ok(())
     }
 
-    #[async_trait::async_trait]
-    impl ZipProvider<SerializedProject> for MockZipProvider {
-        async fn extract(&self, path: &Path) -> zip_service::Result<SerializedProject> {
-            self.call_tracker.write().unwrap().extract_calls += 1;
-            
-            if self.settings.read().unwrap().fail_extract {
-                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!"))))
-            }
-            
-            self.serialized_project.clone()
-                .ok_or(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::NotFound, "Project not found"))))
-                .map_err(Into::into)
-        }
+    async fn set_pack_icon(&self, project_id: ProjectID, data: Vec<u8>) -> Result<Vec<PackIconWarning>> {
+        let icon = PackIcon::new(data)?;
+        let warnings = icon.warnings();
 
-        async fn zip(&self, path: &Path, data: &SerializedProject, overwrite_existing: bool) -> zip_service::Result<()> {
-            self.call_tracker.write().unwrap().zip_calls += 1;
+        let project_provider = self.project_provider.read().await;
+        Self::check_writable(&*project_provider, project_id)?;
 
-            if self.settings.read().unwrap().fail_zip {
-                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!"))))
-            }
-            
-            if self.settings.read().unwrap().project_already_exists && !overwrite_existing {
-                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::AlreadyExists, "Project already exists"))))
-            }
-            
-            Ok(())
-        }
+        project_provider.with_project_mut(project_id, |project| project.set_pack_icon(icon))
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)?;
 
-        async fn cleanup_file(&self, path: &Path) -> zip_service::Result<()> {
-            self.call_tracker.write().unwrap().cleanup_calls += 1;
+        Ok(warnings)
+    }
 
-            if self.settings.read().unwrap().fail_cleanup {
-                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::Other, "Mock error!"))))
-            }
+    async fn pack_icon(&self, project_id: ProjectID) -> Result<Option<PackIcon>> {
+        self.project_provider.read().await
+            .with_project(project_id, |project| project.pack_icon().clone())
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
+    }
 
-            Ok(())
-        }
+    async fn zip_pack_icon(&self, path: &Path) -> Result<Option<PackIcon>> {
+        let data = match self.zip_provider.read().await.read_entry(path, PACK_ICON_FILE).await {
+            Ok(data) => data,
+            Err(zip_service::ZipError::EntryNotFound { .. }) => return Ok(None),
+            Err(error) => return Err(ZipError::Zipping(error).into()),
+        };
+
+        Ok(Some(PackIcon::new(data)?))
     }
 
-    static PROJECT_ADAPTER_CONFIG: Lazy<std::sync::RwLock<ProjectAdapterConfig>> = Lazy::new(|| {
-        std::sync::RwLock::new(ProjectAdapterConfig::default())
-    });
+    fn detect_install_context(&self, path: &Path) -> Option<InstallContext> {
+        InstallContext::detect(path)
+    }
 
-    #[derive(Debug, Default)]
-    struct ProjectAdapterConfig {
-        serialized_project: Option<SerializedProject>,
-        project: Option<Project>,
-        fail_conversion: std::sync::RwLock<bool>,
+    async fn index_project(&self, project_id: ProjectID, on_progress: IndexProgressCallback) -> Result<Arc<ProjectIndex>> {
+        // Indexing works on a snapshot, so the project isn't locked against edits while it runs
+        let project = self.project_provider.read().await
+            .with_project_async(project_id, |project: Arc<RwLock<Project>>| Box::pin(async move { project.read().await.clone() }))
+            .await
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)?;
+
+        let indexing = tokio::task::spawn_blocking(move || ProjectIndex::build(&project, on_progress));
+        let index = metrics_service::timed(&*self.metrics, Operation::Index, indexing)
+            .await
+            .unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()));
+
+        let index = Arc::new(index);
+        self.indexes.insert(project_id, index.clone());
+        Ok(index)
     }
-    
-    impl ProjectAdapterConfig {
-        fn new(serialized_project: SerializedProject, project: Project) -> Self {
+
+    fn project_index(&self, project_id: ProjectID) -> Option<Arc<ProjectIndex>> {
+        self.indexes.get(&project_id).map(|index| index.clone())
+    }
+
+    fn metrics(&self) -> &(dyn MetricsProvider + Send + Sync) {
+        &*self.metrics
+    }
+
+    async fn find_in_project(&self, project_id: ProjectID, query: SearchQuery, mut on_match: SearchMatchCallback, cancellation: SearchCancellation) -> Result<SearchSummary> {
+        // Searches a snapshot, so the project isn't locked against edits while it runs
+        let files: Vec<(PathBuf, String)> = self.project_provider.read().await
+            .with_project_async(project_id, |project: Arc<RwLock<Project>>| Box::pin(async move {
+                project.read().await.iter_files()
+                    .map(|file| (file.path.to_path_buf(), file.contents.to_string()))
+                    .collect()
+            }))
+            .await
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)?;
+
+        let mut summary = SearchSummary { total_files: files.len(), ..SearchSummary::default() };
+        let query = Arc::new(query);
+        let mut files = files.into_iter();
+        let mut searching = tokio::task::JoinSet::new();
+
+        loop {
+            while searching.len() < MAX_CONCURRENT_SEARCHES && !cancellation.is_cancelled() {
+                let Some((path, contents)) = files.next() else { break };
+                let query = query.clone();
+                searching.spawn_blocking(move || query.find_in_file(&path, &contents));
+            }
+
+            let Some(result) = searching.join_next().await else { break };
+            let matches = result.unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()));
+
+            if cancellation.is_cancelled() {
+                searching.abort_all();
+                break;
+            }
+
+            summary.files_searched += 1;
+            summary.matches += matches.len();
+            if !matches.is_empty() {
+                on_match(matches);
+            }
+        }
+
+        summary.cancelled = cancellation.is_cancelled();
+        Ok(summary)
+    }
+
+    async fn lint_project(&self, project_id: ProjectID, lint_provider: &(dyn LintProvider + Send + Sync)) -> Result<Vec<LintDiagnostic>> {
+        self.project_provider.read().await
+            .with_project_async(project_id, |project: Arc<RwLock<Project>>| Box::pin(async move {
+                // The stored index can be behind unsaved edits, so the project as it is now is indexed again
+                let project = project.read().await;
+                lint_provider.lint(&project, &ProjectIndex::build(&project, |_| {}))
+            }))
+            .await
+            .ok_or(ProjectServiceError::ProjectDoesNotExist)
+    }
+
+    async fn export_zip(
+        &self,
+        zip_data: ProjectZipData,
+        overwrite_existing: bool,
+    ) -> Result<Vec<CompatibilityWarning>> {
+        metrics_service::timed(&*self.metrics, Operation::Export, self.write_archives(zip_data, ArchiveWrite::Create { overwrite_existing })).await
+    }
+
+    async fn update_export(&self, zip_data: ProjectZipData) -> Result<Vec<CompatibilityWarning>> {
+        metrics_service::timed(&*self.metrics, Operation::Export, self.write_archives(zip_data, ArchiveWrite::Update)).await
+    }
+
+    async fn export_zips(
+        &self,
+        exports: Vec<ProjectZipData>,
+        overwrite_existing: bool,
+    ) -> Vec<(ProjectID, Result<Vec<CompatibilityWarning>>)> {
+        let mut results = Vec::with_capacity(exports.len());
+        for zip_data in exports {
+            let project_id = zip_data.project_id;
+            results.push((project_id, self.export_zip(zip_data, overwrite_existing).await));
+        }
+        results
+    }
+
+    async fn plan_export(&self, zip_data: ProjectZipData) -> Result<ExportPlan> {
+        let (serialized_project, project_type, warnings) = self.serialize_for_export(zip_data.project_id, zip_data.strict_compatibility, zip_data.json_style).await?;
+
+        let archives = match (&zip_data.path, &serialized_project) {
+            (
+                ZipPath::Single(path),
+                SerializedProjectData::Data(project) | SerializedProjectData::Resource(project),
+            ) => vec![PlannedArchive::new(path, project)],
+            (
+                ZipPath::Combined { data_path, resource_path },
+                SerializedProjectData::Combined { data_project, resource_project },
+            ) => vec![
+                PlannedArchive::new(data_path, data_project),
+                PlannedArchive::new(resource_path, resource_project),
+            ],
+            _ => Err(ZipError::MismatchedPaths(project_type, zip_data.path))?,
+        };
+
+        Ok(ExportPlan { archives, warnings })
+    }
+
+    async fn diff_against_export(&self, project_id: ProjectID, zip_path: ZipPath) -> Result<ExportDiff> {
+        let (current, project_type, _) = self.serialize_for_export(project_id, false, JsonStyle::default()).await?;
+
+        let paths_match = matches!(
+            (&zip_path, &current),
+            (ZipPath::Single(_), SerializedProjectData::Data(_) | SerializedProjectData::Resource(_))
+                | (ZipPath::Combined { .. }, SerializedProjectData::Combined { .. })
+        );
+        if !paths_match {
+            return Err(ZipError::MismatchedPaths(project_type, zip_path).into());
+        }
+
+        let exported = self.extract_zip_path(zip_path).await?;
+
+        let diff = match (&exported, &current) {
+            (
+                SerializedProjectData::Combined { data_project: exported_data, resource_project: exported_resource },
+                SerializedProjectData::Combined { data_project: current_data, resource_project: current_resource },
+            ) => ExportDiff::between(exported_data, current_data).await.merge(ExportDiff::between(exported_resource, current_resource).await),
+            (SerializedProjectData::Combined { data_project: exported, .. }, SerializedProjectData::Data(current))
+                | (SerializedProjectData::Combined { resource_project: exported, .. }, SerializedProjectData::Resource(current))
+                | (SerializedProjectData::Data(exported) | SerializedProjectData::Resource(exported), SerializedProjectData::Data(current) | SerializedProjectData::Resource(current))
+                => ExportDiff::between(exported, current).await,
+            (_, SerializedProjectData::Combined { .. }) => unreachable!("Combined projects are only compared with a pair of zips"),
+        };
+
+        Ok(diff)
+    }
+}
+
+type Result<T> = std::result::Result<T, ProjectServiceError>;
+
+/// Files searched at once by [`ProjectServiceProvider::find_in_project`]
+const MAX_CONCURRENT_SEARCHES: usize = 4;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectServiceError {
+    #[error(transparent)]
+    RepoError(#[from] ProjectRepoError),
+    #[error("Cannot close with unsaved changes!")]
+    CannotCloseUnsavedChanges,
+    #[error("Project does not exist!")]
+    ProjectDoesNotExist,
+    #[error("Project was opened read-only, so it can't be changed or saved!")]
+    ReadOnly,
+    #[error("Invalid project version! {0}")]
+    InvalidVersion(String),
+    #[error(transparent)]
+    Save(#[from] SaveError),
+    #[error(transparent)]
+    Zip(#[from] ZipError),
+    #[error(transparent)]
+    RenameNamespace(#[from] NamespaceRenameError),
+    #[error(transparent)]
+    MoveFile(#[from] MoveFileError),
+    #[error(transparent)]
+    CasingFix(#[from] CasingFixError),
+    #[error(transparent)]
+    ExtensionFix(#[from] ExtensionFixError),
+    #[error(transparent)]
+    Description(#[from] DescriptionError),
+    #[error(transparent)]
+    PackIcon(#[from] PackIconError),
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+    #[error("Couldn't convert a resource location! {0}")]
+    ResourceLocation(AdapterRepoError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SaveError {
+    #[error("No changes to save!")]
+    NoChangesToSave,
+    #[error("No filepath set for project!")]
+    NoPathSet,
+    #[error("A file already exists at {0:?}!")]
+    FileAlreadyExists(PathBuf),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ZipError {
+    #[error("Mismatched zip export data and project type! Project type was {project_type:?}, zip export type was {zip_type}", 
+            project_type = .0, 
+            zip_type = type_name_of(.1))]
+    MismatchedPaths(ProjectType, ZipPath),
+    #[error("Zip does not contain a {0:?} pack!")]
+    MissingPack(PackHalf),
+    #[error(transparent)]
+    Zipping(zip_service::ZipError),
+    #[error(transparent)]
+    Deserialization(AdapterRepoError),
+    #[error(transparent)]
+    Serialization(AdapterRepoError),
+    #[error("Project is not compatible with its pack format! {count} issue(s) found", count = .0.len())]
+    Incompatible(Vec<CompatibilityWarning>),
+    #[error("The data and resource packs have no mc version in common! Data format: {data_format}, Resource format: {resource_format}{suggestion}",
+            data_format = .mismatch.data_format,
+            resource_format = .mismatch.resource_format,
+            suggestion = .suggested_resource_format.map(|format| format!(". Resource format {} would match the data pack", format)).unwrap_or_default())]
+    NoCommonVersion {
+        mismatch: FormatMismatch,
+        /// The resource format nearest the resource pack's own which supports a version the data pack does
+        suggested_resource_format: Option<u8>,
+    },
+}
+
+/// Of the resource formats for the data pack's versions, the nearest to the resource pack's own, preferring the newer of two as near
+fn suggest_resource_format(mismatch: &FormatMismatch) -> Option<u8> {
+    mismatch.data_versions.iter()
+        .filter_map(|version| versions::find_resourcepack_format_for_version(*version))
+        .map(|format| format.get_format_id())
+        .min_by_key(|format| (format.abs_diff(mismatch.resource_format), Reverse(*format)))
+}
+
+#[derive(Debug)]
+pub enum ZipPath {
+    Single(PathBuf),
+    Combined{
+        data_path: PathBuf,
+        resource_path: PathBuf
+    }
+}
+
+/// Stage an import has reached, reported by [`ProjectServiceProvider::import_zip_with_progress`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ImportProgress {
+    Extracting,
+    Parsing,
+    /// Reported after each file is indexed, as by [`ProjectServiceProvider::index_project`]
+    Indexing(IndexProgress),
+    Done,
+}
+
+pub type ImportProgressCallback = Box<dyn FnMut(ImportProgress) + Send>;
+
+/// Gives the new location for a reference passed to [`ProjectServiceProvider::rewrite_references`], or `None` to leave it as it is
+pub type ReferenceRewriter = dyn Fn(&ResourceLocation) -> Option<ResourceLocation> + Send + Sync;
+
+/// How an export writes its archives
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum ArchiveWrite {
+    Create { overwrite_existing: bool },
+    Update,
+}
+
+pub struct ProjectZipData {
+    pub project_id: ProjectID,
+    pub path: ZipPath,
+    /// Refuse to export if the project uses anything unavailable in its pack format
+    pub strict_compatibility: bool,
+    /// How JSON files are written into the zip
+    pub json_style: JsonStyle,
+}
+
+/// Archive entries which differ between an earlier export and the project as it is now,
+/// as worked out by [`ProjectServiceProvider::diff_against_export`]. Each list is sorted
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ExportDiff {
+    /// Only in the project
+    pub added: Vec<PathBuf>,
+    /// Only in the earlier export
+    pub removed: Vec<PathBuf>,
+    pub changed: Vec<PathBuf>,
+}
+
+impl ExportDiff {
+    /// Compares an exported pack with the same pack serialized from the project now. JSON is compared by value,
+    /// so the style either was written in doesn't count as a change
+    async fn between(exported: &SerializedProject, current: &SerializedProject) -> Self {
+        let mut diff = Self::default();
+
+        let exported_entries = ExportEntry::of(exported);
+        let current_entries = ExportEntry::of(current);
+
+        for (path, current_entry) in &current_entries {
+            match exported_entries.get(path) {
+                None => diff.added.push(path.to_path_buf()),
+                Some(exported_entry) if !exported_entry.same_as(current_entry, path) => diff.changed.push(path.to_path_buf()),
+                Some(_) => {},
+            }
+        }
+        diff.removed = exported_entries.keys()
+            .filter(|path| !current_entries.contains_key(*path))
+            .map(|path| path.to_path_buf())
+            .collect();
+
+        if *exported.pack_info().read().await != *current.pack_info().read().await {
+            diff.changed.push(PathBuf::from(PACK_INFO_FILE));
+            diff.changed.sort();
+        }
+
+        diff
+    }
+
+    /// Combines the differences of a combined project's two packs. Files outside of both, such as `pack.png`, are only listed once
+    fn merge(mut self, other: Self) -> Self {
+        for (paths, other_paths) in [(&mut self.added, other.added), (&mut self.removed, other.removed), (&mut self.changed, other.changed)] {
+            paths.extend(other_paths);
+            paths.sort();
+            paths.dedup();
+        }
+        self
+    }
+}
+
+/// One entry of a serialized pack, other than its `pack.mcmeta`, which is compared by its pack info instead
+enum ExportEntry<'a> {
+    Text(&'a str),
+    Structure(&'a Structure),
+    /// The icon, or any other file kept as bytes
+    Binary(&'a [u8]),
+}
+
+impl<'a> ExportEntry<'a> {
+    fn of(project: &'a SerializedProject) -> BTreeMap<&'a Path, Self> {
+        project.files().iter()
+            .filter(|(path, _)| path.as_path() != Path::new(PACK_INFO_FILE))
+            .map(|(path, contents)| (path.as_path(), ExportEntry::Text(contents)))
+            .chain(project.structures().iter().map(|(path, structure)| (path.as_path(), ExportEntry::Structure(structure))))
+            .chain(project.pack_icon().iter().map(|icon| (Path::new(PACK_ICON_FILE), ExportEntry::Binary(icon))))
+            .chain(project.binary_files().iter().map(|(path, data)| (path.as_path(), ExportEntry::Binary(data))))
+            .collect()
+    }
+
+    fn same_as(&self, other: &Self, path: &Path) -> bool {
+        match (self, other) {
+            (ExportEntry::Text(text), ExportEntry::Text(other_text)) if text == other_text => true,
+            (ExportEntry::Text(text), ExportEntry::Text(other_text)) if path.extension().is_some_and(|extension| extension == "json") => {
+                matches!(
+                    (serde_json::from_str::<serde_json::Value>(text), serde_json::from_str::<serde_json::Value>(other_text)),
+                    (Ok(value), Ok(other_value)) if value == other_value
+                )
+            }
+            (ExportEntry::Structure(structure), ExportEntry::Structure(other_structure)) => structure == other_structure,
+            (ExportEntry::Binary(data), ExportEntry::Binary(other_data)) => data == other_data,
+            _ => false,
+        }
+    }
+}
+
+/// What an export would write, as worked out by [`ProjectServiceProvider::plan_export`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExportPlan {
+    pub archives: Vec<PlannedArchive>,
+    pub warnings: Vec<CompatibilityWarning>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PlannedArchive {
+    pub path: PathBuf,
+    /// Paths of the entries inside the archive, sorted
+    pub entries: Vec<PathBuf>,
+}
+
+impl PlannedArchive {
+    fn new(path: &Path, project: &SerializedProject) -> Self {
+        let mut entries: Vec<_> = project.files().keys()
+            .chain(project.structures().keys())
+            .chain(project.binary_files().keys())
+            .cloned()
+            .chain(project.pack_icon().as_ref().map(|_| PathBuf::from(PACK_ICON_FILE)))
+            .chain(std::iter::once(PathBuf::from(PACK_INFO_FILE)))
+            .collect();
+        entries.sort();
+        entries.dedup();
+
+        Self { path: path.to_path_buf(), entries }
+    }
+}
+
+fn type_name_of<T>(_: &T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+//------ Tests ------//
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+    use std::future::Future;
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use anyhow::anyhow;
+    use once_cell::sync::Lazy;
+    use sea_orm::Iden;
+    use tokio::sync::RwLock;
+    use crate::data::adapters::{Adapter, AdapterError, AdapterInput};
+    use crate::data::adapters::project::{ProjectDeserializeError, SerializedProjectData};
+    use crate::data::domain::pack_info::PackDescription;
+    use crate::data::domain::project::{Project, ProjectDescription, ProjectID, ProjectSettings, ProjectType, ProjectVersion};
+    use crate::data::domain::project_settings_file::ProjectSettingsFile;
+    use crate::data::domain::versions;
+    use crate::data::serialization::pack_info::{PackData, PackInfo};
+    use crate::data::serialization::json::{JsonLayout, JsonStyle, KeyOrder};
+    use crate::data::serialization::project::{Project as SerializedProject, SerializedProjectType};
+    use crate::repositories::adapter_repo::{AdapterProvider, AdapterProviderContext, AdapterRepoError};
+    use crate::repositories::project_repo;
+    use crate::repositories::project_repo::{ProjectCloseError, ProjectCreationError, ProjectOpenError, ProjectProvider, ProjectRepoError, SaveCancellation, SaveProgress, SaveProgressCallback};
+    use crate::services::filesystem_service::FilesystemProviderError;
+    use crate::services::project_service::{DefaultAdapterProvider, ProjectService, ProjectServiceError, ProjectServiceProvider, SaveError};
+    use crate::services::zip_service::{self, ZipProvider, ZipUpdate};
+    use crate::services::metrics_service::{MetricsProvider, MetricsService, Operation};
+
+    #[derive(Debug, Default)]
+    struct ProjectProviderCallTracker {
+        add_project_calls: usize,
+        open_project_calls: usize,
+        close_project_calls: usize,
+        save_project_calls: usize,
+        write_settings_file_calls: usize,
+    }
+
+    #[derive(Debug, Default, Copy, Clone)]
+    struct MockProjectProviderSettings {
+        fail_calls: bool,
+    }
+
+    #[derive(Default)]
+    struct MockProjectProvider {
+        project: std::sync::RwLock<Option<Project>>,
+        is_project_open: std::sync::RwLock<bool>,
+        existing_paths: Vec<PathBuf>,
+        stored_settings_file: std::sync::RwLock<Option<ProjectSettingsFile>>,
+        workspace_dir: PathBuf,
+
+        call_tracker: std::sync::RwLock<ProjectProviderCallTracker>,
+        settings: MockProjectProviderSettings,
+    }
+
+    impl MockProjectProvider {
+        fn with_project(project: Project) -> Self {
             Self {
-                serialized_project: Some(serialized_project),
-                project: Some(project),
-                fail_conversion: std::sync::RwLock::new(false),
+                project: std::sync::RwLock::new(Some(project)),
+                ..Self::default()
+            }
+        }
+
+        fn with_open_project(project: Project) -> Self {
+            Self {
+                project: std::sync::RwLock::new(Some(project)),
+                is_project_open: std::sync::RwLock::new(true),
+                ..Self::default()
             }
         }
         
-        fn fail_conversion(self) -> Self {
-            {
-                *self.fail_conversion.write().unwrap() = true;
+        fn with_settings(settings: MockProjectProviderSettings) -> Self {
+            Self {
+                settings,
+                ..Self::default()
             }
-            self
         }
     }
 
-    #[derive(Debug)]
-    struct MockProjectAdapter;
+    #[async_trait::async_trait]
+    impl ProjectProvider for MockProjectProvider {
+        fn add_project(&self, project: Project, overwrite_existing: bool) -> project_repo::Result<ProjectID> {
+            self.call_tracker.write().unwrap().add_project_calls += 1;
+
+            if let Some(existing_project) = self.project.read().unwrap().as_ref() {
+                if !overwrite_existing && existing_project.path() == project.path() {
+                    return Err(ProjectRepoError::Create(ProjectCreationError::FileAlreadyExists))
+                }
+            }
+            
+            if self.settings.fail_calls {
+                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::other("Mock error!"))));
+            }
+
+            let id = *project.id();
+
+            let mut stored_project = self.project.write().unwrap();
+            *stored_project = Some(project);
+            Ok(id)
+        }
+
+        fn with_project<F, R>(&self, _project_id: ProjectID, callback: F) -> Option<R>
+        where
+            F: FnOnce(&Project) -> R
+        {
+            if let Some(project) = self.project.read().unwrap().as_ref() {
+                Some(callback(project))
+            }
+            else {
+                None
+            }
+        }
+
+        fn with_project_mut<F, R>(&self, _project_id: ProjectID, callback: F) -> Option<R>
+        where
+            F: FnOnce(&mut Project) -> R
+        {
+            let mut project = self.project.read().unwrap().clone()?;
+            let ret = Some(callback(&mut project));
+            self.project.write().unwrap().replace(project);
+
+            ret
+        }
+
+        async fn with_project_async<'a, F, R>(&self, _project_id: ProjectID, callback: F) -> Option<R>
+        where
+            F: FnOnce(Arc<RwLock<Project>>) -> Pin<Box<dyn Future<Output = R> + Send + 'a>> + Send + Sync,
+            R: Send + Sync,
+        {
+            let project = {
+                if let Some(project) = self.project.read().unwrap().as_ref() {
+                    project.clone()
+                }
+                else {
+                    return None;
+                }
+            };
+
+            Some(callback(Arc::new(RwLock::new(project))).await)
+        }
+
+
+        async fn read_project(&self, _path: &Path) -> project_repo::Result<SerializedProject> {
+            if self.settings.fail_calls {
+                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::other("Mock error!"))));
+            }
+
+            match self.project.read().unwrap().as_ref() {
+                Some(_) => Ok(default_serialized_project()),
+                None => Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::NotFound, "Project not found")))),
+            }
+        }
+
+        async fn open_project(&self, project: Project) -> project_repo::Result<ProjectID> {
+            self.call_tracker.write().unwrap().open_project_calls += 1;
+
+            if *self.is_project_open.read().unwrap() {
+                return Err(ProjectRepoError::Open(ProjectOpenError::AlreadyOpen));
+            }
+
+            let project_id = *project.id();
+            *self.project.write().unwrap() = Some(project);
+            *self.is_project_open.write().unwrap() = true;
+            Ok(project_id)
+        }
+
+        fn close_project(&self, _project_id: ProjectID) -> project_repo::Result<()> {
+            self.call_tracker.write().unwrap().close_project_calls += 1;
+
+            if self.settings.fail_calls {
+                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::other("Mock error!"))));
+            }
+
+            if !*self.is_project_open.read().unwrap() {
+                return Err(ProjectRepoError::Close(ProjectCloseError::FileNotOpen));
+            }
+
+            let mut is_project_open = self.is_project_open.write().unwrap();
+            *is_project_open = false;
+            Ok(())
+        }
+
+        async fn save_project(&self, _project_id: ProjectID) -> project_repo::Result<PathBuf> {
+            self.call_tracker.write().unwrap().save_project_calls += 1;
+
+            if self.settings.fail_calls {
+                return Err(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::other("Mock error!"))));
+            }
+
+            if !*self.is_project_open.read().unwrap() {
+                return Err(ProjectRepoError::Close(ProjectCloseError::FileNotOpen));
+            }
+
+            self.project.read().unwrap().clone()
+                .map(|project| project.path().clone())
+                .flatten()
+                .ok_or(ProjectRepoError::Filesystem(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::NotFound, "Project not found"))))
+        }
+
+        async fn save_project_with_progress(&self, project_id: ProjectID, mut on_progress: SaveProgressCallback, cancellation: SaveCancellation) -> project_repo::Result<PathBuf> {
+            if cancellation.is_cancelled() {
+                return Err(ProjectRepoError::SaveCancelled);
+            }
+
+            let path = self.save_project(project_id).await?;
+            on_progress(SaveProgress { files_written: 1, total_files: 1 });
+
+            Ok(path)
+        }
+
+        async fn read_settings_file(&self, _project_root: &Path) -> project_repo::Result<ProjectSettingsFile> {
+            Ok(self.stored_settings_file.read().unwrap().clone().unwrap_or_default())
+        }
+
+        async fn write_settings_file(&self, _project_root: &Path, settings_file: &ProjectSettingsFile) -> project_repo::Result<()> {
+            self.call_tracker.write().unwrap().write_settings_file_calls += 1;
+            *self.stored_settings_file.write().unwrap() = Some(settings_file.clone());
+            Ok(())
+        }
+
+        async fn path_exists(&self, path: &Path) -> project_repo::Result<bool> {
+            Ok(self.existing_paths.iter().any(|existing_path| existing_path == path))
+        }
+
+        fn workspace_dir(&self) -> &Path {
+            &self.workspace_dir
+        }
+
+        fn set_workspace_dir(&mut self, workspace_dir: PathBuf) {
+            self.workspace_dir = workspace_dir;
+        }
+
+        async fn write_autosave(&self, project_id: ProjectID) -> project_repo::Result<PathBuf> {
+            Ok(self.workspace_dir.join("autosave").join(project_id.to_string()))
+        }
+
+        async fn write_snapshot(&self, project_id: ProjectID) -> project_repo::Result<PathBuf> {
+            Ok(self.workspace_dir.join("snapshots").join(project_id.to_string()))
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct ZipProviderCallTracker {
+        extract_calls: usize,
+        zip_calls: usize,
+        update_calls: usize,
+        cleanup_calls: usize,
+    }
+    
+    #[derive(Debug, Default)]
+    struct MockZipProviderSettings {
+        fail_extract: bool,
+        fail_zip: bool,
+        fail_cleanup: bool,
+        project_already_exists: bool,
+    }
+    
+    #[derive(Debug, Default)]
+    struct MockZipProvider {
+        serialized_project: Option<SerializedProject>,
+        /// Extracted from their own paths instead of the default project, e.g. for combined projects with different packs
+        projects_at_paths: std::collections::HashMap<PathBuf, SerializedProject>,
+        /// Paths which already hold a file, so zipping to them fails unless overwriting
+        existing_paths: Vec<PathBuf>,
+        /// Entries read one at a time by name, without extracting a project
+        entries: std::collections::HashMap<String, Vec<u8>>,
+        settings: std::sync::RwLock<MockZipProviderSettings>,
+        call_tracker: std::sync::RwLock<ZipProviderCallTracker>,
+    }
+
+    impl MockZipProvider {
+        fn with_project(serialized_project: SerializedProject) -> Self {
+            Self {
+                serialized_project: Some(serialized_project),
+                ..Self::default()
+            }
+        }
+        
+        fn with_projects_at(projects: impl IntoIterator<Item = (PathBuf, SerializedProject)>) -> Self {
+            Self {
+                projects_at_paths: projects.into_iter().collect(),
+                ..Self::default()
+            }
+        }
+        
+        fn settings(self, settings: MockZipProviderSettings) -> Self {
+            {
+                *self.settings.write().unwrap() = settings;
+            }
+            self
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ZipProvider<SerializedProject> for MockZipProvider {
+        async fn extract(&self, path: &Path) -> zip_service::Result<SerializedProject> {
+            self.call_tracker.write().unwrap().extract_calls += 1;
+            
+            if self.settings.read().unwrap().fail_extract {
+                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::other("Mock error!"))))
+            }
+            
+            self.projects_at_paths.get(path).or(self.serialized_project.as_ref()).cloned()
+                .ok_or(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::NotFound, "Project not found"))))
+                .map_err(Into::into)
+        }
+
+        async fn read_entry(&self, path: &Path, entry_name: &str) -> zip_service::Result<Vec<u8>> {
+            self.entries.get(entry_name).cloned()
+                .ok_or_else(|| zip_service::ZipError::EntryNotFound { archive: path.to_path_buf(), entry: entry_name.to_string() })
+        }
+
+        async fn zip(&self, path: &Path, data: &SerializedProject, overwrite_existing: bool) -> zip_service::Result<()> {
+            self.call_tracker.write().unwrap().zip_calls += 1;
+
+            if self.settings.read().unwrap().fail_zip {
+                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::other("Mock error!"))))
+            }
+            
+            let already_exists = self.settings.read().unwrap().project_already_exists || self.existing_paths.iter().any(|existing| existing == path);
+            if already_exists && !overwrite_existing {
+                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::new(io::ErrorKind::AlreadyExists, "Project already exists"))))
+            }
+            
+            Ok(())
+        }
+
+        async fn update_zip(&self, _path: &Path, _data: &SerializedProject) -> zip_service::Result<ZipUpdate> {
+            self.call_tracker.write().unwrap().update_calls += 1;
+
+            if self.settings.read().unwrap().fail_zip {
+                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::other("Mock error!"))))
+            }
+
+            Ok(ZipUpdate::default())
+        }
+
+        async fn cleanup_file(&self, path: &Path) -> zip_service::Result<()> {
+            self.call_tracker.write().unwrap().cleanup_calls += 1;
+
+            if self.settings.read().unwrap().fail_cleanup {
+                return Err(zip_service::ZipError::IOError(FilesystemProviderError::IO(io::Error::other("Mock error!"))))
+            }
+
+            Ok(())
+        }
+    }
+
+    static PROJECT_ADAPTER_CONFIG: Lazy<std::sync::RwLock<ProjectAdapterConfig>> = Lazy::new(|| {
+        std::sync::RwLock::new(ProjectAdapterConfig::default())
+    });
+
+    #[derive(Debug, Default)]
+    struct ProjectAdapterConfig {
+        serialized_project: Option<SerializedProject>,
+        project: Option<Project>,
+        fail_conversion: std::sync::RwLock<bool>,
+    }
+    
+    impl ProjectAdapterConfig {
+        fn new(serialized_project: SerializedProject, project: Project) -> Self {
+            Self {
+                serialized_project: Some(serialized_project),
+                project: Some(project),
+                fail_conversion: std::sync::RwLock::new(false),
+            }
+        }
+        
+        fn fail_conversion(self) -> Self {
+            {
+                *self.fail_conversion.write().unwrap() = true;
+            }
+            self
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockProjectAdapter;
+
+    impl MockProjectAdapter {
+        fn set_config(config: ProjectAdapterConfig) {
+            *PROJECT_ADAPTER_CONFIG.write().unwrap() = config;
+        }
+
+        fn reset_config() {
+            *PROJECT_ADAPTER_CONFIG.write().unwrap() = ProjectAdapterConfig::default();
+        }
+    }
+    
+    #[derive(Debug, thiserror::Error)]
+    #[error("Test error")]
+    struct TestError;
+    impl AdapterError for TestError {}
+
+    #[async_trait::async_trait]
+    impl Adapter<SerializedProjectData, Project> for MockProjectAdapter {
+        type ConversionError = ProjectDeserializeError;
+        type SerializedConversionError = Infallible;
+
+        async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(_serialized: AdapterInput<'_, SerializedProjectData>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Project, Self::ConversionError> {
+            let config = PROJECT_ADAPTER_CONFIG.read().unwrap();
+
+            if *config.fail_conversion.read().unwrap() {
+                return Err(Self::ConversionError::PackInfo(AdapterRepoError::DeserializationError(Box::new(TestError))));
+            }
+
+            Ok(config.project.clone().unwrap())
+        }
+
+        async fn serialize<AdpProvider: AdapterProvider + ?Sized>(domain: AdapterInput<'_, Project>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<SerializedProjectData, Self::SerializedConversionError> {
+            match domain.project_type() {
+                ProjectType::Combined => {
+                    let serialized_project = PROJECT_ADAPTER_CONFIG.read().expect("Failed to read config").serialized_project.clone().unwrap();
+                    Ok(SerializedProjectData::Combined {
+                        data_project: serialized_project.clone(),
+                        resource_project: serialized_project,
+                    })
+                }
+                ProjectType::DataPack => {
+                    Ok(SerializedProjectData::Data(PROJECT_ADAPTER_CONFIG.read().expect("Failed to read config").serialized_project.clone().unwrap()))
+                }
+                ProjectType::ResourcePack => {
+                    Ok(SerializedProjectData::Resource(PROJECT_ADAPTER_CONFIG.read().expect("Failed to read config").serialized_project.clone().unwrap()))
+                }
+            }
+        }
+    }
+    
+    fn default_test_adapter_provider() -> DefaultAdapterProvider {
+        let provider = DefaultAdapterProvider::new();
+        provider.register::<MockProjectAdapter, SerializedProjectData, Project>();
+        provider
+    }
+
+    fn default_test_service() -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
+        ProjectService::with_no_adapters(
+            MockProjectProvider::default(),
+            MockZipProvider::default(),
+            default_test_adapter_provider(),
+        )
+    }
+
+    fn test_service_with_project_provider(project_provider: MockProjectProvider) -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
+        ProjectService::with_no_adapters(
+            project_provider,
+            MockZipProvider::default(),
+            default_test_adapter_provider(),
+        )
+    }
+
+    fn test_service_with_zip_provider(zip_provider: MockZipProvider) -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
+        ProjectService::with_no_adapters(
+            MockProjectProvider::default(),
+            zip_provider,
+            default_test_adapter_provider(),
+        )
+    }
+
+    fn test_service_with_project_zip_provider(project_provider: MockProjectProvider, zip_provider: MockZipProvider) -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
+        ProjectService::with_no_adapters(
+            project_provider,
+            zip_provider,
+            default_test_adapter_provider(),
+        )
+    }
+
+    fn default_test_project_settings() -> ProjectSettings {
+        ProjectSettings::DataPack {
+            name: "Test Project".to_string(),
+            description: PackDescription::String("Test Description".to_string()),
+            path: Some("test/file/path".into()),
+            project_version: ProjectVersion { version: *versions::V1_20_4 },
+        }
+    }
+    
+    fn default_serialized_project() -> SerializedProject {
+        SerializedProject::with_name(
+            "Test Project".to_string(),
+            SerializedProjectType::Data,
+            PackInfo::new(
+                PackData::new(
+                    "test_pack".into(),
+                    versions::get_datapack_format_for_version(versions::latest()).get_format_id() as u32,
+                    None
+                ),
+                None, None, None, None
+            )
+        )
+    }
+    
+    mod create_project {
+        use std::str::FromStr;
+        use mc_version::MinecraftVersion;
+        use crate::data::domain::project::{ProjectType, ProjectVersion};
+        use crate::data::domain::project_template::TemplateError;
+        use crate::data::domain::versions;
+        use super::*;
+        
+        /// Test creating a project
+        #[tokio::test]
+        async fn test_create_project() {
+            let project_service = default_test_service();
+
+            // Given valid project settings
+            
+            let project_settings = default_test_project_settings();
+            
+            // When I create a project
+            
+            let project_id = project_service.create_project(project_settings.clone(), false).await.unwrap();
+            
+            // It should create the new project
+
+            // Verify that the project created by the service matches one manually created
+            let project_provider = project_service.project_provider.read().await;
+
+            let created_settings = project_provider.with_project(project_id, |project| {
+                let mut comparison_project = Project::from_settings(project_settings.clone());
+                comparison_project.set_id(project_id);
+
+                assert_eq!(*project, comparison_project);
+
+                project.recreate_settings()
+            }).unwrap();
+            
+            // Verify individual settings
+            assert_eq!(created_settings.name(), project_settings.name());
+            assert_eq!(created_settings.path(), project_settings.path());
+            
+            // Validate that a valid UUID was supplied
+            assert_ne!(project_id, ProjectID::nil());
+
+            // Verify calls to the provider
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.add_project_calls, 1);
+        }
+
+        /// Test creating a project
+        #[tokio::test]
+        async fn test_create_project_special_characters() {
+            let project_service = default_test_service();
+
+            // Given valid project settings
+
+            let project_settings = ProjectSettings::DataPack {
+                // 测试项目 means "Test Project" in simplified chinese
+                name: "测试项目".to_string(),
+                description: PackDescription::String("Test Description".to_string()),
+                path: Some("test/file/测试项目".into()),
+                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            };
+
+            // When I create a project
+
+            let project_id = project_service.create_project(project_settings.clone(), false).await.unwrap();
+
+            // It should create the new project
+
+            // Verify that the project created by the service matches one manually created
+            let project_provider = project_service.project_provider.read().await;
+
+            let created_settings = project_provider.with_project(project_id, |project| {
+                let mut comparison_project = Project::from_settings(project_settings.clone());
+                comparison_project.set_id(project_id);
+
+                assert_eq!(*project, comparison_project);
+
+                project.recreate_settings()
+            }).unwrap();
+
+            // Verify individual settings
+            assert_eq!(created_settings.name(), project_settings.name());
+            assert_eq!(created_settings.path(), project_settings.path());
+        }
+
+        /// Test attempting to create a project with an invalid path
+        #[tokio::test]
+        async fn test_create_project_invalid_path() {
+            let project_service = default_test_service();
+
+            // Given valid project settings
+
+            let project_settings = ProjectSettings::DataPack {
+                name: "Test Project".to_string(),
+                description: PackDescription::String("Test Description".to_string()),
+                path: Some("test?/invalid</path>".into()),
+                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            };
+
+            // When I create a project
+
+            let project_id = project_service.create_project(project_settings.clone(), false).await.unwrap();
+
+            // It should create the new project
+            let project_provider = project_service.project_provider.read().await;
+
+            #[cfg(target_os = "windows")]
+            let expected_path = "test_\\invalid_\\path_";
+            #[cfg(not(target_os = "windows"))]
+            let expected_path = "test_/invalid_/path_";
+
+            let created_settings = project_provider.with_project(project_id, |project| project.recreate_settings()).unwrap();
+            assert_eq!(created_settings.path().as_ref().unwrap().to_string_lossy(), expected_path);
+        }
+        
+        /// Test attempting to create a project while one already exists with the same name
+        #[tokio::test]
+        async fn test_create_duplicate_project() {
+            // Given a project that already exists
+
+            let project_settings = ProjectSettings::DataPack {
+                name: "Test Project".to_string(),
+                description: PackDescription::String("Test Description".to_string()),
+                path: Some("test/file/path".into()),
+                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            };
+
+            let existing_project = Project::from_settings(project_settings.clone());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
+            
+            // When I try to create that project again
+
+            let result = project_service.create_project(project_settings.clone(), false).await;
+            
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        }
+        
+        /// Test creating a new project while one already exists with the same name
+        /// but the overwrite flag is set
+        #[tokio::test]
+        async fn test_overwrite_existing_project() {
+            // Given a project that already exists
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
+
+            // When I try to create that project again with the overwrite flag set
+
+            let result = project_service.create_project(project_settings.clone(), true).await;
+
+            // It should create the project
+
+            assert!(result.is_ok());
+        }
+
+        /// Test creating a combined project for a version which only one half of the pack supports
+        #[tokio::test]
+        async fn test_create_combined_project_incompatible_version() {
+            let project_service = default_test_service();
+
+            // Given combined project settings for a version from before data packs
+            let project_settings = ProjectSettings::Combined {
+                name: "Test Project".to_string(),
+                data_description: PackDescription::String("Test Description".to_string()),
+                resource_description: PackDescription::String("Test Description".to_string()),
+                path: Some("test/file/path".into()),
+                project_version: ProjectVersion { version: MinecraftVersion::from_str("1.12.2").unwrap() },
+            };
+
+            // When I try to create it
+            let result = project_service.create_project(project_settings, false).await;
+
+            // It should be refused before anything is created
+            assert!(matches!(result, Err(ProjectServiceError::InvalidVersion(_))));
+            assert_eq!(project_service.project_provider.read().await.call_tracker.read().unwrap().add_project_calls, 0);
+        }
+
+        /// Test creating a combined project for a version both halves support
+        #[tokio::test]
+        async fn test_create_combined_project_compatible_version() {
+            let project_service = default_test_service();
+
+            // Given combined project settings for a version with both formats
+            let project_settings = ProjectSettings::Combined {
+                name: "Test Project".to_string(),
+                data_description: PackDescription::String("Test Description".to_string()),
+                resource_description: PackDescription::String("Test Description".to_string()),
+                path: Some("test/file/path".into()),
+                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            };
+
+            // When I create it
+            let result = project_service.create_project(project_settings, false).await;
+
+            // It should be created
+            assert!(result.is_ok());
+        }
+
+        /// Test thread safety when multiple threads try to create the same project
+        #[test]
+        fn test_create_project_concurrent() {
+            // Given valid project settings
+
+            // When I try to create that project multiple times on different threads
+
+            // Only one project should be created with no other side effects`
+
+            // TODO: Implement test`
+        }
+        
+        /// Test graceful error handling when the project provider returns an error 
+        #[tokio::test]
+        async fn test_create_project_provider_failure() {
+            // Given an error from the repo
+
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_settings(
+                MockProjectProviderSettings {
+                    fail_calls: true,
+                }
+            ));
+
+            let project_settings = default_test_project_settings();
+            
+            // When I try to create a new project
+
+            let result = project_service.create_project(project_settings.clone(), false).await;
+
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        }
+
+        /// Test scaffolding a project from a template
+        #[tokio::test]
+        async fn test_create_from_template() {
+            let project_service = default_test_service();
+
+            // Given settings for a data pack on the latest version
+            let project_settings = ProjectSettings::DataPack {
+                name: "Test Project".to_string(),
+                description: PackDescription::String("Test Description".to_string()),
+                path: Some("test/file/path".into()),
+                project_version: ProjectVersion { version: versions::latest() },
+            };
+
+            // When I create a project from the data pack template
+            let project_id = project_service.create_from_template("data_pack", project_settings, false).await.unwrap();
+
+            // Then it should start with functions in a namespace made from its name, run by the load and tick tags
+            let project_provider = project_service.project_provider.read().await;
+            let files = project_provider.with_project(project_id, |project| project.files().clone()).unwrap();
+
+            let paths: Vec<_> = files.keys().cloned().collect();
+            assert_eq!(paths, vec![
+                PathBuf::from("data/minecraft/tags/function/load.json"),
+                PathBuf::from("data/minecraft/tags/function/tick.json"),
+                PathBuf::from("data/test_project/function/load.mcfunction"),
+                PathBuf::from("data/test_project/function/tick.mcfunction"),
+            ]);
+
+            let load_tag: serde_json::Value = serde_json::from_str(&files[Path::new("data/minecraft/tags/function/load.json")]).unwrap();
+            let tick_tag: serde_json::Value = serde_json::from_str(&files[Path::new("data/minecraft/tags/function/tick.json")]).unwrap();
+            assert_eq!(load_tag, serde_json::json!({"values": ["test_project:load"]}));
+            assert_eq!(tick_tag, serde_json::json!({"values": ["test_project:tick"]}));
+        }
+
+        /// Test a template which doesn't exist
+        #[tokio::test]
+        async fn test_create_from_unknown_template() {
+            let project_service = default_test_service();
+
+            // Given valid project settings
+            let project_settings = default_test_project_settings();
+
+            // When I create a project from a template which doesn't exist
+            let result = project_service.create_from_template("modpack", project_settings, false).await;
+
+            // It should return an appropriate error without creating a project
+            assert!(matches!(result, Err(ProjectServiceError::Template(TemplateError::UnknownTemplate(_)))));
+            assert_eq!(project_service.project_provider.read().await.call_tracker.read().unwrap().add_project_calls, 0);
+        }
+    }
+    
+    mod open_project {
+        use super::*;
+        use crate::services::project_service::ZipError;
+        
+        /// Test opening a project
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_open_project() {
+            // Given a project that exists
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            MockProjectAdapter::set_config(ProjectAdapterConfig::new(default_serialized_project(), existing_project.clone()));
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
+            
+            // When I open it
+
+            let project_id = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await.unwrap();
+            
+            // It should be opened properly
+
+            let project_provider = project_service.project_provider.read().await;
+
+            let created_settings = project_provider.with_project(project_id, |project| {
+                let mut comparison_project = Project::from_settings(project_settings.clone());
+                comparison_project.set_id(project_id);
+
+                assert_eq!(*project, comparison_project);
+
+                project.recreate_settings()
+            });
+
+            assert!(*project_provider.is_project_open.read().unwrap());
+
+            // Verify calls to the provider
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.open_project_calls, 1);
+        }
+
+        /// Test that opening a project restores its editor state
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_open_project_restores_settings_file() {
+            // Given a project with stored editor state
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            MockProjectAdapter::set_config(ProjectAdapterConfig::new(default_serialized_project(), existing_project.clone()));
+            let stored_settings_file = ProjectSettingsFile {
+                open_tabs: vec![PathBuf::from("pack.mcmeta")],
+                active_tab: Some(PathBuf::from("pack.mcmeta")),
+                theme: None,
+                word_wrap: false,
+            };
+
+            let project_provider = MockProjectProvider::with_project(existing_project);
+            *project_provider.stored_settings_file.write().unwrap() = Some(stored_settings_file.clone());
+            let project_service = test_service_with_project_provider(project_provider);
+
+            // When I open it
+
+            let project_id = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await.unwrap();
+
+            // The editor state should be restored, without counting as an unsaved change
+
+            assert_eq!(project_service.settings_file(project_id).await.unwrap(), stored_settings_file);
+
+            let project_provider = project_service.project_provider.read().await;
+            assert_eq!(project_provider.with_project(project_id, |project| *project.has_unsaved_changes()), Some(false));
+        }
+
+        /// Test opening a project which can't be deserialized
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_open_project_invalid() {
+            // Given a project that exists, but is invalid
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            MockProjectAdapter::set_config(ProjectAdapterConfig::new(default_serialized_project(), existing_project.clone()).fail_conversion());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
+
+            // When I try to open it
+
+            let result = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await;
+
+            // It should return an appropriate error without opening anything
+
+            assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::Deserialization(_)))));
+            assert_eq!(project_service.project_provider.read().await.call_tracker.read().unwrap().open_project_calls, 0);
+        }
+
+        /// Test trying to open a project that doesn't exist
+        #[tokio::test]
+        async fn test_open_project_non_existent() {
+            let project_service = default_test_service();
+
+            // Given a project that does not exist
+
+            let path = Path::new("nonexistent/path");
+            
+            // When I try to open it
+
+            let result = project_service.open_project(path).await;
+            
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        }
+        
+        /// Test trying to open a project which is already open
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_open_project_already_open() {
+            // Given a project which is already open
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            MockProjectAdapter::set_config(ProjectAdapterConfig::new(default_serialized_project(), existing_project.clone()));
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project));
+            
+            // When I try to open it again
+
+            let result = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await;
+
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        }
+        
+        /// Test thread safety when multiple threads try to open the same project
+        #[tokio::test]
+        async fn test_open_project_concurrent() {
+            // Given a project that exists
+            
+            // When I try to open it multiple times on different threads
+            
+            // Only one should succeed with no other side effects
+
+            // TODO: Implement test
+        }
+
+        /// Test graceful error handling when the provider returns an error
+        #[tokio::test]
+        async fn test_open_project_provider_failure() {
+            // Given an error from the repo
+
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_settings(
+                MockProjectProviderSettings {
+                    fail_calls: true,
+                }
+            ));
+
+            let project_settings = default_test_project_settings();
+
+            // When I try to open a project
+
+            let result = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await;
+
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        }
+    }
+    
+    mod close_project {
+        use super::*;
+        
+        /// Test closing a project
+        #[tokio::test]
+        async fn test_close_project() {
+            // Given an open project
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project.clone()));
+            
+            // When I close it
+
+            let result = project_service.close_project(*existing_project.id()).await;
+
+            // It should be closed properly
+
+            assert!(result.is_ok());
+
+            let project_provider = project_service.project_provider.read().await;
+            assert!(!*project_provider.is_project_open.read().unwrap());
+
+            // Verify calls to the provider
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.close_project_calls, 1);
+        }
+
+        /// Test trying to close a project which has unsaved changes
+        #[tokio::test]
+        async fn test_close_project_unsaved_changes() {
+            // Given a project with unsaved changes
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::with_unsaved_changes(project_settings.clone());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project.clone()));
+            
+            // When I try to close it
+
+            let result = project_service.close_project(*existing_project.id()).await;
+            
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::CannotCloseUnsavedChanges)));
+        }
+
+        /// Test checking for unsaved changes before closing
+        #[rstest::rstest]
+        #[case::saved(false)]
+        #[case::unsaved(true)]
+        #[tokio::test]
+        async fn test_has_unsaved_changes(#[case] unsaved: bool) {
+            // Given a project with or without unsaved changes
+
+            let existing_project = match unsaved {
+                true => Project::with_unsaved_changes(default_test_project_settings()),
+                false => Project::from_settings(default_test_project_settings()),
+            };
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project.clone()));
+
+            // When I check it
+
+            let result = project_service.has_unsaved_changes(*existing_project.id()).await;
+
+            // It should report whether there is anything to save
+
+            assert_eq!(result.unwrap(), unsaved);
+        }
+
+        /// Test closing a project after discarding its unsaved changes
+        #[tokio::test]
+        async fn test_close_project_after_discarding_changes() {
+            // Given a project with unsaved changes
+
+            let existing_project = Project::with_unsaved_changes(default_test_project_settings());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project.clone()));
+
+            // When I discard the changes and then close it
+
+            project_service.discard_changes(*existing_project.id()).await.unwrap();
+            let result = project_service.close_project(*existing_project.id()).await;
+
+            // It should close without being saved
+
+            assert!(result.is_ok());
+
+            let project_provider = project_service.project_provider.read().await;
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.close_project_calls, 1);
+            assert_eq!(call_tracker.save_project_calls, 0);
+        }
+
+        /// Test that closing a project stores its editor state
+        #[tokio::test]
+        async fn test_close_project_persists_settings_file() {
+            // Given an open project whose editor state has changed
+
+            let existing_project = Project::from_settings(default_test_project_settings());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project.clone()));
+
+            let settings_file = ProjectSettingsFile {
+                open_tabs: vec![PathBuf::from("data/test/function/load.mcfunction")],
+                ..ProjectSettingsFile::default()
+            };
+            project_service.set_settings_file(*existing_project.id(), settings_file.clone()).await.unwrap();
+
+            // When I close it
+
+            project_service.close_project(*existing_project.id()).await.unwrap();
+
+            // The editor state should be written beside it
+
+            let project_provider = project_service.project_provider.read().await;
+            assert_eq!(*project_provider.stored_settings_file.read().unwrap(), Some(settings_file));
+            assert_eq!(project_provider.call_tracker.read().unwrap().write_settings_file_calls, 1);
+        }
+
+        /// Test that closing a read-only project leaves its folder untouched
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_close_project_read_only() {
+            // Given a project opened read-only
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            MockProjectAdapter::set_config(ProjectAdapterConfig::new(default_serialized_project(), existing_project.clone()));
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
+            let project_id = project_service.open_project_read_only(project_settings.path().as_ref().unwrap()).await.unwrap();
+
+            // When I close it
+
+            project_service.close_project(project_id).await.unwrap();
+
+            // Its editor state should not be written
+
+            let project_provider = project_service.project_provider.read().await;
+            assert_eq!(project_provider.call_tracker.read().unwrap().write_settings_file_calls, 0);
+            assert_eq!(project_provider.call_tracker.read().unwrap().close_project_calls, 1);
+        }
+
+        /// Test trying to close a project which is not open
+        #[tokio::test]
+        async fn test_close_project_not_open() {
+            // Given a project which is not open
+
+            let project_settings = default_test_project_settings();
+            let existing_project = Project::from_settings(project_settings.clone());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project.clone()));
+            
+            // When I try to close it
+
+            let result = project_service.close_project(*existing_project.id()).await;
+
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        }
+
+        /// Test trying to close a project which does not exist
+        #[tokio::test]
+        async fn test_close_project_nonexistent() {
+            let project_service = default_test_service();
+
+            // Given a project which does not exist
+
+            let project_id = Project::generate_test_id();
+
+            // When I try to close it
+
+            let result = project_service.close_project(project_id).await;
+
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::ProjectDoesNotExist)));
+        }
+
+        /// Test thread safety when multiple threads try to close the same project
+        #[test]
+        fn test_close_project_concurrent() {
+            // Given an open project
+
+            // When I try to close it multiple times on different threads
+
+            // Only one should succeed with no other side effects
+
+            // TODO: Implement test
+        }
+
+        /// Test graceful error handling when the provider returns an error
+        #[tokio::test]
+        async fn test_close_project_provider_failure() {
+            // Given an error from the repo
+
+            let project_settings = default_test_project_settings();
+            let project = Project::from_settings(project_settings.clone());
+
+            let mut project_provider = MockProjectProvider::with_open_project(project.clone());
+            project_provider.settings = MockProjectProviderSettings {
+                fail_calls: true,
+            };
+
+            let project_service = test_service_with_project_provider(project_provider);
+
+            // When I try to close a project
+
+            let result = project_service.close_project(*project.id()).await;
+
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        }
+    }
+    
+    mod save_project {
+        use super::*;
+
+        /// Test saving a project
+        #[tokio::test]
+        async fn test_save_project() {
+            // Given a project with unsaved changes
+
+            let project_settings = default_test_project_settings();
+            let project = Project::with_unsaved_changes(project_settings.clone());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
+            
+            // When I save it
+
+            let result = project_service.save_project(project_id).await;
+            
+            // It should be saved
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap().as_path(), Path::new("test/file/path"));
+
+            // Verify calls to the provider
+            let project_provider = project_service.project_provider.read().await;
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.save_project_calls, 1);
+        }
+
+        /// Test that saving records how long the save took
+        #[tokio::test]
+        async fn test_save_project_records_duration() {
+            // Given a project with unsaved changes, and somewhere to record timings
+            let project = Project::with_unsaved_changes(default_test_project_settings());
+            let project_id = *project.id();
+            let metrics = Arc::new(MetricsService::new());
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project))
+                .with_metrics(metrics.clone());
+
+            // When I save it
+            project_service.save_project(project_id).await.unwrap();
+
+            // Then a non-zero duration should be recorded for the save, and nothing else
+            let stats = project_service.metrics().stats(Operation::Save).unwrap();
+            assert_eq!(stats.count, 1);
+            assert!(stats.last > std::time::Duration::ZERO);
+            assert_eq!(metrics.stats(Operation::Save), Some(stats));
+            assert_eq!(metrics.stats(Operation::Import), None);
+        }
+
+        /// Test that saving marks the project as having no unsaved changes
+        #[tokio::test]
+        async fn test_save_project_clears_unsaved_changes() {
+            // Given a project with unsaved changes
+            let project = Project::with_unsaved_changes(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
+
+            // When I save it
+            project_service.save_project(project_id).await.unwrap();
+
+            // It should no longer have unsaved changes
+            let project_provider = project_service.project_provider.read().await;
+            let has_unsaved_changes = project_provider.with_project(project_id, |project| *project.has_unsaved_changes()).unwrap();
+            assert!(!has_unsaved_changes);
+        }
+
+        /// Test saving a project without a path
+        #[tokio::test]
+        async fn test_save_project_no_path() {
+            // Given a project with unsaved changes but no path
+            let project_settings = default_test_project_settings().with_path(None);
+            let project = Project::with_unsaved_changes(project_settings);
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
+
+            // When I try to save it
+            let result = project_service.save_project(project_id).await;
+
+            // It should return an error asking for a path
+            assert!(matches!(result, Err(ProjectServiceError::Save(SaveError::NoPathSet))));
+
+            // And it should not reach the provider
+            let project_provider = project_service.project_provider.read().await;
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.save_project_calls, 0);
+        }
+
+        /// Test saving a project with no changes
+        #[tokio::test]
+        async fn test_save_project_no_changes() {
+            // Given a project without unsaved changes
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
+
+            // When I try to save it
+            let result = project_service.save_project(project_id).await;
+
+            // It should return an error stating there is nothing to save
+            assert!(matches!(result, Err(ProjectServiceError::Save(SaveError::NoChangesToSave))));
+
+            // And it should not reach the provider
+            let project_provider = project_service.project_provider.read().await;
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.save_project_calls, 0);
+        }
+
+        /// Test saving a project which doesn't exist
+        #[tokio::test]
+        async fn test_save_project_does_not_exist() {
+            // Given no existing projects
+            let project_service = default_test_service();
+
+            // When I try to save a project
+            let result = project_service.save_project(ProjectID::nil()).await;
+
+            // It should return an error
+            assert!(matches!(result, Err(ProjectServiceError::ProjectDoesNotExist)));
+        }
+
+        /// Test thread safety when multiple threads try to save the same project
+        #[test]
+        fn test_save_project_concurrent() {
+            // Given a project
+
+            // When I try to save it multiple times on different threads
+
+            // Only one should succeed with no other side effects
 
-    impl MockProjectAdapter {
-        fn set_config(config: ProjectAdapterConfig) {
-            *PROJECT_ADAPTER_CONFIG.write().unwrap() = config;
+            // TODO: Implement test
         }
 
-        fn reset_config() {
-            *PROJECT_ADAPTER_CONFIG.write().unwrap() = ProjectAdapterConfig::default();
-        }
-    }
-    
-    #[derive(Debug, thiserror::Error)]
-    #[error("Test error")]
-    struct TestError;
-    impl AdapterError for TestError {}
+        /// Test graceful error handling when the provider returns an error
+        #[tokio::test]
+        async fn test_save_project_provider_failure() {
+            // Given an error from the repo
 
-    #[async_trait::async_trait]
-    impl Adapter<SerializedProjectData, Project> for MockProjectAdapter {
-        type ConversionError = ProjectDeserializeError;
-        type SerializedConversionError = Infallible;
+            let project_settings = default_test_project_settings();
+            let project = Project::with_unsaved_changes(project_settings.clone());
+            let project_id = *project.id();
 
-        async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(_serialized: AdapterInput<&SerializedProjectData>, context: AdapterProviderContext<'_, AdpProvider>) -> Result<Project, Self::ConversionError> {
-            let config = PROJECT_ADAPTER_CONFIG.read().unwrap();
+            let mut project_provider = MockProjectProvider::with_open_project(project.clone());
+            project_provider.settings = MockProjectProviderSettings {
+                fail_calls: true,
+            };
 
-            if *config.fail_conversion.read().unwrap() {
-                return Err(Self::ConversionError::PackInfo(AdapterRepoError::DeserializationError(Box::new(TestError))));
-            }
+            let project_service = test_service_with_project_provider(project_provider);
 
-            Ok(config.project.clone().unwrap())
+            // When I try to save a project
+
+            let result = project_service.save_project(project_id).await;
+
+            // It should return an appropriate error
+
+            assert!(result.is_err());
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
         }
 
-        async fn serialize<AdpProvider: AdapterProvider + ?Sized>(domain: AdapterInput<&Project>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<SerializedProjectData, Self::SerializedConversionError> {
-            match domain.project_type() {
-                ProjectType::Combined => {
-                    let serialized_project = PROJECT_ADAPTER_CONFIG.read().expect("Failed to read config").serialized_project.clone().unwrap();
-                    Ok(SerializedProjectData::Combined {
-                        data_project: serialized_project.clone(),
-                        resource_project: serialized_project,
-                    })
-                }
-                ProjectType::DataPack => {
-                    Ok(SerializedProjectData::Data(PROJECT_ADAPTER_CONFIG.read().expect("Failed to read config").serialized_project.clone().unwrap()))
-                }
-                ProjectType::ResourcePack => {
-                    Ok(SerializedProjectData::Resource(PROJECT_ADAPTER_CONFIG.read().expect("Failed to read config").serialized_project.clone().unwrap()))
-                }
-            }
+        /// Test that a project opened read-only can't be saved
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_save_project_read_only() {
+            // Given a project opened read-only, which somehow has unsaved changes
+            let project_settings = default_test_project_settings();
+            let project = Project::with_unsaved_changes(project_settings.clone());
+            MockProjectAdapter::set_config(ProjectAdapterConfig::new(default_serialized_project(), project.clone()));
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
+            let project_id = project_service.open_project_read_only(project_settings.path().as_ref().unwrap()).await.unwrap();
+
+            // When I try to save it
+            let result = project_service.save_project(project_id).await;
+
+            // It should return an error saying it is read-only
+            assert!(matches!(result, Err(ProjectServiceError::ReadOnly)));
+            assert_eq!(result.unwrap_err().to_string(), "Project was opened read-only, so it can't be changed or saved!");
+
+            // And it should not reach the provider
+            let project_provider = project_service.project_provider.read().await;
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.save_project_calls, 0);
         }
-    }
-    
-    fn default_test_adapter_provider() -> DefaultAdapterProvider {
-        let provider = DefaultAdapterProvider::new();
-        provider.register::<MockProjectAdapter, SerializedProjectData, Project>();
-        provider
-    }
 
-    fn default_test_service() -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
-        ProjectService::with_no_adapters(
-            MockProjectProvider::default(),
-            MockZipProvider::default(),
-            default_test_adapter_provider(),
-        )
-    }
+        /// Test that saving with progress reports it and clears unsaved changes
+        #[tokio::test]
+        async fn test_save_project_with_progress() {
+            // Given a project with unsaved changes
+            let project = Project::with_unsaved_changes(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
 
-    fn test_service_with_project_provider(project_provider: MockProjectProvider) -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
-        ProjectService::with_no_adapters(
-            project_provider,
-            MockZipProvider::default(),
-            default_test_adapter_provider(),
-        )
-    }
+            // When I save it with progress reporting
+            let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let reported = progress.clone();
+            let result = project_service.save_project_with_progress(
+                project_id,
+                Box::new(move |update| reported.lock().unwrap().push(update)),
+                SaveCancellation::new(),
+            ).await;
 
-    fn test_service_with_zip_provider(zip_provider: MockZipProvider) -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
-        ProjectService::with_no_adapters(
-            MockProjectProvider::default(),
-            zip_provider,
-            default_test_adapter_provider(),
-        )
-    }
+            // Then it should be saved, with its progress reported
+            assert_eq!(result.unwrap().as_path(), Path::new("test/file/path"));
+            assert_eq!(*progress.lock().unwrap(), vec![SaveProgress { files_written: 1, total_files: 1 }]);
 
-    fn test_service_with_project_zip_provider(project_provider: MockProjectProvider, zip_provider: MockZipProvider) -> ProjectService<MockProjectProvider, MockZipProvider, DefaultAdapterProvider> {
-        ProjectService::with_no_adapters(
-            project_provider,
-            zip_provider,
-            default_test_adapter_provider(),
-        )
-    }
+            let project_provider = project_service.project_provider.read().await;
+            let has_unsaved_changes = project_provider.with_project(project_id, |project| *project.has_unsaved_changes()).unwrap();
+            assert!(!has_unsaved_changes);
+        }
 
-    fn default_test_project_settings() -> ProjectSettings {
-        ProjectSettings::DataPack {
-            name: "Test Project".to_string(),
-            description: PackDescription::String("Test Description".to_string()),
-            path: Some("test/file/path".into()),
-            project_version: ProjectVersion { version: *versions::V1_20_4 },
+        /// Test that a cancelled save keeps the project's unsaved changes
+        #[tokio::test]
+        async fn test_save_project_with_progress_cancelled() {
+            // Given a project with unsaved changes, and a save which has already been cancelled
+            let project = Project::with_unsaved_changes(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
+            let cancellation = SaveCancellation::new();
+            cancellation.cancel();
+
+            // When I save it
+            let result = project_service.save_project_with_progress(project_id, Box::new(|_| {}), cancellation).await;
+
+            // Then it should fail, and the project should still have unsaved changes
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(ProjectRepoError::SaveCancelled))));
+
+            let project_provider = project_service.project_provider.read().await;
+            let has_unsaved_changes = project_provider.with_project(project_id, |project| *project.has_unsaved_changes()).unwrap();
+            assert!(has_unsaved_changes);
         }
     }
     
-    fn default_serialized_project() -> SerializedProject {
-        SerializedProject::with_name(
-            "Test Project".to_string(),
-            SerializedProjectType::Data,
-            PackInfo::new(
-                PackData::new(
-                    "test_pack".into(),
-                    versions::get_datapack_format_for_version(versions::latest()).get_format_id() as u32,
-                    None
-                ),
-                None, None, None, None
-            )
-        )
-    }
-    
-    mod create_project {
-        use crate::data::domain::project::{ProjectType, ProjectVersion};
-        use crate::data::domain::versions;
+    mod save_project_as {
         use super::*;
-        
-        /// Test creating a project
-        #[tokio::test]
-        async fn test_create_project() {
-            let project_service = default_test_service();
-
-            // Given valid project settings
-            
-            let project_settings = default_test_project_settings();
-            
-            // When I create a project
-            
-            let project_id = project_service.create_project(project_settings.clone(), false).await.unwrap();
-            
-            // It should create the new project
 
-            // Verify that the project created by the service matches one manually created
-            let project_provider = project_service.project_provider.read().await;
+        /// Test saving a project which has no path yet
+        #[tokio::test]
+        async fn test_save_project_as_no_path() {
+            // Given a project with no path
+            let project_settings = default_test_project_settings().with_path(None);
+            let project = Project::with_unsaved_changes(project_settings);
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
 
-            let created_settings = project_provider.with_project(project_id, |project| {
-                let mut comparison_project = Project::from_settings(project_settings.clone());
-                comparison_project.set_id(project_id);
+            // When I save it to a new path
+            let result = project_service.save_project_as(project_id, Path::new("new/file/path"), false).await;
 
-                assert_eq!(*project, comparison_project);
+            // It should be saved there
+            assert_eq!(result.unwrap().as_path(), Path::new("new/file/path"));
 
-                project.recreate_settings()
+            // And the project should now use that path, with no unsaved changes
+            let project_provider = project_service.project_provider.read().await;
+            let (path, has_unsaved_changes) = project_provider.with_project(project_id, |project| {
+                (project.path().clone(), *project.has_unsaved_changes())
             }).unwrap();
-            
-            // Verify individual settings
-            assert_eq!(created_settings.name(), project_settings.name());
-            assert_eq!(created_settings.path(), project_settings.path());
-            
-            // Validate that a valid UUID was supplied
-            assert_ne!(project_id, ProjectID::nil());
 
-            // Verify calls to the provider
+            assert_eq!(path, Some(PathBuf::from("new/file/path")));
+            assert!(!has_unsaved_changes);
+
             let call_tracker = project_provider.call_tracker.read().unwrap();
-            assert_eq!(call_tracker.add_project_calls, 1);
+            assert_eq!(call_tracker.save_project_calls, 1);
         }
 
-        /// Test creating a project
+        /// Test that the new path is sanitized
         #[tokio::test]
-        async fn test_create_project_special_characters() {
-            let project_service = default_test_service();
+        async fn test_save_project_as_sanitizes_path() {
+            // Given a project
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
 
-            // Given valid project settings
+            // When I save it to a path with invalid characters
+            let result = project_service.save_project_as(project_id, Path::new("new/file?/path*"), false).await;
 
-            let project_settings = ProjectSettings::DataPack {
-                // 测试项目 means "Test Project" in simplified chinese
-                name: "测试项目".to_string(),
-                description: PackDescription::String("Test Description".to_string()),
-                path: Some("test/file/测试项目".into()),
-                project_version: ProjectVersion { version: *versions::V1_20_4 },
-            };
+            // It should be saved to the sanitized path
+            assert_eq!(result.unwrap().as_path(), Path::new("new/file_/path_"));
+        }
 
-            // When I create a project
+        /// Test that paths climbing out of their directory are refused
+        #[rstest::rstest]
+        #[case::relative("../escape")]
+        #[case::nested("projects/../../escape")]
+        #[tokio::test]
+        async fn test_save_project_as_parent_dir(#[case] path: &str) {
+            // Given a project
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
 
-            let project_id = project_service.create_project(project_settings.clone(), false).await.unwrap();
+            // When I save it to a path with a `..` segment
+            let result = project_service.save_project_as(project_id, Path::new(path), false).await;
 
-            // It should create the new project
+            // It should be refused, without saving anything
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(ProjectRepoError::Create(ProjectCreationError::InvalidPath(_))))));
 
-            // Verify that the project created by the service matches one manually created
             let project_provider = project_service.project_provider.read().await;
+            assert_eq!(project_provider.call_tracker.read().unwrap().save_project_calls, 0);
+        }
 
-            let created_settings = project_provider.with_project(project_id, |project| {
-                let mut comparison_project = Project::from_settings(project_settings.clone());
-                comparison_project.set_id(project_id);
+        /// Test saving over an existing file without overwriting
+        #[tokio::test]
+        async fn test_save_project_as_collision() {
+            // Given a project and an existing file at the target path
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
 
-                assert_eq!(*project, comparison_project);
+            let mut project_provider = MockProjectProvider::with_open_project(project);
+            project_provider.existing_paths = vec![PathBuf::from("existing/path")];
 
-                project.recreate_settings()
-            }).unwrap();
+            let project_service = test_service_with_project_provider(project_provider);
 
-            // Verify individual settings
-            assert_eq!(created_settings.name(), project_settings.name());
-            assert_eq!(created_settings.path(), project_settings.path());
+            // When I save it to that path without overwriting
+            let result = project_service.save_project_as(project_id, Path::new("existing/path"), false).await;
+
+            // It should return an error
+            assert!(matches!(result, Err(ProjectServiceError::Save(SaveError::FileAlreadyExists(path))) if path == Path::new("existing/path")));
+
+            // And the project should keep its original path without being saved
+            let project_provider = project_service.project_provider.read().await;
+            let path = project_provider.with_project(project_id, |project| project.path().clone()).unwrap();
+            assert_eq!(path, Some(PathBuf::from("test/file/path")));
+
+            let call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(call_tracker.save_project_calls, 0);
         }
 
-        /// Test attempting to create a project with an invalid path
+        /// Test saving over an existing file with overwriting
         #[tokio::test]
-        async fn test_create_project_invalid_path() {
-            let project_service = default_test_service();
+        async fn test_save_project_as_overwrite() {
+            // Given a project and an existing file at the target path
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
 
-            // Given valid project settings
+            let mut project_provider = MockProjectProvider::with_open_project(project);
+            project_provider.existing_paths = vec![PathBuf::from("existing/path")];
 
-            let project_settings = ProjectSettings::DataPack {
-                name: "Test Project".to_string(),
-                description: PackDescription::String("Test Description".to_string()),
-                path: Some("test?/invalid</path>".into()),
-                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            let project_service = test_service_with_project_provider(project_provider);
+
+            // When I save it to that path, allowing overwrites
+            let result = project_service.save_project_as(project_id, Path::new("existing/path"), true).await;
+
+            // It should be saved there
+            assert_eq!(result.unwrap().as_path(), Path::new("existing/path"));
+        }
+
+        /// Test that a failed save restores the original path
+        #[tokio::test]
+        async fn test_save_project_as_provider_failure() {
+            // Given a project and a failing provider
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+
+            let mut project_provider = MockProjectProvider::with_open_project(project);
+            project_provider.settings = MockProjectProviderSettings {
+                fail_calls: true,
             };
 
-            // When I create a project
+            let project_service = test_service_with_project_provider(project_provider);
+
+            // When I try to save it to a new path
+            let result = project_service.save_project_as(project_id, Path::new("new/file/path"), false).await;
+
+            // It should return an error
+            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+
+            // And the project should be left as it was
+            let project_provider = project_service.project_provider.read().await;
+            let (path, has_unsaved_changes) = project_provider.with_project(project_id, |project| {
+                (project.path().clone(), *project.has_unsaved_changes())
+            }).unwrap();
 
-            let project_id = project_service.create_project(project_settings.clone(), false).await.unwrap();
+            assert_eq!(path, Some(PathBuf::from("test/file/path")));
+            assert!(!has_unsaved_changes);
+        }
+    }
+    
+    mod workspace {
+        use super::*;
 
-            // It should create the new project
-            let project_provider = project_service.project_provider.read().await;
+        /// Test that autosaves and snapshots follow the configured workspace directory
+        #[tokio::test]
+        async fn test_backups_use_workspace_dir() {
+            // Given an open project
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
 
-            #[cfg(target_os = "windows")]
-            let expected_path = "test_\\invalid_\\path_";
-            #[cfg(not(target_os = "windows"))]
-            let expected_path = "test_/invalid_/path_";
+            // When I move the workspace directory, then autosave and snapshot the project
+            project_service.set_workspace_dir(PathBuf::from("/backups")).await;
+            let autosave = project_service.autosave_project(project_id).await.unwrap();
+            let snapshot = project_service.snapshot_project(project_id).await.unwrap();
 
-            let created_settings = project_provider.with_project(project_id, |project| project.recreate_settings()).unwrap();
-            assert_eq!(created_settings.path().as_ref().unwrap().to_string_lossy(), expected_path);
+            // Then both should be written under the new workspace directory
+            assert!(autosave.starts_with("/backups/autosave"));
+            assert!(snapshot.starts_with("/backups/snapshots"));
+            assert_eq!(project_service.project_provider.read().await.workspace_dir(), Path::new("/backups"));
         }
-        
-        /// Test attempting to create a project while one already exists with the same name
-        #[tokio::test]
-        async fn test_create_duplicate_project() {
-            // Given a project that already exists
+    }
 
-            let project_settings = ProjectSettings::DataPack {
-                name: "Test Project".to_string(),
-                description: PackDescription::String("Test Description".to_string()),
-                path: Some("test/file/path".into()),
-                project_version: ProjectVersion { version: *versions::V1_20_4 },
-            };
+    mod import_zip {
+        use std::collections::HashMap;
+        use crate::data::domain::project::PackHalf;
+        use crate::data::domain::project_index::IndexProgress;
+        use crate::services::project_service::{DefaultAdapterProvider, ImportProgress, ZipError, ZipPath};
+        use super::*;
 
-            let existing_project = Project::from_settings(project_settings.clone());
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
+        /// Test importing a datapack from a zip as a new project
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_from_zip() {
+            // Given a valid zip
+
+            let serialized_project = default_serialized_project()
+                .with_files(HashMap::from([(PathBuf::from("data/test/function/load.mcfunction"), "say hi".to_string())]));
+            let project = Project::from_settings(default_test_project_settings());
             
-            // When I try to create that project again
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(project),
+                fail_conversion: Default::default(),
+            });
+            
+            let path = ZipPath::Single("test/file/path.zip".into());
+            
+            let project_service = test_service_with_zip_provider(MockZipProvider::with_project(serialized_project));
+            
+            // When I import it
+            
+            let (project_id, import_report) = project_service.import_zip(path).await.unwrap();
+            
+            // It should return a new project, with nothing to report
+            
+            assert_ne!(project_id, ProjectID::nil());
+            assert!(import_report.is_empty());
 
-            let result = project_service.create_project(project_settings.clone(), false).await;
+            let project_provider = project_service.project_provider.read().await;
             
-            // It should return an appropriate error
+            let imported_project = project_provider.with_project(project_id, |_| {});
+            assert!(imported_project.is_some());
+            
+            let project_provider_call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(project_provider_call_tracker.add_project_calls, 1);
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+            let zip_provider = project_service.zip_provider.read().await;
+            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
+            assert_eq!(zip_provider_call_tracker.extract_calls, 1);
         }
         
-        /// Test creating a new project while one already exists with the same name
-        /// but the overwrite flag is set
+        /// Test that every stage of an import is reported in order
         #[tokio::test]
-        async fn test_overwrite_existing_project() {
-            // Given a project that already exists
-
-            let project_settings = default_test_project_settings();
-            let existing_project = Project::from_settings(project_settings.clone());
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
-
-            // When I try to create that project again with the overwrite flag set
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_with_progress() {
+            // Given a valid zip with one file
+            let serialized_project = default_serialized_project()
+                .with_files(HashMap::from([(PathBuf::from("data/test/function/load.mcfunction"), "say hi".to_string())]));
+            let project = Project::from_settings(default_test_project_settings())
+                .with_files([(PathBuf::from("data/test/function/load.mcfunction"), "say hi".to_string())]);
 
-            let result = project_service.create_project(project_settings.clone(), true).await;
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig::new(serialized_project.clone(), project));
 
-            // It should create the project
+            let project_service = test_service_with_zip_provider(MockZipProvider::with_project(serialized_project));
 
-            assert!(result.is_ok());
+            // When I import it with progress
+            let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let progress_log = progress.clone();
+            let (project_id, _) = project_service.import_zip_with_progress(
+                ZipPath::Single("test/file/path.zip".into()),
+                Box::new(move |stage| progress_log.lock().unwrap().push(stage)),
+            ).await.unwrap();
+
+            // Then each stage should be reported as it began, with indexing reported per file, and the project should be indexed
+            assert_eq!(*progress.lock().unwrap(), vec![
+                ImportProgress::Extracting,
+                ImportProgress::Parsing,
+                ImportProgress::Indexing(IndexProgress { files_indexed: 1, total_files: 1 }),
+                ImportProgress::Done,
+            ]);
+            assert!(project_service.project_index(project_id).is_some());
         }
 
-        /// Test thread safety when multiple threads try to create the same project
-        #[test]
-        fn test_create_project_concurrent() {
-            // Given valid project settings
+        /// Test that importing a pack without any resources is pointed out
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_empty_pack() {
+            // Given a zip with only a pack.mcmeta
+            let serialized_project = default_serialized_project();
 
-            // When I try to create that project multiple times on different threads
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(Project::from_settings(default_test_project_settings())),
+                fail_conversion: Default::default(),
+            });
 
-            // Only one project should be created with no other side effects`
+            let project_service = test_service_with_zip_provider(MockZipProvider::with_project(serialized_project));
 
-            // TODO: Implement test`
+            // When I import it
+            let (_, import_report) = project_service.import_zip(ZipPath::Single("test/file/path.zip".into())).await.unwrap();
+
+            // Then it should still be imported, but reported as empty
+            assert!(import_report.empty_pack);
+            assert!(!import_report.is_empty());
         }
-        
-        /// Test graceful error handling when the project provider returns an error 
+
         #[tokio::test]
-        async fn test_create_project_provider_failure() {
-            // Given an error from the repo
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_combined_project() {
+            // Given a valid zip pair
 
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_settings(
-                MockProjectProviderSettings {
-                    fail_calls: true,
-                    ..Default::default()
-                }
-            ));
+            let serialized_project = default_serialized_project();
+            let project = Project::from_settings(default_test_project_settings());
 
-            let project_settings = default_test_project_settings();
-            
-            // When I try to create a new project
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(project),
+                fail_conversion: Default::default(),
+            });
 
-            let result = project_service.create_project(project_settings.clone(), false).await;
+            let path = ZipPath::Combined {
+                data_path: "test/file/path_data.zip".into(),
+                resource_path: "test/file/path_resource.zip".into(),
+            };
 
-            // It should return an appropriate error
+            let project_service = test_service_with_zip_provider(MockZipProvider::with_project(serialized_project));
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
-        }
-    }
-    
-    mod open_project {
-        use super::*;
-        
-        /// Test opening a project
-        #[tokio::test]
-        async fn test_open_project() {
-            // Given a project that exists
+            // When I import them
 
-            let project_settings = default_test_project_settings();
-            let existing_project = Project::from_settings(project_settings.clone());
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project));
-            
-            // When I open it
+            let (project_id, _) = project_service.import_zip(path).await.unwrap();
 
-            let project_id = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await.unwrap();
-            
-            // It should be opened properly
+            // It should return a new project
+
+            assert_ne!(project_id, ProjectID::nil());
 
             let project_provider = project_service.project_provider.read().await;
 
-            let created_settings = project_provider.with_project(project_id, |project| {
-                let mut comparison_project = Project::from_settings(project_settings.clone());
-                comparison_project.set_id(project_id);
+            let imported_project = project_provider.with_project(project_id, |_| {});
+            assert!(imported_project.is_some());
 
-                assert_eq!(*project, comparison_project);
+            let project_provider_call_tracker = project_provider.call_tracker.read().unwrap();
+            assert_eq!(project_provider_call_tracker.add_project_calls, 1);
 
-                project.recreate_settings()
-            });
+            let zip_provider = project_service.zip_provider.read().await;
+            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
+            assert_eq!(zip_provider_call_tracker.extract_calls, 2);
+        }
 
-            assert!(*project_provider.is_project_open.read().unwrap());
+        /// Test that a pair of zips with no version in common is reported with a resource format that would fix it
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_combined_without_common_version() {
+            // Given a data pack for 1.21 and a resource pack for 1.20.1, using the default adapters
+            let pack = |project_type, format: u8| SerializedProject::with_name(
+                "Test Project".to_string(),
+                project_type,
+                PackInfo::new(PackData::new("test_pack".into(), format as u32, None), None, None, None, None),
+            );
+            let data_format = versions::get_datapack_format_for_version(*versions::V1_21).get_format_id();
+            let resource_format = versions::get_resourcepack_format_for_version(*versions::V1_20_1).get_format_id();
+
+            let project_service = ProjectService::new(
+                MockProjectProvider::default(),
+                MockZipProvider::with_projects_at([
+                    (PathBuf::from("test/file/path_data.zip"), pack(SerializedProjectType::Data, data_format)),
+                    (PathBuf::from("test/file/path_resource.zip"), pack(SerializedProjectType::Resource, resource_format)),
+                ]),
+                DefaultAdapterProvider::new(),
+            );
 
-            // Verify calls to the provider
-            let call_tracker = project_provider.call_tracker.read().unwrap();
-            assert_eq!(call_tracker.open_project_calls, 1);
+            // When I import them together
+            let result = project_service.import_zip(ZipPath::Combined {
+                data_path: "test/file/path_data.zip".into(),
+                resource_path: "test/file/path_resource.zip".into(),
+            }).await;
+
+            // Then the formats should be reported, along with the resource format for the data pack's version
+            let Err(ProjectServiceError::Zip(ZipError::NoCommonVersion { mismatch, suggested_resource_format })) = result else {
+                panic!("Expected no common version, got {:?}", result);
+            };
+            assert_eq!((mismatch.data_format, mismatch.resource_format), (data_format, resource_format));
+            assert!(mismatch.data_versions.contains(&*versions::V1_21));
+            assert!(mismatch.resource_versions.contains(&*versions::V1_20_1));
+            assert_eq!(suggested_resource_format, Some(versions::get_resourcepack_format_for_version(*versions::V1_21).get_format_id()));
         }
 
-        /// Test opening a project
+        /// Test importing a pack made for a version newer than the editor knows about
         #[tokio::test]
-        async fn test_open_project_invalid() {
-            // Given a project that exists, but is invalid
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_unknown_pack_format() {
+            // Given a data pack with a format past the newest known one, using the default adapters
+            let newest_format = versions::get_datapack_format_for_version(versions::latest()).get_format_id();
+            let serialized_project = SerializedProject::with_name(
+                "Test Project".to_string(),
+                SerializedProjectType::Data,
+                PackInfo::new(PackData::new("test_pack".into(), newest_format as u32 + 10, None), None, None, None, None),
+            );
 
-            // When I try to open it
+            let project_service = ProjectService::new(
+                MockProjectProvider::default(),
+                MockZipProvider::with_project(serialized_project),
+                DefaultAdapterProvider::new(),
+            );
 
-            // It should return an appropriate error
+            // When I import it
+            let (project_id, _) = project_service.import_zip(ZipPath::Single("test/file/path.zip".into())).await.unwrap();
 
-            // TODO: Implement test
+            // Then it should be imported for a version of the newest known format
+            let project_provider = project_service.project_provider.read().await;
+            let version = project_provider.with_project(project_id, |project| project.project_version().version).unwrap();
+            assert_eq!(versions::get_datapack_format_for_version(version).get_format_id(), newest_format);
         }
 
-        /// Test trying to open a project that doesn't exist
+        /// Test trying to import an invalid zip file
         #[tokio::test]
-        async fn test_open_project_non_existent() {
-            let project_service = default_test_service();
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_provider_error() {
+            // Given an error from the zip provider
 
-            // Given a project that does not exist
+            let serialized_project = default_serialized_project();
+            let project = Project::from_settings(default_test_project_settings());
 
-            let path = Path::new("nonexistent/path");
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(project),
+                fail_conversion: Default::default(),
+            });
+
+            let path = ZipPath::Single("test/file/path.zip".into());
+
+            let zip_provider = MockZipProvider::with_project(serialized_project).settings(MockZipProviderSettings {
+                fail_extract: true,
+                ..MockZipProviderSettings::default()
+            });
+            let project_service = test_service_with_zip_provider(zip_provider);
             
-            // When I try to open it
+            // When I try to import it
 
-            let result = project_service.open_project(path).await;
+            let result = project_service.import_zip(path).await;
             
             // It should return an appropriate error
 
             assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+            assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::Zipping(_)))));
         }
-        
-        /// Test trying to open a project which is already open
-        #[tokio::test]
-        async fn test_open_project_already_open() {
-            // Given a project which is already open
-
-            let project_settings = default_test_project_settings();
-            let existing_project = Project::from_settings(project_settings.clone());
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project));
-            
-            // When I try to open it again
 
-            let result = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await;
-
-            // It should return an appropriate error
-
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+        fn mixed_serialized_project() -> SerializedProject {
+            SerializedProject::with_name(
+                "Test Project".to_string(),
+                SerializedProjectType::Data,
+                PackInfo::new(
+                    PackData::new(
+                        "test_pack".into(),
+                        versions::get_datapack_format_for_version(*versions::V1_21).get_format_id() as u32,
+                        None
+                    ),
+                    None, None, None, None
+                )
+            ).with_files(HashMap::from([
+                (PathBuf::from("data/test/function/load.mcfunction"), "say hi".to_string()),
+                (PathBuf::from("assets/test/lang/en_us.json"), "{}".to_string()),
+            ]))
         }
-        
-        /// Test thread safety when multiple threads try to open the same project
-        #[tokio::test]
-        async fn test_open_project_concurrent() {
-            // Given a project that exists
-            
-            // When I try to open it multiple times on different threads
-            
-            // Only one should succeed with no other side effects
 
-            // TODO: Implement test
+        /// Test that a single zip with both data and assets is imported as a combined project
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_mixed_zip_as_combined() {
+            // Given a single zip with both a data pack and a resource pack in it, using the default adapters
+            let project_service = ProjectService::new(
+                MockProjectProvider::default(),
+                MockZipProvider::with_project(mixed_serialized_project()),
+                DefaultAdapterProvider::new(),
+            );
+
+            // When I import it
+            let (project_id, _) = project_service.import_zip(ZipPath::Single("test/file/path.zip".into())).await.unwrap();
+
+            // Then it should be a combined project for the version of its data format, with the files of both packs
+            let project_provider = project_service.project_provider.read().await;
+            let (project_type, version, files) = project_provider.with_project(project_id, |project| {
+                (project.project_type(), project.project_version().version, project.files().keys().cloned().collect::<Vec<_>>())
+            }).unwrap();
+
+            assert_eq!(project_type, ProjectType::Combined);
+            assert_eq!(version, *versions::V1_21);
+            assert_eq!(files, vec![
+                PathBuf::from("assets/test/lang/en_us.json"),
+                PathBuf::from("data/test/function/load.mcfunction"),
+            ]);
         }
 
-        /// Test graceful error handling when the provider returns an error
+        /// Test forcing one pack of a mixed zip to be imported on its own
         #[tokio::test]
-        async fn test_open_project_provider_failure() {
-            // Given an error from the repo
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_mixed_zip_as_single_pack() {
+            // Given a single zip with both a data pack and a resource pack in it, using the default adapters
+            let project_service = ProjectService::new(
+                MockProjectProvider::default(),
+                MockZipProvider::with_project(mixed_serialized_project()),
+                DefaultAdapterProvider::new(),
+            );
 
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_settings(
-                MockProjectProviderSettings {
-                    fail_calls: true,
-                    ..Default::default()
-                }
-            ));
+            // When I import only its resource pack
+            let (project_id, _) = project_service.import_zip_as(Path::new("test/file/path.zip"), PackHalf::Resource).await.unwrap();
 
-            let project_settings = default_test_project_settings();
+            // Then it should be a resource pack without any of the data pack's files
+            let project_provider = project_service.project_provider.read().await;
+            let (project_type, files) = project_provider.with_project(project_id, |project| {
+                (project.project_type(), project.files().keys().cloned().collect::<Vec<_>>())
+            }).unwrap();
 
-            // When I try to open a project
+            assert_eq!(project_type, ProjectType::ResourcePack);
+            assert_eq!(files, vec![PathBuf::from("assets/test/lang/en_us.json")]);
+        }
 
-            let result = project_service.open_project(project_settings.path().as_ref().unwrap().as_path()).await;
+        /// Test forcing a pack type a zip doesn't have
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_import_missing_pack() {
+            // Given a zip with only a data pack in it
+            let serialized_project = default_serialized_project()
+                .with_files(HashMap::from([(PathBuf::from("data/test/function/load.mcfunction"), "say hi".to_string())]));
+            let project_service = test_service_with_zip_provider(MockZipProvider::with_project(serialized_project));
 
-            // It should return an appropriate error
+            // When I try to import it as a resource pack
+            let result = project_service.import_zip_as(Path::new("test/file/path.zip"), PackHalf::Resource).await;
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+            // Then it should be refused
+            assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::MissingPack(PackHalf::Resource)))));
         }
     }
-    
-    mod close_project {
+
+    mod rename_namespace {
+        use std::collections::BTreeMap;
+        use crate::data::domain::project::NamespaceRenameError;
         use super::*;
-        
-        /// Test closing a project
-        #[tokio::test]
-        async fn test_close_project() {
-            // Given an open project
 
-            let project_settings = default_test_project_settings();
-            let existing_project = Project::from_settings(project_settings.clone());
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project.clone()));
-            
-            // When I close it
+        fn forked_project() -> Project {
+            Project::from_settings(default_test_project_settings()).with_files([
+                (PathBuf::from("data/upstream/function/load.mcfunction"), "function upstream:setup\nexecute if entity @s run function upstream:tick".to_string()),
+                (PathBuf::from("data/upstream/function/setup.mcfunction"), "scoreboard objectives add upstream_score dummy".to_string()),
+                (PathBuf::from("data/minecraft/tags/function/load.json"), r#"{"values": ["upstream:load", "my_upstream:load"]}"#.to_string()),
+                (PathBuf::from("data/other/recipe/gem.json"), r#"{"type": "minecraft:crafting_shapeless", "result": {"id": "upstream:gem"}}"#.to_string()),
+            ])
+        }
 
-            let result = project_service.close_project(*existing_project.id()).await;
+        /// Test renaming a custom namespace
+        #[tokio::test]
+        async fn test_rename_namespace() {
+            // Given a project with a custom namespace which is referenced from other files
+            let project = forked_project();
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
 
-            // It should be closed properly
+            // When I rename the namespace
+            let moved = project_service.rename_namespace(project_id, "upstream", "fork").await.unwrap();
 
-            assert!(result.is_ok());
+            // Then its files should be moved
+            assert_eq!(moved, 2);
 
             let project_provider = project_service.project_provider.read().await;
-            assert!(!*project_provider.is_project_open.read().unwrap());
+            let (files, has_unsaved_changes) = project_provider.with_project(project_id, |project| {
+                (project.files().clone(), *project.has_unsaved_changes())
+            }).unwrap();
 
-            // Verify calls to the provider
-            let call_tracker = project_provider.call_tracker.read().unwrap();
-            assert_eq!(call_tracker.close_project_calls, 1);
+            assert_eq!(files, BTreeMap::from([
+                (PathBuf::from("data/fork/function/load.mcfunction"), "function fork:setup\nexecute if entity @s run function fork:tick".to_string()),
+                (PathBuf::from("data/fork/function/setup.mcfunction"), "scoreboard objectives add upstream_score dummy".to_string()),
+                (PathBuf::from("data/minecraft/tags/function/load.json"), r#"{"values": ["fork:load", "my_upstream:load"]}"#.to_string()),
+                (PathBuf::from("data/other/recipe/gem.json"), r#"{"type": "minecraft:crafting_shapeless", "result": {"id": "fork:gem"}}"#.to_string()),
+            ]));
+
+            // And the project should need saving
+            assert!(has_unsaved_changes);
         }
 
-        /// Test trying to close a project which has unsaved changes
+        /// Test that the vanilla namespace is protected
         #[tokio::test]
-        async fn test_close_project_unsaved_changes() {
-            // Given a project with unsaved changes
+        async fn test_rename_vanilla_namespace() {
+            // Given a project which overrides vanilla files
+            let project = forked_project();
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project.clone()));
 
-            let project_settings = default_test_project_settings();
-            let existing_project = Project::with_unsaved_changes(project_settings.clone());
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(existing_project.clone()));
-            
-            // When I try to close it
+            // When I try to rename the vanilla namespace, or rename to it
+            let from_vanilla = project_service.rename_namespace(project_id, "minecraft", "fork").await;
+            let to_vanilla = project_service.rename_namespace(project_id, "upstream", "minecraft").await;
 
-            let result = project_service.close_project(*existing_project.id()).await;
-            
-            // It should return an appropriate error
+            // Then both should be refused, leaving the files untouched
+            assert!(matches!(from_vanilla, Err(ProjectServiceError::RenameNamespace(NamespaceRenameError::Vanilla))));
+            assert!(matches!(to_vanilla, Err(ProjectServiceError::RenameNamespace(NamespaceRenameError::Vanilla))));
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::CannotCloseUnsavedChanges)));
+            let project_provider = project_service.project_provider.read().await;
+            let files = project_provider.with_project(project_id, |project| project.files().clone()).unwrap();
+            assert_eq!(&files, project.files());
         }
 
-        /// Test trying to close a project which is not open
+        /// Test renaming onto a namespace which is already in use
         #[tokio::test]
-        async fn test_close_project_not_open() {
-            // Given a project which is not open
+        async fn test_rename_namespace_collision() {
+            // Given a project with two custom namespaces
+            let project = forked_project();
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
 
-            let project_settings = default_test_project_settings();
-            let existing_project = Project::from_settings(project_settings.clone());
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(existing_project.clone()));
-            
-            // When I try to close it
+            // When I rename one to the other
+            let result = project_service.rename_namespace(project_id, "upstream", "other").await;
 
-            let result = project_service.close_project(*existing_project.id()).await;
+            // Then it should be refused
+            assert!(matches!(result, Err(ProjectServiceError::RenameNamespace(NamespaceRenameError::NamespaceAlreadyExists(_)))));
+        }
 
-            // It should return an appropriate error
+        /// Test renaming a namespace which isn't in the project
+        #[tokio::test]
+        async fn test_rename_missing_namespace() {
+            // Given a project
+            let project = forked_project();
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+            // When I rename a namespace it doesn't have
+            let result = project_service.rename_namespace(project_id, "missing", "fork").await;
+
+            // Then it should be refused
+            assert!(matches!(result, Err(ProjectServiceError::RenameNamespace(NamespaceRenameError::NamespaceNotFound(_)))));
         }
+    }
 
-        /// Test trying to close a project which does not exist
+    mod set_pack_icon {
+        use crate::data::domain::pack_icon::{self, PackIconError, PackIconWarning};
+        use crate::data::serialization::project::PACK_ICON_FILE;
+        use super::*;
+
+        /// Test replacing the icon with one the game would stretch
         #[tokio::test]
-        async fn test_close_project_nonexistent() {
-            let project_service = default_test_service();
+        async fn test_set_pack_icon() {
+            // Given a project
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
 
-            // Given a project which does not exist
+            // When I set its icon to a PNG which isn't square
+            let data = pack_icon::test_png(128, 64);
+            let warnings = project_service.set_pack_icon(project_id, data.clone()).await.unwrap();
 
-            let project_id = Project::generate_test_id();
+            // Then it should be kept, with a warning
+            assert_eq!(warnings, vec![PackIconWarning::NotSquare { width: 128, height: 64 }]);
 
-            // When I try to close it
+            let icon = project_service.pack_icon(project_id).await.unwrap().unwrap();
+            assert_eq!(icon.data(), data);
 
-            let result = project_service.close_project(project_id).await;
+            // And the project should need saving
+            let project_provider = project_service.project_provider.read().await;
+            assert!(project_provider.with_project(project_id, |project| *project.has_unsaved_changes()).unwrap());
+        }
 
-            // It should return an appropriate error
+        /// Test that an image which isn't a PNG is refused
+        #[tokio::test]
+        async fn test_set_pack_icon_not_png() {
+            // Given a project
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::ProjectDoesNotExist)));
-        }
+            // When I set its icon to something other than a PNG
+            let result = project_service.set_pack_icon(project_id, b"GIF89a".to_vec()).await;
 
-        /// Test thread safety when multiple threads try to close the same project
-        #[test]
-        fn test_close_project_concurrent() {
-            // Given an open project
+            // Then it should be refused, leaving the project without an icon
+            assert!(matches!(result, Err(ProjectServiceError::PackIcon(PackIconError::NotPng))));
+            assert!(project_service.pack_icon(project_id).await.unwrap().is_none());
+        }
 
-            // When I try to close it multiple times on different threads
+        /// Test previewing the icon of a zip which hasn't been imported
+        #[tokio::test]
+        async fn test_zip_pack_icon() {
+            // Given a zip with an icon
+            let data = pack_icon::test_png(64, 64);
+            let project_service = test_service_with_zip_provider(MockZipProvider {
+                entries: [(PACK_ICON_FILE.to_string(), data.clone())].into_iter().collect(),
+                ..MockZipProvider::default()
+            });
 
-            // Only one should succeed with no other side effects
+            // When I read its icon
+            let icon = project_service.zip_pack_icon(Path::new("pack.zip")).await.unwrap();
 
-            // TODO: Implement test
+            // Then it should be read without extracting the pack
+            assert_eq!(icon.unwrap().data(), data);
+            assert_eq!(project_service.zip_provider.read().await.call_tracker.read().unwrap().extract_calls, 0);
         }
 
-        /// Test graceful error handling when the provider returns an error
+        /// Test that a zip without an icon has nothing to preview
         #[tokio::test]
-        async fn test_close_project_provider_failure() {
-            // Given an error from the repo
+        async fn test_zip_pack_icon_missing() {
+            // Given a zip without an icon
+            let project_service = test_service_with_zip_provider(MockZipProvider::default());
 
-            let project_settings = default_test_project_settings();
-            let project = Project::from_settings(project_settings.clone());
+            // When I read its icon
+            let icon = project_service.zip_pack_icon(Path::new("pack.zip")).await.unwrap();
 
-            let mut project_provider = MockProjectProvider::with_open_project(project.clone());
-            project_provider.settings = MockProjectProviderSettings {
-                fail_calls: true,
-                ..Default::default()
-            };
+            // Then there should be none
+            assert!(icon.is_none());
+        }
+    }
 
-            let project_service = test_service_with_project_provider(project_provider);
+    mod rewrite_references {
+        use std::collections::BTreeMap;
+        use crate::data::domain::project::ReferenceRewrite;
+        use crate::data::domain::resource::resource::ResourceLocation;
+        use super::*;
 
-            // When I try to close a project
+        /// Test a rewrite which only changes some of the references
+        #[tokio::test]
+        async fn test_rewrite_references() {
+            // Given a project referring to functions and items in a few namespaces
+            let project = Project::from_settings(default_test_project_settings()).with_files([
+                (PathBuf::from("data/gems/function/load.mcfunction"), "function gems:setup\ngive @s gems:ruby\ngive @s minecraft:diamond".to_string()),
+                (PathBuf::from("data/gems/recipe/ruby.json"), r#"{"result": {"id": "gems:ruby"}}"#.to_string()),
+                (PathBuf::from("data/minecraft/tags/function/load.json"), r#"{"values": ["gems:load"]}"#.to_string()),
+            ]);
+            let project_id = *project.id();
+            let project_service = ProjectService::new(MockProjectProvider::with_project(project), MockZipProvider::default(), DefaultAdapterProvider::new());
 
-            let result = project_service.close_project(*project.id()).await;
+            // When I rewrite only the references to items in the gems namespace
+            let rewriter = |location: &ResourceLocation| (location.to_string() == "gems:ruby")
+                .then(|| ResourceLocation::new("gems", "red_gem").unwrap());
+            let summary = project_service.rewrite_references(project_id, &rewriter).await.unwrap();
 
-            // It should return an appropriate error
+            // Then only those references should change, wherever they are written
+            assert_eq!(summary, ReferenceRewrite { files_changed: 2, references_rewritten: 2 });
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+            let project_provider = project_service.project_provider.read().await;
+            let files = project_provider.with_project(project_id, |project| project.files().clone()).unwrap();
+            assert_eq!(files, BTreeMap::from([
+                (PathBuf::from("data/gems/function/load.mcfunction"), "function gems:setup\ngive @s gems:red_gem\ngive @s minecraft:diamond".to_string()),
+                (PathBuf::from("data/gems/recipe/ruby.json"), r#"{"result": {"id": "gems:red_gem"}}"#.to_string()),
+                (PathBuf::from("data/minecraft/tags/function/load.json"), r#"{"values": ["gems:load"]}"#.to_string()),
+            ]));
+        }
+
+        /// Test that a read-only project isn't rewritten
+        #[tokio::test]
+        async fn test_rewrite_references_read_only() {
+            // Given a project opened read-only
+            let mut project = Project::from_settings(default_test_project_settings())
+                .with_files([(PathBuf::from("data/gems/function/load.mcfunction"), "function gems:setup".to_string())]);
+            project.set_read_only(true);
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
+
+            // When I rewrite its references
+            let result = project_service.rewrite_references(project_id, &|_: &ResourceLocation| ResourceLocation::new("other", "setup").ok()).await;
+
+            // Then it should be refused
+            assert!(matches!(result, Err(ProjectServiceError::ReadOnly)));
         }
     }
-    
-    mod save_project {
+
+    mod move_file {
         use super::*;
 
-        /// Test saving a project
+        /// Test moving a file within a project
         #[tokio::test]
-        async fn test_save_project() {
-            // Given a project with unsaved changes
-
-            let project_settings = default_test_project_settings();
-            let project = Project::with_unsaved_changes(project_settings.clone());
+        async fn test_move_file() {
+            // Given a project with a function
+            let project = Project::from_settings(default_test_project_settings())
+                .with_files([(PathBuf::from("data/gems/function/load.mcfunction"), "say loaded".to_string())]);
             let project_id = *project.id();
-            let project_service = test_service_with_project_provider(MockProjectProvider::with_open_project(project));
-            
-            // When I save it
-
-            let result = project_service.save_project(project_id).await;
-            
-            // It should be saved
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
 
-            assert!(result.is_ok());
-            assert_eq!(result.unwrap().as_path(), Path::new("test/file/path"));
+            // When I move it
+            project_service.move_file(project_id, Path::new("data/gems/function/load.mcfunction"), Path::new("data/gems/function/init.mcfunction")).await.unwrap();
 
-            // Verify calls to the provider
+            // Then it should be at its new path, as an unsaved change
             let project_provider = project_service.project_provider.read().await;
-            let call_tracker = project_provider.call_tracker.read().unwrap();
-            assert_eq!(call_tracker.save_project_calls, 1);
+            let (files, unsaved) = project_provider.with_project(project_id, |project| (project.files().clone(), *project.has_unsaved_changes())).unwrap();
+            assert_eq!(files.keys().collect::<Vec<_>>(), vec![Path::new("data/gems/function/init.mcfunction")]);
+            assert!(unsaved);
         }
 
-        /// Test thread safety when multiple threads try to save the same project
-        #[test]
-        fn test_save_project_concurrent() {
-            // Given a project
-
-            // When I try to save it multiple times on different threads
+        /// Test that a file saved by the editor is taken in without counting as a change
+        #[tokio::test]
+        async fn test_file_saved() {
+            // Given a project with a function
+            let project = Project::from_settings(default_test_project_settings())
+                .with_files([(PathBuf::from("data/gems/function/load.mcfunction"), "say loaded".to_string())]);
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
+            let path = Path::new("data/gems/function/load.mcfunction");
+            project_service.adapter_provider.read().await.parse_cache().get_or_parse(path, b"say loaded", || None);
 
-            // Only one should succeed with no other side effects
+            // When the editor saves new contents for it, with a byte order mark
+            project_service.file_saved(project_id, path, "say hello".to_string(), true).await.unwrap();
 
-            // TODO: Implement test
+            // Then the project should have them and the mark, with nothing left to save, and its old parse forgotten
+            assert_eq!(project_service.adapter_provider.read().await.parse_cache().stats().entries, 0);
+            let project_provider = project_service.project_provider.read().await;
+            let (contents, bom, unsaved) = project_provider.with_project(project_id, |project| {
+                (project.files()[path].clone(), project.has_bom(path), *project.has_unsaved_changes())
+            }).unwrap();
+            assert_eq!(contents, "say hello");
+            assert!(bom);
+            assert!(!unsaved);
         }
+    }
 
-        /// Test graceful error handling when the provider returns an error
-        #[tokio::test]
-        async fn test_save_project_provider_failure() {
-            // Given an error from the repo
+    mod index_project {
+        use std::str::FromStr;
+        use crate::data::domain::project_index::IndexProgress;
+        use crate::data::domain::resource::resource::ResourceLocation;
+        use super::*;
 
-            let project_settings = default_test_project_settings();
-            let project = Project::with_unsaved_changes(project_settings.clone());
+        /// Test that indexing completes and the index can be queried afterwards
+        #[tokio::test]
+        async fn test_index_project() {
+            // Given an open project with functions calling each other
+            let project = Project::from_settings(default_test_project_settings()).with_files([
+                (PathBuf::from("data/example/function/load.mcfunction"), "function example:setup".to_string()),
+                (PathBuf::from("data/example/function/setup.mcfunction"), "say hi".to_string()),
+            ]);
             let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
+            assert!(project_service.project_index(project_id).is_none());
 
-            let mut project_provider = MockProjectProvider::with_open_project(project.clone());
-            project_provider.settings = MockProjectProviderSettings {
-                fail_calls: true,
-                ..Default::default()
-            };
+            // When I index it
+            let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let reported = progress.clone();
+            project_service.index_project(project_id, Box::new(move |update| reported.lock().unwrap().push(update))).await.unwrap();
 
-            let project_service = test_service_with_project_provider(project_provider);
+            // Then every file should have been reported, and the index should be available
+            assert_eq!(progress.lock().unwrap().last(), Some(&IndexProgress { files_indexed: 2, total_files: 2 }));
 
-            // When I try to save a project
+            let index = project_service.project_index(project_id).unwrap();
+            let setup = ResourceLocation::from_str("example:setup").unwrap();
+            assert_eq!(index.definitions("function", &setup), [PathBuf::from("data/example/function/setup.mcfunction")]);
+            assert_eq!(index.call_graph().callers(&setup).len(), 1);
+        }
 
-            let result = project_service.save_project(project_id).await;
+        /// Test indexing a project which isn't open
+        #[tokio::test]
+        async fn test_index_missing_project() {
+            // Given no open project
+            let project_service = test_service_with_project_provider(MockProjectProvider::default());
 
-            // It should return an appropriate error
+            // When I try to index one
+            let result = project_service.index_project(ProjectID::nil(), Box::new(|_| {})).await;
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::RepoError(_))));
+            // Then it should fail
+            assert!(matches!(result, Err(ProjectServiceError::ProjectDoesNotExist)));
         }
     }
-    
-    mod import_zip {
-        use crate::services::project_service::{ZipError, ZipPath};
-        use super::*;
 
-        /// Test importing a datapack from a zip as a new project
-        #[tokio::test]
-        #[serial_test::serial(project_service_zip)]
-        async fn test_import_from_zip() {
-            // Given a valid zip
+    mod find_in_project {
+        use crate::data::domain::search::{SearchCancellation, SearchMatch, SearchQuery, SearchSummary};
+        use super::*;
 
-            let serialized_project = default_serialized_project();
-            let project = Project::from_settings(default_test_project_settings());
-            
-            MockProjectAdapter::reset_config();
-            MockProjectAdapter::set_config(ProjectAdapterConfig {
-                serialized_project: Some(serialized_project.clone()),
-                project: Some(project),
-                fail_conversion: Default::default(),
-            });
-            
-            let path = ZipPath::Single("test/file/path.zip".into());
-            
-            let project_service = test_service_with_zip_provider(MockZipProvider::with_project(serialized_project));
-            
-            // When I import it
-            
-            let project_id = project_service.import_zip(path).await.unwrap();
-            
-            // It should return a new project
-            
-            assert_ne!(project_id, ProjectID::nil());
+        fn project_with_functions(count: usize) -> Project {
+            Project::from_settings(default_test_project_settings()).with_files((0..count).map(|i| (
+                PathBuf::from(format!("data/example/function/f{:02}.mcfunction", i)),
+                "say diamond".to_string(),
+            )))
+        }
 
-            let project_provider = project_service.project_provider.read().await;
-            
-            let imported_project = project_provider.with_project(project_id, |_| {});
-            assert!(imported_project.is_some());
-            
-            let project_provider_call_tracker = project_provider.call_tracker.read().unwrap();
-            assert_eq!(project_provider_call_tracker.add_project_calls, 1);
+        /// Test that matches are reported file by file, as each is searched
+        #[tokio::test]
+        async fn test_find_in_project_streams_matches() {
+            // Given an open project where every function matches
+            let project = project_with_functions(10);
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
+
+            // When I search it
+            let batches = Arc::new(std::sync::Mutex::new(Vec::<Vec<SearchMatch>>::new()));
+            let received = batches.clone();
+            let summary = project_service.find_in_project(
+                project_id,
+                SearchQuery::new("Diamond"),
+                Box::new(move |matches| received.lock().unwrap().push(matches)),
+                SearchCancellation::new(),
+            ).await.unwrap();
+
+            // Then each file's matches should have arrived separately
+            let batches = batches.lock().unwrap();
+            assert_eq!(batches.len(), 10);
+            assert!(batches.iter().all(|batch| batch.len() == 1));
+            assert_eq!(summary, SearchSummary { files_searched: 10, total_files: 10, matches: 10, cancelled: false });
+        }
 
-            let zip_provider = project_service.zip_provider.read().await;
-            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
-            assert_eq!(zip_provider_call_tracker.extract_calls, 1);
+        /// Test that cancelling stops the search from going through the rest of the files
+        #[tokio::test]
+        async fn test_find_in_project_cancelled() {
+            // Given an open project with more functions than are searched at once
+            let project = project_with_functions(50);
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
+
+            // When I cancel the search as soon as the first matches arrive
+            let cancellation = SearchCancellation::new();
+            let cancel = cancellation.clone();
+            let batches = Arc::new(std::sync::Mutex::new(0));
+            let received = batches.clone();
+            let summary = project_service.find_in_project(
+                project_id,
+                SearchQuery::new("diamond"),
+                Box::new(move |_| {
+                    *received.lock().unwrap() += 1;
+                    cancel.cancel();
+                }),
+                cancellation,
+            ).await.unwrap();
+
+            // Then no more matches should be reported, and the rest of the files left unsearched
+            assert_eq!(*batches.lock().unwrap(), 1);
+            assert!(summary.cancelled);
+            assert_eq!(summary.files_searched, 1);
+            assert_eq!(summary.total_files, 50);
         }
-        
+
+        /// Test searching a project which isn't open
         #[tokio::test]
-        #[serial_test::serial(project_service_zip)]
-        async fn test_import_combined_project() {
-            // Given a valid zip pair
+        async fn test_find_in_missing_project() {
+            // Given no open project
+            let project_service = test_service_with_project_provider(MockProjectProvider::default());
 
-            let serialized_project = default_serialized_project();
-            let project = Project::from_settings(default_test_project_settings());
+            // When I try to search one
+            let result = project_service.find_in_project(ProjectID::nil(), SearchQuery::new("diamond"), Box::new(|_| {}), SearchCancellation::new()).await;
 
-            MockProjectAdapter::reset_config();
-            MockProjectAdapter::set_config(ProjectAdapterConfig {
-                serialized_project: Some(serialized_project.clone()),
-                project: Some(project),
-                fail_conversion: Default::default(),
-            });
+            // Then it should fail
+            assert!(matches!(result, Err(ProjectServiceError::ProjectDoesNotExist)));
+        }
+    }
 
-            let path = ZipPath::Combined {
-                data_path: "test/file/path_data.zip".into(),
-                resource_path: "test/file/path_resource.zip".into(),
-            };
+    mod lint_project {
+        use crate::services::lint_service::{LintRule, LintService};
+        use super::*;
 
-            let project_service = test_service_with_zip_provider(MockZipProvider::with_project(serialized_project));
+        /// Test that the linter sees the open project
+        #[tokio::test]
+        async fn test_lint_project() {
+            // Given an open project with a function nothing calls
+            let project = Project::from_settings(default_test_project_settings()).with_files([
+                (PathBuf::from("data/example/function/unused.mcfunction"), "say hi".to_string()),
+            ]);
+            let project_id = *project.id();
+            let project_service = test_service_with_project_provider(MockProjectProvider::with_project(project));
 
-            // When I import them
+            // When I lint it
+            let diagnostics = project_service.lint_project(project_id, &LintService::new()).await.unwrap();
 
-            let project_id = project_service.import_zip(path).await.unwrap();
+            // Then the function should be reported
+            assert!(diagnostics.iter().any(|diagnostic| diagnostic.rule == LintRule::UnusedFunction
+                && diagnostic.path == Path::new("data/example/function/unused.mcfunction")));
+        }
+    }
 
-            // It should return a new project
+    mod with_adapters {
+        use crate::data::domain::compatibility::{FeatureVersion, PackFeature};
+        use crate::data::domain::resource::resource::ResourceCategory;
+        use crate::repositories::adapter_repo::AdapterRegistrationError;
+        use super::*;
 
-            assert_ne!(project_id, ProjectID::nil());
+        #[derive(Debug, PartialEq)]
+        struct Schematic(String);
+        #[derive(Debug, PartialEq)]
+        struct SerializedSchematic(Vec<u8>);
 
-            let project_provider = project_service.project_provider.read().await;
+        struct SchematicAdapter;
+        #[async_trait::async_trait]
+        impl Adapter<SerializedSchematic, Schematic> for SchematicAdapter {
+            type ConversionError = Infallible;
+            type SerializedConversionError = Infallible;
 
-            let imported_project = project_provider.with_project(project_id, |_| {});
-            assert!(imported_project.is_some());
+            async fn deserialize<AdpProvider: AdapterProvider + ?Sized>(serialized: AdapterInput<'_, SerializedSchematic>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<Schematic, Self::ConversionError> {
+                Ok(Schematic(String::from_utf8_lossy(&serialized.0).into_owned()))
+            }
 
-            let project_provider_call_tracker = project_provider.call_tracker.read().unwrap();
-            assert_eq!(project_provider_call_tracker.add_project_calls, 1);
+            async fn serialize<AdpProvider: AdapterProvider + ?Sized>(domain: AdapterInput<'_, Schematic>, _context: AdapterProviderContext<'_, AdpProvider>) -> Result<SerializedSchematic, Self::SerializedConversionError> {
+                Ok(SerializedSchematic(domain.0.as_bytes().to_vec()))
+            }
 
-            let zip_provider = project_service.zip_provider.read().await;
-            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
-            assert_eq!(zip_provider_call_tracker.extract_calls, 2);
+            fn min_version() -> Option<FeatureVersion> {
+                Some(FeatureVersion { feature: PackFeature::Directory(ResourceCategory::Data, "schematic"), since: *versions::V1_21_5 })
+            }
         }
 
-        /// Test trying to import an invalid zip file
+        /// Test that an adapter for a new file type can be registered and then used
         #[tokio::test]
-        #[serial_test::serial(project_service_zip)]
-        async fn test_import_provider_error() {
-            // Given an error from the zip provider
-
-            let serialized_project = default_serialized_project();
-            let project = Project::from_settings(default_test_project_settings());
-
-            MockProjectAdapter::reset_config();
-            MockProjectAdapter::set_config(ProjectAdapterConfig {
-                serialized_project: Some(serialized_project.clone()),
-                project: Some(project),
-                fail_conversion: Default::default(),
-            });
+        async fn test_register_custom_adapter() {
+            // Given an adapter for a file type with no default adapter
+            // When I register it through the builder
+            let project_service = ProjectService::with_adapters(
+                MockProjectProvider::default(),
+                MockZipProvider::default(),
+                DefaultAdapterProvider::new(),
+                |registrations| { registrations.register::<SchematicAdapter, SerializedSchematic, Schematic>(); },
+            ).unwrap();
+
+            // Then it should be used to serialize that type
+            let adapter_provider = project_service.adapter_provider.read().await;
+            let context = AdapterProviderContext::new(project_service.adapter_provider.read().await);
+            let serialized: SerializedSchematic = adapter_provider.serialize(AdapterInput::new(&Schematic("house".to_string())), context).await.unwrap();
+
+            assert_eq!(serialized, SerializedSchematic(b"house".to_vec()));
+        }
 
-            let path = ZipPath::Single("test/file/path.zip".into());
+        /// Test that a file type an adapter declares as newer raises the version the project requires
+        #[tokio::test]
+        async fn test_adapter_raises_required_version() {
+            // Given a project using the 1.21 directory names, with an adapter for schematics from 1.21.5
+            let project = Project::from_settings(default_test_project_settings()).with_files([
+                (PathBuf::from("data/test/function/load.mcfunction"), "say hi".to_string()),
+                (PathBuf::from("data/test/schematic/house.json"), "{}".to_string()),
+            ]);
+            let project_id = *project.id();
+            let project_service = ProjectService::with_adapters(
+                MockProjectProvider::with_project(project),
+                MockZipProvider::default(),
+                DefaultAdapterProvider::new(),
+                |registrations| { registrations.register::<SchematicAdapter, SerializedSchematic, Schematic>(); },
+            ).unwrap();
+
+            // When I get its stats
+            let stats = project_service.project_stats(project_id).await.unwrap();
+
+            // Then the schematic should raise the required version from 1.21
+            let required_version = stats.required_version.unwrap();
+            assert_eq!(required_version.version, *versions::V1_21_5);
+            assert_eq!(required_version.path, PathBuf::from("data/test/schematic/house.json"));
+        }
 
-            let zip_provider = MockZipProvider::with_project(serialized_project).settings(MockZipProviderSettings {
-                fail_extract: true,
-                ..MockZipProviderSettings::default()
-            });
-            let project_service = test_service_with_zip_provider(zip_provider);
-            
-            // When I try to import it
+        /// Test that registering over a default adapter is refused
+        #[tokio::test]
+        async fn test_register_conflicting_adapter() {
+            // Given an adapter for a conversion the default project adapter already covers
+            // When I register it through the builder
+            let result = ProjectService::with_adapters(
+                MockProjectProvider::default(),
+                MockZipProvider::default(),
+                DefaultAdapterProvider::new(),
+                |registrations| {
+                    registrations
+                        .register::<MockProjectAdapter, SerializedProjectData, Project>()
+                        .register::<SchematicAdapter, SerializedSchematic, Schematic>()
+                        .register::<SchematicAdapter, SerializedSchematic, Schematic>();
+                },
+            );
 
-            let result = project_service.import_zip(path).await;
-            
-            // It should return an appropriate error
+            // Then both conflicts should be reported
+            let Err(AdapterRegistrationError(conflicts)) = result else {
+                panic!("Expected the conflicting adapters to be refused");
+            };
 
-            assert!(result.is_err());
-            assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::Zipping(_)))));
+            assert_eq!(conflicts.len(), 2);
+            assert!(conflicts[0].existing_is_default);
+            assert!(conflicts[0].existing.ends_with("ProjectAdapter"));
+            assert!(!conflicts[1].existing_is_default);
         }
     }
-    
+
     mod export_zip {
-        use crate::services::project_service::{ProjectZipData, ZipError, ZipPath};
+        use std::collections::HashMap;
+        use crate::data::domain::compatibility::{CompatibilityIssue, PackFeature};
+        use crate::services::project_service::{DefaultAdapterProvider, DefaultProjectProvider, ExportDiff, PlannedArchive, ProjectZipData, ZipError, ZipPath};
         use super::*;
 
         /// Test exporting a single-typed project to a zip
@@ -1417,6 +3825,8 @@ mod test {
             let project_zip_data = ProjectZipData {
                 project_id: *project.id(),
                 path,
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
             };
 
             // When I export it
@@ -1467,6 +3877,8 @@ mod test {
             let project_zip_data = ProjectZipData {
                 project_id: *project.id(),
                 path,
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
             };
 
             // When I export it
@@ -1514,6 +3926,8 @@ mod test {
             let project_zip_data = ProjectZipData {
                 project_id: *project.id(),
                 path,
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
             };
 
             // When I export it
@@ -1555,6 +3969,8 @@ mod test {
             let project_zip_data = ProjectZipData {
                 project_id: *project.id(),
                 path,
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
             };
 
             // When I export it
@@ -1598,6 +4014,8 @@ mod test {
             let project_zip_data = ProjectZipData {
                 project_id: *project.id(),
                 path,
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
             };
 
             // When I try to export that zip again
@@ -1642,6 +4060,8 @@ mod test {
             let project_zip_data = ProjectZipData {
                 project_id: *project.id(),
                 path,
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
             };
 
             // When I try to overwrite that zip
@@ -1684,6 +4104,8 @@ mod test {
             let project_zip_data = ProjectZipData {
                 project_id: *project.id(),
                 path,
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
             };
 
             // When I try to export a project
@@ -1696,6 +4118,366 @@ mod test {
             assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::Zipping(_)))));
         }
         
+        /// Test that a failed update leaves the existing archive alone
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_update_export_error_keeps_archive() {
+            // Given an error from the zip provider
+
+            let serialized_project = default_serialized_project();
+            let project = Project::from_settings(default_test_project_settings());
+
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(project.clone()),
+                fail_conversion: Default::default(),
+            });
+
+            let zip_provider = MockZipProvider::with_project(serialized_project).settings(MockZipProviderSettings {
+                fail_zip: true,
+                ..MockZipProviderSettings::default()
+            });
+
+            let project_service = test_service_with_project_zip_provider(
+                MockProjectProvider::with_project(project.clone()),
+                zip_provider,
+            );
+
+            // When I try to update an earlier export
+
+            let result = project_service.update_export(ProjectZipData {
+                project_id: *project.id(),
+                path: ZipPath::Single("test/file/path.zip".into()),
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
+            }).await;
+
+            // The error should be returned, without deleting the archive that was already there
+
+            assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::Zipping(_)))));
+
+            let zip_provider = project_service.zip_provider.read().await;
+            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
+            assert_eq!(zip_provider_call_tracker.update_calls, 1);
+            assert_eq!(zip_provider_call_tracker.zip_calls, 0);
+            assert_eq!(zip_provider_call_tracker.cleanup_calls, 0);
+        }
+        
+        /// Test exporting a project which uses a recipe type newer than its pack format
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_export_incompatible_resource_warns() {
+            // Given a 1.20.4 project containing a recipe type added in 1.21.2
+
+            let serialized_project = default_serialized_project().with_files(incompatible_files());
+            let project = Project::from_settings(default_test_project_settings());
+
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(project.clone()),
+                fail_conversion: Default::default(),
+            });
+
+            let project_service = test_service_with_project_zip_provider(
+                MockProjectProvider::with_project(project.clone()),
+                MockZipProvider::with_project(serialized_project),
+            );
+
+            let project_zip_data = ProjectZipData {
+                project_id: *project.id(),
+                path: ZipPath::Single("test/file/path.zip".into()),
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
+            };
+
+            // When I export it
+
+            let warnings = project_service.export_zip(project_zip_data, false).await.unwrap();
+
+            // It should export, reporting the incompatible recipe
+
+            assert_eq!(warnings.len(), 1);
+            assert_eq!(warnings[0].path, PathBuf::from("data/test/recipes/dye.json"));
+            assert!(matches!(
+                warnings[0].issue,
+                CompatibilityIssue::NotYetAvailable { feature: PackFeature::RecipeType("minecraft:crafting_transmute"), .. }
+            ));
+
+            let zip_provider = project_service.zip_provider.read().await;
+            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
+            assert_eq!(zip_provider_call_tracker.zip_calls, 1);
+        }
+
+        /// Test that strict exports refuse projects which are incompatible with their pack format
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_export_incompatible_resource_strict() {
+            // Given a 1.20.4 project containing a recipe type added in 1.21.2
+
+            let serialized_project = default_serialized_project().with_files(incompatible_files());
+            let project = Project::from_settings(default_test_project_settings());
+
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(project.clone()),
+                fail_conversion: Default::default(),
+            });
+
+            let project_service = test_service_with_project_zip_provider(
+                MockProjectProvider::with_project(project.clone()),
+                MockZipProvider::with_project(serialized_project),
+            );
+
+            let project_zip_data = ProjectZipData {
+                project_id: *project.id(),
+                path: ZipPath::Single("test/file/path.zip".into()),
+                strict_compatibility: true,
+                json_style: JsonStyle::default(),
+            };
+
+            // When I export it strictly
+
+            let result = project_service.export_zip(project_zip_data, false).await;
+
+            // It should refuse without writing anything
+
+            assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::Incompatible(warnings))) if warnings.len() == 1));
+
+            let zip_provider = project_service.zip_provider.read().await;
+            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
+            assert_eq!(zip_provider_call_tracker.zip_calls, 0);
+        }
+
+        /// Test planning an export, which should list what would be written without writing it
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_plan_export() {
+            // Given a project with some files
+
+            let serialized_project = default_serialized_project().with_files(incompatible_files());
+            let project = Project::from_settings(default_test_project_settings());
+
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(project.clone()),
+                fail_conversion: Default::default(),
+            });
+
+            let project_service = test_service_with_project_zip_provider(
+                MockProjectProvider::with_project(project.clone()),
+                MockZipProvider::with_project(serialized_project),
+            );
+
+            let project_zip_data = ProjectZipData {
+                project_id: *project.id(),
+                path: ZipPath::Single("test/file/path.zip".into()),
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
+            };
+
+            // When I plan its export
+
+            let plan = project_service.plan_export(project_zip_data).await.unwrap();
+
+            // It should list every entry of the zip, along with the warnings the export would give
+
+            assert_eq!(plan.archives, vec![PlannedArchive {
+                path: PathBuf::from("test/file/path.zip"),
+                entries: vec![
+                    PathBuf::from("data/test/functions/load.mcfunction"),
+                    PathBuf::from("data/test/recipes/dye.json"),
+                    PathBuf::from("pack.mcmeta"),
+                ],
+            }]);
+            assert_eq!(plan.warnings.len(), 1);
+
+            // And nothing should be written
+
+            let zip_provider = project_service.zip_provider.read().await;
+            let zip_provider_call_tracker = zip_provider.call_tracker.read().unwrap();
+            assert_eq!(zip_provider_call_tracker.zip_calls, 0);
+            assert_eq!(zip_provider_call_tracker.cleanup_calls, 0);
+        }
+
+        /// Test that files the project ignores are left out of the plan, along with the editor's own files
+        #[tokio::test]
+        async fn test_plan_export_excludes_ignored_files() {
+            // Given a project with working files it ignores, using the default adapters
+            let project = Project::from_settings(ProjectSettings::DataPack {
+                name: "Test Project".to_string(),
+                description: PackDescription::String("Test Description".to_string()),
+                path: None,
+                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            }).with_files([
+                (PathBuf::from(".gaeaignore"), "notes/\n*.psd".to_string()),
+                (PathBuf::from(".gaea/scratch.json"), "{}".to_string()),
+                (PathBuf::from("notes/todo.md"), "- add recipes".to_string()),
+                (PathBuf::from("pack.psd"), "layers".to_string()),
+                (PathBuf::from("data/test/function/load.mcfunction"), "say loaded".to_string()),
+            ]);
+            let project_id = *project.id();
+
+            let project_provider = DefaultProjectProvider::default();
+            project_provider.add_project(project, false).unwrap();
+            let project_service = ProjectService::new(project_provider, MockZipProvider::default(), DefaultAdapterProvider::new());
+
+            // When I plan its export
+            let plan = project_service.plan_export(ProjectZipData {
+                project_id,
+                path: ZipPath::Single("test/file/path.zip".into()),
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
+            }).await.unwrap();
+
+            // Then only the pack's own files should be in the zip
+            assert_eq!(plan.archives, vec![PlannedArchive {
+                path: PathBuf::from("test/file/path.zip"),
+                entries: vec![
+                    PathBuf::from("data/test/function/load.mcfunction"),
+                    PathBuf::from("pack.mcmeta"),
+                ],
+            }]);
+        }
+
+        /// Test comparing a project with an export made before a resource was added and another was changed
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_diff_against_export() {
+            // Given an export of a project, using the default adapters
+            let settings = ProjectSettings::DataPack {
+                name: "Test Project".to_string(),
+                description: PackDescription::String("Test Description".to_string()),
+                path: None,
+                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            };
+            let exported_project = Project::from_settings(settings).with_files([
+                (PathBuf::from("data/test/function/load.mcfunction"), "say loaded".to_string()),
+                (PathBuf::from("data/test/recipe/gem.json"), r#"{"type": "minecraft:crafting_shapeless", "count": 1}"#.to_string()),
+                (PathBuf::from("data/test/loot_table/gem.json"), r#"{"pools": []}"#.to_string()),
+            ]);
+            let export_service = ProjectService::new(
+                MockProjectProvider::with_project(exported_project.clone()),
+                MockZipProvider::default(),
+                DefaultAdapterProvider::new(),
+            );
+            let (SerializedProjectData::Data(export), _, _) = export_service.serialize_for_export(*exported_project.id(), false, JsonStyle::Reformat { layout: JsonLayout::Compact, key_order: KeyOrder::Vanilla }).await.unwrap() else {
+                panic!("Expected a data pack");
+            };
+
+            // And the project since then, with a recipe changed, a function added and the loot table only reformatted
+            let project = exported_project.with_files([
+                (PathBuf::from("data/test/function/load.mcfunction"), "say loaded".to_string()),
+                (PathBuf::from("data/test/function/tick.mcfunction"), "say tick".to_string()),
+                (PathBuf::from("data/test/recipe/gem.json"), r#"{"type": "minecraft:crafting_shapeless", "count": 2}"#.to_string()),
+                (PathBuf::from("data/test/loot_table/gem.json"), "{\n  \"pools\": []\n}".to_string()),
+            ]);
+            let project_id = *project.id();
+            let project_service = ProjectService::new(
+                MockProjectProvider::with_project(project),
+                MockZipProvider::with_project(export),
+                DefaultAdapterProvider::new(),
+            );
+
+            // When I compare it with the export
+            let diff = project_service.diff_against_export(project_id, ZipPath::Single("test/file/path.zip".into())).await.unwrap();
+
+            // Then only the added function and the changed recipe should be reported
+            assert_eq!(diff, ExportDiff {
+                added: vec![PathBuf::from("data/test/function/tick.mcfunction")],
+                removed: vec![],
+                changed: vec![PathBuf::from("data/test/recipe/gem.json")],
+            });
+        }
+
+        /// Test comparing a project with zips which don't match its type
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_diff_against_export_mismatched_paths() {
+            // Given a data pack
+            let project = Project::from_settings(default_test_project_settings());
+            let project_id = *project.id();
+            let project_service = ProjectService::new(
+                MockProjectProvider::with_project(project),
+                MockZipProvider::default(),
+                DefaultAdapterProvider::new(),
+            );
+
+            // When I compare it with a pair of zips
+            let result = project_service.diff_against_export(project_id, ZipPath::Combined {
+                data_path: "test/file/path_data.zip".into(),
+                resource_path: "test/file/path_resource.zip".into(),
+            }).await;
+
+            // Then it should be refused
+            assert!(matches!(result, Err(ProjectServiceError::Zip(ZipError::MismatchedPaths(ProjectType::DataPack, _)))));
+        }
+
+        /// Test that a batch export reports every project, even after one fails
+        #[tokio::test]
+        #[serial_test::serial(project_service_zip)]
+        async fn test_export_zips_reports_each_project() {
+            // Given two open projects, and a zip already where one of them is to be exported
+            let serialized_project = default_serialized_project();
+            let settings = |name: &str| ProjectSettings::DataPack {
+                name: name.to_string(),
+                description: PackDescription::String("Test Description".to_string()),
+                path: None,
+                project_version: ProjectVersion { version: *versions::V1_20_4 },
+            };
+            let first = Project::from_settings(settings("First"));
+            let second = Project::from_settings(settings("Second"));
+
+            MockProjectAdapter::reset_config();
+            MockProjectAdapter::set_config(ProjectAdapterConfig {
+                serialized_project: Some(serialized_project.clone()),
+                project: Some(first.clone()),
+                fail_conversion: Default::default(),
+            });
+
+            let project_provider = DefaultProjectProvider::default();
+            project_provider.add_project(first.clone(), false).unwrap();
+            project_provider.add_project(second.clone(), false).unwrap();
+
+            let zip_provider = MockZipProvider {
+                existing_paths: vec![PathBuf::from("exports/first.zip")],
+                ..MockZipProvider::with_project(serialized_project)
+            };
+            let project_service = ProjectService::with_no_adapters(project_provider, zip_provider, default_test_adapter_provider());
+
+            // When I export both
+            let export = |project: &Project, path: &str| ProjectZipData {
+                project_id: *project.id(),
+                path: ZipPath::Single(path.into()),
+                strict_compatibility: false,
+                json_style: JsonStyle::default(),
+            };
+            let results = project_service.export_zips(vec![
+                export(&first, "exports/first.zip"),
+                export(&second, "exports/second.zip"),
+            ], false).await;
+
+            // Then the collision should be reported for the first, without stopping the second
+            assert_eq!(results.len(), 2);
+            assert_eq!(results[0].0, *first.id());
+            assert!(matches!(results[0].1, Err(ProjectServiceError::Zip(ZipError::Zipping(_)))));
+            assert_eq!(results[1].0, *second.id());
+            assert!(results[1].1.is_ok());
+
+            let zip_provider = project_service.zip_provider.read().await;
+            assert_eq!(zip_provider.call_tracker.read().unwrap().zip_calls, 2);
+        }
+
+        fn incompatible_files() -> HashMap<PathBuf, String> {
+            HashMap::from([
+                (PathBuf::from("data/test/functions/load.mcfunction"), "say loaded".to_string()),
+                (PathBuf::from("data/test/recipes/dye.json"), r#"{"type": "minecraft:crafting_transmute"}"#.to_string()),
+            ])
+        }
+
         // TODO: More in depth error handling testing on cleanup calls, etc
     }
 }
\ No newline at end of file
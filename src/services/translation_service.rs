@@ -1,13 +1,15 @@
 use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
 use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use include_dir::{include_dir, Dir};
 use once_cell::sync::Lazy;
 use serde::de::Error;
 use serde_json::Value;
 use tokio::sync::RwLock;
 use crate::RUNTIME;
-use crate::services::filesystem_service::{DefaultFilesystemProvider, FilesystemProvider, FilesystemProviderError, PathValidationStatus};
+use crate::services::filesystem_service::{DefaultFilesystemProvider, FileWriteOptions, FilesystemProvider, FilesystemProviderError, PathValidationStatus};
 
 pub trait TranslationProvider {
     fn translate(&self, key: &dyn TranslationKey) -> String;
@@ -18,49 +20,226 @@ pub trait TranslationProvider {
     fn get_current_language(&self) -> Language;
     fn get_default_language(&self) -> Language;
     fn reload_languages(&mut self) -> Result<(), TranslationError>;
+    /// Registers a callback run with the new language whenever the current language changes,
+    /// so anything showing translated text knows to translate it again
+    fn subscribe_language_changes(&mut self, callback: LanguageChangeCallback);
 }
 
+pub type LanguageChangeCallback = Box<dyn Fn(&Language) + Send + Sync>;
+
 #[derive(Debug)]
 pub struct TranslationService<Filesystem: FilesystemProvider + Send + Sync + 'static = DefaultFilesystemProvider> {
-    language_path: PathBuf,
+    language_source: LanguageSource,
+    external_language_path: Option<PathBuf>,
     
     current_language_code: LanguageCode,
     default_language_code: LanguageCode,
     
     languages: HashMap<LanguageCode, Language>,
+    language_listeners: LanguageListeners,
     
     filesystem: Arc<RwLock<Filesystem>>,
 }
 
+#[derive(Default)]
+struct LanguageListeners(Vec<LanguageChangeCallback>);
+
+impl LanguageListeners {
+    fn notify(&self, language: &Language) {
+        for listener in &self.0 {
+            listener(language);
+        }
+    }
+}
+
+impl Debug for LanguageListeners {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} language listeners", self.0.len())
+    }
+}
+
 static DEFAULT_LANGUAGE_CODE: Lazy<LanguageCode> = Lazy::new(|| LanguageCode("en_us".to_string()));
 const DEFAULT_LANGUAGE_PATH: &str = "./resources/assets/localization";
 
+/// The localization directory compiled into the executable, so a shipped binary works without an adjacent resources folder
+pub static EMBEDDED_LANGUAGES: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/resources/assets/localization");
+
+/// Environment variable which can point the app at an additional localization directory, e.g. for community language packs
+pub const EXTERNAL_LANGUAGE_PATH_VAR: &str = "GAEA_LOCALIZATION_DIR";
+
 impl<Filesystem> TranslationService<Filesystem>
 where
     Filesystem: FilesystemProvider + Send + Sync + 'static,
 {
+    /// Loads the default language set, from the executable when built with the `embedded-languages` feature,
+    /// or from the resources folder otherwise
     pub fn try_with_default_language(filesystem: Arc<RwLock<Filesystem>>) -> Result<Self, TranslationError> {
-        Self::try_new(DEFAULT_LANGUAGE_CODE.clone(), Path::new(DEFAULT_LANGUAGE_PATH), filesystem)
+        let language_source = if cfg!(feature = "embedded-languages") {
+            LanguageSource::Embedded(&EMBEDDED_LANGUAGES)
+        } else {
+            LanguageSource::Filesystem(PathBuf::from(DEFAULT_LANGUAGE_PATH))
+        };
+
+        Self::try_with_source(DEFAULT_LANGUAGE_CODE.clone(), language_source, filesystem)
     }
     
     pub fn try_new(language_code: LanguageCode, language_path: impl AsRef<Path> + Send, filesystem: Arc<RwLock<Filesystem>>) -> Result<Self, TranslationError> {
-        let languages = RUNTIME.block_on(
-            Self::read_languages(language_path.as_ref(), filesystem.clone())
-        )?;
+        Self::try_with_source(language_code, LanguageSource::Filesystem(language_path.as_ref().to_path_buf()), filesystem)
+    }
+
+    pub fn try_with_source(language_code: LanguageCode, language_source: LanguageSource, filesystem: Arc<RwLock<Filesystem>>) -> Result<Self, TranslationError> {
+        RUNTIME.block_on(Self::try_with_source_async(language_code, language_source, filesystem))
+    }
+
+    /// Like [`TranslationService::try_new`], for use inside the async runtime, where blocking on it would deadlock
+    pub async fn try_new_async(language_code: LanguageCode, language_path: impl AsRef<Path> + Send, filesystem: Arc<RwLock<Filesystem>>) -> Result<Self, TranslationError> {
+        Self::try_with_source_async(language_code, LanguageSource::Filesystem(language_path.as_ref().to_path_buf()), filesystem).await
+    }
+
+    pub async fn try_with_source_async(language_code: LanguageCode, language_source: LanguageSource, filesystem: Arc<RwLock<Filesystem>>) -> Result<Self, TranslationError> {
+        let languages = Self::read_languages(&language_source, filesystem.clone()).await?;
 
         Ok(Self {
-            language_path: language_path.as_ref().to_path_buf(),
+            language_source,
+            external_language_path: None,
 
             current_language_code: language_code.clone(),
             default_language_code: language_code,
 
             languages,
+            language_listeners: LanguageListeners::default(),
 
             filesystem,
         })
     }
     
+    /// Sets an additional localization directory, which is merged over the bundled languages.
+    /// On conflicts, translations from the external directory take precedence.
+    /// If the external languages fail to load, the previous languages are kept
+    pub fn set_external_language_path(&mut self, external_language_path: Option<PathBuf>) -> Result<(), TranslationError> {
+        let previous_path = std::mem::replace(&mut self.external_language_path, external_language_path);
+
+        let result = self.reload_languages();
+        if result.is_err() {
+            self.external_language_path = previous_path;
+        }
+
+        result
+    }
+
+    /// Like [`TranslationProvider::reload_languages`], for use inside the async runtime
+    pub async fn reload_languages_async(&mut self) -> Result<(), TranslationError> {
+        let languages = Self::read_all_languages(&self.language_source, self.external_language_path.as_deref(), self.filesystem.clone()).await?;
+
+        let fell_back = !languages.contains_key(&self.current_language_code);
+        if fell_back {
+            self.current_language_code = self.default_language_code.clone();
+        }
+
+        self.languages = languages;
+        
+        if fell_back {
+            self.language_listeners.notify(&self.get_current_language());
+        }
+        Ok(())
+    }
+
+    /// Keys the default language translates which `language` doesn't, sorted
+    pub fn missing_keys(&self, language: &Language) -> Vec<String> {
+        let reference = self.get_default_language();
+        let reference_keys = reference.translation_map.read().unwrap();
+        let translated = language.translation_map.read().unwrap();
+
+        let mut missing: Vec<String> = reference_keys.keys()
+            .filter(|key| !translated.contains_key(*key))
+            .cloned()
+            .collect();
+        missing.sort();
+        missing
+    }
+
+    /// How complete each language is against the default language, with the keys it still needs
+    pub fn coverage_report(&self) -> String {
+        let reference = self.get_default_language();
+        let total_keys = reference.translation_map.read().unwrap().len();
+
+        let mut languages: Vec<&Language> = self.languages.values()
+            .filter(|language| language.code != self.default_language_code)
+            .collect();
+        languages.sort_by(|a, b| a.code.0.cmp(&b.code.0));
+
+        let mut report = format!("Translation coverage against {} ({}), {} keys\n", reference.name, reference.code.0, total_keys);
+        for language in languages {
+            let missing = self.missing_keys(language);
+            let percent_complete = if total_keys == 0 {
+                100.0
+            } else {
+                (total_keys - missing.len()) as f64 / total_keys as f64 * 100.0
+            };
+
+            report.push_str(&format!("\n{} ({}): {:.1}% complete\n", language.name, language.code.0, percent_complete));
+            for key in missing {
+                report.push_str(&format!("  missing: {}\n", key));
+            }
+        }
+
+        report
+    }
+
+    /// Writes [`TranslationService::coverage_report`] to a file, replacing any previous report
+    pub async fn write_coverage_report(&self, path: impl AsRef<Path> + Send) -> Result<(), TranslationError> {
+        self.filesystem.read().await
+            .write_file(path.as_ref(), self.coverage_report().as_bytes(), FileWriteOptions::Overwrite)
+            .await?;
+        Ok(())
+    }
+
+    async fn read_all_languages(
+        language_source: &LanguageSource,
+        external_language_path: Option<&Path>,
+        filesystem: Arc<RwLock<Filesystem>>
+    ) -> Result<HashMap<LanguageCode, Language>, TranslationError> {
+        let mut languages = Self::read_languages(language_source, filesystem.clone()).await?;
+
+        let Some(external_language_path) = external_language_path else {
+            return Ok(languages);
+        };
+
+        for (code, external_language) in Self::read_directory_languages(external_language_path, filesystem).await? {
+            match languages.get_mut(&code) {
+                Some(language) => {
+                    language.name = external_language.name;
+                    language.translation_map.write().unwrap()
+                        .extend(external_language.translation_map.read().unwrap().clone());
+                }
+                None => {
+                    languages.insert(code, external_language);
+                }
+            }
+        }
+
+        Ok(languages)
+    }
+
     async fn read_languages(
+        language_source: &LanguageSource,
+        filesystem: Arc<RwLock<Filesystem>>
+    ) -> Result<HashMap<LanguageCode, Language>, TranslationError> {
+        match language_source {
+            LanguageSource::Filesystem(path) => Self::read_directory_languages(path, filesystem).await,
+            LanguageSource::Embedded(embedded_languages) => Ok(Self::read_embedded_languages(embedded_languages)),
+        }
+    }
+
+    fn read_embedded_languages(embedded_languages: &Dir) -> HashMap<LanguageCode, Language> {
+        embedded_languages.files()
+            .filter(|file| file.path().extension().is_some_and(|extension| extension == "json"))
+            .filter_map(|file| Self::parse_language(file.path().file_stem()?.to_str()?, file.contents()))
+            .map(|language| (language.code.clone(), language))
+            .collect()
+    }
+
+    async fn read_directory_languages(
         path: impl AsRef<Path> + Send,
         filesystem: Arc<RwLock<Filesystem>>
     ) -> Result<HashMap<LanguageCode, Language>, TranslationError> {
@@ -91,75 +270,91 @@ where
                 }
 
                 let filename = filepath.with_extension("").file_name().unwrap().to_str().unwrap().to_string();
+                let file_contents = filesystem.read().await.read_file(filepath.as_path()).await?;
 
-                use serde_json::Value;
-
-                let json: serde_json::error::Result<Value> = {
-                    let file_contents = filesystem.read().await.read_file(filepath.as_path()).await?;
-                    let file = io::Cursor::new(file_contents);
-                    let reader = io::BufReader::new(file);
-                    serde_json::from_reader(reader)
-                };
-                
-                if let Err(error) = json {
-                    tracing::error!("Failed to read file {} - {}", filename, error);
-                    continue;
+                if let Some(language) = Self::parse_language(&filename, &file_contents) {
+                    languages.insert(language.code.clone(), language);
                 }
-
-                let json = json?;
-                let json = match json.as_object() {
-                    Some(json) => json,
-                    None => {
-                        tracing::warn!("Invalid json file {} - Must have object as root", filename);
-                        continue;
-                    }
-                };
-                
-                let name = match json.get("name") {
-                    Some(name) => name,
-                    None => {
-                        tracing::warn!("Invalid json file {} - Missing parameter \"name\"", filename);
-                        continue;
-                    }
-                };
-                
-                let code = LanguageCode(filename.clone());
-                
-                let translations = match json.get("translations") {
-                    Some(translations) => if translations.is_object() {
-                        translations.as_object().unwrap()
-                    }
-                    else { 
-                        tracing::warn!("Invalid json file {} - \"translations\" must be an object", filename);
-                        continue;
-                    },
-                    None => {
-                        tracing::warn!("Invalid json file {} - Missing parameter \"translations\"", filename);
-                        continue;
-                    }
-                };
-                
-                let translation_map = match Self::load_translations(translations) {
-                    Ok(translation_map) => translation_map,
-                    Err(error) => {
-                        tracing::error!("Failed to load translations for language {} - {}", code.0, error);
-                        continue;
-                    }
-                };
-
-                let language = Language {
-                    code: code.clone(),
-                    name: name.as_str().unwrap().to_string(),
-                    translation_map: Arc::new(std::sync::RwLock::new(translation_map)),
-                };
-
-                languages.insert(code, language);
             }
         }
         
         Ok(languages)
     }
 
+    /// Parses a language file, logging and skipping it if it is malformed
+    fn parse_language(filename: &str, contents: &[u8]) -> Option<Language> {
+        let json: serde_json::error::Result<Value> = {
+            let file = io::Cursor::new(contents);
+            let reader = io::BufReader::new(file);
+            serde_json::from_reader(reader)
+        };
+
+        let json = match json {
+            Ok(json) => json,
+            Err(error) => {
+                tracing::error!("Failed to read file {} - {}", filename, error);
+                return None;
+            }
+        };
+
+        let json = match json.as_object() {
+            Some(json) => json,
+            None => {
+                tracing::warn!("Invalid json file {} - Must have object as root", filename);
+                return None;
+            }
+        };
+
+        let name = match json.get("name") {
+            Some(name) => name,
+            None => {
+                tracing::warn!("Invalid json file {} - Missing parameter \"name\"", filename);
+                return None;
+            }
+        };
+
+        let code = LanguageCode(filename.to_string());
+
+        let translations = match json.get("translations") {
+            Some(translations) => if translations.is_object() {
+                translations.as_object().unwrap()
+            }
+            else {
+                tracing::warn!("Invalid json file {} - \"translations\" must be an object", filename);
+                return None;
+            },
+            None => {
+                tracing::warn!("Invalid json file {} - Missing parameter \"translations\"", filename);
+                return None;
+            }
+        };
+
+        let translation_map = match Self::load_translations(translations) {
+            Ok(translation_map) => translation_map,
+            Err(error) => {
+                tracing::error!("Failed to load translations for language {} - {}", code.0, error);
+                return None;
+            }
+        };
+
+        // Languages can say which way they read, otherwise it is worked out from the language code
+        let rtl = match json.get("rtl") {
+            Some(Value::Bool(rtl)) => *rtl,
+            Some(_) => {
+                tracing::warn!("Invalid json file {} - \"rtl\" must be a boolean", filename);
+                return None;
+            }
+            None => is_rtl_code(&code),
+        };
+
+        Some(Language {
+            code,
+            name: name.as_str().unwrap().to_string(),
+            rtl,
+            translation_map: Arc::new(std::sync::RwLock::new(translation_map)),
+        })
+    }
+
     fn load_translations(translations: &serde_json::map::Map<String, Value>) -> Result<HashMap<String, String>, TranslationError> {
         let mut translation_map = HashMap::new();
         for (key, value) in translations {
@@ -203,7 +398,10 @@ where
             return Err(TranslationError::LanguageNotFound(language.code.0.clone()));
         }
         
-        self.current_language_code = language.code.clone();
+        if self.current_language_code != language.code {
+            self.current_language_code = language.code.clone();
+            self.language_listeners.notify(language);
+        }
         Ok(())
     }
 
@@ -228,16 +426,11 @@ where
     }
 
     fn reload_languages(&mut self) -> Result<(), TranslationError> {
-        let languages = RUNTIME.block_on(
-            Self::read_languages(&self.language_path, self.filesystem.clone())
-        )?;
-
-        if !languages.contains_key(&self.current_language_code) {
-            self.current_language_code = self.default_language_code.clone();
-        }
+        RUNTIME.block_on(self.reload_languages_async())
+    }
 
-        self.languages = languages;
-        Ok(())
+    fn subscribe_language_changes(&mut self, callback: LanguageChangeCallback) {
+        self.language_listeners.0.push(callback);
     }
 }
 
@@ -256,52 +449,114 @@ pub enum TranslationError {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct LanguageCode(String);
 
+/// Where the bundled language set is loaded from
+#[derive(Debug, Clone, PartialEq)]
+pub enum LanguageSource {
+    /// A directory of language files, read through the filesystem provider
+    Filesystem(PathBuf),
+    /// A directory of language files compiled into the executable
+    Embedded(&'static Dir<'static>),
+}
+
 #[derive(Debug, Clone)]
 pub struct Language {
     code: LanguageCode,
     name: String,
+    /// Written right-to-left, like Arabic or Hebrew
+    rtl: bool,
     translation_map: Arc<std::sync::RwLock<HashMap<String, String>>>
 }
 
+impl Language {
+    pub fn is_rtl(&self) -> bool {
+        self.rtl
+    }
+
+    pub fn layout_direction(&self) -> LayoutDirection {
+        if self.is_rtl() {
+            LayoutDirection::RightToLeft
+        } else {
+            LayoutDirection::LeftToRight
+        }
+    }
+}
+
+/// Languages of Minecraft's language codes which are written right-to-left
+const RTL_LANGUAGES: &[&str] = &["ar", "fa", "he", "ur", "yi"];
+
+fn is_rtl_code(code: &LanguageCode) -> bool {
+    let language = code.0.split('_').next().unwrap_or_default();
+    RTL_LANGUAGES.contains(&language)
+}
+
+/// Which way the interface is laid out for a language. Only the interface around the editor flips,
+/// as the code being edited always reads left-to-right
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum LayoutDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    /// Puts items written in left-to-right order into the order they should be laid out in
+    pub fn arrange<T>(self, mut items: Vec<T>) -> Vec<T> {
+        if self == LayoutDirection::RightToLeft {
+            items.reverse();
+        }
+        items
+    }
+}
+
 pub trait TranslationKey {
     fn key(&self) -> &'static str;
     fn english_text(&self) -> &'static str;
     fn all_variants() -> Vec<Self> where Self: Sized;
 }
 
+/// Collects the key and English default for every variant of a key enum
+#[cfg(any(test, feature = "translation-export"))]
+pub fn english_defaults<Key: TranslationKey>() -> Vec<(&'static str, &'static str)> {
+    Key::all_variants().iter()
+        .map(|key| (key.key(), key.english_text()))
+        .collect()
+}
+
+/// Builds a language file skeleton for translators, adding any keys missing from `existing` with their English defaults.
+/// Existing translations are never overwritten
+#[cfg(any(test, feature = "translation-export"))]
+pub fn merge_language_skeleton(existing: Option<&[u8]>, defaults: &[(&'static str, &'static str)]) -> Result<Vec<u8>, TranslationError> {
+    let mut json = match existing {
+        Some(existing) => serde_json::from_slice::<Value>(existing)?,
+        None => serde_json::json!({}),
+    };
+
+    let root = json.as_object_mut()
+        .ok_or(serde_json::Error::custom("Language file must have an object as root"))?;
+
+    root.entry("name").or_insert_with(|| Value::String("English".to_string()));
+
+    let translations = root.entry("translations")
+        .or_insert_with(|| Value::Object(serde_json::Map::new()))
+        .as_object_mut()
+        .ok_or(serde_json::Error::custom("\"translations\" must be an object"))?;
+
+    for (key, english_text) in defaults {
+        translations.entry(*key).or_insert_with(|| Value::String(english_text.to_string()));
+    }
+
+    Ok(serde_json::to_vec_pretty(&json)?)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::fs::Metadata;
     use std::path::PathBuf;
-    use async_trait::async_trait;
-    use mockall::mock;
     use rstest::fixture;
     use serde_json::json;
-    use crate::services::filesystem_service;
-    use crate::services::filesystem_service::{ChunkedFileReadResult, FileDeleteOptions, FileWriteOptions, PathValidationStatus};
+    use crate::services::filesystem_service::FileWriteOptions;
+    use crate::services::in_memory_filesystem::InMemoryFilesystem;
     use super::*;
 
-    mock! {
-        FilesystemService {}
-        #[async_trait]
-        impl FilesystemProvider for FilesystemService {
-            async fn write_file(&self, path: &Path, content: &[u8], options: FileWriteOptions) -> filesystem_service::Result<()>;
-            async fn read_file(&self, path: &Path) -> filesystem_service::Result<Vec<u8>>;
-            async fn read_file_chunked(&self, path: &Path, chunk_size: usize, callback: Box<dyn FnMut(Vec<u8>) -> ChunkedFileReadResult + Send>) -> filesystem_service::Result<()>;
-            async fn delete_file(&self, path: &Path, options: FileDeleteOptions) -> filesystem_service::Result<()>;
-            async fn copy_file(&self, source: &Path, destination: &Path) -> filesystem_service::Result<()>;
-            async fn move_file(&self, source: &Path, destination: &Path) -> filesystem_service::Result<()>;
-            async fn create_directory(&self, path: &Path) -> filesystem_service::Result<()>;
-            async fn create_directory_recursive(&self, path: &Path) -> filesystem_service::Result<()>;
-            async fn delete_directory(&self, path: &Path) -> filesystem_service::Result<()>;
-            async fn list_directory(&self, path: &Path) -> filesystem_service::Result<Vec<PathBuf>>;
-            async fn validate_path(&self, path: &Path) -> filesystem_service::Result<PathValidationStatus>;
-            async fn file_exists(&self, path: &Path) -> filesystem_service::Result<bool>;
-            async fn is_directory(&self, path: &Path) -> filesystem_service::Result<bool>;
-            async fn get_metadata(&self, path: &Path) -> filesystem_service::Result<Metadata>;
-        }
-    }
-
     fn create_test_language_content(code: &str, name: &str, translations: Vec<(&str, &str)>) -> Vec<u8> {
         let mut translation_obj = serde_json::Map::new();
         for (key, value) in translations {
@@ -316,42 +571,39 @@ mod tests {
 
         serde_json::to_vec(&language_json).unwrap()
     }
+
+    fn write_language(filesystem: &InMemoryFilesystem, directory: &str, code: &str, name: &str, translations: Vec<(&str, &str)>) {
+        RUNTIME.block_on(async {
+            let directory = Path::new(directory);
+            filesystem.create_directory_recursive(directory).await.unwrap();
+            filesystem.write_file(
+                &directory.join(format!("{}.json", code)),
+                &create_test_language_content(code, name, translations),
+                FileWriteOptions::Overwrite
+            ).await.unwrap();
+        });
+    }
     
-    /// Tests handling the construction of the translation service and loading of the translation files
     /// Tests handling the construction of the translation service and loading of the translation files
     mod file_tests {
         use super::*;
-        use mockall::predicate::*;
         use std::sync::Arc;
+        use include_dir::{DirEntry, File};
 
         #[test]
         fn test_try_with_default_language_success() {
-            let mut mock_fs = MockFilesystemService::new();
-
             // Given a valid default language file
-            mock_fs.expect_read_file()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH).join("en_us.json")))
-                .returning(|_| {
-                    Ok(create_test_language_content("en_us", "English", vec![
-                        ("hello", "Hello"),
-                        ("goodbye", "Goodbye")
-                    ]))
-                });
-
-            mock_fs.expect_list_directory()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(vec![PathBuf::from(DEFAULT_LANGUAGE_PATH).join("en_us.json")]));
-            
-            mock_fs.expect_is_directory()
-                .with(eq(PathBuf::from(DEFAULT_LANGUAGE_PATH).join("en_us.json")))
-                .returning(|_| Ok(false));
-            
-            mock_fs.expect_validate_path()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(PathValidationStatus::Valid { is_file: false }));
-
-            // When I try to load it 
-            let result = TranslationService::try_with_default_language(Arc::new(RwLock::new(mock_fs)));
+            let filesystem = InMemoryFilesystem::new();
+            write_language(&filesystem, DEFAULT_LANGUAGE_PATH, "en_us", "English", vec![
+                ("hello", "Hello"),
+                ("goodbye", "Goodbye")
+            ]);
+
+            // When I try to load it from the filesystem, which is where it's read from outside of distribution builds
+            let result = TranslationService::try_with_source(
+                DEFAULT_LANGUAGE_CODE.clone(),
+                LanguageSource::Filesystem(PathBuf::from(DEFAULT_LANGUAGE_PATH)),
+                Arc::new(RwLock::new(filesystem)));
 
             // Then it should load correctly
             assert!(result.is_ok());
@@ -359,7 +611,7 @@ mod tests {
             let service = result.unwrap();
             assert_eq!(service.default_language_code, *DEFAULT_LANGUAGE_CODE);
             assert_eq!(service.current_language_code, *DEFAULT_LANGUAGE_CODE);
-            assert_eq!(service.language_path, PathBuf::from(DEFAULT_LANGUAGE_PATH));
+            assert_eq!(service.language_source, LanguageSource::Filesystem(PathBuf::from(DEFAULT_LANGUAGE_PATH)));
             
             assert!(service.languages.contains_key(&DEFAULT_LANGUAGE_CODE));
             
@@ -378,40 +630,22 @@ mod tests {
 
         #[test]
         fn test_try_new_with_custom_language() {
-            let mut mock_fs = MockFilesystemService::new();
-            
             // Given a valid language file with a custom resource directory and
             // non-english language file
             let test_path = PathBuf::from("./test/localization");
             let language_code = LanguageCode("fr_fr".to_string());
 
-            mock_fs.expect_read_file()
-                .with(eq(test_path.join("fr_fr.json")))
-                .returning(|_| {
-                    Ok(create_test_language_content("fr_fr", "French", vec![
-                        ("hello", "Bonjour"),
-                        ("goodbye", "Au revoir")
-                    ]))
-                });
-
-            let test_path_clone = test_path.clone();
-            mock_fs.expect_list_directory()
-                .with(eq(test_path.clone()))
-                .returning(move |_| Ok(vec![test_path_clone.join("fr_fr.json")]));
-
-            mock_fs.expect_is_directory()
-                .with(eq(test_path.join("fr_fr.json")))
-                .returning(|_| Ok(false));
-
-            mock_fs.expect_validate_path()
-                .with(eq(test_path.clone()))
-                .returning(|_| Ok(PathValidationStatus::Valid { is_file: false }));
+            let filesystem = InMemoryFilesystem::new();
+            write_language(&filesystem, "./test/localization", "fr_fr", "French", vec![
+                ("hello", "Bonjour"),
+                ("goodbye", "Au revoir")
+            ]);
 
             // When I try to load it
             let result = TranslationService::try_new(
                 language_code.clone(),
                 test_path.clone(),
-                Arc::new(RwLock::new(mock_fs)));
+                Arc::new(RwLock::new(filesystem)));
             
             // Then it should load correctly
             assert!(result.is_ok());
@@ -419,91 +653,82 @@ mod tests {
             let service = result.unwrap();
             assert_eq!(service.current_language_code, language_code);
             assert_eq!(service.default_language_code, language_code);
-            assert_eq!(service.language_path, test_path);
+            assert_eq!(service.language_source, LanguageSource::Filesystem(test_path));
             assert!(service.languages.contains_key(&language_code));
         }
 
         #[test]
         fn test_try_new_language_not_found() {
-            let mut mock_fs = MockFilesystemService::new();
-            
-            // Given a default language file which does not exist
+            // Given a language directory which does not exist
             let language_code = LanguageCode("invalid".to_string());
 
-            mock_fs.expect_read_file()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH).join("invalid.json")))
-                .returning(|_| {
-                    Err(FilesystemProviderError::IO(io::Error::new(
-                        io::ErrorKind::NotFound,
-                        "File not found"
-                    )))
-                });
-
-            mock_fs.expect_list_directory()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(vec![PathBuf::from(DEFAULT_LANGUAGE_PATH).join("invalid.json")]));
-
-            mock_fs.expect_is_directory()
-                .with(eq(PathBuf::from(DEFAULT_LANGUAGE_PATH).join("invalid.json")))
-                .returning(|_| Ok(false));
-
-            mock_fs.expect_validate_path()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(PathValidationStatus::Valid { is_file: false }));
-
+            // When I try to load it
             let result = TranslationService::try_new(
                 language_code.clone(),
                 Path::new(DEFAULT_LANGUAGE_PATH),
-                Arc::new(RwLock::new(mock_fs)));
+                Arc::new(RwLock::new(InMemoryFilesystem::new())));
 
+            // Then it should return an error
             assert!(result.is_err());
-            assert!(matches!(result, Err(TranslationError::IO(_))));
+            assert!(matches!(result, Err(TranslationError::InvalidFilepath(_))));
         }
 
         #[test]
-        fn test_read_languages_multiple_files() {
-            let mut mock_fs = MockFilesystemService::new();
+        fn test_try_with_embedded_languages() {
+            // Given an empty filesystem, which should never be read
+            let filesystem = InMemoryFilesystem::new();
+
+            // And an embedded language, next to a file which isn't one
+            static LANGUAGES: Dir<'static> = Dir::new("", &[
+                DirEntry::File(File::new("en_us.json", br#"{"name": "English", "translations": {"hello": "Hello"}}"#)),
+                DirEntry::File(File::new("README.md", b"Not a language")),
+            ]);
+
+            // When I load it
+            let result = TranslationService::try_with_source(
+                DEFAULT_LANGUAGE_CODE.clone(),
+                LanguageSource::Embedded(&LANGUAGES),
+                Arc::new(RwLock::new(filesystem)));
+
+            // Then only the language should be loaded
+            let service = result.unwrap();
+            assert_eq!(service.languages.len(), 1);
+            let language = service.languages.get(&DEFAULT_LANGUAGE_CODE).unwrap();
 
+            assert_eq!(language.name, "English");
+            assert_eq!(language.translation_map.read().unwrap().get("hello").unwrap(), "Hello");
+        }
+
+        #[test]
+        fn test_bundled_languages_are_embedded() {
+            // When I load the languages compiled into the executable
+            let result = TranslationService::try_with_source(
+                DEFAULT_LANGUAGE_CODE.clone(),
+                LanguageSource::Embedded(&EMBEDDED_LANGUAGES),
+                Arc::new(RwLock::new(InMemoryFilesystem::new())));
+
+            // Then the default language should be available
+            assert!(result.unwrap().languages.contains_key(&DEFAULT_LANGUAGE_CODE));
+        }
+
+        #[test]
+        fn test_read_languages_multiple_files() {
             // Given multiple translation files
-            mock_fs.expect_read_file()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH).join("en_us.json")))
-                .returning(|_| {
-                    Ok(create_test_language_content("en_us", "English", vec![
-                        ("hello", "Hello"),
-                        ("goodbye", "Goodbye")
-                    ]))
-                });
-
-            mock_fs.expect_read_file()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH).join("fr_fr.json")))
-                .returning(|_| {
-                    Ok(create_test_language_content("fr_fr", "French", vec![
-                        ("hello", "Bonjour"),
-                        ("goodbye", "Au revoir")
-                    ]))
-                });
-
-            mock_fs.expect_list_directory()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(vec![
-                    PathBuf::from(DEFAULT_LANGUAGE_PATH).join("en_us.json"),
-                    PathBuf::from(DEFAULT_LANGUAGE_PATH).join("fr_fr.json"),
-                ]));
-
-            mock_fs.expect_is_directory()
-                .with(eq(PathBuf::from(DEFAULT_LANGUAGE_PATH).join("en_us.json")))
-                .returning(|_| Ok(false));
-
-            mock_fs.expect_is_directory()
-                .with(eq(PathBuf::from(DEFAULT_LANGUAGE_PATH).join("fr_fr.json")))
-                .returning(|_| Ok(false));
-
-            mock_fs.expect_validate_path()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(PathValidationStatus::Valid { is_file: false }));
-
-            // When I try to load them
-            let result =  TranslationService::try_with_default_language(Arc::new(RwLock::new(mock_fs)));
+            let filesystem = InMemoryFilesystem::new();
+            write_language(&filesystem, DEFAULT_LANGUAGE_PATH, "en_us", "English", vec![
+                ("hello", "Hello"),
+                ("goodbye", "Goodbye")
+            ]);
+            write_language(&filesystem, DEFAULT_LANGUAGE_PATH, "fr_fr", "French", vec![
+                ("hello", "Bonjour"),
+                ("goodbye", "Au revoir")
+            ]);
+
+            // When I try to load them from the filesystem
+            let result = TranslationService::try_with_source(
+                DEFAULT_LANGUAGE_CODE.clone(),
+                LanguageSource::Filesystem(PathBuf::from(DEFAULT_LANGUAGE_PATH)),
+                Arc::new(RwLock::new(filesystem)));
 
             // Then they should all be loaded correctly
             assert!(result.is_ok());
@@ -519,57 +744,96 @@ mod tests {
         }
     }
     
+    /// Tests handling additional localization directories layered over the bundled one
+    mod external_language_tests {
+        use super::*;
+
+        fn language_code(code: &str) -> LanguageCode {
+            LanguageCode(code.to_string())
+        }
+
+        #[test]
+        fn test_external_languages_take_precedence() {
+            // Given a bundled and an external directory which both define english
+            let filesystem = InMemoryFilesystem::new();
+            write_language(&filesystem, "/bundled", "en_us", "English", vec![
+                ("hello", "Hello"),
+                ("goodbye", "Goodbye"),
+            ]);
+            write_language(&filesystem, "/external", "en_us", "English (Community)", vec![
+                ("hello", "Howdy"),
+            ]);
+
+            // When I load both
+            let mut service = TranslationService::try_new(language_code("en_us"), "/bundled", Arc::new(RwLock::new(filesystem))).unwrap();
+            service.set_external_language_path(Some(PathBuf::from("/external"))).unwrap();
+
+            // Then the external translations should override the bundled ones
+            let language = service.get_default_language();
+            let translation_map = language.translation_map.read().unwrap();
+
+            assert_eq!(language.name, "English (Community)");
+            assert_eq!(translation_map.get("hello").unwrap(), "Howdy");
+
+            // And bundled translations missing from the external directory should be kept
+            assert_eq!(translation_map.get("goodbye").unwrap(), "Goodbye");
+        }
+
+        #[test]
+        fn test_external_languages_are_merged() {
+            // Given an external directory with a language which is not bundled
+            let filesystem = InMemoryFilesystem::new();
+            write_language(&filesystem, "/bundled", "en_us", "English", vec![("hello", "Hello")]);
+            write_language(&filesystem, "/external", "de_de", "Deutsch", vec![("hello", "Hallo")]);
+
+            // When I load both
+            let mut service = TranslationService::try_new(language_code("en_us"), "/bundled", Arc::new(RwLock::new(filesystem))).unwrap();
+            service.set_external_language_path(Some(PathBuf::from("/external"))).unwrap();
+
+            // Then languages from both should be available
+            assert!(service.get_language(language_code("en_us")).is_some());
+            assert!(service.get_language(language_code("de_de")).is_some());
+        }
+
+        #[test]
+        fn test_invalid_external_path_keeps_languages() {
+            // Given a bundled directory
+            let filesystem = InMemoryFilesystem::new();
+            write_language(&filesystem, "/bundled", "en_us", "English", vec![("hello", "Hello")]);
+
+            let mut service = TranslationService::try_new(language_code("en_us"), "/bundled", Arc::new(RwLock::new(filesystem))).unwrap();
+
+            // When I point it at an external directory which doesn't exist
+            let result = service.set_external_language_path(Some(PathBuf::from("/missing")));
+
+            // Then it should return an error and keep the bundled languages
+            assert!(matches!(result, Err(TranslationError::InvalidFilepath(_))));
+            assert_eq!(service.external_language_path, None);
+            assert!(service.get_language(language_code("en_us")).is_some());
+        }
+    }
+
     /// Tests handling the implementation of the public API for the translation service
     mod api_tests {
-        use mockall::predicate::eq;
         use rstest::rstest;
         use translation_macro::TranslationKey;
         use super::*;
 
         #[fixture]
-        fn translation_service() -> TranslationService<MockFilesystemService> {
-            let mut mock_fs = MockFilesystemService::new();
-
-            mock_fs.expect_read_file()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH).join("en_us.json")))
-                .returning(|_| {
-                    Ok(create_test_language_content("en_us", "English", vec![
-                        ("test.hello", "Hello"),
-                        ("test.hello_default_only", "Hello Default")
-                    ]))
-                });
-
-            mock_fs.expect_read_file()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH).join("fr_fr.json")))
-                .returning(|_| {
-                    Ok(create_test_language_content("fr_fr", "French", vec![
-                        ("test.hello", "Bonjour"),
-                    ]))
-                });
-
-            mock_fs.expect_list_directory()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(vec![
-                    PathBuf::from(DEFAULT_LANGUAGE_PATH).join("en_us.json"),
-                    PathBuf::from(DEFAULT_LANGUAGE_PATH).join("fr_fr.json"),
-                ]));
-
-            mock_fs.expect_is_directory()
-                .with(eq(PathBuf::from(DEFAULT_LANGUAGE_PATH).join("en_us.json")))
-                .returning(|_| Ok(false));
-
-            mock_fs.expect_is_directory()
-                .with(eq(PathBuf::from(DEFAULT_LANGUAGE_PATH).join("fr_fr.json")))
-                .returning(|_| Ok(false));
-
-            mock_fs.expect_validate_path()
-                .with(eq(Path::new(DEFAULT_LANGUAGE_PATH)))
-                .returning(|_| Ok(PathValidationStatus::Valid { is_file: false }));
+        fn translation_service() -> TranslationService<InMemoryFilesystem> {
+            let filesystem = InMemoryFilesystem::new();
+            write_language(&filesystem, DEFAULT_LANGUAGE_PATH, "en_us", "English", vec![
+                ("test.hello", "Hello"),
+                ("test.hello_default_only", "Hello Default")
+            ]);
+            write_language(&filesystem, DEFAULT_LANGUAGE_PATH, "fr_fr", "French", vec![
+                ("test.hello", "Bonjour"),
+            ]);
             
             TranslationService::try_new(
                     DEFAULT_LANGUAGE_CODE.clone(),
                     Path::new(DEFAULT_LANGUAGE_PATH),
-                    Arc::new(RwLock::new(mock_fs))
+                    Arc::new(RwLock::new(filesystem))
                 )
                 .expect("Failed to create test translation service")
         }
@@ -583,9 +847,45 @@ mod tests {
             Invalid,
         }
         
+        #[test]
+        fn test_generate_language_skeleton() {
+            // Given a key enum and no existing language file
+            let defaults = english_defaults::<TestTranslationKeys>();
+
+            // When I generate a skeleton
+            let skeleton = merge_language_skeleton(None, &defaults).unwrap();
+
+            // Then every key should be present with its English default
+            let json: Value = serde_json::from_slice(&skeleton).unwrap();
+            assert_eq!(json, json!({
+                "name": "English",
+                "translations": {
+                    "test.hello": "Hello",
+                    "test.hello_default_only": "Hello Default",
+                    "test.invalid": "MISSING_TRANSLATION_FOR_INVALID",
+                }
+            }));
+        }
+
+        #[test]
+        fn test_language_skeleton_keeps_existing_translations() {
+            // Given an existing language file with one translated key
+            let existing = create_test_language_content("en_us", "English (US)", vec![("test.hello", "Hi there")]);
+            let defaults = english_defaults::<TestTranslationKeys>();
+
+            // When I generate a skeleton from it
+            let skeleton = merge_language_skeleton(Some(&existing), &defaults).unwrap();
+
+            // Then the existing translation should be kept, and missing keys added
+            let json: Value = serde_json::from_slice(&skeleton).unwrap();
+            assert_eq!(json["name"], "English (US)");
+            assert_eq!(json["translations"]["test.hello"], "Hi there");
+            assert_eq!(json["translations"]["test.hello_default_only"], "Hello Default");
+        }
+
         #[rstest]
         #[test]
-        fn test_translate_key(translation_service: TranslationService<MockFilesystemService>) {
+        fn test_translate_key(translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a valid translation key
             let key = TestTranslationKeys::Hello;
 
@@ -598,7 +898,7 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_translate_key_non_default_language(mut translation_service: TranslationService<MockFilesystemService>) {
+        fn test_translate_key_non_default_language(mut translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a valid translation key with the language set to something other than the default
             let language = translation_service
                 .get_language(LanguageCode("fr_fr".to_string()))
@@ -619,7 +919,7 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_translate_key_default_fallback(mut translation_service: TranslationService<MockFilesystemService>) {
+        fn test_translate_key_default_fallback(mut translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a valid translation key, but which is only present in the default language
             let language = translation_service
                 .get_language(LanguageCode("fr_fr".to_string()))
@@ -640,7 +940,7 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_translate_key_missing(translation_service: TranslationService<MockFilesystemService>) {
+        fn test_translate_key_missing(translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a missing translation key
             let key = TestTranslationKeys::Invalid;
 
@@ -653,7 +953,7 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_set_language(mut translation_service: TranslationService<MockFilesystemService>) {
+        fn test_set_language(mut translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a language which exists
             let language = translation_service
                 .get_language(LanguageCode("fr_fr".to_string()))
@@ -669,7 +969,27 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_set_language_to_current(mut translation_service: TranslationService<MockFilesystemService>) {
+        fn test_set_language_notifies_subscribers(mut translation_service: TranslationService<InMemoryFilesystem>) {
+            // Given a subscriber to language changes
+            let notified = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let recorded = notified.clone();
+            translation_service.subscribe_language_changes(Box::new(move |language| recorded.lock().unwrap().push(language.code.clone())));
+
+            let language = translation_service
+                .get_language(LanguageCode("fr_fr".to_string()))
+                .expect("Failed to get language definition");
+
+            // When I switch to another language, then set it again
+            translation_service.set_language(&language).unwrap();
+            translation_service.set_language(&language).unwrap();
+
+            // Then the subscriber should be told once, about the new language
+            assert_eq!(*notified.lock().unwrap(), vec![language.code]);
+        }
+
+        #[rstest]
+        #[test]
+        fn test_set_language_to_current(mut translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a language which is set as the current language
             let language = translation_service
                 .get_language(LanguageCode("fr_fr".to_string()))
@@ -689,7 +1009,7 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_set_language_to_default(mut translation_service: TranslationService<MockFilesystemService>) {
+        fn test_set_language_to_default(mut translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a service set to another language
             let language = translation_service
                 .get_language(LanguageCode("fr_fr".to_string()))
@@ -709,7 +1029,7 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_set_language_to_default_current(mut translation_service: TranslationService<MockFilesystemService>) {
+        fn test_set_language_to_default_current(mut translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a service set to the default language
             assert_eq!(translation_service.get_current_language().code, translation_service.default_language_code);
             
@@ -724,11 +1044,12 @@ mod tests {
 
         #[rstest]
         #[test]
-        fn test_set_language_nonexistent(mut translation_service: TranslationService<MockFilesystemService>) {
+        fn test_set_language_nonexistent(mut translation_service: TranslationService<InMemoryFilesystem>) {
             // Given a language which does not exist
             let invalid_language = Language {
                 code: LanguageCode("invalid".to_string()),
                 name: "Invalid".to_string(),
+                rtl: false,
                 translation_map: Arc::new(Default::default()),
             };
             
@@ -740,4 +1061,115 @@ mod tests {
             assert!(matches!(result, Err(TranslationError::LanguageNotFound(_))));
         }
     }
-}
\ No newline at end of file
+    
+    /// Tests building and reloading the service from inside the async runtime
+    mod async_tests {
+        use super::*;
+
+        async fn write_language(filesystem: &InMemoryFilesystem, code: &str, name: &str) {
+            let directory = Path::new("/languages");
+            filesystem.create_directory_recursive(directory).await.unwrap();
+            filesystem.write_file(
+                &directory.join(format!("{}.json", code)),
+                &create_test_language_content(code, name, vec![("hello", "Hello")]),
+                FileWriteOptions::Overwrite
+            ).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_try_new_async_inside_runtime() {
+            // Given a directory with a language, inside a running async task
+            let filesystem = Arc::new(RwLock::new(InMemoryFilesystem::new()));
+            write_language(&*filesystem.read().await, "en_us", "English").await;
+
+            // When I build the service without blocking
+            let mut translation_service = TranslationService::try_new_async(LanguageCode("en_us".to_string()), "/languages", filesystem.clone()).await.unwrap();
+
+            // Then it should load, and reload to pick up new languages
+            assert_eq!(translation_service.get_current_language().name, "English");
+
+            write_language(&*filesystem.read().await, "fr_fr", "French").await;
+            translation_service.reload_languages_async().await.unwrap();
+
+            assert!(translation_service.get_language(LanguageCode("fr_fr".to_string())).is_some());
+        }
+    }
+    
+    /// Tests working out which way a language is laid out
+    mod layout_tests {
+        use rstest::rstest;
+        use super::*;
+
+        #[rstest]
+        #[case::arabic_by_code("ar_sa", r#"{"name": "العربية", "translations": {}}"#, true)]
+        #[case::hebrew_by_code("he_il", r#"{"name": "עברית", "translations": {}}"#, true)]
+        #[case::english_by_code("en_us", r#"{"name": "English", "translations": {}}"#, false)]
+        #[case::flagged_in_file("qya_aa", r#"{"name": "Quenya", "rtl": true, "translations": {}}"#, true)]
+        #[case::file_overrides_code("ar_sa", r#"{"name": "Arabic (Latin)", "rtl": false, "translations": {}}"#, false)]
+        fn test_is_rtl(#[case] code: &str, #[case] contents: &str, #[case] expected: bool) {
+            // Given a language file
+            // When I parse it
+            let language = TranslationService::<InMemoryFilesystem>::parse_language(code, contents.as_bytes()).unwrap();
+
+            // Then it should read right-to-left only when flagged or known to
+            assert_eq!(language.is_rtl(), expected);
+            assert_eq!(language.layout_direction() == LayoutDirection::RightToLeft, expected);
+        }
+
+        #[test]
+        fn test_arrange() {
+            // Given items in left-to-right order
+            let items = vec!["menu", "space", "theme"];
+
+            // When they are arranged in each direction
+            // Then only right-to-left should flip them
+            assert_eq!(LayoutDirection::LeftToRight.arrange(items.clone()), vec!["menu", "space", "theme"]);
+            assert_eq!(LayoutDirection::RightToLeft.arrange(items), vec!["theme", "space", "menu"]);
+        }
+    }
+    
+    /// Tests reporting how complete each translation is
+    mod coverage_tests {
+        use super::*;
+
+        async fn write_language(filesystem: &InMemoryFilesystem, code: &str, name: &str, translations: Vec<(&str, &str)>) {
+            let directory = Path::new("/languages");
+            filesystem.create_directory_recursive(directory).await.unwrap();
+            filesystem.write_file(
+                &directory.join(format!("{}.json", code)),
+                &create_test_language_content(code, name, translations),
+                FileWriteOptions::Overwrite
+            ).await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_write_coverage_report() {
+            // Given English with four keys, and two partial translations
+            let filesystem = Arc::new(RwLock::new(InMemoryFilesystem::new()));
+            {
+                let filesystem = filesystem.read().await;
+                write_language(&filesystem, "en_us", "English", vec![("open", "Open"), ("save", "Save"), ("close", "Close"), ("quit", "Quit")]).await;
+                write_language(&filesystem, "de_de", "Deutsch", vec![("open", "Öffnen"), ("save", "Speichern"), ("close", "Schließen")]).await;
+                write_language(&filesystem, "fr_fr", "Français", vec![("open", "Ouvrir")]).await;
+            }
+            let translation_service = TranslationService::try_new_async(LanguageCode("en_us".to_string()), "/languages", filesystem.clone()).await.unwrap();
+
+            // When I write a coverage report
+            translation_service.write_coverage_report("/coverage.txt").await.unwrap();
+
+            // Then it should give each language's completion and the keys it is missing
+            let report = String::from_utf8(filesystem.read().await.read_file(Path::new("/coverage.txt")).await.unwrap()).unwrap();
+            assert_eq!(report, concat!(
+                "Translation coverage against English (en_us), 4 keys\n",
+                "\n",
+                "Deutsch (de_de): 75.0% complete\n",
+                "  missing: quit\n",
+                "\n",
+                "Français (fr_fr): 25.0% complete\n",
+                "  missing: close\n",
+                "  missing: quit\n",
+                "  missing: save\n",
+            ));
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
 use std::marker::PhantomData;
-use std::path::Path;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use zip::ZipArchive;
-use crate::data::serialization::project::{SerializedProjectError, ZippableProject};
+use zip::{ZipArchive, ZipWriter};
+use zip::write::ExtendedFileOptions;
+use crate::data::serialization::project::{self, SerializedProjectError, ZippableProject};
 use crate::services::filesystem_service::{FileDeleteOptions, FileWriteOptions, FilesystemProvider, FilesystemProviderError, FilesystemService};
 
 #[async_trait::async_trait]
@@ -12,7 +16,14 @@ where
     T: Send + Sync + Sized + ZippableProject,
 {
     async fn extract(&self, path: &Path) -> Result<T>;
+    /// Reads one entry of an archive without extracting the rest, such as to preview a pack's icon before importing it.
+    /// Entry names use `/` separators and are relative to the pack, so a pack zipped as its folder is read the same
+    /// as one zipped from inside it
+    async fn read_entry(&self, path: &Path, entry_name: &str) -> Result<Vec<u8>>;
     async fn zip(&self, path: &Path, data: &T, overwrite_existing: bool) -> Result<()>;
+    /// Rewrites an existing archive, copying entries whose contents haven't changed across byte for byte,
+    /// with their original timestamps and compression. Creates the archive if it doesn't exist yet
+    async fn update_zip(&self, path: &Path, data: &T) -> Result<ZipUpdate>;
     async fn cleanup_file(&self, path: &Path) -> Result<()>;
 }
 
@@ -22,6 +33,16 @@ pub(crate) type Result<T> = std::result::Result<T, ZipError>;
 pub enum ZipError {
     #[error("Invalid Path: {0}!")]
     InvalidPath(String),
+    #[error("{archive} has no entry named {entry}")]
+    EntryNotFound {
+        archive: PathBuf,
+        entry: String,
+    },
+    #[error("{archive} has several top-level folders ({}), so it isn't clear which is the pack", .roots.join(", "))]
+    AmbiguousRoot {
+        archive: PathBuf,
+        roots: Vec<String>,
+    },
     #[error(transparent)]
     IOError(#[from] FilesystemProviderError),
     #[error(transparent)]
@@ -30,6 +51,15 @@ pub enum ZipError {
     SerializedProjectError(#[from] SerializedProjectError),
 }
 
+/// Entries of an archive sorted by what an update did to them, by their names in the zip
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ZipUpdate {
+    pub unchanged: Vec<String>,
+    pub changed: Vec<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
 pub struct ZipService<T, Filesystem = FilesystemService>
 where
     T: Send + Sync + Sized + ZippableProject,
@@ -61,7 +91,7 @@ where
     async fn extract(&self, path: &Path) -> Result<T> {
         let zip_file = self.filesystem_provider.read().await.read_file(path).await?;
         let zip_file = std::io::Cursor::new(zip_file);
-        let zip_archive = ZipArchive::new(zip_file)?;
+        let zip_archive = unwrap_single_root(path, ZipArchive::new(zip_file)?)?;
         
         let name = path.with_extension("");
         let name = name.file_name().unwrap().to_string_lossy();
@@ -69,12 +99,46 @@ where
         T::extract(name.as_ref(), zip_archive).await.map_err(Into::into)
     }
 
+    async fn read_entry(&self, path: &Path, entry_name: &str) -> Result<Vec<u8>> {
+        let zip_file = self.filesystem_provider.read().await.read_file(path).await?;
+        let mut zip_archive = unwrap_single_root(path, ZipArchive::new(Cursor::new(zip_file))?)?;
+
+        let mut entry = match zip_archive.by_name(entry_name) {
+            Ok(entry) => entry,
+            Err(zip::result::ZipError::FileNotFound) => return Err(ZipError::EntryNotFound {
+                archive: path.to_path_buf(),
+                entry: entry_name.to_string(),
+            }),
+            Err(error) => return Err(error.into()),
+        };
+
+        // The size in the header is whatever the archive claims, so it isn't used to allocate up front
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).map_err(zip::result::ZipError::from)?;
+        Ok(contents)
+    }
+
     async fn zip(&self, path: &Path, data: &T, overwrite_existing: bool) -> Result<()> {
         let zip_contents = data.zip().await?;
         let settings = if overwrite_existing { FileWriteOptions::Overwrite } else { FileWriteOptions::CreateNew };
         self.filesystem_provider.read().await.write_file(path, zip_contents.as_slice(), settings).await.map_err(ZipError::IOError)
     }
 
+    async fn update_zip(&self, path: &Path, data: &T) -> Result<ZipUpdate> {
+        let entries = data.entries().await?;
+        let filesystem = self.filesystem_provider.read().await;
+
+        let existing = if filesystem.file_exists(path).await? {
+            Some(ZipArchive::new(Cursor::new(filesystem.read_file(path).await?))?)
+        } else {
+            None
+        };
+        let (zip_contents, update) = merge_unchanged_entries(existing, entries)?;
+
+        filesystem.write_file(path, zip_contents.as_slice(), FileWriteOptions::Overwrite).await?;
+        Ok(update)
+    }
+
     async fn cleanup_file(&self, path: &Path) -> Result<()> {
         if self.filesystem_provider.read().await.file_exists(path).await? {
             self.filesystem_provider.read().await.delete_file(path, FileDeleteOptions::ErrorIfNotExists).await?;
@@ -83,140 +147,209 @@ where
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::error::Error;
-    use std::fs::Metadata;
-    use std::io::{Cursor, Read, Write};
-    use std::path::PathBuf;
-    use async_trait::async_trait;
-    use mockall::predicate::*;
-    use mockall::*;
-    use zip::write::{ExtendedFileOptions, FileOptions, ZipWriter};
-    use crate::services::filesystem_service;
-    use crate::services::filesystem_service::{ChunkedFileReadResult, PathValidationStatus};
+/// Directories which sit at the root of a pack, so an archive with them at the top level isn't wrapped in a folder
+const PACK_ROOT_DIRECTORIES: &[&str] = &["data", "assets"];
 
-    #[async_trait]
-    trait TestFilesystemProvider {
-        async fn read_file(&self, path: &Path) -> filesystem_service::Result<Vec<u8>>;
-        async fn write_file(&self, path: &Path, content: &[u8], options: FileWriteOptions) -> filesystem_service::Result<()>;
-        async fn delete_file(&self, path: &Path, options: FileDeleteOptions) -> filesystem_service::Result<()>;
-        async fn file_exists(&self, path: &Path) -> filesystem_service::Result<bool>;
+/// Files and folders the OS adds when zipping, such as the resource forks macOS keeps under `__MACOSX`.
+/// They're never part of the pack, so they don't count when looking for its folder
+const METADATA_ENTRIES: &[&str] = &["__MACOSX", ".DS_Store", "Thumbs.db", "desktop.ini"];
+
+fn is_metadata(name: &str) -> bool {
+    let top_level = name.split('/').next().unwrap_or_default();
+    METADATA_ENTRIES.contains(&top_level)
+}
+
+/// Packs are sometimes zipped as their folder, nesting every entry under one directory. Such archives are rewritten
+/// with that directory stripped, as though the pack had been zipped from inside it. Archives which are already flat are
+/// returned as they are, and ones with several top-level folders are refused, since there's no telling which is the pack.
+/// OS metadata beside the folder is dropped along with it
+fn unwrap_single_root(path: &Path, mut zip_archive: ZipArchive<Cursor<Vec<u8>>>) -> Result<ZipArchive<Cursor<Vec<u8>>>> {
+    let names: Vec<String> = zip_archive.file_names()
+        .filter(|name| !is_metadata(name))
+        .map(str::to_string)
+        .collect();
+
+    // Any file at the top level, such as `pack.mcmeta`, means the archive is already flat
+    if names.iter().any(|name| !name.contains('/')) {
+        return Ok(zip_archive);
     }
 
-    // Create a wrapper struct that adapts TestFilesystemProvider to FilesystemProvider
-    struct FilesystemProviderAdapter<T: TestFilesystemProvider>(T);
+    let roots: BTreeSet<&str> = names.iter()
+        .filter_map(|name| name.split_once('/').map(|(root, _)| root))
+        .collect();
 
-    #[async_trait]
-    impl<T: TestFilesystemProvider + Send + Sync> FilesystemProvider for FilesystemProviderAdapter<T> {
-        async fn write_file(&self, path: &Path, content: &[u8], options: FileWriteOptions) -> filesystem_service::Result<()> {
-            self.0.write_file(path.as_ref(), content, options).await
-        }
+    if roots.iter().any(|root| PACK_ROOT_DIRECTORIES.contains(root)) {
+        return Ok(zip_archive);
+    }
 
-        async fn read_file(&self, path: &Path) -> filesystem_service::Result<Vec<u8>> {
-            self.0.read_file(path.as_ref()).await
-        }
+    let root = match roots.len() {
+        0 => return Ok(zip_archive),
+        1 => format!("{}/", roots.first().unwrap()),
+        _ => return Err(ZipError::AmbiguousRoot {
+            archive: path.to_path_buf(),
+            roots: roots.into_iter().map(str::to_string).collect(),
+        }),
+    };
 
-        async fn read_file_chunked(&self, path: &Path, chunk_size: usize, callback: Box<dyn FnMut(Vec<u8>) -> ChunkedFileReadResult + Send>,) -> filesystem_service::Result<()> { unimplemented!("Not needed for these tests") }
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
 
-        async fn delete_file(&self, path: &Path, options: FileDeleteOptions) -> filesystem_service::Result<()> {
-            self.0.delete_file(path.as_ref(), options).await
+    for index in 0..zip_archive.len() {
+        let entry = zip_archive.by_index_raw(index)?;
+        let name = entry.name().strip_prefix(&root).unwrap_or_default().to_string();
+
+        // The root directory's own entry has nothing left once stripped, and neither does anything outside it
+        if name.is_empty() {
+            continue;
         }
 
-        async fn copy_file(&self, _source: &Path, _destination: &Path) -> filesystem_service::Result<()> { unimplemented!("Not needed for these tests") }
-        async fn move_file(&self, _source: &Path, _destination: &Path) -> filesystem_service::Result<()> { unimplemented!("Not needed for these tests") }
-        async fn create_directory(&self, path: &Path) -> filesystem_service::Result<()> { unimplemented!("Not needed for these tests") }
-        async fn create_directory_recursive(&self, path: &Path) -> filesystem_service::Result<()> { unimplemented!("Not needed for these tests") }
-        async fn delete_directory(&self, path: &Path) -> filesystem_service::Result<()> { unimplemented!("Not needed for these tests") }
-        async fn list_directory(&self, path: &Path) -> filesystem_service::Result<Vec<PathBuf>> { unimplemented!("Not needed for these tests") }
-        async fn validate_path(&self, path: &Path) -> filesystem_service::Result<PathValidationStatus> { unimplemented!("Not needed for these tests") }
+        zip.raw_copy_file_rename(entry, name)?;
+    }
 
-        async fn file_exists(&self, path: &Path) -> filesystem_service::Result<bool> {
-            self.0.file_exists(path.as_ref()).await
+    Ok(ZipArchive::new(zip.finish()?)?)
+}
+
+/// Builds the archive to write for an update, in the order of the project's entries. Entries are matched by name, and taken as
+/// unchanged when the earlier archive's checksum and size agree with the new contents, so only changed and added entries are compressed
+fn merge_unchanged_entries(mut existing: Option<ZipArchive<Cursor<Vec<u8>>>>, entries: Vec<(String, Cow<'_, [u8]>)>) -> Result<(Vec<u8>, ZipUpdate)> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut update = ZipUpdate::default();
+
+    for (name, contents) in &entries {
+        let existing_index = existing.as_ref().and_then(|archive| archive.index_for_name(name));
+
+        match existing.as_mut().zip(existing_index) {
+            Some((archive, index)) => {
+                let existing_entry = archive.by_index_raw(index)?;
+
+                if existing_entry.size() == contents.len() as u64 && existing_entry.crc32() == crc32(contents) {
+                    zip.raw_copy_file(existing_entry)?;
+                    update.unchanged.push(name.clone());
+                } else {
+                    write_entry(&mut zip, name, contents)?;
+                    update.changed.push(name.clone());
+                }
+            }
+            None => {
+                write_entry(&mut zip, name, contents)?;
+                update.added.push(name.clone());
+            }
         }
+    }
 
-        async fn is_directory(&self, path: &Path) -> filesystem_service::Result<bool> { unimplemented!("Not needed for these tests") }
-        async fn get_metadata(&self, path: &Path) -> filesystem_service::Result<Metadata> { unimplemented!("Not needed for these tests") }
+    if let Some(existing) = &existing {
+        let names: HashSet<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        update.removed = existing.file_names()
+            .filter(|name| !names.contains(name))
+            .map(str::to_string)
+            .collect();
     }
 
-    // Now create a mock for the simpler trait
-    mock! {
-        FilesystemProviderMock {}
-        #[async_trait]
-        impl TestFilesystemProvider for FilesystemProviderMock {
-            async fn read_file(&self, path: &Path) -> filesystem_service::Result<Vec<u8>>;
-            async fn write_file(&self, path: &Path, content: &[u8], options: FileWriteOptions) -> filesystem_service::Result<()>;
-            async fn delete_file(&self, path: &Path, options: FileDeleteOptions) -> filesystem_service::Result<()>;
-            async fn file_exists(&self, path: &Path) -> filesystem_service::Result<bool>;
+    Ok((zip.finish()?.into_inner(), update))
+}
+
+fn write_entry(zip: &mut ZipWriter<Cursor<Vec<u8>>>, name: &str, contents: &[u8]) -> Result<()> {
+    zip.start_file::<&str, ExtendedFileOptions>(name, project::entry_options())?;
+    zip.write_all(contents).map_err(zip::result::ZipError::from)?;
+    Ok(())
+}
+
+/// The same checksum zip entries store, of their uncompressed contents
+fn crc32(contents: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(contents);
+    crc.sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use async_trait::async_trait;
+    use zip::write::{ExtendedFileOptions, FileOptions, ZipWriter};
+    use crate::data::serialization::project::Project as SerializedProject;
+    use crate::services::filesystem_service;
+    use crate::services::in_memory_filesystem::InMemoryFilesystem;
+
+    /// A zip service over an in-memory filesystem holding the given files
+    async fn service_with_files<T: Send + Sync + ZippableProject>(files: &[(&str, &[u8])]) -> ZipService<T, InMemoryFilesystem> {
+        let filesystem = InMemoryFilesystem::new();
+        for (path, contents) in files {
+            filesystem.write_file(Path::new(path), contents, FileWriteOptions::CreateNew).await.unwrap();
         }
+
+        ZipService::new(Arc::new(RwLock::new(filesystem)))
+    }
+
+    async fn read_file<T: Send + Sync + ZippableProject>(service: &ZipService<T, InMemoryFilesystem>, path: &str) -> filesystem_service::Result<Vec<u8>> {
+        service.filesystem_provider.read().await.read_file(Path::new(path)).await
     }
-    
-    // Mock implementation of a ZippableProject for testing
+
+    // A ZippableProject made of whichever entries it's given, each counted as a resource, and extracted from every entry
+    // of an archive, so tests can check which entries were written and what extraction sees
     #[derive(Debug, Clone, PartialEq)]
     struct TestProject {
-        content: String,
+        entries: Vec<(String, String)>,
+    }
+
+    impl TestProject {
+        fn new(entries: &[(&str, &str)]) -> Self {
+            Self { entries: entries.iter().map(|(name, contents)| (name.to_string(), contents.to_string())).collect() }
+        }
+
+        fn single() -> Self {
+            Self::new(&[("test.txt", "test content")])
+        }
+
+        fn sorted_names(&self) -> Vec<String> {
+            let mut names: Vec<String> = self.entries.iter().map(|(name, _)| name.clone()).collect();
+            names.sort();
+            names
+        }
     }
 
     #[async_trait]
     impl ZippableProject for TestProject {
-        async fn zip(&self) -> std::result::Result<Vec<u8>, SerializedProjectError> {
-            let buffer = Cursor::new(Vec::new());
-            let mut zip = ZipWriter::new(buffer);
+        async fn entries(&self) -> std::result::Result<Vec<(String, Cow<'_, [u8]>)>, SerializedProjectError> {
+            Ok(self.entries.iter().map(|(name, contents)| (name.clone(), Cow::Borrowed(contents.as_bytes()))).collect())
+        }
 
-            zip.start_file::<&str, ExtendedFileOptions>("test.txt", FileOptions::default())?;
-            zip.write_all(self.content.as_bytes())?;
+        async fn extract(_name: &str, mut zip_archive: ZipArchive<Cursor<Vec<u8>>>) -> std::result::Result<Self, SerializedProjectError> {
+            let mut entries = Vec::new();
 
-            let zip_data = zip.finish()?;
-            Ok(zip_data.into_inner())
-        }
+            for index in 0..zip_archive.len() {
+                let mut file = zip_archive.by_index(index)?;
+                let mut contents = String::new();
+                file.read_to_string(&mut contents)?;
 
-        async fn extract(name: &str, mut zip_archive: ZipArchive<Cursor<Vec<u8>>>) -> std::result::Result<Self, SerializedProjectError> {
-            let mut file = zip_archive.by_index(0)?;
-            let mut content = String::new();
-            file.read_to_string(&mut content)?;
+                entries.push((file.name().to_string(), contents));
+            }
 
-            Ok(TestProject { content })
+            Ok(TestProject { entries })
+        }
+
+        fn resource_count(&self) -> usize {
+            self.entries.len()
         }
     }
 
     #[tokio::test]
     async fn test_extract_success() {
         //Given a simple test project file 
-        let test_project = TestProject { content: "test content".to_string() };
+        let test_project = TestProject::single();
         let zip_data = test_project.zip().await.unwrap();
-
-        let mut mock = MockFilesystemProviderMock::new();
-        
-        mock.expect_read_file()
-            .with(eq(PathBuf::from("test.zip")))
-            .returning(move |_| Ok(zip_data.clone()));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        let service = service_with_files::<TestProject>(&[("test.zip", &zip_data)]).await;
 
         // When I extract the file
         let result = service.extract(Path::new("test.zip")).await.unwrap();
         
         // Then it should read the contents correctly
-        assert_eq!(result.content, "test content");
+        assert_eq!(result, test_project);
     }
 
     #[tokio::test]
     async fn test_extract_filesystem_error() {
         // Given a file which does not exist
-        let mut mock = MockFilesystemProviderMock::new();
-        
-        mock.expect_read_file()
-            .with(eq(PathBuf::from("missing.zip")))
-            .returning(|_| Err(std::io::Error::new(std::io::ErrorKind::NotFound, "File not found").into()));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        let service = service_with_files::<TestProject>(&[]).await;
 
         // When I try to extract it
         let result = service.extract(Path::new("missing.zip")).await;
@@ -226,154 +359,277 @@ mod test {
         assert!(matches!(result.unwrap_err(), ZipError::IOError(_)))
     }
 
+    fn multi_entry_zip() -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+        for (name, contents) in [("pack.mcmeta", "{}"), ("data/test/recipe/stone.json", "{\"type\": \"minecraft:smelting\"}"), ("pack.png", "icon")] {
+            zip.start_file::<&str, ExtendedFileOptions>(name, FileOptions::default()).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+
+        zip.finish().unwrap().into_inner()
+    }
+
+    async fn extract_entries(zip_data: Vec<u8>) -> Result<Vec<String>> {
+        let service = service_with_files::<TestProject>(&[("pack.zip", &zip_data)]).await;
+
+        Ok(service.extract(Path::new("pack.zip")).await?.sorted_names())
+    }
+
+    #[tokio::test]
+    async fn test_extract_flat_archive() {
+        // Given a pack zipped from inside its folder
+        let zip_data = multi_entry_zip();
+
+        // When I extract it
+        let names = extract_entries(zip_data).await.unwrap();
+
+        // Then its entries should be left where they are
+        assert_eq!(names, vec!["data/test/recipe/stone.json", "pack.mcmeta", "pack.png"]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_single_root_archive() {
+        // Given a pack zipped as its folder, so every entry is nested under it
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.add_directory::<&str, ExtendedFileOptions>("My Pack/", FileOptions::default()).unwrap();
+        zip.add_directory::<&str, ExtendedFileOptions>("My Pack/data/", FileOptions::default()).unwrap();
+
+        for (name, contents) in [("My Pack/pack.mcmeta", "{}"), ("My Pack/data/test/function/tick.mcfunction", "say hi")] {
+            zip.start_file::<&str, ExtendedFileOptions>(name, FileOptions::default()).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+
+        // When I extract it
+        let names = extract_entries(zip.finish().unwrap().into_inner()).await.unwrap();
+
+        // Then the folder should be stripped, leaving the pack at the root
+        assert_eq!(names, vec!["data/", "data/test/function/tick.mcfunction", "pack.mcmeta"]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_single_root_archive_with_metadata() {
+        // Given a pack zipped as its folder by macOS, which adds resource forks and folder settings beside it
+        let zip_data = zip_entries(&[
+            ("My Pack/pack.mcmeta", "{}"),
+            ("My Pack/.DS_Store", ""),
+            ("__MACOSX/My Pack/._pack.mcmeta", ""),
+            (".DS_Store", ""),
+        ], FileOptions::default());
+
+        // When I extract it
+        let names = extract_entries(zip_data).await.unwrap();
+
+        // Then the folder should still be stripped, leaving out the metadata beside it
+        assert_eq!(names, vec![".DS_Store", "pack.mcmeta"]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_ambiguous_root_archive() {
+        // Given an archive holding two packs side by side
+        let zip_data = zip_entries(&[("Pack A/pack.mcmeta", "{}"), ("Pack B/pack.mcmeta", "{}")], FileOptions::default());
+
+        // When I try to extract it
+        let result = extract_entries(zip_data).await;
+
+        // Then it should be refused, naming both folders
+        assert!(matches!(result, Err(ZipError::AmbiguousRoot { roots, .. }) if roots == vec!["Pack A", "Pack B"]));
+    }
+
+    #[tokio::test]
+    async fn test_import_non_pack_archive() {
+        // Given a mod jar, which isn't a pack even though it's a zip
+        let zip_data = zip_entries(&[("fabric.mod.json", r#"{"id": "example"}"#), ("com/example/ExampleMod.class", "")], FileOptions::default());
+        let service = service_with_files::<SerializedProject>(&[("example.jar", &zip_data)]).await;
+
+        // When I try to import it
+        let result = service.extract(Path::new("example.jar")).await;
+
+        // Then it should be refused as not being a pack, rather than giving an empty project
+        assert!(matches!(result, Err(ZipError::SerializedProjectError(SerializedProjectError::NotAPack(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_read_entry() {
+        // Given a zip with several entries
+        let zip_data = multi_entry_zip();
+        let service = service_with_files::<TestProject>(&[("pack.zip", &zip_data)]).await;
+
+        // When I read one of them by name
+        let recipe = service.read_entry(Path::new("pack.zip"), "data/test/recipe/stone.json").await.unwrap();
+
+        // Then only that entry's bytes should be returned
+        assert_eq!(recipe, br#"{"type": "minecraft:smelting"}"#);
+
+        // And a missing entry should be reported by name
+        let missing = service.read_entry(Path::new("pack.zip"), "data/test/recipe/dirt.json").await;
+        assert!(matches!(missing, Err(ZipError::EntryNotFound { entry, .. }) if entry == "data/test/recipe/dirt.json"));
+    }
+
+    #[tokio::test]
+    async fn test_read_entry_single_root_archive() {
+        // Given a pack zipped as its folder
+        let zip_data = zip_entries(&[("My Pack/pack.mcmeta", "{}"), ("My Pack/pack.png", "icon")], FileOptions::default());
+        let service = service_with_files::<TestProject>(&[("pack.zip", &zip_data)]).await;
+
+        // When I read an entry by its name within the pack
+        let icon = service.read_entry(Path::new("pack.zip"), "pack.png").await.unwrap();
+
+        // Then it should be found under the folder
+        assert_eq!(icon, b"icon");
+    }
+
     #[tokio::test]
     async fn test_zip_success() {
         // Given a simple test project
-        let test_project = TestProject { content: "test content".to_string() };
-        let zip_data = test_project.zip().await.unwrap();
-
-        let mut mock = MockFilesystemProviderMock::new();
-        
-        mock.expect_write_file()
-            .withf(|path, contents, options| {
-                path == Path::new("output.zip") &&
-                    options == &FileWriteOptions::CreateNew
-            })
-            .returning(|_, _, _| Ok(()));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        let test_project = TestProject::single();
+        let service = service_with_files::<TestProject>(&[]).await;
 
         // When I try to zip it
         let result = service.zip(Path::new("output.zip"), &test_project, false).await;
         
         // Then it should zip correctly
         assert!(result.is_ok());
+        assert_eq!(read_file(&service, "output.zip").await.unwrap(), test_project.zip().await.unwrap());
     }
 
     #[tokio::test]
     async fn test_zip_with_overwrite() {
         // Given a test project and a file that already exists
-        let test_project = TestProject { content: "test content".to_string() };
-
-        let mut mock = MockFilesystemProviderMock::new();
-        
-        mock.expect_write_file()
-            .withf(|path, contents, options| {
-                path == Path::new("output.zip") &&
-                    options == &FileWriteOptions::Overwrite
-            })
-            .returning(|_, _, _| Ok(()));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        let test_project = TestProject::single();
+        let service = service_with_files::<TestProject>(&[("output.zip", b"old")]).await;
 
         // When I try to overwrite it
         let result = service.zip(Path::new("output.zip"), &test_project, true).await;
         
         // Then it should zip correctly
         assert!(result.is_ok());
+        assert_eq!(read_file(&service, "output.zip").await.unwrap(), test_project.zip().await.unwrap());
     }
 
     #[tokio::test]
     async fn test_zip_already_exists_no_overwrite() {
         // Given a test project and a file that already exists
-        let test_project = TestProject { content: "test content".to_string() };
-
-        let mut mock = MockFilesystemProviderMock::new();
-
-        mock.expect_write_file()
-            .withf(|path, contents, options| {
-                path == Path::new("output.zip") &&
-                    options != &FileWriteOptions::Overwrite
-            })
-            .returning(|_, _, _| Err(std::io::Error::new(std::io::ErrorKind::AlreadyExists, "Already Exists").into()));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        let test_project = TestProject::single();
+        let service = service_with_files::<TestProject>(&[("output.zip", b"old")]).await;
 
         // When I try to overwrite it
         let result = service.zip(Path::new("output.zip"), &test_project, false).await;
 
-        // Then it should return an error
+        // Then it should return an error, leaving the file as it was
         assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), ZipError::IOError(_)))
+        assert!(matches!(result.unwrap_err(), ZipError::IOError(_)));
+        assert_eq!(read_file(&service, "output.zip").await.unwrap(), b"old");
     }
 
     #[tokio::test]
     async fn test_zip_filesystem_error() {
-        // Given a mock which returns an error
-        let test_project = TestProject { content: "test content".to_string() };
-
-        let mut mock = MockFilesystemProviderMock::new();
-        
-        mock.expect_write_file()
-            .returning(|_, _, _| Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "Permission Denied").into()));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        // Given a destination whose folder does not exist
+        let test_project = TestProject::single();
+        let service = service_with_files::<TestProject>(&[]).await;
 
         // When I try to zip a project
-        let result = service.zip(Path::new("output.zip"), &test_project, false).await;
+        let result = service.zip(Path::new("missing/output.zip"), &test_project, false).await;
         
         // Then the error should be propagated
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ZipError::IOError(_)))
     }
 
+    fn zip_entries(entries: &[(&str, &str)], options: FileOptions<ExtendedFileOptions>) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+        for (name, contents) in entries {
+            zip.start_file(*name, options.clone()).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+
+        zip.finish().unwrap().into_inner()
+    }
+
     #[tokio::test]
-    async fn test_cleanup_file_exists() {
-        // Given a file that exists
-        let mut mock = MockFilesystemProviderMock::new();
-        
-        mock.expect_delete_file()
-            .with(eq(PathBuf::from("existing.zip")), eq(FileDeleteOptions::ErrorIfNotExists))
-            .returning(|_, _| Ok(()));
+    async fn test_update_zip_only_rewrites_changed_entries() {
+        // Given an earlier export, and a project where one entry changed, one was added and one was removed since
+        let exported_at = zip::DateTime::from_date_and_time(2020, 1, 1, 12, 0, 0).unwrap();
+        let existing = zip_entries(
+            &[("pack.mcmeta", "{}"), ("data/test/recipe/stone.json", "{\"type\": \"minecraft:smelting\"}"), ("pack.png", "icon"), ("data/test/recipe/dirt.json", "{}")],
+            FileOptions::default().last_modified_time(exported_at),
+        );
+
+        let project = TestProject::new(&[("pack.mcmeta", "{}"), ("data/test/recipe/stone.json", "{\"type\": \"minecraft:blasting\"}"), ("pack.png", "icon"), ("data/test/recipe/glass.json", "{}")]);
+
+        let service = service_with_files::<TestProject>(&[("output.zip", &existing)]).await;
+
+        // When I update the export
+        let update = service.update_zip(Path::new("output.zip"), &project).await.unwrap();
+
+        // Then each entry should be sorted by what happened to it
+        assert_eq!(update, ZipUpdate {
+            unchanged: vec!["pack.mcmeta".to_string(), "pack.png".to_string()],
+            changed: vec!["data/test/recipe/stone.json".to_string()],
+            added: vec!["data/test/recipe/glass.json".to_string()],
+            removed: vec!["data/test/recipe/dirt.json".to_string()],
+        });
+
+        // And only the changed entries should differ from the earlier export
+        let mut existing = ZipArchive::new(Cursor::new(existing)).unwrap();
+        let mut updated = ZipArchive::new(Cursor::new(read_file(&service, "output.zip").await.unwrap())).unwrap();
+
+        for name in ["pack.mcmeta", "pack.png"] {
+            let existing_entry = existing.by_name(name).unwrap();
+            let updated_entry = updated.by_name(name).unwrap();
+            assert_eq!(updated_entry.last_modified(), existing_entry.last_modified());
+            assert_eq!(updated_entry.crc32(), existing_entry.crc32());
+        }
 
-        mock.expect_file_exists()
-            .with(eq(Path::new("existing.zip")))
-            .returning(|_| Ok(true));
+        let mut stone = String::new();
+        let mut stone_entry = updated.by_name("data/test/recipe/stone.json").unwrap();
+        assert_ne!(stone_entry.last_modified(), Some(exported_at));
+        stone_entry.read_to_string(&mut stone).unwrap();
+        assert_eq!(stone, "{\"type\": \"minecraft:blasting\"}");
+        drop(stone_entry);
 
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        assert!(updated.by_name("data/test/recipe/glass.json").is_ok());
+        assert!(matches!(updated.by_name("data/test/recipe/dirt.json"), Err(zip::result::ZipError::FileNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_update_missing_zip() {
+        // Given a project which hasn't been exported yet
+        let project = TestProject::new(&[("pack.mcmeta", "{}"), ("data/test/recipe/stone.json", "{\"type\": \"minecraft:smelting\"}")]);
+
+        let service = service_with_files::<TestProject>(&[]).await;
+
+        // When I update its export
+        let update = service.update_zip(Path::new("output.zip"), &project).await.unwrap();
+
+        // Then every entry should be added, in the same archive a full export writes
+        assert_eq!(update, ZipUpdate {
+            added: vec!["pack.mcmeta".to_string(), "data/test/recipe/stone.json".to_string()],
+            ..ZipUpdate::default()
+        });
+        let zipped = project.zip().await.unwrap();
+        assert_eq!(read_file(&service, "output.zip").await.unwrap(), zipped);
+    }
 
+    #[tokio::test]
+    async fn test_cleanup_file_exists() {
+        // Given a file that exists
+        let service = service_with_files::<TestProject>(&[("existing.zip", b"zip")]).await;
         let path = Path::new("existing.zip");
 
         // When I clean up the file
         let result = service.cleanup_file(path).await;
         
-        // Then it should work correctly
+        // Then it should be deleted
         assert!(result.is_ok());
+        assert!(read_file(&service, "existing.zip").await.is_err());
     }
 
     #[tokio::test]
     async fn test_cleanup_file_not_exists() {
         // Given a file which does not exist
-        let mut mock = MockFilesystemProviderMock::new();
-
-        mock.expect_delete_file()
-            .with(eq(PathBuf::from("nonexistent.zip")), eq(FileDeleteOptions::ErrorIfNotExists))
-            .returning(|_, _| Ok(()));
-        
-        mock.expect_file_exists()
-            .with(eq(Path::new("nonexistent.zip")))
-            .returning(|_| Ok(true));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
-
+        let service = service_with_files::<TestProject>(&[]).await;
         let path = PathBuf::from("nonexistent.zip");
         
         // When I try to clean up the file
@@ -385,20 +641,9 @@ mod test {
 
     #[tokio::test]
     async fn test_cleanup_file_error() {
-        // Given a mock which returns an error
-        let mut mock = MockFilesystemProviderMock::new();
-        
-        mock.expect_delete_file()
-            .returning(|_, _| Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "Permission Denied").into()));
-
-        mock.expect_file_exists()
-            .with(eq(Path::new("existing.zip")))
-            .returning(|_| Ok(true));
-
-        let service = ZipService::<TestProject, FilesystemProviderAdapter<MockFilesystemProviderMock>> {
-            _phantom: PhantomData,
-            filesystem_provider: Arc::new(RwLock::new(FilesystemProviderAdapter(mock))),
-        };
+        // Given a folder where the file should be, which can't be deleted as one
+        let service = service_with_files::<TestProject>(&[]).await;
+        service.filesystem_provider.read().await.create_directory(Path::new("existing.zip")).await.unwrap();
 
         let path = Path::new("existing.zip");
 
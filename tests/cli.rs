@@ -0,0 +1,122 @@
+//! Runs the command line import and export end to end, against packs in a temporary directory
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Output};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+const PACK_INFO: &str = r#"{"pack": {"pack_format": 48, "description": "Test Pack"}}"#;
+const FUNCTION_PATH: &str = "data/test/function/hello.mcfunction";
+
+fn write_pack(path: &Path) {
+    let mut zip = ZipWriter::new(File::create(path).unwrap());
+    let options = SimpleFileOptions::default();
+
+    zip.start_file("pack.mcmeta", options).unwrap();
+    zip.write_all(PACK_INFO.as_bytes()).unwrap();
+
+    zip.add_directory("data/", options).unwrap();
+    zip.start_file(FUNCTION_PATH, options).unwrap();
+    zip.write_all(b"say hello").unwrap();
+
+    zip.finish().unwrap();
+}
+
+fn gaea(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_gaea-mc"))
+        .args(args)
+        .env("RUST_LOG", "off")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_import_and_export() {
+    // Given a zipped pack
+    let temp_dir = tempfile::tempdir().unwrap();
+    let pack = temp_dir.path().join("pack.zip");
+    write_pack(&pack);
+
+    // When I import it from the command line
+    let project_dir = temp_dir.path().join("project");
+    let import = gaea(&[Path::new("import"), &pack, &project_dir]);
+
+    // Then it should be saved as a project
+    assert!(import.status.success(), "{}", String::from_utf8_lossy(&import.stderr));
+    assert_eq!(std::fs::read_to_string(project_dir.join(FUNCTION_PATH)).unwrap(), "say hello");
+
+    // When I export the saved project
+    let out = temp_dir.path().join("out.zip");
+    let export = gaea(&[Path::new("export"), &project_dir, &out]);
+
+    // Then the zip should hold the same files
+    assert!(export.status.success(), "{}", String::from_utf8_lossy(&export.stderr));
+
+    let mut archive = ZipArchive::new(File::open(&out).unwrap()).unwrap();
+    let mut function = String::new();
+    archive.by_name(FUNCTION_PATH).unwrap().read_to_string(&mut function).unwrap();
+    assert_eq!(function, "say hello");
+    assert!(archive.by_name("pack.mcmeta").is_ok());
+
+    // And the pack should validate
+    let validate = gaea(&[Path::new("validate"), &pack]);
+    assert!(validate.status.success(), "{}", String::from_utf8_lossy(&validate.stderr));
+}
+
+#[test]
+fn test_describe() {
+    // Given an imported pack
+    let temp_dir = tempfile::tempdir().unwrap();
+    let pack = temp_dir.path().join("pack.zip");
+    write_pack(&pack);
+
+    let project_dir = temp_dir.path().join("project");
+    let import = gaea(&[Path::new("import"), &pack, &project_dir]);
+    assert!(import.status.success(), "{}", String::from_utf8_lossy(&import.stderr));
+
+    // When I change its description from the command line
+    let describe = gaea(&[Path::new("describe"), &project_dir, Path::new("data"), Path::new("Adds gems")]);
+
+    // Then the saved pack.mcmeta should have the new description
+    assert!(describe.status.success(), "{}", String::from_utf8_lossy(&describe.stderr));
+
+    let pack_info: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(project_dir.join("pack.mcmeta")).unwrap()).unwrap();
+    assert_eq!(pack_info["pack"]["description"], "Adds gems");
+}
+
+#[test]
+fn test_validate_json() {
+    // Given a zipped pack with a function nothing runs
+    let temp_dir = tempfile::tempdir().unwrap();
+    let pack = temp_dir.path().join("pack.zip");
+    write_pack(&pack);
+
+    // When I validate it with JSON output
+    let validate = gaea(&[Path::new("validate"), &pack, Path::new("--format"), Path::new("json")]);
+
+    // Then it should pass, listing the unused function as a warning
+    assert!(validate.status.success(), "{}", String::from_utf8_lossy(&validate.stderr));
+
+    let output: serde_json::Value = serde_json::from_slice(&validate.stdout).unwrap();
+    assert_eq!(output["version"], 1);
+    assert_eq!(output["diagnostics"], serde_json::json!([{
+        "path": FUNCTION_PATH,
+        "line": null,
+        "severity": "warning",
+        "rule": "unused-function",
+        "message": "Function test:hello is never run",
+    }]));
+}
+
+#[test]
+fn test_invalid_arguments() {
+    // Given a command missing its arguments
+    // When I run it
+    let output = gaea(&[Path::new("export")]);
+
+    // Then it should fail with the usage
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Usage"));
+}